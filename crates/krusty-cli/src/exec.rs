@@ -0,0 +1,481 @@
+//! Headless `krusty exec` command
+//!
+//! Runs one prompt through the full agent loop without the TUI, for use
+//! from scripts, git hooks and CI. The conversation is saved like a TUI
+//! session, so it can be resumed later with `--resume` or opened in the TUI.
+
+use std::io::{IsTerminal, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use clap::{Args, ValueEnum};
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::agent::{
+    HeadlessEvent, HeadlessOutcome, HeadlessResult, HeadlessRunner, LoggingHook, PermissionHook,
+    SafetyHook, UserPostToolHook, UserPreToolHook,
+};
+use crate::ai::client::AiClient;
+use crate::ai::cost::UsageSource;
+use crate::ai::providers::{get_provider, ProviderId};
+use crate::ai::types::{Content, ModelMessage, Role};
+use crate::storage::credentials::ActiveProviderStore;
+use crate::storage::{CredentialStore, SessionManager};
+use crate::tools::{register_acp_tools, ToolRegistry};
+use crate::tui::app_builder::{
+    init_cost_tracker, init_model_registry, init_permissions, init_preferences,
+    init_session_manager, init_user_hooks,
+};
+use crate::{acp, paths};
+use krusty_core::lsp::LspManager;
+use krusty_core::mcp::McpManager;
+
+/// The agent finished and its last tool calls succeeded
+pub const EXIT_SUCCESS: i32 = 0;
+/// The run could not start or the provider failed
+pub const EXIT_ERROR: i32 = 1;
+/// The agent finished, but the last tools it ran failed
+pub const EXIT_TOOL_FAILED: i32 = 2;
+/// The turn budget ran out before the agent finished
+pub const EXIT_BUDGET_EXHAUSTED: i32 = 3;
+
+/// How `krusty exec` reports progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputMode {
+    /// Stream the response to stdout and tool activity to stderr
+    Text,
+    /// Print only the final assistant message
+    Final,
+    /// One JSON event per line
+    Json,
+}
+
+/// Arguments for `krusty exec`
+#[derive(Debug, Args)]
+pub struct ExecArgs {
+    /// Prompt to run (read from stdin when omitted or "-")
+    pub prompt: Option<String>,
+
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = OutputMode::Text)]
+    pub output: OutputMode,
+
+    /// Provider to use instead of the active one (e.g. anthropic, openrouter)
+    #[arg(long)]
+    pub provider: Option<String>,
+
+    /// Model to use instead of the current one
+    #[arg(long, short)]
+    pub model: Option<String>,
+
+    /// Maximum number of model turns
+    #[arg(long, default_value_t = krusty_core::agent::headless::DEFAULT_MAX_TURNS)]
+    pub max_turns: usize,
+
+    /// Comma-separated tools the agent may use (`mcp__*` matches a prefix)
+    #[arg(long, value_delimiter = ',')]
+    pub allowed_tools: Vec<String>,
+
+    /// Continue an existing session by ID
+    #[arg(long)]
+    pub resume: Option<String>,
+}
+
+/// Run `krusty exec`, returning the process exit code
+pub async fn run(args: ExecArgs) -> i32 {
+    let output = args.output;
+    match execute(args).await {
+        Ok((session_id, result)) => {
+            report_result(output, session_id.as_deref(), &result);
+            exit_code(result.outcome)
+        }
+        Err(e) => {
+            if output == OutputMode::Json {
+                println!("{}", json!({ "type": "error", "message": e.to_string() }));
+            } else {
+                eprintln!("Error: {:#}", e);
+            }
+            EXIT_ERROR
+        }
+    }
+}
+
+/// Exit code for a finished run
+pub fn exit_code(outcome: HeadlessOutcome) -> i32 {
+    match outcome {
+        HeadlessOutcome::Completed => EXIT_SUCCESS,
+        HeadlessOutcome::ToolFailed => EXIT_TOOL_FAILED,
+        HeadlessOutcome::MaxTurns => EXIT_BUDGET_EXHAUSTED,
+    }
+}
+
+async fn execute(args: ExecArgs) -> Result<(Option<String>, HeadlessResult)> {
+    let prompt = read_prompt(args.prompt.as_deref())?;
+    let working_dir = std::env::current_dir()?;
+    let db_path = paths::config_dir().join("krusty.db");
+
+    let client = Arc::new(create_client(
+        &db_path,
+        args.provider.as_deref(),
+        args.model.as_deref(),
+    )?);
+    let tools = init_tools(&db_path).await;
+
+    // Project MCP servers, as in the TUI
+    let mcp = Arc::new(McpManager::new(working_dir.clone()));
+    if let Err(e) = mcp.load_config().await {
+        tracing::warn!("Failed to load MCP config: {}", e);
+    } else if mcp.has_servers().await {
+        let _ = mcp.connect_all().await;
+        krusty_core::mcp::tool::register_mcp_tools(mcp.clone(), &tools).await;
+    }
+
+    // Restore or create the session the conversation is saved to
+    let sessions = init_session_manager(&db_path);
+    let mut conversation = Vec::new();
+    let session_id = match (&sessions, args.resume) {
+        (Some(sessions), Some(id)) => {
+            conversation = load_conversation(sessions, &id)?;
+            Some(id)
+        }
+        (None, Some(_)) => bail!("Session storage is unavailable, cannot resume"),
+        (Some(sessions), None) => {
+            let title = SessionManager::generate_title_from_content(&prompt);
+            let dir = working_dir.to_string_lossy();
+            match sessions.create_session(&title, Some(&client.config().model), Some(&dir)) {
+                Ok(id) => Some(id),
+                Err(e) => {
+                    tracing::warn!("Failed to create session: {}", e);
+                    None
+                }
+            }
+        }
+        (None, None) => None,
+    };
+    if let Some(tracker) = client.cost_tracker() {
+        tracker.set_session(session_id.as_deref());
+    }
+
+    let user_message = ModelMessage {
+        role: Role::User,
+        content: vec![Content::Text { text: prompt }],
+    };
+    if let (Some(sessions), Some(id)) = (&sessions, &session_id) {
+        save_message(sessions, id, &user_message);
+    }
+    conversation.push(user_message);
+
+    if args.output == OutputMode::Json {
+        println!(
+            "{}",
+            json!({
+                "type": "session",
+                "session_id": session_id,
+                "model": client.config().model,
+            })
+        );
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let lsp = Arc::new(LspManager::new(working_dir.clone()));
+    let mcp_for_tools = mcp.clone();
+    let mut runner = HeadlessRunner::new(client, tools, working_dir)
+        .with_max_turns(args.max_turns)
+        .with_context(move |ctx| {
+            ctx.with_mcp_manager(mcp_for_tools.clone())
+                .with_lsp_manager(lsp.clone())
+        })
+        .with_events(tx);
+    if !args.allowed_tools.is_empty() {
+        runner = runner.with_allowed_tools(args.allowed_tools);
+    }
+
+    let run = tokio::spawn(async move { runner.run(&mut conversation).await });
+
+    let mut streamed_text = false;
+    while let Some(event) = rx.recv().await {
+        match event {
+            HeadlessEvent::Message(message) => {
+                if let (Some(sessions), Some(id)) = (&sessions, &session_id) {
+                    save_message(sessions, id, &message);
+                }
+            }
+            event => {
+                streamed_text |= matches!(event, HeadlessEvent::Text { .. });
+                render_event(args.output, &event);
+            }
+        }
+    }
+    if streamed_text && args.output == OutputMode::Text {
+        println!();
+    }
+
+    let result = run
+        .await
+        .map_err(|e| anyhow!("Agent task failed: {}", e))??;
+    Ok((session_id, result))
+}
+
+/// Prompt from the argument, or stdin when omitted or "-"
+fn read_prompt(arg: Option<&str>) -> Result<String> {
+    let prompt = match arg {
+        Some(prompt) if prompt != "-" => prompt.to_string(),
+        _ => {
+            let mut stdin = std::io::stdin();
+            if stdin.is_terminal() {
+                bail!("No prompt given. Pass one as an argument or pipe it on stdin");
+            }
+            let mut prompt = String::new();
+            stdin.read_to_string(&mut prompt)?;
+            prompt
+        }
+    };
+    let prompt = prompt.trim();
+    if prompt.is_empty() {
+        bail!("Prompt is empty");
+    }
+    Ok(prompt.to_string())
+}
+
+/// Parse a provider name as accepted by `--provider`
+fn parse_provider(name: &str) -> Option<ProviderId> {
+    let normalized: String = name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase();
+    ProviderId::all().iter().copied().find(|provider| {
+        let key: String = provider
+            .storage_key()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        let display: String = provider
+            .to_string()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+        normalized == key || normalized == display
+    })
+}
+
+/// Create the AI client from overrides, stored credentials and environment
+fn create_client(db_path: &Path, provider: Option<&str>, model: Option<&str>) -> Result<AiClient> {
+    let credentials = CredentialStore::load().unwrap_or_else(|e| {
+        tracing::warn!("Failed to load credential store: {}", e);
+        CredentialStore::default()
+    });
+    let (preferences, _) = init_preferences(db_path);
+
+    let active = ActiveProviderStore::load();
+    let provider_id = match provider {
+        Some(name) => parse_provider(name).ok_or_else(|| anyhow!("Unknown provider: {}", name))?,
+        None => active,
+    };
+
+    let mut model_id = model.map(str::to_string);
+    let api_key = match credentials
+        .get_auth(&provider_id)
+        .or_else(|| acp::get_provider_api_key(provider_id))
+    {
+        Some(key) => key,
+        // Nothing for the active provider; take whatever the environment offers
+        None if provider.is_none() => {
+            let detected = acp::detect_api_key_from_env()
+                .ok_or_else(|| anyhow!("No API key configured. Run `krusty` to log in, or set ANTHROPIC_API_KEY (or KRUSTY_PROVIDER + KRUSTY_API_KEY)"))?;
+            model_id = model_id.or(detected.model);
+            return Ok(build_client(
+                db_path,
+                detected.provider,
+                model_id,
+                detected.api_key,
+                &credentials,
+                &preferences,
+                false,
+            ));
+        }
+        None => bail!("No API key configured for {}", provider_id),
+    };
+
+    Ok(build_client(
+        db_path,
+        provider_id,
+        model_id,
+        api_key,
+        &credentials,
+        &preferences,
+        provider_id == active,
+    ))
+}
+
+fn build_client(
+    db_path: &Path,
+    provider: ProviderId,
+    model: Option<String>,
+    api_key: String,
+    credentials: &CredentialStore,
+    preferences: &Option<crate::storage::Preferences>,
+    use_current_model: bool,
+) -> AiClient {
+    // The saved model belongs to the active provider; others use their default
+    let model = model
+        .or_else(|| {
+            use_current_model
+                .then(|| preferences.as_ref().map(|p| p.get_current_model()))
+                .flatten()
+        })
+        .or_else(|| get_provider(provider).map(|p| p.default_model().to_string()))
+        .unwrap_or_else(|| krusty_core::constants::ai::DEFAULT_MODEL.to_string());
+
+    let registry = init_model_registry(preferences);
+    let config = crate::tui::auth::create_client_config(provider, &model, credentials, &registry);
+    let client = AiClient::with_api_key(config, api_key);
+    match init_cost_tracker(db_path, &registry, preferences) {
+        Some(tracker) => client.with_cost_tracker(tracker, UsageSource::Chat),
+        None => client,
+    }
+}
+
+/// Tool registry with the same safety, permission and user hooks as the TUI
+///
+/// There is nobody to answer "ask" permission rules, so they deny.
+async fn init_tools(db_path: &Path) -> Arc<ToolRegistry> {
+    let user_hooks = init_user_hooks(db_path).await;
+    let permissions = init_permissions(db_path);
+
+    let mut registry = ToolRegistry::new();
+    // First, so safety and permission checks see any rewritten input
+    registry.add_pre_hook(Arc::new(UserPreToolHook::new(user_hooks.clone())));
+    registry.add_pre_hook(Arc::new(SafetyHook::new()));
+    registry.add_pre_hook(Arc::new(PermissionHook::new(permissions)));
+    registry.add_post_hook(Arc::new(LoggingHook::new()));
+    registry.add_post_hook(Arc::new(UserPostToolHook::new(user_hooks)));
+    let registry = Arc::new(registry);
+    register_acp_tools(&registry).await;
+    registry
+}
+
+fn load_conversation(sessions: &SessionManager, id: &str) -> Result<Vec<ModelMessage>> {
+    if sessions.get_session(id)?.is_none() {
+        bail!("Session not found: {}", id);
+    }
+    let mut messages = Vec::new();
+    for (role, content_json) in sessions.load_session_messages(id)? {
+        let role = match role.as_str() {
+            "user" => Role::User,
+            "assistant" => Role::Assistant,
+            "system" => Role::System,
+            "tool" => Role::Tool,
+            other => {
+                tracing::warn!("Unknown role '{}' in stored message, skipping", other);
+                continue;
+            }
+        };
+        match serde_json::from_str(&content_json) {
+            Ok(content) => messages.push(ModelMessage { role, content }),
+            Err(e) => tracing::warn!("Failed to deserialize message content: {}", e),
+        }
+    }
+    Ok(messages)
+}
+
+fn save_message(sessions: &SessionManager, id: &str, message: &ModelMessage) {
+    let role = match message.role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+        Role::System => "system",
+        Role::Tool => "tool",
+    };
+    let result = serde_json::to_string(&message.content)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| sessions.save_message(id, role, &json));
+    if let Err(e) = result {
+        tracing::warn!("Failed to save message: {}", e);
+    }
+}
+
+fn render_event(output: OutputMode, event: &HeadlessEvent) {
+    match output {
+        OutputMode::Json => {
+            if let Ok(line) = serde_json::to_string(event) {
+                println!("{}", line);
+            }
+        }
+        OutputMode::Text => match event {
+            HeadlessEvent::Text { delta } => {
+                print!("{}", delta);
+                let _ = std::io::stdout().flush();
+            }
+            HeadlessEvent::ToolCall { name, .. } => eprintln!("\n▸ {}", name),
+            HeadlessEvent::ToolResult {
+                name,
+                output,
+                is_error: true,
+                ..
+            } => {
+                let first_line = output.lines().next().unwrap_or_default();
+                eprintln!("✗ {}: {}", name, first_line);
+            }
+            _ => {}
+        },
+        OutputMode::Final => {}
+    }
+}
+
+fn report_result(output: OutputMode, session_id: Option<&str>, result: &HeadlessResult) {
+    match output {
+        OutputMode::Json => println!(
+            "{}",
+            json!({
+                "type": "result",
+                "outcome": result.outcome,
+                "turns": result.turns,
+                "session_id": session_id,
+                "final_message": result.final_message,
+            })
+        ),
+        OutputMode::Final => println!("{}", result.final_message),
+        OutputMode::Text => {}
+    }
+
+    if output != OutputMode::Json {
+        match result.outcome {
+            HeadlessOutcome::Completed => {}
+            HeadlessOutcome::ToolFailed => eprintln!("Finished after a failed tool call"),
+            HeadlessOutcome::MaxTurns => {
+                eprintln!("Stopped after reaching --max-turns ({})", result.turns)
+            }
+        }
+        if let Some(id) = session_id {
+            eprintln!("Session: {} (continue with --resume {})", id, id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_provider() {
+        assert_eq!(parse_provider("anthropic"), Some(ProviderId::Anthropic));
+        assert_eq!(parse_provider("OpenRouter"), Some(ProviderId::OpenRouter));
+        assert_eq!(
+            parse_provider("opencode_zen"),
+            Some(ProviderId::OpenCodeZen)
+        );
+        assert_eq!(parse_provider("z.ai"), Some(ProviderId::ZAi));
+        assert_eq!(parse_provider("nope"), None);
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(exit_code(HeadlessOutcome::Completed), EXIT_SUCCESS);
+        assert_eq!(exit_code(HeadlessOutcome::ToolFailed), EXIT_TOOL_FAILED);
+        assert_eq!(exit_code(HeadlessOutcome::MaxTurns), EXIT_BUDGET_EXHAUSTED);
+    }
+}
@@ -0,0 +1,273 @@
+//! Session export and import
+//!
+//! Shared by `krusty export`/`krusty import` and the TUI's `/export` and
+//! `/import`. Markdown and JSON come from the core session bundle; HTML is
+//! the Markdown transcript rendered into a self-contained page. Raw HTML
+//! in the transcript is escaped, except for the `<details>` folds the
+//! transcript itself emits.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, ValueEnum};
+
+use crate::paths;
+use crate::storage::{Database, SessionBundle, SessionManager};
+
+/// Export file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Readable transcript with thinking, tool calls and diffs collapsed
+    #[value(alias = "md")]
+    Markdown,
+    /// Lossless bundle that `krusty import` recreates the session from
+    Json,
+    /// Self-contained HTML page
+    Html,
+}
+
+impl ExportFormat {
+    pub fn parse(name: &str) -> Option<Self> {
+        <Self as ValueEnum>::from_str(name, true).ok()
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Html => "html",
+        }
+    }
+}
+
+/// Arguments for `krusty export`
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Session ID or unique ID prefix (default: latest session in this directory)
+    pub session: Option<String>,
+
+    /// Output format
+    #[arg(long, short, value_enum, default_value_t = ExportFormat::Markdown)]
+    pub format: ExportFormat,
+
+    /// File to write (default: stdout)
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for `krusty import`
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    /// JSON bundle written by `krusty export --format json`
+    pub bundle: PathBuf,
+
+    /// Directory to file the session under (default: current directory)
+    #[arg(long)]
+    pub dir: Option<PathBuf>,
+}
+
+/// Render a session bundle in the given format
+pub fn render(bundle: &SessionBundle, format: ExportFormat) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Markdown => bundle.to_markdown(),
+        ExportFormat::Json => serde_json::to_string_pretty(bundle)?,
+        ExportFormat::Html => to_html(bundle),
+    })
+}
+
+/// File name for an export, e.g. `krusty-fix-login-1a2b3c4d.md`
+pub fn default_file_name(bundle: &SessionBundle, format: ExportFormat) -> String {
+    let slug: String = bundle
+        .session
+        .title
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug: Vec<&str> = slug.split('-').filter(|s| !s.is_empty()).take(6).collect();
+    let short_id: String = bundle.session.id.chars().take(8).collect();
+    format!(
+        "krusty-{}{}.{}",
+        slug.iter().map(|s| format!("{}-", s)).collect::<String>(),
+        short_id,
+        format.extension()
+    )
+}
+
+/// Read and import a bundle file, returning the new session's ID
+pub fn import_file(sm: &SessionManager, path: &Path, working_dir: &Path) -> Result<String> {
+    let json = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let bundle = SessionBundle::from_json(&json)?;
+    sm.import_session(&bundle, Some(&working_dir.to_string_lossy()))
+}
+
+/// Run `krusty export`
+pub fn run_export(args: ExportArgs) -> Result<()> {
+    let sm = open_sessions()?;
+    let session_id = resolve_session(&sm, args.session.as_deref())?;
+    let bundle = sm.export_session(&session_id)?;
+    let rendered = render(&bundle, args.format)?;
+
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, rendered)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!("Exported '{}' to {}", bundle.session.title, path.display());
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
+
+/// Run `krusty import`
+pub fn run_import(args: ImportArgs) -> Result<()> {
+    let sm = open_sessions()?;
+    let dir = match args.dir {
+        Some(dir) => dir,
+        None => std::env::current_dir()?,
+    };
+    let session_id = import_file(&sm, &args.bundle, &dir)?;
+    println!("Imported session {} (open it with /load)", session_id);
+    Ok(())
+}
+
+fn open_sessions() -> Result<SessionManager> {
+    let db = Database::new(&paths::config_dir().join("krusty.db"))?;
+    Ok(SessionManager::new(db))
+}
+
+/// Find a session by ID prefix, or the latest one in the current directory
+fn resolve_session(sm: &SessionManager, id: Option<&str>) -> Result<String> {
+    let Some(prefix) = id else {
+        let cwd = std::env::current_dir()?;
+        return sm
+            .list_sessions(Some(&cwd.to_string_lossy()))?
+            .into_iter()
+            .next()
+            .map(|s| s.id)
+            .context("No sessions in this directory; pass a session ID");
+    };
+
+    let matches: Vec<String> = sm
+        .list_sessions(None)?
+        .into_iter()
+        .filter(|s| s.id.starts_with(prefix))
+        .map(|s| s.id)
+        .collect();
+    match matches.as_slice() {
+        [id] => Ok(id.clone()),
+        [] => bail!("No session matches '{}'", prefix),
+        _ => bail!(
+            "'{}' matches {} sessions; use a longer ID",
+            prefix,
+            matches.len()
+        ),
+    }
+}
+
+/// Markdown transcript rendered into a standalone HTML page
+fn to_html(bundle: &SessionBundle) -> String {
+    let body = markdown_to_html(&bundle.to_markdown());
+    let title = bundle
+        .session
+        .title
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n{}</main>\n</body>\n</html>\n",
+        title, HTML_STYLE, body
+    )
+}
+
+/// Render Markdown, escaping any raw HTML except `<details>` folds
+fn markdown_to_html(markdown: &str) -> String {
+    use pulldown_cmark::{html, Event, Options, Parser};
+
+    let events = Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH,
+    )
+    .map(|event| match event {
+        Event::Html(markup) if is_details_markup(&markup) => Event::Html(markup),
+        Event::Html(markup) | Event::InlineHtml(markup) => Event::Text(markup),
+        other => other,
+    });
+
+    let mut body = String::new();
+    html::push_html(&mut body, events);
+    body
+}
+
+/// Whether raw HTML is only the `<details>`/`<summary>` markup of a fold
+///
+/// Summary text is escaped when the transcript is built, so the only tags
+/// allowed inside it are `<code>` wrappers.
+fn is_details_markup(markup: &str) -> bool {
+    markup.lines().all(|line| {
+        let line = line.trim();
+        if line.is_empty() || line == "<details>" || line == "</details>" {
+            return true;
+        }
+        line.strip_prefix("<summary>")
+            .and_then(|rest| rest.strip_suffix("</summary>"))
+            .is_some_and(|inner| {
+                !inner
+                    .replace("<code>", "")
+                    .replace("</code>", "")
+                    .contains(['<', '>'])
+            })
+    })
+}
+
+const HTML_STYLE: &str = "
+body { margin: 0; background: #1a1b26; color: #c0caf5;
+  font: 15px/1.6 -apple-system, BlinkMacSystemFont, 'Segoe UI', sans-serif; }
+main { max-width: 860px; margin: 0 auto; padding: 2rem 1.5rem 4rem; }
+h1 { color: #ff9e64; }
+h2 { color: #7aa2f7; border-bottom: 1px solid #292e42; padding-bottom: .25rem; margin-top: 2rem; }
+a { color: #7dcfff; }
+code, pre { font: 13px/1.5 ui-monospace, SFMono-Regular, Menlo, monospace; }
+:not(pre) > code { background: #292e42; padding: .1rem .3rem; border-radius: 4px; }
+pre { background: #16161e; padding: .75rem 1rem; border-radius: 6px; overflow-x: auto; }
+details { background: #1f2335; border: 1px solid #292e42; border-radius: 6px;
+  padding: .4rem .8rem; margin: .5rem 0; }
+summary { cursor: pointer; color: #9aa5ce; }
+hr { border: none; border-top: 1px solid #292e42; }
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_escapes_raw_markup() {
+        let html = markdown_to_html(
+            "Hi <script>alert(1)</script>\n\n<img src=x onerror=alert(2)>\n\n\
+             <details>\n<summary>Tool: bash <code>ls &amp; pwd</code></summary>\n\n\
+             ```\nout\n```\n</details>\n",
+        );
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<img"));
+        assert!(html.contains("<details>"));
+        assert!(html.contains("<summary>Tool: bash <code>ls &amp; pwd</code></summary>"));
+    }
+
+    #[test]
+    fn test_details_markup_rejects_smuggled_tags() {
+        assert!(is_details_markup(
+            "<details>\n<summary>Thinking</summary>\n"
+        ));
+        assert!(!is_details_markup("<details>\n<script>x()</script>\n"));
+        assert!(!is_details_markup("<summary><img src=x></summary>"));
+    }
+}
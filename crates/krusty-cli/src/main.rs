@@ -5,12 +5,16 @@
 //! - Single-mode Chat UI with slash commands
 //! - Clean architecture from day one
 
+use std::path::PathBuf;
+
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 
 // Re-export core modules for TUI usage
 use krusty_core::{acp, agent, ai, constants, extensions, paths, plan, process, storage, tools};
 
+mod exec;
+mod export;
 mod tui;
 
 /// Krusty - AI Coding Assistant
@@ -34,6 +38,38 @@ enum Commands {
     /// - KRUSTY_PROVIDER + KRUSTY_API_KEY (+ optional KRUSTY_MODEL)
     /// - Or provider-specific: ANTHROPIC_API_KEY, OPENROUTER_API_KEY, etc.
     Acp,
+
+    /// Run a prompt non-interactively and exit
+    ///
+    /// Runs the full agent loop with tools until the model finishes, then
+    /// exits with 0 on success, 1 on error, 2 if the last tool calls failed,
+    /// or 3 if --max-turns ran out. Reads the prompt from stdin when it is
+    /// omitted or "-".
+    Exec(exec::ExecArgs),
+
+    /// Replay a recorded session trace in the TUI
+    ///
+    /// Traces are written to ~/.krusty/traces/<session-id>.jsonl. Replays
+    /// re-render the session from the recorded events without calling any
+    /// provider, and are read-only.
+    Replay {
+        /// Trace file to replay
+        trace: PathBuf,
+
+        /// Playback speed multiplier
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+    },
+
+    /// Export a session as Markdown, a JSON bundle or HTML
+    ///
+    /// Markdown and HTML are readable transcripts with thinking, tool calls
+    /// and diffs collapsed. JSON bundles are lossless and can be loaded on
+    /// another machine with `krusty import`.
+    Export(export::ExportArgs),
+
+    /// Import a session from a JSON bundle
+    Import(export::ImportArgs),
 }
 
 /// Restore terminal state - called on panic or unexpected exit
@@ -115,6 +151,23 @@ async fn main() -> Result<()> {
             let server = acp::AcpServer::new()?;
             server.run().await?;
         }
+        Some(Commands::Exec(args)) => {
+            tracing::info!("Starting Krusty in headless exec mode");
+            std::process::exit(exec::run(args).await);
+        }
+        Some(Commands::Replay { trace, speed }) => {
+            tracing::info!("Replaying trace {}", trace.display());
+            let replay = agent::TraceReplay::open(&trace, speed)?;
+            let title = trace
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let mut app = tui::App::new().await;
+            app.start_replay(replay, title);
+            app.run().await?;
+        }
+        Some(Commands::Export(args)) => export::run_export(args)?,
+        Some(Commands::Import(args)) => export::run_import(args)?,
         None => {
             // Default: Start TUI chat
             let mut app = tui::App::new().await;
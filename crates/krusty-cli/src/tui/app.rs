@@ -19,22 +19,26 @@ use std::{io, path::PathBuf, sync::Arc, time::Duration};
 use tokio::sync::RwLock;
 
 use crate::agent::{
-    dual_mind::DualMind, AgentCancellation, AgentConfig, AgentEventBus, AgentState, UserHookManager,
+    dual_mind::DualMind, AgentCancellation, AgentConfig, AgentEventBus, AgentState,
+    PermissionManager, PermissionRequest, TraceRecorder, UserHookManager,
 };
 use crate::ai::client::AiClient;
+use crate::ai::cost::CostTracker;
 use crate::ai::models::SharedModelRegistry;
 use crate::ai::providers::ProviderId;
 use crate::ai::types::{AiTool, AiToolCall, Content};
 use crate::extensions::WasmHost;
+use crate::paths;
 use crate::plan::{PlanFile, PlanManager};
 use crate::process::ProcessRegistry;
 use crate::storage::{CredentialStore, Preferences, SessionManager};
-use crate::tools::ToolRegistry;
+use crate::tools::{Checkpointer, ToolRegistry};
 use crate::tui::animation::MenuAnimator;
 use crate::tui::input::{AutocompletePopup, MultiLineInput};
 use crate::tui::markdown::MarkdownCache;
 use crate::tui::polling::{
-    poll_background_processes, poll_init_exploration, poll_mcp_status, poll_oauth_status,
+    poll_background_processes, poll_init_exploration, poll_mcp_prompt, poll_mcp_status,
+    poll_oauth_status,
 };
 use crate::tui::state::{
     BlockManager, BlockUiStates, ChatState, PopupState, ScrollSystem, ToolResultCache,
@@ -65,6 +69,10 @@ pub enum Popup {
     FilePreview,
     SkillsBrowser,
     Hooks,
+    Checkpoints,
+    Cost,
+    Memory,
+    MessageActions,
 }
 
 /// Work mode - BUILD (coding) or PLAN (planning)
@@ -88,6 +96,8 @@ pub struct AppServices {
     // Plan/session storage
     pub plan_manager: PlanManager,
     pub session_manager: Option<SessionManager>,
+    pub checkpoints: Option<Arc<Checkpointer>>,
+    pub cost_tracker: Option<Arc<CostTracker>>,
     pub preferences: Option<Preferences>,
 
     // Credentials/models
@@ -98,6 +108,7 @@ pub struct AppServices {
     pub tool_registry: Arc<ToolRegistry>,
     pub cached_ai_tools: Vec<AiTool>,
     pub user_hook_manager: Arc<RwLock<UserHookManager>>,
+    pub permission_manager: Arc<RwLock<PermissionManager>>,
 
     // Extensions (not yet wired into tool dispatch)
     #[allow(dead_code)]
@@ -106,6 +117,7 @@ pub struct AppServices {
     // Skills/MCP
     pub skills_manager: Arc<RwLock<SkillsManager>>,
     pub mcp_manager: Arc<krusty_core::mcp::McpManager>,
+    pub lsp_manager: Arc<krusty_core::lsp::LspManager>,
     pub mcp_status_tx: tokio::sync::mpsc::UnboundedSender<crate::tui::utils::McpStatusUpdate>,
     pub oauth_status_tx: tokio::sync::mpsc::UnboundedSender<crate::tui::utils::OAuthStatusUpdate>,
 }
@@ -224,10 +236,20 @@ pub struct AppRuntime {
     pub init_explore_id: Option<String>,
     /// Cached languages for /init
     pub cached_init_languages: Option<Vec<String>>,
+    /// Background watcher keeping the codebase index fresh
+    pub index_watcher: Option<krusty_core::index::IndexWatcher>,
+    /// Background /summarize run
+    pub summary_run: Option<crate::tui::handlers::summaries::SummaryRun>,
+    /// Conversation length whose learnings are already recorded
+    pub memory_baseline: usize,
     /// Queued tool calls waiting for explore
     pub queued_tools: Vec<AiToolCall>,
     /// Pending tool results to combine
     pub pending_tool_results: Vec<Content>,
+    /// Permission request awaiting the user's answer
+    pub pending_permission: Option<PermissionRequest>,
+    /// MCP prompt (server, prompt) awaiting argument values
+    pub pending_mcp_prompt: Option<(String, krusty_core::mcp::McpPrompt)>,
     /// Agent event bus
     pub event_bus: AgentEventBus,
     /// Agent state
@@ -262,6 +284,8 @@ pub struct AppRuntime {
     pub just_updated: bool,
     /// Update status
     pub update_status: Option<krusty_core::updater::UpdateStatus>,
+    /// Trace replay in progress (`krusty replay`)
+    pub replay: Option<crate::tui::handlers::replay::ReplayState>,
     /// Should quit flag
     pub should_quit: bool,
 }
@@ -294,8 +318,13 @@ impl AppRuntime {
             channels: AsyncChannels::new(),
             init_explore_id: None,
             cached_init_languages: None,
+            index_watcher: None,
+            summary_run: None,
+            memory_baseline: 0,
             queued_tools: Vec::new(),
             pending_tool_results: Vec::new(),
+            pending_permission: None,
+            pending_mcp_prompt: None,
             event_bus: AgentEventBus::new(),
             agent_state: AgentState::new(),
             agent_config: AgentConfig::default(),
@@ -312,6 +341,7 @@ impl AppRuntime {
             exploration_budget_count: 0,
             just_updated: false,
             update_status: None,
+            replay: None,
             should_quit: false,
         }
     }
@@ -357,6 +387,9 @@ impl App {
             ..runtime
         };
 
+        // Record every session's events for `krusty replay`
+        TraceRecorder::new(paths::traces_dir()).spawn(&runtime.event_bus);
+
         Self {
             ui,
             runtime,
@@ -510,6 +543,12 @@ impl App {
 
     /// Show a toast notification
     pub fn show_toast(&mut self, toast: crate::tui::components::Toast) {
+        self.fire_user_hooks(crate::agent::HookEvent::notification(
+            self.runtime.current_session_id.as_deref(),
+            &self.runtime.working_dir,
+            "toast",
+            &toast.message,
+        ));
         self.ui.toasts.push(toast);
     }
 
@@ -652,6 +691,9 @@ impl App {
         // Eagerly initialize embedding engine in background
         self.runtime.embedding_handle = Some(krusty_core::index::EmbeddingEngine::init_async());
 
+        // Keep an existing codebase index fresh while the app is open
+        self.start_index_watcher();
+
         enable_raw_mode()?;
         let mut stdout = io::stdout();
         execute!(
@@ -742,6 +784,18 @@ impl App {
                 if let Some(processes) = self.runtime.process_registry.try_list() {
                     self.ui.popups.process.update(processes);
                 }
+                // Stream new output into the log view
+                if let Some(view) = self.ui.popups.process.log_view.as_mut() {
+                    if let Some(chunk) = self
+                        .runtime
+                        .process_registry
+                        .try_logs(&view.process_id, view.next_offset)
+                    {
+                        if view.append(chunk) {
+                            self.ui.needs_redraw = true;
+                        }
+                    }
+                }
             }
 
             // Process streaming events (extracted to handlers/stream_events.rs)
@@ -754,6 +808,11 @@ impl App {
             // Execute tools when ready
             self.check_and_execute_tools();
 
+            // Surface tool permission requests from the policy hook
+            if self.poll_permission_requests() {
+                self.ui.needs_redraw = true;
+            }
+
             // Check for completed tool execution
             if let Some(ref mut rx) = self.runtime.channels.tool_results {
                 match rx.try_recv() {
@@ -778,6 +837,7 @@ impl App {
             // Poll async operations
             self.poll_openrouter_fetch();
             self.poll_opencodezen_fetch();
+            self.poll_custom_models_fetch();
             self.poll_title_generation();
             self.poll_summarization();
 
@@ -807,6 +867,9 @@ impl App {
             // Poll build progress channel for builder updates
             self.poll_build_progress();
 
+            // Apply due events of a trace replay
+            self.poll_replay();
+
             // Poll dual-mind dialogue for Big Claw / Little Claw updates
             let dual_mind_result = self.poll_dual_mind();
             if dual_mind_result.needs_redraw {
@@ -823,6 +886,13 @@ impl App {
             if indexing_result.needs_redraw {
                 self.ui.needs_redraw = true;
             }
+            self.process_poll_actions(indexing_result);
+
+            // Poll background index watcher updates
+            self.poll_index_watch();
+
+            // Poll background /summarize run
+            self.poll_summaries();
 
             // Poll /init exploration progress and result
             // Clone cached languages to avoid borrow conflict (cleared on completion)
@@ -850,6 +920,11 @@ impl App {
                 self.ui.needs_redraw = true;
             }
             self.process_poll_actions(mcp_result);
+            let prompt_result = poll_mcp_prompt(&mut self.runtime.channels);
+            if prompt_result.needs_redraw {
+                self.ui.needs_redraw = true;
+            }
+            self.process_poll_actions(prompt_result);
 
             // Poll OAuth status updates from background tasks
             let oauth_result = poll_oauth_status(
@@ -6,18 +6,23 @@ use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use crate::agent::{UserHookManager, UserPostToolHook, UserPreToolHook};
+use crate::agent::{
+    PermissionHook, PermissionManager, PermissionRequest, UserHookManager, UserPostToolHook,
+    UserPreToolHook,
+};
+use crate::ai::cost::CostTracker;
 use crate::ai::models::{create_model_registry, ModelMetadata, SharedModelRegistry};
-use crate::ai::providers::{builtin_providers, ProviderId};
+use crate::ai::providers::{all_providers, ProviderId};
 use crate::extensions::WasmHost;
 use crate::paths;
 use crate::plan::PlanManager;
 use crate::process::ProcessRegistry;
 use crate::storage::{CredentialStore, Database, Preferences, SessionManager};
-use crate::tools::{register_all_tools, ToolRegistry};
+use crate::tools::{register_all_tools, Checkpointer, ToolRegistry};
 use crate::tui::app::AppServices;
 use crate::tui::themes::{Theme, THEME_REGISTRY};
-use crate::tui::utils::{AsyncChannels, McpStatusUpdate};
+use crate::tui::utils::{AppWorktreeDelegate, AsyncChannels, McpStatusUpdate};
+use krusty_core::lsp::LspManager;
 use krusty_core::skills::SkillsManager;
 
 /// Initialize core services (tools, extensions, etc.)
@@ -32,7 +37,8 @@ pub async fn init_services(
     String,
     ProviderId,
 ) {
-    let process_registry = Arc::new(ProcessRegistry::new());
+    let process_registry =
+        Arc::new(ProcessRegistry::new().with_log_dir(paths::logs_dir().join("processes")));
 
     // WASM extension host
     let extensions_dir = paths::extensions_dir();
@@ -46,8 +52,13 @@ pub async fn init_services(
     // User hook manager
     let user_hook_manager = init_user_hooks(&db_path).await;
 
+    // Permission rules (allow/ask/deny) and the channel for "ask" prompts
+    let permission_manager = init_permissions(&db_path);
+    let (permission_tx, permission_rx) = tokio::sync::mpsc::unbounded_channel();
+
     // Tool registry with hooks
-    let tool_registry = init_tool_registry(&user_hook_manager).await;
+    let tool_registry =
+        init_tool_registry(&user_hook_manager, &permission_manager, permission_tx).await;
     let cached_ai_tools = tool_registry.get_ai_tools().await;
 
     // Preferences and theme
@@ -57,6 +68,9 @@ pub async fn init_services(
     // Session manager
     let session_manager = init_session_manager(&db_path);
 
+    // File checkpoints for /undo
+    let checkpoints = init_checkpoints(&db_path);
+
     // Plan manager
     let plan_manager = init_plan_manager(&db_path);
 
@@ -70,6 +84,9 @@ pub async fn init_services(
     // Model registry
     let model_registry = init_model_registry(&preferences);
 
+    // Per-request cost accounting and budgets
+    let cost_tracker = init_cost_tracker(&db_path, &model_registry, &preferences);
+
     // Current model from preferences
     let current_model = preferences
         .as_ref()
@@ -92,23 +109,34 @@ pub async fn init_services(
     // Connect MCP servers in background
     spawn_mcp_connections(&mcp_manager, &tool_registry, &mcp_status_tx).await;
 
+    // Language servers (started on first use) plus any from installed extensions
+    let lsp_manager = Arc::new(LspManager::new(working_dir.to_path_buf()));
+    if let Some(ref host) = wasm_host {
+        spawn_extension_language_servers(host, &lsp_manager, working_dir, extensions_dir);
+    }
+
     // Set up channels
     let mut channels = AsyncChannels::new();
     channels.mcp_status = Some(mcp_status_rx);
     channels.oauth_status = Some(oauth_status_rx);
+    channels.permission_requests = Some(permission_rx);
 
     let services = AppServices {
         plan_manager,
         session_manager,
+        checkpoints,
+        cost_tracker,
         preferences,
         credential_store,
         model_registry,
         tool_registry,
         cached_ai_tools,
         user_hook_manager,
+        permission_manager,
         wasm_host,
         skills_manager,
         mcp_manager,
+        lsp_manager,
         mcp_status_tx,
         oauth_status_tx,
     };
@@ -125,7 +153,7 @@ pub async fn init_services(
 }
 
 /// Initialize user hooks from database
-async fn init_user_hooks(db_path: &Path) -> Arc<RwLock<UserHookManager>> {
+pub(crate) async fn init_user_hooks(db_path: &Path) -> Arc<RwLock<UserHookManager>> {
     let user_hook_manager = Arc::new(RwLock::new(UserHookManager::new()));
     if let Ok(db) = Database::new(db_path) {
         let hook_count = {
@@ -142,13 +170,35 @@ async fn init_user_hooks(db_path: &Path) -> Arc<RwLock<UserHookManager>> {
     user_hook_manager
 }
 
+/// Initialize permission rules from database
+pub(crate) fn init_permissions(db_path: &Path) -> Arc<RwLock<PermissionManager>> {
+    let mut manager = PermissionManager::new();
+    if let Ok(db) = Database::new(db_path) {
+        if let Err(e) = manager.load(&db) {
+            tracing::warn!("Failed to load permission rules: {}", e);
+        } else if !manager.rules().is_empty() {
+            tracing::info!("Loaded {} permission rules", manager.rules().len());
+        }
+    }
+    Arc::new(RwLock::new(manager))
+}
+
 /// Initialize tool registry with safety hooks
-async fn init_tool_registry(user_hook_manager: &Arc<RwLock<UserHookManager>>) -> Arc<ToolRegistry> {
+async fn init_tool_registry(
+    user_hook_manager: &Arc<RwLock<UserHookManager>>,
+    permission_manager: &Arc<RwLock<PermissionManager>>,
+    permission_tx: tokio::sync::mpsc::UnboundedSender<PermissionRequest>,
+) -> Arc<ToolRegistry> {
     let mut tool_registry = ToolRegistry::new();
+    // First, so safety and permission checks see any rewritten input
+    tool_registry.add_pre_hook(Arc::new(UserPreToolHook::new(user_hook_manager.clone())));
     tool_registry.add_pre_hook(Arc::new(crate::agent::SafetyHook::new()));
     tool_registry.add_pre_hook(Arc::new(crate::agent::PlanModeHook::new()));
+    // After the hard blocks so users are never asked about calls that would be refused anyway
+    tool_registry.add_pre_hook(Arc::new(
+        PermissionHook::new(permission_manager.clone()).with_prompter(permission_tx),
+    ));
     tool_registry.add_post_hook(Arc::new(crate::agent::LoggingHook::new()));
-    tool_registry.add_pre_hook(Arc::new(UserPreToolHook::new(user_hook_manager.clone())));
     tool_registry.add_post_hook(Arc::new(UserPostToolHook::new(user_hook_manager.clone())));
     let tool_registry = Arc::new(tool_registry);
     register_all_tools(&tool_registry).await;
@@ -156,7 +206,7 @@ async fn init_tool_registry(user_hook_manager: &Arc<RwLock<UserHookManager>>) ->
 }
 
 /// Initialize preferences and get theme name
-fn init_preferences(db_path: &Path) -> (Option<Preferences>, String) {
+pub(crate) fn init_preferences(db_path: &Path) -> (Option<Preferences>, String) {
     match Database::new(db_path) {
         Ok(db) => {
             let prefs = Preferences::new(db);
@@ -171,7 +221,7 @@ fn init_preferences(db_path: &Path) -> (Option<Preferences>, String) {
 }
 
 /// Initialize session manager
-fn init_session_manager(db_path: &Path) -> Option<SessionManager> {
+pub(crate) fn init_session_manager(db_path: &Path) -> Option<SessionManager> {
     match Database::new(db_path) {
         Ok(db) => {
             tracing::info!("Session database initialized at {:?}", db_path);
@@ -184,6 +234,37 @@ fn init_session_manager(db_path: &Path) -> Option<SessionManager> {
     }
 }
 
+/// Initialize the file checkpoint recorder
+fn init_checkpoints(db_path: &Path) -> Option<Arc<Checkpointer>> {
+    match Checkpointer::open(db_path) {
+        Ok(checkpointer) => Some(Arc::new(checkpointer)),
+        Err(e) => {
+            tracing::warn!("Failed to initialize checkpoints: {}", e);
+            None
+        }
+    }
+}
+
+/// Initialize the cost tracker with the saved budget limits
+pub(crate) fn init_cost_tracker(
+    db_path: &Path,
+    model_registry: &SharedModelRegistry,
+    preferences: &Option<Preferences>,
+) -> Option<Arc<CostTracker>> {
+    match CostTracker::open(db_path, model_registry.clone()) {
+        Ok(tracker) => {
+            if let Some(prefs) = preferences {
+                tracker.set_limits(prefs.get_budget_limits());
+            }
+            Some(Arc::new(tracker))
+        }
+        Err(e) => {
+            tracing::warn!("Failed to initialize cost tracking: {}", e);
+            None
+        }
+    }
+}
+
 /// Initialize plan manager with migration
 fn init_plan_manager(db_path: &Path) -> PlanManager {
     let plan_manager =
@@ -208,25 +289,18 @@ fn init_plan_manager(db_path: &Path) -> PlanManager {
 }
 
 /// Initialize model registry with static and cached models
-fn init_model_registry(preferences: &Option<Preferences>) -> SharedModelRegistry {
+pub(crate) fn init_model_registry(preferences: &Option<Preferences>) -> SharedModelRegistry {
     let model_registry = create_model_registry();
 
-    // Load static models from builtin providers
-    for provider in builtin_providers() {
+    // Load static models from builtin and custom providers
+    for provider in all_providers() {
         if provider.models.is_empty() {
             continue;
         }
         let models: Vec<ModelMetadata> = provider
             .models
             .iter()
-            .map(|m| {
-                let mut meta = ModelMetadata::new(&m.id, &m.display_name, provider.id)
-                    .with_context(m.context_window, m.max_output);
-                if let Some(format) = m.reasoning {
-                    meta = meta.with_thinking(format);
-                }
-                meta
-            })
+            .map(|m| ModelMetadata::from_model_info(provider, m))
             .collect();
         futures::executor::block_on(model_registry.set_models(provider.id, models));
     }
@@ -304,3 +378,52 @@ async fn spawn_mcp_connections(
         }
     });
 }
+
+/// Register language servers provided by installed extensions in background
+fn spawn_extension_language_servers(
+    host: &Arc<WasmHost>,
+    lsp_manager: &Arc<LspManager>,
+    working_dir: &Path,
+    extensions_dir: std::path::PathBuf,
+) {
+    let host = host.clone();
+    let lsp = lsp_manager.clone();
+    let worktree = AppWorktreeDelegate::new(working_dir.to_path_buf());
+
+    tokio::spawn(async move {
+        let Ok(entries) = std::fs::read_dir(&extensions_dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            if !dir.join("extension.toml").exists() {
+                continue;
+            }
+            let extension = match host.load_extension_from_dir(&dir).await {
+                Ok(extension) => extension,
+                Err(e) => {
+                    tracing::debug!("Skipping extension {}: {}", dir.display(), e);
+                    continue;
+                }
+            };
+            if extension.manifest.language_servers.is_empty() {
+                continue;
+            }
+            match lsp
+                .register_extension_servers(&extension, worktree.clone())
+                .await
+            {
+                Ok(count) => tracing::info!(
+                    "Registered {} language server(s) from {}",
+                    count,
+                    extension.manifest.name
+                ),
+                Err(e) => tracing::warn!(
+                    "Failed to register language servers from {}: {}",
+                    extension.manifest.name,
+                    e
+                ),
+            }
+        }
+    });
+}
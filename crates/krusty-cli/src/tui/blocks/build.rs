@@ -5,6 +5,9 @@
 //!
 //! "The Kraken" is the builder swarm - Opus agents that write code.
 //! (Octopus + Opus = Kraken unleashed)
+//!
+//! Builders an isolated build couldn't merge are listed with their diffstat
+//! and [accept]/[reject] buttons.
 
 use crossterm::event::{Event, KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
@@ -16,7 +19,7 @@ use std::collections::HashMap;
 use std::time::{Duration, Instant};
 use unicode_width::UnicodeWidthStr;
 
-use super::{ClipContext, EventResult, StreamBlock};
+use super::{BlockEvent, ClipContext, EventResult, StreamBlock};
 use crate::agent::subagent::{AgentProgress, AgentProgressStatus};
 use crate::tui::themes::Theme;
 
@@ -39,6 +42,15 @@ const WAVE_INTERVAL: Duration = Duration::from_millis(150);
 /// Spiral spinner interval (agent rows)
 const SPIRAL_INTERVAL: Duration = Duration::from_millis(180);
 
+/// Width of the block box
+const BOX_WIDTH: u16 = 68;
+
+/// Changed files listed per unmerged builder
+const MAX_PENDING_FILES: usize = 4;
+
+const ACCEPT_LABEL: &str = "[accept]";
+const REJECT_LABEL: &str = "[reject]";
+
 /// State of a single builder agent
 #[derive(Debug, Clone)]
 struct BuilderEntry {
@@ -72,6 +84,58 @@ struct BuilderEntry {
 
 const LERP_SPEED: f32 = 0.15;
 
+/// A builder whose changes an isolated build left on its branch
+#[derive(Debug, Clone)]
+struct PendingMerge {
+    task_id: String,
+    branch: String,
+    /// Why it wasn't merged, if not because of conflicts
+    reason: Option<String>,
+    /// Changed files with their diffstat
+    files: Vec<String>,
+    /// Set once accepted (true) or rejected (false)
+    decision: Option<bool>,
+}
+
+impl PendingMerge {
+    fn height(&self) -> u16 {
+        1 + u16::from(self.reason.is_some()) + self.files.len().min(MAX_PENDING_FILES) as u16
+    }
+
+    /// Parse the `## Unmerged:` sections of the build tool output
+    fn parse_all(output: &str) -> Vec<Self> {
+        let mut pending: Vec<Self> = Vec::new();
+        let mut in_section = false;
+        for line in output.lines() {
+            if let Some(rest) = line.strip_prefix("## Unmerged: ") {
+                let mut parts = rest.split_whitespace();
+                if let (Some(task_id), Some(branch)) = (parts.next(), parts.next()) {
+                    pending.push(Self {
+                        task_id: task_id.to_string(),
+                        branch: branch.to_string(),
+                        reason: None,
+                        files: Vec::new(),
+                        decision: None,
+                    });
+                    in_section = true;
+                    continue;
+                }
+            }
+            let Some(current) = pending.last_mut().filter(|_| in_section) else {
+                continue;
+            };
+            if let Some(file) = line.strip_prefix("- ") {
+                current.files.push(file.to_string());
+            } else if line.is_empty() || line.starts_with("**") || line.starts_with("##") {
+                in_section = false;
+            } else {
+                current.reason = Some(line.to_string());
+            }
+        }
+        pending
+    }
+}
+
 impl BuilderEntry {
     fn from_progress(progress: &AgentProgress) -> Self {
         let mut entry = Self {
@@ -197,6 +261,8 @@ pub struct BuildBlock {
     selected_idx: Option<usize>,
     /// Final summary text
     summary: Option<String>,
+    /// Builders left unmerged by an isolated build
+    pending_merges: Vec<PendingMerge>,
 
     // === Cached totals ===
     cached_total_tools: usize,
@@ -226,6 +292,7 @@ impl BuildBlock {
             spiral_idx: 0,
             selected_idx: None,
             summary: None,
+            pending_merges: Vec::new(),
             cached_total_tools: 0,
             cached_total_tokens: 0,
             cached_total_elapsed: 0,
@@ -295,9 +362,144 @@ impl BuildBlock {
             }
         }
 
+        self.pending_merges = PendingMerge::parse_all(&output);
         self.summary = Some(output);
     }
 
+    /// Record that an unmerged builder branch was accepted or rejected
+    pub fn set_merge_decision(&mut self, branch: &str, accepted: bool) {
+        if let Some(pending) = self.pending_merges.iter_mut().find(|p| p.branch == branch) {
+            pending.decision = Some(accepted);
+        }
+    }
+
+    /// Lines used by builder rows and their expanded output
+    fn builder_lines(&self) -> u16 {
+        self.builder_order
+            .iter()
+            .filter_map(|task_id| self.builders.get(task_id))
+            .map(|builder| {
+                let output = if builder.expanded {
+                    builder.output.lines().count().min(4) as u16
+                } else {
+                    0
+                };
+                1 + output
+            })
+            .sum()
+    }
+
+    /// Column of the [accept] and [reject] buttons
+    fn button_columns(x: u16, width: u16) -> (u16, u16) {
+        let reject = x + width.saturating_sub(3 + REJECT_LABEL.len() as u16);
+        let accept = reject.saturating_sub(1 + ACCEPT_LABEL.len() as u16);
+        (accept, reject)
+    }
+
+    /// Render unmerged builders starting at `y`, returning the next free row
+    fn render_pending_merges(
+        &self,
+        area: Rect,
+        mut y: u16,
+        buf: &mut Buffer,
+        theme: &Theme,
+    ) -> u16 {
+        let border_style = Style::default().fg(theme.border_color);
+        let dim_style = Style::default().fg(theme.dim_color);
+        let text_style = Style::default().fg(theme.text_color);
+        let bottom = area.y + area.height;
+        let max_len = (area.width as usize).saturating_sub(6);
+
+        for pending in &self.pending_merges {
+            if y >= bottom {
+                break;
+            }
+            buf.set_string(area.x, y, "│ ", border_style);
+            buf.set_string(area.x + 2, y, "⑂", Style::default().fg(theme.warning_color));
+            let (accept_x, reject_x) = Self::button_columns(area.x, area.width);
+            let label: String = format!("{} unmerged", pending.task_id)
+                .chars()
+                .take((accept_x.saturating_sub(area.x + 5)) as usize)
+                .collect();
+            buf.set_string(area.x + 4, y, &label, text_style);
+            match pending.decision {
+                None => {
+                    let button = Style::default()
+                        .fg(theme.accent_color)
+                        .add_modifier(Modifier::BOLD);
+                    buf.set_string(accept_x, y, ACCEPT_LABEL, button);
+                    buf.set_string(
+                        reject_x,
+                        y,
+                        REJECT_LABEL,
+                        Style::default().fg(theme.error_color),
+                    );
+                }
+                Some(true) => {
+                    buf.set_string(
+                        reject_x,
+                        y,
+                        "merged",
+                        Style::default().fg(theme.success_color),
+                    );
+                }
+                Some(false) => {
+                    buf.set_string(reject_x, y, "rejected", dim_style);
+                }
+            }
+            buf.set_string(area.x + area.width - 1, y, "│", border_style);
+            y += 1;
+
+            let details = pending
+                .reason
+                .iter()
+                .chain(pending.files.iter().take(MAX_PENDING_FILES));
+            for detail in details {
+                if y >= bottom {
+                    break;
+                }
+                buf.set_string(area.x, y, "│   ", border_style);
+                let truncated: String = detail.chars().take(max_len).collect();
+                let style = if detail.ends_with("(conflict)") {
+                    Style::default().fg(theme.warning_color)
+                } else {
+                    dim_style
+                };
+                buf.set_string(area.x + 4, y, &truncated, style);
+                buf.set_string(area.x + area.width - 1, y, "│", border_style);
+                y += 1;
+            }
+        }
+        y
+    }
+
+    /// Accept/reject clicks on an unmerged builder's row
+    fn pending_merge_click(&self, area: Rect, x: u16, y: u16) -> Option<BlockEvent> {
+        let width = BOX_WIDTH.min(area.width);
+        let (accept_x, reject_x) = Self::button_columns(area.x, width);
+        let mut row = area.y + 1 + self.builder_lines();
+        for pending in &self.pending_merges {
+            if y == row {
+                if pending.decision.is_some() {
+                    return None;
+                }
+                let accept = if (accept_x..accept_x + ACCEPT_LABEL.len() as u16).contains(&x) {
+                    true
+                } else if (reject_x..reject_x + REJECT_LABEL.len() as u16).contains(&x) {
+                    false
+                } else {
+                    return None;
+                };
+                return Some(BlockEvent::MergeBranch {
+                    branch: pending.branch.clone(),
+                    accept,
+                });
+            }
+            row += pending.height();
+        }
+        None
+    }
+
     fn update_cached_totals(&mut self) {
         self.cached_total_tools = self.builders.values().map(|b| b.displayed_tools()).sum();
         self.cached_total_tokens = self
@@ -482,6 +684,8 @@ impl BuildBlock {
             }
         }
 
+        y = self.render_pending_merges(area, y, buf, theme);
+
         while y < area.y + area.height {
             buf.set_string(area.x, y, "│", border_style);
             buf.set_string(area.x + area.width - 1, y, "│", border_style);
@@ -560,16 +764,8 @@ impl StreamBlock for BuildBlock {
         if self.collapsed {
             1
         } else {
-            let mut builder_lines = 0u16;
-            for task_id in &self.builder_order {
-                if let Some(builder) = self.builders.get(task_id) {
-                    builder_lines += 1;
-                    if builder.expanded {
-                        builder_lines += builder.output.lines().count().min(4) as u16;
-                    }
-                }
-            }
-            4 + builder_lines
+            let pending_lines: u16 = self.pending_merges.iter().map(|p| p.height()).sum();
+            4 + self.builder_lines() + pending_lines
         }
     }
 
@@ -587,8 +783,7 @@ impl StreamBlock for BuildBlock {
 
         let (clip_top, _clip_bottom) = clip.map(|c| (c.clip_top, c.clip_bottom)).unwrap_or((0, 0));
 
-        let content_width = 68u16;
-        let width = content_width.min(area.width);
+        let width = BOX_WIDTH.min(area.width);
 
         if clip_top == 0 {
             self.render_header(Rect::new(area.x, area.y, width, 1), buf, theme);
@@ -656,6 +851,7 @@ impl StreamBlock for BuildBlock {
         match event {
             Event::Mouse(MouseEvent {
                 kind: MouseEventKind::Down(MouseButton::Left),
+                column,
                 row,
                 ..
             }) => {
@@ -666,6 +862,12 @@ impl StreamBlock for BuildBlock {
                     return EventResult::Consumed;
                 }
 
+                if !self.collapsed {
+                    if let Some(event) = self.pending_merge_click(area, *column, y) {
+                        return EventResult::Action(event);
+                    }
+                }
+
                 if !self.collapsed && y > area.y && y < area.y + area.height - 2 {
                     let idx = (y - area.y - 1) as usize;
                     if idx < self.builder_order.len() {
@@ -88,6 +88,8 @@ pub enum BlockEvent {
     Pinned(bool),
     /// Toggle global diff display mode (unified <-> side-by-side)
     ToggleDiffMode,
+    /// Accept (merge) or reject an unmerged builder branch
+    MergeBranch { branch: String, accept: bool },
 }
 
 /// Simple scrolling for blocks with fixed-line content (no width dependency)
@@ -1,4 +1,4 @@
-//! Tool result block - collapsible display for search results (grep/glob)
+//! Tool result block - collapsible display for search results (grep/glob) and fetched pages
 //!
 //! Shows search/find results like thinking blocks:
 //! - Collapsed: ▶ grep (pattern) N results
@@ -136,6 +136,11 @@ impl ToolResultBlock {
                         .collect();
                     self.count = self.results.len();
                 }
+            } else if self.tool_name == "web_fetch" {
+                if let Some(content) = json.get("content").and_then(|v| v.as_str()) {
+                    self.results = content.lines().map(|l| l.to_string()).collect();
+                    self.count = self.results.len();
+                }
             }
         }
     }
@@ -145,6 +150,15 @@ impl ToolResultBlock {
         self.duration = Some(self.start_time.elapsed());
     }
 
+    /// What the count in the header counts
+    fn count_label(&self) -> &'static str {
+        if self.tool_name == "web_fetch" {
+            "lines"
+        } else {
+            "results"
+        }
+    }
+
     pub fn toggle(&mut self) {
         // Don't allow expanding if no results
         if self.collapsed && self.count == 0 {
@@ -219,8 +233,11 @@ impl ToolResultBlock {
             )
         } else {
             format!(
-                "▶ {} ({}) {} results",
-                self.tool_name, pat_display, self.count
+                "▶ {} ({}) {} {}",
+                self.tool_name,
+                pat_display,
+                self.count,
+                self.count_label()
             )
         };
 
@@ -284,8 +301,11 @@ impl ToolResultBlock {
 
         // Also consider header width
         let header_text = format!(
-            " ▼ {} ({}) {} results ",
-            self.tool_name, pat_display, self.count
+            " ▼ {} ({}) {} {} ",
+            self.tool_name,
+            pat_display,
+            self.count,
+            self.count_label()
         );
         let header_width = UnicodeWidthStr::width(header_text.as_str());
 
@@ -308,8 +328,11 @@ impl ToolResultBlock {
         // Top border - only if not clipped
         if clip_top == 0 {
             let header = format!(
-                " ▼ {} ({}) {} results ",
-                self.tool_name, pat_display, self.count
+                " ▼ {} ({}) {} {} ",
+                self.tool_name,
+                pat_display,
+                self.count,
+                self.count_label()
             );
 
             if let Some(cell) = buf.cell_mut((area.x, render_y)) {
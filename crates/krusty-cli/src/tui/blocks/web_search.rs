@@ -93,6 +93,18 @@ impl WebSearchBlock {
         self.duration = Some(self.start_time.elapsed());
     }
 
+    /// Set results from the client-side `web_search` tool's JSON output
+    pub fn set_results_from_output(&mut self, output: &str) {
+        let results = serde_json::from_str::<serde_json::Value>(output)
+            .ok()
+            .and_then(|json| json.get("results").cloned())
+            .and_then(|results| serde_json::from_value::<Vec<WebSearchResult>>(results).ok());
+        match results {
+            Some(results) => self.set_results(results),
+            None => self.complete(),
+        }
+    }
+
     pub fn toggle(&mut self) {
         // Don't allow expanding if no results
         if self.collapsed && self.results.is_empty() {
@@ -3,6 +3,7 @@
 //! A unified prompt widget for user decisions:
 //! - Plan confirmation (Execute/Modify/Abandon)
 //! - AskUserQuestion tool (Claude's questions with options)
+//! - Tool permission requests (Allow once/Always/Deny)
 
 use ratatui::{
     buffer::Buffer,
@@ -22,6 +23,10 @@ pub enum PromptType {
     PlanConfirm,
     /// AskUserQuestion tool from Claude
     AskUserQuestion,
+    /// Permission policy asking before a tool runs
+    ToolPermission,
+    /// Argument values for an MCP prompt slash command
+    McpPromptArguments,
 }
 
 /// A single option in a question
@@ -164,6 +169,44 @@ impl DecisionPrompt {
         self.visible = true;
     }
 
+    /// Show tool permission prompt for an "ask" rule
+    pub fn show_permission(&mut self, tool_name: &str, summary: &str) {
+        self.questions =
+            vec![
+                PromptQuestion::new(format!("Allow {}?", tool_name), summary.to_string())
+                    .add_option(PromptOption::new("Allow once"))
+                    .add_option(
+                        PromptOption::new("Always allow")
+                            .with_description("Remember for this project"),
+                    )
+                    .add_option(PromptOption::new("Deny")),
+            ];
+
+        self.current_index = 0;
+        self.selected_option = 0;
+        self.scroll_offset = 0;
+        self.toggled_options.clear();
+        self.answers.clear();
+        self.prompt_type = PromptType::ToolPermission;
+        self.tool_use_id = None;
+        self.custom_input_mode = false;
+        self.visible = true;
+    }
+
+    /// Show one question per MCP prompt argument
+    pub fn show_mcp_prompt_arguments(&mut self, questions: Vec<PromptQuestion>) {
+        self.questions = questions;
+        self.current_index = 0;
+        self.selected_option = 0;
+        self.scroll_offset = 0;
+        self.toggled_options.clear();
+        self.answers.clear();
+        self.prompt_type = PromptType::McpPromptArguments;
+        self.tool_use_id = None;
+        self.custom_input_mode = false;
+        self.visible = true;
+    }
+
     /// Hide the prompt
     pub fn hide(&mut self) {
         self.visible = false;
@@ -472,15 +515,19 @@ impl DecisionPrompt {
         // Footer hint (at bottom)
         if inner.y + inner.height > y {
             let hint = if self.custom_input_mode {
-                if self.prompt_type == PromptType::PlanConfirm {
-                    "typing modification... (Esc to cancel)"
-                } else {
-                    "typing custom response... (Esc to cancel)"
+                match self.prompt_type {
+                    PromptType::PlanConfirm => "typing modification... (Esc to cancel)",
+                    PromptType::ToolPermission => "typing reason to deny... (Esc to cancel)",
+                    PromptType::AskUserQuestion => "typing custom response... (Esc to cancel)",
+                    PromptType::McpPromptArguments => "typing value... (Esc to cancel)",
                 }
-            } else if self.prompt_type == PromptType::PlanConfirm {
-                "press 1/2, click, or type to modify plan"
             } else {
-                "type number, click, or enter custom response"
+                match self.prompt_type {
+                    PromptType::PlanConfirm => "press 1/2, click, or type to modify plan",
+                    PromptType::ToolPermission => "press 1/2/3, click, or type a reason to deny",
+                    PromptType::AskUserQuestion => "type number, click, or enter custom response",
+                    PromptType::McpPromptArguments => "type a value, Esc to cancel",
+                }
             };
 
             let hint_line = Line::from(Span::styled(
@@ -14,6 +14,7 @@ use unicode_width::UnicodeWidthStr;
 use crate::tui::themes::Theme;
 
 /// Render the status bar at the bottom of the screen
+#[allow(clippy::too_many_arguments)]
 pub fn render_status_bar(
     f: &mut Frame,
     area: Rect,
@@ -21,6 +22,7 @@ pub fn render_status_bar(
     model: &str,
     cwd: &Path,
     context_tokens: Option<(usize, usize)>, // (used, max)
+    session_cost: Option<f64>,
     running_processes: usize,
     process_elapsed: Option<Duration>,
 ) {
@@ -67,6 +69,18 @@ pub fn render_status_bar(
         ));
     }
 
+    // Running session cost
+    if let Some(cost) = session_cost {
+        let cost_text = crate::ai::cost::format_cost(cost);
+        left_width += 3 + cost_text.width() as u16; // " │ " + text
+
+        left_spans.push(Span::styled(" │ ", Style::default().fg(theme.dim_color)));
+        left_spans.push(Span::styled(
+            cost_text,
+            Style::default().fg(theme.dim_color),
+        ));
+    }
+
     // Running processes indicator with elapsed time
     if running_processes > 0 {
         let elapsed_str = process_elapsed
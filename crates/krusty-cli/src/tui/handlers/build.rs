@@ -0,0 +1,59 @@
+//! Build merge handlers
+//!
+//! An isolated Kraken build (`isolation: "worktree"`) can leave builders
+//! unmerged on their branches. Their rows in the build block have
+//! [accept]/[reject] buttons, handled here.
+
+use crate::agent::worktree;
+use crate::tui::app::App;
+
+impl App {
+    /// Merge an unmerged builder branch into the working tree, or discard it
+    ///
+    /// Either way the branch is deleted afterwards. Accepted changes that
+    /// still conflict are written with conflict markers.
+    pub(crate) fn resolve_build_branch(&mut self, block_idx: usize, branch: &str, accept: bool) {
+        let working_dir = self.runtime.working_dir.clone();
+        let checkpoints = self.services.checkpoints.clone();
+        let result = futures::executor::block_on(async {
+            let repo_root = worktree::repo_root(&working_dir).await?;
+            let outcome = if accept {
+                Some(
+                    worktree::merge_branch(&working_dir, branch, true, checkpoints.as_deref())
+                        .await?,
+                )
+            } else {
+                None
+            };
+            worktree::delete_branches(&repo_root, &[branch.to_string()]).await;
+            anyhow::Ok(outcome)
+        });
+
+        let message = match result {
+            Ok(outcome) => {
+                if let Some(block) = self.runtime.blocks.build.get_mut(block_idx) {
+                    block.set_merge_decision(branch, accept);
+                }
+                match outcome {
+                    Some(outcome) if outcome.conflicts.is_empty() => format!(
+                        "Merged {}: {} file{} updated",
+                        branch,
+                        outcome.applied.len(),
+                        if outcome.applied.len() == 1 { "" } else { "s" }
+                    ),
+                    Some(outcome) => format!(
+                        "Merged {} with conflicts to resolve in {}",
+                        branch,
+                        outcome.conflicts.join(", ")
+                    ),
+                    None => format!("Discarded {}", branch),
+                }
+            }
+            Err(e) => format!("Failed to merge {}: {}", branch, e),
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+    }
+}
@@ -0,0 +1,148 @@
+//! File checkpoint handlers
+//!
+//! Starts a checkpoint turn for each user message and implements `/undo`,
+//! `/checkpoints` and "restore files to here" on a user message. All three
+//! open the checkpoints popup so the diff is reviewed before restoring.
+
+use crate::tui::app::{App, Popup};
+
+impl App {
+    /// Record file checkpoints for a new user turn
+    ///
+    /// Turns are numbered by the user messages shown in the chat, so a
+    /// message can be mapped back to the checkpoint taken for it.
+    pub(crate) fn begin_checkpoint_turn(&self, label: &str) {
+        let (Some(checkpoints), Some(session_id)) =
+            (&self.services.checkpoints, &self.runtime.current_session_id)
+        else {
+            return;
+        };
+        let last = self.runtime.chat.messages.len().saturating_sub(1);
+        let turn = self.checkpoint_turn_for_message(last);
+        checkpoints.begin_turn(session_id, turn, label);
+    }
+
+    /// Stop recording checkpoints, e.g. when leaving a session
+    pub(crate) fn end_checkpoint_turn(&self) {
+        if let Some(checkpoints) = &self.services.checkpoints {
+            checkpoints.end_turn();
+        }
+    }
+
+    /// Checkpoint turn of the user message at `msg_idx` in the chat
+    pub(crate) fn checkpoint_turn_for_message(&self, msg_idx: usize) -> usize {
+        self.runtime
+            .chat
+            .messages
+            .iter()
+            .take(msg_idx + 1)
+            .filter(|(role, _)| role == "user")
+            .count()
+    }
+
+    /// Open the checkpoints popup
+    ///
+    /// With `from_turn`, selects the oldest checkpoint at or after it;
+    /// otherwise the newest.
+    fn open_checkpoints_popup(&mut self, title: &str, from_turn: Option<usize>) {
+        let list = match (&self.services.checkpoints, &self.runtime.current_session_id) {
+            (Some(checkpoints), Some(session_id)) => checkpoints.list(session_id),
+            _ => Ok(Vec::new()),
+        };
+
+        let message = match list {
+            Ok(list) if list.is_empty() => "No file changes to restore in this session".to_string(),
+            Ok(list) if from_turn.is_some_and(|t| list.iter().all(|c| c.turn < t)) => {
+                "No file changes since this message".to_string()
+            }
+            Ok(list) => {
+                self.ui.popups.checkpoints.open(title, list, from_turn);
+                self.refresh_checkpoint_preview();
+                self.ui.popup = Popup::Checkpoints;
+                return;
+            }
+            Err(e) => format!("Failed to load checkpoints: {}", e),
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+    }
+
+    /// `/checkpoints` - browse and restore any turn
+    pub fn handle_checkpoints_command(&mut self) {
+        self.open_checkpoints_popup("Checkpoints", None);
+    }
+
+    /// `/undo` - revert the files changed in the last turn
+    pub fn handle_undo_command(&mut self) {
+        self.open_checkpoints_popup("Undo Last Turn", None);
+    }
+
+    /// "Restore files to here" for the user message at `msg_idx`
+    pub(crate) fn open_checkpoints_for_message(&mut self, msg_idx: usize) {
+        let turn = self.checkpoint_turn_for_message(msg_idx);
+        self.open_checkpoints_popup("Restore Files To Here", Some(turn));
+    }
+
+    /// Load the diff preview for the selected checkpoint
+    pub(crate) fn refresh_checkpoint_preview(&mut self) {
+        let Some(turn) = self.ui.popups.checkpoints.get_selected().map(|c| c.turn) else {
+            return;
+        };
+        let (Some(checkpoints), Some(session_id)) =
+            (&self.services.checkpoints, &self.runtime.current_session_id)
+        else {
+            return;
+        };
+        match checkpoints.preview_restore(session_id, turn) {
+            Ok(preview) => self.ui.popups.checkpoints.set_preview(preview),
+            Err(e) => self
+                .ui
+                .popups
+                .checkpoints
+                .set_error(format!("Failed to preview checkpoint: {}", e)),
+        }
+    }
+
+    /// Restore files to the selected checkpoint
+    pub(crate) fn restore_selected_checkpoint(&mut self) {
+        if self.is_busy() {
+            self.ui
+                .popups
+                .checkpoints
+                .set_error("Wait for the agent to finish before restoring".to_string());
+            return;
+        }
+        let Some(checkpoint) = self.ui.popups.checkpoints.get_selected().cloned() else {
+            return;
+        };
+        let (Some(checkpoints), Some(session_id)) = (
+            self.services.checkpoints.clone(),
+            self.runtime.current_session_id.clone(),
+        ) else {
+            return;
+        };
+
+        match checkpoints.restore(&session_id, checkpoint.turn) {
+            Ok(count) => {
+                self.ui.popup = Popup::None;
+                self.runtime.chat.messages.push((
+                    "system".to_string(),
+                    format!(
+                        "Restored {} file{} to before turn #{} ({})",
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        checkpoint.turn,
+                        checkpoint.label.lines().next().unwrap_or_default()
+                    ),
+                ));
+            }
+            Err(e) => self
+                .ui
+                .popups
+                .checkpoints
+                .set_error(format!("Failed to restore: {}", e)),
+        }
+    }
+}
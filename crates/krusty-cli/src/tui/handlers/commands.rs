@@ -12,11 +12,16 @@ impl App {
 
         match command.as_str() {
             "/home" => {
+                self.extract_session_memories();
+                self.runtime.memory_baseline = 0;
                 self.runtime.current_session_id = None;
                 self.runtime.chat.messages.clear();
                 self.runtime.chat.streaming_assistant_idx = None;
                 self.runtime.chat.conversation.clear();
                 self.clear_plan();
+                self.clear_session_permissions();
+                self.end_checkpoint_turn();
+                self.sync_cost_session();
                 self.ui.view = View::StartMenu;
             }
             "/load" => {
@@ -24,16 +29,10 @@ impl App {
                 let current_dir = self.runtime.working_dir.to_string_lossy().into_owned();
                 self.ui.popups.session.set_current_directory(&current_dir);
 
-                // Get sessions for current directory only
-                let sessions: Vec<_> = self
-                    .list_sessions_for_directory(&current_dir)
-                    .into_iter()
-                    .map(|s| crate::tui::popups::session_list::SessionInfo {
-                        id: s.id,
-                        title: s.title,
-                        updated_at: s.updated_at.format("%Y-%m-%d %H:%M").to_string(),
-                    })
-                    .collect();
+                // Get sessions for current directory only, with forks nested
+                let sessions = crate::tui::popups::session_list::session_tree(
+                    self.list_sessions_for_directory(&current_dir),
+                );
 
                 self.ui.popups.session.set_sessions(sessions);
                 self.ui.popup = Popup::SessionList;
@@ -84,6 +83,10 @@ impl App {
                         self.start_opencodezen_fetch();
                     }
                 }
+
+                // Custom providers with discovery are refreshed on every open,
+                // since self-hosted servers change what they serve
+                self.start_custom_models_fetch();
             }
             "/auth" => {
                 self.ui.popups.auth.reset();
@@ -127,9 +130,36 @@ impl App {
             "/hooks" => {
                 self.open_hooks_popup();
             }
+            "/undo" => {
+                self.handle_undo_command();
+            }
+            "/checkpoints" => {
+                self.handle_checkpoints_command();
+            }
+            "/permissions" | "/perms" => {
+                self.handle_permissions_command(&parts[1..]);
+            }
+            "/cost" => {
+                self.handle_cost_command(&parts[1..]);
+            }
+            "/summarize" => {
+                self.handle_summarize_command(&parts[1..]);
+            }
+            "/memory" => {
+                self.handle_memory_command();
+            }
+            "/export" => {
+                self.handle_export_command(&parts[1..]);
+            }
+            "/import" => {
+                self.handle_import_command(&parts[1..]);
+            }
             "/update" => {
                 self.start_update_check();
             }
+            _ if parts
+                .first()
+                .is_some_and(|name| self.try_mcp_prompt_command(name)) => {}
             _ => {
                 self.runtime
                     .chat
@@ -254,7 +284,7 @@ impl App {
         use krusty_core::storage::Database;
         use std::sync::Arc;
 
-        let client = match self.create_ai_client() {
+        let client = match self.create_ai_client_for(crate::ai::cost::UsageSource::Explore) {
             Some(c) => Arc::new(c),
             None => {
                 self.runtime.chat.messages.push((
@@ -269,6 +299,10 @@ impl App {
         let cancellation = self.runtime.cancellation.clone();
         let current_model = self.runtime.current_model.clone();
 
+        // /init rebuilds the index itself; the watcher restarts once it's done
+        self.runtime.index_watcher = None;
+        self.runtime.channels.index_watch = None;
+
         // Cache languages once at /init start (used during polling)
         self.runtime.cached_init_languages = Some(self.detect_project_languages());
 
@@ -0,0 +1,169 @@
+//! Cost and budget handlers
+//!
+//! Checks spend against the budget before each agent request and implements
+//! `/cost`, which opens the breakdown popup or manages budget limits.
+
+use crate::ai::cost::{format_cost, BudgetLimits, BudgetStatus};
+use crate::tui::app::{App, Popup};
+
+impl App {
+    /// Check the budget before sending another request
+    ///
+    /// Soft limits add a warning to the chat; returns false if a hard limit
+    /// has been reached and the agent should stop.
+    pub(crate) fn check_budget(&mut self) -> bool {
+        let Some(tracker) = &self.services.cost_tracker else {
+            return true;
+        };
+        tracker.set_session(self.runtime.current_session_id.as_deref());
+
+        let (message, allowed) = match tracker.check_budget() {
+            BudgetStatus::Ok => return true,
+            BudgetStatus::Warning(msg) => (format!("Warning: {}", msg), true),
+            BudgetStatus::Exceeded(msg) => (
+                format!("{}. Agent stopped; raise the limit with /cost budget.", msg),
+                false,
+            ),
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+        allowed
+    }
+
+    /// Attribute costs to the current session (call after switching sessions)
+    pub(crate) fn sync_cost_session(&self) {
+        if let Some(tracker) = &self.services.cost_tracker {
+            tracker.set_session(self.runtime.current_session_id.as_deref());
+        }
+    }
+
+    /// Known cost of the current session, once anything has been spent
+    pub(crate) fn session_cost(&self) -> Option<f64> {
+        self.services
+            .cost_tracker
+            .as_ref()
+            .map(|t| t.session_cost())
+            .filter(|cost| *cost > 0.0)
+    }
+
+    /// Handle /cost command
+    ///
+    /// - `/cost` opens the breakdown for this session and today
+    /// - `/cost budget` shows the limits
+    /// - `/cost budget <session|daily> <soft|hard> <amount|off>`
+    pub(crate) fn handle_cost_command(&mut self, args: &[&str]) {
+        let message = match args.first().copied() {
+            None => {
+                self.open_cost_popup();
+                return;
+            }
+            Some("budget") => match args.get(1..) {
+                Some([]) | None => self.format_budget_limits(),
+                Some([scope, kind, amount]) => self.set_budget_limit(scope, kind, amount),
+                Some(_) => {
+                    "Usage: /cost budget <session|daily> <soft|hard> <amount|off>".to_string()
+                }
+            },
+            Some(other) => format!(
+                "Unknown cost subcommand '{}'. Use /cost or /cost budget.",
+                other
+            ),
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+    }
+
+    /// Open the cost popup with fresh numbers
+    fn open_cost_popup(&mut self) {
+        let Some(tracker) = &self.services.cost_tracker else {
+            self.runtime.chat.messages.push((
+                "system".to_string(),
+                "Cost tracking is unavailable (no database)".to_string(),
+            ));
+            return;
+        };
+        tracker.set_session(self.runtime.current_session_id.as_deref());
+
+        let loaded = tracker
+            .session_breakdown()
+            .and_then(|session| Ok((session, tracker.day_breakdown()?, tracker.day_cost()?)));
+        match loaded {
+            Ok((session, today, day_cost)) => {
+                self.ui.popups.cost.open(
+                    session,
+                    today,
+                    tracker.session_cost(),
+                    day_cost,
+                    tracker.limits(),
+                );
+                self.ui.popup = Popup::Cost;
+            }
+            Err(e) => self
+                .runtime
+                .chat
+                .messages
+                .push(("system".to_string(), format!("Failed to load costs: {}", e))),
+        }
+    }
+
+    fn format_budget_limits(&self) -> String {
+        let limits = self
+            .services
+            .cost_tracker
+            .as_ref()
+            .map(|t| t.limits())
+            .unwrap_or_default();
+        let show = |limit: Option<f64>| limit.map_or("off".to_string(), format_cost);
+        format!(
+            "Budget limits:\n  Session: soft {}, hard {}\n  Daily:   soft {}, hard {}\n\n\
+             Soft limits warn once; hard limits stop the agent.\n\
+             Set with: /cost budget <session|daily> <soft|hard> <amount|off>",
+            show(limits.session_soft),
+            show(limits.session_hard),
+            show(limits.daily_soft),
+            show(limits.daily_hard),
+        )
+    }
+
+    /// Parse and save one limit from command arguments
+    fn set_budget_limit(&mut self, scope: &str, kind: &str, amount: &str) -> String {
+        let Some(tracker) = &self.services.cost_tracker else {
+            return "Cost tracking is unavailable (no database)".to_string();
+        };
+
+        let value = if amount.eq_ignore_ascii_case("off") {
+            None
+        } else {
+            match amount.trim_start_matches('$').parse::<f64>() {
+                Ok(v) if v > 0.0 && v.is_finite() => Some(v),
+                _ => return format!("Invalid amount '{}'. Use a dollar amount or 'off'.", amount),
+            }
+        };
+
+        let mut limits: BudgetLimits = tracker.limits();
+        let slot = match (scope, kind) {
+            ("session", "soft") => &mut limits.session_soft,
+            ("session", "hard") => &mut limits.session_hard,
+            ("daily" | "day", "soft") => &mut limits.daily_soft,
+            ("daily" | "day", "hard") => &mut limits.daily_hard,
+            _ => return "Usage: /cost budget <session|daily> <soft|hard> <amount|off>".to_string(),
+        };
+        *slot = value;
+
+        if let Some(prefs) = &self.services.preferences {
+            if let Err(e) = prefs.set_budget_limits(&limits) {
+                tracing::warn!("Failed to save budget limits: {}", e);
+            }
+        }
+        tracker.set_limits(limits);
+
+        match value {
+            Some(v) => format!("Budget: {} {} limit set to {}", scope, kind, format_cost(v)),
+            None => format!("Budget: {} {} limit removed", scope, kind),
+        }
+    }
+}
@@ -46,7 +46,11 @@ impl App {
 
     /// Poll explore progress channel and update ExploreBlock with agent progress
     pub(crate) fn poll_explore_progress(&mut self) -> PollResult {
-        poll_explore_progress(&mut self.runtime.channels, &mut self.runtime.blocks.explore)
+        poll_explore_progress(
+            &mut self.runtime.channels,
+            &mut self.runtime.blocks.explore,
+            &self.runtime.event_bus,
+        )
     }
 
     /// Poll build progress channel and update BuildBlock with builder progress
@@ -56,6 +60,7 @@ impl App {
             &mut self.runtime.blocks.build,
             &mut self.runtime.active_plan,
             &self.services.plan_manager,
+            &self.runtime.event_bus,
         )
     }
 
@@ -141,6 +146,12 @@ impl App {
                 PollAction::SwitchProvider(provider) => {
                     self.switch_provider(provider);
                 }
+                PollAction::SubmitInput(text) => {
+                    self.handle_input_submit(text);
+                }
+                PollAction::StartIndexWatcher => {
+                    self.start_index_watcher();
+                }
                 PollAction::StoreInitInsights {
                     architecture,
                     conventions,
@@ -158,6 +169,85 @@ impl App {
         }
     }
 
+    /// Start the background index watcher for the working directory
+    ///
+    /// Only runs when the codebase has been indexed before (via /init) and
+    /// the `index_watch` preference is enabled.
+    pub(crate) fn start_index_watcher(&mut self) {
+        use krusty_core::index::{CodebaseStore, IndexWatcher, DEFAULT_WATCH_INTERVAL};
+
+        if self.runtime.index_watcher.is_some() {
+            return;
+        }
+        let enabled = self
+            .services
+            .preferences
+            .as_ref()
+            .map(|p| p.get_index_watch())
+            .unwrap_or(true);
+        if !enabled {
+            return;
+        }
+        let Some(sm) = &self.services.session_manager else {
+            return;
+        };
+
+        let working_dir_str = self.runtime.working_dir.to_string_lossy().to_string();
+        match CodebaseStore::new(sm.db().conn()).get_by_path(&working_dir_str) {
+            Ok(Some(codebase)) if codebase.indexed_at.is_some() => {}
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to look up codebase for index watcher");
+                return;
+            }
+        }
+
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        match IndexWatcher::start(
+            crate::paths::config_dir().join("krusty.db"),
+            self.runtime.working_dir.clone(),
+            DEFAULT_WATCH_INTERVAL,
+            Some(tx),
+        ) {
+            Ok(watcher) => {
+                self.runtime.index_watcher = Some(watcher);
+                self.runtime.channels.index_watch = Some(rx);
+            }
+            Err(e) => tracing::warn!(error = %e, "Failed to start index watcher"),
+        }
+    }
+
+    /// Drain index watcher updates, surfacing large re-indexes as a toast
+    ///
+    /// Small edits are re-indexed silently; a branch switch or similar bulk
+    /// change gets a notification once it completes.
+    pub(crate) fn poll_index_watch(&mut self) {
+        use krusty_core::index::IndexPhase;
+
+        const NOTIFY_MIN_FILES: usize = 20;
+
+        let Some(rx) = self.runtime.channels.index_watch.as_mut() else {
+            return;
+        };
+
+        let mut reindexed_files = None;
+        let mut changed_files = 0;
+        while let Ok(progress) = rx.try_recv() {
+            match progress.phase {
+                IndexPhase::Parsing => changed_files = progress.total,
+                IndexPhase::Complete => reindexed_files = Some(changed_files),
+                _ => {}
+            }
+        }
+
+        if let Some(files) = reindexed_files.filter(|&n| n >= NOTIFY_MIN_FILES) {
+            self.show_toast(crate::tui::components::Toast::success(format!(
+                "Re-indexed {} changed files",
+                files
+            )));
+        }
+    }
+
     /// Store /init exploration results as codebase insights
     fn store_init_insights(
         &self,
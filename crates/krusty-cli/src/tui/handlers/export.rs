@@ -0,0 +1,120 @@
+//! Session export and import handlers
+//!
+//! `/export [md|json|html] [path]` writes the current session to a file,
+//! `/import <path>` recreates a session from a JSON bundle and opens it.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+
+use crate::export::{default_file_name, import_file, render, ExportFormat};
+use crate::tui::app::{App, View};
+
+impl App {
+    /// Handle /export command
+    ///
+    /// The format defaults to Markdown and the file to one named after the
+    /// session in the working directory.
+    pub(crate) fn handle_export_command(&mut self, args: &[&str]) {
+        let (format, rest) = match args.first().and_then(|a| ExportFormat::parse(a)) {
+            Some(format) => (format, &args[1..]),
+            None => (ExportFormat::Markdown, args),
+        };
+        let path = (!rest.is_empty()).then(|| self.resolve_path(&rest.join(" ")));
+
+        let message = match self.export_current_session(format, path) {
+            Ok(path) => format!("Exported session to {}", path.display()),
+            Err(e) => format!("Export failed: {}", e),
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+    }
+
+    /// Handle /import command
+    pub(crate) fn handle_import_command(&mut self, args: &[&str]) {
+        if args.is_empty() {
+            self.runtime.chat.messages.push((
+                "system".to_string(),
+                "Usage: /import <bundle.json>".to_string(),
+            ));
+            return;
+        }
+        let path = self.resolve_path(&args.join(" "));
+
+        let imported = self
+            .services
+            .session_manager
+            .as_ref()
+            .context("No session manager")
+            .and_then(|sm| import_file(sm, &path, &self.runtime.working_dir));
+        let session_id = match imported {
+            Ok(id) => id,
+            Err(e) => {
+                self.runtime
+                    .chat
+                    .messages
+                    .push(("system".to_string(), format!("Import failed: {}", e)));
+                return;
+            }
+        };
+
+        self.save_block_ui_states();
+        match self.load_session(&session_id) {
+            Ok(()) => {
+                self.runtime.chat.messages.push((
+                    "system".to_string(),
+                    format!("Imported session from {}", path.display()),
+                ));
+                self.ui.pending_view_change = Some(View::Chat);
+            }
+            Err(e) => self.runtime.chat.messages.push((
+                "system".to_string(),
+                format!(
+                    "Imported session {} but failed to open it: {}",
+                    session_id, e
+                ),
+            )),
+        }
+    }
+
+    fn export_current_session(
+        &self,
+        format: ExportFormat,
+        path: Option<PathBuf>,
+    ) -> Result<PathBuf> {
+        let session_id = self
+            .runtime
+            .current_session_id
+            .as_deref()
+            .context("No active session to export")?;
+        let sm = self
+            .services
+            .session_manager
+            .as_ref()
+            .context("No session manager")?;
+
+        // Persist collapsed/expanded state so the bundle matches the screen
+        self.save_block_ui_states();
+        let bundle = sm.export_session(session_id)?;
+        let path = path.unwrap_or_else(|| {
+            self.runtime
+                .working_dir
+                .join(default_file_name(&bundle, format))
+        });
+        std::fs::write(&path, render(&bundle, format)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// Resolve a user-supplied path against the working directory
+    fn resolve_path(&self, path: &str) -> PathBuf {
+        let path = PathBuf::from(path);
+        if path.is_absolute() {
+            path
+        } else {
+            self.runtime.working_dir.join(path)
+        }
+    }
+}
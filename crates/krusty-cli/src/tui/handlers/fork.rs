@@ -0,0 +1,168 @@
+//! Session fork handlers
+//!
+//! "Fork from here" on a user or assistant message starts a new session
+//! with the conversation up to that message, leaving the original intact.
+//! Forking at a user message puts it back in the input so it can be edited
+//! and sent again.
+
+use crate::ai::types::{Content, Role};
+use crate::tui::app::{App, Popup, View};
+use crate::tui::popups::message_actions::MessageAction;
+
+impl App {
+    /// Open the actions popup for the message at `msg_idx` in the chat
+    pub(crate) fn open_message_actions(&mut self, msg_idx: usize) {
+        let Some((role, content)) = self.runtime.chat.messages.get(msg_idx) else {
+            return;
+        };
+        let is_user = role == "user";
+        self.ui
+            .popups
+            .message_actions
+            .open(msg_idx, is_user, content);
+        self.ui.popup = Popup::MessageActions;
+    }
+
+    /// Run the action selected in the message actions popup
+    pub(crate) fn run_selected_message_action(&mut self) {
+        let Some(action) = self.ui.popups.message_actions.get_selected() else {
+            return;
+        };
+        let msg_idx = self.ui.popups.message_actions.msg_idx;
+        self.ui.popup = Popup::None;
+        match action {
+            MessageAction::Fork => self.fork_from_message(msg_idx, false),
+            MessageAction::ForkRestoringFiles => self.fork_from_message(msg_idx, true),
+            MessageAction::RestoreFiles => self.open_checkpoints_for_message(msg_idx),
+        }
+    }
+
+    /// Fork the current session at a chat message
+    ///
+    /// With `restore_files`, files are first restored to how they were at
+    /// that message, the same as "restore files to here".
+    fn fork_from_message(&mut self, msg_idx: usize, restore_files: bool) {
+        if let Err(message) = self.try_fork_from_message(msg_idx, restore_files) {
+            self.runtime
+                .chat
+                .messages
+                .push(("system".to_string(), message));
+        }
+    }
+
+    fn try_fork_from_message(&mut self, msg_idx: usize, restore_files: bool) -> Result<(), String> {
+        if self.is_busy() {
+            return Err("Wait for the agent to finish before forking".to_string());
+        }
+        let source_id = self
+            .runtime
+            .current_session_id
+            .clone()
+            .ok_or("No session to fork")?;
+        let (role, text) = self
+            .runtime
+            .chat
+            .messages
+            .get(msg_idx)
+            .cloned()
+            .ok_or("Message not found")?;
+        let is_user = role == "user";
+        let conversation_idx = self
+            .conversation_index_for_message(msg_idx)
+            .ok_or("Couldn't find this message in the conversation")?;
+        // A user message is left out so it can be sent again
+        let message_count = if is_user {
+            conversation_idx
+        } else {
+            conversation_idx + 1
+        };
+
+        let restored = if restore_files {
+            // Files as they were before this turn, or after it for a response
+            let turn = self.checkpoint_turn_for_message(msg_idx) + usize::from(!is_user);
+            match &self.services.checkpoints {
+                Some(checkpoints) => Some(
+                    checkpoints
+                        .restore(&source_id, turn)
+                        .map_err(|e| format!("Failed to restore files: {}", e))?,
+                ),
+                None => Some(0),
+            }
+        } else {
+            None
+        };
+
+        self.save_block_ui_states();
+        let title = format!(
+            "{} (fork)",
+            self.runtime.session_title.as_deref().unwrap_or("Session")
+        );
+        let fork_id = self
+            .services
+            .session_manager
+            .as_ref()
+            .ok_or("No session manager")?
+            .fork_session(&source_id, message_count, &title)
+            .map_err(|e| format!("Failed to fork session: {}", e))?;
+
+        self.load_session(&fork_id)
+            .map_err(|e| format!("Forked session but failed to open it: {}", e))?;
+        self.ui.pending_view_change = Some(View::Chat);
+        if is_user {
+            self.ui.input.clear();
+            self.ui.input.insert_text(&text);
+        }
+
+        let mut note = "Forked into a new session; the original is kept under /load".to_string();
+        if let Some(count) = restored {
+            note.push_str(&format!(
+                ". Restored {} file{}",
+                count,
+                if count == 1 { "" } else { "s" }
+            ));
+        }
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), note));
+        Ok(())
+    }
+
+    /// Index in the conversation of the message shown at `msg_idx`
+    ///
+    /// Each text block of a user or assistant message is shown as its own
+    /// chat message, so the n-th chat message of a role is the message
+    /// holding that role's n-th text block.
+    fn conversation_index_for_message(&self, msg_idx: usize) -> Option<usize> {
+        let (role, _) = self.runtime.chat.messages.get(msg_idx)?;
+        let target_role = match role.as_str() {
+            "user" => Role::User,
+            "assistant" => Role::Assistant,
+            _ => return None,
+        };
+        let ordinal = self.runtime.chat.messages[..msg_idx]
+            .iter()
+            .filter(|(r, _)| r == role)
+            .count();
+
+        let mut seen = 0;
+        for (idx, message) in self.runtime.chat.conversation.iter().enumerate() {
+            if message.role != target_role {
+                continue;
+            }
+            // Filler "." messages aren't shown
+            let texts = match message.content.as_slice() {
+                [Content::Text { text }] if text == "." => 0,
+                content => content
+                    .iter()
+                    .filter(|c| matches!(c, Content::Text { .. }))
+                    .count(),
+            };
+            if ordinal < seen + texts {
+                return Some(idx);
+            }
+            seen += texts;
+        }
+        None
+    }
+}
@@ -153,77 +153,7 @@ impl App {
 
         for (role, content) in &self.runtime.chat.messages {
             if let Some((block_type, idx)) = indices.get_and_increment(role) {
-                // Get block height based on type
-                let height = match block_type {
-                    BlockType::Thinking => self
-                        .runtime
-                        .blocks
-                        .thinking
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::Bash => self
-                        .runtime
-                        .blocks
-                        .bash
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::TerminalPane => {
-                        // Skip pinned terminal - handled separately at top
-                        if self.runtime.blocks.pinned_terminal == Some(idx) {
-                            None
-                        } else {
-                            self.runtime
-                                .blocks
-                                .terminal
-                                .get(idx)
-                                .map(|b| b.height(content_width, &self.ui.theme))
-                        }
-                    }
-                    BlockType::ToolResult => self
-                        .runtime
-                        .blocks
-                        .tool_result
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::Read => self
-                        .runtime
-                        .blocks
-                        .read
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::Edit => self
-                        .runtime
-                        .blocks
-                        .edit
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::Write => self
-                        .runtime
-                        .blocks
-                        .write
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::WebSearch => self
-                        .runtime
-                        .blocks
-                        .web_search
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::Explore => self
-                        .runtime
-                        .blocks
-                        .explore
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                    BlockType::Build => self
-                        .runtime
-                        .blocks
-                        .build
-                        .get(idx)
-                        .map(|b| b.height(content_width, &self.ui.theme)),
-                };
-
-                if let Some(height) = height {
+                if let Some(height) = self.message_block_height(block_type, idx, content_width) {
                     if let Some(result) = check_hit(current_line, height, idx, block_type) {
                         return Some(result);
                     }
@@ -249,6 +179,117 @@ impl App {
         None
     }
 
+    /// Rendered height of the block shown for a message entry
+    fn message_block_height(
+        &self,
+        block_type: BlockType,
+        idx: usize,
+        content_width: u16,
+    ) -> Option<u16> {
+        match block_type {
+            BlockType::Thinking => self
+                .runtime
+                .blocks
+                .thinking
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::Bash => self
+                .runtime
+                .blocks
+                .bash
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::TerminalPane => {
+                // Skip pinned terminal - handled separately at top
+                if self.runtime.blocks.pinned_terminal == Some(idx) {
+                    None
+                } else {
+                    self.runtime
+                        .blocks
+                        .terminal
+                        .get(idx)
+                        .map(|b| b.height(content_width, &self.ui.theme))
+                }
+            }
+            BlockType::ToolResult => self
+                .runtime
+                .blocks
+                .tool_result
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::Read => self
+                .runtime
+                .blocks
+                .read
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::Edit => self
+                .runtime
+                .blocks
+                .edit
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::Write => self
+                .runtime
+                .blocks
+                .write
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::WebSearch => self
+                .runtime
+                .blocks
+                .web_search
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::Explore => self
+                .runtime
+                .blocks
+                .explore
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+            BlockType::Build => self
+                .runtime
+                .blocks
+                .build
+                .get(idx)
+                .map(|b| b.height(content_width, &self.ui.theme)),
+        }
+    }
+
+    /// Index in `chat.messages` of the user or assistant message at screen coordinates
+    pub fn hit_test_chat_message(&self, screen_x: u16, screen_y: u16) -> Option<usize> {
+        let (line_index, _) = self.hit_test_messages(screen_x, screen_y)?;
+        let area = self.ui.scroll_system.layout.messages_area?;
+        // Same widths as hit_test_any_block()
+        let content_width = area.width.saturating_sub(2).saturating_sub(4);
+        let wrap_width = content_width.saturating_sub(2) as usize;
+
+        let mut indices = BlockIndices::new();
+        let mut current_line = 0usize;
+        for (msg_idx, (role, content)) in self.runtime.chat.messages.iter().enumerate() {
+            let height = if let Some((block_type, idx)) = indices.get_and_increment(role) {
+                match self.message_block_height(block_type, idx, content_width) {
+                    Some(height) => height as usize + 1,
+                    None => continue,
+                }
+            } else if role == "assistant" {
+                self.get_markdown_line_count(content, wrap_width) + 1
+            } else {
+                content
+                    .lines()
+                    .map(|line| count_wrapped_lines(line, wrap_width).max(1))
+                    .sum::<usize>()
+                    + 1
+            };
+
+            if line_index < current_line + height {
+                return (role == "user" || role == "assistant").then_some(msg_idx);
+            }
+            current_line += height;
+        }
+        None
+    }
+
     /// Convert screen coordinates to input text position (line, column)
     /// Returns None if click is outside content
     pub fn hit_test_input(&self, screen_x: u16, screen_y: u16) -> Option<(usize, usize)> {
@@ -6,6 +6,7 @@ use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 
 use crate::agent::{AgentEvent, InterruptReason};
 use crate::tui::app::{App, Popup, View};
+use crate::tui::components::PromptType;
 use crate::tui::input::InputAction;
 use crate::tui::utils::TitleAction;
 
@@ -113,7 +114,8 @@ impl App {
                 // Only plain Enter selects autocomplete - Shift+Enter should insert newline
                 KeyCode::Enter if modifiers.is_empty() => {
                     if let Some(cmd) = self.ui.autocomplete.get_selected() {
-                        self.handle_slash_command(cmd.primary);
+                        let command = cmd.primary.to_string();
+                        self.handle_slash_command(&command);
                         self.ui.input.clear();
                         self.ui.autocomplete.hide();
                     }
@@ -461,6 +463,9 @@ impl App {
             } else if self.ui.autocomplete.visible {
                 self.ui.autocomplete.update(query);
             } else {
+                let prompts =
+                    futures::executor::block_on(self.services.mcp_manager.get_all_prompts());
+                self.ui.autocomplete.set_mcp_prompts(&prompts);
                 self.ui.autocomplete.show(query);
             }
         } else {
@@ -495,6 +500,9 @@ impl App {
             if self.ui.file_search.visible {
                 self.ui.file_search.update(query);
             } else {
+                let resources =
+                    futures::executor::block_on(self.services.mcp_manager.get_all_resources());
+                self.ui.file_search.set_mcp_resources(resources);
                 self.ui.file_search.show(query);
             }
         } else {
@@ -540,8 +548,12 @@ impl App {
         }
 
         match code {
-            // Number keys select option directly
-            KeyCode::Char(c) if c.is_ascii_digit() && c != '0' => {
+            // Number keys select option directly (argument values may be numbers)
+            KeyCode::Char(c)
+                if c.is_ascii_digit()
+                    && c != '0'
+                    && self.ui.decision_prompt.prompt_type != PromptType::McpPromptArguments =>
+            {
                 let num = (c as usize) - ('0' as usize);
                 if self.ui.decision_prompt.select_by_number(num) {
                     let all_done = self.ui.decision_prompt.confirm_selection();
@@ -589,9 +601,16 @@ impl App {
             }
             // Escape goes back or dismisses
             KeyCode::Esc => {
-                if !self.ui.decision_prompt.go_back() {
+                if self.ui.decision_prompt.prompt_type == PromptType::ToolPermission {
+                    // Dismissing a permission prompt denies the call
+                    self.ui.decision_prompt.hide();
+                    self.handle_permission_answer(&[]);
+                } else if !self.ui.decision_prompt.go_back() {
                     // No previous question - close the prompt
                     self.ui.decision_prompt.hide();
+                    if self.ui.decision_prompt.prompt_type == PromptType::McpPromptArguments {
+                        self.runtime.pending_mcp_prompt = None;
+                    }
                 }
                 true
             }
@@ -606,8 +625,6 @@ impl App {
 
     /// Handle completion of decision prompt (all questions answered)
     pub(crate) fn handle_decision_prompt_complete(&mut self) {
-        use crate::tui::components::PromptType;
-
         let prompt_type = self.ui.decision_prompt.prompt_type.clone();
         let answers = self.ui.decision_prompt.answers.clone();
         let tool_use_id = self.ui.decision_prompt.tool_use_id.clone();
@@ -623,6 +640,12 @@ impl App {
                     self.handle_ask_user_answer(id, &answers);
                 }
             }
+            PromptType::ToolPermission => {
+                self.handle_permission_answer(&answers);
+            }
+            PromptType::McpPromptArguments => {
+                self.handle_mcp_prompt_arguments(&answers);
+            }
         }
     }
 
@@ -0,0 +1,48 @@
+//! User hooks for prompt, notification and lifecycle events
+//!
+//! Tool hooks run on the tool registry; these fire from the TUI itself.
+//! Most run in the background. UserPromptSubmit and PrePinch run before
+//! the action they guard so a hook can block it.
+
+use crate::agent::{HookEvent, HookEventOutcome, UserHookExecutor, UserHookType};
+use crate::tui::app::App;
+
+impl App {
+    /// Whether any enabled hook of this type is configured
+    fn has_user_hooks(&self, hook_type: UserHookType) -> bool {
+        self.services
+            .user_hook_manager
+            .try_read()
+            .is_ok_and(|manager| manager.has_enabled(hook_type))
+    }
+
+    /// Fire hooks for an event in the background, ignoring their output
+    pub(crate) fn fire_user_hooks(&self, event: HookEvent) {
+        if !self.has_user_hooks(event.hook_type) {
+            return;
+        }
+        let manager = self.services.user_hook_manager.clone();
+        tokio::spawn(async move {
+            UserHookExecutor::fire(&manager, &event).await;
+        });
+    }
+
+    /// Run hooks for an event and wait for their outcome
+    pub(crate) fn run_user_hooks(&self, event: HookEvent) -> HookEventOutcome {
+        if !self.has_user_hooks(event.hook_type) {
+            return HookEventOutcome::default();
+        }
+        let manager = self.services.user_hook_manager.clone();
+        futures::executor::block_on(async move { UserHookExecutor::fire(&manager, &event).await })
+    }
+
+    /// Show hook warnings in the chat
+    pub(crate) fn show_hook_warnings(&mut self, outcome: &HookEventOutcome) {
+        for warning in &outcome.warnings {
+            self.runtime
+                .chat
+                .messages
+                .push(("system".to_string(), format!("Hook warning: {}", warning)));
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! MCP prompt slash commands
+//!
+//! Prompts exposed by connected MCP servers run as `/server:prompt`. Their
+//! arguments are collected through the decision prompt, then the expanded
+//! prompt is submitted as if the user had typed it.
+
+use std::collections::HashMap;
+
+use tokio::sync::oneshot;
+
+use crate::tui::app::App;
+use crate::tui::components::{PromptAnswer, PromptOption, PromptQuestion};
+
+impl App {
+    /// Run `/server:prompt` if it names a prompt from a connected MCP server
+    ///
+    /// Returns false when no such prompt exists.
+    pub(crate) fn try_mcp_prompt_command(&mut self, command: &str) -> bool {
+        let Some((server, name)) = command.trim_start_matches('/').split_once(':') else {
+            return false;
+        };
+        let prompts = futures::executor::block_on(self.services.mcp_manager.get_all_prompts());
+        let Some((server, prompt)) = prompts
+            .into_iter()
+            .find(|(s, p)| s == server && p.name.eq_ignore_ascii_case(name))
+        else {
+            return false;
+        };
+
+        if prompt.arguments.is_empty() {
+            self.run_mcp_prompt(server, prompt.name, HashMap::new());
+            return true;
+        }
+
+        let header = format!("/{}:{}", server, prompt.name);
+        let questions = prompt
+            .arguments
+            .iter()
+            .map(|arg| {
+                let mut question = arg.name.clone();
+                if let Some(ref description) = arg.description {
+                    question.push_str(" - ");
+                    question.push_str(description);
+                }
+                let question = PromptQuestion::new(header.clone(), question);
+                if arg.required {
+                    question
+                } else {
+                    question.add_option(
+                        PromptOption::new("Skip").with_description("Leave this argument unset"),
+                    )
+                }
+            })
+            .collect();
+        self.ui.decision_prompt.show_mcp_prompt_arguments(questions);
+        self.runtime.pending_mcp_prompt = Some((server, prompt));
+        true
+    }
+
+    /// Fill in the pending MCP prompt's arguments and run it
+    pub(crate) fn handle_mcp_prompt_arguments(&mut self, answers: &[PromptAnswer]) {
+        let Some((server, prompt)) = self.runtime.pending_mcp_prompt.take() else {
+            return;
+        };
+
+        let mut arguments = HashMap::new();
+        for (arg, answer) in prompt.arguments.iter().zip(answers) {
+            match answer {
+                PromptAnswer::Custom(value) if !value.trim().is_empty() => {
+                    arguments.insert(arg.name.clone(), value.trim().to_string());
+                }
+                _ if arg.required => {
+                    self.runtime.chat.messages.push((
+                        "system".to_string(),
+                        format!(
+                            "/{}:{} requires a value for '{}'",
+                            server, prompt.name, arg.name
+                        ),
+                    ));
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        self.run_mcp_prompt(server, prompt.name, arguments);
+    }
+
+    /// Fetch the expanded prompt in the background
+    fn run_mcp_prompt(&mut self, server: String, name: String, arguments: HashMap<String, String>) {
+        let mcp = self.services.mcp_manager.clone();
+        let (tx, rx) = oneshot::channel();
+        self.runtime.channels.mcp_prompt = Some(rx);
+
+        tokio::spawn(async move {
+            let result = match mcp.get_prompt(&server, &name, arguments).await {
+                Ok(result) if result.text().trim().is_empty() => {
+                    Err(format!("/{}:{} returned no text", server, name))
+                }
+                Ok(result) => Ok(result.text()),
+                Err(e) => Err(format!("/{}:{} failed: {}", server, name, e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+}
@@ -0,0 +1,160 @@
+//! Session memory handlers
+//!
+//! Pinch and leaving a session record what was learned about the codebase
+//! as session memories; a background pass promotes them into insights.
+//! `/memory` reviews, edits, pins or deletes those insights.
+
+use std::path::PathBuf;
+
+use krusty_core::index::{
+    promote_memories, summary_model, CodebaseInsight, CodebaseStore, EmbeddingEngine, InsightStore,
+    Learning, SessionMemoryStore,
+};
+use krusty_core::storage::Database;
+
+use crate::agent::extract_learnings;
+use crate::ai::cost::UsageSource;
+use crate::tui::app::{App, Popup};
+use crate::tui::handlers::streaming::INSIGHTS_IN_CONTEXT;
+
+/// Fewest new messages worth extracting learnings from
+const MIN_NEW_MESSAGES: usize = 4;
+
+impl App {
+    /// Handle /memory command - open the insights popup
+    pub(crate) fn handle_memory_command(&mut self) {
+        match self.load_insights() {
+            Ok(insights) => {
+                self.ui.popups.memory.open(insights, INSIGHTS_IN_CONTEXT);
+                self.ui.popup = Popup::Memory;
+            }
+            Err(e) => self.runtime.chat.messages.push(("system".to_string(), e)),
+        }
+    }
+
+    /// Insights for the working directory, in context order
+    fn load_insights(&self) -> Result<Vec<CodebaseInsight>, String> {
+        let Some(sm) = &self.services.session_manager else {
+            return Err("Memory needs the database, which is unavailable".to_string());
+        };
+        let conn = sm.db().conn();
+        let working_dir = self.runtime.working_dir.to_string_lossy().to_string();
+        let codebase = match CodebaseStore::new(conn).get_by_path(&working_dir) {
+            Ok(Some(codebase)) => codebase,
+            Ok(None) => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to look up codebase: {}", e)),
+        };
+        InsightStore::new(conn)
+            .get_by_codebase(&codebase.id)
+            .map_err(|e| format!("Failed to load insights: {}", e))
+    }
+
+    /// Apply a change to the insights and refresh the popup
+    pub(crate) fn update_insights(
+        &mut self,
+        change: impl FnOnce(&InsightStore) -> anyhow::Result<()>,
+    ) {
+        let Some(sm) = &self.services.session_manager else {
+            return;
+        };
+        if let Err(e) = change(&InsightStore::new(sm.db().conn())) {
+            tracing::warn!("Failed to update insight: {}", e);
+        }
+        match self.load_insights() {
+            Ok(insights) => self.ui.popups.memory.set_insights(insights),
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    /// Record the learnings of a pinch summary for the session being pinched
+    pub(crate) fn record_pinch_learnings(&mut self, learnings: &[Learning]) {
+        let Some(session_id) = self.runtime.current_session_id.clone() else {
+            return;
+        };
+        self.runtime.memory_baseline = self.runtime.chat.conversation.len();
+        remember_learnings(
+            self.runtime.working_dir.clone(),
+            session_id,
+            learnings.to_vec(),
+        );
+    }
+
+    /// Extract learnings from the session being left, in the background
+    ///
+    /// Only runs when enough messages were added since the session was
+    /// loaded or last pinched.
+    pub(crate) fn extract_session_memories(&mut self) {
+        let Some(session_id) = self.runtime.current_session_id.clone() else {
+            return;
+        };
+        let len = self.runtime.chat.conversation.len();
+        if len < self.runtime.memory_baseline + MIN_NEW_MESSAGES {
+            return;
+        }
+        self.runtime.memory_baseline = len;
+
+        let Some(client) = self.create_ai_client_for(UsageSource::Memory) else {
+            return;
+        };
+        let model = summary_model(self.runtime.active_provider, &self.runtime.current_model);
+        let conversation = self.runtime.chat.conversation.clone();
+        let working_dir = self.runtime.working_dir.clone();
+
+        tokio::spawn(async move {
+            match extract_learnings(&client, &model, &conversation).await {
+                Ok(learnings) => remember_learnings(working_dir, session_id, learnings),
+                Err(e) => tracing::warn!("Failed to extract session learnings: {}", e),
+            }
+        });
+    }
+}
+
+/// Record learnings as session memories and promote them into insights
+fn remember_learnings(working_dir: PathBuf, session_id: String, learnings: Vec<Learning>) {
+    if learnings.is_empty() {
+        return;
+    }
+
+    // Opens its own connection: rusqlite::Connection is !Send
+    let db_path = crate::paths::config_dir().join("krusty.db");
+    tokio::task::spawn_blocking(move || {
+        let result = (|| -> anyhow::Result<_> {
+            let db = Database::new(&db_path)?;
+            let conn = db.conn();
+            let recorded = SessionMemoryStore::new(conn).record(&session_id, &learnings)?;
+            if recorded == 0 {
+                return Ok(None);
+            }
+
+            let codebase = CodebaseStore::new(conn).get_or_create(&working_dir)?;
+            // Only load the embedding model where indexing already has
+            let engine = match codebase.indexed_at {
+                Some(_) => EmbeddingEngine::new()
+                    .inspect_err(|e| {
+                        tracing::info!("Embeddings unavailable ({e}), deduping by words")
+                    })
+                    .ok(),
+                None => None,
+            };
+            let report = tokio::runtime::Handle::current().block_on(promote_memories(
+                conn,
+                &codebase.id,
+                &session_id,
+                engine.as_ref(),
+            ))?;
+            Ok(Some((recorded, report)))
+        })();
+
+        match result {
+            Ok(Some((recorded, report))) => tracing::info!(
+                "Recorded {} learnings for session {}: {} new insights, {} reinforced",
+                recorded,
+                session_id,
+                report.created,
+                report.reinforced
+            ),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to record session learnings: {}", e),
+        }
+    });
+}
@@ -2,21 +2,32 @@
 //!
 //! All event handling logic extracted from app.rs for better organization.
 
+pub mod build;
+pub mod checkpoints;
 pub mod commands;
+pub mod cost;
 pub mod event_loop;
+pub mod export;
+pub mod fork;
 pub mod hit_test;
 pub mod keyboard;
+pub mod lifecycle_hooks;
+pub mod mcp_prompts;
+pub mod memory;
 pub mod models;
 pub mod mouse;
+pub mod permissions;
 pub mod pinch;
 pub mod popup_keys;
 pub mod provider;
 pub mod rendering;
+pub mod replay;
 pub mod scrollbar;
 pub mod selection;
 pub mod sessions;
 pub mod stream_events;
 pub mod streaming;
+pub mod summaries;
 pub mod terminal;
 pub mod themes;
 pub mod update;
@@ -1,8 +1,10 @@
 //! Model fetching handlers
 //!
-//! Async model fetching from dynamic providers (OpenRouter, OpenCode Zen).
+//! Async model fetching from dynamic providers (OpenRouter, OpenCode Zen,
+//! custom providers with model discovery).
 
-use crate::ai::providers::ProviderId;
+use crate::ai::custom_providers;
+use crate::ai::providers::{get_provider, ProviderId};
 use crate::tui::app::App;
 
 impl App {
@@ -96,6 +98,88 @@ impl App {
         });
     }
 
+    /// Start async model discovery for configured custom providers
+    ///
+    /// Covers every custom provider with `discover_models` enabled and
+    /// credentials (or no need for them).
+    pub fn start_custom_models_fetch(&mut self) {
+        if self.runtime.channels.custom_models.is_some() {
+            return;
+        }
+
+        let providers: Vec<_> = self
+            .configured_providers()
+            .into_iter()
+            .filter(|p| p.is_custom())
+            .filter_map(|p| {
+                let config = get_provider(p).filter(|c| c.dynamic_models)?;
+                let api_key = self.services.credential_store.api_key(&p)?;
+                Some((config, api_key))
+            })
+            .collect();
+        if providers.is_empty() {
+            return;
+        }
+
+        self.ui.popups.model.set_loading(true);
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.runtime.channels.custom_models = Some(rx);
+
+        let registry = self.services.model_registry.clone();
+
+        tokio::spawn(async move {
+            let mut total = 0;
+            let mut errors = Vec::new();
+            for (config, api_key) in providers {
+                match custom_providers::fetch_models(config, &api_key).await {
+                    Ok(models) => {
+                        total += models.len();
+                        registry.set_models(config.id, models).await;
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to fetch {} models: {}", config.name, e);
+                        errors.push(e.to_string());
+                    }
+                }
+            }
+
+            let result = if errors.is_empty() {
+                Ok(total)
+            } else {
+                Err(errors.join("; "))
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    /// Poll for custom provider model discovery completion
+    pub fn poll_custom_models_fetch(&mut self) {
+        if let Some(rx) = &mut self.runtime.channels.custom_models {
+            match rx.try_recv() {
+                Ok(result) => {
+                    // Partial failures still refresh with what was found
+                    self.refresh_model_popup();
+                    match result {
+                        Ok(count) => {
+                            tracing::info!("Custom provider models loaded: {} models", count);
+                        }
+                        Err(e) => self.ui.popups.model.set_error(e),
+                    }
+                    self.runtime.channels.custom_models = None;
+                }
+                Err(tokio::sync::oneshot::error::TryRecvError::Empty) => {}
+                Err(tokio::sync::oneshot::error::TryRecvError::Closed) => {
+                    self.ui
+                        .popups
+                        .model
+                        .set_error("Fetch task closed unexpectedly".to_string());
+                    self.runtime.channels.custom_models = None;
+                }
+            }
+        }
+    }
+
     /// Poll for OpenRouter model fetch completion
     pub fn poll_openrouter_fetch(&mut self) {
         if let Some(rx) = &mut self.runtime.channels.openrouter_models {
@@ -62,6 +62,12 @@ impl App {
             MouseEventKind::Down(MouseButton::Left) => {
                 self.handle_left_click(mouse);
             }
+            MouseEventKind::Down(MouseButton::Right) => {
+                // Right-click a message to fork from it or restore files
+                if let Some(msg_idx) = self.hit_test_chat_message(mouse.column, mouse.row) {
+                    self.open_message_actions(msg_idx);
+                }
+            }
             MouseEventKind::Drag(MouseButton::Left) => {
                 self.handle_drag(mouse.column, mouse.row);
             }
@@ -626,8 +632,17 @@ impl App {
                     }
                 }
             }
-            BlockType::Explore | BlockType::Build => {
-                // These blocks don't have click interaction yet
+            BlockType::Build => {
+                if let Some(block) = self.runtime.blocks.build.get_mut(idx) {
+                    let result = block.handle_event(&event, block_area, clip);
+                    if let EventResult::Action(BlockEvent::MergeBranch { branch, accept }) = result
+                    {
+                        self.resolve_build_branch(idx, &branch, accept);
+                    }
+                }
+            }
+            BlockType::Explore => {
+                // No click interaction yet
             }
         }
 
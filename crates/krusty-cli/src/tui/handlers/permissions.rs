@@ -0,0 +1,204 @@
+//! Tool permission handlers
+//!
+//! Surfaces "ask" decisions from the permission hook through the decision
+//! prompt, and implements the /permissions command for managing rules.
+
+use crate::agent::permissions::remembered_rule;
+use crate::agent::{
+    HookEvent, PermissionAction, PermissionResponse, PermissionRule, PermissionScope,
+};
+use crate::paths;
+use crate::storage::Database;
+use crate::tui::app::App;
+use crate::tui::components::PromptAnswer;
+
+impl App {
+    /// Show the next pending permission request, if any
+    ///
+    /// Returns true if a prompt was opened.
+    pub(crate) fn poll_permission_requests(&mut self) -> bool {
+        // One prompt at a time; other prompts take precedence
+        if self.runtime.pending_permission.is_some() || self.ui.decision_prompt.visible {
+            return false;
+        }
+
+        let Some(ref mut rx) = self.runtime.channels.permission_requests else {
+            return false;
+        };
+
+        // Skip requests whose tool call was cancelled while queued
+        while let Ok(request) = rx.try_recv() {
+            if request.respond.is_closed() {
+                continue;
+            }
+            self.ui
+                .decision_prompt
+                .show_permission(&request.tool_name, &request.summary);
+            self.fire_user_hooks(HookEvent::notification(
+                self.runtime.current_session_id.as_deref(),
+                &self.runtime.working_dir,
+                "permission_request",
+                &format!(
+                    "{} wants permission: {}",
+                    request.tool_name, request.summary
+                ),
+            ));
+            self.runtime.pending_permission = Some(request);
+            return true;
+        }
+        false
+    }
+
+    /// Resolve the pending permission request from the prompt answers
+    ///
+    /// Empty answers (prompt dismissed) deny the call.
+    pub(crate) fn handle_permission_answer(&mut self, answers: &[PromptAnswer]) {
+        let Some(request) = self.runtime.pending_permission.take() else {
+            return;
+        };
+
+        let response = match answers.first() {
+            Some(PromptAnswer::Selected(0)) => PermissionResponse::Allow,
+            Some(PromptAnswer::Selected(1)) => {
+                let rule =
+                    remembered_rule(&request.tool_name, &request.params, &request.working_dir);
+                let description = rule.describe();
+                self.save_permission_rule(rule);
+                self.runtime.chat.messages.push((
+                    "system".to_string(),
+                    format!("Permission rule added: {}", description),
+                ));
+                PermissionResponse::Allow
+            }
+            Some(PromptAnswer::Custom(text)) => PermissionResponse::Deny {
+                reason: Some(text.clone()),
+            },
+            _ => PermissionResponse::Deny { reason: None },
+        };
+
+        let _ = request.respond.send(response);
+    }
+
+    /// Handle /permissions command
+    ///
+    /// - `/permissions` lists rules for this project
+    /// - `/permissions <allow|ask|deny> <tool> [path-glob|command-prefix] [--session|--project|--global]`
+    /// - `/permissions remove <n>`
+    pub(crate) fn handle_permissions_command(&mut self, args: &[&str]) {
+        let message = match args.first().copied() {
+            None | Some("list") => self.format_permission_rules(),
+            Some("remove") | Some("rm") => self.remove_permission_rule(args.get(1).copied()),
+            Some(action) => match PermissionAction::parse(action) {
+                Some(action) => self.add_permission_rule(action, &args[1..]),
+                None => format!(
+                    "Unknown permissions subcommand '{}'. Use allow, ask, deny, remove or list.",
+                    action
+                ),
+            },
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+    }
+
+    /// Format rules that apply in the current project
+    fn format_permission_rules(&self) -> String {
+        let manager = futures::executor::block_on(self.services.permission_manager.read());
+        let rules = manager.rules_for(&self.runtime.working_dir);
+        if rules.is_empty() {
+            return "No permission rules. All tools run without asking.\n\n\
+                    Add one with: /permissions ask bash git push"
+                .to_string();
+        }
+
+        let mut out = String::from("Permission rules (most restrictive match wins):\n");
+        for (i, rule) in rules.iter().enumerate() {
+            out.push_str(&format!("  {}. {}\n", i + 1, rule.describe()));
+        }
+        out.push_str("\nRemove with: /permissions remove <n>");
+        out
+    }
+
+    /// Parse and add a rule from command arguments
+    fn add_permission_rule(&mut self, action: PermissionAction, args: &[&str]) -> String {
+        let mut scope = PermissionScope::Project;
+        let mut words = Vec::new();
+        for arg in args {
+            match *arg {
+                "--session" => scope = PermissionScope::Session,
+                "--project" => scope = PermissionScope::Project,
+                "--global" => scope = PermissionScope::Global,
+                other => words.push(other),
+            }
+        }
+
+        let Some((tool, pattern)) = words.split_first() else {
+            return "Usage: /permissions <allow|ask|deny> <tool> [path-glob|command-prefix] \
+                    [--session|--project|--global]"
+                .to_string();
+        };
+
+        let mut rule = PermissionRule::new(*tool, action, scope);
+        if scope == PermissionScope::Project {
+            rule = rule.for_project(&self.runtime.working_dir);
+        }
+        if !pattern.is_empty() {
+            let pattern = pattern.join(" ");
+            rule = if matches!(*tool, "bash" | "shell" | "execute") {
+                rule.with_command_prefix(pattern)
+            } else {
+                rule.with_path_glob(pattern)
+            };
+        }
+
+        let description = rule.describe();
+        self.save_permission_rule(rule);
+        format!("Permission rule added: {}", description)
+    }
+
+    /// Remove a rule by its 1-based index in the list
+    fn remove_permission_rule(&mut self, index: Option<&str>) -> String {
+        let Some(n) = index.and_then(|s| s.parse::<usize>().ok()) else {
+            return "Usage: /permissions remove <n>".to_string();
+        };
+
+        let rule = {
+            let manager = futures::executor::block_on(self.services.permission_manager.read());
+            manager
+                .rules_for(&self.runtime.working_dir)
+                .get(n.wrapping_sub(1))
+                .map(|r| (r.id.clone(), r.describe()))
+        };
+        let Some((id, description)) = rule else {
+            return format!("No permission rule #{}", n);
+        };
+
+        let result = Database::new(&paths::config_dir().join("krusty.db")).and_then(|db| {
+            futures::executor::block_on(self.services.permission_manager.write()).delete(&db, &id)
+        });
+        match result {
+            Ok(()) => format!("Removed permission rule: {}", description),
+            Err(e) => format!("Failed to remove permission rule: {}", e),
+        }
+    }
+
+    /// Drop session-scoped rules when leaving a session
+    pub(crate) fn clear_session_permissions(&mut self) {
+        futures::executor::block_on(self.services.permission_manager.write()).clear_session_rules();
+    }
+
+    /// Add a rule to the shared manager, persisting non-session rules
+    fn save_permission_rule(&mut self, rule: PermissionRule) {
+        let mut manager = futures::executor::block_on(self.services.permission_manager.write());
+        if rule.scope == PermissionScope::Session {
+            manager.add_session_rule(rule);
+            return;
+        }
+        let result = Database::new(&paths::config_dir().join("krusty.db"))
+            .and_then(|db| manager.save(&db, rule));
+        if let Err(e) = result {
+            tracing::warn!("Failed to save permission rule: {}", e);
+        }
+    }
+}
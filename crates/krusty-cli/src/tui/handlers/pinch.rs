@@ -8,8 +8,9 @@
 
 use std::path::PathBuf;
 
-use crate::agent::{generate_summary, PinchContext, SummarizationResult};
+use crate::agent::{generate_summary, AgentEvent, HookEvent, PinchContext, SummarizationResult};
 use crate::ai::client::AiClient;
+use crate::ai::cost::UsageSource;
 use crate::storage::{FileActivityTracker, RankedFile};
 use crate::tui::app::App;
 use crate::tui::utils::{SummarizationUpdate, TitleUpdate};
@@ -26,6 +27,17 @@ impl App {
         // Move popup to summarizing state
         self.ui.popups.pinch.start_summarizing();
 
+        if let Some(reason) = self.run_pre_pinch_hooks("manual") {
+            self.ui
+                .popups
+                .pinch
+                .set_error(format!("Pinch blocked by hook: {}", reason));
+            return;
+        }
+        self.runtime.event_bus.emit(AgentEvent::PinchStart {
+            trigger: "manual".to_string(),
+        });
+
         // Get preservation hints from first stage
         let preservation_hints = self
             .ui
@@ -142,7 +154,7 @@ impl App {
 
     /// Create AI client for summarization
     fn create_summarization_client(&self) -> Option<AiClient> {
-        self.create_ai_client()
+        self.create_ai_client_for(UsageSource::Summary)
     }
 
     /// Poll for summarization results
@@ -207,10 +219,32 @@ impl App {
         Vec::new()
     }
 
+    /// Run PrePinch hooks, returning the reason if one blocked the pinch
+    fn run_pre_pinch_hooks(&mut self, trigger: &str) -> Option<String> {
+        let outcome = self.run_user_hooks(HookEvent::pre_pinch(
+            self.runtime.current_session_id.as_deref(),
+            &self.runtime.working_dir,
+            trigger,
+            self.runtime.chat.conversation.len(),
+        ));
+        self.show_hook_warnings(&outcome);
+        outcome.blocked
+    }
+
     /// Start auto-pinch (bypasses popup, used when AI is working autonomously)
     ///
     /// Directly starts summarization without popup interaction.
     pub fn start_auto_pinch(&mut self) {
+        if let Some(reason) = self.run_pre_pinch_hooks("auto") {
+            self.runtime.chat.messages.push((
+                "system".to_string(),
+                format!("Pinch blocked by hook: {}", reason),
+            ));
+            return;
+        }
+        self.runtime.event_bus.emit(AgentEvent::PinchStart {
+            trigger: "auto".to_string(),
+        });
         self.runtime.auto_pinch_in_progress = true;
 
         let ranked_files = self.get_ranked_files_for_summarization();
@@ -313,8 +347,9 @@ impl App {
             .collect();
         let active_plan = self.runtime.active_plan.as_ref().map(|p| p.to_markdown());
 
-        // Extract summary text before consuming summary_result
+        // Extract summary text and learnings before consuming summary_result
         let summary_text = summary_result.work_summary.clone();
+        let learnings = summary_result.learnings.clone();
 
         let pinch_ctx = PinchContext::new(
             self.runtime.current_session_id.clone().unwrap_or_default(),
@@ -361,6 +396,10 @@ impl App {
             Some(&self.runtime.working_dir.to_string_lossy()),
         ) {
             Ok(new_id) => {
+                self.runtime.event_bus.emit(AgentEvent::PinchComplete {
+                    new_session_id: new_id.clone(),
+                });
+
                 // Save pinch context as first message
                 let system_msg = pinch_ctx.to_system_message();
                 if let Err(e) = sm.save_message(&new_id, "system", &system_msg) {
@@ -390,6 +429,7 @@ impl App {
 
                 // Spawn title generation
                 self.spawn_pinch_title_generation(new_id.clone(), parent_title, summary_text, None);
+                self.record_pinch_learnings(&learnings);
 
                 // Load the new session and resume
                 self.save_block_ui_states();
@@ -455,8 +495,11 @@ impl App {
                 key_decisions: Vec::new(),
                 pending_tasks: Vec::new(),
                 important_files: Vec::new(),
+                learnings: Vec::new(),
             });
 
+        let learnings = summary_result.learnings.clone();
+
         // Build pinch context with FULL context for continuation
         let ranked_files = self.get_ranked_files_for_summarization();
 
@@ -524,6 +567,10 @@ impl App {
             Some(&self.runtime.working_dir.to_string_lossy()),
         ) {
             Ok(new_id) => {
+                self.runtime.event_bus.emit(AgentEvent::PinchComplete {
+                    new_session_id: new_id.clone(),
+                });
+
                 // Save pinch context as first message
                 let system_msg = pinch_ctx.to_system_message();
                 if let Err(e) = sm.save_message(&new_id, "system", &system_msg) {
@@ -581,6 +628,7 @@ impl App {
 
                 // Spawn async AI title generation
                 self.spawn_pinch_title_generation(new_id.clone(), parent_title, summary, direction);
+                self.record_pinch_learnings(&learnings);
 
                 // Show completion - auto_continue triggers AI response after switch
                 self.ui
@@ -630,6 +678,6 @@ impl App {
 
     /// Create AI client for pinch title generation
     fn create_pinch_title_client(&self) -> Option<AiClient> {
-        self.create_ai_client()
+        self.create_ai_client_for(UsageSource::Title)
     }
 }
@@ -17,7 +17,9 @@ impl App {
                 KeyCode::Up => self.ui.popups.auth.prev_provider(),
                 KeyCode::Down => self.ui.popups.auth.next_provider(),
                 KeyCode::Enter => {
-                    self.ui.popups.auth.confirm_provider();
+                    if let Some(provider) = self.ui.popups.auth.confirm_provider() {
+                        self.use_keyless_provider(provider);
+                    }
                 }
                 _ => {}
             },
@@ -95,6 +97,9 @@ impl App {
                         if provider == ProviderId::OpenCodeZen {
                             self.start_opencodezen_fetch();
                         }
+                        if provider.is_custom() {
+                            self.start_custom_models_fetch();
+                        }
                     }
                 }
             }
@@ -102,6 +107,16 @@ impl App {
         }
     }
 
+    /// Switch to a custom provider that needs no API key
+    fn use_keyless_provider(&mut self, provider: ProviderId) {
+        self.switch_provider(provider);
+        self.runtime.chat.messages.push((
+            "system".to_string(),
+            format!("Switched to {} (no API key needed)", provider),
+        ));
+        self.start_custom_models_fetch();
+    }
+
     /// Start OAuth flow for a provider
     pub(super) fn start_oauth_flow(&mut self, provider: ProviderId, method: AuthMethod) {
         let status_tx = self.services.oauth_status_tx.clone();
@@ -0,0 +1,28 @@
+//! Checkpoints popup keyboard handler
+
+use crossterm::event::KeyCode;
+
+use crate::tui::app::{App, Popup};
+
+impl App {
+    /// Handle checkpoints popup keyboard events
+    pub fn handle_checkpoints_popup_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.ui.popup = Popup::None,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.ui.popups.checkpoints.prev();
+                self.refresh_checkpoint_preview();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.ui.popups.checkpoints.next();
+                self.refresh_checkpoint_preview();
+            }
+            KeyCode::PageDown | KeyCode::Char('J') => {
+                self.ui.popups.checkpoints.scroll_diff_down(10)
+            }
+            KeyCode::PageUp | KeyCode::Char('K') => self.ui.popups.checkpoints.scroll_diff_up(10),
+            KeyCode::Enter => self.restore_selected_checkpoint(),
+            _ => {}
+        }
+    }
+}
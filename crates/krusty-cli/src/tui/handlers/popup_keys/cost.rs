@@ -0,0 +1,18 @@
+//! Cost popup keyboard handler
+
+use crossterm::event::KeyCode;
+
+use crate::tui::app::{App, Popup};
+
+impl App {
+    /// Handle cost popup keyboard events
+    pub fn handle_cost_popup_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.ui.popup = Popup::None,
+            KeyCode::Tab => self.ui.popups.cost.toggle_scope(),
+            KeyCode::Up | KeyCode::Char('k') => self.ui.popups.cost.scroll_up(),
+            KeyCode::Down | KeyCode::Char('j') => self.ui.popups.cost.scroll_down(),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Memory popup keyboard handler
+
+use crossterm::event::KeyCode;
+
+use crate::tui::app::{App, Popup};
+
+impl App {
+    /// Handle memory popup keyboard events
+    pub fn handle_memory_popup_key(&mut self, code: KeyCode) {
+        if self.ui.popups.memory.is_editing() {
+            match code {
+                KeyCode::Esc => self.ui.popups.memory.cancel_edit(),
+                KeyCode::Enter => self.save_insight_edit(),
+                KeyCode::Backspace => self.ui.popups.memory.backspace(),
+                KeyCode::Char(c) => self.ui.popups.memory.add_char(c),
+                _ => {}
+            }
+            return;
+        }
+
+        match code {
+            KeyCode::Esc => self.ui.popup = Popup::None,
+            KeyCode::Up | KeyCode::Char('k') => self.ui.popups.memory.prev(),
+            KeyCode::Down | KeyCode::Char('j') => self.ui.popups.memory.next(),
+            KeyCode::Enter | KeyCode::Char('e') => self.ui.popups.memory.start_edit(),
+            KeyCode::Char('p') => self.toggle_selected_insight_pin(),
+            KeyCode::Char('d') | KeyCode::Delete => self.delete_selected_insight(),
+            _ => {}
+        }
+    }
+
+    /// Save the edited text of the selected insight
+    fn save_insight_edit(&mut self) {
+        let Some((id, content)) = self.ui.popups.memory.take_edit() else {
+            return;
+        };
+        if content.is_empty() {
+            return;
+        }
+        self.update_insights(|store| store.update_content(&id, &content));
+    }
+
+    /// Pin or unpin the selected insight
+    fn toggle_selected_insight_pin(&mut self) {
+        let Some(insight) = self.ui.popups.memory.selected() else {
+            return;
+        };
+        let (id, pinned) = (insight.id.clone(), !insight.pinned);
+        self.update_insights(|store| store.set_pinned(&id, pinned));
+    }
+
+    /// Delete the selected insight
+    fn delete_selected_insight(&mut self) {
+        let Some(insight) = self.ui.popups.memory.selected() else {
+            return;
+        };
+        let id = insight.id.clone();
+        self.update_insights(|store| store.delete(&id));
+    }
+}
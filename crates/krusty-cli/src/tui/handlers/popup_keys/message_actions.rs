@@ -0,0 +1,18 @@
+//! Message actions popup keyboard handler
+
+use crossterm::event::KeyCode;
+
+use crate::tui::app::{App, Popup};
+
+impl App {
+    /// Handle message actions popup keyboard events
+    pub fn handle_message_actions_popup_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') => self.ui.popup = Popup::None,
+            KeyCode::Up | KeyCode::Char('k') => self.ui.popups.message_actions.prev(),
+            KeyCode::Down | KeyCode::Char('j') => self.ui.popups.message_actions.next(),
+            KeyCode::Enter => self.run_selected_message_action(),
+            _ => {}
+        }
+    }
+}
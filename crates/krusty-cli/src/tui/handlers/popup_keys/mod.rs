@@ -4,9 +4,13 @@
 //! Each popup type has its own module for focused, testable handlers.
 
 mod auth;
+mod checkpoints;
+mod cost;
 mod file_preview;
 mod hooks;
 mod mcp;
+mod memory;
+mod message_actions;
 mod pinch;
 mod process;
 mod skills;
@@ -82,6 +86,18 @@ impl App {
             Popup::Hooks => {
                 self.handle_hooks_popup_key(code);
             }
+            Popup::Checkpoints => {
+                self.handle_checkpoints_popup_key(code);
+            }
+            Popup::Cost => {
+                self.handle_cost_popup_key(code);
+            }
+            Popup::Memory => {
+                self.handle_memory_popup_key(code);
+            }
+            Popup::MessageActions => {
+                self.handle_message_actions_popup_key(code);
+            }
             Popup::None => {}
         }
     }
@@ -164,31 +180,57 @@ impl App {
 
     /// Handle session list popup keys
     fn handle_session_list_key(&mut self, code: KeyCode) {
+        if self.ui.popups.session.search_active {
+            match code {
+                KeyCode::Esc => self.ui.popups.session.toggle_search(),
+                KeyCode::Up => self.ui.popups.session.prev(),
+                KeyCode::Down => self.ui.popups.session.next(),
+                KeyCode::Enter if self.ui.popups.session.showing_results() => {
+                    self.open_search_result();
+                }
+                KeyCode::Enter => self.load_selected_session(),
+                KeyCode::Backspace => {
+                    self.ui.popups.session.backspace_search();
+                    self.update_session_search();
+                }
+                KeyCode::Char(c) => {
+                    self.ui.popups.session.add_search_char(c);
+                    self.update_session_search();
+                }
+                _ => {}
+            }
+            return;
+        }
+
         match code {
             KeyCode::Esc => self.ui.popup = Popup::None,
             KeyCode::Up | KeyCode::Char('k') => self.ui.popups.session.prev(),
             KeyCode::Down | KeyCode::Char('j') => self.ui.popups.session.next(),
+            KeyCode::Char('/') => self.ui.popups.session.toggle_search(),
             KeyCode::Char('d') | KeyCode::Delete => {
                 if let Some(session) = self.ui.popups.session.delete_selected() {
                     self.delete_session(&session.id);
                 }
             }
-            KeyCode::Enter => {
-                if let Some(session) = self.ui.popups.session.get_selected_session() {
-                    let session_id = session.id.clone();
-                    self.save_block_ui_states();
-                    if let Err(e) = self.load_session(&session_id) {
-                        self.runtime.chat.messages.push((
-                            "system".to_string(),
-                            format!("Failed to load session: {}", e),
-                        ));
-                    } else {
-                        self.ui.pending_view_change = Some(crate::tui::app::View::Chat);
-                    }
-                    self.ui.popup = Popup::None;
-                }
-            }
+            KeyCode::Enter => self.load_selected_session(),
             _ => {}
         }
     }
+
+    /// Load the session selected in the session list
+    fn load_selected_session(&mut self) {
+        if let Some(session) = self.ui.popups.session.get_selected_session() {
+            let session_id = session.id.clone();
+            self.save_block_ui_states();
+            if let Err(e) = self.load_session(&session_id) {
+                self.runtime.chat.messages.push((
+                    "system".to_string(),
+                    format!("Failed to load session: {}", e),
+                ));
+            } else {
+                self.ui.pending_view_change = Some(crate::tui::app::View::Chat);
+            }
+            self.ui.popup = Popup::None;
+        }
+    }
 }
@@ -7,10 +7,25 @@ use crate::tui::app::{App, Popup};
 impl App {
     /// Handle process list popup keyboard events
     pub fn handle_process_popup_key(&mut self, code: KeyCode) {
+        if let Some(view) = self.ui.popups.process.log_view.as_mut() {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => self.ui.popups.process.close_logs(),
+                KeyCode::Up | KeyCode::Char('k') => view.scroll_up(1),
+                KeyCode::Down | KeyCode::Char('j') => view.scroll_down(1),
+                KeyCode::PageUp => view.scroll_up(10),
+                KeyCode::PageDown => view.scroll_down(10),
+                KeyCode::Home | KeyCode::Char('g') => view.scroll_to_top(),
+                KeyCode::End | KeyCode::Char('G') => view.follow(),
+                _ => {}
+            }
+            return;
+        }
+
         match code {
             KeyCode::Esc => self.ui.popup = Popup::None,
             KeyCode::Up | KeyCode::Char('k') => self.ui.popups.process.prev(),
             KeyCode::Down | KeyCode::Char('j') => self.ui.popups.process.next(),
+            KeyCode::Enter | KeyCode::Char('l') => self.ui.popups.process.open_logs(),
             KeyCode::Char('s') => {
                 self.toggle_process_suspend();
             }
@@ -9,6 +9,7 @@ use tokio::sync::RwLock;
 
 use crate::agent::dual_mind::{DualMind, DualMindConfig};
 use crate::ai::client::AiClient;
+use crate::ai::cost::UsageSource;
 use crate::ai::providers::ProviderId;
 use crate::tools::{register_build_tool, register_explore_tool, register_search_tool};
 use crate::tui::app::App;
@@ -35,15 +36,14 @@ impl App {
 
     /// Register explore and build tools if client is available
     pub(crate) async fn register_explore_tool_if_client(&mut self) {
-        let client = self.create_ai_client();
-
-        if let Some(client) = client {
-            let client = Arc::new(client);
+        let explore_client = self.create_ai_client_for(UsageSource::Explore);
+        let build_client = self.create_ai_client_for(UsageSource::Build);
 
+        if let (Some(explore_client), Some(build_client)) = (explore_client, build_client) {
             // Register explore tool
             register_explore_tool(
                 &self.services.tool_registry,
-                client.clone(),
+                Arc::new(explore_client),
                 self.runtime.cancellation.clone(),
             )
             .await;
@@ -51,12 +51,12 @@ impl App {
             // Register build tool (The Kraken)
             register_build_tool(
                 &self.services.tool_registry,
-                client,
+                Arc::new(build_client),
                 self.runtime.cancellation.clone(),
             )
             .await;
 
-            // Register search_codebase tool (keyword + semantic search over indexed symbols)
+            // Register search_codebase and code_graph tools (search and navigation over indexed symbols)
             // Shares the embedding engine ref so the tool gets semantic search once initialized.
             let db_path = crate::paths::config_dir().join("krusty.db");
             let codebase_path = self.runtime.working_dir.to_string_lossy().to_string();
@@ -89,11 +89,17 @@ impl App {
 
     /// Create an AI client with the current provider configuration
     pub fn create_ai_client(&self) -> Option<AiClient> {
+        self.create_ai_client_for(UsageSource::Chat)
+    }
+
+    /// Create an AI client whose usage is recorded against `source`
+    pub fn create_ai_client_for(&self, source: UsageSource) -> Option<AiClient> {
         let config = self.create_client_config();
-        self.runtime
-            .api_key
-            .as_ref()
-            .map(|key| AiClient::with_api_key(config, key.clone()))
+        let client = AiClient::with_api_key(config, self.runtime.api_key.clone()?);
+        Some(match &self.services.cost_tracker {
+            Some(tracker) => client.with_cost_tracker(tracker.clone(), source),
+            None => client,
+        })
     }
 
     /// Set API key for current provider and create client
@@ -189,7 +195,7 @@ impl App {
     /// Initialize dual-mind system when AI client is available
     /// Public because it's called when model changes in popup handlers
     pub fn init_dual_mind(&mut self) {
-        let Some(client) = self.create_ai_client() else {
+        let Some(client) = self.create_ai_client_for(UsageSource::DualMind) else {
             self.runtime.dual_mind = None;
             // DEBUG: Log initialization failure
             if let Ok(mut file) = std::fs::OpenOptions::new()
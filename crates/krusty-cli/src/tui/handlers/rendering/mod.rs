@@ -46,6 +46,10 @@ impl App {
             Popup::SkillsBrowser => self.ui.popups.skills.render(f, &self.ui.theme),
             Popup::McpBrowser => self.ui.popups.mcp.render(f, &self.ui.theme),
             Popup::Hooks => self.ui.popups.hooks.render(f, &self.ui.theme),
+            Popup::Checkpoints => self.ui.popups.checkpoints.render(f, &self.ui.theme),
+            Popup::MessageActions => self.ui.popups.message_actions.render(f, &self.ui.theme),
+            Popup::Cost => self.ui.popups.cost.render(f, &self.ui.theme),
+            Popup::Memory => self.ui.popups.memory.render(f, &self.ui.theme),
         }
 
         // Render toasts on top of everything
@@ -31,7 +31,9 @@ impl App {
         // Pre-render markdown to cache (same as render_messages) to ensure consistent line counts
         self.ui.markdown_cache.check_width(wrap_width);
 
+        let mut offsets = Vec::with_capacity(self.runtime.chat.messages.len());
         for (role, content) in &self.runtime.chat.messages {
+            offsets.push(total);
             if let Some((block_type, idx)) = indices.get_and_increment(role) {
                 // Handle block types
                 let height = match block_type {
@@ -130,6 +132,7 @@ impl App {
                 total += 1; // Blank line after
             }
         }
+        self.ui.scroll_system.layout_cache.message_offsets = offsets;
         total
     }
 }
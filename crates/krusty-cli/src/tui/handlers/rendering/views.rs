@@ -371,6 +371,7 @@ impl App {
             &self.runtime.current_model,
             &self.runtime.working_dir,
             None,
+            None,
             self.runtime.running_process_count,
             self.runtime.running_process_elapsed,
         );
@@ -509,6 +510,10 @@ impl App {
             .scroll
             .update_max_scroll(msg_total_lines, msg_visible_height);
         self.ui.scroll_system.scroll.apply_scroll_to_bottom();
+        self.ui
+            .scroll_system
+            .scroll
+            .apply_jump_to_message(&self.ui.scroll_system.layout_cache.message_offsets);
 
         // NOW render messages with correct scroll position
         self.render_messages(f, messages_chunk);
@@ -641,6 +646,7 @@ impl App {
             &self.runtime.current_model,
             &self.runtime.working_dir,
             context_tokens,
+            self.session_cost(),
             self.runtime.running_process_count,
             self.runtime.running_process_elapsed,
         );
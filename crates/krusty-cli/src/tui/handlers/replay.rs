@@ -0,0 +1,115 @@
+//! Trace replay
+//!
+//! Re-renders a recorded session trace (`krusty replay <trace>`) without
+//! calling any provider. Messages rebuild the chat as they did live and text
+//! deltas stream into the current assistant message.
+
+use std::time::Instant;
+
+use crate::agent::{AgentEvent, TraceReplay};
+use crate::tui::app::{App, View};
+
+/// A replay in progress
+pub struct ReplayState {
+    replay: TraceReplay,
+    /// When the next event is due
+    next_at: Instant,
+}
+
+impl App {
+    /// Start replaying a trace; input is read-only until the app exits
+    pub fn start_replay(&mut self, replay: TraceReplay, title: String) {
+        self.ui.view = View::Chat;
+        self.runtime.session_title = Some(format!("Replay: {}", title));
+        self.runtime.replay = Some(ReplayState {
+            replay,
+            next_at: Instant::now(),
+        });
+    }
+
+    pub(crate) fn is_replaying(&self) -> bool {
+        self.runtime.replay.is_some()
+    }
+
+    /// Apply every trace event that is due
+    pub(crate) fn poll_replay(&mut self) {
+        loop {
+            let Some(state) = self.runtime.replay.as_mut() else {
+                return;
+            };
+            if state.replay.is_finished() || Instant::now() < state.next_at {
+                return;
+            }
+            let Some(event) = state.replay.pop() else {
+                return;
+            };
+            if let Some(delay) = state.replay.next_delay() {
+                state.next_at = Instant::now() + delay;
+            }
+            let finished = state.replay.is_finished();
+
+            self.apply_replay_event(event);
+            self.ui.needs_redraw = true;
+
+            if finished {
+                self.runtime.chat.stop_streaming();
+                self.runtime.chat.messages.push((
+                    "system".to_string(),
+                    "Replay finished. Press Ctrl+Q to exit.".to_string(),
+                ));
+                return;
+            }
+        }
+    }
+
+    fn apply_replay_event(&mut self, event: AgentEvent) {
+        match event {
+            AgentEvent::SessionStart { model, .. } => {
+                self.runtime.current_model = model;
+            }
+            AgentEvent::Message { message } => {
+                self.runtime.chat.conversation.push(message);
+                self.build_tool_results_cache();
+                self.build_display_from_conversation();
+            }
+            AgentEvent::TurnStart { .. } => self.runtime.chat.start_streaming(),
+            AgentEvent::TurnComplete { .. } | AgentEvent::StreamEnd { .. } => {
+                self.runtime.chat.stop_streaming();
+            }
+            AgentEvent::TextDelta { delta } => self.append_replay_delta(delta),
+            AgentEvent::ToolCallStart { name, .. } => {
+                self.runtime.chat.current_activity = Some(name);
+            }
+            AgentEvent::StreamError { error } => self
+                .runtime
+                .chat
+                .messages
+                .push(("system".to_string(), format!("Error: {}", error))),
+            AgentEvent::Interrupt { reason, .. } => self
+                .runtime
+                .chat
+                .messages
+                .push(("system".to_string(), format!("Interrupted: {:?}", reason))),
+            AgentEvent::PinchComplete { new_session_id } => self.runtime.chat.messages.push((
+                "system".to_string(),
+                format!("Pinched into session {}", new_session_id),
+            )),
+            AgentEvent::ThinkingDelta { .. }
+            | AgentEvent::ToolCallComplete { .. }
+            | AgentEvent::SubAgentProgress { .. }
+            | AgentEvent::PinchStart { .. } => {}
+        }
+    }
+
+    /// Stream text into the current assistant message
+    fn append_replay_delta(&mut self, delta: String) {
+        let chat = &mut self.runtime.chat;
+        match chat.streaming_assistant_idx {
+            Some(idx) if idx < chat.messages.len() => chat.messages[idx].1.push_str(&delta),
+            _ => {
+                chat.streaming_assistant_idx = Some(chat.messages.len());
+                chat.messages.push(("assistant".to_string(), delta));
+            }
+        }
+    }
+}
@@ -4,16 +4,22 @@
 
 use anyhow::Result;
 
+use crate::agent::{AgentEvent, HookEvent};
 use crate::ai::client::AiClient;
+use crate::ai::cost::UsageSource;
 use crate::ai::types::{Content, ModelMessage, Role};
 use crate::storage::SessionManager;
-use crate::tui::app::{App, WorkMode};
+use crate::tui::app::{App, Popup, View, WorkMode};
 use crate::tui::blocks::{
-    BashBlock, EditBlock, ReadBlock, ThinkingBlock, ToolResultBlock, WriteBlock,
+    BashBlock, EditBlock, ReadBlock, ThinkingBlock, ToolResultBlock, WebSearchBlock, WriteBlock,
 };
+use crate::tui::popups::session_list::parse_search;
 use crate::tui::state::{hash_content, BlockManager};
 use crate::tui::utils::TitleUpdate;
 
+/// Most search results shown in the session list
+const SEARCH_RESULT_LIMIT: usize = 50;
+
 impl App {
     /// Create a new session
     pub fn create_session(&mut self, first_message: &str) -> Option<String> {
@@ -34,6 +40,7 @@ impl App {
                 tracing::info!("Created new session: {}", id);
                 self.runtime.current_session_id = Some(id.clone());
                 self.runtime.session_title = Some(fallback_title);
+                self.emit_session_start(&id);
 
                 // Clear any active plan when starting a new session
                 self.clear_plan();
@@ -41,6 +48,12 @@ impl App {
                 // Spawn AI title generation in background
                 self.spawn_title_generation(id.clone(), first_message.to_string());
 
+                self.fire_user_hooks(HookEvent::session_start(
+                    &id,
+                    &self.runtime.working_dir,
+                    "new",
+                ));
+
                 Some(id)
             }
             Err(e) => {
@@ -72,7 +85,7 @@ impl App {
 
     /// Create AI client for title generation
     fn create_title_client(&self) -> Option<AiClient> {
-        self.create_ai_client()
+        self.create_ai_client_for(UsageSource::Title)
     }
 
     /// Poll for AI-generated title updates
@@ -123,9 +136,23 @@ impl App {
         }
     }
 
+    /// Publish that the agent is now working in this session
+    fn emit_session_start(&self, session_id: &str) {
+        self.runtime.event_bus.emit(AgentEvent::SessionStart {
+            session_id: session_id.to_string(),
+            model: self.runtime.current_model.clone(),
+            working_dir: self.runtime.working_dir.to_string_lossy().into_owned(),
+        });
+    }
+
     /// Save a message to the current session
     /// Content is serialized as JSON for full fidelity (supports tools, images, etc.)
+    /// and the message is published on the event bus.
     pub fn save_model_message(&self, message: &ModelMessage) {
+        self.runtime.event_bus.emit(AgentEvent::Message {
+            message: message.clone(),
+        });
+
         let Some(sm) = &self.services.session_manager else {
             tracing::warn!("Cannot save message: no session manager");
             return;
@@ -188,6 +215,9 @@ impl App {
         self.runtime.session_title = session_info.as_ref().map(|i| i.title.clone());
         let stored_token_count = session_info.as_ref().and_then(|i| i.token_count);
 
+        // Remember what the session being left taught about the codebase
+        self.extract_session_memories();
+
         // Clear current state
         self.runtime.chat.messages.clear();
         self.runtime.chat.conversation.clear();
@@ -196,6 +226,15 @@ impl App {
         self.runtime.tool_results.clear();
         self.runtime.chat.streaming_assistant_idx = None;
         self.runtime.current_session_id = Some(session_id.to_string());
+        self.clear_session_permissions();
+        self.end_checkpoint_turn();
+        self.sync_cost_session();
+        self.emit_session_start(session_id);
+        self.fire_user_hooks(HookEvent::session_start(
+            session_id,
+            &self.runtime.working_dir,
+            "resume",
+        ));
 
         // Load plan for this session (strict 1:1 linkage, no working_dir fallback)
         match self.services.plan_manager.get_plan(session_id) {
@@ -283,6 +322,7 @@ impl App {
         // Build caches and display from conversation
         self.build_tool_results_cache();
         self.build_display_from_conversation();
+        self.runtime.memory_baseline = self.runtime.chat.conversation.len();
 
         // Restore persisted block UI states (collapsed/scroll positions)
         if !ui_states.is_empty() {
@@ -342,6 +382,54 @@ impl App {
             .unwrap_or_default()
     }
 
+    /// Re-run the session search for the current query
+    pub(crate) fn update_session_search(&mut self) {
+        let popup = &mut self.ui.popups.session;
+        if !popup.showing_results() {
+            popup.set_results(Ok(Vec::new()));
+            return;
+        }
+        let Some(sm) = &self.services.session_manager else {
+            popup.set_results(Err(
+                "Session search is unavailable (no database)".to_string()
+            ));
+            return;
+        };
+
+        let default_dir = self.runtime.working_dir.to_string_lossy();
+        let results =
+            parse_search(&popup.search_query, &default_dir).and_then(|(query, filter)| {
+                sm.search_messages(&query, &filter, SEARCH_RESULT_LIMIT)
+                    .map_err(|e| format!("Search failed: {}", e))
+            });
+        popup.set_results(results);
+    }
+
+    /// Load the session of the selected search result and jump to the message
+    pub(crate) fn open_search_result(&mut self) {
+        let Some(hit) = self.ui.popups.session.get_selected_result() else {
+            return;
+        };
+        let (session_id, message_index) = (hit.session_id.clone(), hit.message_index);
+
+        self.save_block_ui_states();
+        if let Err(e) = self.load_session(&session_id) {
+            self.runtime.chat.messages.push((
+                "system".to_string(),
+                format!("Failed to load session: {}", e),
+            ));
+        } else {
+            if let Some(&display_idx) = self.runtime.chat.display_starts.get(message_index) {
+                self.ui
+                    .scroll_system
+                    .scroll
+                    .request_jump_to_message(display_idx);
+            }
+            self.ui.pending_view_change = Some(View::Chat);
+        }
+        self.ui.popup = Popup::None;
+    }
+
     /// Save all block UI states to the database
     pub fn save_block_ui_states(&self) {
         let Some(sm) = &self.services.session_manager else {
@@ -380,7 +468,7 @@ impl App {
     }
 
     /// Build tool results cache from conversation
-    fn build_tool_results_cache(&mut self) {
+    pub(crate) fn build_tool_results_cache(&mut self) {
         self.runtime.tool_results.clear();
 
         for msg in &self.runtime.chat.conversation {
@@ -435,12 +523,14 @@ impl App {
     /// - "bash" → BashBlock at current bash index
     /// - "read" / "edit" / "write" → respective block types
     /// - "tool_result" → ToolResultBlock (grep/glob/unknown tools)
-    fn build_display_from_conversation(&mut self) {
+    pub(crate) fn build_display_from_conversation(&mut self) {
         self.runtime.chat.messages.clear();
         self.runtime.chat.streaming_assistant_idx = None;
         self.runtime.blocks = BlockManager::new();
+        let mut display_starts = Vec::with_capacity(self.runtime.chat.conversation.len());
 
         for msg in &self.runtime.chat.conversation {
+            display_starts.push(self.runtime.chat.messages.len());
             let base_role = match msg.role {
                 Role::User => "user",
                 Role::Assistant => "assistant",
@@ -677,6 +767,46 @@ impl App {
                                 }
                             }
 
+                            "web_fetch" => {
+                                self.runtime
+                                    .chat
+                                    .messages
+                                    .push(("tool_result".to_string(), id.clone()));
+
+                                let url = input
+                                    .get("url")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("")
+                                    .to_string();
+                                let mut block = ToolResultBlock::new(id.clone(), name.clone(), url);
+                                if let Some(result) = self.runtime.tool_results.get(id) {
+                                    block.set_results(&result.output);
+                                    block.complete();
+                                }
+                                block.set_collapsed(true);
+                                self.ui.block_ui.set_collapsed(id, true);
+                                self.runtime.blocks.tool_result.push(block);
+                            }
+
+                            "web_search" => {
+                                self.runtime
+                                    .chat
+                                    .messages
+                                    .push(("web_search".to_string(), id.clone()));
+
+                                let query = input
+                                    .get("query")
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or("")
+                                    .to_string();
+                                let mut block = WebSearchBlock::new(id.clone(), query);
+                                match self.runtime.tool_results.get(id) {
+                                    Some(result) => block.set_results_from_output(&result.output),
+                                    None => block.complete(),
+                                }
+                                self.runtime.blocks.web_search.push(block);
+                            }
+
                             "grep" | "glob" => {
                                 self.runtime
                                     .chat
@@ -794,6 +924,7 @@ impl App {
                 }
             }
         }
+        self.runtime.chat.display_starts = display_starts;
     }
 
     /// Fix orphaned tool calls by injecting placeholder results
@@ -6,7 +6,7 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::agent::AgentEvent;
+use crate::agent::{AgentEvent, HookEvent};
 use crate::ai::types::{Content, ModelMessage, Role};
 use crate::plan::PlanFile;
 use crate::tui::app::{App, WorkMode};
@@ -57,9 +57,15 @@ impl App {
     fn handle_stream_event(&mut self, event: StreamEvent) {
         match event {
             StreamEvent::TextDelta { delta } => {
+                self.runtime.event_bus.emit(AgentEvent::TextDelta {
+                    delta: delta.clone(),
+                });
                 self.handle_text_delta(delta);
             }
             StreamEvent::TextDeltaWithCitations { delta, citations } => {
+                self.runtime.event_bus.emit(AgentEvent::TextDelta {
+                    delta: delta.clone(),
+                });
                 self.handle_text_delta_with_citations(delta, citations);
             }
             StreamEvent::ToolStart { name } => {
@@ -112,6 +118,9 @@ impl App {
                 self.handle_thinking_start();
             }
             StreamEvent::ThinkingDelta { thinking } => {
+                self.runtime.event_bus.emit(AgentEvent::ThinkingDelta {
+                    delta: thinking.clone(),
+                });
                 self.handle_thinking_delta(thinking);
             }
             StreamEvent::ThinkingComplete { signature } => {
@@ -347,6 +356,8 @@ impl App {
                 | "Task"
                 | "explore"
                 | "build"
+                | "web_fetch"
+                | "web_search"
                 | "AskUserQuestion"
                 | "task_start"         // Silent - updates plan sidebar
                 | "task_complete"      // Silent - updates plan sidebar
@@ -500,6 +511,11 @@ impl App {
         // If no tools to execute, build and save the assistant message now
         if !self.runtime.streaming.is_ready_for_tools() {
             self.stop_streaming();
+            self.fire_user_hooks(HookEvent::stop(
+                self.runtime.current_session_id.as_deref(),
+                &self.runtime.working_dir,
+                &final_text,
+            ));
 
             // Build and save assistant message using StreamingManager
             if let Some(assistant_msg) = self.runtime.streaming.build_assistant_message() {
@@ -9,6 +9,9 @@ use crate::ai::types::Content;
 use crate::tui::app::{App, WorkMode};
 use krusty_core::index::{CodebaseStore, InsightStore, SearchQuery, SemanticRetrieval};
 
+/// Most codebase insights injected into a request
+pub(crate) const INSIGHTS_IN_CONTEXT: usize = 20;
+
 /// Sanitize plan titles for safe markdown embedding
 /// Escapes backticks and quotes that could break formatting
 pub fn sanitize_plan_title(title: &str) -> String {
@@ -254,7 +257,7 @@ You MUST follow this disciplined workflow. Do NOT batch-complete tasks or skip s
         };
 
         let insight_store = InsightStore::new(conn);
-        let insights = match insight_store.get_top(&codebase_id, 20) {
+        let insights = match insight_store.get_top(&codebase_id, INSIGHTS_IN_CONTEXT) {
             Ok(insights) if !insights.is_empty() => insights,
             _ => return String::new(),
         };
@@ -267,11 +270,16 @@ You MUST follow this disciplined workflow. Do NOT batch-complete tasks or skip s
 
         let mut context = String::from("[CODEBASE RULES]\nIMPORTANT: These are verified patterns and conventions for this codebase. You MUST follow them.\nViolating these will introduce inconsistencies and bugs.\n\n");
         for insight in &insights {
+            let weight = if insight.pinned {
+                "pinned".to_string()
+            } else {
+                format!("confidence: {:.0}%", insight.confidence * 100.0)
+            };
             context.push_str(&format!(
-                "- [{}] {} (confidence: {:.0}%)\n",
+                "- [{}] {} ({})\n",
                 insight.insight_type.as_str(),
                 insight.content,
-                insight.confidence * 100.0
+                weight
             ));
         }
         context
@@ -332,7 +340,7 @@ You MUST follow this disciplined workflow. Do NOT batch-complete tasks or skip s
             })
     }
 
-    /// Build search context from codebase index (semantic or keyword search)
+    /// Build search context from codebase index (hybrid or keyword search)
     pub fn build_search_context(&self) -> String {
         let query_text = match self.extract_latest_user_query() {
             Some(text) if !text.is_empty() => text,
@@ -374,11 +382,7 @@ You MUST follow this disciplined workflow. Do NOT batch-complete tasks or skip s
 
         if filtered.is_empty() {
             tracing::debug!(
-                mode = if has_embeddings {
-                    "semantic"
-                } else {
-                    "keyword"
-                },
+                mode = if has_embeddings { "hybrid" } else { "keyword" },
                 "Search: no results above threshold"
             );
             return String::new();
@@ -386,11 +390,7 @@ You MUST follow this disciplined workflow. Do NOT batch-complete tasks or skip s
 
         let top_score = filtered.first().map(|r| r.score).unwrap_or(0.0);
         tracing::info!(
-            mode = if has_embeddings {
-                "semantic"
-            } else {
-                "keyword"
-            },
+            mode = if has_embeddings { "hybrid" } else { "keyword" },
             results = filtered.len(),
             top_score = format!("{:.2}", top_score),
             "Search: matched symbols"
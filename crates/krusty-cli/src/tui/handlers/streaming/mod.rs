@@ -10,15 +10,18 @@
 mod context_building;
 mod tool_execution;
 
+pub(crate) use context_building::INSIGHTS_IN_CONTEXT;
+
 use std::sync::Arc;
 
 use tokio::sync::mpsc;
 
-use crate::agent::{AgentEvent, InterruptReason};
+use crate::agent::{AgentEvent, HookEvent, InterruptReason};
 use crate::ai::client::CallOptions;
 use crate::ai::streaming::StreamPart;
 use crate::ai::types::{
-    Content, ContextManagement, ModelMessage, Role, ThinkingConfig, WebFetchConfig, WebSearchConfig,
+    Content, ContextManagement, ImageContent, ModelMessage, Role, ThinkingConfig, WebFetchConfig,
+    WebSearchConfig,
 };
 use crate::tools::{load_from_clipboard_rgba, load_from_path, load_from_url};
 use crate::tui::app::{App, View};
@@ -35,6 +38,45 @@ fn check_file_limit(count: usize) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Turn the contents of an attached MCP resource into message content
+///
+/// Text is wrapped in a tag naming its source; image blobs are attached as
+/// images and other binary contents are noted but left out.
+fn mcp_resource_content(
+    server: &str,
+    uri: &str,
+    contents: Vec<krusty_core::mcp::McpResourceContents>,
+) -> Vec<Content> {
+    contents
+        .into_iter()
+        .map(|c| {
+            let mime_type = c.mime_type.unwrap_or_default();
+            match (c.text, c.blob) {
+                (Some(text), _) => Content::Text {
+                    text: format!(
+                        "<mcp_resource server=\"{}\" uri=\"{}\">\n{}\n</mcp_resource>",
+                        server, c.uri, text
+                    ),
+                },
+                (None, Some(blob)) if mime_type.starts_with("image/") => Content::Image {
+                    image: ImageContent {
+                        url: None,
+                        base64: Some(blob),
+                        media_type: Some(mime_type),
+                    },
+                    detail: None,
+                },
+                _ => Content::Text {
+                    text: format!(
+                        "[MCP resource {} from {} has binary contents ({}) that can't be attached]",
+                        uri, server, mime_type
+                    ),
+                },
+            }
+        })
+        .collect()
+}
+
 impl App {
     /// Resolve the embedding engine from the background init handle, or fall back to sync init.
     /// Populates the shared Arc<RwLock<...>> so the search_codebase tool can also use it.
@@ -102,6 +144,14 @@ impl App {
 
     /// Handle user input submission (message or command)
     pub fn handle_input_submit(&mut self, text: String) {
+        if self.is_replaying() {
+            self.runtime.chat.messages.push((
+                "system".to_string(),
+                "Replays are read-only. Press Ctrl+Q to exit.".to_string(),
+            ));
+            return;
+        }
+
         // Check if this is a slash command vs a file path
         if text.starts_with('/') && !Self::looks_like_file_path(&text) {
             self.handle_slash_command(&text);
@@ -121,11 +171,26 @@ impl App {
             return;
         }
 
+        let hook_outcome = self.run_user_hooks(HookEvent::user_prompt_submit(
+            self.runtime.current_session_id.as_deref(),
+            &self.runtime.working_dir,
+            &text,
+        ));
+        self.show_hook_warnings(&hook_outcome);
+        if let Some(reason) = &hook_outcome.blocked {
+            self.ui.input.insert_text(&text);
+            self.runtime.chat.messages.push((
+                "system".to_string(),
+                format!("Prompt blocked by hook: {}", reason),
+            ));
+            return;
+        }
+
         if self.runtime.current_session_id.is_none() {
             self.create_session(&text);
         }
 
-        let (content_blocks, display_text) = match self.build_user_content(&text) {
+        let (mut content_blocks, display_text) = match self.build_user_content(&text) {
             Ok(result) => result,
             Err(e) => {
                 self.runtime
@@ -139,7 +204,11 @@ impl App {
         self.runtime
             .chat
             .messages
-            .push(("user".to_string(), display_text));
+            .push(("user".to_string(), display_text.clone()));
+        self.begin_checkpoint_turn(&display_text);
+        if let Some(context) = hook_outcome.prompt_context() {
+            content_blocks.push(Content::Text { text: context });
+        }
         let user_msg = ModelMessage {
             role: Role::User,
             content: content_blocks,
@@ -197,6 +266,17 @@ impl App {
                     content_blocks.push(loaded.content);
                     display_parts.push(format!("[Image: {}]", loaded.display_name));
                 }
+                InputSegment::McpResource { server, uri } => {
+                    file_count += 1;
+                    check_file_limit(file_count)?;
+                    let mcp = self.services.mcp_manager.clone();
+                    let contents = futures::executor::block_on(mcp.read_resource(&server, &uri))
+                        .map_err(|e| {
+                            anyhow::anyhow!("Failed to read {} from {}: {}", uri, server, e)
+                        })?;
+                    content_blocks.extend(mcp_resource_content(&server, &uri, contents));
+                    display_parts.push(format!("[Resource: {}:{}]", server, uri));
+                }
                 InputSegment::ClipboardImage(id) => {
                     // Extract clipboard id (format: "clipboard:uuid")
                     let clipboard_id = id.strip_prefix("clipboard:").unwrap_or(&id);
@@ -312,6 +392,14 @@ impl App {
             return;
         };
 
+        if !self.check_budget() {
+            self.runtime.event_bus.emit(AgentEvent::Interrupt {
+                turn: self.runtime.agent_state.current_turn,
+                reason: InterruptReason::BudgetExceeded,
+            });
+            return;
+        }
+
         self.start_streaming();
         self.runtime.streaming.reset();
 
@@ -452,7 +540,20 @@ impl App {
         tracing::info!("Sending {} tools to API: {:?}", tools.len(), tool_names);
 
         let can_use_thinking = self.runtime.thinking_enabled;
-        let thinking = can_use_thinking.then(ThinkingConfig::default);
+        let (thinking, reasoning_format) = if self.runtime.active_provider.is_custom() {
+            // Custom providers declare per model whether and how reasoning is requested
+            let format = can_use_thinking
+                .then(|| {
+                    self.services
+                        .model_registry
+                        .try_get_model(&self.runtime.current_model)
+                })
+                .flatten()
+                .and_then(|m| m.reasoning_format);
+            (None, format)
+        } else {
+            (can_use_thinking.then(ThinkingConfig::default), None)
+        };
 
         let context_management = match (can_use_thinking, !tools.is_empty()) {
             (true, _) => Some(ContextManagement::default_for_thinking_and_tools()),
@@ -463,6 +564,7 @@ impl App {
         let options = CallOptions {
             tools: (!tools.is_empty()).then_some(tools),
             thinking,
+            reasoning_format,
             enable_caching: true,
             context_management,
             web_search: Some(WebSearchConfig::default()),
@@ -6,6 +6,7 @@ use tokio::sync::{mpsc, oneshot};
 
 use crate::agent::dual_mind::{DialogueResult, Observation, ObservedAction};
 use crate::agent::subagent::AgentProgress;
+use crate::agent::AgentEvent;
 use crate::ai::types::{AiToolCall, Content};
 use crate::tools::{ToolContext, ToolOutputChunk};
 use crate::tui::app::App;
@@ -497,7 +498,7 @@ impl App {
         let all_readonly = tool_calls.iter().all(|t| {
             matches!(
                 t.name.as_str(),
-                "read" | "glob" | "grep" | "search_codebase"
+                "read" | "glob" | "grep" | "search_codebase" | "code_graph" | "lsp"
             )
         });
         let has_action = tool_calls.iter().any(|t| {
@@ -695,9 +696,13 @@ impl App {
         let tool_registry = self.services.tool_registry.clone();
         let process_registry = self.runtime.process_registry.clone();
         let skills_manager = self.services.skills_manager.clone();
+        let lsp_manager = self.services.lsp_manager.clone();
+        let checkpoints = self.services.checkpoints.clone();
         let cancel_token = self.runtime.cancellation.child_token();
         let plan_mode = self.ui.work_mode == crate::tui::app::WorkMode::Plan;
         let current_model = self.runtime.current_model.clone();
+        let user_hooks = self.services.user_hook_manager.clone();
+        let event_bus = self.runtime.event_bus.clone();
         let dual_mind = self.runtime.dual_mind.clone();
         let dual_mind_tx = dual_mind_tx;
 
@@ -750,8 +755,13 @@ impl App {
                 let mut ctx =
                     ToolContext::with_process_registry(working_dir, process_registry.clone())
                         .with_skills_manager(skills_manager.clone())
-                        .with_current_model(current_model.clone());
+                        .with_lsp_manager(lsp_manager.clone())
+                        .with_current_model(current_model.clone())
+                        .with_user_hooks(user_hooks.clone());
                 ctx.plan_mode = plan_mode;
+                if let Some(ref checkpoints) = checkpoints {
+                    ctx = ctx.with_checkpoints(checkpoints.clone());
+                }
 
                 if tool_name == "bash" {
                     ctx = ctx.with_output_stream(output_tx.clone(), tool_call.id.clone());
@@ -771,6 +781,13 @@ impl App {
                     }
                 }
 
+                event_bus.emit(AgentEvent::ToolCallStart {
+                    id: tool_call.id.clone(),
+                    name: tool_name.clone(),
+                    input: tool_call.arguments.clone(),
+                });
+                let started = std::time::Instant::now();
+
                 let result = tokio::select! {
                     _ = cancel_token.cancelled() => {
                         tracing::info!("Tool execution cancelled during {}", tool_name);
@@ -784,6 +801,18 @@ impl App {
                     }
                 };
 
+                let (output, is_error) = match &result {
+                    Some(r) => (r.output.clone(), r.is_error),
+                    None => (format!("Error: Unknown tool '{}'", tool_name), true),
+                };
+                event_bus.emit(AgentEvent::ToolCallComplete {
+                    id: tool_call.id.clone(),
+                    name: tool_name.clone(),
+                    output,
+                    is_error,
+                    duration_ms: started.elapsed().as_millis() as u64,
+                });
+
                 if let Some(result) = result {
                     // Sync observation to Little Claw (so it knows what happened)
                     if let Some(ref dm) = dual_mind {
@@ -915,6 +944,47 @@ impl App {
                     .push(("tool_result".to_string(), tool_call.id.clone()));
             }
 
+            if tool_name == "web_fetch" {
+                let url = tool_call
+                    .arguments
+                    .get("url")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                self.runtime
+                    .blocks
+                    .tool_result
+                    .push(crate::tui::blocks::ToolResultBlock::new(
+                        tool_call.id.clone(),
+                        tool_name.clone(),
+                        url,
+                    ));
+                self.runtime
+                    .chat
+                    .messages
+                    .push(("tool_result".to_string(), tool_call.id.clone()));
+            }
+
+            if tool_name == "web_search" {
+                let query = tool_call
+                    .arguments
+                    .get("query")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                self.runtime
+                    .blocks
+                    .web_search
+                    .push(crate::tui::blocks::WebSearchBlock::new(
+                        tool_call.id.clone(),
+                        query,
+                    ));
+                self.runtime
+                    .chat
+                    .messages
+                    .push(("web_search".to_string(), tool_call.id.clone()));
+            }
+
             if tool_name == "read" {
                 let file_path = tool_call
                     .arguments
@@ -1078,6 +1148,7 @@ impl App {
                 self.update_bash_block(tool_use_id, output_str);
                 self.update_explore_block(tool_use_id, output_str);
                 self.update_build_block(tool_use_id, output_str);
+                self.update_web_search_block(tool_use_id, output_str);
             }
         }
 
@@ -1224,6 +1295,19 @@ impl App {
         }
     }
 
+    /// Update WebSearchBlock for the client-side web_search tool
+    fn update_web_search_block(&mut self, tool_use_id: &str, output_str: &str) {
+        if let Some(block) = self
+            .runtime
+            .blocks
+            .web_search
+            .iter_mut()
+            .find(|b| b.tool_use_id() == tool_use_id)
+        {
+            block.set_results_from_output(output_str);
+        }
+    }
+
     /// Update BuildBlock with results
     fn update_build_block(&mut self, tool_use_id: &str, output_str: &str) {
         for block in &mut self.runtime.blocks.build {
@@ -0,0 +1,259 @@
+//! Symbol summary handlers
+//!
+//! `/summarize` estimates what it costs to have a cheap model describe the
+//! codebase's public symbols; `/summarize run` writes the summaries in the
+//! background within a budget.
+
+use std::sync::Arc;
+
+use krusty_core::index::{
+    summary_model, CodebaseStore, EmbeddingEngine, Summarizer, SummaryPlan, SummaryProgress,
+    SummaryReport,
+};
+use krusty_core::storage::Database;
+use tokio::sync::oneshot::error::TryRecvError;
+use tokio_util::sync::CancellationToken;
+
+use crate::ai::cost::{format_cost, model_pricing, ModelPricing, UsageSource};
+use crate::tui::app::App;
+
+/// Default budget as a multiple of the estimate
+const BUDGET_MARGIN: f64 = 1.25;
+
+/// A background summarisation run
+pub struct SummaryRun {
+    pub model: String,
+    pub total: usize,
+    pub progress: Option<SummaryProgress>,
+    pub cancellation: CancellationToken,
+}
+
+impl App {
+    /// Handle /summarize command
+    ///
+    /// - `/summarize` shows the cost estimate, or progress during a run
+    /// - `/summarize run [max USD]` starts a background run (default budget:
+    ///   the estimate plus a margin)
+    /// - `/summarize stop` cancels the run after the requests in flight
+    pub(crate) fn handle_summarize_command(&mut self, args: &[&str]) {
+        let message = match args {
+            [] => self.summary_status(),
+            ["run"] => self.start_summaries(None),
+            ["run", amount] => match amount.trim_start_matches('$').parse::<f64>() {
+                Ok(budget) if budget > 0.0 => self.start_summaries(Some(budget)),
+                _ => format!("Invalid budget '{}'. Use an amount like 0.50.", amount),
+            },
+            ["stop"] => match &self.runtime.summary_run {
+                Some(run) => {
+                    run.cancellation.cancel();
+                    "Stopping summaries after the requests in flight...".to_string()
+                }
+                None => "No summaries are being written.".to_string(),
+            },
+            _ => "Usage: /summarize [run [max USD] | stop]".to_string(),
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+    }
+
+    /// Progress of the current run, or the estimate for a new one
+    fn summary_status(&self) -> String {
+        if let Some(run) = &self.runtime.summary_run {
+            let (done, spent) = run
+                .progress
+                .as_ref()
+                .map_or((0, 0.0), |p| (p.done, p.spent));
+            return format!(
+                "Writing summaries with {}: {}/{} symbols, ~{} spent. /summarize stop cancels.",
+                run.model,
+                done,
+                run.total,
+                format_cost(spent)
+            );
+        }
+
+        let (plan, model, pricing) = match self.plan_summaries() {
+            Ok(planned) => planned,
+            Err(e) => return e,
+        };
+        if plan.is_empty() {
+            return nothing_to_summarize(&plan);
+        }
+        let cost = match pricing {
+            Some(pricing) => format!("≈ {}", format_cost(plan.estimated_cost(&pricing))),
+            None => "(pricing unknown)".to_string(),
+        };
+        format!(
+            "{} public symbols need summaries ({} already have one). Estimated {}k input + {}k output tokens {} with {}.\nRun /summarize run [max USD] to write them in the background.",
+            plan.jobs.len(),
+            plan.summarized,
+            plan.input_tokens.div_ceil(1000),
+            plan.output_tokens.div_ceil(1000),
+            cost,
+            model
+        )
+    }
+
+    /// Pending summaries for the working directory, the model and its pricing
+    fn plan_summaries(&self) -> Result<(SummaryPlan, String, Option<ModelPricing>), String> {
+        let Some(sm) = &self.services.session_manager else {
+            return Err("Summaries need the database, which is unavailable".to_string());
+        };
+        let conn = sm.db().conn();
+        let working_dir = self.runtime.working_dir.to_string_lossy().to_string();
+        let codebase = match CodebaseStore::new(conn).get_by_path(&working_dir) {
+            Ok(Some(codebase)) if codebase.indexed_at.is_some() => codebase,
+            Ok(_) => return Err("Codebase not indexed. Run /init first.".to_string()),
+            Err(e) => return Err(format!("Failed to look up codebase: {}", e)),
+        };
+        let plan = SummaryPlan::build(conn, &codebase.id)
+            .map_err(|e| format!("Failed to plan summaries: {}", e))?;
+
+        let provider = self.runtime.active_provider;
+        let model = summary_model(provider, &self.runtime.current_model);
+        let pricing = futures::executor::block_on(model_pricing(
+            &self.services.model_registry,
+            provider,
+            &model,
+        ));
+        Ok((plan, model, pricing))
+    }
+
+    /// Start writing summaries in the background
+    fn start_summaries(&mut self, budget: Option<f64>) -> String {
+        if self.runtime.summary_run.is_some() {
+            return "Summaries are already being written. /summarize shows progress.".to_string();
+        }
+        let (plan, model, pricing) = match self.plan_summaries() {
+            Ok(planned) => planned,
+            Err(e) => return e,
+        };
+        if plan.is_empty() {
+            return nothing_to_summarize(&plan);
+        }
+        let Some(client) = self.create_ai_client_for(UsageSource::Index) else {
+            return "Failed to create AI client for summaries".to_string();
+        };
+
+        let budget =
+            budget.or_else(|| pricing.map(|pricing| plan.estimated_cost(&pricing) * BUDGET_MARGIN));
+        let budget_note = match (budget, pricing) {
+            (Some(budget), Some(_)) => format!("budget {}", format_cost(budget)),
+            (Some(_), None) => "pricing unknown, so the budget can't be enforced".to_string(),
+            (None, _) => "no budget".to_string(),
+        };
+
+        let cancellation = CancellationToken::new();
+        let (progress_tx, progress_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (result_tx, result_rx) = tokio::sync::oneshot::channel();
+        self.runtime.channels.summary_progress = Some(progress_rx);
+        self.runtime.channels.summary_result = Some(result_rx);
+        self.runtime.summary_run = Some(SummaryRun {
+            model: model.clone(),
+            total: plan.jobs.len(),
+            progress: None,
+            cancellation: cancellation.clone(),
+        });
+
+        let message = format!(
+            "Writing {} summaries with {} in the background ({}). /summarize shows progress.",
+            plan.jobs.len(),
+            model,
+            budget_note
+        );
+
+        // Opens its own connection: rusqlite::Connection is !Send
+        let db_path = crate::paths::config_dir().join("krusty.db");
+        tokio::task::spawn_blocking(move || {
+            let result = (|| -> Result<SummaryReport, String> {
+                let db = Database::new(&db_path).map_err(|e| e.to_string())?;
+                let mut summarizer = Summarizer::new(Arc::new(client), model)
+                    .with_pricing(pricing)
+                    .with_cancellation(cancellation);
+                if let Some(budget) = budget {
+                    summarizer = summarizer.with_budget(budget);
+                }
+                match EmbeddingEngine::new() {
+                    Ok(engine) => summarizer = summarizer.with_embeddings(engine),
+                    Err(e) => {
+                        tracing::info!("Embeddings unavailable ({e}), summaries not embedded")
+                    }
+                }
+                tokio::runtime::Handle::current()
+                    .block_on(summarizer.run(db.conn(), &plan, Some(progress_tx)))
+                    .map_err(|e| e.to_string())
+            })();
+            let _ = result_tx.send(result);
+        });
+
+        message
+    }
+
+    /// Track progress of a background run and report when it ends
+    pub(crate) fn poll_summaries(&mut self) {
+        if let Some(rx) = self.runtime.channels.summary_progress.as_mut() {
+            while let Ok(progress) = rx.try_recv() {
+                if let Some(run) = self.runtime.summary_run.as_mut() {
+                    run.progress = Some(progress);
+                }
+            }
+        }
+
+        let Some(rx) = self.runtime.channels.summary_result.as_mut() else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Closed) => Err("Summary task ended unexpectedly".to_string()),
+        };
+        self.runtime.channels.summary_result = None;
+        self.runtime.channels.summary_progress = None;
+        self.runtime.summary_run = None;
+
+        let message = match result {
+            Ok(report) => format_report(&report),
+            Err(e) => format!("Failed to write summaries: {}", e),
+        };
+        self.runtime
+            .chat
+            .messages
+            .push(("system".to_string(), message));
+        self.ui.needs_redraw = true;
+    }
+}
+
+fn nothing_to_summarize(plan: &SummaryPlan) -> String {
+    if plan.summarized > 0 {
+        format!(
+            "All {} public symbols already have summaries.",
+            plan.summarized
+        )
+    } else {
+        "No public symbols to summarise.".to_string()
+    }
+}
+
+fn format_report(report: &SummaryReport) -> String {
+    let mut message = format!(
+        "Wrote {} symbol summaries (~{}).",
+        report.written,
+        format_cost(report.spent)
+    );
+    if report.failed > 0 {
+        message.push_str(&format!(" {} failed.", report.failed));
+    }
+    if let Some(error) = &report.error {
+        message.push_str(&format!(" Stopped after an error: {}", error));
+    } else if report.budget_reached {
+        message.push_str(&format!(
+            " Stopped at the budget with {} left; /summarize run <max USD> continues.",
+            report.remaining
+        ));
+    } else if report.cancelled {
+        message.push_str(&format!(" Stopped with {} left.", report.remaining));
+    }
+    message
+}
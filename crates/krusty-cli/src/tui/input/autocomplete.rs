@@ -8,13 +8,16 @@ use ratatui::{
     Frame,
 };
 
+use std::borrow::Cow;
+
 use crate::tui::themes::Theme;
+use krusty_core::mcp::McpPrompt;
 
 #[derive(Debug, Clone)]
 pub struct CommandSuggestion {
-    pub primary: &'static str,
+    pub primary: Cow<'static, str>,
     pub aliases: Vec<&'static str>,
-    pub description: &'static str,
+    pub description: Cow<'static, str>,
 }
 
 /// Autocomplete popup for slash commands
@@ -44,6 +47,23 @@ impl AutocompletePopup {
         }
     }
 
+    /// Offer MCP prompts as `/server:prompt` commands after the built-ins
+    pub fn set_mcp_prompts(&mut self, prompts: &[(String, McpPrompt)]) {
+        self.suggestions = get_all_commands();
+        self.suggestions
+            .extend(prompts.iter().map(|(server, prompt)| {
+                CommandSuggestion {
+                    primary: format!("/{}:{}", server, prompt.name).into(),
+                    aliases: vec![],
+                    description: prompt
+                        .description
+                        .clone()
+                        .unwrap_or_else(|| format!("MCP prompt from {}", server))
+                        .into(),
+                }
+            }));
+    }
+
     pub fn show(&mut self, query: &str) {
         self.query = query.to_string();
         self.visible = true;
@@ -155,14 +175,14 @@ impl AutocompletePopup {
                 }
 
                 spans.push(Span::styled(
-                    cmd.primary,
+                    cmd.primary.clone(),
                     Style::default()
                         .fg(theme.accent_color)
                         .add_modifier(Modifier::BOLD),
                 ));
                 spans.push(Span::raw("  "));
                 spans.push(Span::styled(
-                    cmd.description,
+                    cmd.description.clone(),
                     Style::default().fg(theme.text_color),
                 ));
 
@@ -235,79 +255,119 @@ fn fuzzy_match(text: &str, pattern: &str) -> Option<i32> {
 pub fn get_all_commands() -> Vec<CommandSuggestion> {
     vec![
         CommandSuggestion {
-            primary: "/home",
+            primary: "/home".into(),
             aliases: vec![],
-            description: "Return to start menu",
+            description: "Return to start menu".into(),
         },
         CommandSuggestion {
-            primary: "/load",
+            primary: "/load".into(),
             aliases: vec![],
-            description: "Load previous session",
+            description: "Load previous session".into(),
         },
         CommandSuggestion {
-            primary: "/model",
+            primary: "/model".into(),
             aliases: vec![],
-            description: "Select AI model",
+            description: "Select AI model".into(),
         },
         CommandSuggestion {
-            primary: "/auth",
+            primary: "/auth".into(),
             aliases: vec![],
-            description: "Manage API providers",
+            description: "Manage API providers".into(),
         },
         CommandSuggestion {
-            primary: "/init",
+            primary: "/init".into(),
             aliases: vec![],
-            description: "Initialize project (create KRAB.md)",
+            description: "Initialize project (create KRAB.md)".into(),
         },
         CommandSuggestion {
-            primary: "/theme",
+            primary: "/theme".into(),
             aliases: vec![],
-            description: "Change color theme",
+            description: "Change color theme".into(),
         },
         CommandSuggestion {
-            primary: "/clear",
+            primary: "/clear".into(),
             aliases: vec![],
-            description: "Clear chat messages",
+            description: "Clear chat messages".into(),
         },
         CommandSuggestion {
-            primary: "/pinch",
+            primary: "/pinch".into(),
             aliases: vec![],
-            description: "Continue in new session with context",
+            description: "Continue in new session with context".into(),
         },
         CommandSuggestion {
-            primary: "/cmd",
+            primary: "/cmd".into(),
             aliases: vec![],
-            description: "Show all controls",
+            description: "Show all controls".into(),
         },
         CommandSuggestion {
-            primary: "/terminal",
+            primary: "/terminal".into(),
             aliases: vec!["term", "shell"],
-            description: "Open interactive terminal",
+            description: "Open interactive terminal".into(),
         },
         CommandSuggestion {
-            primary: "/ps",
+            primary: "/ps".into(),
             aliases: vec!["processes"],
-            description: "View background processes",
+            description: "View background processes".into(),
+        },
+        CommandSuggestion {
+            primary: "/skills".into(),
+            aliases: vec![],
+            description: "Browse and manage skills".into(),
+        },
+        CommandSuggestion {
+            primary: "/plan".into(),
+            aliases: vec![],
+            description: "View or manage active plan".into(),
+        },
+        CommandSuggestion {
+            primary: "/mcp".into(),
+            aliases: vec![],
+            description: "Browse and manage MCP servers".into(),
+        },
+        CommandSuggestion {
+            primary: "/hooks".into(),
+            aliases: vec![],
+            description: "Configure tool execution hooks".into(),
         },
         CommandSuggestion {
-            primary: "/skills",
+            primary: "/permissions".into(),
+            aliases: vec!["perms"],
+            description: "Allow, ask or deny tool calls".into(),
+        },
+        CommandSuggestion {
+            primary: "/undo".into(),
+            aliases: vec![],
+            description: "Revert file changes from the last turn".into(),
+        },
+        CommandSuggestion {
+            primary: "/checkpoints".into(),
+            aliases: vec![],
+            description: "Restore files to an earlier turn".into(),
+        },
+        CommandSuggestion {
+            primary: "/cost".into(),
             aliases: vec![],
-            description: "Browse and manage skills",
+            description: "Show spend and set budget limits".into(),
         },
         CommandSuggestion {
-            primary: "/plan",
+            primary: "/summarize".into(),
             aliases: vec![],
-            description: "View or manage active plan",
+            description: "Summarise public symbols for code search".into(),
         },
         CommandSuggestion {
-            primary: "/mcp",
+            primary: "/memory".into(),
             aliases: vec![],
-            description: "Browse and manage MCP servers",
+            description: "Review, edit, pin or delete codebase insights".into(),
         },
         CommandSuggestion {
-            primary: "/hooks",
+            primary: "/export".into(),
             aliases: vec![],
-            description: "Configure tool execution hooks",
+            description: "Export session as Markdown, JSON or HTML".into(),
+        },
+        CommandSuggestion {
+            primary: "/import".into(),
+            aliases: vec![],
+            description: "Import a session from a JSON bundle".into(),
         },
     ]
 }
@@ -332,4 +392,21 @@ mod tests {
         let first = ac.get_selected().unwrap();
         assert_eq!(first.primary, "/model");
     }
+
+    #[test]
+    fn test_mcp_prompt_suggestions() {
+        let prompt = McpPrompt {
+            name: "review".to_string(),
+            description: Some("Review a file".to_string()),
+            arguments: vec![],
+        };
+        let mut ac = AutocompletePopup::new();
+        ac.set_mcp_prompts(&[("docs".to_string(), prompt)]);
+        ac.show("docs:rev");
+        assert_eq!(ac.get_selected().unwrap().primary, "/docs:review");
+
+        // Refreshing replaces earlier prompts rather than appending
+        ac.set_mcp_prompts(&[]);
+        assert_eq!(ac.suggestions.len(), get_all_commands().len());
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! Triggered by `@` in input, similar to slash command autocomplete.
 //! Two modes:
-//! - Fuzzy search: type to filter files
+//! - Fuzzy search: type to filter files and MCP resources
 //! - Tree browser: expandable/collapsible directory tree
 
 use ratatui::{
@@ -15,7 +15,9 @@ use ratatui::{
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+use super::image_parser::MCP_RESOURCE_PREFIX;
 use crate::tui::themes::Theme;
+use krusty_core::mcp::McpResource;
 
 /// File search mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -35,6 +37,8 @@ pub struct FileEntry {
     pub name: String,
     /// Is this a directory?
     pub is_dir: bool,
+    /// Is this an MCP resource? (`path` is then `mcp:server:uri`)
+    pub is_resource: bool,
 }
 
 /// A visible entry in the tree (includes depth for indentation)
@@ -85,7 +89,9 @@ pub struct FileSearchPopup {
     pub query: String,
     /// All indexed files
     files: Vec<FileEntry>,
-    /// Filtered results with scores (fuzzy mode)
+    /// Resources from connected MCP servers (searched after files)
+    resources: Vec<FileEntry>,
+    /// Filtered results with scores (fuzzy mode), indexing files then resources
     filtered: Vec<(usize, i32)>,
     /// Selected index (fuzzy mode)
     pub selected: usize,
@@ -107,6 +113,7 @@ impl FileSearchPopup {
             mode: FileSearchMode::Fuzzy,
             query: String::new(),
             files: Vec::new(),
+            resources: Vec::new(),
             filtered: Vec::new(),
             selected: 0,
             scroll_offset: 0,
@@ -158,6 +165,7 @@ impl FileSearchPopup {
                 path: rel.to_string_lossy().into_owned(),
                 name,
                 is_dir,
+                is_resource: false,
             });
 
             // Recurse into directories
@@ -167,6 +175,26 @@ impl FileSearchPopup {
         }
     }
 
+    /// Replace the MCP resources offered for `@server:uri` references
+    pub fn set_mcp_resources(&mut self, resources: Vec<(String, McpResource)>) {
+        self.resources = resources
+            .into_iter()
+            .map(|(server, resource)| FileEntry {
+                path: format!("{}{}:{}", MCP_RESOURCE_PREFIX, server, resource.uri),
+                name: resource.name,
+                is_dir: false,
+                is_resource: true,
+            })
+            .collect();
+    }
+
+    /// Entry by index across files and resources
+    fn entry(&self, idx: usize) -> Option<&FileEntry> {
+        self.files
+            .get(idx)
+            .or_else(|| self.resources.get(idx.checked_sub(self.files.len())?))
+    }
+
     /// Show the popup with a query
     pub fn show(&mut self, query: &str) {
         self.query = query.to_string();
@@ -427,7 +455,7 @@ impl FileSearchPopup {
             FileSearchMode::Fuzzy => self
                 .filtered
                 .get(self.selected)
-                .and_then(|(idx, _)| self.files.get(*idx))
+                .and_then(|(idx, _)| self.entry(*idx))
                 .map(|f| f.path.as_str()),
             FileSearchMode::Tree => self
                 .tree
@@ -452,6 +480,7 @@ impl FileSearchPopup {
             self.filtered = self
                 .files
                 .iter()
+                .chain(&self.resources)
                 .enumerate()
                 .filter(|(_, f)| !f.is_dir) // Only files in empty query
                 .take(100)
@@ -463,7 +492,7 @@ impl FileSearchPopup {
         let query = self.query.to_lowercase();
         let mut scored: Vec<(usize, i32)> = Vec::new();
 
-        for (idx, file) in self.files.iter().enumerate() {
+        for (idx, file) in self.files.iter().chain(&self.resources).enumerate() {
             let path_lower = file.path.to_lowercase();
             let name_lower = file.name.to_lowercase();
 
@@ -583,7 +612,9 @@ impl FileSearchPopup {
                 .enumerate()
             {
                 let actual_idx = self.scroll_offset + display_idx;
-                let file = &self.files[*file_idx];
+                let Some(file) = self.entry(*file_idx) else {
+                    continue;
+                };
                 let is_selected = actual_idx == self.selected;
 
                 let mut spans = vec![];
@@ -596,7 +627,26 @@ impl FileSearchPopup {
                 }
 
                 // Directory indicator
-                if file.is_dir {
+                if file.is_resource {
+                    let reference = file
+                        .path
+                        .strip_prefix(MCP_RESOURCE_PREFIX)
+                        .unwrap_or(&file.path);
+                    spans.push(Span::styled(
+                        reference.to_string(),
+                        Style::default()
+                            .fg(theme.accent_color)
+                            .add_modifier(if is_selected {
+                                Modifier::BOLD
+                            } else {
+                                Modifier::empty()
+                            }),
+                    ));
+                    spans.push(Span::styled(
+                        format!("  {}", file.name),
+                        Style::default().fg(theme.dim_color),
+                    ));
+                } else if file.is_dir {
                     spans.push(Span::styled(
                         format!("{}/", file.path),
                         Style::default()
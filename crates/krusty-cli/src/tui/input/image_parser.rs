@@ -5,6 +5,7 @@
 //! - Bracketed paths: [/path/to/file.pdf]
 //! - Raw paths: /path/to/file.pdf (when pasted)
 //! - URLs: https://example.com/image.png
+//! - MCP resources: [mcp:server:uri]
 
 use once_cell::sync::Lazy;
 use regex::Regex;
@@ -21,6 +22,23 @@ pub enum InputSegment {
     ImageUrl(String),
     /// Clipboard image reference (from paste)
     ClipboardImage(String),
+    /// Resource on a connected MCP server
+    McpResource { server: String, uri: String },
+}
+
+/// Prefix of bracketed MCP resource references
+pub const MCP_RESOURCE_PREFIX: &str = "mcp:";
+
+/// Split `server:uri` from an MCP resource reference
+fn parse_mcp_resource(inner: &str) -> Option<InputSegment> {
+    let (server, uri) = inner.strip_prefix(MCP_RESOURCE_PREFIX)?.split_once(':')?;
+    if server.is_empty() || uri.is_empty() {
+        return None;
+    }
+    Some(InputSegment::McpResource {
+        server: server.to_string(),
+        uri: uri.to_string(),
+    })
 }
 
 // Bracketed patterns: [path] or [url]
@@ -40,6 +58,7 @@ static RAW_PATH_PATTERN: Lazy<Regex> = Lazy::new(|| {
 /// - `[/absolute/path/image.jpg]` - bracketed absolute paths
 /// - `[https://example.com/image.png]` - bracketed URLs
 /// - `[clipboard:id]` - clipboard image references
+/// - `[mcp:server:uri]` - MCP resources
 /// - `/path/to/file.pdf` - raw paths (when pasted)
 /// - `./file.png` - raw relative paths
 pub fn parse_input(text: &str, working_dir: &Path) -> Vec<InputSegment> {
@@ -55,6 +74,8 @@ pub fn parse_input(text: &str, working_dir: &Path) -> Vec<InputSegment> {
             InputSegment::ImageUrl(inner.to_string())
         } else if inner.starts_with("clipboard:") {
             InputSegment::ClipboardImage(inner.to_string())
+        } else if let Some(resource) = parse_mcp_resource(inner) {
+            resource
         } else if has_supported_extension(inner) {
             let path = if Path::new(inner).is_absolute() {
                 PathBuf::from(inner)
@@ -158,6 +179,7 @@ pub fn has_file_references(text: &str) -> bool {
         inner.starts_with("http://")
             || inner.starts_with("https://")
             || inner.starts_with("clipboard:")
+            || parse_mcp_resource(inner).is_some()
             || has_supported_extension(inner)
     })
 }
@@ -248,4 +270,17 @@ mod tests {
         assert!(matches!(&segments[0], InputSegment::Text(t) if t == "[note] check"));
         assert!(matches!(&segments[1], InputSegment::ImagePath(p) if p.ends_with("photo.jpg")));
     }
+
+    #[test]
+    fn test_parse_mcp_resource() {
+        let text = "summarize [mcp:docs:file:///guide.md] please";
+        let segments = parse_input(text, Path::new("/home"));
+        assert_eq!(segments.len(), 3);
+        assert!(matches!(
+            &segments[1],
+            InputSegment::McpResource { server, uri } if server == "docs" && uri == "file:///guide.md"
+        ));
+        assert!(has_file_references(text));
+        assert!(!has_file_references("[mcp:docs]"));
+    }
 }
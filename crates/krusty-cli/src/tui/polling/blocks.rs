@@ -4,6 +4,7 @@
 
 use std::path::Path;
 
+use crate::agent::{AgentEvent, AgentEventBus};
 use crate::plan::{PlanFile, PlanManager};
 use crate::tui::blocks::{BuildBlock, ExploreBlock, StreamBlock};
 use crate::tui::handlers::commands::generate_krab_from_exploration;
@@ -15,6 +16,7 @@ use super::{PollAction, PollResult};
 pub fn poll_explore_progress(
     channels: &mut AsyncChannels,
     explore_blocks: &mut [ExploreBlock],
+    event_bus: &AgentEventBus,
 ) -> PollResult {
     let mut result = PollResult::new();
 
@@ -26,6 +28,10 @@ pub fn poll_explore_progress(
         match rx.try_recv() {
             Ok(progress) => {
                 result.needs_redraw = true;
+                event_bus.emit(AgentEvent::SubAgentProgress {
+                    agent_type: "explore".to_string(),
+                    progress: progress.clone(),
+                });
                 // Find matching ExploreBlock by tool_use_id (derived from task_id prefix)
                 // Task IDs are like "dir-0", "file-1", "main" - we find the parent explore block
                 // by looking for blocks that are still streaming
@@ -56,6 +62,7 @@ pub fn poll_build_progress(
     build_blocks: &mut [BuildBlock],
     active_plan: &mut Option<PlanFile>,
     plan_manager: &PlanManager,
+    event_bus: &AgentEventBus,
 ) -> PollResult {
     let mut result = PollResult::new();
 
@@ -67,6 +74,10 @@ pub fn poll_build_progress(
         match rx.try_recv() {
             Ok(progress) => {
                 result.needs_redraw = true;
+                event_bus.emit(AgentEvent::SubAgentProgress {
+                    agent_type: "build".to_string(),
+                    progress: progress.clone(),
+                });
                 // Find matching BuildBlock that is still streaming
                 for block in build_blocks.iter_mut() {
                     if block.is_streaming() {
@@ -287,6 +298,7 @@ pub fn poll_indexing_progress(
             }
             Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => {
                 tracing::debug!("Indexing progress channel disconnected");
+                result = result.with_action(PollAction::StartIndexWatcher);
                 // Clear indexing progress when channel closes (indexing complete)
                 if let Some(ref explore_id) = init_explore_id {
                     for block in explore_blocks.iter_mut() {
@@ -1,6 +1,7 @@
 //! MCP status channel polling
 //!
-//! Handles status updates from background MCP connection tasks.
+//! Handles status updates from background MCP connection tasks and
+//! expanded prompts from MCP prompt slash commands.
 
 use crate::tui::popups::mcp_browser::McpBrowserPopup;
 use crate::tui::utils::AsyncChannels;
@@ -53,3 +54,31 @@ pub fn poll_mcp_status(
 
     result
 }
+
+/// Poll for an expanded MCP prompt
+///
+/// The prompt text is submitted as user input via `PollAction::SubmitInput`.
+pub fn poll_mcp_prompt(channels: &mut AsyncChannels) -> PollResult {
+    let mut result = PollResult::new();
+
+    let Some(mut rx) = channels.mcp_prompt.take() else {
+        return result;
+    };
+
+    match rx.try_recv() {
+        Ok(Ok(text)) => {
+            result.needs_redraw = true;
+            result = result.with_action(PollAction::SubmitInput(text));
+        }
+        Ok(Err(e)) => {
+            result.needs_redraw = true;
+            result = result.with_message("system", e);
+        }
+        Err(tokio::sync::oneshot::error::TryRecvError::Empty) => {
+            channels.mcp_prompt = Some(rx);
+        }
+        Err(tokio::sync::oneshot::error::TryRecvError::Closed) => {}
+    }
+
+    result
+}
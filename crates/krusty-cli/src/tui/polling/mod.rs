@@ -18,7 +18,7 @@ pub use blocks::{
     poll_build_progress, poll_explore_progress, poll_indexing_progress, poll_init_exploration,
 };
 pub use dual_mind::poll_dual_mind;
-pub use mcp::poll_mcp_status;
+pub use mcp::{poll_mcp_prompt, poll_mcp_status};
 pub use oauth::poll_oauth_status;
 pub use processes::poll_background_processes;
 
@@ -46,6 +46,10 @@ pub enum PollAction {
     RefreshAiTools,
     /// Switch to a provider (after OAuth success)
     SwitchProvider(ProviderId),
+    /// Submit text as if the user typed it (expanded MCP prompts)
+    SubmitInput(String),
+    /// Start the background index watcher (after /init indexing finishes)
+    StartIndexWatcher,
     /// Store /init exploration results as codebase insights
     StoreInitInsights {
         architecture: String,
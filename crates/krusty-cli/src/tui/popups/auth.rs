@@ -11,7 +11,7 @@ use ratatui::{
 use super::common::{
     center_rect, popup_block, popup_title, render_popup_background, scroll_indicator, PopupSize,
 };
-use crate::ai::providers::{builtin_providers, ProviderId};
+use crate::ai::providers::{all_providers, get_provider, ProviderId};
 use crate::tui::themes::Theme;
 use krusty_core::auth::AuthMethod;
 
@@ -101,7 +101,7 @@ impl AuthPopup {
     /// Navigate down in provider list
     pub fn next_provider(&mut self) {
         if let AuthState::ProviderSelection { selected_index, .. } = &mut self.state {
-            let providers = builtin_providers();
+            let providers = all_providers();
             if *selected_index < providers.len() - 1 {
                 *selected_index += 1;
                 self.ensure_visible(10); // Use reasonable visible height
@@ -125,10 +125,18 @@ impl AuthPopup {
     }
 
     /// Confirm provider selection - go to auth method selection or API key input
-    pub fn confirm_provider(&mut self) {
+    ///
+    /// Returns the provider if it needs no credentials and is ready to use.
+    pub fn confirm_provider(&mut self) -> Option<ProviderId> {
         if let AuthState::ProviderSelection { selected_index, .. } = &self.state {
-            let providers = builtin_providers();
+            let providers = all_providers();
             if let Some(provider) = providers.get(*selected_index) {
+                if !provider.id.requires_api_key() {
+                    self.state = AuthState::Complete {
+                        provider: provider.id,
+                    };
+                    return Some(provider.id);
+                }
                 // Check if provider supports OAuth
                 if provider.id.supports_oauth() {
                     // Show auth method selection
@@ -146,6 +154,7 @@ impl AuthPopup {
                 }
             }
         }
+        None
     }
 
     /// Navigate up in auth method list
@@ -390,7 +399,7 @@ impl AuthPopup {
         f.render_widget(title, chunks[0]);
 
         // Provider list - simplified to one line per provider
-        let providers = builtin_providers();
+        let providers = all_providers();
         let mut lines = Vec::new();
 
         // Calculate visible height (content area minus potential scroll indicators)
@@ -749,6 +758,12 @@ impl AuthPopup {
             ProviderId::MiniMax => "https://platform.minimax.io/",
             ProviderId::Kimi => "https://platform.moonshot.cn/",
             ProviderId::OpenAI => "https://platform.openai.com/api-keys",
+            ProviderId::Custom(_) => get_provider(provider).map_or("", |p| p.base_url.as_str()),
+        };
+        let url_label = if provider.is_custom() {
+            "Endpoint: "
+        } else {
+            "Get your key from: "
         };
 
         let instructions = Paragraph::new(vec![
@@ -762,7 +777,7 @@ impl AuthPopup {
             ]),
             Line::from(""),
             Line::from(vec![
-                Span::raw("Get your key from: "),
+                Span::raw(url_label),
                 Span::styled(
                     url,
                     Style::default()
@@ -846,10 +861,15 @@ impl AuthPopup {
         let title = Paragraph::new(title_lines).alignment(Alignment::Center);
         f.render_widget(title, chunks[0]);
 
+        let message = if provider.requires_api_key() {
+            "API key saved successfully!"
+        } else {
+            "No API key needed."
+        };
         let content = vec![
             Line::from(""),
             Line::from(Span::styled(
-                "API key saved successfully!",
+                message,
                 Style::default()
                     .fg(theme.success_color)
                     .add_modifier(Modifier::BOLD),
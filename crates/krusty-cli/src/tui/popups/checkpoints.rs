@@ -0,0 +1,283 @@
+//! Checkpoints popup - preview and restore files from earlier turns
+//!
+//! Shared by `/checkpoints`, `/undo` and "restore files to here" on a user
+//! message. The diff of the selected turn is always shown before anything
+//! is written.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::common::{
+    center_rect, popup_block, popup_title, render_popup_background, scroll_indicator,
+};
+use super::scroll::ScrollState;
+use crate::storage::Checkpoint;
+use crate::tools::FileRestore;
+use crate::tui::themes::Theme;
+use crate::tui::utils::truncate_ellipsis;
+
+/// Rows given to the checkpoint list; the rest shows the diff
+const LIST_HEIGHT: usize = 6;
+
+/// Checkpoints popup state
+pub struct CheckpointsPopup {
+    title: String,
+    /// Checkpoints, newest first
+    pub checkpoints: Vec<Checkpoint>,
+    scroll: ScrollState,
+    /// Changes restoring the selected checkpoint would make
+    preview: Vec<FileRestore>,
+    /// Rendered diff lines for the preview
+    diff_lines: Vec<String>,
+    diff_scroll: usize,
+    error: Option<String>,
+}
+
+impl Default for CheckpointsPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CheckpointsPopup {
+    pub fn new() -> Self {
+        Self {
+            title: "Checkpoints".to_string(),
+            checkpoints: Vec::new(),
+            scroll: ScrollState::new(0),
+            preview: Vec::new(),
+            diff_lines: Vec::new(),
+            diff_scroll: 0,
+            error: None,
+        }
+    }
+
+    /// Show `checkpoints` (oldest first, as stored)
+    ///
+    /// Selects the oldest checkpoint at or after `from_turn`, or the newest.
+    pub fn open(
+        &mut self,
+        title: &str,
+        mut checkpoints: Vec<Checkpoint>,
+        from_turn: Option<usize>,
+    ) {
+        checkpoints.reverse();
+        self.title = title.to_string();
+        self.scroll = ScrollState::new(checkpoints.len());
+        self.scroll.set_visible_height(LIST_HEIGHT);
+        if let Some(idx) = from_turn.and_then(|t| checkpoints.iter().rposition(|c| c.turn >= t)) {
+            self.scroll.selected = idx;
+            self.scroll.ensure_visible();
+        }
+        self.checkpoints = checkpoints;
+        self.set_preview(Vec::new());
+        self.error = None;
+    }
+
+    pub fn next(&mut self) {
+        self.scroll.next();
+    }
+
+    pub fn prev(&mut self) {
+        self.scroll.prev();
+    }
+
+    pub fn get_selected(&self) -> Option<&Checkpoint> {
+        self.checkpoints.get(self.scroll.selected)
+    }
+
+    /// Replace the diff preview for the selected checkpoint
+    pub fn set_preview(&mut self, preview: Vec<FileRestore>) {
+        self.diff_lines = preview
+            .iter()
+            .flat_map(|restore| {
+                let mut lines = vec![format!("{} {}", restore.action(), restore.path.display())];
+                lines.extend(restore.diff().lines().map(str::to_string));
+                lines.push(String::new());
+                lines
+            })
+            .collect();
+        self.preview = preview;
+        self.diff_scroll = 0;
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn scroll_diff_down(&mut self, amount: usize) {
+        let max = self.diff_lines.len().saturating_sub(1);
+        self.diff_scroll = (self.diff_scroll + amount).min(max);
+    }
+
+    pub fn scroll_diff_up(&mut self, amount: usize) {
+        self.diff_scroll = self.diff_scroll.saturating_sub(amount);
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let width = 90.min(f.area().width.saturating_sub(4));
+        let height = 34.min(f.area().height.saturating_sub(2));
+        let area = center_rect(width, height, f.area());
+        render_popup_background(f, area, theme);
+
+        let block = popup_block(theme);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),                      // Title
+                Constraint::Length(LIST_HEIGHT as u16 + 2), // Checkpoint list
+                Constraint::Min(3),                         // Diff preview
+                Constraint::Length(2),                      // Footer
+            ])
+            .split(inner);
+
+        let title = Paragraph::new(popup_title(&self.title, theme)).alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        self.render_list(f, chunks[1], theme);
+        self.render_diff(f, chunks[2], theme);
+
+        let key_style = Style::default()
+            .fg(theme.accent_color)
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text_color);
+        let footer = Paragraph::new(Line::from(vec![
+            Span::styled("↑↓", key_style),
+            Span::styled(": turn  ", text_style),
+            Span::styled("PgUp/PgDn", key_style),
+            Span::styled(": scroll diff  ", text_style),
+            Span::styled(
+                "Enter",
+                Style::default()
+                    .fg(theme.warning_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": restore  ", text_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": close", text_style),
+        ]))
+        .alignment(Alignment::Center);
+        f.render_widget(footer, chunks[3]);
+    }
+
+    fn render_list(&self, f: &mut Frame, area: ratatui::layout::Rect, theme: &Theme) {
+        let mut lines: Vec<Line> = Vec::new();
+
+        if self.checkpoints.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  No checkpoints in this session yet",
+                Style::default()
+                    .fg(theme.dim_color)
+                    .add_modifier(Modifier::ITALIC),
+            )));
+        } else {
+            if self.scroll.offset > 0 {
+                lines.push(scroll_indicator("up", self.scroll.offset, theme));
+            }
+            let label_width = (area.width as usize).saturating_sub(34);
+            for idx in self.scroll.visible_range() {
+                let checkpoint = &self.checkpoints[idx];
+                let is_selected = self.scroll.is_selected(idx);
+                let style = if is_selected {
+                    Style::default()
+                        .fg(theme.accent_color)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(theme.text_color)
+                };
+                let label = checkpoint.label.lines().next().unwrap_or_default();
+                let files = checkpoint.files.len();
+
+                lines.push(Line::from(vec![
+                    Span::styled(if is_selected { "▶ " } else { "  " }, style),
+                    Span::styled(format!("#{:<4}", checkpoint.turn), style),
+                    Span::styled(truncate_ellipsis(label, label_width), style),
+                    Span::styled(
+                        format!(
+                            "  {} file{} · {}",
+                            files,
+                            if files == 1 { "" } else { "s" },
+                            checkpoint
+                                .created_at
+                                .with_timezone(&chrono::Local)
+                                .format("%b %d %H:%M")
+                        ),
+                        Style::default().fg(theme.dim_color),
+                    ),
+                ]));
+            }
+            if self.scroll.items_below() > 0 {
+                lines.push(scroll_indicator("down", self.scroll.items_below(), theme));
+            }
+        }
+
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn render_diff(&self, f: &mut Frame, area: ratatui::layout::Rect, theme: &Theme) {
+        let width = (area.width as usize).saturating_sub(2);
+        let mut lines: Vec<Line> = Vec::new();
+
+        if let Some(ref error) = self.error {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", error),
+                Style::default().fg(theme.error_color),
+            )));
+        } else if self.checkpoints.is_empty() {
+            // Nothing selected
+        } else if self.diff_lines.is_empty() {
+            lines.push(Line::from(Span::styled(
+                " Files already match this checkpoint",
+                Style::default()
+                    .fg(theme.dim_color)
+                    .add_modifier(Modifier::ITALIC),
+            )));
+        } else {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    " Restoring will change {} file{}:",
+                    self.preview.len(),
+                    if self.preview.len() == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(theme.warning_color),
+            )));
+            let visible = (area.height as usize).saturating_sub(1);
+            for line in self.diff_lines.iter().skip(self.diff_scroll).take(visible) {
+                let style = if line.starts_with("+++") || line.starts_with("---") {
+                    Style::default().fg(theme.dim_color)
+                } else if line.starts_with('+') {
+                    Style::default()
+                        .fg(theme.diff_add_color)
+                        .bg(theme.diff_add_bg_color)
+                } else if line.starts_with('-') {
+                    Style::default()
+                        .fg(theme.diff_remove_color)
+                        .bg(theme.diff_remove_bg_color)
+                } else if line.starts_with("@@") {
+                    Style::default().fg(theme.accent_color)
+                } else if line.starts_with(' ') || line.is_empty() {
+                    Style::default().fg(theme.diff_context_color)
+                } else {
+                    // File header ("revert path", "delete path", ...)
+                    Style::default()
+                        .fg(theme.title_color)
+                        .add_modifier(Modifier::BOLD)
+                };
+                lines.push(Line::from(Span::styled(
+                    format!(" {}", truncate_ellipsis(line, width)),
+                    style,
+                )));
+            }
+        }
+
+        f.render_widget(Paragraph::new(lines), area);
+    }
+}
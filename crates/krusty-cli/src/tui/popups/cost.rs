@@ -0,0 +1,277 @@
+//! Cost popup - spend for this session and today, per model and source
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::common::{
+    center_rect, popup_block, popup_title, render_popup_background, scroll_indicator,
+};
+use crate::ai::cost::{format_cost, BudgetLimits};
+use crate::storage::UsageSummary;
+use crate::tui::themes::Theme;
+use crate::tui::utils::truncate_ellipsis;
+
+/// Cost popup state
+pub struct CostPopup {
+    session: Vec<UsageSummary>,
+    today: Vec<UsageSummary>,
+    session_cost: f64,
+    day_cost: f64,
+    limits: BudgetLimits,
+    /// Show today's breakdown instead of the session's
+    show_today: bool,
+    scroll: usize,
+}
+
+impl Default for CostPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CostPopup {
+    pub fn new() -> Self {
+        Self {
+            session: Vec::new(),
+            today: Vec::new(),
+            session_cost: 0.0,
+            day_cost: 0.0,
+            limits: BudgetLimits::default(),
+            show_today: false,
+            scroll: 0,
+        }
+    }
+
+    pub fn open(
+        &mut self,
+        session: Vec<UsageSummary>,
+        today: Vec<UsageSummary>,
+        session_cost: f64,
+        day_cost: f64,
+        limits: BudgetLimits,
+    ) {
+        self.session = session;
+        self.today = today;
+        self.session_cost = session_cost;
+        self.day_cost = day_cost;
+        self.limits = limits;
+        self.show_today = false;
+        self.scroll = 0;
+    }
+
+    /// Switch between the session and today's breakdown
+    pub fn toggle_scope(&mut self) {
+        self.show_today = !self.show_today;
+        self.scroll = 0;
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.rows().len() {
+            self.scroll += 1;
+        }
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    fn rows(&self) -> &[UsageSummary] {
+        if self.show_today {
+            &self.today
+        } else {
+            &self.session
+        }
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let width = 90.min(f.area().width.saturating_sub(4));
+        let height = 26.min(f.area().height.saturating_sub(2));
+        let area = center_rect(width, height, f.area());
+        render_popup_background(f, area, theme);
+
+        let block = popup_block(theme);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Length(4), // Totals
+                Constraint::Min(3),    // Breakdown
+                Constraint::Length(2), // Footer
+            ])
+            .split(inner);
+
+        let title = Paragraph::new(popup_title("Cost", theme)).alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        self.render_totals(f, chunks[1], theme);
+        self.render_breakdown(f, chunks[2], theme);
+
+        let key_style = Style::default()
+            .fg(theme.accent_color)
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text_color);
+        let footer = Paragraph::new(Line::from(vec![
+            Span::styled("Tab", key_style),
+            Span::styled(": session/today  ", text_style),
+            Span::styled("↑↓", key_style),
+            Span::styled(": scroll  ", text_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": close", text_style),
+        ]))
+        .alignment(Alignment::Center);
+        f.render_widget(footer, chunks[3]);
+    }
+
+    fn render_totals(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let label_style = Style::default().fg(theme.dim_color);
+        let value_style = Style::default()
+            .fg(theme.text_color)
+            .add_modifier(Modifier::BOLD);
+        let total_line = |label: &str, cost: f64, soft: Option<f64>, hard: Option<f64>| {
+            let over = hard.is_some_and(|l| cost >= l) || soft.is_some_and(|l| cost >= l);
+            let mut spans = vec![
+                Span::styled(format!(" {:<9}", label), label_style),
+                Span::styled(
+                    format!("{:<10}", format_cost(cost)),
+                    if over {
+                        value_style.fg(theme.warning_color)
+                    } else {
+                        value_style
+                    },
+                ),
+            ];
+            let limits: Vec<String> = [("soft", soft), ("hard", hard)]
+                .into_iter()
+                .filter_map(|(kind, limit)| limit.map(|l| format!("{} {}", kind, format_cost(l))))
+                .collect();
+            if !limits.is_empty() {
+                spans.push(Span::styled(
+                    format!("budget: {}", limits.join(", ")),
+                    label_style,
+                ));
+            }
+            Line::from(spans)
+        };
+
+        let lines = vec![
+            total_line(
+                "Session",
+                self.session_cost,
+                self.limits.session_soft,
+                self.limits.session_hard,
+            ),
+            total_line(
+                "Today",
+                self.day_cost,
+                self.limits.daily_soft,
+                self.limits.daily_hard,
+            ),
+            Line::default(),
+            Line::from(vec![
+                Span::styled(" Breakdown: ", label_style),
+                Span::styled(
+                    if self.show_today { "today" } else { "session" },
+                    Style::default()
+                        .fg(theme.accent_color)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+        ];
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn render_breakdown(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let rows = self.rows();
+        let header_style = Style::default()
+            .fg(theme.dim_color)
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text_color);
+        let dim_style = Style::default().fg(theme.dim_color);
+
+        let model_width = (area.width as usize).saturating_sub(58).max(12);
+        let mut lines = vec![Line::from(Span::styled(
+            format!(
+                " {:<mw$} {:<9} {:>5} {:>8} {:>8} {:>15} {:>9}",
+                "Model",
+                "Source",
+                "Reqs",
+                "Input",
+                "Output",
+                "Cache read/wr",
+                "Cost",
+                mw = model_width
+            ),
+            header_style,
+        ))];
+
+        if rows.is_empty() {
+            lines.push(Line::from(Span::styled(
+                " No API requests recorded yet",
+                dim_style.add_modifier(Modifier::ITALIC),
+            )));
+            f.render_widget(Paragraph::new(lines), area);
+            return;
+        }
+
+        let visible = (area.height as usize).saturating_sub(3);
+        if self.scroll > 0 {
+            lines.push(scroll_indicator("up", self.scroll, theme));
+        }
+        for row in rows.iter().skip(self.scroll).take(visible) {
+            let cost = match (row.cost_usd, row.unpriced_requests) {
+                (_, n) if n == row.requests => "—".to_string(),
+                (c, 0) => format_cost(c),
+                (c, _) => format!("{}+", format_cost(c)),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!(
+                        " {:<mw$}",
+                        truncate_ellipsis(&row.model, model_width),
+                        mw = model_width
+                    ),
+                    text_style,
+                ),
+                Span::styled(
+                    format!(
+                        " {:<9} {:>5} {:>8} {:>8} {:>15}",
+                        row.source,
+                        row.requests,
+                        format_tokens(row.input_tokens),
+                        format_tokens(row.output_tokens),
+                        format!(
+                            "{}/{}",
+                            format_tokens(row.cache_read_tokens),
+                            format_tokens(row.cache_write_tokens)
+                        ),
+                    ),
+                    dim_style,
+                ),
+                Span::styled(format!(" {:>9}", cost), text_style),
+            ]));
+        }
+        let below = rows.len().saturating_sub(self.scroll + visible);
+        if below > 0 {
+            lines.push(scroll_indicator("down", below, theme));
+        }
+
+        f.render_widget(Paragraph::new(lines), area);
+    }
+}
+
+/// Compact token count (e.g. 12.3k, 1.2M)
+fn format_tokens(tokens: usize) -> String {
+    match tokens {
+        0..=999 => tokens.to_string(),
+        1_000..=999_999 => format!("{:.1}k", tokens as f64 / 1_000.0),
+        _ => format!("{:.1}M", tokens as f64 / 1_000_000.0),
+    }
+}
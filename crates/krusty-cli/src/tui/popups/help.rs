@@ -153,6 +153,14 @@ impl HelpPopup {
             ("/pinch", "Compress context to new session"),
             ("/plan", "View/manage active plan"),
             ("/mcp", "Browse and manage MCP servers"),
+            ("/permissions", "Allow/ask/deny rules for tools"),
+            ("/undo", "Revert last turn's file changes"),
+            ("/checkpoints", "Restore files to an earlier turn"),
+            ("/cost", "Spend breakdown and budgets"),
+            ("/summarize", "Summarise indexed symbols"),
+            ("/memory", "Codebase insights in context"),
+            ("/export", "Export session (md/json/html)"),
+            ("/import", "Import a session bundle"),
             ("/skills", "Browse skills"),
             ("/ps", "View background processes"),
             ("/terminal", "Open interactive terminal"),
@@ -162,10 +162,19 @@ impl HooksPopup {
     pub fn confirm_type(&mut self) {
         if let HooksStage::SelectType { selected_index } = &self.stage {
             let types = UserHookType::all();
-            if let Some(hook_type) = types.get(*selected_index) {
-                self.stage = HooksStage::EnterMatcher {
-                    hook_type: *hook_type,
-                    input: String::new(),
+            if let Some(&hook_type) = types.get(*selected_index) {
+                self.stage = if hook_type.matcher_label().is_some() {
+                    HooksStage::EnterMatcher {
+                        hook_type,
+                        input: String::new(),
+                    }
+                } else {
+                    // Nothing to match against; the hook runs on every event
+                    HooksStage::EnterCommand {
+                        hook_type,
+                        tool_pattern: ".*".to_string(),
+                        input: String::new(),
+                    }
                 };
             }
         }
@@ -246,9 +255,17 @@ impl HooksPopup {
                 tool_pattern,
                 ..
             } => {
-                self.stage = HooksStage::EnterMatcher {
-                    hook_type: *hook_type,
-                    input: tool_pattern.clone(),
+                self.stage = if hook_type.matcher_label().is_some() {
+                    HooksStage::EnterMatcher {
+                        hook_type: *hook_type,
+                        input: tool_pattern.clone(),
+                    }
+                } else {
+                    let selected_index = UserHookType::all()
+                        .iter()
+                        .position(|t| t == hook_type)
+                        .unwrap_or(0);
+                    HooksStage::SelectType { selected_index }
                 };
             }
             HooksStage::Confirm {
@@ -610,7 +627,7 @@ impl HooksPopup {
             .split(inner);
 
         // Title
-        let title_lines = popup_title("Enter Tool Matcher", theme);
+        let title_lines = popup_title("Enter Matcher", theme);
         let title = Paragraph::new(title_lines).alignment(Alignment::Center);
         f.render_widget(title, chunks[0]);
 
@@ -625,8 +642,9 @@ impl HooksPopup {
         f.render_widget(type_line, chunks[1]);
 
         // Input field
+        let input_title = format!("{} (regex)", hook_type.matcher_label().unwrap_or("Pattern"));
         let input_block = Block::default()
-            .title("Tool pattern (regex)")
+            .title(input_title)
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
             .border_style(Style::default().fg(theme.border_color));
@@ -651,23 +669,23 @@ impl HooksPopup {
             f.render_widget(error_widget, chunks[3]);
         }
 
-        // Available tools hint
+        // Example matchers for this event
         let mut hint_lines = vec![Line::from(Span::styled(
             "Example matchers:",
             Style::default().fg(theme.dim_color),
         ))];
-        hint_lines.push(Line::from(vec![
-            Span::styled("  Write", Style::default().fg(theme.text_color)),
-            Span::styled(" (single tool)", Style::default().fg(theme.dim_color)),
-        ]));
-        hint_lines.push(Line::from(vec![
-            Span::styled("  Write|Edit", Style::default().fg(theme.text_color)),
-            Span::styled(" (multiple tools)", Style::default().fg(theme.dim_color)),
-        ]));
-        hint_lines.push(Line::from(vec![
-            Span::styled("  .*", Style::default().fg(theme.text_color)),
-            Span::styled(" (all tools)", Style::default().fg(theme.dim_color)),
-        ]));
+        for (pattern, meaning) in matcher_examples(hook_type) {
+            hint_lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {}", pattern),
+                    Style::default().fg(theme.text_color),
+                ),
+                Span::styled(
+                    format!(" ({})", meaning),
+                    Style::default().fg(theme.dim_color),
+                ),
+            ]));
+        }
 
         let hints = Paragraph::new(hint_lines);
         f.render_widget(hints, chunks[4]);
@@ -778,12 +796,26 @@ impl HooksPopup {
             )),
             Line::from(vec![
                 Span::styled("  0", Style::default().fg(theme.success_color)),
-                Span::styled(" = continue silently", Style::default().fg(theme.dim_color)),
+                Span::styled(
+                    if hook_type == UserHookType::UserPromptSubmit {
+                        " = continue, stdout added as context"
+                    } else {
+                        " = continue silently"
+                    },
+                    Style::default().fg(theme.dim_color),
+                ),
             ]),
             Line::from(vec![
                 Span::styled("  2", Style::default().fg(theme.error_color)),
                 Span::styled(
-                    " = block tool, show stderr to model",
+                    match hook_type {
+                        UserHookType::PreToolUse | UserHookType::PostToolUse => {
+                            " = block tool, show stderr to model"
+                        }
+                        UserHookType::UserPromptSubmit => " = block prompt, show stderr",
+                        UserHookType::PrePinch => " = block pinch, show stderr",
+                        _ => " = ignored",
+                    },
                     Style::default().fg(theme.dim_color),
                 ),
             ]),
@@ -902,3 +934,34 @@ impl HooksPopup {
         f.render_widget(footer, chunks[2]);
     }
 }
+
+/// Example matchers shown while entering a pattern
+fn matcher_examples(hook_type: UserHookType) -> [(&'static str, &'static str); 3] {
+    match hook_type {
+        UserHookType::Notification => [
+            ("permission_request", "tool needs approval"),
+            ("toast", "status toasts"),
+            (".*", "all notifications"),
+        ],
+        UserHookType::SessionStart => [
+            ("new", "new sessions"),
+            ("resume", "resumed sessions"),
+            (".*", "all sessions"),
+        ],
+        UserHookType::SubagentStop => [
+            ("explore", "explore agents"),
+            ("build", "builder agents"),
+            (".*", "all sub-agents"),
+        ],
+        UserHookType::PrePinch => [
+            ("manual", "/pinch"),
+            ("auto", "context limit reached"),
+            (".*", "every pinch"),
+        ],
+        _ => [
+            ("Write", "single tool"),
+            ("Write|Edit", "multiple tools"),
+            (".*", "all tools"),
+        ],
+    }
+}
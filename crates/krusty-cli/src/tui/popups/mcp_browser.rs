@@ -1,6 +1,7 @@
 //! MCP server browser popup
 //!
-//! Browse and manage MCP servers.
+//! Browse and manage MCP servers and the tools, resources and prompts they
+//! expose.
 
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout},
@@ -15,7 +16,7 @@ use super::common::{
     scroll_indicator, PopupSize,
 };
 use crate::tui::themes::Theme;
-use krusty_core::mcp::{McpServerInfo, McpServerStatus, McpToolDef};
+use krusty_core::mcp::{McpServerInfo, McpServerStatus};
 
 /// MCP browser popup state
 pub struct McpBrowserPopup {
@@ -177,13 +178,12 @@ impl McpBrowserPopup {
                 lines.push(render_server_line(server, is_selected, theme));
                 displayed += 1;
 
-                if is_expanded && displayed < visible_height {
-                    for (tool_idx, tool) in server.tools.iter().enumerate() {
+                if is_expanded {
+                    for line in render_server_details(server, theme) {
                         if displayed >= visible_height {
                             break;
                         }
-                        let is_last = tool_idx == server.tools.len() - 1;
-                        lines.push(render_tool_line(tool, is_last, theme));
+                        lines.push(line);
                         displayed += 1;
                     }
                 }
@@ -272,7 +272,19 @@ fn render_server_line<'a>(server: &McpServerInfo, is_selected: bool, theme: &The
     };
 
     let status_text = match &server.status {
-        McpServerStatus::Connected => format!("{} tools", server.tool_count),
+        McpServerStatus::Connected => {
+            let mut counts = vec![format!("{} tools", server.tool_count)];
+            if !server.resources.is_empty() || !server.resource_templates.is_empty() {
+                counts.push(format!(
+                    "{} resources",
+                    server.resources.len() + server.resource_templates.len()
+                ));
+            }
+            if !server.prompts.is_empty() {
+                counts.push(format!("{} prompts", server.prompts.len()));
+            }
+            counts.join(", ")
+        }
         McpServerStatus::Disconnected => "disconnected".to_string(),
         McpServerStatus::Error(e) => {
             let msg = server.error.as_deref().unwrap_or(e);
@@ -300,19 +312,97 @@ fn render_server_line<'a>(server: &McpServerInfo, is_selected: bool, theme: &The
     ])
 }
 
-fn render_tool_line<'a>(tool: &McpToolDef, is_last: bool, theme: &Theme) -> Line<'a> {
+/// Tools, resources and prompts of an expanded server, grouped by section
+fn render_server_details<'a>(server: &McpServerInfo, theme: &Theme) -> Vec<Line<'a>> {
+    let tools: Vec<_> = server
+        .tools
+        .iter()
+        .map(|t| (t.name.clone(), t.description.clone()))
+        .collect();
+    let resources: Vec<_> = server
+        .resources
+        .iter()
+        .map(|r| (r.uri.clone(), Some(r.name.clone())))
+        .chain(
+            server
+                .resource_templates
+                .iter()
+                .map(|t| (t.uri_template.clone(), Some(t.name.clone()))),
+        )
+        .collect();
+    let prompts: Vec<_> = server
+        .prompts
+        .iter()
+        .map(|p| {
+            let args: Vec<_> = p
+                .arguments
+                .iter()
+                .map(|a| {
+                    if a.required {
+                        format!("<{}>", a.name)
+                    } else {
+                        format!("[{}]", a.name)
+                    }
+                })
+                .collect();
+            let mut name = format!("/{}:{}", server.name, p.name);
+            if !args.is_empty() {
+                name = format!("{} {}", name, args.join(" "));
+            }
+            (name, p.description.clone())
+        })
+        .collect();
+
+    let sections = [
+        ("tools", tools),
+        ("resources", resources),
+        ("prompts", prompts),
+    ];
+    // Only label sections when there is more than tools to show
+    let labelled = sections.iter().skip(1).any(|(_, items)| !items.is_empty());
+
+    let mut lines = Vec::new();
+    for (label, items) in &sections {
+        if items.is_empty() {
+            continue;
+        }
+        if labelled {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", label),
+                Style::default()
+                    .fg(theme.dim_color)
+                    .add_modifier(Modifier::ITALIC),
+            )));
+        }
+        for (idx, (name, description)) in items.iter().enumerate() {
+            let is_last = idx == items.len() - 1;
+            lines.push(render_item_line(
+                name,
+                description.as_deref(),
+                is_last,
+                theme,
+            ));
+        }
+    }
+    lines
+}
+
+fn render_item_line<'a>(
+    name: &str,
+    description: Option<&str>,
+    is_last: bool,
+    theme: &Theme,
+) -> Line<'a> {
     let prefix = if is_last {
         "    └─ "
     } else {
         "    ├─ "
     };
 
-    let desc = tool
-        .description
-        .as_deref()
+    let desc = description
         .map(|d| {
-            if d.len() > 40 {
-                format!(" - {}...", &d[..37])
+            if d.chars().count() > 40 {
+                format!(" - {}...", d.chars().take(37).collect::<String>())
             } else {
                 format!(" - {}", d)
             }
@@ -321,7 +411,7 @@ fn render_tool_line<'a>(tool: &McpToolDef, is_last: bool, theme: &Theme) -> Line
 
     Line::from(vec![
         Span::styled(prefix.to_string(), Style::default().fg(theme.dim_color)),
-        Span::styled(tool.name.clone(), Style::default().fg(theme.text_color)),
+        Span::styled(name.to_string(), Style::default().fg(theme.text_color)),
         Span::styled(desc, Style::default().fg(theme.dim_color)),
     ])
 }
@@ -0,0 +1,275 @@
+//! Memory popup - review, edit, pin or delete codebase insights
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::common::{
+    center_rect, popup_block, popup_title, render_popup_background, scroll_indicator,
+};
+use crate::tui::themes::Theme;
+use crate::tui::utils::truncate_ellipsis;
+use krusty_core::index::CodebaseInsight;
+
+/// Memory popup state
+pub struct MemoryPopup {
+    insights: Vec<CodebaseInsight>,
+    /// How many of the top insights are injected into requests
+    in_context: usize,
+    selected: usize,
+    /// Text being edited for the selected insight
+    editing: Option<String>,
+}
+
+impl Default for MemoryPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryPopup {
+    pub fn new() -> Self {
+        Self {
+            insights: Vec::new(),
+            in_context: 0,
+            selected: 0,
+            editing: None,
+        }
+    }
+
+    pub fn open(&mut self, insights: Vec<CodebaseInsight>, in_context: usize) {
+        self.insights = insights;
+        self.in_context = in_context;
+        self.selected = 0;
+        self.editing = None;
+    }
+
+    /// Replace the list after a change, keeping the selection in range
+    pub fn set_insights(&mut self, insights: Vec<CodebaseInsight>) {
+        self.insights = insights;
+        self.selected = self.selected.min(self.insights.len().saturating_sub(1));
+        self.editing = None;
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.insights.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn selected(&self) -> Option<&CodebaseInsight> {
+        self.insights.get(self.selected)
+    }
+
+    // =========================================================================
+    // Editing
+    // =========================================================================
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    pub fn start_edit(&mut self) {
+        self.editing = self.selected().map(|insight| insight.content.clone());
+    }
+
+    pub fn cancel_edit(&mut self) {
+        self.editing = None;
+    }
+
+    pub fn add_char(&mut self, c: char) {
+        if let Some(input) = &mut self.editing {
+            input.push(c);
+        }
+    }
+
+    pub fn backspace(&mut self) {
+        if let Some(input) = &mut self.editing {
+            input.pop();
+        }
+    }
+
+    /// The selected insight's id and its edited text, ending the edit
+    pub fn take_edit(&mut self) -> Option<(String, String)> {
+        let input = self.editing.take()?;
+        let id = self.selected()?.id.clone();
+        Some((id, input.trim().to_string()))
+    }
+
+    // =========================================================================
+    // Rendering
+    // =========================================================================
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let width = 90.min(f.area().width.saturating_sub(4));
+        let height = 28.min(f.area().height.saturating_sub(2));
+        let area = center_rect(width, height, f.area());
+        render_popup_background(f, area, theme);
+
+        let block = popup_block(theme);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Length(2), // Summary
+                Constraint::Min(4),    // List or editor
+                Constraint::Length(2), // Footer
+            ])
+            .split(inner);
+
+        let title = Paragraph::new(popup_title("Memory", theme)).alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        let pinned = self.insights.iter().filter(|i| i.pinned).count();
+        let summary = format!(
+            " {} insights, {} pinned. The top {} are injected into every request.",
+            self.insights.len(),
+            pinned,
+            self.in_context.min(self.insights.len())
+        );
+        f.render_widget(
+            Paragraph::new(Span::styled(summary, Style::default().fg(theme.dim_color))),
+            chunks[1],
+        );
+
+        match &self.editing {
+            Some(input) => self.render_editor(f, chunks[2], theme, input),
+            None => self.render_list(f, chunks[2], theme),
+        }
+
+        let key_style = Style::default()
+            .fg(theme.accent_color)
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text_color);
+        let keys: &[(&str, &str)] = if self.is_editing() {
+            &[("Enter", ": save  "), ("Esc", ": cancel")]
+        } else {
+            &[
+                ("↑↓", ": navigate  "),
+                ("e", ": edit  "),
+                ("p", ": pin  "),
+                ("d", ": delete  "),
+                ("Esc", ": close"),
+            ]
+        };
+        let spans: Vec<Span> = keys
+            .iter()
+            .flat_map(|(key, label)| {
+                [
+                    Span::styled(*key, key_style),
+                    Span::styled(*label, text_style),
+                ]
+            })
+            .collect();
+        let footer = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+        f.render_widget(footer, chunks[3]);
+    }
+
+    fn render_list(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let dim_style = Style::default().fg(theme.dim_color);
+        if self.insights.is_empty() {
+            let lines = vec![
+                Line::default(),
+                Line::from(Span::styled(
+                    "  No insights yet. They are learned at pinch, when you leave a session,",
+                    dim_style,
+                )),
+                Line::from(Span::styled("  and by /init.", dim_style)),
+            ];
+            f.render_widget(Paragraph::new(lines), area);
+            return;
+        }
+
+        // Two lines per insight, with a line each for the scroll indicators
+        let visible = (area.height as usize).saturating_sub(2) / 2;
+        let visible = visible.max(1);
+        let start = (self.selected + 1).saturating_sub(visible);
+        let content_width = (area.width as usize).saturating_sub(6);
+
+        let mut lines = Vec::new();
+        if start > 0 {
+            lines.push(scroll_indicator("up", start, theme));
+        } else {
+            lines.push(Line::default());
+        }
+
+        for (i, insight) in self.insights.iter().enumerate().skip(start).take(visible) {
+            let is_selected = i == self.selected;
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.accent_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_color)
+            };
+            let (marker, marker_color) = if insight.pinned {
+                ("◆", theme.accent_color)
+            } else if i < self.in_context {
+                ("●", theme.success_color)
+            } else {
+                ("○", theme.dim_color)
+            };
+
+            lines.push(Line::from(vec![
+                Span::styled(if is_selected { "› " } else { "  " }, style),
+                Span::styled(format!("{} ", marker), Style::default().fg(marker_color)),
+                Span::styled(format!("{:<14}", insight.insight_type.as_str()), style),
+                Span::styled(
+                    format!(
+                        "{:>3.0}%  used {}×",
+                        insight.confidence * 100.0,
+                        insight.access_count
+                    ),
+                    dim_style,
+                ),
+            ]));
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(
+                    truncate_ellipsis(&insight.content, content_width).into_owned(),
+                    if is_selected {
+                        Style::default().fg(theme.text_color)
+                    } else {
+                        dim_style
+                    },
+                ),
+            ]));
+        }
+
+        let remaining = self.insights.len().saturating_sub(start + visible);
+        if remaining > 0 {
+            lines.push(scroll_indicator("down", remaining, theme));
+        }
+
+        f.render_widget(Paragraph::new(lines), area);
+    }
+
+    fn render_editor(&self, f: &mut Frame, area: Rect, theme: &Theme, input: &str) {
+        let title = self
+            .selected()
+            .map(|insight| format!("Edit {} insight", insight.insight_type.as_str()))
+            .unwrap_or_else(|| "Edit insight".to_string());
+        let input_block = Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.border_color));
+
+        let editor = Paragraph::new(format!("{}▏", input))
+            .style(Style::default().fg(theme.text_color))
+            .wrap(Wrap { trim: false })
+            .block(input_block);
+        f.render_widget(editor, area);
+    }
+}
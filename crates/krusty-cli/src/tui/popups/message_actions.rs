@@ -0,0 +1,181 @@
+//! Message actions popup
+//!
+//! Opened by right-clicking a user or assistant message: fork the session
+//! from that message, optionally restoring files, or restore files only.
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::common::{
+    center_content, center_rect, popup_block, popup_title, render_popup_background, PopupSize,
+};
+use crate::tui::themes::Theme;
+use crate::tui::utils::truncate_ellipsis;
+
+/// Something to do with a chat message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageAction {
+    /// New session with the history up to this message
+    Fork,
+    /// Fork and restore files to how they were at this message
+    ForkRestoringFiles,
+    /// Review and restore files to before this message
+    RestoreFiles,
+}
+
+impl MessageAction {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Fork => "Fork from here",
+            Self::ForkRestoringFiles => "Fork from here and restore files",
+            Self::RestoreFiles => "Restore files to here",
+        }
+    }
+
+    fn description(self, is_user: bool) -> &'static str {
+        match (self, is_user) {
+            (Self::Fork, true) => "Retry this message in a new session",
+            (Self::Fork, false) => "New session ending with this reply",
+            (Self::ForkRestoringFiles, _) => "Also rewind later file changes",
+            (Self::RestoreFiles, _) => "Review and rewind files only",
+        }
+    }
+}
+
+/// Message actions popup state
+pub struct MessageActionsPopup {
+    /// Index of the message in `chat.messages`
+    pub msg_idx: usize,
+    is_user: bool,
+    preview: String,
+    actions: Vec<MessageAction>,
+    selected: usize,
+}
+
+impl Default for MessageActionsPopup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageActionsPopup {
+    pub fn new() -> Self {
+        Self {
+            msg_idx: 0,
+            is_user: false,
+            preview: String::new(),
+            actions: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Open for a message; restoring files alone is only offered on user messages
+    pub fn open(&mut self, msg_idx: usize, is_user: bool, content: &str) {
+        self.msg_idx = msg_idx;
+        self.is_user = is_user;
+        self.preview = content
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        self.actions = vec![MessageAction::Fork, MessageAction::ForkRestoringFiles];
+        if is_user {
+            self.actions.push(MessageAction::RestoreFiles);
+        }
+        self.selected = 0;
+    }
+
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.actions.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn get_selected(&self) -> Option<MessageAction> {
+        self.actions.get(self.selected).copied()
+    }
+
+    pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        let (w, _) = PopupSize::Small.dimensions();
+        let h = 9 + 2 * self.actions.len() as u16;
+        let area = center_rect(w, h, f.area());
+        render_popup_background(f, area, theme);
+
+        let block = popup_block(theme);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(3),    // Content
+                Constraint::Length(2), // Footer
+            ])
+            .split(inner);
+
+        let title = if self.is_user {
+            "Your Message"
+        } else {
+            "Assistant Message"
+        };
+        let title = Paragraph::new(popup_title(title, theme)).alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        let content_area = center_content(chunks[1], 2);
+        let width = content_area.width.saturating_sub(4) as usize;
+        let mut lines = vec![
+            Line::from(Span::styled(
+                format!("  {}", truncate_ellipsis(&self.preview, width)),
+                Style::default()
+                    .fg(theme.dim_color)
+                    .add_modifier(Modifier::ITALIC),
+            )),
+            Line::from(""),
+        ];
+        for (idx, action) in self.actions.iter().enumerate() {
+            let style = if idx == self.selected {
+                Style::default()
+                    .fg(theme.accent_color)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_color)
+            };
+            let prefix = if idx == self.selected { "▶ " } else { "  " };
+            lines.push(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(action.label(), style),
+            ]));
+            lines.push(Line::from(Span::styled(
+                format!("    {}", action.description(self.is_user)),
+                Style::default().fg(theme.dim_color),
+            )));
+        }
+        f.render_widget(Paragraph::new(lines), content_area);
+
+        let key_style = Style::default()
+            .fg(theme.accent_color)
+            .add_modifier(Modifier::BOLD);
+        let text_style = Style::default().fg(theme.text_color);
+        let footer = Paragraph::new(Line::from(vec![
+            Span::styled("↑↓", key_style),
+            Span::styled(": select  ", text_style),
+            Span::styled("Enter", key_style),
+            Span::styled(": confirm  ", text_style),
+            Span::styled("Esc", key_style),
+            Span::styled(": cancel", text_style),
+        ]))
+        .alignment(Alignment::Center);
+        f.render_widget(footer, chunks[2]);
+    }
+}
@@ -8,11 +8,15 @@
 //! - Theme-aware colors
 
 pub mod auth;
+pub mod checkpoints;
 pub mod common;
+pub mod cost;
 pub mod file_preview;
 pub mod help;
 pub mod hooks;
 pub mod mcp_browser;
+pub mod memory;
+pub mod message_actions;
 pub mod model_select;
 pub mod pinch;
 pub mod process_list;
@@ -11,15 +11,79 @@ use ratatui::{
 use super::common::{
     center_rect, popup_block, popup_title, render_popup_background, scroll_indicator, PopupSize,
 };
-use crate::process::{ProcessInfo, ProcessStatus};
+use crate::process::{
+    LogChunk, OutputLine, OutputStream, ProcessId, ProcessInfo, ProcessStatus, DEFAULT_BUFFER_LINES,
+};
 use crate::tui::themes::Theme;
 use crate::tui::utils::truncate_ellipsis;
 
+/// Live output view for a single process
+pub struct ProcessLogView {
+    pub process_id: ProcessId,
+    pub title: String,
+    pub lines: Vec<OutputLine>,
+    /// Offset to request the next chunk from
+    pub next_offset: u64,
+    /// Lines scrolled up from the bottom (0 = following new output)
+    pub scroll_from_bottom: usize,
+}
+
+impl ProcessLogView {
+    fn new(process: &ProcessInfo) -> Self {
+        Self {
+            process_id: process.id.clone(),
+            title: process
+                .description
+                .clone()
+                .unwrap_or_else(|| process.command.clone()),
+            lines: Vec::new(),
+            next_offset: 0,
+            scroll_from_bottom: 0,
+        }
+    }
+
+    /// Append newly captured lines, keeping the scroll position stable
+    pub fn append(&mut self, chunk: LogChunk) -> bool {
+        if chunk.lines.is_empty() {
+            return false;
+        }
+        if self.scroll_from_bottom > 0 {
+            self.scroll_from_bottom += chunk.lines.len();
+        }
+        self.next_offset = chunk.next_offset;
+        self.lines.extend(chunk.lines);
+        if self.lines.len() > DEFAULT_BUFFER_LINES {
+            let excess = self.lines.len() - DEFAULT_BUFFER_LINES;
+            self.lines.drain(..excess);
+        }
+        self.scroll_from_bottom = self.scroll_from_bottom.min(self.lines.len());
+        true
+    }
+
+    pub fn scroll_up(&mut self, amount: usize) {
+        self.scroll_from_bottom = (self.scroll_from_bottom + amount).min(self.lines.len());
+    }
+
+    pub fn scroll_down(&mut self, amount: usize) {
+        self.scroll_from_bottom = self.scroll_from_bottom.saturating_sub(amount);
+    }
+
+    pub fn follow(&mut self) {
+        self.scroll_from_bottom = 0;
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll_from_bottom = self.lines.len();
+    }
+}
+
 /// Process list popup state
 pub struct ProcessListPopup {
     pub selected_index: usize,
     pub scroll_offset: usize,
     pub processes: Vec<ProcessInfo>,
+    /// Open log view, if any
+    pub log_view: Option<ProcessLogView>,
 }
 
 impl Default for ProcessListPopup {
@@ -34,6 +98,7 @@ impl ProcessListPopup {
             selected_index: 0,
             scroll_offset: 0,
             processes: Vec::new(),
+            log_view: None,
         }
     }
 
@@ -87,7 +152,23 @@ impl ProcessListPopup {
         self.processes.get(self.selected_index)
     }
 
+    /// Open the log view for the selected process
+    pub fn open_logs(&mut self) {
+        if let Some(process) = self.get_selected() {
+            self.log_view = Some(ProcessLogView::new(process));
+        }
+    }
+
+    pub fn close_logs(&mut self) {
+        self.log_view = None;
+    }
+
     pub fn render(&self, f: &mut Frame, theme: &Theme) {
+        if let Some(view) = &self.log_view {
+            self.render_logs(view, f, theme);
+            return;
+        }
+
         let (w, h) = PopupSize::Medium.dimensions();
         let area = center_rect(w, h, f.area());
         render_popup_background(f, area, theme);
@@ -202,6 +283,13 @@ impl ProcessListPopup {
                     .add_modifier(Modifier::BOLD),
             ),
             Span::styled(": nav  ", Style::default().fg(theme.text_color)),
+            Span::styled(
+                "Enter",
+                Style::default()
+                    .fg(theme.accent_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": logs  ", Style::default().fg(theme.text_color)),
             Span::styled(
                 "s",
                 Style::default()
@@ -227,6 +315,94 @@ impl ProcessListPopup {
         .alignment(Alignment::Center);
         f.render_widget(footer, chunks[2]);
     }
+
+    /// Render the live output of one process
+    fn render_logs(&self, view: &ProcessLogView, f: &mut Frame, theme: &Theme) {
+        let (w, h) = PopupSize::Large.dimensions();
+        let area = center_rect(w, h, f.area());
+        render_popup_background(f, area, theme);
+
+        let block = popup_block(theme);
+        let inner = block.inner(area);
+        f.render_widget(block, area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // Title
+                Constraint::Min(5),    // Output
+                Constraint::Length(2), // Footer
+            ])
+            .split(inner);
+
+        let title = truncate_ellipsis(&view.title, 50);
+        let title_lines = popup_title(&title, theme);
+        f.render_widget(
+            Paragraph::new(title_lines).alignment(Alignment::Center),
+            chunks[0],
+        );
+
+        let visible_height = chunks[1].height as usize;
+        let width = (chunks[1].width as usize).saturating_sub(2);
+        let end = view.lines.len().saturating_sub(view.scroll_from_bottom);
+        let start = end.saturating_sub(visible_height);
+
+        let lines: Vec<Line> = if view.lines.is_empty() {
+            vec![Line::from(Span::styled(
+                "  No output yet".to_string(),
+                Style::default()
+                    .fg(theme.dim_color)
+                    .add_modifier(Modifier::ITALIC),
+            ))]
+        } else {
+            view.lines[start..end]
+                .iter()
+                .map(|line| {
+                    let color = match line.stream {
+                        OutputStream::Stdout => theme.text_color,
+                        OutputStream::Stderr => theme.error_color,
+                    };
+                    Line::from(Span::styled(
+                        format!(" {}", truncate_ellipsis(&line.text, width)),
+                        Style::default().fg(color),
+                    ))
+                })
+                .collect()
+        };
+        let content = Paragraph::new(lines).style(Style::default().bg(theme.bg_color));
+        f.render_widget(content, chunks[1]);
+
+        let follow_label = if view.scroll_from_bottom == 0 {
+            ": following  "
+        } else {
+            ": follow  "
+        };
+        let footer = Paragraph::new(Line::from(vec![
+            Span::styled(
+                "↑↓",
+                Style::default()
+                    .fg(theme.accent_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": scroll  ", Style::default().fg(theme.text_color)),
+            Span::styled(
+                "End",
+                Style::default()
+                    .fg(theme.accent_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(follow_label, Style::default().fg(theme.text_color)),
+            Span::styled(
+                "Esc",
+                Style::default()
+                    .fg(theme.accent_color)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(": back", Style::default().fg(theme.text_color)),
+        ]))
+        .alignment(Alignment::Center);
+        f.render_widget(footer, chunks[2]);
+    }
 }
 
 fn format_duration(d: std::time::Duration) -> String {
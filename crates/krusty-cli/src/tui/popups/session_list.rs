@@ -2,14 +2,18 @@
 //!
 //! TUI shows sessions for the current working directory only.
 //! User already knows where they are (they launched from there).
+//! Forks and pinch continuations are nested under the session they came
+//! from. `/` searches message contents instead, across all sessions of the
+//! directory or, with filters, any directory.
 
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use super::common::{
@@ -17,7 +21,9 @@ use super::common::{
     scroll_indicator, PopupSize,
 };
 use super::scroll::ScrollState;
+use crate::storage::{SearchFilter, SearchHit, HIGHLIGHT_END, HIGHLIGHT_START};
 use crate::tui::themes::Theme;
+use crate::tui::utils::truncate_ellipsis;
 
 /// Session metadata for display
 #[derive(Debug, Clone)]
@@ -25,6 +31,49 @@ pub struct SessionInfo {
     pub id: String,
     pub title: String,
     pub updated_at: String,
+    /// Nesting level in the session tree (0 for top-level sessions)
+    pub depth: usize,
+    /// Forked from its parent rather than continued by a pinch
+    pub is_fork: bool,
+}
+
+/// Order sessions as a tree, each followed by its forks and pinch continuations
+///
+/// Sessions are expected newest first, which is kept at every level.
+/// Sessions whose parent isn't listed are shown at the top level.
+pub fn session_tree(sessions: Vec<crate::storage::SessionInfo>) -> Vec<SessionInfo> {
+    let ids: HashSet<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+    let mut roots = Vec::new();
+    let mut children: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (idx, session) in sessions.iter().enumerate() {
+        match session.parent_session_id.as_deref() {
+            Some(parent) if ids.contains(parent) && parent != session.id => {
+                children.entry(parent).or_default().push(idx)
+            }
+            _ => roots.push(idx),
+        }
+    }
+
+    let mut tree = Vec::with_capacity(sessions.len());
+    let mut visited = HashSet::new();
+    let mut stack: Vec<(usize, usize)> = roots.into_iter().rev().map(|idx| (idx, 0)).collect();
+    while let Some((idx, depth)) = stack.pop() {
+        if !visited.insert(idx) {
+            continue;
+        }
+        let session = &sessions[idx];
+        if let Some(kids) = children.get(session.id.as_str()) {
+            stack.extend(kids.iter().rev().map(|&kid| (kid, depth + 1)));
+        }
+        tree.push(SessionInfo {
+            id: session.id.clone(),
+            title: session.title.clone(),
+            updated_at: session.updated_at.format("%Y-%m-%d %H:%M").to_string(),
+            depth,
+            is_fork: session.fork_point.is_some(),
+        });
+    }
+    tree
 }
 
 /// Session list popup state
@@ -34,6 +83,13 @@ pub struct SessionListPopup {
     pub sessions: Vec<SessionInfo>,
     /// Current working directory (for title display)
     current_dir: Option<String>,
+    /// Search mode: typing searches message contents
+    pub search_active: bool,
+    pub search_query: String,
+    results: Vec<SearchHit>,
+    result_scroll: ScrollState,
+    /// Why the last search failed (e.g. an invalid filter)
+    search_error: Option<String>,
 }
 
 impl Default for SessionListPopup {
@@ -48,6 +104,11 @@ impl SessionListPopup {
             scroll: ScrollState::new(0),
             sessions: Vec::new(),
             current_dir: None,
+            search_active: false,
+            search_query: String::new(),
+            results: Vec::new(),
+            result_scroll: ScrollState::new(0),
+            search_error: None,
         }
     }
 
@@ -61,14 +122,63 @@ impl SessionListPopup {
         let count = sessions.len();
         self.sessions = sessions;
         self.scroll = ScrollState::new(count);
+        self.search_active = false;
+        self.search_query.clear();
+        self.set_results(Ok(Vec::new()));
     }
 
     pub fn next(&mut self) {
-        self.scroll.next();
+        if self.showing_results() {
+            self.result_scroll.next();
+        } else {
+            self.scroll.next();
+        }
     }
 
     pub fn prev(&mut self) {
-        self.scroll.prev();
+        if self.showing_results() {
+            self.result_scroll.prev();
+        } else {
+            self.scroll.prev();
+        }
+    }
+
+    /// Toggle search mode
+    pub fn toggle_search(&mut self) {
+        self.search_active = !self.search_active;
+        if !self.search_active {
+            self.search_query.clear();
+            self.set_results(Ok(Vec::new()));
+        }
+    }
+
+    pub fn add_search_char(&mut self, c: char) {
+        self.search_query.push(c);
+    }
+
+    pub fn backspace_search(&mut self) {
+        self.search_query.pop();
+    }
+
+    /// Whether search results are shown instead of the session list
+    pub fn showing_results(&self) -> bool {
+        self.search_active && !self.search_query.trim().is_empty()
+    }
+
+    /// Show the outcome of a search
+    pub fn set_results(&mut self, results: Result<Vec<SearchHit>, String>) {
+        let (results, error) = match results {
+            Ok(results) => (results, None),
+            Err(e) => (Vec::new(), Some(e)),
+        };
+        self.result_scroll = ScrollState::new(results.len());
+        self.results = results;
+        self.search_error = error;
+    }
+
+    /// Get selected search result
+    pub fn get_selected_result(&self) -> Option<&SearchHit> {
+        self.results.get(self.result_scroll.selected)
     }
 
     /// Get selected session
@@ -82,6 +192,13 @@ impl SessionListPopup {
         }
 
         let session = self.sessions.remove(self.scroll.selected);
+        // Its children become top-level sessions, as they are in the database
+        for child in self.sessions[self.scroll.selected..]
+            .iter_mut()
+            .take_while(|s| s.depth > session.depth)
+        {
+            child.depth -= session.depth + 1;
+        }
         tracing::info!(session_id = %session.id, title = %session.title, "Removed session from list");
 
         // Update scroll state with new count
@@ -90,7 +207,12 @@ impl SessionListPopup {
     }
 
     pub fn render(&mut self, f: &mut Frame, theme: &Theme) {
-        let (w, h) = PopupSize::Medium.dimensions();
+        let size = if self.search_active {
+            PopupSize::Large
+        } else {
+            PopupSize::Medium
+        };
+        let (w, h) = size.dimensions();
         let area = center_rect(w, h, f.area());
         render_popup_background(f, area, theme);
 
@@ -101,13 +223,29 @@ impl SessionListPopup {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // Title
-                Constraint::Min(5),    // Content
-                Constraint::Length(2), // Footer
+                Constraint::Length(3),                                      // Title
+                Constraint::Length(if self.search_active { 2 } else { 0 }), // Search
+                Constraint::Min(5),                                         // Content
+                Constraint::Length(2),                                      // Footer
             ])
             .split(inner);
 
-        let visible_height = (chunks[1].height as usize).saturating_sub(2);
+        if self.search_active {
+            let search = Paragraph::new(Line::from(vec![
+                Span::styled("  / ", Style::default().fg(theme.accent_color)),
+                Span::styled(&self.search_query, Style::default().fg(theme.text_color)),
+                Span::styled("█", Style::default().fg(theme.accent_color)),
+            ]));
+            f.render_widget(search, chunks[1]);
+        }
+
+        if self.showing_results() {
+            self.render_results(f, chunks[0], chunks[2], theme);
+            self.render_footer(f, chunks[3], theme);
+            return;
+        }
+
+        let visible_height = (chunks[2].height as usize).saturating_sub(2);
         self.scroll.set_visible_height(visible_height);
 
         // Title with directory context
@@ -157,8 +295,18 @@ impl SessionListPopup {
                 };
 
                 let prefix = if is_selected { "▶ " } else { "  " };
+                let branch = if session.depth == 0 {
+                    String::new()
+                } else {
+                    format!("{}└ ", "  ".repeat(session.depth - 1))
+                };
+                let marker = if session.is_fork { "⑂ " } else { "" };
                 lines.push(Line::from(vec![
                     Span::styled(prefix.to_string(), style),
+                    Span::styled(
+                        format!("{}{}", branch, marker),
+                        Style::default().fg(theme.dim_color),
+                    ),
                     Span::styled(session.title.clone(), style),
                     Span::styled(
                         format!("  {}", session.updated_at),
@@ -175,46 +323,182 @@ impl SessionListPopup {
         }
 
         let content = Paragraph::new(lines).style(Style::default().bg(theme.bg_color));
-        let content_area = center_content(chunks[1], 4);
+        let content_area = center_content(chunks[2], 4);
         f.render_widget(content, content_area);
 
-        // Footer
-        let footer_text = vec![
-            Span::styled(
-                "↑↓",
-                Style::default()
-                    .fg(theme.accent_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(": navigate  ", Style::default().fg(theme.text_color)),
-            Span::styled(
-                "Enter",
-                Style::default()
-                    .fg(theme.accent_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(": load  ", Style::default().fg(theme.text_color)),
-            Span::styled(
-                "d",
-                Style::default()
-                    .fg(theme.accent_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(": delete  ", Style::default().fg(theme.text_color)),
-            Span::styled(
-                "Esc",
+        self.render_footer(f, chunks[3], theme);
+    }
+
+    /// Render search results: title line plus highlighted snippet per hit
+    fn render_results(&mut self, f: &mut Frame, title_area: Rect, area: Rect, theme: &Theme) {
+        let title = Paragraph::new(popup_title(
+            &format!("Search ({} matches)", self.results.len()),
+            theme,
+        ))
+        .alignment(Alignment::Center);
+        f.render_widget(title, title_area);
+
+        let content_area = center_content(area, 2);
+        let width = content_area.width as usize;
+        // Two lines per hit, minus the scroll indicators
+        let visible_hits = (content_area.height as usize).saturating_sub(2) / 2;
+        self.result_scroll.set_visible_height(visible_hits);
+
+        let dim = Style::default().fg(theme.dim_color);
+        let mut lines: Vec<Line> = Vec::new();
+        if let Some(error) = &self.search_error {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(theme.error_color),
+            )));
+        } else if self.results.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  No matching messages",
+                dim.add_modifier(Modifier::ITALIC),
+            )));
+        }
+
+        let items_above = self.result_scroll.items_above();
+        if items_above > 0 {
+            lines.push(scroll_indicator("up", items_above, theme));
+        }
+        for idx in self.result_scroll.visible_range() {
+            let hit = &self.results[idx];
+            let is_selected = self.result_scroll.is_selected(idx);
+            let title_style = if is_selected {
                 Style::default()
                     .fg(theme.accent_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(": cancel", Style::default().fg(theme.text_color)),
-        ];
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text_color)
+            };
+
+            let meta = format!(
+                "  {} · {}{}",
+                hit.created_at.format("%Y-%m-%d %H:%M"),
+                hit.role,
+                hit.model
+                    .as_deref()
+                    .map(|m| format!(" · {}", m))
+                    .unwrap_or_default()
+            );
+            let prefix = if is_selected { "▶ " } else { "  " };
+            let title_width = width.saturating_sub(meta.chars().count() + prefix.len());
+            lines.push(Line::from(vec![
+                Span::styled(prefix, title_style),
+                Span::styled(
+                    truncate_ellipsis(&hit.session_title, title_width).into_owned(),
+                    title_style,
+                ),
+                Span::styled(meta, dim),
+            ]));
+            lines.push(snippet_line(&hit.snippet, width, theme));
+        }
+        let items_below = self.result_scroll.items_below();
+        if items_below > 0 {
+            lines.push(scroll_indicator("down", items_below, theme));
+        }
+
+        f.render_widget(
+            Paragraph::new(lines).style(Style::default().bg(theme.bg_color)),
+            content_area,
+        );
+    }
+
+    fn render_footer(&self, f: &mut Frame, area: Rect, theme: &Theme) {
+        let key = Style::default()
+            .fg(theme.accent_color)
+            .add_modifier(Modifier::BOLD);
+        let text = Style::default().fg(theme.text_color);
+        let footer_text = if self.search_active {
+            vec![
+                Span::styled("↑↓", key),
+                Span::styled(": navigate  ", text),
+                Span::styled("Enter", key),
+                Span::styled(": open  ", text),
+                Span::styled("dir:all model: after: before:", key),
+                Span::styled(" filter  ", text),
+                Span::styled("Esc", key),
+                Span::styled(": close search", text),
+            ]
+        } else {
+            vec![
+                Span::styled("↑↓", key),
+                Span::styled(": navigate  ", text),
+                Span::styled("Enter", key),
+                Span::styled(": load  ", text),
+                Span::styled("/", key),
+                Span::styled(": search  ", text),
+                Span::styled("d", key),
+                Span::styled(": delete  ", text),
+                Span::styled("Esc", key),
+                Span::styled(": cancel", text),
+            ]
+        };
 
         let footer = Paragraph::new(Line::from(footer_text)).alignment(Alignment::Center);
-        f.render_widget(footer, chunks[2]);
+        f.render_widget(footer, area);
     }
 }
 
+/// One line of a search snippet with its matches highlighted
+fn snippet_line(snippet: &str, width: usize, theme: &Theme) -> Line<'static> {
+    let normal = Style::default().fg(theme.dim_color);
+    let highlight = Style::default()
+        .fg(theme.warning_color)
+        .add_modifier(Modifier::BOLD);
+
+    let mut spans = vec![Span::raw("    ")];
+    let mut remaining = width.saturating_sub(4);
+    let mut highlighted = false;
+    let flat = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    for (i, part) in flat.split([HIGHLIGHT_START, HIGHLIGHT_END]).enumerate() {
+        if i > 0 {
+            highlighted = !highlighted;
+        }
+        if part.is_empty() || remaining == 0 {
+            continue;
+        }
+        let part = truncate_ellipsis(part, remaining).into_owned();
+        remaining = remaining.saturating_sub(part.chars().count());
+        spans.push(Span::styled(
+            part,
+            if highlighted { highlight } else { normal },
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Split search input into query words and filters
+///
+/// Filters are `dir:<path>` (or `dir:all` for every directory),
+/// `model:<name>`, `after:<YYYY-MM-DD>` and `before:<YYYY-MM-DD>`. Without
+/// a `dir:` filter the search is limited to `default_dir`.
+pub fn parse_search(input: &str, default_dir: &str) -> Result<(String, SearchFilter), String> {
+    let parse_date = |value: &str| {
+        chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+            .map(|d| d.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc())
+            .map_err(|_| format!("Invalid date '{}', use YYYY-MM-DD", value))
+    };
+
+    let mut words = Vec::new();
+    let mut filter = SearchFilter {
+        working_dir: Some(default_dir.to_string()),
+        ..Default::default()
+    };
+    for word in input.split_whitespace() {
+        match word.split_once(':') {
+            Some(("dir", "all")) => filter.working_dir = None,
+            Some(("dir", dir)) if !dir.is_empty() => filter.working_dir = Some(dir.to_string()),
+            Some(("model", model)) if !model.is_empty() => filter.model = Some(model.to_string()),
+            Some(("after", date)) if !date.is_empty() => filter.since = Some(parse_date(date)?),
+            Some(("before", date)) if !date.is_empty() => filter.until = Some(parse_date(date)?),
+            _ => words.push(word),
+        }
+    }
+    Ok((words.join(" "), filter))
+}
+
 /// Shorten a path for display (show last 2-3 components)
 fn shorten_path(path: &str) -> String {
     let path = PathBuf::from(path);
@@ -228,3 +512,64 @@ fn shorten_path(path: &str) -> String {
     let last_three: PathBuf = components[components.len() - 3..].iter().collect();
     format!(".../{}", last_three.display())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_search_filters() {
+        let (query, filter) = parse_search("oauth refresh", "/repo").unwrap();
+        assert_eq!(query, "oauth refresh");
+        assert_eq!(filter.working_dir.as_deref(), Some("/repo"));
+
+        let (query, filter) =
+            parse_search("dir:all model:sonnet after:2026-01-02 token bug", "/repo").unwrap();
+        assert_eq!(query, "token bug");
+        assert_eq!(filter.working_dir, None);
+        assert_eq!(filter.model.as_deref(), Some("sonnet"));
+        assert_eq!(
+            filter.since.unwrap().format("%Y-%m-%d").to_string(),
+            "2026-01-02"
+        );
+
+        assert!(parse_search("before:yesterday", "/repo").is_err());
+    }
+
+    #[test]
+    fn test_session_tree_nests_children() {
+        let session = |id: &str, parent: Option<&str>, fork: bool| crate::storage::SessionInfo {
+            id: id.to_string(),
+            title: id.to_string(),
+            updated_at: chrono::Utc::now(),
+            token_count: None,
+            parent_session_id: parent.map(String::from),
+            working_dir: None,
+            user_id: None,
+            fork_point: fork.then_some(4),
+        };
+        // Newest first, as listed by the session manager
+        let tree = session_tree(vec![
+            session("fork-b", Some("root"), true),
+            session("other", None, false),
+            session("pinched", Some("fork-a"), false),
+            session("fork-a", Some("root"), true),
+            session("root", None, false),
+            session("orphan", Some("deleted"), true),
+        ]);
+
+        let shape: Vec<(&str, usize)> = tree.iter().map(|s| (s.id.as_str(), s.depth)).collect();
+        assert_eq!(
+            shape,
+            vec![
+                ("other", 0),
+                ("root", 0),
+                ("fork-b", 1),
+                ("fork-a", 1),
+                ("pinched", 2),
+                ("orphan", 0),
+            ]
+        );
+        assert!(tree[2].is_fork && !tree[4].is_fork);
+    }
+}
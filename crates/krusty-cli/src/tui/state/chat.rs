@@ -21,6 +21,9 @@ pub struct ChatState {
     pub current_activity: Option<String>,
     /// Cache for streaming assistant message index (avoids O(n) scan per delta)
     pub streaming_assistant_idx: Option<usize>,
+    /// First display message of each conversation message, as last built
+    /// from the conversation (used to jump to search results)
+    pub display_starts: Vec<usize>,
 }
 
 impl ChatState {
@@ -3,10 +3,12 @@
 //! Groups all popup controller states into a single component.
 
 use crate::tui::popups::{
-    auth::AuthPopup, file_preview::FilePreviewPopup, help::HelpPopup, hooks::HooksPopup,
-    mcp_browser::McpBrowserPopup, model_select::ModelSelectPopup, pinch::PinchPopup,
-    process_list::ProcessListPopup, session_list::SessionListPopup,
-    skills_browser::SkillsBrowserPopup, theme_select::ThemeSelectPopup,
+    auth::AuthPopup, checkpoints::CheckpointsPopup, cost::CostPopup,
+    file_preview::FilePreviewPopup, help::HelpPopup, hooks::HooksPopup,
+    mcp_browser::McpBrowserPopup, memory::MemoryPopup, message_actions::MessageActionsPopup,
+    model_select::ModelSelectPopup, pinch::PinchPopup, process_list::ProcessListPopup,
+    session_list::SessionListPopup, skills_browser::SkillsBrowserPopup,
+    theme_select::ThemeSelectPopup,
 };
 
 /// All popup controller states grouped together
@@ -22,6 +24,10 @@ pub struct PopupState {
     pub file_preview: FilePreviewPopup,
     pub skills: SkillsBrowserPopup,
     pub hooks: HooksPopup,
+    pub checkpoints: CheckpointsPopup,
+    pub cost: CostPopup,
+    pub memory: MemoryPopup,
+    pub message_actions: MessageActionsPopup,
 }
 
 impl PopupState {
@@ -41,6 +47,10 @@ impl PopupState {
             file_preview,
             skills: SkillsBrowserPopup::new(),
             hooks: HooksPopup::new(),
+            checkpoints: CheckpointsPopup::new(),
+            cost: CostPopup::new(),
+            memory: MemoryPopup::new(),
+            message_actions: MessageActionsPopup::new(),
         }
     }
 }
@@ -5,11 +5,11 @@
 use tokio::sync::{mpsc, oneshot};
 
 use crate::agent::subagent::AgentProgress;
-use crate::agent::SummarizationResult;
+use crate::agent::{PermissionRequest, SummarizationResult};
 use crate::ai::models::ModelMetadata;
 use crate::ai::types::Content;
 use crate::tools::ToolOutputChunk;
-use krusty_core::index::IndexProgress;
+use krusty_core::index::{IndexProgress, SummaryProgress, SummaryReport};
 
 /// AI-generated title update
 pub struct TitleUpdate {
@@ -86,6 +86,8 @@ pub struct DeviceCodeInfo {
 pub struct AsyncChannels {
     /// MCP status updates from background connection tasks
     pub mcp_status: Option<mpsc::UnboundedReceiver<McpStatusUpdate>>,
+    /// Expanded MCP prompt text (or error) for a prompt slash command
+    pub mcp_prompt: Option<oneshot::Receiver<Result<String, String>>>,
     /// Streaming bash output receiver
     pub bash_output: Option<mpsc::UnboundedReceiver<ToolOutputChunk>>,
     /// Pending tool execution results receiver
@@ -102,18 +104,28 @@ pub struct AsyncChannels {
     pub openrouter_models: Option<oneshot::Receiver<Result<Vec<ModelMetadata>, String>>>,
     /// OpenCode Zen model fetch result receiver
     pub opencodezen_models: Option<oneshot::Receiver<Result<Vec<ModelMetadata>, String>>>,
+    /// Custom provider model discovery result (number of models found)
+    pub custom_models: Option<oneshot::Receiver<Result<usize, String>>>,
     /// /init codebase exploration result receiver
     pub init_exploration: Option<oneshot::Receiver<InitExplorationResult>>,
     /// /init exploration progress updates
     pub init_progress: Option<mpsc::UnboundedReceiver<AgentProgress>>,
     /// /init indexing progress updates (file scanning/parsing before AI exploration)
     pub indexing_progress: Option<mpsc::UnboundedReceiver<IndexProgress>>,
+    /// Incremental index updates from the background index watcher
+    pub index_watch: Option<mpsc::UnboundedReceiver<IndexProgress>>,
+    /// /summarize background run progress
+    pub summary_progress: Option<mpsc::UnboundedReceiver<SummaryProgress>>,
+    /// /summarize background run result
+    pub summary_result: Option<oneshot::Receiver<Result<SummaryReport, String>>>,
     /// Auto-updater status updates
     pub update_status: Option<mpsc::UnboundedReceiver<krusty_core::updater::UpdateStatus>>,
     /// OAuth authentication status updates
     pub oauth_status: Option<mpsc::UnboundedReceiver<OAuthStatusUpdate>>,
     /// Dual-mind dialogue updates from tool execution
     pub dual_mind: Option<mpsc::UnboundedReceiver<DualMindUpdate>>,
+    /// Tool permission requests ("ask" rules) from the permission hook
+    pub permission_requests: Option<mpsc::UnboundedReceiver<PermissionRequest>>,
 }
 
 impl AsyncChannels {
@@ -6,6 +6,7 @@ mod channels;
 mod syntax;
 mod text;
 mod title;
+mod worktree;
 
 pub use channels::{
     AsyncChannels, DeviceCodeInfo, DualMindUpdate, InitExplorationResult, McpStatusUpdate,
@@ -14,3 +15,4 @@ pub use channels::{
 pub use syntax::highlight_code;
 pub use text::{count_wrapped_lines, truncate_ellipsis, wrap_line, wrap_text};
 pub use title::{TitleAction, TitleEditor};
+pub use worktree::AppWorktreeDelegate;
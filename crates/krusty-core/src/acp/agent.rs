@@ -2,7 +2,7 @@
 //!
 //! This is the core ACP agent that handles all protocol methods.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use agent_client_protocol::{
@@ -23,10 +23,16 @@ use super::bridge::NotificationBridge;
 use super::error::AcpError;
 use super::processor::PromptProcessor;
 use super::session::{SessionManager, SessionState};
+use crate::agent::{
+    HookEvent, UserHookExecutor, UserHookManager, UserPostToolHook, UserPreToolHook,
+};
+use crate::ai::custom_providers;
+use crate::ai::models::ModelMetadata;
 use crate::ai::opencodezen;
 use crate::ai::openrouter;
 use crate::ai::providers::{get_provider, ProviderId};
 use crate::storage::credentials::CredentialStore;
+use crate::storage::Database;
 use crate::tools::ToolRegistry;
 
 /// ACP protocol version supported by this agent (10 is current)
@@ -66,31 +72,32 @@ pub struct KrustyAgent {
 
 impl KrustyAgent {
     /// Create a new Krusty ACP agent
+    ///
+    /// User PreToolUse/PostToolUse hooks run on its tool registry once
+    /// loaded with [`KrustyAgent::load_user_hooks`].
     pub fn new() -> Self {
-        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
-        let tools = Arc::new(ToolRegistry::new());
-        Self {
-            sessions: Arc::new(SessionManager::new()),
-            tools: tools.clone(),
-            client_capabilities: RwLock::new(None),
-            api_key: RwLock::new(None),
-            processor: RwLock::new(PromptProcessor::new(tools, cwd.clone())),
-            notification_tx: RwLock::new(None),
-            current_model: RwLock::new(None),
-            available_models: RwLock::new(Vec::new()),
-            cwd,
-        }
+        let user_hooks = Arc::new(RwLock::new(UserHookManager::new()));
+        let mut tools = ToolRegistry::new();
+        tools.add_pre_hook(Arc::new(UserPreToolHook::new(user_hooks.clone())));
+        tools.add_post_hook(Arc::new(UserPostToolHook::new(user_hooks.clone())));
+        Self::build(Arc::new(tools), user_hooks)
     }
 
     /// Create with custom tool registry
     pub fn with_tools(tools: Arc<ToolRegistry>) -> Self {
+        Self::build(tools, Arc::new(RwLock::new(UserHookManager::new())))
+    }
+
+    fn build(tools: Arc<ToolRegistry>, user_hooks: Arc<RwLock<UserHookManager>>) -> Self {
         let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        let processor =
+            PromptProcessor::new(tools.clone(), cwd.clone()).with_user_hooks(user_hooks);
         Self {
             sessions: Arc::new(SessionManager::new()),
-            tools: tools.clone(),
+            tools,
             client_capabilities: RwLock::new(None),
             api_key: RwLock::new(None),
-            processor: RwLock::new(PromptProcessor::new(tools, cwd.clone())),
+            processor: RwLock::new(processor),
             notification_tx: RwLock::new(None),
             current_model: RwLock::new(None),
             available_models: RwLock::new(Vec::new()),
@@ -98,6 +105,37 @@ impl KrustyAgent {
         }
     }
 
+    /// Load user hooks from the database
+    pub async fn load_user_hooks(&self, db: &Database) {
+        let processor = self.processor.read().await;
+        let mut user_hooks = processor.user_hooks().write().await;
+        match user_hooks.load(db) {
+            Ok(()) => info!("Loaded {} user hooks", user_hooks.hooks().len()),
+            Err(e) => warn!("Failed to load user hooks: {}", e),
+        }
+    }
+
+    /// Connect the client's MCP servers and inject workspace context
+    ///
+    /// Shared by new and loaded sessions so both get the client's tools.
+    async fn prepare_session(&self, session: &SessionState, cwd: &Path) {
+        self.processor.write().await.set_cwd(cwd.to_path_buf());
+        session.connect_mcp_servers().await;
+        session
+            .add_system_context(build_workspace_context(cwd))
+            .await;
+        info!("Injected workspace context for: {:?}", cwd);
+    }
+
+    /// Run SessionStart hooks in the background
+    async fn fire_session_start(&self, session_id: &SessionId, cwd: &Path, source: &str) {
+        let user_hooks = self.processor.read().await.user_hooks().clone();
+        let event = HookEvent::session_start(&session_id.to_string(), cwd, source);
+        tokio::spawn(async move {
+            UserHookExecutor::fire(&user_hooks, &event).await;
+        });
+    }
+
     /// Detect all available models from configured providers
     /// Returns: Vec<(model_id, provider, actual_model_id, api_key, display_name)>
     pub async fn detect_available_models(
@@ -126,7 +164,7 @@ impl KrustyAgent {
                 continue;
             }
 
-            if let Some(api_key) = store.get(&provider) {
+            if let Some(ref api_key) = store.api_key(&provider) {
                 match provider {
                     // Dynamic providers - fetch models from API
                     ProviderId::OpenRouter => {
@@ -213,6 +251,33 @@ impl KrustyAgent {
                             }
                         }
                     }
+                    // Custom providers with model discovery
+                    ProviderId::Custom(_)
+                        if get_provider(provider).is_some_and(|p| p.dynamic_models) =>
+                    {
+                        let Some(provider_config) = get_provider(provider) else {
+                            continue;
+                        };
+                        let fetched = custom_providers::fetch_models(provider_config, api_key)
+                            .await
+                            .unwrap_or_else(|e| {
+                                warn!("Failed to fetch {} models: {}", provider, e);
+                                provider_config
+                                    .models
+                                    .iter()
+                                    .map(|m| ModelMetadata::from_model_info(provider_config, m))
+                                    .collect()
+                            });
+                        for model in fetched {
+                            models.push((
+                                format!("{}:{}", provider.storage_key(), model.id),
+                                provider,
+                                model.id,
+                                api_key.clone(),
+                                model.display_name,
+                            ));
+                        }
+                    }
                     // Static providers - use hardcoded models
                     _ => {
                         if let Some(provider_config) = get_provider(provider) {
@@ -323,10 +388,11 @@ impl KrustyAgent {
         caps.load_session = true;
         caps.session_capabilities = SessionCapabilities::new();
 
-        // MCP capabilities
+        // MCP capabilities - client servers connect over stdio, Streamable
+        // HTTP or legacy SSE
         let mut mcp_caps = McpCapabilities::new();
-        mcp_caps.http = false;
-        mcp_caps.sse = false;
+        mcp_caps.http = true;
+        mcp_caps.sse = true;
         caps.mcp_capabilities = mcp_caps;
 
         caps
@@ -454,10 +520,6 @@ impl Agent for KrustyAgent {
             mcp_servers.len()
         );
 
-        // Update the processor's working directory to match the session's cwd
-        // This ensures tools execute in the correct directory (the project open in Zed)
-        self.processor.write().await.set_cwd(cwd.clone());
-
         // Pass as Option to our session manager which handles defaults
         let session = self.sessions.create_session(
             Some(cwd.clone()),
@@ -468,10 +530,9 @@ impl Agent for KrustyAgent {
             },
         );
 
-        // Build and inject workspace context so the AI understands the codebase
-        let workspace_context = build_workspace_context(&cwd);
-        session.add_system_context(workspace_context).await;
-        info!("Injected workspace context for: {:?}", cwd);
+        // Point tools at the project open in the client, connect its MCP
+        // servers and tell the AI about the codebase
+        self.prepare_session(&session, &cwd).await;
 
         // Detect available models from all configured providers
         let detected_models = self.detect_available_models().await;
@@ -539,6 +600,8 @@ impl Agent for KrustyAgent {
         // Send available slash commands
         self.send_available_commands(&session.id).await;
 
+        self.fire_session_start(&session.id, &cwd, "new").await;
+
         Ok(response)
     }
 
@@ -547,7 +610,18 @@ impl Agent for KrustyAgent {
     /// Attempts to load a session from persistent storage if available.
     /// Falls back to creating a new session if not found.
     async fn load_session(&self, request: LoadSessionRequest) -> AcpResult<LoadSessionResponse> {
-        info!("ACP load_session: id={}", request.session_id);
+        info!(
+            "ACP load_session: id={}, cwd={:?}, mcp_servers={}",
+            request.session_id,
+            request.cwd,
+            request.mcp_servers.len()
+        );
+        let cwd = request.cwd;
+        let mcp_servers = if request.mcp_servers.is_empty() {
+            None
+        } else {
+            Some(request.mcp_servers)
+        };
 
         // Check if session exists in memory
         if self.sessions.has_session(&request.session_id) {
@@ -572,15 +646,22 @@ impl Agent for KrustyAgent {
             // Create session and restore from storage
             match self
                 .sessions
-                .create_session_from_storage(&session_id_str, None, None)
+                .create_session_from_storage(
+                    &session_id_str,
+                    Some(cwd.clone()),
+                    mcp_servers.clone(),
+                )
                 .await
             {
                 Ok(session) => {
+                    self.prepare_session(&session, &cwd).await;
                     info!(
                         "Session {} restored from storage with {} messages",
                         session.id,
                         session.get_messages().await.len()
                     );
+                    self.fire_session_start(&session.id, &session.cwd, "resume")
+                        .await;
                     return Ok(LoadSessionResponse::new());
                 }
                 Err(e) => {
@@ -594,7 +675,8 @@ impl Agent for KrustyAgent {
             "Session {} not found in memory or storage, creating new session",
             request.session_id
         );
-        let _session = self.sessions.create_session(None, None);
+        let session = self.sessions.create_session(Some(cwd.clone()), mcp_servers);
+        self.prepare_session(&session, &cwd).await;
 
         Ok(LoadSessionResponse::new())
     }
@@ -852,4 +934,30 @@ mod tests {
 
         assert!(agent.sessions().has_session(&response.session_id));
     }
+
+    #[tokio::test]
+    async fn test_load_session_attaches_client_mcp_servers() {
+        use agent_client_protocol::{McpServer, McpServerStdio};
+
+        let agent = KrustyAgent::new();
+        let mut request = LoadSessionRequest::new("missing", "/tmp");
+        request.mcp_servers = vec![McpServer::Stdio(McpServerStdio::new(
+            "missing",
+            "/nonexistent/krusty-mcp-server",
+        ))];
+        agent.load_session(request).await.unwrap();
+
+        let id = agent.sessions().session_ids().pop().unwrap();
+        let session = agent.sessions().get_session(&id).unwrap();
+        assert_eq!(session.cwd, PathBuf::from("/tmp"));
+        assert_eq!(session.mcp_manager.list_servers().await.len(), 1);
+        session.shutdown_mcp().await;
+    }
+
+    #[test]
+    fn test_advertises_remote_mcp_transports() {
+        let caps = KrustyAgent::new().agent_capabilities();
+        assert!(caps.mcp_capabilities.http);
+        assert!(caps.mcp_capabilities.sse);
+    }
 }
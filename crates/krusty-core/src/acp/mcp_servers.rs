@@ -0,0 +1,106 @@
+//! MCP servers supplied by the ACP client
+//!
+//! Editors pass context servers in `session/new`. They are converted to
+//! Krusty's MCP configs and connected by a manager owned by the session, so
+//! their tools only exist for that session.
+
+use std::collections::HashMap;
+
+use agent_client_protocol::{HttpHeader, McpServer};
+
+use crate::mcp::McpServerConfig;
+
+/// Convert a client-supplied server to a named MCP config
+///
+/// Returns `None` for transports this version doesn't know about.
+pub fn server_config(server: &McpServer) -> Option<(String, McpServerConfig)> {
+    match server {
+        McpServer::Stdio(stdio) => Some((
+            stdio.name.clone(),
+            McpServerConfig::Local {
+                command: stdio.command.to_string_lossy().into_owned(),
+                args: stdio.args.clone(),
+                env: stdio
+                    .env
+                    .iter()
+                    .map(|var| (var.name.clone(), var.value.clone()))
+                    .collect(),
+            },
+        )),
+        // The HTTP transport falls back to legacy SSE on its own
+        McpServer::Http(http) => Some((http.name.clone(), remote_config(&http.url, &http.headers))),
+        McpServer::Sse(sse) => Some((sse.name.clone(), remote_config(&sse.url, &sse.headers))),
+        _ => None,
+    }
+}
+
+fn remote_config(url: &str, headers: &[HttpHeader]) -> McpServerConfig {
+    let mut authorization_token = None;
+    let mut extra = HashMap::new();
+    for header in headers {
+        // Keep bearer tokens separate so the transport sets them consistently
+        match header.value.strip_prefix("Bearer ") {
+            Some(token) if header.name.eq_ignore_ascii_case("authorization") => {
+                authorization_token = Some(token.to_string());
+            }
+            _ => {
+                extra.insert(header.name.clone(), header.value.clone());
+            }
+        }
+    }
+    McpServerConfig::Remote {
+        url: url.to_string(),
+        authorization_token,
+        headers: extra,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use agent_client_protocol::{EnvVariable, McpServerHttp, McpServerStdio};
+
+    #[test]
+    fn test_stdio_server_config() {
+        let server = McpServer::Stdio(
+            McpServerStdio::new("files", "/usr/bin/mcp-files")
+                .args(vec!["--root".to_string(), ".".to_string()])
+                .env(vec![EnvVariable::new("TOKEN", "abc")]),
+        );
+        let (name, config) = server_config(&server).unwrap();
+        assert_eq!(name, "files");
+        match config {
+            McpServerConfig::Local { command, args, env } => {
+                assert_eq!(command, "/usr/bin/mcp-files");
+                assert_eq!(args, vec!["--root", "."]);
+                assert_eq!(env.get("TOKEN").map(String::as_str), Some("abc"));
+            }
+            other => panic!("expected local config, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_http_server_config() {
+        let server = McpServer::Http(
+            McpServerHttp::new("docs", "https://example.com/mcp").headers(vec![
+                HttpHeader::new("Authorization", "Bearer secret"),
+                HttpHeader::new("X-Team", "krusty"),
+            ]),
+        );
+        let (name, config) = server_config(&server).unwrap();
+        assert_eq!(name, "docs");
+        match config {
+            McpServerConfig::Remote {
+                url,
+                authorization_token,
+                headers,
+            } => {
+                assert_eq!(url, "https://example.com/mcp");
+                assert_eq!(authorization_token.as_deref(), Some("secret"));
+                assert_eq!(headers.len(), 1);
+                assert_eq!(headers["X-Team"], "krusty");
+            }
+            other => panic!("expected remote config, got {:?}", other),
+        }
+    }
+}
@@ -32,6 +32,7 @@
 mod agent;
 mod bridge;
 mod error;
+mod mcp_servers;
 mod model_manager;
 mod processor;
 mod server;
@@ -45,6 +46,6 @@ pub use bridge::{create_notification_channel, NotificationBridge};
 pub use error::AcpError;
 pub use model_manager::{CachedProviderInfo, ModelManager};
 pub use processor::PromptProcessor;
-pub use server::AcpServer;
+pub use server::{detect_api_key_from_env, get_provider_api_key, AcpEnvConfig, AcpServer};
 pub use session::{SessionManager, SessionState};
 pub use workspace_context::WorkspaceContextBuilder;
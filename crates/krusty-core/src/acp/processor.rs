@@ -15,6 +15,7 @@
 
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Instant;
 
 use agent_client_protocol::{
     Client as AcpClient, ContentBlock as AcpContent, ContentChunk, EmbeddedResourceResource,
@@ -27,11 +28,13 @@ use tracing::{debug, error, info, warn};
 use crate::agent::dual_mind::{
     DialogueResult, DialogueTurn, DualMind, DualMindConfig, Observation,
 };
+use crate::agent::{AgentEvent, AgentEventBus, HookEvent, UserHookExecutor, UserHookManager};
 use crate::ai::client::{AiClient, AiClientConfig, CallOptions};
 use crate::ai::format_detection::detect_api_format;
 use crate::ai::providers::{get_provider, AuthHeader, ProviderId};
 use crate::ai::streaming::StreamPart;
-use crate::ai::types::{AiToolCall, Content, FinishReason};
+use crate::ai::types::{AiToolCall, Content, FinishReason, Usage};
+use crate::lsp::LspManager;
 use crate::tools::git_identity::{GitIdentity, GitIdentityMode};
 use crate::tools::{ToolContext, ToolRegistry, ToolResult};
 
@@ -95,6 +98,12 @@ pub struct PromptProcessor {
     exploration_tracker: ExplorationTracker,
     /// Git identity for commit attribution
     git_identity: Option<GitIdentity>,
+    /// Language servers for the working directory
+    lsp_manager: Arc<LspManager>,
+    /// User hooks for prompt, turn and sub-agent events
+    user_hooks: Arc<RwLock<UserHookManager>>,
+    /// Turn, stream and tool events for subscribers
+    event_bus: AgentEventBus,
 }
 
 impl PromptProcessor {
@@ -103,14 +112,32 @@ impl PromptProcessor {
         Self {
             ai_client: None,
             tools,
+            lsp_manager: Arc::new(LspManager::new(cwd.clone())),
             cwd,
             dual_mind: None,
             dual_mind_config: DualMindConfig::default(),
             exploration_tracker: ExplorationTracker::new(),
             git_identity: Some(GitIdentity::default()),
+            user_hooks: Arc::new(RwLock::new(UserHookManager::new())),
+            event_bus: AgentEventBus::new(),
         }
     }
 
+    /// Share a user hook manager (e.g. the one the tool registry uses)
+    pub fn with_user_hooks(mut self, user_hooks: Arc<RwLock<UserHookManager>>) -> Self {
+        self.user_hooks = user_hooks;
+        self
+    }
+
+    pub fn user_hooks(&self) -> &Arc<RwLock<UserHookManager>> {
+        &self.user_hooks
+    }
+
+    /// Event bus carrying this processor's turn, stream and tool events
+    pub fn event_bus(&self) -> &AgentEventBus {
+        &self.event_bus
+    }
+
     /// Create with dual-mind disabled
     pub fn without_dual_mind(tools: Arc<ToolRegistry>, cwd: PathBuf) -> Self {
         let mut processor = Self::new(tools, cwd);
@@ -132,6 +159,9 @@ impl PromptProcessor {
 
     /// Update the working directory (called when session cwd changes)
     pub fn set_cwd(&mut self, cwd: PathBuf) {
+        if cwd != self.cwd {
+            self.lsp_manager = Arc::new(LspManager::new(cwd.clone()));
+        }
         self.cwd = cwd;
     }
 
@@ -218,17 +248,50 @@ impl PromptProcessor {
 
         // Convert initial ACP content to Krusty messages and add to history
         // Handle Text, Resource (embedded files), and ResourceLink (file references)
-        let initial_content: Vec<Content> =
+        let mut initial_content: Vec<Content> =
             prompt.into_iter().filter_map(convert_acp_content).collect();
 
+        // UserPromptSubmit hooks can block the prompt or add context to it
+        let prompt_text = initial_content
+            .iter()
+            .filter_map(|c| match c {
+                Content::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let session_id = session.id.to_string();
+        let outcome = UserHookExecutor::fire(
+            &self.user_hooks,
+            &HookEvent::user_prompt_submit(Some(&session_id), &self.cwd, &prompt_text),
+        )
+        .await;
+        if let Some(reason) = outcome.blocked {
+            info!("Prompt blocked by user hook: {}", reason);
+            let chunk = ContentChunk::new(AcpContent::Text(TextContent::new(format!(
+                "Prompt blocked by hook: {}",
+                reason
+            ))));
+            let notification = SessionNotification::new(
+                session.id.clone(),
+                SessionUpdate::AgentMessageChunk(chunk),
+            );
+            let _ = connection.session_notification(notification).await;
+            return Ok(StopReason::Refusal);
+        }
+        if let Some(text) = outcome.prompt_context() {
+            initial_content.push(Content::Text { text });
+        }
+
         if !initial_content.is_empty() {
             session
                 .add_user_message_content(initial_content.clone())
                 .await;
         }
 
-        // Get tool definitions for the AI
-        let tool_defs = self.tools.get_ai_tools().await;
+        // Get tool definitions for the AI, including the session's MCP tools
+        let mut tool_defs = self.tools.get_ai_tools().await;
+        tool_defs.extend(session.mcp_tools.get_ai_tools().await);
 
         // Agentic loop - continue until AI stops requesting tools
         const MAX_ITERATIONS: usize = 50; // Safety limit
@@ -242,6 +305,11 @@ impl PromptProcessor {
 
             // Get current conversation history
             let messages = session.history().await;
+            let turn_started = Instant::now();
+            self.event_bus.emit(AgentEvent::TurnStart {
+                turn: iteration + 1,
+                message_count: messages.len(),
+            });
 
             // Set up call options
             let options = CallOptions {
@@ -264,6 +332,7 @@ impl PromptProcessor {
             let mut accumulated_text = String::new();
             let mut pending_tool_calls: Vec<AiToolCall> = Vec::new();
             let mut stop_reason = StopReason::EndTurn;
+            let mut usage = Usage::default();
 
             while let Some(part) = rx.recv().await {
                 if session.is_cancelled() {
@@ -278,6 +347,9 @@ impl PromptProcessor {
 
                     StreamPart::TextDelta { delta } => {
                         accumulated_text.push_str(&delta);
+                        self.event_bus.emit(AgentEvent::TextDelta {
+                            delta: delta.clone(),
+                        });
                         // Stream text chunk to client
                         let chunk = ContentChunk::new(AcpContent::Text(TextContent::new(&delta)));
                         let notification = SessionNotification::new(
@@ -290,6 +362,9 @@ impl PromptProcessor {
                     }
 
                     StreamPart::ThinkingDelta { thinking, .. } => {
+                        self.event_bus.emit(AgentEvent::ThinkingDelta {
+                            delta: thinking.clone(),
+                        });
                         // Stream thinking as thought chunk
                         let chunk =
                             ContentChunk::new(AcpContent::Text(TextContent::new(&thinking)));
@@ -323,6 +398,10 @@ impl PromptProcessor {
                         pending_tool_calls.push(tool_call);
                     }
 
+                    StreamPart::Usage { usage: u } => {
+                        usage = u;
+                    }
+
                     StreamPart::Finish { reason } => {
                         debug!("Stream finished: {:?}", reason);
                         stop_reason = convert_finish_reason(reason);
@@ -330,6 +409,9 @@ impl PromptProcessor {
 
                     StreamPart::Error { error } => {
                         error!("Stream error: {}", error);
+                        self.event_bus.emit(AgentEvent::StreamError {
+                            error: error.clone(),
+                        });
                         return Err(AcpError::AiClientError(error));
                     }
 
@@ -339,6 +421,12 @@ impl PromptProcessor {
                 }
             }
 
+            self.event_bus.emit(AgentEvent::TurnComplete {
+                turn: iteration + 1,
+                duration_ms: turn_started.elapsed().as_millis() as u64,
+                tokens: usage,
+            });
+
             // Add the assistant's text response to conversation history
             if !accumulated_text.is_empty() {
                 session
@@ -349,6 +437,11 @@ impl PromptProcessor {
             // If no tool calls, we're done
             if pending_tool_calls.is_empty() {
                 info!("Agentic loop complete after {} iterations", iteration + 1);
+                let user_hooks = self.user_hooks.clone();
+                let event = HookEvent::stop(Some(&session_id), &self.cwd, &accumulated_text);
+                tokio::spawn(async move {
+                    UserHookExecutor::fire(&user_hooks, &event).await;
+                });
                 return Ok(stop_reason);
             }
 
@@ -427,7 +520,9 @@ impl PromptProcessor {
         let mut ctx = ToolContext {
             working_dir: self.cwd.clone(),
             ..Default::default()
-        };
+        }
+        .with_lsp_manager(self.lsp_manager.clone())
+        .with_user_hooks(self.user_hooks.clone());
 
         if let Some(ref identity) = self.git_identity {
             if identity.mode != GitIdentityMode::Disabled {
@@ -452,12 +547,27 @@ impl PromptProcessor {
             if let Err(e) = connection.session_notification(notification).await {
                 warn!("Failed to send tool start: {}", e);
             }
-
-            // Execute the tool
-            let result = self
+            let started = Instant::now();
+            self.event_bus.emit(AgentEvent::ToolCallStart {
+                id: tool_call.id.clone(),
+                name: tool_call.name.clone(),
+                input: tool_call.arguments.clone(),
+            });
+
+            // Execute the tool, falling back to the session's MCP tools
+            let result = match self
                 .tools
                 .execute(&tool_call.name, tool_call.arguments.clone(), &ctx)
-                .await;
+                .await
+            {
+                Some(result) => Some(result),
+                None => {
+                    session
+                        .mcp_tools
+                        .execute(&tool_call.name, tool_call.arguments.clone(), &ctx)
+                        .await
+                }
+            };
 
             // Send tool call result
             let (update, output_for_history, is_error_for_history) = match &result {
@@ -484,6 +594,15 @@ impl PromptProcessor {
                     (create_tool_call_failed(&tool_call.id, &msg), None, true)
                 }
             };
+            self.event_bus.emit(AgentEvent::ToolCallComplete {
+                id: tool_call.id.clone(),
+                name: tool_call.name.clone(),
+                output: output_for_history
+                    .clone()
+                    .unwrap_or_else(|| format!("Tool '{}' not found", tool_call.name)),
+                is_error: is_error_for_history,
+                duration_ms: started.elapsed().as_millis() as u64,
+            });
 
             let notification =
                 SessionNotification::new(session.id.clone(), SessionUpdate::ToolCallUpdate(update));
@@ -508,7 +627,15 @@ impl PromptProcessor {
                 // Track cumulative exploration for review triggers
                 let is_readonly = matches!(
                     tool_call.name.as_str(),
-                    "Read" | "read" | "Glob" | "glob" | "Grep" | "grep" | "search_codebase"
+                    "Read"
+                        | "read"
+                        | "Glob"
+                        | "glob"
+                        | "Grep"
+                        | "grep"
+                        | "search_codebase"
+                        | "code_graph"
+                        | "lsp"
                 );
                 if is_readonly {
                     self.exploration_tracker.record_read(output.len());
@@ -641,7 +768,8 @@ fn create_observation(tool_name: &str, output: &str, success: bool) -> Observati
 /// Determine if a tool output warrants post-review
 fn should_post_review(tool_name: &str, output: &str, tracker: &ExplorationTracker) -> bool {
     match tool_name {
-        "Read" | "read" | "Glob" | "glob" | "Grep" | "grep" | "search_codebase" => {
+        "Read" | "read" | "Glob" | "glob" | "Grep" | "grep" | "search_codebase" | "code_graph"
+        | "lsp" => {
             // Individual large output OR cumulative exploration threshold
             output.len() > 2000 || tracker.should_review()
         }
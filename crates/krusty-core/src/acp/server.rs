@@ -16,7 +16,9 @@ use tracing::{error, info, warn};
 
 use super::agent::KrustyAgent;
 use crate::ai::providers::ProviderId;
+use crate::paths;
 use crate::storage::credentials::{ActiveProviderStore, CredentialStore};
+use crate::storage::Database;
 use crate::tools::{register_acp_tools, ToolRegistry};
 
 /// ACP Server configuration
@@ -72,6 +74,12 @@ impl AcpServer {
             self.agent.tools().get_ai_tools().await.len()
         );
 
+        // Load user hooks (PreToolUse, UserPromptSubmit, Stop, ...)
+        match Database::new(&paths::config_dir().join("krusty.db")) {
+            Ok(db) => self.agent.load_user_hooks(&db).await,
+            Err(e) => warn!("Failed to open database for user hooks: {}", e),
+        }
+
         // Auto-initialize AI client from environment variables
         if let Some(config) = detect_api_key_from_env() {
             info!(
@@ -144,7 +152,9 @@ impl AcpServer {
                 let session_ids = agent_for_cleanup.sessions().session_ids();
                 let session_count = session_ids.len();
                 for id in session_ids {
-                    agent_for_cleanup.sessions().remove_session(&id);
+                    if let Some(session) = agent_for_cleanup.sessions().remove_session(&id) {
+                        session.shutdown_mcp().await;
+                    }
                 }
                 if session_count > 0 {
                     info!("Cleaned up {} sessions on disconnect", session_count);
@@ -184,10 +194,11 @@ pub struct AcpEnvConfig {
 /// 3. Krusty's stored credentials (~/.krusty/tokens/credentials.json)
 ///
 /// Environment variable options:
-/// - KRUSTY_PROVIDER: anthropic, openrouter, opencodezen, zai, minimax, kimi
+/// - KRUSTY_PROVIDER: anthropic, openrouter, opencodezen, zai, minimax, kimi,
+///   or the name of a custom provider from providers.json
 /// - KRUSTY_MODEL: Override the default model for the provider
 /// - KRUSTY_API_KEY: Generic API key (used with KRUSTY_PROVIDER)
-fn detect_api_key_from_env() -> Option<AcpEnvConfig> {
+pub fn detect_api_key_from_env() -> Option<AcpEnvConfig> {
     let model = std::env::var("KRUSTY_MODEL").ok().filter(|s| !s.is_empty());
 
     // Check for explicit provider configuration first
@@ -199,7 +210,10 @@ fn detect_api_key_from_env() -> Option<AcpEnvConfig> {
             "zai" | "z.ai" => Some(ProviderId::ZAi),
             "minimax" => Some(ProviderId::MiniMax),
             "kimi" => Some(ProviderId::Kimi),
-            _ => None,
+            name => ProviderId::all()
+                .iter()
+                .copied()
+                .find(|p| p.is_custom() && p.storage_key().eq_ignore_ascii_case(name)),
         };
 
         if let Some(provider) = provider {
@@ -266,13 +280,13 @@ fn detect_from_credential_store(model: Option<String>) -> Option<AcpEnvConfig> {
     let active_provider = ActiveProviderStore::load();
 
     // Try active provider first
-    if let Some(api_key) = store.get(&active_provider) {
+    if let Some(api_key) = store.api_key(&active_provider) {
         info!(
             "Using active provider {:?} from credential store",
             active_provider
         );
         return Some(AcpEnvConfig {
-            api_key: api_key.clone(),
+            api_key,
             provider: active_provider,
             model,
         });
@@ -281,13 +295,13 @@ fn detect_from_credential_store(model: Option<String>) -> Option<AcpEnvConfig> {
     // Fall back to first configured provider
     let configured = store.configured_providers();
     if let Some(provider) = configured.first() {
-        if let Some(api_key) = store.get(provider) {
+        if let Some(api_key) = store.api_key(provider) {
             info!(
                 "Using first configured provider {:?} from credential store",
                 provider
             );
             return Some(AcpEnvConfig {
-                api_key: api_key.clone(),
+                api_key,
                 provider: *provider,
                 model,
             });
@@ -298,17 +312,34 @@ fn detect_from_credential_store(model: Option<String>) -> Option<AcpEnvConfig> {
 }
 
 /// Get API key for a specific provider from environment
-fn get_provider_api_key(provider: ProviderId) -> Option<String> {
+///
+/// Custom providers read `KRUSTY_<NAME>_API_KEY`, or get an empty key if
+/// they need none.
+pub fn get_provider_api_key(provider: ProviderId) -> Option<String> {
     let env_var = match provider {
-        ProviderId::Anthropic => "ANTHROPIC_API_KEY",
-        ProviderId::OpenRouter => "OPENROUTER_API_KEY",
-        ProviderId::OpenCodeZen => "OPENCODEZEN_API_KEY",
-        ProviderId::ZAi => "ZAI_API_KEY",
-        ProviderId::MiniMax => "MINIMAX_API_KEY",
-        ProviderId::Kimi => "KIMI_API_KEY",
-        ProviderId::OpenAI => "OPENAI_API_KEY",
+        ProviderId::Anthropic => "ANTHROPIC_API_KEY".to_string(),
+        ProviderId::OpenRouter => "OPENROUTER_API_KEY".to_string(),
+        ProviderId::OpenCodeZen => "OPENCODEZEN_API_KEY".to_string(),
+        ProviderId::ZAi => "ZAI_API_KEY".to_string(),
+        ProviderId::MiniMax => "MINIMAX_API_KEY".to_string(),
+        ProviderId::Kimi => "KIMI_API_KEY".to_string(),
+        ProviderId::OpenAI => "OPENAI_API_KEY".to_string(),
+        ProviderId::Custom(id) => format!(
+            "KRUSTY_{}_API_KEY",
+            id.as_str()
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                })
+                .collect::<String>()
+        ),
     };
-    std::env::var(env_var).ok().filter(|s| !s.is_empty())
+    std::env::var(env_var)
+        .ok()
+        .filter(|s| !s.is_empty())
+        .or_else(|| (!provider.requires_api_key()).then(String::new))
 }
 
 /// Check if we should run in ACP mode
@@ -2,7 +2,7 @@
 //!
 //! Manages session state for ACP connections. Each session maintains:
 //! - Working directory context
-//! - MCP server configurations
+//! - MCP servers supplied by the client, with their tools
 //! - Conversation history
 //! - Cancellation state
 //! - Optional persistence to SQLite via storage::SessionManager
@@ -17,9 +17,12 @@ use tokio::sync::{Mutex, RwLock};
 use tracing::{debug, info, warn};
 
 use super::error::AcpError;
+use super::mcp_servers::server_config;
 use crate::ai::types::{ModelMessage, Role};
+use crate::mcp::tool::register_mcp_tools;
+use crate::mcp::McpManager;
 use crate::storage::SessionManager as StorageSessionManager;
-use crate::tools::ToolContext;
+use crate::tools::{ToolContext, ToolRegistry};
 
 /// Thread-safe wrapper for storage session manager
 ///
@@ -35,6 +38,10 @@ pub struct SessionState {
     pub cwd: PathBuf,
     /// MCP server configurations passed by the client
     pub mcp_servers: Vec<McpServer>,
+    /// Connections to the client's MCP servers
+    pub mcp_manager: Arc<McpManager>,
+    /// Tools from the client's MCP servers, only offered in this session
+    pub mcp_tools: Arc<ToolRegistry>,
     /// Current session mode (e.g., "code", "architect", "ask")
     pub mode: RwLock<Option<String>>,
     /// Conversation messages
@@ -69,6 +76,8 @@ impl SessionState {
 
         Self {
             id,
+            mcp_manager: Arc::new(McpManager::new(working_dir.clone())),
+            mcp_tools: Arc::new(ToolRegistry::new()),
             cwd: working_dir,
             mcp_servers: mcp_servers.unwrap_or_default(),
             mode: RwLock::new(None),
@@ -80,6 +89,46 @@ impl SessionState {
         }
     }
 
+    /// Connect the client's MCP servers and register their tools
+    ///
+    /// Servers that fail to connect are logged and skipped. Returns the
+    /// number of tools registered.
+    pub async fn connect_mcp_servers(&self) -> usize {
+        if self.mcp_servers.is_empty() {
+            return 0;
+        }
+
+        for server in &self.mcp_servers {
+            match server_config(server) {
+                Some((name, config)) => self.mcp_manager.add_server(name, config).await,
+                None => warn!("Skipping MCP server with unsupported transport"),
+            }
+        }
+
+        if let Err(e) = self.mcp_manager.connect_all().await {
+            warn!(
+                "Failed to connect MCP servers for session {}: {}",
+                self.id, e
+            );
+        }
+        register_mcp_tools(self.mcp_manager.clone(), &self.mcp_tools).await;
+
+        let count = self.mcp_tools.get_ai_tools().await.len();
+        info!(
+            "Session {} registered {} tools from {} MCP servers",
+            self.id,
+            count,
+            self.mcp_servers.len()
+        );
+        count
+    }
+
+    /// Disconnect the client's MCP servers and drop their tools
+    pub async fn shutdown_mcp(&self) {
+        self.mcp_tools.unregister_by_prefix("mcp__").await;
+        self.mcp_manager.disconnect_all().await;
+    }
+
     /// Cancel this session
     pub fn cancel(&self) {
         debug!("Cancelling session {}", self.id);
@@ -463,6 +512,24 @@ mod tests {
         assert!(session.is_cancelled());
     }
 
+    #[tokio::test]
+    async fn test_unreachable_mcp_server_is_skipped() {
+        use agent_client_protocol::McpServerStdio;
+
+        let manager = SessionManager::new();
+        let server = McpServer::Stdio(McpServerStdio::new(
+            "missing",
+            "/nonexistent/krusty-mcp-server",
+        ));
+        let session = manager.create_session(None, Some(vec![server]));
+
+        assert_eq!(session.connect_mcp_servers().await, 0);
+        assert_eq!(session.mcp_manager.list_servers().await.len(), 1);
+        assert!(session.mcp_manager.get_client("missing").await.is_none());
+
+        session.shutdown_mcp().await;
+    }
+
     #[test]
     fn test_session_lookup() {
         let manager = SessionManager::new();
@@ -36,7 +36,9 @@ pub fn tool_name_to_kind(tool_name: &str) -> ToolKind {
         // Edit operations
         "edit" | "Edit" | "write" | "Write" | "patch" => ToolKind::Edit,
         // Search operations
-        "grep" | "Grep" | "glob" | "Glob" | "find" | "search" | "ripgrep" => ToolKind::Search,
+        "grep" | "Grep" | "glob" | "Glob" | "find" | "search" | "ripgrep" | "lsp" => {
+            ToolKind::Search
+        }
         // Execute operations
         "bash" | "Bash" | "shell" | "exec" | "run" | "terminal" => ToolKind::Execute,
         // Fetch operations
@@ -1,27 +1,78 @@
-//! Agent event bus
-//!
-//! Central hub for agent events.
-
-use super::events::AgentEvent;
-
-/// Event bus for agent events
-pub struct AgentEventBus {
-    // Events are logged for debugging via tracing
-}
-
-impl AgentEventBus {
-    pub fn new() -> Self {
-        Self {}
-    }
-
-    /// Emit an event (logged via tracing)
-    pub fn emit(&mut self, event: AgentEvent) {
-        tracing::debug!("Agent event: {:?}", event);
-    }
-}
-
-impl Default for AgentEventBus {
-    fn default() -> Self {
-        Self::new()
-    }
-}
+//! Agent event bus
+//!
+//! Central hub for agent events. Events are broadcast to every subscriber
+//! (UI, trace recorder, ...); subscribers that fall too far behind skip
+//! the oldest events rather than slowing the agent down.
+
+use tokio::sync::broadcast;
+
+use super::events::AgentEvent;
+
+/// Events buffered per subscriber before old ones are dropped
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Event bus for agent events
+///
+/// Cheap to clone; clones publish to the same subscribers.
+#[derive(Clone)]
+pub struct AgentEventBus {
+    sender: broadcast::Sender<AgentEvent>,
+}
+
+impl AgentEventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    /// Emit an event to all current subscribers
+    pub fn emit(&self, event: AgentEvent) {
+        tracing::debug!("Agent event: {:?}", event);
+        // No subscribers is fine
+        let _ = self.sender.send(event);
+    }
+
+    /// Subscribe to events emitted from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<AgentEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Number of active subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for AgentEventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let bus = AgentEventBus::new();
+        bus.emit(AgentEvent::TextDelta {
+            delta: "dropped".to_string(),
+        });
+
+        let mut first = bus.subscribe();
+        let mut second = bus.clone().subscribe();
+        assert_eq!(bus.subscriber_count(), 2);
+
+        bus.emit(AgentEvent::TurnStart {
+            turn: 1,
+            message_count: 3,
+        });
+        for rx in [&mut first, &mut second] {
+            match rx.recv().await.unwrap() {
+                AgentEvent::TurnStart { turn, .. } => assert_eq!(turn, 1),
+                other => panic!("Unexpected event: {:?}", other),
+            }
+        }
+    }
+}
@@ -1,13 +1,26 @@
 //! Agent events
 //!
-//! Events that occur during agent execution.
+//! Events that occur during agent execution, published on the
+//! [`AgentEventBus`](super::AgentEventBus) and recorded in traces.
 
-use crate::ai::types::{FinishReason, Usage};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::agent::subagent::AgentProgress;
+use crate::ai::types::{FinishReason, ModelMessage, Usage};
 
 /// Events during agent execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum AgentEvent {
+    /// A session was created or loaded; later events belong to it
+    SessionStart {
+        session_id: String,
+        model: String,
+        working_dir: String,
+    },
+    /// A message was added to the conversation
+    Message { message: ModelMessage },
     /// Turn started
     TurnStart { turn: usize, message_count: usize },
     /// Turn completed
@@ -16,6 +29,33 @@ pub enum AgentEvent {
         duration_ms: u64,
         tokens: Usage,
     },
+    /// Streamed response text
+    TextDelta { delta: String },
+    /// Streamed thinking text
+    ThinkingDelta { delta: String },
+    /// A tool call started executing
+    ToolCallStart {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    /// A tool call finished
+    ToolCallComplete {
+        id: String,
+        name: String,
+        output: String,
+        is_error: bool,
+        duration_ms: u64,
+    },
+    /// Progress from an explore or build sub-agent
+    SubAgentProgress {
+        agent_type: String,
+        progress: AgentProgress,
+    },
+    /// Context compression started (`manual` or `auto`)
+    PinchStart { trigger: String },
+    /// Context compression finished and continues in a new session
+    PinchComplete { new_session_id: String },
     /// Stream ended
     StreamEnd { reason: FinishReason },
     /// Stream error
@@ -28,8 +68,9 @@ pub enum AgentEvent {
 }
 
 /// Reasons for interrupting execution
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum InterruptReason {
     UserRequested,
     MaxTurnsReached,
+    BudgetExceeded,
 }
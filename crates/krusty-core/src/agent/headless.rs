@@ -0,0 +1,343 @@
+//! Headless agent loop
+//!
+//! Runs the agent without a UI: call the model, execute the tools it asks
+//! for, feed the results back, and repeat until it answers without tools or
+//! the turn budget runs out. Progress is reported as `HeadlessEvent`s so
+//! callers can render text, JSON or nothing at all.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::ai::client::{AiClient, CallOptions};
+use crate::ai::cost::BudgetStatus;
+use crate::ai::streaming::StreamPart;
+use crate::ai::types::{AiToolCall, Content, ModelMessage, Role, Usage};
+use crate::tools::{ToolContext, ToolRegistry, ToolResult};
+
+/// Default number of model turns before giving up
+pub const DEFAULT_MAX_TURNS: usize = 50;
+
+/// Progress from a headless run
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HeadlessEvent {
+    /// A model turn started (1-based)
+    TurnStart { turn: usize },
+    /// Streamed assistant text
+    Text { delta: String },
+    /// The model asked for a tool
+    ToolCall {
+        id: String,
+        name: String,
+        input: Value,
+    },
+    /// A tool finished
+    ToolResult {
+        id: String,
+        name: String,
+        output: String,
+        is_error: bool,
+    },
+    /// Token usage reported for a turn
+    Usage {
+        prompt_tokens: usize,
+        completion_tokens: usize,
+    },
+    /// A message was added to the conversation
+    #[serde(skip)]
+    Message(ModelMessage),
+}
+
+/// How a headless run ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HeadlessOutcome {
+    /// The model finished answering
+    Completed,
+    /// The model finished, but the last tools it ran failed
+    ToolFailed,
+    /// The turn budget ran out before the model finished
+    MaxTurns,
+}
+
+/// Result of a headless run
+#[derive(Debug, Clone)]
+pub struct HeadlessResult {
+    pub outcome: HeadlessOutcome,
+    /// Text of the final assistant message
+    pub final_message: String,
+    /// Model turns used
+    pub turns: usize,
+}
+
+/// Runs the agent loop to completion without user interaction
+pub struct HeadlessRunner {
+    client: Arc<AiClient>,
+    tools: Arc<ToolRegistry>,
+    working_dir: PathBuf,
+    max_turns: usize,
+    /// Tool name patterns the model may use (`None` allows all)
+    allowed_tools: Option<Vec<String>>,
+    /// Extra setup applied to each tool context
+    context: Option<Arc<dyn Fn(ToolContext) -> ToolContext + Send + Sync>>,
+    events: Option<mpsc::UnboundedSender<HeadlessEvent>>,
+}
+
+impl HeadlessRunner {
+    pub fn new(client: Arc<AiClient>, tools: Arc<ToolRegistry>, working_dir: PathBuf) -> Self {
+        Self {
+            client,
+            tools,
+            working_dir,
+            max_turns: DEFAULT_MAX_TURNS,
+            allowed_tools: None,
+            context: None,
+            events: None,
+        }
+    }
+
+    /// Limit the number of model turns
+    pub fn with_max_turns(mut self, max_turns: usize) -> Self {
+        self.max_turns = max_turns.max(1);
+        self
+    }
+
+    /// Only offer tools matching these names (`prefix*` matches a prefix)
+    pub fn with_allowed_tools(mut self, allowed: Vec<String>) -> Self {
+        self.allowed_tools = Some(allowed);
+        self
+    }
+
+    /// Customize the context tools run with (MCP, LSP, process registry...)
+    pub fn with_context(
+        mut self,
+        setup: impl Fn(ToolContext) -> ToolContext + Send + Sync + 'static,
+    ) -> Self {
+        self.context = Some(Arc::new(setup));
+        self
+    }
+
+    /// Report progress on a channel
+    pub fn with_events(mut self, tx: mpsc::UnboundedSender<HeadlessEvent>) -> Self {
+        self.events = Some(tx);
+        self
+    }
+
+    fn emit(&self, event: HeadlessEvent) {
+        if let Some(ref tx) = self.events {
+            let _ = tx.send(event);
+        }
+    }
+
+    fn is_allowed(&self, name: &str) -> bool {
+        self.allowed_tools
+            .as_ref()
+            .is_none_or(|allowed| tool_allowed(allowed, name))
+    }
+
+    fn push_message(&self, conversation: &mut Vec<ModelMessage>, message: ModelMessage) {
+        conversation.push(message.clone());
+        self.emit(HeadlessEvent::Message(message));
+    }
+
+    /// Run until the model stops calling tools or the turn budget is spent
+    ///
+    /// `conversation` must end with the user's prompt; new messages are
+    /// appended to it as the run progresses.
+    pub async fn run(&self, conversation: &mut Vec<ModelMessage>) -> Result<HeadlessResult> {
+        let tool_defs: Vec<_> = self
+            .tools
+            .get_ai_tools()
+            .await
+            .into_iter()
+            .filter(|t| self.is_allowed(&t.name))
+            .collect();
+        info!("Headless run with {} tools", tool_defs.len());
+
+        let mut ctx = ToolContext {
+            working_dir: self.working_dir.clone(),
+            ..Default::default()
+        };
+        if let Some(ref setup) = self.context {
+            ctx = setup(ctx);
+        }
+
+        let mut last_batch_failed = false;
+        for turn in 1..=self.max_turns {
+            self.emit(HeadlessEvent::TurnStart { turn });
+            if let Some(tracker) = self.client.cost_tracker() {
+                match tracker.check_budget() {
+                    BudgetStatus::Ok => {}
+                    BudgetStatus::Warning(msg) => warn!("{}", msg),
+                    BudgetStatus::Exceeded(msg) => bail!("{}", msg),
+                }
+            }
+
+            let options = CallOptions {
+                tools: (!tool_defs.is_empty()).then(|| tool_defs.clone()),
+                ..Default::default()
+            };
+            let mut rx = self
+                .client
+                .call_streaming(conversation.clone(), &options)
+                .await?;
+
+            let mut text = String::new();
+            let mut tool_calls: Vec<AiToolCall> = Vec::new();
+            while let Some(part) = rx.recv().await {
+                match part {
+                    StreamPart::TextDelta { delta }
+                    | StreamPart::TextDeltaWithCitations { delta, .. } => {
+                        text.push_str(&delta);
+                        self.emit(HeadlessEvent::Text { delta });
+                    }
+                    StreamPart::ToolCallComplete { tool_call } => tool_calls.push(tool_call),
+                    StreamPart::Usage { usage } => self.emit(usage_event(&usage)),
+                    StreamPart::Error { error } => return Err(anyhow!(error)),
+                    _ => {}
+                }
+            }
+
+            let mut content = Vec::new();
+            if !text.is_empty() {
+                content.push(Content::Text { text: text.clone() });
+            }
+            for call in &tool_calls {
+                content.push(Content::ToolUse {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    input: call.arguments.clone(),
+                });
+            }
+            if !content.is_empty() {
+                self.push_message(
+                    conversation,
+                    ModelMessage {
+                        role: Role::Assistant,
+                        content,
+                    },
+                );
+            }
+
+            if tool_calls.is_empty() {
+                let outcome = if last_batch_failed {
+                    HeadlessOutcome::ToolFailed
+                } else {
+                    HeadlessOutcome::Completed
+                };
+                return Ok(HeadlessResult {
+                    outcome,
+                    final_message: text,
+                    turns: turn,
+                });
+            }
+
+            let mut results = Vec::new();
+            last_batch_failed = false;
+            for call in tool_calls {
+                self.emit(HeadlessEvent::ToolCall {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    input: call.arguments.clone(),
+                });
+
+                let result = self.execute_tool(&call, &ctx).await;
+                last_batch_failed |= result.is_error;
+                self.emit(HeadlessEvent::ToolResult {
+                    id: call.id.clone(),
+                    name: call.name.clone(),
+                    output: result.output.clone(),
+                    is_error: result.is_error,
+                });
+                results.push(Content::ToolResult {
+                    tool_use_id: call.id,
+                    output: Value::String(result.output),
+                    is_error: result.is_error.then_some(true),
+                });
+            }
+            self.push_message(
+                conversation,
+                ModelMessage {
+                    role: Role::User,
+                    content: results,
+                },
+            );
+        }
+
+        warn!("Headless run hit max turns ({})", self.max_turns);
+        Ok(HeadlessResult {
+            outcome: HeadlessOutcome::MaxTurns,
+            final_message: String::new(),
+            turns: self.max_turns,
+        })
+    }
+
+    async fn execute_tool(&self, call: &AiToolCall, ctx: &ToolContext) -> ToolResult {
+        if !self.is_allowed(&call.name) {
+            return ToolResult::error(format!("Tool '{}' is not allowed in this run", call.name));
+        }
+        self.tools
+            .execute(&call.name, call.arguments.clone(), ctx)
+            .await
+            .unwrap_or_else(|| ToolResult::error(format!("Tool '{}' not found", call.name)))
+    }
+}
+
+fn usage_event(usage: &Usage) -> HeadlessEvent {
+    HeadlessEvent::Usage {
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+    }
+}
+
+/// Whether `name` matches one of the allowed tool patterns
+///
+/// Matching is case-insensitive; a trailing `*` matches any suffix.
+pub fn tool_allowed(allowed: &[String], name: &str) -> bool {
+    let name = name.to_lowercase();
+    allowed.iter().any(|pattern| {
+        let pattern = pattern.trim().to_lowercase();
+        match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_allowed() {
+        let allowed = vec!["read".to_string(), "Grep".to_string(), "mcp__*".to_string()];
+        assert!(tool_allowed(&allowed, "read"));
+        assert!(tool_allowed(&allowed, "grep"));
+        assert!(tool_allowed(&allowed, "mcp__github_search"));
+        assert!(!tool_allowed(&allowed, "bash"));
+        assert!(!tool_allowed(&allowed, "reader"));
+        assert!(!tool_allowed(&[], "read"));
+    }
+
+    #[test]
+    fn test_event_serialization() {
+        let event = HeadlessEvent::ToolResult {
+            id: "t1".to_string(),
+            name: "bash".to_string(),
+            output: "ok".to_string(),
+            is_error: false,
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "tool_result");
+        assert_eq!(json["name"], "bash");
+
+        let outcome = serde_json::to_value(HeadlessOutcome::MaxTurns).unwrap();
+        assert_eq!(outcome, "max_turns");
+    }
+}
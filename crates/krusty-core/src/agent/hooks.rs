@@ -20,6 +20,12 @@ use std::time::Duration;
 pub enum HookResult {
     /// Continue with execution (no changes)
     Continue,
+    /// Replace the tool parameters (pre-hooks only)
+    Modify(Value),
+    /// Replace the tool output (post-hooks only)
+    ReplaceOutput(String),
+    /// Append text to the tool output (post-hooks only)
+    AppendOutput(String),
     /// Block execution with a reason
     Block { reason: String },
 }
@@ -41,8 +47,10 @@ pub trait PreToolHook: Send + Sync {
 pub trait PostToolHook: Send + Sync {
     /// Called after a tool executes
     ///
-    /// Can inspect the result and duration but typically just logs.
-    /// Returns `HookResult` for potential future use (result modification).
+    /// Returns:
+    /// - `Continue` to keep the result as is
+    /// - `ReplaceOutput(output)` to replace the output
+    /// - `AppendOutput(text)` to append to the output
     async fn after_execute(
         &self,
         name: &str,
@@ -5,6 +5,7 @@
 //!
 //! ## Core Components
 //! - `AgentEventBus` - Central event dispatcher
+//! - `TraceRecorder` - Writes bus events to per-session JSONL traces
 //! - `AgentState` - Turn tracking and execution state
 //! - `AgentCancellation` - Proper task cancellation
 //!
@@ -12,11 +13,15 @@
 //! - `SafetyHook` - Blocks dangerous bash commands
 //! - `LoggingHook` - Logs all tool executions
 //! - `UserHookManager` - User-configurable hooks
+//! - `PermissionHook` - Allow/ask/deny permission rules
 //!
 //! ## Pinch (Context Continuation)
 //! - `PinchContext` - Structured context for session transitions
 //! - `SummarizationResult` - Output from summarization agent
 //!
+//! ## Headless
+//! - `HeadlessRunner` - Agent loop without a UI (`krusty exec`)
+//!
 //! ## Sub-agents
 //! - `SubAgentPool` - Concurrent execution of lightweight agents
 //! - `SubAgentTask` - Task configuration for sub-agents
@@ -24,6 +29,7 @@
 //! ## Builder Swarm (Octopod)
 //! - `SharedBuildContext` - Coordination for builder agents
 //! - Type registry, file locks, conventions
+//! - `BuildWorktrees` - Optional per-builder git worktrees, merged back after the build
 
 pub mod build_context;
 pub mod cache;
@@ -32,25 +38,36 @@ pub mod constants;
 pub mod dual_mind;
 pub mod event_bus;
 pub mod events;
+pub mod headless;
 pub mod hooks;
+pub mod permissions;
 pub mod pinch_context;
 pub mod state;
 pub mod subagent;
 pub mod summarizer;
+pub mod trace;
 pub mod user_hooks;
+pub mod worktree;
 
 pub use build_context::SharedBuildContext;
 pub use cancellation::AgentCancellation;
 pub use event_bus::AgentEventBus;
 pub use events::{AgentEvent, InterruptReason};
+pub use headless::{HeadlessEvent, HeadlessOutcome, HeadlessResult, HeadlessRunner};
 pub use hooks::{LoggingHook, PlanModeHook, SafetyHook};
+pub use permissions::{
+    PermissionAction, PermissionHook, PermissionManager, PermissionRequest, PermissionResponse,
+    PermissionRule, PermissionScope,
+};
 pub use pinch_context::PinchContext;
 pub use state::{AgentConfig, AgentState};
-pub use summarizer::{generate_summary, SummarizationResult};
+pub use summarizer::{extract_learnings, generate_summary, SummarizationResult};
+pub use trace::{TraceEntry, TraceRecorder, TraceReplay};
 pub use user_hooks::{
-    UserHook, UserHookExecutor, UserHookManager, UserHookResult, UserHookType, UserPostToolHook,
-    UserPreToolHook,
+    HookEvent, HookEventOutcome, UserHook, UserHookExecutor, UserHookManager, UserHookResult,
+    UserHookType, UserPostToolHook, UserPreToolHook,
 };
+pub use worktree::{BuildWorktrees, BuilderBranch, FileChange, MergeOutcome};
 
 // Dual-mind system (Big Claw / Little Claw)
 pub use dual_mind::{
@@ -0,0 +1,772 @@
+//! Tool permission policy
+//!
+//! Rule-based gate in front of tool execution. Each rule matches on a tool
+//! name (glob, e.g. `mcp__*`) and optionally a path glob (read/write/edit)
+//! or a command prefix (bash), and resolves to allow, ask or deny.
+//!
+//! ## Precedence
+//! A matching `Deny` rule always wins. Otherwise the most specific matching
+//! rule decides between `Ask` and `Allow` (a path or command qualifier beats
+//! a bare tool, an exact tool name beats a glob), and among equally specific
+//! rules the most recently added one wins, so an "always allow" answer
+//! overrides the `Ask` rule that prompted it. Calls that match no rule are
+//! allowed, which keeps the default behaviour unchanged for users without
+//! rules.
+//!
+//! ## Scopes
+//! - `Session` - kept in memory only, cleared when the session changes
+//! - `Project` - persisted, only applies inside `project_path`
+//! - `Global` - persisted, applies everywhere
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot, RwLock};
+
+use crate::agent::hooks::{HookResult, PreToolHook};
+use crate::storage::Database;
+use crate::tools::registry::ToolContext;
+
+/// Tools whose `command` parameter is matched against command prefixes
+const COMMAND_TOOLS: &[&str] = &["bash", "shell", "execute"];
+
+/// What to do when a rule matches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum PermissionAction {
+    /// Run without asking
+    Allow,
+    /// Ask the user before running
+    Ask,
+    /// Refuse to run
+    Deny,
+}
+
+impl PermissionAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionAction::Allow => "allow",
+            PermissionAction::Ask => "ask",
+            PermissionAction::Deny => "deny",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "allow" => Some(PermissionAction::Allow),
+            "ask" => Some(PermissionAction::Ask),
+            "deny" => Some(PermissionAction::Deny),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PermissionAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Where a rule applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PermissionScope {
+    /// Current session only (never persisted)
+    Session,
+    /// Current project directory
+    Project,
+    /// Everywhere
+    Global,
+}
+
+impl PermissionScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PermissionScope::Session => "session",
+            PermissionScope::Project => "project",
+            PermissionScope::Global => "global",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "session" => Some(PermissionScope::Session),
+            "project" => Some(PermissionScope::Project),
+            "global" => Some(PermissionScope::Global),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PermissionScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A single permission rule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PermissionRule {
+    /// Unique identifier
+    pub id: String,
+    /// Tool name glob (e.g. "bash", "edit", "mcp__*")
+    pub tool: String,
+    /// Optional path glob, matched against the tool's `file_path`/`path`
+    pub path_glob: Option<String>,
+    /// Optional command prefix, matched against bash `command`
+    pub command_prefix: Option<String>,
+    /// Action when the rule matches
+    pub action: PermissionAction,
+    /// Where the rule applies
+    pub scope: PermissionScope,
+    /// Project root for project-scoped rules
+    pub project_path: Option<String>,
+    /// When the rule was created
+    pub created_at: String,
+}
+
+impl PermissionRule {
+    /// Create a rule that matches every call to `tool`
+    pub fn new(tool: impl Into<String>, action: PermissionAction, scope: PermissionScope) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            tool: tool.into(),
+            path_glob: None,
+            command_prefix: None,
+            action,
+            scope,
+            project_path: None,
+            created_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    /// Restrict the rule to paths matching a glob
+    pub fn with_path_glob(mut self, glob: impl Into<String>) -> Self {
+        self.path_glob = Some(glob.into());
+        self
+    }
+
+    /// Restrict the rule to commands starting with a prefix
+    pub fn with_command_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.command_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Bind the rule to a project directory
+    pub fn for_project(mut self, project_path: &Path) -> Self {
+        self.project_path = Some(project_path.to_string_lossy().into_owned());
+        self
+    }
+
+    /// Short human-readable description for lists and prompts
+    pub fn describe(&self) -> String {
+        let target = match (&self.command_prefix, &self.path_glob) {
+            (Some(prefix), _) => format!("{}({}*)", self.tool, prefix),
+            (None, Some(glob)) => format!("{}({})", self.tool, glob),
+            (None, None) => self.tool.clone(),
+        };
+        format!("{} {} [{}]", self.action, target, self.scope)
+    }
+
+    /// Check whether this rule applies to a tool call
+    pub fn matches(&self, tool_name: &str, params: &Value, working_dir: &Path) -> bool {
+        if !self.applies_in(working_dir) || !glob_matches(&self.tool, tool_name) {
+            return false;
+        }
+
+        if let Some(ref prefix) = self.command_prefix {
+            if !COMMAND_TOOLS.contains(&tool_name) {
+                return false;
+            }
+            let command = params.get("command").and_then(|v| v.as_str()).unwrap_or("");
+            if !command.trim_start().starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(ref glob) = self.path_glob {
+            let Some(path) = tool_path(params) else {
+                return false;
+            };
+            if !path_matches(glob, path, working_dir) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// How narrowly the rule targets a call, used to break Ask/Allow ties
+    fn specificity(&self) -> u8 {
+        let qualified = self.command_prefix.is_some() || self.path_glob.is_some();
+        let exact_tool = !self.tool.contains(['*', '?', '[']);
+        u8::from(qualified) * 2 + u8::from(exact_tool)
+    }
+
+    /// Check whether the rule's scope covers the working directory
+    fn applies_in(&self, working_dir: &Path) -> bool {
+        match self.scope {
+            PermissionScope::Session | PermissionScope::Global => true,
+            PermissionScope::Project => self
+                .project_path
+                .as_ref()
+                .is_some_and(|p| working_dir.starts_with(p)),
+        }
+    }
+}
+
+/// Extract the path argument of file tools
+fn tool_path(params: &Value) -> Option<&str> {
+    params
+        .get("file_path")
+        .or_else(|| params.get("path"))
+        .and_then(|v| v.as_str())
+}
+
+/// Match a name against a simple glob, falling back to equality on bad patterns
+fn glob_matches(pattern: &str, name: &str) -> bool {
+    glob::Pattern::new(pattern)
+        .map(|p| p.matches(name))
+        .unwrap_or_else(|_| pattern == name)
+}
+
+/// Match a tool path against a glob
+///
+/// Absolute globs match the resolved absolute path; relative globs match
+/// the path relative to the working directory.
+fn path_matches(glob: &str, path: &str, working_dir: &Path) -> bool {
+    let Ok(pattern) = glob::Pattern::new(glob) else {
+        return false;
+    };
+
+    let raw = PathBuf::from(path);
+    let absolute = if raw.is_absolute() {
+        raw
+    } else {
+        working_dir.join(raw)
+    };
+
+    if Path::new(glob).is_absolute() {
+        return pattern.matches_path(&absolute);
+    }
+
+    absolute
+        .strip_prefix(working_dir)
+        .map(|rel| pattern.matches_path(rel))
+        .unwrap_or(false)
+}
+
+/// Manager for permission rules - handles evaluation and persistence
+#[derive(Default)]
+pub struct PermissionManager {
+    rules: Vec<PermissionRule>,
+}
+
+impl PermissionManager {
+    /// Create a new empty manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load persisted rules (project and global) from the database
+    ///
+    /// Session rules already in memory are kept.
+    pub fn load(&mut self, db: &Database) -> Result<()> {
+        let mut stmt = db.conn().prepare(
+            "SELECT id, tool, path_glob, command_prefix, action, scope, project_path, created_at
+             FROM permission_rules ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PermissionRule {
+                id: row.get(0)?,
+                tool: row.get(1)?,
+                path_glob: row.get(2)?,
+                command_prefix: row.get(3)?,
+                action: PermissionAction::parse(&row.get::<_, String>(4)?)
+                    .unwrap_or(PermissionAction::Ask),
+                scope: PermissionScope::parse(&row.get::<_, String>(5)?)
+                    .unwrap_or(PermissionScope::Global),
+                project_path: row.get(6)?,
+                created_at: row.get(7)?,
+            })
+        })?;
+        let persisted = rows.collect::<Result<Vec<_>, _>>()?;
+
+        self.rules.retain(|r| r.scope == PermissionScope::Session);
+        self.rules.extend(persisted);
+        Ok(())
+    }
+
+    /// Add a rule, persisting it unless it is session-scoped
+    pub fn save(&mut self, db: &Database, rule: PermissionRule) -> Result<()> {
+        use rusqlite::params;
+
+        if rule.scope != PermissionScope::Session {
+            db.conn().execute(
+                "INSERT INTO permission_rules
+                    (id, tool, path_glob, command_prefix, action, scope, project_path, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    rule.id,
+                    rule.tool,
+                    rule.path_glob,
+                    rule.command_prefix,
+                    rule.action.as_str(),
+                    rule.scope.as_str(),
+                    rule.project_path,
+                    rule.created_at,
+                ],
+            )?;
+        }
+        self.rules.push(rule);
+        Ok(())
+    }
+
+    /// Add a session-scoped rule (never persisted)
+    pub fn add_session_rule(&mut self, mut rule: PermissionRule) {
+        rule.scope = PermissionScope::Session;
+        self.rules.push(rule);
+    }
+
+    /// Delete a rule by ID
+    pub fn delete(&mut self, db: &Database, id: &str) -> Result<()> {
+        use rusqlite::params;
+
+        db.conn()
+            .execute("DELETE FROM permission_rules WHERE id = ?1", params![id])?;
+        self.rules.retain(|r| r.id != id);
+        Ok(())
+    }
+
+    /// Drop all session-scoped rules (call on session switch)
+    pub fn clear_session_rules(&mut self) {
+        self.rules.retain(|r| r.scope != PermissionScope::Session);
+    }
+
+    /// Get all rules
+    pub fn rules(&self) -> &[PermissionRule] {
+        &self.rules
+    }
+
+    /// Rules that apply in a working directory
+    pub fn rules_for(&self, working_dir: &Path) -> Vec<&PermissionRule> {
+        self.rules
+            .iter()
+            .filter(|r| r.applies_in(working_dir))
+            .collect()
+    }
+
+    /// Resolve the action for a tool call
+    ///
+    /// Returns the first matching `Deny` rule, otherwise the most specific
+    /// matching rule (latest wins on ties), or `None` when no rule matches
+    /// (the call is allowed).
+    pub fn evaluate(
+        &self,
+        tool_name: &str,
+        params: &Value,
+        working_dir: &Path,
+    ) -> Option<&PermissionRule> {
+        let matching: Vec<&PermissionRule> = self
+            .rules
+            .iter()
+            .filter(|r| r.matches(tool_name, params, working_dir))
+            .collect();
+
+        if let Some(deny) = matching.iter().find(|r| r.action == PermissionAction::Deny) {
+            return Some(deny);
+        }
+
+        matching
+            .into_iter()
+            .enumerate()
+            .max_by_key(|(i, r)| (r.specificity(), *i))
+            .map(|(_, r)| r)
+    }
+}
+
+/// Build the rule remembered by "always allow for this project"
+///
+/// Bash calls remember the exact command, file tools the exact path,
+/// everything else the tool as a whole.
+pub fn remembered_rule(tool_name: &str, params: &Value, working_dir: &Path) -> PermissionRule {
+    let rule = PermissionRule::new(tool_name, PermissionAction::Allow, PermissionScope::Project)
+        .for_project(working_dir);
+
+    if COMMAND_TOOLS.contains(&tool_name) {
+        if let Some(command) = params.get("command").and_then(|v| v.as_str()) {
+            return rule.with_command_prefix(command.trim());
+        }
+    }
+
+    if let Some(path) = tool_path(params) {
+        let escaped = glob::Pattern::escape(path);
+        return rule.with_path_glob(escaped);
+    }
+
+    rule
+}
+
+/// One-line summary of a tool call for prompts
+pub fn summarize_call(tool_name: &str, params: &Value) -> String {
+    if COMMAND_TOOLS.contains(&tool_name) {
+        if let Some(command) = params.get("command").and_then(|v| v.as_str()) {
+            return format!("{}: {}", tool_name, command);
+        }
+    }
+    match tool_path(params) {
+        Some(path) => format!("{}: {}", tool_name, path),
+        None => tool_name.to_string(),
+    }
+}
+
+/// Answer to an interactive permission request
+#[derive(Debug, Clone)]
+pub enum PermissionResponse {
+    /// Run the tool
+    Allow,
+    /// Refuse, with optional feedback for the model
+    Deny { reason: Option<String> },
+}
+
+/// Interactive permission request sent to the UI for "ask" decisions
+pub struct PermissionRequest {
+    pub tool_name: String,
+    pub params: Value,
+    pub working_dir: PathBuf,
+    /// One-line description of the call
+    pub summary: String,
+    /// Channel for the user's answer (dropping it denies the call)
+    pub respond: oneshot::Sender<PermissionResponse>,
+}
+
+/// Pre-tool hook that enforces the permission policy
+pub struct PermissionHook {
+    manager: Arc<RwLock<PermissionManager>>,
+    /// Where "ask" decisions go; without a prompter they are denied
+    prompt_tx: Option<mpsc::UnboundedSender<PermissionRequest>>,
+}
+
+impl PermissionHook {
+    pub fn new(manager: Arc<RwLock<PermissionManager>>) -> Self {
+        Self {
+            manager,
+            prompt_tx: None,
+        }
+    }
+
+    /// Route "ask" decisions to an interactive prompter
+    pub fn with_prompter(mut self, tx: mpsc::UnboundedSender<PermissionRequest>) -> Self {
+        self.prompt_tx = Some(tx);
+        self
+    }
+}
+
+#[async_trait]
+impl PreToolHook for PermissionHook {
+    async fn before_execute(&self, name: &str, params: &Value, ctx: &ToolContext) -> HookResult {
+        let action = {
+            let manager = self.manager.read().await;
+            manager
+                .evaluate(name, params, &ctx.working_dir)
+                .map(|r| r.action)
+        };
+
+        match action {
+            None | Some(PermissionAction::Allow) => HookResult::Continue,
+            Some(PermissionAction::Deny) => {
+                tracing::info!(tool = name, "Permission policy denied tool call");
+                HookResult::Block {
+                    reason: format!(
+                        "Permission denied by policy for '{}'",
+                        summarize_call(name, params)
+                    ),
+                }
+            }
+            Some(PermissionAction::Ask) => {
+                let Some(ref tx) = self.prompt_tx else {
+                    return HookResult::Block {
+                        reason: format!(
+                            "'{}' requires approval and no interactive prompt is available",
+                            summarize_call(name, params)
+                        ),
+                    };
+                };
+
+                let (respond, answer) = oneshot::channel();
+                let request = PermissionRequest {
+                    tool_name: name.to_string(),
+                    params: params.clone(),
+                    working_dir: ctx.working_dir.clone(),
+                    summary: summarize_call(name, params),
+                    respond,
+                };
+                if tx.send(request).is_err() {
+                    return HookResult::Block {
+                        reason: "Permission prompt unavailable".to_string(),
+                    };
+                }
+
+                match answer.await {
+                    Ok(PermissionResponse::Allow) => HookResult::Continue,
+                    Ok(PermissionResponse::Deny { reason }) => HookResult::Block {
+                        reason: match reason {
+                            Some(r) => format!("User denied permission: {}", r),
+                            None => "User denied permission".to_string(),
+                        },
+                    },
+                    Err(_) => HookResult::Block {
+                        reason: "Permission request was dismissed".to_string(),
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn project() -> PathBuf {
+        PathBuf::from("/work/project")
+    }
+
+    #[test]
+    fn test_no_rules_allows() {
+        let manager = PermissionManager::new();
+        assert!(manager
+            .evaluate("bash", &json!({"command": "ls"}), &project())
+            .is_none());
+    }
+
+    #[test]
+    fn test_command_prefix_matching() {
+        let rule = PermissionRule::new("bash", PermissionAction::Ask, PermissionScope::Global)
+            .with_command_prefix("git push");
+
+        assert!(rule.matches("bash", &json!({"command": "git push origin"}), &project()));
+        assert!(rule.matches("bash", &json!({"command": "  git push"}), &project()));
+        assert!(!rule.matches("bash", &json!({"command": "git status"}), &project()));
+        assert!(!rule.matches("edit", &json!({"command": "git push"}), &project()));
+    }
+
+    #[test]
+    fn test_path_glob_relative_and_absolute() {
+        let rule = PermissionRule::new("edit", PermissionAction::Deny, PermissionScope::Global)
+            .with_path_glob("secrets/**");
+
+        assert!(rule.matches("edit", &json!({"file_path": "secrets/key.pem"}), &project()));
+        assert!(rule.matches(
+            "edit",
+            &json!({"file_path": "/work/project/secrets/a/b.txt"}),
+            &project()
+        ));
+        assert!(!rule.matches("edit", &json!({"file_path": "src/main.rs"}), &project()));
+        assert!(!rule.matches("edit", &json!({}), &project()));
+
+        let abs = PermissionRule::new("read", PermissionAction::Deny, PermissionScope::Global)
+            .with_path_glob("/etc/*");
+        assert!(abs.matches("read", &json!({"file_path": "/etc/passwd"}), &project()));
+    }
+
+    #[test]
+    fn test_tool_glob() {
+        let rule = PermissionRule::new("mcp__*", PermissionAction::Ask, PermissionScope::Global);
+        assert!(rule.matches("mcp__github__create_issue", &json!({}), &project()));
+        assert!(!rule.matches("bash", &json!({}), &project()));
+    }
+
+    #[test]
+    fn test_project_scope() {
+        let rule = PermissionRule::new("write", PermissionAction::Allow, PermissionScope::Project)
+            .for_project(&project());
+
+        assert!(rule.matches("write", &json!({}), &project()));
+        assert!(rule.matches("write", &json!({}), &project().join("sub")));
+        assert!(!rule.matches("write", &json!({}), Path::new("/work/other")));
+    }
+
+    #[test]
+    fn test_most_restrictive_wins() {
+        let mut manager = PermissionManager::new();
+        manager.add_session_rule(PermissionRule::new(
+            "bash",
+            PermissionAction::Allow,
+            PermissionScope::Session,
+        ));
+        manager.add_session_rule(
+            PermissionRule::new("bash", PermissionAction::Deny, PermissionScope::Session)
+                .with_command_prefix("rm "),
+        );
+
+        let allowed = manager.evaluate("bash", &json!({"command": "ls"}), &project());
+        assert_eq!(allowed.map(|r| r.action), Some(PermissionAction::Allow));
+
+        let denied = manager.evaluate("bash", &json!({"command": "rm -r x"}), &project());
+        assert_eq!(denied.map(|r| r.action), Some(PermissionAction::Deny));
+    }
+
+    #[test]
+    fn test_deny_beats_more_specific_allow() {
+        let mut manager = PermissionManager::new();
+        manager.add_session_rule(PermissionRule::new(
+            "bash",
+            PermissionAction::Deny,
+            PermissionScope::Session,
+        ));
+        manager.add_session_rule(
+            PermissionRule::new("bash", PermissionAction::Allow, PermissionScope::Session)
+                .with_command_prefix("ls"),
+        );
+
+        let result = manager.evaluate("bash", &json!({"command": "ls"}), &project());
+        assert_eq!(result.map(|r| r.action), Some(PermissionAction::Deny));
+    }
+
+    #[test]
+    fn test_always_allow_overrides_ask() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(&temp.path().join("test.db")).unwrap();
+        let mut manager = PermissionManager::new();
+        manager
+            .save(
+                &db,
+                PermissionRule::new("mcp__*", PermissionAction::Ask, PermissionScope::Global),
+            )
+            .unwrap();
+        manager
+            .save(
+                &db,
+                PermissionRule::new("bash", PermissionAction::Ask, PermissionScope::Global),
+            )
+            .unwrap();
+
+        let bash = json!({"command": "cargo test"});
+        let tool = "mcp__github__list";
+        assert_eq!(
+            manager
+                .evaluate("bash", &bash, &project())
+                .map(|r| r.action),
+            Some(PermissionAction::Ask)
+        );
+
+        // "Always allow" answers to both prompts
+        manager
+            .save(&db, remembered_rule("bash", &bash, &project()))
+            .unwrap();
+        manager
+            .save(&db, remembered_rule(tool, &json!({}), &project()))
+            .unwrap();
+
+        assert_eq!(
+            manager
+                .evaluate("bash", &bash, &project())
+                .map(|r| r.action),
+            Some(PermissionAction::Allow)
+        );
+        assert_eq!(
+            manager
+                .evaluate(tool, &json!({}), &project())
+                .map(|r| r.action),
+            Some(PermissionAction::Allow)
+        );
+        // Other commands still ask
+        assert_eq!(
+            manager
+                .evaluate("bash", &json!({"command": "rm -r x"}), &project())
+                .map(|r| r.action),
+            Some(PermissionAction::Ask)
+        );
+
+        // And the decision survives a reload
+        let mut reloaded = PermissionManager::new();
+        reloaded.load(&db).unwrap();
+        assert_eq!(
+            reloaded
+                .evaluate("bash", &bash, &project())
+                .map(|r| r.action),
+            Some(PermissionAction::Allow)
+        );
+    }
+
+    #[test]
+    fn test_remembered_rule_is_exact() {
+        let rule = remembered_rule("bash", &json!({"command": "cargo test"}), &project());
+        assert_eq!(rule.command_prefix.as_deref(), Some("cargo test"));
+        assert_eq!(rule.scope, PermissionScope::Project);
+
+        let rule = remembered_rule("edit", &json!({"file_path": "src/[a].rs"}), &project());
+        assert!(rule.matches("edit", &json!({"file_path": "src/[a].rs"}), &project()));
+        assert!(!rule.matches("edit", &json!({"file_path": "src/a.rs"}), &project()));
+    }
+
+    #[test]
+    fn test_persistence_skips_session_rules() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(&temp.path().join("test.db")).unwrap();
+
+        let mut manager = PermissionManager::new();
+        manager
+            .save(
+                &db,
+                PermissionRule::new("edit", PermissionAction::Ask, PermissionScope::Global),
+            )
+            .unwrap();
+        manager
+            .save(
+                &db,
+                PermissionRule::new("bash", PermissionAction::Allow, PermissionScope::Session),
+            )
+            .unwrap();
+
+        let mut reloaded = PermissionManager::new();
+        reloaded.load(&db).unwrap();
+        assert_eq!(reloaded.rules().len(), 1);
+        assert_eq!(reloaded.rules()[0].tool, "edit");
+
+        let id = reloaded.rules()[0].id.clone();
+        reloaded.delete(&db, &id).unwrap();
+        assert!(reloaded.rules().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_hook_ask_without_prompter_blocks() {
+        let manager = Arc::new(RwLock::new(PermissionManager::new()));
+        manager.write().await.add_session_rule(PermissionRule::new(
+            "write",
+            PermissionAction::Ask,
+            PermissionScope::Session,
+        ));
+        let hook = PermissionHook::new(manager);
+        let ctx = ToolContext::default();
+
+        let result = hook.before_execute("write", &json!({}), &ctx).await;
+        assert!(matches!(result, HookResult::Block { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_hook_ask_routes_to_prompter() {
+        let manager = Arc::new(RwLock::new(PermissionManager::new()));
+        manager.write().await.add_session_rule(PermissionRule::new(
+            "write",
+            PermissionAction::Ask,
+            PermissionScope::Session,
+        ));
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let hook = PermissionHook::new(manager).with_prompter(tx);
+
+        tokio::spawn(async move {
+            let request: PermissionRequest = rx.recv().await.unwrap();
+            assert_eq!(request.tool_name, "write");
+            let _ = request.respond.send(PermissionResponse::Allow);
+        });
+
+        let ctx = ToolContext::default();
+        let result = hook.before_execute("write", &json!({}), &ctx).await;
+        assert!(matches!(result, HookResult::Continue));
+    }
+}
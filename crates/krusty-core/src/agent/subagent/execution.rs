@@ -245,6 +245,7 @@ pub(crate) async fn execute_agent_loop<C: AgentConfig>(
     let ctx = ToolContext {
         working_dir: task.working_dir.clone(),
         timeout: Some(Duration::from_secs(config.timeout_secs())),
+        checkpoints: task.checkpoints.clone(),
         ..Default::default()
     };
 
@@ -343,6 +344,28 @@ pub(crate) async fn execute_agent_loop<C: AgentConfig>(
             };
         }
 
+        // Stop before another request once a hard spending limit is reached
+        if client.cost_tracker().is_some_and(|t| t.is_over_budget()) {
+            warn!(task_id = %task_id, turns = turns, "Sub-agent stopped by budget limit");
+            send_progress(
+                AgentProgressStatus::Failed,
+                "budget limit reached",
+                total_tool_calls,
+                estimated_tokens,
+                config,
+            );
+            config.cleanup();
+            return SubAgentResult {
+                task_id,
+                success: false,
+                output: final_output,
+                files_examined,
+                duration_ms: start.elapsed().as_millis() as u64,
+                turns_used: turns,
+                error: Some("Budget limit reached".to_string()),
+            };
+        }
+
         // Get system prompt (may be dynamic for builders)
         let system_prompt = config.system_prompt(turns);
 
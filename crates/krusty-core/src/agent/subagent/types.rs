@@ -4,10 +4,12 @@
 
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use crate::ai::retry::is_retryable_status;
 use crate::ai::retry::IsRetryable;
+use crate::tools::Checkpointer;
 
 /// Error type for subagent API calls that supports retry logic
 #[derive(Debug)]
@@ -79,7 +81,7 @@ pub fn extract_status_from_error(message: &str) -> Option<u16> {
 }
 
 /// Real-time progress update from a sub-agent
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AgentProgress {
     /// Agent task ID
     pub task_id: String,
@@ -102,7 +104,8 @@ pub struct AgentProgress {
 }
 
 /// Status of a sub-agent
-#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum AgentProgressStatus {
     /// Agent is running
     #[default]
@@ -129,6 +132,8 @@ pub struct SubAgentTask {
     pub plan_task_id: Option<String>,
     /// Whether thinking/reasoning is enabled for this agent
     pub thinking_enabled: bool,
+    /// Checkpoints for files this agent modifies
+    pub checkpoints: Option<Arc<Checkpointer>>,
 }
 
 impl SubAgentTask {
@@ -142,6 +147,7 @@ impl SubAgentTask {
             working_dir: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
             plan_task_id: None,
             thinking_enabled: false, // Default off for sub-agents
+            checkpoints: None,
         }
     }
 
@@ -165,6 +171,11 @@ impl SubAgentTask {
         self
     }
 
+    pub fn with_checkpoints(mut self, checkpoints: Option<Arc<Checkpointer>>) -> Self {
+        self.checkpoints = checkpoints;
+        self
+    }
+
     pub(crate) fn system_prompt(&self) -> String {
         format!(
             r#"You are a codebase explorer. Your task is to systematically investigate the codebase and answer questions.
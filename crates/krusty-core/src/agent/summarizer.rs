@@ -10,6 +10,7 @@ use serde::{Deserialize, Serialize};
 use crate::ai::client::AiClient;
 use crate::ai::providers::ProviderId;
 use crate::ai::types::{Content, ModelMessage, Role};
+use crate::index::Learning;
 use crate::storage::RankedFile;
 
 /// Truncate a string to at most `max_bytes` without splitting a UTF-8 char.
@@ -50,7 +51,8 @@ You MUST respond with a valid JSON object (no markdown code blocks, no extra tex
   "work_summary": "2-3 paragraph summary of what was accomplished, focusing on the WHY and WHAT",
   "key_decisions": ["Important architectural or design decisions made"],
   "pending_tasks": ["Incomplete work or clearly identified next steps"],
-  "important_files": ["Top 10 most relevant file paths for continuing work"]
+  "important_files": ["Top 10 most relevant file paths for continuing work"],
+  "learnings": [{"type": "convention|pitfall|architecture|best_practice|dependency|performance", "content": "One durable fact about the codebase"}]
 }
 
 ## Guidelines
@@ -63,6 +65,8 @@ You MUST respond with a valid JSON object (no markdown code blocks, no extra tex
 
 4. **Important Files**: List files most critical for continuing the work. Prioritize files that were modified or are central to the work.
 
+5. **Learnings**: Facts about the codebase that stay true beyond this task: conventions it follows, gotchas that cost time, how its parts fit together. Leave out task progress. At most 8; an empty list is fine.
+
 ## Priority
 
 If the user provided preservation hints, weight those areas HEAVILY in your summary. The user knows what matters most."#;
@@ -74,6 +78,9 @@ pub struct SummarizationResult {
     pub key_decisions: Vec<String>,
     pub pending_tasks: Vec<String>,
     pub important_files: Vec<String>,
+    /// Durable learnings about the codebase, recorded as session memories
+    #[serde(default)]
+    pub learnings: Vec<Learning>,
 }
 
 impl Default for SummarizationResult {
@@ -83,6 +90,7 @@ impl Default for SummarizationResult {
             key_decisions: Vec::new(),
             pending_tasks: Vec::new(),
             important_files: Vec::new(),
+            learnings: Vec::new(),
         }
     }
 }
@@ -328,6 +336,55 @@ pub async fn generate_summary(
     parse_summary_response(&response)
 }
 
+/// Max tokens for learning extraction
+const LEARNINGS_MAX_TOKENS: usize = 1500;
+
+/// System prompt for learning extraction at session end
+const LEARNINGS_SYSTEM_PROMPT: &str = r#"You extract durable learnings about a codebase from a coding session.
+
+A learning is a fact that will still help in a future session on the same codebase: a convention it follows, a gotcha that cost time, how its parts fit together, a dependency quirk. Leave out task progress, one-off details and anything true of every codebase.
+
+You MUST respond with a valid JSON object (no markdown code blocks, no extra text):
+{
+  "learnings": [{"type": "convention|pitfall|architecture|best_practice|dependency|performance", "content": "One self-contained sentence"}]
+}
+
+At most 8 learnings. If the session taught nothing durable, return an empty list."#;
+
+#[derive(Deserialize)]
+struct LearningsResponse {
+    #[serde(default)]
+    learnings: Vec<Learning>,
+}
+
+/// Extract durable learnings from a conversation
+///
+/// Used when a session ends without a pinch; pinch summaries carry their
+/// own learnings.
+pub async fn extract_learnings(
+    client: &AiClient,
+    model: &str,
+    conversation: &[ModelMessage],
+) -> Result<Vec<Learning>> {
+    let prompt = build_summarization_prompt(conversation, None, &[], &[], None);
+    let response = client
+        .call_simple(
+            model,
+            LEARNINGS_SYSTEM_PROMPT,
+            &prompt,
+            LEARNINGS_MAX_TOKENS,
+        )
+        .await?;
+    parse_learnings_response(&response)
+}
+
+/// Parse the JSON response from learning extraction
+fn parse_learnings_response(response: &str) -> Result<Vec<Learning>> {
+    let parsed: LearningsResponse = serde_json::from_str(&extract_json(response))
+        .map_err(|e| anyhow::anyhow!("Failed to parse learnings response: {}", e))?;
+    Ok(parsed.learnings)
+}
+
 /// Parse the JSON response from the summarization AI
 fn parse_summary_response(response: &str) -> Result<SummarizationResult> {
     // Try to extract JSON from the response
@@ -411,5 +468,18 @@ Done!"#;
         let result = parse_summary_response(input).unwrap();
         assert_eq!(result.work_summary, "Built a feature");
         assert_eq!(result.key_decisions, vec!["Used Rust"]);
+        assert!(result.learnings.is_empty());
+    }
+
+    #[test]
+    fn test_parse_learnings() {
+        let input = r#"```json
+{"learnings": [{"type": "pitfall", "content": "Tests need a temp database"}, {"content": "Errors use anyhow"}]}
+```"#;
+        let learnings = parse_learnings_response(input).unwrap();
+        assert_eq!(learnings.len(), 2);
+        assert_eq!(learnings[0].kind, "pitfall");
+        assert_eq!(learnings[1].kind, "");
+        assert!(parse_learnings_response("no learnings").is_err());
     }
 }
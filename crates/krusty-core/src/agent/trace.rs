@@ -0,0 +1,234 @@
+//! Session traces
+//!
+//! Records every agent event as a JSON line, one file per session under
+//! `~/.krusty/traces`, and reads them back for `krusty replay`.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinHandle;
+
+use super::event_bus::AgentEventBus;
+use super::events::AgentEvent;
+
+/// Longest pause between replayed events, so idle time doesn't stall a replay
+const MAX_REPLAY_GAP: Duration = Duration::from_secs(2);
+
+/// One line of a trace file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TraceEntry {
+    pub timestamp: DateTime<Utc>,
+    pub event: AgentEvent,
+}
+
+/// Path of a session's trace file
+pub fn trace_path(dir: &Path, session_id: &str) -> PathBuf {
+    dir.join(format!("{}.jsonl", session_id))
+}
+
+/// Writes bus events to per-session trace files
+///
+/// Events are written to the file of the most recent `SessionStart`;
+/// events before the first one are not recorded. Resumed sessions append
+/// to their existing trace.
+pub struct TraceRecorder {
+    dir: PathBuf,
+}
+
+impl TraceRecorder {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Record events in the background until the bus is dropped
+    pub fn spawn(self, bus: &AgentEventBus) -> JoinHandle<()> {
+        let mut rx = bus.subscribe();
+        tokio::spawn(async move {
+            let mut writer: Option<LineWriter<File>> = None;
+            loop {
+                let event = match rx.recv().await {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("Trace recorder fell behind, skipped {} events", skipped);
+                        continue;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+
+                if let AgentEvent::SessionStart { session_id, .. } = &event {
+                    writer = match self.open(session_id) {
+                        Ok(file) => Some(LineWriter::new(file)),
+                        Err(e) => {
+                            tracing::warn!("Failed to open trace for {}: {}", session_id, e);
+                            None
+                        }
+                    };
+                }
+
+                if let Some(w) = writer.as_mut() {
+                    if let Err(e) = write_entry(w, event) {
+                        tracing::warn!("Failed to write trace entry: {}", e);
+                    }
+                }
+            }
+        })
+    }
+
+    fn open(&self, session_id: &str) -> Result<File> {
+        std::fs::create_dir_all(&self.dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(trace_path(&self.dir, session_id))?;
+        Ok(file)
+    }
+}
+
+fn write_entry(writer: &mut impl Write, event: AgentEvent) -> Result<()> {
+    let entry = TraceEntry {
+        timestamp: Utc::now(),
+        event,
+    };
+    serde_json::to_writer(&mut *writer, &entry)?;
+    writer.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Read all entries of a trace file
+pub fn read_trace(path: &Path) -> Result<Vec<TraceEntry>> {
+    let file =
+        File::open(path).with_context(|| format!("Failed to open trace {}", path.display()))?;
+    let mut entries = Vec::new();
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str(&line)
+            .with_context(|| format!("Invalid trace entry on line {}", i + 1))?;
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// Plays back trace entries with their recorded timing
+pub struct TraceReplay {
+    entries: VecDeque<TraceEntry>,
+    speed: f64,
+    last: Option<DateTime<Utc>>,
+}
+
+impl TraceReplay {
+    /// Replay entries, `speed` times faster than recorded
+    pub fn new(entries: Vec<TraceEntry>, speed: f64) -> Self {
+        Self {
+            entries: entries.into(),
+            speed: if speed > 0.0 { speed } else { 1.0 },
+            last: None,
+        }
+    }
+
+    /// Open a trace file for replay
+    pub fn open(path: &Path, speed: f64) -> Result<Self> {
+        Ok(Self::new(read_trace(path)?, speed))
+    }
+
+    /// Delay before the next event is due
+    pub fn next_delay(&self) -> Option<Duration> {
+        let next = self.entries.front()?;
+        let Some(last) = self.last else {
+            return Some(Duration::ZERO);
+        };
+        let gap = (next.timestamp - last).to_std().unwrap_or_default();
+        Some(gap.div_f64(self.speed).min(MAX_REPLAY_GAP))
+    }
+
+    /// Take the next event
+    pub fn pop(&mut self) -> Option<AgentEvent> {
+        let entry = self.entries.pop_front()?;
+        self.last = Some(entry.timestamp);
+        Some(entry.event)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::types::{Content, ModelMessage, Role};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_records_events_per_session() {
+        let dir = TempDir::new().unwrap();
+        let bus = AgentEventBus::new();
+        let recorder = TraceRecorder::new(dir.path().to_path_buf()).spawn(&bus);
+
+        bus.emit(AgentEvent::TextDelta {
+            delta: "before any session".to_string(),
+        });
+        bus.emit(AgentEvent::SessionStart {
+            session_id: "s1".to_string(),
+            model: "test-model".to_string(),
+            working_dir: "/tmp".to_string(),
+        });
+        bus.emit(AgentEvent::Message {
+            message: ModelMessage {
+                role: Role::User,
+                content: vec![Content::Text {
+                    text: "hello".to_string(),
+                }],
+            },
+        });
+        bus.emit(AgentEvent::ToolCallStart {
+            id: "t1".to_string(),
+            name: "read".to_string(),
+            input: serde_json::json!({"file_path": "a.rs"}),
+        });
+        drop(bus);
+        recorder.await.unwrap();
+
+        let entries = read_trace(&trace_path(dir.path(), "s1")).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert!(matches!(
+            entries[0].event,
+            AgentEvent::SessionStart { ref session_id, .. } if session_id == "s1"
+        ));
+        match &entries[1].event {
+            AgentEvent::Message { message } => assert_eq!(message.role, Role::User),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        assert!(matches!(entries[2].event, AgentEvent::ToolCallStart { .. }));
+    }
+
+    #[test]
+    fn test_replay_timing() {
+        let start = Utc::now();
+        let entry = |secs: i64| TraceEntry {
+            timestamp: start + chrono::Duration::seconds(secs),
+            event: AgentEvent::PinchStart {
+                trigger: "manual".to_string(),
+            },
+        };
+        let mut replay = TraceReplay::new(vec![entry(0), entry(1), entry(60)], 2.0);
+
+        assert_eq!(replay.next_delay(), Some(Duration::ZERO));
+        replay.pop();
+        assert_eq!(replay.next_delay(), Some(Duration::from_millis(500)));
+        replay.pop();
+        assert_eq!(replay.next_delay(), Some(MAX_REPLAY_GAP));
+        replay.pop();
+        assert!(replay.is_finished());
+        assert_eq!(replay.next_delay(), None);
+    }
+}
@@ -1,18 +1,33 @@
 //! User-configurable hooks system
 //!
 //! Allows users to define custom hooks that execute shell commands
-//! before/after tool execution. Hooks can block, warn, or silently proceed
-//! based on exit codes.
+//! before/after tool execution and on lifecycle events (session start,
+//! prompt submit, turn end, sub-agent end, notifications, pinch). Hooks
+//! receive a JSON description of the event on stdin and can block, warn,
+//! or silently proceed based on exit codes.
 //!
 //! ## Exit Code Protocol
-//! - 0: Continue (stdout/stderr not shown)
-//! - 2: Block tool execution, show stderr to model
+//! - 0: Continue (stdout not shown, except for `UserPromptSubmit` where it
+//!   is added to the prompt as context)
+//! - 2: Block tool execution, prompt or pinch, show stderr as the reason
 //! - Other: Warn user with stderr, but continue
+//!
+//! ## JSON Decisions
+//! On exit 0 a hook may instead print a JSON object to stdout:
+//! - `{"decision": "block", "reason": "..."}` blocks like exit code 2
+//! - `{"tool_input": {...}}` replaces the tool input (`PreToolUse`)
+//! - `{"output": "..."}` replaces the tool output (`PostToolUse`)
+//! - `{"append_output": "..."}` appends to the tool output (`PostToolUse`)
+//! - `{"context": "..."}` adds context to the prompt (`UserPromptSubmit`)
+
+use std::path::Path;
 
 use anyhow::Result;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use crate::agent::subagent::SubAgentResult;
+
 /// Type of user hook
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UserHookType {
@@ -22,8 +37,16 @@ pub enum UserHookType {
     PostToolUse,
     /// Fires on notification events (non-blocking)
     Notification,
-    /// Fires when user submits a prompt
+    /// Fires when user submits a prompt, can block or add context
     UserPromptSubmit,
+    /// Fires when a session is created or resumed
+    SessionStart,
+    /// Fires when the agent finishes responding
+    Stop,
+    /// Fires when an explore or build sub-agent finishes
+    SubagentStop,
+    /// Fires before a conversation is pinched, can block
+    PrePinch,
 }
 
 impl UserHookType {
@@ -34,6 +57,10 @@ impl UserHookType {
             UserHookType::PostToolUse,
             UserHookType::Notification,
             UserHookType::UserPromptSubmit,
+            UserHookType::SessionStart,
+            UserHookType::Stop,
+            UserHookType::SubagentStop,
+            UserHookType::PrePinch,
         ]
     }
 
@@ -44,6 +71,10 @@ impl UserHookType {
             UserHookType::PostToolUse => "PostToolUse",
             UserHookType::Notification => "Notification",
             UserHookType::UserPromptSubmit => "UserPromptSubmit",
+            UserHookType::SessionStart => "SessionStart",
+            UserHookType::Stop => "Stop",
+            UserHookType::SubagentStop => "SubagentStop",
+            UserHookType::PrePinch => "PrePinch",
         }
     }
 
@@ -54,6 +85,23 @@ impl UserHookType {
             UserHookType::PostToolUse => "After tool execution",
             UserHookType::Notification => "When notifications are sent",
             UserHookType::UserPromptSubmit => "When the user submits a prompt",
+            UserHookType::SessionStart => "When a session starts or resumes",
+            UserHookType::Stop => "When the agent finishes responding",
+            UserHookType::SubagentStop => "When a sub-agent finishes",
+            UserHookType::PrePinch => "Before a conversation is pinched",
+        }
+    }
+
+    /// What the hook pattern is matched against, or `None` if every hook
+    /// of this type runs
+    pub fn matcher_label(&self) -> Option<&'static str> {
+        match self {
+            UserHookType::PreToolUse | UserHookType::PostToolUse => Some("Tool pattern"),
+            UserHookType::Notification => Some("Notification type pattern"),
+            UserHookType::SessionStart => Some("Source pattern"),
+            UserHookType::SubagentStop => Some("Agent type pattern"),
+            UserHookType::PrePinch => Some("Trigger pattern"),
+            UserHookType::UserPromptSubmit | UserHookType::Stop => None,
         }
     }
 
@@ -64,6 +112,10 @@ impl UserHookType {
             "PostToolUse" => Some(UserHookType::PostToolUse),
             "Notification" => Some(UserHookType::Notification),
             "UserPromptSubmit" => Some(UserHookType::UserPromptSubmit),
+            "SessionStart" => Some(UserHookType::SessionStart),
+            "Stop" => Some(UserHookType::Stop),
+            "SubagentStop" => Some(UserHookType::SubagentStop),
+            "PrePinch" => Some(UserHookType::PrePinch),
             _ => None,
         }
     }
@@ -143,12 +195,205 @@ impl UserHook {
 pub enum UserHookResult {
     /// Continue with tool execution
     Continue,
-    /// Block tool execution with reason (exit code 2)
+    /// Run the tool with this input instead (`tool_input` decision)
+    Modify { tool_input: serde_json::Value },
+    /// Replace the tool output (`output` decision)
+    ReplaceOutput { output: String },
+    /// Append to the tool output (`append_output` decision)
+    AppendOutput { text: String },
+    /// Block tool execution with reason (exit code 2 or `block` decision)
     Block { reason: String },
     /// Warning shown to user, but continue (other non-zero exit)
     Warn { message: String },
 }
 
+/// Structured decision a hook prints to stdout as a JSON object
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct HookDecision {
+    /// `"block"` blocks; anything else continues
+    decision: Option<String>,
+    reason: Option<String>,
+    /// Replacement tool input
+    tool_input: Option<serde_json::Value>,
+    /// Replacement tool output
+    output: Option<String>,
+    /// Text appended to the tool output
+    append_output: Option<String>,
+    /// Context added to the prompt
+    context: Option<String>,
+}
+
+impl HookDecision {
+    /// Parse stdout as a decision, if it is a JSON object
+    fn parse(stdout: &str) -> Option<Self> {
+        let stdout = stdout.trim();
+        if !stdout.starts_with('{') {
+            return None;
+        }
+        serde_json::from_str(stdout).ok()
+    }
+
+    fn to_result(&self) -> UserHookResult {
+        if self.decision.as_deref() == Some("block") {
+            return UserHookResult::Block {
+                reason: self
+                    .reason
+                    .clone()
+                    .unwrap_or_else(|| "Hook blocked execution".to_string()),
+            };
+        }
+        if let Some(tool_input) = &self.tool_input {
+            return UserHookResult::Modify {
+                tool_input: tool_input.clone(),
+            };
+        }
+        match (&self.output, &self.append_output) {
+            (Some(output), Some(text)) => UserHookResult::ReplaceOutput {
+                output: format!("{}\n{}", output, text),
+            },
+            (Some(output), None) => UserHookResult::ReplaceOutput {
+                output: output.clone(),
+            },
+            (None, Some(text)) => UserHookResult::AppendOutput { text: text.clone() },
+            (None, None) => UserHookResult::Continue,
+        }
+    }
+}
+
+/// A lifecycle event delivered to user hooks
+#[derive(Debug, Clone)]
+pub struct HookEvent {
+    pub hook_type: UserHookType,
+    /// Value hook patterns are matched against (`None` runs every hook)
+    pub matcher: Option<String>,
+    /// JSON object sent to the hook on stdin
+    pub payload: serde_json::Value,
+}
+
+impl HookEvent {
+    fn new(
+        hook_type: UserHookType,
+        session_id: Option<&str>,
+        cwd: &Path,
+        matcher: Option<&str>,
+        fields: serde_json::Value,
+    ) -> Self {
+        let mut payload = serde_json::json!({
+            "hook_event_name": hook_type.display_name(),
+            "session_id": session_id,
+            "cwd": cwd,
+        });
+        if let (Some(payload), serde_json::Value::Object(fields)) =
+            (payload.as_object_mut(), fields)
+        {
+            payload.extend(fields);
+        }
+        Self {
+            hook_type,
+            matcher: matcher.map(str::to_string),
+            payload,
+        }
+    }
+
+    /// A session was created (`source` = "new") or resumed ("resume")
+    pub fn session_start(session_id: &str, cwd: &Path, source: &str) -> Self {
+        Self::new(
+            UserHookType::SessionStart,
+            Some(session_id),
+            cwd,
+            Some(source),
+            serde_json::json!({ "source": source }),
+        )
+    }
+
+    /// The user submitted `prompt`
+    pub fn user_prompt_submit(session_id: Option<&str>, cwd: &Path, prompt: &str) -> Self {
+        Self::new(
+            UserHookType::UserPromptSubmit,
+            session_id,
+            cwd,
+            None,
+            serde_json::json!({ "prompt": prompt }),
+        )
+    }
+
+    /// The agent finished responding with `last_message`
+    pub fn stop(session_id: Option<&str>, cwd: &Path, last_message: &str) -> Self {
+        Self::new(
+            UserHookType::Stop,
+            session_id,
+            cwd,
+            None,
+            serde_json::json!({ "last_message": last_message }),
+        )
+    }
+
+    /// A sub-agent of `agent_type` ("explore" or "build") finished
+    pub fn subagent_stop(cwd: &Path, agent_type: &str, result: &SubAgentResult) -> Self {
+        Self::new(
+            UserHookType::SubagentStop,
+            None,
+            cwd,
+            Some(agent_type),
+            serde_json::json!({
+                "agent_type": agent_type,
+                "agent_id": result.task_id,
+                "success": result.success,
+                "turns": result.turns_used,
+                "duration_ms": result.duration_ms,
+                "output": result.output,
+                "error": result.error,
+            }),
+        )
+    }
+
+    /// A notification of `kind` (e.g. "permission_request") was shown
+    pub fn notification(session_id: Option<&str>, cwd: &Path, kind: &str, message: &str) -> Self {
+        Self::new(
+            UserHookType::Notification,
+            session_id,
+            cwd,
+            Some(kind),
+            serde_json::json!({ "notification_type": kind, "message": message }),
+        )
+    }
+
+    /// A pinch is about to summarize the conversation (`trigger` = "manual" or "auto")
+    pub fn pre_pinch(session_id: Option<&str>, cwd: &Path, trigger: &str, messages: usize) -> Self {
+        Self::new(
+            UserHookType::PrePinch,
+            session_id,
+            cwd,
+            Some(trigger),
+            serde_json::json!({ "trigger": trigger, "message_count": messages }),
+        )
+    }
+}
+
+/// Combined result of the hooks run for a lifecycle event
+#[derive(Debug, Default)]
+pub struct HookEventOutcome {
+    /// Reason given by the first hook that blocked
+    pub blocked: Option<String>,
+    /// Non-empty stdout (or `context` decisions) of hooks that succeeded
+    pub context: Vec<String>,
+    /// Messages from hooks that failed
+    pub warnings: Vec<String>,
+}
+
+impl HookEventOutcome {
+    /// Hook output to add to the prompt, wrapped so the model can tell it apart
+    pub fn prompt_context(&self) -> Option<String> {
+        (!self.context.is_empty()).then(|| {
+            format!(
+                "<user-prompt-submit-hook>\n{}\n</user-prompt-submit-hook>",
+                self.context.join("\n")
+            )
+        })
+    }
+}
+
 /// Manager for user hooks - handles CRUD and persistence
 pub struct UserHookManager {
     hooks: Vec<UserHook>,
@@ -338,6 +583,30 @@ impl UserHookManager {
             .collect()
     }
 
+    /// Whether any enabled hook has `hook_type`
+    pub fn has_enabled(&self, hook_type: UserHookType) -> bool {
+        self.hooks
+            .iter()
+            .any(|h| h.enabled && h.hook_type == hook_type)
+    }
+
+    /// Get enabled hooks for a lifecycle event
+    pub fn matching_event_hooks(&mut self, event: &HookEvent) -> Vec<UserHook> {
+        match event.matcher.as_deref() {
+            Some(matcher) => self
+                .matching_hooks(event.hook_type, matcher)
+                .into_iter()
+                .cloned()
+                .collect(),
+            None => self
+                .hooks
+                .iter()
+                .filter(|h| h.enabled && h.hook_type == event.hook_type)
+                .cloned()
+                .collect(),
+        }
+    }
+
     /// Get enabled hooks that match a tool name
     pub fn matching_hooks(&mut self, hook_type: UserHookType, tool_name: &str) -> Vec<&UserHook> {
         use std::collections::HashSet;
@@ -363,6 +632,56 @@ impl UserHookManager {
     }
 }
 
+/// Exit code and output of a hook command
+#[derive(Debug, Default)]
+struct HookOutput {
+    exit_code: i32,
+    stdout: String,
+    stderr: String,
+}
+
+impl HookOutput {
+    /// Interpret the exit code and any JSON decision
+    fn to_result(&self) -> UserHookResult {
+        match self.exit_code {
+            0 => HookDecision::parse(&self.stdout)
+                .map_or(UserHookResult::Continue, |decision| decision.to_result()),
+            2 => {
+                // Block with stderr as reason
+                let reason = self.stderr.trim().to_string();
+                UserHookResult::Block {
+                    reason: if reason.is_empty() {
+                        "Hook blocked execution".to_string()
+                    } else {
+                        reason
+                    },
+                }
+            }
+            code => {
+                // Warn but continue
+                let message = self.stderr.trim().to_string();
+                UserHookResult::Warn {
+                    message: if message.is_empty() {
+                        format!("Hook exited with code {}", code)
+                    } else {
+                        message
+                    },
+                }
+            }
+        }
+    }
+
+    /// Context for the prompt from a successful hook
+    fn context(&self) -> Option<String> {
+        let context = match HookDecision::parse(&self.stdout) {
+            Some(decision) => decision.context?,
+            None => self.stdout.clone(),
+        };
+        let context = context.trim();
+        (!context.is_empty()).then(|| context.to_string())
+    }
+}
+
 /// Executor for user hooks - runs shell commands and interprets results
 pub struct UserHookExecutor;
 
@@ -371,7 +690,7 @@ impl UserHookExecutor {
     ///
     /// The command receives JSON on stdin with tool call details.
     /// Exit codes:
-    /// - 0: Continue (stdout/stderr not shown)
+    /// - 0: Continue, or follow a JSON decision printed to stdout
     /// - 2: Block tool, show stderr to model
     /// - Other: Warn user with stderr, continue
     pub async fn execute(
@@ -379,10 +698,6 @@ impl UserHookExecutor {
         tool_name: &str,
         params: &serde_json::Value,
     ) -> UserHookResult {
-        use std::process::Stdio;
-        use tokio::io::AsyncWriteExt;
-        use tokio::process::Command;
-
         // Build JSON input for the hook
         let input = serde_json::json!({
             "tool_name": tool_name,
@@ -391,11 +706,51 @@ impl UserHookExecutor {
             "hook_type": hook.hook_type.display_name(),
         });
 
-        let input_str = match serde_json::to_string(&input) {
+        match Self::run(hook, &input).await {
+            Ok(output) => output.to_result(),
+            Err(message) => UserHookResult::Warn { message },
+        }
+    }
+
+    /// Execute a PostToolUse hook command
+    ///
+    /// Like [`Self::execute`], with the tool's result as `tool_response`.
+    pub async fn execute_post(
+        hook: &UserHook,
+        tool_name: &str,
+        params: &serde_json::Value,
+        result: &ToolResult,
+    ) -> UserHookResult {
+        let input = serde_json::json!({
+            "tool_name": tool_name,
+            "tool_input": params,
+            "tool_response": {
+                "output": result.output,
+                "is_error": result.is_error,
+            },
+            "hook_id": hook.id,
+            "hook_type": hook.hook_type.display_name(),
+        });
+
+        match Self::run(hook, &input).await {
+            Ok(output) => output.to_result(),
+            Err(message) => UserHookResult::Warn { message },
+        }
+    }
+
+    /// Run the hook command with `input` on stdin
+    ///
+    /// Returns a warning message if the command couldn't run to completion.
+    async fn run(hook: &UserHook, input: &serde_json::Value) -> Result<HookOutput, String> {
+        use std::process::Stdio;
+        use tokio::io::AsyncWriteExt;
+        use tokio::process::Command;
+
+        let input_str = match serde_json::to_string(input) {
             Ok(s) => s,
             Err(e) => {
                 tracing::warn!(hook_id = %hook.id, "Failed to serialize hook input: {}", e);
-                return UserHookResult::Continue;
+                return Ok(HookOutput::default());
             }
         };
 
@@ -411,9 +766,7 @@ impl UserHookExecutor {
             Ok(c) => c,
             Err(e) => {
                 tracing::warn!(hook_id = %hook.id, command = %hook.command, "Failed to spawn hook: {}", e);
-                return UserHookResult::Warn {
-                    message: format!("Hook failed to spawn: {}", e),
-                };
+                return Err(format!("Hook failed to spawn: {}", e));
             }
         };
 
@@ -435,55 +788,83 @@ impl UserHookExecutor {
             Ok(Ok(output)) => output,
             Ok(Err(e)) => {
                 tracing::warn!(hook_id = %hook.id, "Hook execution failed: {}", e);
-                return UserHookResult::Warn {
-                    message: format!("Hook execution failed: {}", e),
-                };
+                return Err(format!("Hook execution failed: {}", e));
             }
             Err(_) => {
                 tracing::warn!(hook_id = %hook.id, "Hook timed out after 30s");
-                return UserHookResult::Warn {
-                    message: "Hook timed out after 30 seconds".to_string(),
-                };
+                return Err("Hook timed out after 30 seconds".to_string());
             }
         };
 
-        let exit_code = output.status.code().unwrap_or(-1);
-        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        let output = HookOutput {
+            exit_code: output.status.code().unwrap_or(-1),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        };
 
         tracing::debug!(
             hook_id = %hook.id,
-            exit_code,
-            stderr_len = stderr.len(),
+            exit_code = output.exit_code,
+            stderr_len = output.stderr.len(),
             "Hook execution complete"
         );
 
-        match exit_code {
-            0 => UserHookResult::Continue,
-            2 => {
-                // Block with stderr as reason
-                let reason = if stderr.is_empty() {
-                    "Hook blocked execution".to_string()
-                } else {
-                    stderr.trim().to_string()
-                };
-                UserHookResult::Block { reason }
-            }
-            _ => {
-                // Warn but continue
-                let message = if stderr.is_empty() {
-                    format!("Hook exited with code {}", exit_code)
-                } else {
-                    stderr.trim().to_string()
-                };
-                UserHookResult::Warn { message }
+        Ok(output)
+    }
+
+    /// Run every hook matching a lifecycle event
+    ///
+    /// Hooks run in order until one blocks. The manager lock is only held
+    /// while collecting the hooks, not while they run.
+    pub async fn fire(manager: &RwLock<UserHookManager>, event: &HookEvent) -> HookEventOutcome {
+        let hooks = manager.write().await.matching_event_hooks(event);
+        let mut outcome = HookEventOutcome::default();
+
+        for hook in hooks {
+            let mut input = event.payload.clone();
+            input["hook_id"] = serde_json::json!(hook.id);
+            input["hook_type"] = serde_json::json!(hook.hook_type.display_name());
+
+            let output = match Self::run(&hook, &input).await {
+                Ok(output) => output,
+                Err(message) => {
+                    outcome.warnings.push(message);
+                    continue;
+                }
+            };
+            match output.to_result() {
+                UserHookResult::Block { reason } => {
+                    tracing::info!(
+                        hook_id = %hook.id,
+                        event = event.hook_type.display_name(),
+                        "User hook blocked: {}",
+                        reason
+                    );
+                    outcome.blocked = Some(reason);
+                    break;
+                }
+                UserHookResult::Warn { message } => {
+                    tracing::warn!(
+                        hook_id = %hook.id,
+                        event = event.hook_type.display_name(),
+                        "User hook warning: {}",
+                        message
+                    );
+                    outcome.warnings.push(message);
+                }
+                _ => outcome.context.extend(output.context()),
             }
         }
+
+        outcome
     }
 
     /// Execute all matching hooks for a tool
     ///
-    /// Returns Block if any hook blocks, otherwise Continue.
-    /// Warnings are logged but don't stop execution.
+    /// Returns Block if any hook blocks, Modify with the final input if any
+    /// hook rewrote it, otherwise Continue. Each hook sees the input as
+    /// rewritten by earlier hooks. Warnings are logged but don't stop
+    /// execution.
     pub async fn execute_matching(
         manager: &mut UserHookManager,
         hook_type: UserHookType,
@@ -496,8 +877,10 @@ impl UserHookExecutor {
             .map(|h| (*h).clone())
             .collect();
 
+        let mut tool_input = params.clone();
+        let mut modified = false;
         for hook in hooks {
-            let result = Self::execute(&hook, tool_name, params).await;
+            let result = Self::execute(&hook, tool_name, &tool_input).await;
             match result {
                 UserHookResult::Block { reason } => {
                     tracing::info!(
@@ -517,11 +900,72 @@ impl UserHookExecutor {
                     );
                     // Continue checking other hooks
                 }
-                UserHookResult::Continue => {}
+                UserHookResult::Modify { tool_input: input } => {
+                    tracing::info!(hook_id = %hook.id, tool = tool_name, "User hook rewrote tool input");
+                    tool_input = input;
+                    modified = true;
+                }
+                UserHookResult::Continue
+                | UserHookResult::ReplaceOutput { .. }
+                | UserHookResult::AppendOutput { .. } => {}
             }
         }
 
-        UserHookResult::Continue
+        if modified {
+            UserHookResult::Modify { tool_input }
+        } else {
+            UserHookResult::Continue
+        }
+    }
+
+    /// Execute all matching PostToolUse hooks for a tool result
+    ///
+    /// Returns ReplaceOutput with the final output if any hook changed it,
+    /// otherwise Continue. Each hook sees the output as changed by earlier
+    /// hooks. The tool already ran, so blocking is not possible.
+    pub async fn execute_matching_post(
+        manager: &mut UserHookManager,
+        tool_name: &str,
+        params: &serde_json::Value,
+        result: &ToolResult,
+    ) -> UserHookResult {
+        let hooks: Vec<UserHook> = manager
+            .matching_hooks(UserHookType::PostToolUse, tool_name)
+            .iter()
+            .map(|h| (*h).clone())
+            .collect();
+
+        let mut current = result.clone();
+        let mut modified = false;
+        for hook in hooks {
+            match Self::execute_post(&hook, tool_name, params, &current).await {
+                UserHookResult::ReplaceOutput { output } => {
+                    current.output = output;
+                    modified = true;
+                }
+                UserHookResult::AppendOutput { text } => {
+                    current.append_output(&text);
+                    modified = true;
+                }
+                UserHookResult::Block { reason } | UserHookResult::Warn { message: reason } => {
+                    tracing::warn!(
+                        hook_id = %hook.id,
+                        tool = tool_name,
+                        "User post-hook warning: {}",
+                        reason
+                    );
+                }
+                UserHookResult::Continue | UserHookResult::Modify { .. } => {}
+            }
+        }
+
+        if modified {
+            UserHookResult::ReplaceOutput {
+                output: current.output,
+            }
+        } else {
+            UserHookResult::Continue
+        }
     }
 }
 
@@ -566,12 +1010,15 @@ impl PreToolHook for UserPreToolHook {
 
         match result {
             UserHookResult::Block { reason } => HookResult::Block { reason },
+            UserHookResult::Modify { tool_input } => HookResult::Modify(tool_input),
             UserHookResult::Warn { message } => {
                 // Log warning but continue
                 tracing::warn!(tool = name, "User pre-hook warning: {}", message);
                 HookResult::Continue
             }
-            UserHookResult::Continue => HookResult::Continue,
+            UserHookResult::Continue
+            | UserHookResult::ReplaceOutput { .. }
+            | UserHookResult::AppendOutput { .. } => HookResult::Continue,
         }
     }
 }
@@ -593,20 +1040,15 @@ impl PostToolHook for UserPostToolHook {
         &self,
         name: &str,
         params: &serde_json::Value,
-        _result: &ToolResult,
+        result: &ToolResult,
         _duration: Duration,
     ) -> HookResult {
         let mut manager = self.manager.write().await;
-        // Post hooks don't block, they just run
-        let _ = UserHookExecutor::execute_matching(
-            &mut manager,
-            UserHookType::PostToolUse,
-            name,
-            params,
-        )
-        .await;
-
-        HookResult::Continue
+        // Post hooks don't block, but can rewrite the output
+        match UserHookExecutor::execute_matching_post(&mut manager, name, params, result).await {
+            UserHookResult::ReplaceOutput { output } => HookResult::ReplaceOutput(output),
+            _ => HookResult::Continue,
+        }
     }
 }
 
@@ -895,4 +1337,145 @@ mod tests {
             UserHookResult::Continue { .. } | UserHookResult::Warn { .. }
         ));
     }
+
+    #[tokio::test]
+    async fn test_fire_prompt_submit_context_and_block() {
+        let mut manager = UserHookManager::new();
+        manager.hooks.push(create_test_hook(
+            UserHookType::UserPromptSubmit,
+            ".*",
+            "grep -q '\"prompt\": *\"hello\"' && echo 'extra context'",
+        ));
+        let manager = RwLock::new(manager);
+        let cwd = Path::new(".");
+
+        let outcome =
+            UserHookExecutor::fire(&manager, &HookEvent::user_prompt_submit(None, cwd, "hello"))
+                .await;
+        assert!(outcome.blocked.is_none());
+        assert_eq!(
+            outcome.prompt_context().as_deref(),
+            Some("<user-prompt-submit-hook>\nextra context\n</user-prompt-submit-hook>")
+        );
+
+        manager.write().await.hooks.push(create_test_hook(
+            UserHookType::UserPromptSubmit,
+            ".*",
+            "echo 'no secrets' >&2; exit 2",
+        ));
+        let outcome =
+            UserHookExecutor::fire(&manager, &HookEvent::user_prompt_submit(None, cwd, "hello"))
+                .await;
+        assert_eq!(outcome.blocked.as_deref(), Some("no secrets"));
+    }
+
+    #[tokio::test]
+    async fn test_fire_matches_event_pattern() {
+        let mut manager = UserHookManager::new();
+        manager.hooks.push(create_test_hook(
+            UserHookType::SessionStart,
+            "resume",
+            "echo resumed",
+        ));
+        manager.hooks.push(create_test_hook(
+            UserHookType::PreToolUse,
+            ".*",
+            "echo tool",
+        ));
+        let manager = RwLock::new(manager);
+        let cwd = Path::new(".");
+
+        let outcome =
+            UserHookExecutor::fire(&manager, &HookEvent::session_start("s1", cwd, "new")).await;
+        assert!(outcome.context.is_empty());
+
+        let outcome =
+            UserHookExecutor::fire(&manager, &HookEvent::session_start("s1", cwd, "resume")).await;
+        assert_eq!(outcome.context, vec!["resumed".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_user_hook_json_decisions() {
+        let hook = create_test_hook(
+            UserHookType::PreToolUse,
+            "bash",
+            r#"echo '{"tool_input": {"command": "ls -la"}}'"#,
+        );
+        let result = UserHookExecutor::execute(&hook, "bash", &json!({"command": "ls"})).await;
+        match result {
+            UserHookResult::Modify { tool_input } => {
+                assert_eq!(tool_input, json!({"command": "ls -la"}))
+            }
+            other => panic!("Expected Modify, got {:?}", other),
+        }
+
+        let hook = create_test_hook(
+            UserHookType::PreToolUse,
+            "bash",
+            r#"echo '{"decision": "block", "reason": "not today"}'"#,
+        );
+        let result = UserHookExecutor::execute(&hook, "bash", &json!({})).await;
+        assert!(matches!(result, UserHookResult::Block { reason } if reason == "not today"));
+
+        // Plain stdout is not a decision
+        let hook = create_test_hook(UserHookType::PreToolUse, "bash", "echo '{not json'");
+        let result = UserHookExecutor::execute(&hook, "bash", &json!({})).await;
+        assert!(matches!(result, UserHookResult::Continue));
+    }
+
+    #[tokio::test]
+    async fn test_execute_matching_chains_rewrites() {
+        let mut manager = UserHookManager::new();
+        manager.hooks.push(create_test_hook(
+            UserHookType::PreToolUse,
+            "Write",
+            r#"echo '{"tool_input": {"file_path": "/tmp/safe.txt"}}'"#,
+        ));
+        manager.hooks.push(create_test_hook(
+            UserHookType::PreToolUse,
+            "Write",
+            "grep -q safe.txt || exit 2",
+        ));
+
+        let result = UserHookExecutor::execute_matching(
+            &mut manager,
+            UserHookType::PreToolUse,
+            "Write",
+            &json!({"file_path": "/etc/passwd"}),
+        )
+        .await;
+        match result {
+            UserHookResult::Modify { tool_input } => {
+                assert_eq!(tool_input, json!({"file_path": "/tmp/safe.txt"}))
+            }
+            other => panic!("Expected Modify, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_matching_post_rewrites_output() {
+        let mut manager = UserHookManager::new();
+        manager.hooks.push(create_test_hook(
+            UserHookType::PostToolUse,
+            "Read",
+            r#"grep -q 'hunter2' && echo '{"output": "[redacted]"}'"#,
+        ));
+        manager.hooks.push(create_test_hook(
+            UserHookType::PostToolUse,
+            "Read",
+            r#"echo '{"append_output": "checked"}'"#,
+        ));
+
+        let result = UserHookExecutor::execute_matching_post(
+            &mut manager,
+            "Read",
+            &json!({}),
+            &ToolResult::success("password: hunter2"),
+        )
+        .await;
+        match result {
+            UserHookResult::ReplaceOutput { output } => assert_eq!(output, "[redacted]\nchecked"),
+            other => panic!("Expected ReplaceOutput, got {:?}", other),
+        }
+    }
 }
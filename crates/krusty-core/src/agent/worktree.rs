@@ -0,0 +1,608 @@
+//! Git worktree isolation for builder agents
+//!
+//! In an isolated build every builder works in its own `git worktree` on a
+//! temporary branch, starting from the working tree as it is now (tracked
+//! files, including uncommitted changes). When the builders are done their
+//! changes are committed on those branches and merged back into the working
+//! tree file by file with `git merge-file`, so one builder's half-finished
+//! edits never land on top of another's.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use tokio::process::Command;
+use tracing::{debug, info, warn};
+
+use crate::tools::{Checkpointer, GitIdentity};
+
+/// Prefix of the temporary branches builders work on
+pub const BRANCH_PREFIX: &str = "krusty/build-";
+
+/// Marker git writes at the start of a conflicted region
+pub const CONFLICT_MARKER: &str = "<<<<<<< ";
+
+/// A builder's worktree
+#[derive(Debug, Clone)]
+pub struct BuilderWorktree {
+    pub task_id: String,
+    pub branch: String,
+    /// Root of the worktree
+    pub path: PathBuf,
+    /// The build's working directory inside this worktree
+    pub working_dir: PathBuf,
+}
+
+/// A file changed on a builder branch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// Path relative to the repository root
+    pub path: String,
+    /// Lines added, None for binary files
+    pub added: Option<usize>,
+    /// Lines removed, None for binary files
+    pub removed: Option<usize>,
+}
+
+/// A builder's committed changes
+#[derive(Debug, Clone)]
+pub struct BuilderBranch {
+    pub task_id: String,
+    pub branch: String,
+    pub files: Vec<FileChange>,
+}
+
+/// Result of merging a builder branch into the working tree
+#[derive(Debug, Clone, Default)]
+pub struct MergeOutcome {
+    /// Files written cleanly
+    pub applied: Vec<String>,
+    /// Files changed on both sides that couldn't be combined
+    pub conflicts: Vec<String>,
+}
+
+/// Worktrees for one isolated build
+#[derive(Debug)]
+pub struct BuildWorktrees {
+    repo_root: PathBuf,
+    /// Directory holding this build's worktrees
+    dir: PathBuf,
+    worktrees: Vec<BuilderWorktree>,
+}
+
+impl BuildWorktrees {
+    /// Create a worktree and branch for each builder
+    ///
+    /// Fails if `working_dir` isn't inside a git repository with at least
+    /// one commit.
+    pub async fn create(working_dir: &Path, task_ids: &[String]) -> Result<Self> {
+        let repo_root = repo_root(working_dir).await?;
+        let stash = git_string(&repo_root, &["stash", "create"]).await?;
+        let base = if stash.is_empty() {
+            git_string(&repo_root, &["rev-parse", "--verify", "HEAD"])
+                .await
+                .context("Repository has no commits")?
+        } else {
+            stash
+        };
+
+        let relative = working_dir
+            .canonicalize()
+            .ok()
+            .and_then(|dir| {
+                dir.strip_prefix(&repo_root)
+                    .ok()
+                    .map(|rel| rel.to_path_buf())
+            })
+            .unwrap_or_default();
+
+        let build_id = uuid::Uuid::new_v4().simple().to_string()[..8].to_string();
+        let mut worktrees = Self {
+            dir: std::env::temp_dir()
+                .join("krusty-worktrees")
+                .join(&build_id),
+            repo_root,
+            worktrees: Vec::new(),
+        };
+
+        for task_id in task_ids {
+            let branch = format!("{}{}/{}", BRANCH_PREFIX, build_id, task_id);
+            let path = worktrees.dir.join(task_id);
+            let path_str = path.to_string_lossy().to_string();
+            let added = git(
+                &worktrees.repo_root,
+                &["worktree", "add", "-q", "-b", &branch, &path_str, &base],
+            )
+            .await;
+            if let Err(e) = added {
+                worktrees.remove().await;
+                delete_branches(&worktrees.repo_root, &worktrees.branches()).await;
+                return Err(e);
+            }
+            debug!("Created worktree {} on {}", path.display(), branch);
+            worktrees.worktrees.push(BuilderWorktree {
+                task_id: task_id.clone(),
+                branch,
+                working_dir: path.join(&relative),
+                path,
+            });
+        }
+
+        info!(
+            "Created {} builder worktrees from {}",
+            worktrees.worktrees.len(),
+            base
+        );
+        Ok(worktrees)
+    }
+
+    pub fn repo_root(&self) -> &Path {
+        &self.repo_root
+    }
+
+    pub fn get(&self, task_id: &str) -> Option<&BuilderWorktree> {
+        self.worktrees.iter().find(|w| w.task_id == task_id)
+    }
+
+    fn branches(&self) -> Vec<String> {
+        self.worktrees.iter().map(|w| w.branch.clone()).collect()
+    }
+
+    /// Commit each builder's changes on its branch and remove the worktrees
+    ///
+    /// Returns the branches with changes, in builder order. Branches without
+    /// changes are deleted.
+    pub async fn finish(self) -> Vec<BuilderBranch> {
+        let identity = GitIdentity::default();
+        let name = format!("user.name={}", identity.name);
+        let email = format!("user.email={}", identity.email);
+
+        let mut branches = Vec::new();
+        let mut unchanged = Vec::new();
+        for worktree in &self.worktrees {
+            match commit_worktree(worktree, &name, &email).await {
+                Ok(true) => match branch_changes(&self.repo_root, &worktree.branch).await {
+                    Ok(files) => branches.push(BuilderBranch {
+                        task_id: worktree.task_id.clone(),
+                        branch: worktree.branch.clone(),
+                        files,
+                    }),
+                    Err(e) => warn!("Failed to diff {}: {}", worktree.branch, e),
+                },
+                Ok(false) => unchanged.push(worktree.branch.clone()),
+                Err(e) => warn!("Failed to commit {}: {}", worktree.task_id, e),
+            }
+        }
+
+        self.remove().await;
+        delete_branches(&self.repo_root, &unchanged).await;
+        branches
+    }
+
+    /// Remove the worktrees, keeping their branches
+    async fn remove(&self) {
+        for worktree in &self.worktrees {
+            let path = worktree.path.to_string_lossy();
+            if let Err(e) = git(
+                &self.repo_root,
+                &["worktree", "remove", "--force", path.as_ref()],
+            )
+            .await
+            {
+                warn!("Failed to remove worktree {}: {}", path, e);
+            }
+        }
+        let _ = tokio::fs::remove_dir_all(&self.dir).await;
+        let _ = git(&self.repo_root, &["worktree", "prune"]).await;
+    }
+}
+
+/// Top level of the repository containing `dir`
+pub async fn repo_root(dir: &Path) -> Result<PathBuf> {
+    let root = git_string(dir, &["rev-parse", "--show-toplevel"])
+        .await
+        .context("Not a git repository")?;
+    let root = PathBuf::from(root);
+    Ok(root.canonicalize().unwrap_or(root))
+}
+
+/// Files changed by the single commit on a builder branch
+pub async fn branch_changes(repo_root: &Path, branch: &str) -> Result<Vec<FileChange>> {
+    let base = format!("{}^", branch);
+    let numstat = git_string(
+        repo_root,
+        &["diff", "--numstat", "--no-renames", &base, branch],
+    )
+    .await?;
+    Ok(numstat.lines().filter_map(parse_numstat_line).collect())
+}
+
+/// Parse a `git diff --numstat` line: `added<TAB>removed<TAB>path`
+fn parse_numstat_line(line: &str) -> Option<FileChange> {
+    let mut parts = line.splitn(3, '\t');
+    let added = parts.next()?;
+    let removed = parts.next()?;
+    let path = parts.next()?;
+    Some(FileChange {
+        path: path.to_string(),
+        added: added.parse().ok(),
+        removed: removed.parse().ok(),
+    })
+}
+
+/// Merge a builder branch into the working tree of the repository containing `dir`
+///
+/// Every file is merged three ways against the commit the builder started
+/// from. Unless `write_conflicts` is set nothing is written when any file
+/// conflicts; with it, conflicting text files get conflict markers.
+/// Files are snapshotted with `checkpoints` before they are written.
+pub async fn merge_branch(
+    dir: &Path,
+    branch: &str,
+    write_conflicts: bool,
+    checkpoints: Option<&Checkpointer>,
+) -> Result<MergeOutcome> {
+    let root = repo_root(dir).await?;
+    let base = format!("{}^", branch);
+    let label = branch.rsplit('/').next().unwrap_or(branch);
+
+    let mut outcome = MergeOutcome::default();
+    let mut writes = Vec::new();
+    for change in branch_changes(&root, branch).await? {
+        let path = root.join(&change.path);
+        let current = match std::fs::read(&path) {
+            Ok(bytes) => Some(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", change.path)),
+        };
+        let ancestor = show_file(&root, &base, &change.path).await;
+        let theirs = show_file(&root, branch, &change.path).await;
+
+        match merge_contents(current, ancestor, theirs, label).await? {
+            FileMerge::Unchanged => {}
+            FileMerge::Clean(content) => {
+                outcome.applied.push(change.path);
+                writes.push((path, content));
+            }
+            FileMerge::Conflict(content) => {
+                outcome.conflicts.push(change.path);
+                writes.push((path, Some(content)));
+            }
+        }
+    }
+
+    if !outcome.conflicts.is_empty() && !write_conflicts {
+        outcome.applied.clear();
+        return Ok(outcome);
+    }
+
+    for (path, content) in writes {
+        if let Some(checkpoints) = checkpoints {
+            checkpoints.snapshot(&path);
+        }
+        match content {
+            Some(bytes) => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, bytes)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+            None => match std::fs::remove_file(&path) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => {
+                    return Err(e).with_context(|| format!("Failed to delete {}", path.display()))
+                }
+            },
+        }
+    }
+    Ok(outcome)
+}
+
+/// Delete builder branches, e.g. after merging or rejecting them
+pub async fn delete_branches(repo_root: &Path, branches: &[String]) {
+    for branch in branches {
+        if !branch.starts_with(BRANCH_PREFIX) {
+            continue;
+        }
+        if let Err(e) = git(repo_root, &["branch", "-D", branch]).await {
+            warn!("Failed to delete branch {}: {}", branch, e);
+        }
+    }
+}
+
+/// How one file merged
+#[derive(Debug, PartialEq, Eq)]
+enum FileMerge {
+    /// The working tree already has the builder's version
+    Unchanged,
+    /// New content, None to delete the file
+    Clean(Option<Vec<u8>>),
+    /// Content to write for the user or resolver to sort out
+    Conflict(Vec<u8>),
+}
+
+/// Three-way merge of one file's working tree, base and builder versions
+async fn merge_contents(
+    current: Option<Vec<u8>>,
+    ancestor: Option<Vec<u8>>,
+    theirs: Option<Vec<u8>>,
+    label: &str,
+) -> Result<FileMerge> {
+    if current == theirs || ancestor == theirs {
+        return Ok(FileMerge::Unchanged);
+    }
+    if current == ancestor {
+        return Ok(FileMerge::Clean(theirs));
+    }
+    match (current, theirs) {
+        (Some(current), Some(theirs)) => {
+            let ancestor = ancestor.unwrap_or_default();
+            if [&current, &ancestor, &theirs]
+                .iter()
+                .any(|c| c.contains(&0))
+            {
+                // Binary: keep the working tree's version
+                return Ok(FileMerge::Conflict(current));
+            }
+            let (merged, clean) = merge_file(&current, &ancestor, &theirs, label).await?;
+            Ok(if clean {
+                FileMerge::Clean(Some(merged))
+            } else {
+                FileMerge::Conflict(merged)
+            })
+        }
+        // Deleted on one side and changed on the other: keep the changes
+        (Some(content), None) | (None, Some(content)) => Ok(FileMerge::Conflict(content)),
+        (None, None) => Ok(FileMerge::Unchanged),
+    }
+}
+
+/// Run `git merge-file` on in-memory contents, returning the result and
+/// whether it merged without conflicts
+async fn merge_file(
+    current: &[u8],
+    ancestor: &[u8],
+    theirs: &[u8],
+    label: &str,
+) -> Result<(Vec<u8>, bool)> {
+    let dir = std::env::temp_dir().join(format!("krusty-merge-{}", uuid::Uuid::new_v4().simple()));
+    tokio::fs::create_dir_all(&dir).await?;
+    let files = [("current", current), ("base", ancestor), ("theirs", theirs)];
+    for (name, content) in files {
+        tokio::fs::write(dir.join(name), content).await?;
+    }
+
+    let output = Command::new("git")
+        .args([
+            "merge-file",
+            "-p",
+            "-L",
+            "current",
+            "-L",
+            "base",
+            "-L",
+            label,
+            "current",
+            "base",
+            "theirs",
+        ])
+        .current_dir(&dir)
+        .output()
+        .await
+        .context("Failed to run git merge-file");
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    let output = output?;
+
+    // The exit code is the number of conflicts; negative on error
+    match output.status.code() {
+        Some(0) => Ok((output.stdout, true)),
+        Some(code) if code > 0 => Ok((output.stdout, false)),
+        _ => Err(anyhow!(
+            "git merge-file failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+async fn commit_worktree(worktree: &BuilderWorktree, name: &str, email: &str) -> Result<bool> {
+    git(&worktree.path, &["add", "-A"]).await?;
+    let status = git_string(&worktree.path, &["status", "--porcelain"]).await?;
+    if status.is_empty() {
+        return Ok(false);
+    }
+    let message = format!("Krusty builder {}", worktree.task_id);
+    git(
+        &worktree.path,
+        &[
+            "-c",
+            name,
+            "-c",
+            email,
+            "-c",
+            "commit.gpgsign=false",
+            "commit",
+            "-q",
+            "--no-verify",
+            "-m",
+            &message,
+        ],
+    )
+    .await?;
+    Ok(true)
+}
+
+/// Contents of `path` at `rev`, None if it doesn't exist there
+async fn show_file(repo_root: &Path, rev: &str, path: &str) -> Option<Vec<u8>> {
+    let spec = format!("{}:{}", rev, path);
+    git(repo_root, &["cat-file", "blob", &spec]).await.ok()
+}
+
+async fn git(dir: &Path, args: &[&str]) -> Result<Vec<u8>> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .await
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(output.stdout)
+}
+
+async fn git_string(dir: &Path, args: &[&str]) -> Result<String> {
+    let stdout = git(dir, args).await?;
+    Ok(String::from_utf8_lossy(&stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn init_repo() -> Option<tempfile::TempDir> {
+        which::which("git").ok()?;
+        let dir = tempfile::tempdir().ok()?;
+        let root = dir.path();
+        git(root, &["init", "-q"]).await.ok()?;
+        std::fs::write(root.join("lib.rs"), "fn a() {}\n\nfn b() {}\n").ok()?;
+        git(root, &["add", "-A"]).await.ok()?;
+        git(
+            root,
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@localhost",
+                "commit",
+                "-q",
+                "-m",
+                "init",
+            ],
+        )
+        .await
+        .ok()?;
+        Some(dir)
+    }
+
+    #[test]
+    fn test_parse_numstat_line() {
+        assert_eq!(
+            parse_numstat_line("3\t1\tsrc/main.rs"),
+            Some(FileChange {
+                path: "src/main.rs".to_string(),
+                added: Some(3),
+                removed: Some(1),
+            })
+        );
+        let binary = parse_numstat_line("-\t-\tlogo.png").unwrap();
+        assert_eq!(binary.added, None);
+        assert!(parse_numstat_line("garbage").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_merge_contents() {
+        let base = b"one\ntwo\nthree\n".to_vec();
+        let ours = b"ONE\ntwo\nthree\n".to_vec();
+        let theirs = b"one\ntwo\nTHREE\n".to_vec();
+
+        assert_eq!(
+            merge_contents(
+                Some(base.clone()),
+                Some(base.clone()),
+                Some(theirs.clone()),
+                "b"
+            )
+            .await
+            .unwrap(),
+            FileMerge::Clean(Some(theirs.clone()))
+        );
+        assert_eq!(
+            merge_contents(
+                Some(theirs.clone()),
+                Some(base.clone()),
+                Some(theirs.clone()),
+                "b"
+            )
+            .await
+            .unwrap(),
+            FileMerge::Unchanged
+        );
+        if which::which("git").is_err() {
+            return;
+        }
+        assert_eq!(
+            merge_contents(Some(ours), Some(base.clone()), Some(theirs), "b")
+                .await
+                .unwrap(),
+            FileMerge::Clean(Some(b"ONE\ntwo\nTHREE\n".to_vec()))
+        );
+        match merge_contents(
+            Some(b"uno\ntwo\nthree\n".to_vec()),
+            Some(base),
+            Some(b"eins\ntwo\nthree\n".to_vec()),
+            "b",
+        )
+        .await
+        .unwrap()
+        {
+            FileMerge::Conflict(content) => {
+                assert!(String::from_utf8_lossy(&content).contains(CONFLICT_MARKER))
+            }
+            other => panic!("expected conflict, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_isolated_builders_merge_back() {
+        let Some(repo) = init_repo().await else {
+            return;
+        };
+        let root = repo.path().canonicalize().unwrap();
+        let ids = vec!["builder-0".to_string(), "builder-1".to_string()];
+        let worktrees = BuildWorktrees::create(&root, &ids).await.unwrap();
+
+        let first = worktrees.get("builder-0").unwrap().working_dir.clone();
+        let second = worktrees.get("builder-1").unwrap().working_dir.clone();
+        assert_ne!(first, root);
+        std::fs::write(first.join("lib.rs"), "fn a() { 1 }\n\nfn b() {}\n").unwrap();
+        std::fs::write(first.join("new.rs"), "fn c() {}\n").unwrap();
+        std::fs::write(second.join("lib.rs"), "fn a() { 2 }\n\nfn b() {}\n").unwrap();
+
+        let branches = worktrees.finish().await;
+        assert_eq!(branches.len(), 2);
+        assert!(!first.exists());
+
+        let merged = merge_branch(&root, &branches[0].branch, false, None)
+            .await
+            .unwrap();
+        assert_eq!(merged.applied.len(), 2);
+        assert!(root.join("new.rs").exists());
+
+        // The second builder changed the same line
+        let conflicted = merge_branch(&root, &branches[1].branch, false, None)
+            .await
+            .unwrap();
+        assert_eq!(conflicted.conflicts, vec!["lib.rs".to_string()]);
+        assert!(conflicted.applied.is_empty());
+        let lib = std::fs::read_to_string(root.join("lib.rs")).unwrap();
+        assert!(!lib.contains(CONFLICT_MARKER));
+
+        merge_branch(&root, &branches[1].branch, true, None)
+            .await
+            .unwrap();
+        let lib = std::fs::read_to_string(root.join("lib.rs")).unwrap();
+        assert!(lib.contains(CONFLICT_MARKER));
+
+        let names: Vec<String> = branches.iter().map(|b| b.branch.clone()).collect();
+        delete_branches(&root, &names).await;
+        let remaining = git_string(&root, &["branch", "--list", "krusty/*"])
+            .await
+            .unwrap();
+        assert!(remaining.is_empty());
+    }
+}
@@ -41,10 +41,22 @@ impl Default for AiClientConfig {
     }
 }
 
+/// Strip the endpoint path from an API URL, leaving its root
+///
+/// `https://host/v1/chat/completions` and `https://host/v1` both give
+/// `https://host/v1`.
+pub fn api_base_url(url: &str) -> &str {
+    url.trim_end_matches('/')
+        .trim_end_matches("/messages")
+        .trim_end_matches("/chat/completions")
+        .trim_end_matches("/responses")
+}
+
 impl AiClientConfig {
     /// Get the API URL to use
     ///
-    /// For OpenCode Zen, routes to correct endpoint based on model's API format:
+    /// For OpenCode Zen and custom providers, routes to the endpoint for the
+    /// model's API format:
     /// - Anthropic format → /v1/messages
     /// - OpenAI format → /v1/chat/completions
     /// - OpenAI Responses → /v1/responses
@@ -53,12 +65,9 @@ impl AiClientConfig {
         const DEFAULT_API_URL: &str = "https://api.anthropic.com/v1/messages";
 
         if let Some(base) = &self.base_url {
-            // For OpenCode Zen, modify the endpoint based on format
-            if self.provider_id == ProviderId::OpenCodeZen {
-                let base_without_endpoint = base
-                    .trim_end_matches("/messages")
-                    .trim_end_matches("/chat/completions")
-                    .trim_end_matches("/responses");
+            // For OpenCode Zen and custom providers, modify the endpoint based on format
+            if self.provider_id == ProviderId::OpenCodeZen || self.provider_id.is_custom() {
+                let base_without_endpoint = api_base_url(base);
 
                 return match self.api_format {
                     ApiFormat::Anthropic => format!("{}/messages", base_without_endpoint),
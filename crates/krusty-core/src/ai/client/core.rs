@@ -3,12 +3,16 @@
 //! The main AiClient struct that handles API communication with multiple providers.
 //! Routes requests through appropriate format handlers based on API format.
 
+use std::sync::Arc;
+
 use anyhow::Result;
 use reqwest::Client;
 use tracing::{error, info};
 
 use super::config::AiClientConfig;
+use crate::ai::cost::{CostTracker, UsageSource};
 use crate::ai::providers::{AuthHeader, ProviderId};
+use crate::ai::types::Usage;
 use crate::constants;
 
 /// API version header for Anthropic
@@ -87,6 +91,8 @@ pub struct AiClient {
     http: Client,
     config: AiClientConfig,
     api_key: String,
+    /// Where request usage is recorded, and what it is attributed to
+    pub(super) cost: Option<(Arc<CostTracker>, UsageSource)>,
 }
 
 impl AiClient {
@@ -110,6 +116,27 @@ impl AiClient {
             http: Self::create_http_client(),
             config,
             api_key,
+            cost: None,
+        }
+    }
+
+    /// Builder: record the usage and cost of every request
+    pub fn with_cost_tracker(mut self, tracker: Arc<CostTracker>, source: UsageSource) -> Self {
+        self.cost = Some((tracker, source));
+        self
+    }
+
+    /// The cost tracker requests are recorded to, if any
+    pub fn cost_tracker(&self) -> Option<&Arc<CostTracker>> {
+        self.cost.as_ref().map(|(tracker, _)| tracker)
+    }
+
+    /// Record a completed request's usage against `model`
+    pub(crate) async fn record_usage(&self, model: &str, usage: &Usage) {
+        if let Some((tracker, source)) = &self.cost {
+            tracker
+                .record(self.provider_id(), model, *source, usage)
+                .await;
         }
     }
 
@@ -156,6 +183,7 @@ impl AiClient {
                 request = request.header("x-api-key", &self.api_key);
                 info!("Using API key authentication");
             }
+            AuthHeader::None => {}
         }
 
         // Add Anthropic API headers if using Anthropic-compatible API
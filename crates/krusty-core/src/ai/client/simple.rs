@@ -7,6 +7,9 @@ use serde_json::Value;
 use tracing::debug;
 
 use super::core::AiClient;
+use crate::ai::format::response::{
+    extract_usage, normalize_google_response, normalize_openai_response,
+};
 
 impl AiClient {
     /// Make a simple non-streaming API call
@@ -68,6 +71,9 @@ impl AiClient {
         let response = self.handle_error_response(response).await?;
 
         let json: Value = response.json().await?;
+        if let Some(usage) = extract_usage(&json) {
+            self.record_usage(model, &usage).await;
+        }
 
         // Extract text from Anthropic response
         // MiniMax and other providers may return thinking blocks before text blocks,
@@ -117,6 +123,9 @@ impl AiClient {
         let response = self.handle_error_response(response).await?;
 
         let json: Value = response.json().await?;
+        if let Some(usage) = extract_usage(&normalize_openai_response(&json)) {
+            self.record_usage(model, &usage).await;
+        }
 
         // Extract text from OpenAI response format
         let text = json
@@ -161,6 +170,9 @@ impl AiClient {
         let response = self.handle_error_response(response).await?;
 
         let json: Value = response.json().await?;
+        if let Some(usage) = extract_usage(&normalize_google_response(&json)) {
+            self.record_usage(model, &usage).await;
+        }
 
         // Extract text from Google response format
         let text = json
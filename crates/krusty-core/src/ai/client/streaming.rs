@@ -11,6 +11,7 @@ use tracing::{debug, error, info, warn};
 
 use super::config::CallOptions;
 use super::core::{AiClient, KRUSTY_SYSTEM_PROMPT};
+use crate::ai::cost::merge_usage;
 use crate::ai::format::anthropic::AnthropicFormat;
 use crate::ai::format::google::GoogleFormat;
 use crate::ai::format::openai::OpenAIFormat;
@@ -21,7 +22,7 @@ use crate::ai::reasoning::ReasoningConfig;
 use crate::ai::sse::{create_streaming_channels, spawn_buffer_processor, SseStreamProcessor};
 use crate::ai::streaming::StreamPart;
 use crate::ai::transform::build_provider_params;
-use crate::ai::types::{Content, ModelMessage, Role};
+use crate::ai::types::{Content, ModelMessage, Role, Usage};
 
 impl AiClient {
     /// Call the API with streaming response
@@ -42,21 +43,47 @@ impl AiClient {
         );
 
         // Route to appropriate format handler based on API format
-        if self.config().uses_openai_format() {
-            return self
-                .call_streaming_openai(messages, options, call_start)
-                .await;
-        }
+        let rx = if self.config().uses_openai_format() {
+            self.call_streaming_openai(messages, options, call_start)
+                .await?
+        } else if self.config().uses_google_format() {
+            self.call_streaming_google(messages, options, call_start)
+                .await?
+        } else {
+            // Anthropic format (default)
+            self.call_streaming_anthropic(messages, options, call_start)
+                .await?
+        };
 
-        if self.config().uses_google_format() {
-            return self
-                .call_streaming_google(messages, options, call_start)
-                .await;
-        }
+        Ok(self.track_stream_usage(rx))
+    }
 
-        // Anthropic format (default)
-        self.call_streaming_anthropic(messages, options, call_start)
-            .await
+    /// Forward a stream, recording its usage once it ends
+    fn track_stream_usage(
+        &self,
+        mut rx: mpsc::UnboundedReceiver<StreamPart>,
+    ) -> mpsc::UnboundedReceiver<StreamPart> {
+        let Some((tracker, source)) = self.cost.clone() else {
+            return rx;
+        };
+        let provider = self.provider_id();
+        let model = self.config().model.clone();
+        let (tx, tracked_rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            let mut usage: Option<Usage> = None;
+            while let Some(part) = rx.recv().await {
+                if let StreamPart::Usage { usage: update } = &part {
+                    merge_usage(usage.get_or_insert_with(Usage::default), update);
+                }
+                // Keep draining after the receiver is dropped so usage is still counted
+                let _ = tx.send(part);
+            }
+            if let Some(usage) = usage {
+                tracker.record(provider, &model, source, &usage).await;
+            }
+        });
+        tracked_rx
     }
 
     /// Streaming call using Anthropic format
@@ -346,6 +373,19 @@ impl AiClient {
                 }
             }
 
+            // Reasoning parameters for models that declare an OpenAI-style format
+            let reasoning_format = options
+                .reasoning_format
+                .filter(|f| *f != ReasoningFormat::Anthropic);
+            if let Some(Value::Object(reasoning)) =
+                ReasoningConfig::build(reasoning_format, true, None, None)
+            {
+                for (key, value) in reasoning {
+                    body[key] = value;
+                }
+                debug!("{:?} reasoning enabled", reasoning_format);
+            }
+
             body
         };
 
@@ -526,9 +566,10 @@ impl AiClient {
         options: &CallOptions,
         capabilities: &ProviderCapabilities,
     ) {
-        // Anthropic server-executed web tools
+        // Anthropic server-executed web tools replace the client-side ones
         if capabilities.web_search {
             if let Some(search) = &options.web_search {
+                all_tools.retain(|tool| tool["name"] != "web_search");
                 let mut spec = serde_json::json!({
                     "type": "web_search_20250305",
                     "name": "web_search",
@@ -543,6 +584,7 @@ impl AiClient {
 
         if capabilities.web_fetch {
             if let Some(fetch) = &options.web_fetch {
+                all_tools.retain(|tool| tool["name"] != "web_fetch");
                 let mut spec = serde_json::json!({
                     "type": "web_fetch_20250910",
                     "name": "web_fetch",
@@ -10,7 +10,8 @@ use tracing::{debug, error, info};
 
 use super::core::AiClient;
 use crate::ai::format::response::{
-    extract_text_from_content, normalize_google_response, normalize_openai_response,
+    extract_text_from_content, extract_usage, normalize_google_response, normalize_openai_response,
+    usage_from_openai,
 };
 
 impl AiClient {
@@ -28,35 +29,36 @@ impl AiClient {
         thinking_enabled: bool,
     ) -> Result<Value> {
         // Route to appropriate format handler based on API format
-        if self.config().uses_openai_format() {
-            return self
-                .call_with_tools_openai(
-                    model,
-                    system_prompt,
-                    messages,
-                    tools,
-                    max_tokens,
-                    thinking_enabled,
-                )
-                .await;
-        }
+        let response = if self.config().uses_openai_format() {
+            self.call_with_tools_openai(
+                model,
+                system_prompt,
+                messages,
+                tools,
+                max_tokens,
+                thinking_enabled,
+            )
+            .await?
+        } else if self.config().uses_google_format() {
+            self.call_with_tools_google(model, system_prompt, messages, tools, max_tokens)
+                .await?
+        } else {
+            // Anthropic format (default)
+            self.call_with_tools_anthropic(
+                model,
+                system_prompt,
+                messages,
+                tools,
+                max_tokens,
+                thinking_enabled,
+            )
+            .await?
+        };
 
-        if self.config().uses_google_format() {
-            return self
-                .call_with_tools_google(model, system_prompt, messages, tools, max_tokens)
-                .await;
+        if let Some(usage) = extract_usage(&response) {
+            self.record_usage(model, &usage).await;
         }
-
-        // Anthropic format (default)
-        self.call_with_tools_anthropic(
-            model,
-            system_prompt,
-            messages,
-            tools,
-            max_tokens,
-            thinking_enabled,
-        )
-        .await
+        Ok(response)
     }
 
     /// Call with tools using Anthropic format
@@ -551,6 +553,7 @@ impl AiClient {
         let mut current_tool_name = String::new();
         let mut current_tool_args = String::new();
         let mut stop_reason = "end_turn".to_string();
+        let mut usage = Value::Null;
 
         let mut stream = response.bytes_stream();
         let mut buffer = String::new();
@@ -636,6 +639,9 @@ impl AiClient {
                                 }
                             }
                             "response.done" | "response.completed" => {
+                                if let Some(u) = json.get("response").and_then(|r| r.get("usage")) {
+                                    usage = usage_from_openai(u);
+                                }
                                 // Finalize any pending tool call
                                 if !current_tool_name.is_empty() {
                                     let input: Value = serde_json::from_str(&current_tool_args)
@@ -667,6 +673,10 @@ impl AiClient {
                                         "Sub-agent Codex usage: input={}, output={}",
                                         input_tokens, output_tokens
                                     );
+                                    usage = serde_json::json!({
+                                        "input_tokens": input_tokens,
+                                        "output_tokens": output_tokens
+                                    });
                                 }
                             }
                             _ => {}
@@ -691,7 +701,8 @@ impl AiClient {
         Ok(serde_json::json!({
             "content": content,
             "stop_reason": stop_reason,
-            "model": model
+            "model": model,
+            "usage": usage
         }))
     }
 
@@ -0,0 +1,511 @@
+//! Cost accounting and budgets
+//!
+//! Clients built with [`AiClient::with_cost_tracker`](crate::ai::AiClient::with_cost_tracker)
+//! report the usage of every request they make. The tracker prices it from
+//! the model's metadata, stores it in `usage_tracking` and checks the running
+//! totals against the configured session and daily budgets.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::Path;
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::ai::models::{ModelMetadata, ModelRegistry, SharedModelRegistry};
+use crate::ai::providers::{get_provider, ProviderId};
+use crate::ai::types::Usage;
+use crate::storage::{today, Database, UsageRecord, UsageStore, UsageSummary};
+
+/// Cache reads cost this fraction of the input price unless the model says otherwise
+const DEFAULT_CACHE_READ_FACTOR: f64 = 0.1;
+/// Cache writes cost this multiple of the input price unless the model says otherwise
+const DEFAULT_CACHE_WRITE_FACTOR: f64 = 1.25;
+
+/// Prices in USD per million tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input: f64,
+    pub output: f64,
+    pub cache_read: f64,
+    pub cache_write: f64,
+}
+
+impl ModelPricing {
+    /// Pricing from model metadata, or `None` if input/output prices are unknown
+    pub fn from_metadata(model: &ModelMetadata) -> Option<Self> {
+        let input = model.input_price?;
+        let output = model.output_price?;
+        Some(Self {
+            input,
+            output,
+            cache_read: model
+                .cache_read_price
+                .unwrap_or(input * DEFAULT_CACHE_READ_FACTOR),
+            cache_write: model
+                .cache_write_price
+                .unwrap_or(input * DEFAULT_CACHE_WRITE_FACTOR),
+        })
+    }
+
+    /// Cost of a request in USD
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let uncached = uncached_input(usage);
+        (uncached as f64 * self.input
+            + usage.completion_tokens as f64 * self.output
+            + usage.cache_read_input_tokens as f64 * self.cache_read
+            + usage.cache_creation_input_tokens as f64 * self.cache_write)
+            / 1_000_000.0
+    }
+}
+
+/// Input tokens billed at the full price
+///
+/// `prompt_tokens` includes cached tokens for every provider's parser.
+fn uncached_input(usage: &Usage) -> usize {
+    usage
+        .prompt_tokens
+        .saturating_sub(usage.cache_read_input_tokens + usage.cache_creation_input_tokens)
+}
+
+/// Merge a usage update from the same response into `total`
+///
+/// Providers report cumulative counts, sometimes split over several events
+/// (Anthropic sends input on `message_start` and output on `message_delta`),
+/// so each field keeps its largest value.
+pub fn merge_usage(total: &mut Usage, update: &Usage) {
+    total.prompt_tokens = total.prompt_tokens.max(update.prompt_tokens);
+    total.completion_tokens = total.completion_tokens.max(update.completion_tokens);
+    total.cache_read_input_tokens = total
+        .cache_read_input_tokens
+        .max(update.cache_read_input_tokens);
+    total.cache_creation_input_tokens = total
+        .cache_creation_input_tokens
+        .max(update.cache_creation_input_tokens);
+    total.total_tokens = total.prompt_tokens + total.completion_tokens;
+}
+
+/// What made an API request
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageSource {
+    /// The main agent loop
+    Chat,
+    /// Explore sub-agents
+    Explore,
+    /// Builder sub-agents
+    Build,
+    /// Dual-mind reviewer
+    DualMind,
+    /// Context summaries (pinch)
+    Summary,
+    /// Session titles
+    Title,
+    /// Codebase index summaries
+    Index,
+    /// Session learnings
+    Memory,
+}
+
+impl UsageSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Chat => "chat",
+            Self::Explore => "explore",
+            Self::Build => "build",
+            Self::DualMind => "dual_mind",
+            Self::Summary => "summary",
+            Self::Title => "title",
+            Self::Index => "index",
+            Self::Memory => "memory",
+        }
+    }
+}
+
+impl fmt::Display for UsageSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Spending limits in USD (`None` = no limit)
+///
+/// Soft limits warn once when crossed; hard limits stop the agent loop.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct BudgetLimits {
+    #[serde(default)]
+    pub session_soft: Option<f64>,
+    #[serde(default)]
+    pub session_hard: Option<f64>,
+    #[serde(default)]
+    pub daily_soft: Option<f64>,
+    #[serde(default)]
+    pub daily_hard: Option<f64>,
+}
+
+impl BudgetLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Result of checking spend against the budget
+#[derive(Debug, Clone, PartialEq)]
+pub enum BudgetStatus {
+    Ok,
+    /// A soft limit was crossed (reported once per limit)
+    Warning(String),
+    /// A hard limit was reached
+    Exceeded(String),
+}
+
+/// Pricing for `model`, preferring fetched metadata over static config
+pub async fn model_pricing(
+    registry: &ModelRegistry,
+    provider: ProviderId,
+    model: &str,
+) -> Option<ModelPricing> {
+    if let Some(meta) = registry
+        .get_model(model)
+        .await
+        .filter(|m| m.provider == provider)
+    {
+        if let Some(pricing) = ModelPricing::from_metadata(&meta) {
+            return Some(pricing);
+        }
+    }
+    let config = get_provider(provider)?;
+    let info = config.models.iter().find(|m| m.id == model)?;
+    ModelPricing::from_metadata(&ModelMetadata::from_model_info(config, info))
+}
+
+/// Records per-request cost and enforces budgets
+pub struct CostTracker {
+    db: Mutex<Database>,
+    registry: SharedModelRegistry,
+    session_id: Mutex<Option<String>>,
+    /// Known cost of the current session
+    session_cost: Mutex<f64>,
+    limits: Mutex<BudgetLimits>,
+    /// Soft limits already warned about ("session", or the day)
+    warned: Mutex<HashSet<String>>,
+}
+
+impl fmt::Debug for CostTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CostTracker").finish_non_exhaustive()
+    }
+}
+
+impl CostTracker {
+    pub fn new(db: Database, registry: SharedModelRegistry) -> Self {
+        Self {
+            db: Mutex::new(db),
+            registry,
+            session_id: Mutex::new(None),
+            session_cost: Mutex::new(0.0),
+            limits: Mutex::new(BudgetLimits::default()),
+            warned: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Open the usage database at `db_path`
+    pub fn open(db_path: &Path, registry: SharedModelRegistry) -> Result<Self> {
+        Ok(Self::new(Database::new(db_path)?, registry))
+    }
+
+    fn with_store<T>(&self, f: impl FnOnce(&UsageStore) -> Result<T>) -> Result<T> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|_| anyhow!("Usage database lock poisoned"))?;
+        f(&UsageStore::new(&db))
+    }
+
+    /// Attribute subsequent requests to `session_id`
+    pub fn set_session(&self, session_id: Option<&str>) {
+        let Ok(mut current) = self.session_id.lock() else {
+            return;
+        };
+        if current.as_deref() == session_id {
+            return;
+        }
+        *current = session_id.map(str::to_string);
+
+        let cost = session_id
+            .map(|id| self.with_store(|store| store.session_cost(id)))
+            .transpose()
+            .unwrap_or_else(|e| {
+                warn!("Failed to load session cost: {}", e);
+                None
+            })
+            .unwrap_or(0.0);
+        if let Ok(mut session_cost) = self.session_cost.lock() {
+            *session_cost = cost;
+        }
+        if let Ok(mut warned) = self.warned.lock() {
+            warned.remove("session");
+        }
+    }
+
+    /// Known cost of the current session in USD
+    pub fn session_cost(&self) -> f64 {
+        self.session_cost.lock().map(|c| *c).unwrap_or(0.0)
+    }
+
+    /// Known cost of today across all sessions in USD
+    pub fn day_cost(&self) -> Result<f64> {
+        self.with_store(|store| store.day_cost(&today()))
+    }
+
+    pub fn limits(&self) -> BudgetLimits {
+        self.limits.lock().map(|l| *l).unwrap_or_default()
+    }
+
+    pub fn set_limits(&self, limits: BudgetLimits) {
+        if let Ok(mut current) = self.limits.lock() {
+            *current = limits;
+        }
+        // Re-warn against the new limits
+        if let Ok(mut warned) = self.warned.lock() {
+            warned.clear();
+        }
+    }
+
+    /// Current session's usage per model and source
+    pub fn session_breakdown(&self) -> Result<Vec<UsageSummary>> {
+        let Some(session_id) = self.session_id.lock().ok().and_then(|s| s.clone()) else {
+            return Ok(Vec::new());
+        };
+        self.with_store(|store| store.session_breakdown(&session_id))
+    }
+
+    /// Today's usage per model and source
+    pub fn day_breakdown(&self) -> Result<Vec<UsageSummary>> {
+        self.with_store(|store| store.day_breakdown(&today()))
+    }
+
+    async fn pricing(&self, provider: ProviderId, model: &str) -> Option<ModelPricing> {
+        model_pricing(&self.registry, provider, model).await
+    }
+
+    /// Price and store one request's usage, returning its cost if known
+    pub async fn record(
+        &self,
+        provider: ProviderId,
+        model: &str,
+        source: UsageSource,
+        usage: &Usage,
+    ) -> Option<f64> {
+        let cost = self
+            .pricing(provider, model)
+            .await
+            .map(|pricing| pricing.cost(usage));
+        let session_id = self.session_id.lock().ok().and_then(|s| s.clone());
+        let record = UsageRecord {
+            session_id,
+            provider: provider.storage_key().to_string(),
+            model: model.to_string(),
+            source: source.as_str().to_string(),
+            input_tokens: uncached_input(usage),
+            output_tokens: usage.completion_tokens,
+            cache_read_tokens: usage.cache_read_input_tokens,
+            cache_write_tokens: usage.cache_creation_input_tokens,
+            cost_usd: cost,
+        };
+        if let Err(e) = self.with_store(|store| store.record(&record)) {
+            warn!("Failed to record usage: {}", e);
+        }
+        if let (Some(cost), Some(_)) = (cost, record.session_id.as_ref()) {
+            if let Ok(mut session_cost) = self.session_cost.lock() {
+                *session_cost += cost;
+            }
+        }
+        debug!(
+            "Usage {} {} ({}): {} tokens, cost {:?}",
+            record.provider,
+            record.model,
+            record.source,
+            record.total_tokens(),
+            cost
+        );
+        cost
+    }
+
+    /// Whether a hard limit has been reached
+    pub fn is_over_budget(&self) -> bool {
+        matches!(self.check_limits(false), BudgetStatus::Exceeded(_))
+    }
+
+    /// Check spend against the budget before another request
+    ///
+    /// Each soft limit produces a warning only the first time it is crossed.
+    pub fn check_budget(&self) -> BudgetStatus {
+        self.check_limits(true)
+    }
+
+    fn check_limits(&self, warn_once: bool) -> BudgetStatus {
+        let limits = self.limits();
+        if limits.is_empty() {
+            return BudgetStatus::Ok;
+        }
+        let session_cost = self.session_cost();
+        let day_cost = if limits.daily_soft.is_some() || limits.daily_hard.is_some() {
+            self.day_cost().unwrap_or_else(|e| {
+                warn!("Failed to load daily cost: {}", e);
+                0.0
+            })
+        } else {
+            0.0
+        };
+
+        if let Some(limit) = limits.session_hard.filter(|l| session_cost >= *l) {
+            return BudgetStatus::Exceeded(format!(
+                "Session cost ${:.2} has reached the ${:.2} session limit",
+                session_cost, limit
+            ));
+        }
+        if let Some(limit) = limits.daily_hard.filter(|l| day_cost >= *l) {
+            return BudgetStatus::Exceeded(format!(
+                "Today's cost ${:.2} has reached the ${:.2} daily limit",
+                day_cost, limit
+            ));
+        }
+
+        let mut warned = match self.warned.lock() {
+            Ok(warned) => warned,
+            Err(_) => return BudgetStatus::Ok,
+        };
+        if let Some(limit) = limits.session_soft.filter(|l| session_cost >= *l) {
+            if !warn_once || warned.insert("session".to_string()) {
+                return BudgetStatus::Warning(format!(
+                    "Session cost ${:.2} is over the ${:.2} session budget",
+                    session_cost, limit
+                ));
+            }
+        }
+        if let Some(limit) = limits.daily_soft.filter(|l| day_cost >= *l) {
+            if !warn_once || warned.insert(today()) {
+                return BudgetStatus::Warning(format!(
+                    "Today's cost ${:.2} is over the ${:.2} daily budget",
+                    day_cost, limit
+                ));
+            }
+        }
+        BudgetStatus::Ok
+    }
+}
+
+/// Format a USD amount for display (more precision for small amounts)
+pub fn format_cost(cost: f64) -> String {
+    if cost > 0.0 && cost < 0.01 {
+        format!("${:.4}", cost)
+    } else {
+        format!("${:.2}", cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::models::create_model_registry;
+    use tempfile::TempDir;
+
+    fn usage(prompt: usize, completion: usize, cache_read: usize, cache_write: usize) -> Usage {
+        Usage {
+            prompt_tokens: prompt,
+            completion_tokens: completion,
+            total_tokens: prompt + completion,
+            cache_read_input_tokens: cache_read,
+            cache_creation_input_tokens: cache_write,
+        }
+    }
+
+    #[test]
+    fn test_cost_includes_cache_pricing() {
+        let mut meta = ModelMetadata::new("m", "M", ProviderId::Anthropic);
+        meta.input_price = Some(3.0);
+        meta.output_price = Some(15.0);
+        let pricing = ModelPricing::from_metadata(&meta).unwrap();
+
+        // 100k uncached, 800k cache reads, 100k cache writes, 10k output
+        let cost = pricing.cost(&usage(1_000_000, 10_000, 800_000, 100_000));
+        let expected = 0.3 + 0.15 + 800_000.0 * 0.3 / 1e6 + 100_000.0 * 3.75 / 1e6;
+        assert!((cost - expected).abs() < 1e-9);
+
+        meta.input_price = None;
+        assert!(ModelPricing::from_metadata(&meta).is_none());
+    }
+
+    #[test]
+    fn test_merge_usage_keeps_cumulative_counts() {
+        // Anthropic: message_start has input and cache, message_delta has output
+        let mut total = usage(12_000, 0, 10_000, 1_000);
+        merge_usage(&mut total, &usage(1_000, 450, 0, 0));
+        assert_eq!(total.prompt_tokens, 12_000);
+        assert_eq!(total.completion_tokens, 450);
+        assert_eq!(total.cache_read_input_tokens, 10_000);
+        assert_eq!(total.total_tokens, 12_450);
+    }
+
+    #[tokio::test]
+    async fn test_budgets_warn_once_then_stop() {
+        let temp = TempDir::new().unwrap();
+        let sessions =
+            crate::storage::SessionManager::new(Database::new(&temp.path().join("t.db")).unwrap());
+        let session = sessions.create_session("test", None, None).unwrap();
+        let tracker =
+            CostTracker::open(&temp.path().join("t.db"), create_model_registry()).unwrap();
+        tracker.set_session(Some(&session));
+        tracker.set_limits(BudgetLimits {
+            session_soft: Some(0.5),
+            session_hard: Some(1.0),
+            ..Default::default()
+        });
+
+        // Opus 4.5 at $5/$25: 100k input = $0.50
+        let model = "claude-opus-4-5-20251101";
+        let cost = tracker
+            .record(
+                ProviderId::Anthropic,
+                model,
+                UsageSource::Chat,
+                &usage(100_000, 0, 0, 0),
+            )
+            .await;
+        assert!((cost.unwrap() - 0.5).abs() < 1e-9);
+        assert!(matches!(tracker.check_budget(), BudgetStatus::Warning(_)));
+        assert_eq!(tracker.check_budget(), BudgetStatus::Ok);
+
+        tracker
+            .record(
+                ProviderId::Anthropic,
+                model,
+                UsageSource::Explore,
+                &usage(100_000, 0, 0, 0),
+            )
+            .await;
+        assert!(matches!(tracker.check_budget(), BudgetStatus::Exceeded(_)));
+        assert!(tracker.is_over_budget());
+
+        // Unknown models are recorded without a cost
+        let cost = tracker
+            .record(
+                ProviderId::Anthropic,
+                "unknown",
+                UsageSource::Chat,
+                &usage(10, 10, 0, 0),
+            )
+            .await;
+        assert_eq!(cost, None);
+        let breakdown = tracker.session_breakdown().unwrap();
+        assert_eq!(breakdown.len(), 3);
+
+        // Switching sessions reloads the running total
+        tracker.set_session(None);
+        assert_eq!(tracker.session_cost(), 0.0);
+        tracker.set_session(Some(&session));
+        assert!((tracker.session_cost() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,421 @@
+//! User-defined providers
+//!
+//! Self-hosted gateways (vLLM, llama.cpp, Ollama) and internal proxies are
+//! declared in `~/.krusty/providers.json`:
+//!
+//! ```json
+//! {
+//!   "providers": {
+//!     "local-vllm": {
+//!       "name": "Local vLLM",
+//!       "base_url": "http://localhost:8000/v1",
+//!       "api_format": "openai",
+//!       "auth_header": "none",
+//!       "discover_models": true,
+//!       "models": [
+//!         { "id": "qwen3-coder", "context_window": 131072, "max_output": 16384 }
+//!       ]
+//!     }
+//!   }
+//! }
+//! ```
+//!
+//! The file is read once, the first time the provider registry is used.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use tracing::{error, info, warn};
+
+use super::client::config::api_base_url;
+use super::models::{ApiFormat, ModelMetadata};
+use super::providers::{
+    builtin_providers, AuthHeader, CustomProviderId, ModelInfo, ProviderConfig, ProviderId,
+    ReasoningFormat,
+};
+
+/// Context window assumed for models without a configured limit
+const DEFAULT_CONTEXT_WINDOW: usize = 128_000;
+
+/// Max output tokens assumed for models without a configured limit
+const DEFAULT_MAX_OUTPUT: usize = 8_192;
+
+/// Contents of providers.json
+#[derive(Debug, Default, Deserialize)]
+struct CustomProvidersFile {
+    #[serde(default)]
+    providers: BTreeMap<String, CustomProviderDef>,
+}
+
+/// One provider entry, keyed by its name in the file
+#[derive(Debug, Deserialize)]
+struct CustomProviderDef {
+    /// Display name (defaults to the key)
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    /// API root (e.g. `http://localhost:8000/v1`) or full endpoint URL
+    base_url: String,
+    #[serde(default = "default_api_format")]
+    api_format: ApiFormat,
+    #[serde(default = "default_auth_header")]
+    auth_header: AuthHeader,
+    /// Extra headers sent with every request
+    #[serde(default)]
+    headers: HashMap<String, String>,
+    /// Fetch the model list from `<base_url>/models`
+    #[serde(default)]
+    discover_models: bool,
+    /// Model to use when none is selected (defaults to the first model)
+    #[serde(default)]
+    default_model: Option<String>,
+    /// Known models and their limits
+    #[serde(default)]
+    models: Vec<CustomModelDef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CustomModelDef {
+    id: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    context_window: Option<usize>,
+    #[serde(default)]
+    max_output: Option<usize>,
+    #[serde(default)]
+    reasoning: Option<ReasoningFormat>,
+    /// USD per million input tokens, for cost tracking
+    #[serde(default)]
+    input_price: Option<f64>,
+    /// USD per million output tokens
+    #[serde(default)]
+    output_price: Option<f64>,
+}
+
+fn default_api_format() -> ApiFormat {
+    ApiFormat::OpenAI
+}
+
+fn default_auth_header() -> AuthHeader {
+    AuthHeader::Bearer
+}
+
+/// Load user-defined providers from `path`
+///
+/// A missing file means no custom providers. Invalid entries are logged and
+/// skipped so one typo doesn't hide the built-in providers.
+pub fn load(path: &Path) -> Vec<ProviderConfig> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            warn!("Failed to read {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+    let file: CustomProvidersFile = match serde_json::from_str(&contents) {
+        Ok(file) => file,
+        Err(e) => {
+            error!("Failed to parse {}: {}", path.display(), e);
+            return Vec::new();
+        }
+    };
+
+    let providers: Vec<ProviderConfig> = file
+        .providers
+        .into_iter()
+        .filter_map(|(key, def)| match to_provider_config(&key, def) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                error!("Skipping custom provider '{}': {}", key, e);
+                None
+            }
+        })
+        .collect();
+    if !providers.is_empty() {
+        info!("Loaded {} custom providers", providers.len());
+    }
+    providers
+}
+
+/// Parse providers.json contents, failing on the first invalid entry
+pub fn parse(contents: &str) -> Result<Vec<ProviderConfig>> {
+    let file: CustomProvidersFile = serde_json::from_str(contents)?;
+    file.providers
+        .into_iter()
+        .map(|(key, def)| {
+            to_provider_config(&key, def).with_context(|| format!("custom provider '{}'", key))
+        })
+        .collect()
+}
+
+fn to_provider_config(key: &str, def: CustomProviderDef) -> Result<ProviderConfig> {
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        bail!("name may only contain letters, digits, '-', '_' and '.'");
+    }
+    if builtin_providers()
+        .iter()
+        .any(|p| p.id.storage_key().eq_ignore_ascii_case(key))
+    {
+        bail!("name clashes with a built-in provider");
+    }
+    if !def.base_url.starts_with("http://") && !def.base_url.starts_with("https://") {
+        bail!("base_url must be an http(s) URL");
+    }
+
+    let mut models: Vec<ModelInfo> = def
+        .models
+        .into_iter()
+        .map(|m| ModelInfo {
+            display_name: m.name.unwrap_or_else(|| m.id.clone()),
+            id: m.id,
+            context_window: m.context_window.unwrap_or(DEFAULT_CONTEXT_WINDOW),
+            max_output: m.max_output.unwrap_or(DEFAULT_MAX_OUTPUT),
+            reasoning: m.reasoning,
+            input_price: m.input_price,
+            output_price: m.output_price,
+        })
+        .collect();
+
+    // The default model goes first, since that's what default_model() returns
+    if let Some(default) = def.default_model {
+        let model = match models.iter().position(|m| m.id == default) {
+            Some(idx) => models.remove(idx),
+            None => ModelInfo::new(
+                &default,
+                &default,
+                DEFAULT_CONTEXT_WINDOW,
+                DEFAULT_MAX_OUTPUT,
+            ),
+        };
+        models.insert(0, model);
+    }
+    if models.is_empty() {
+        bail!("needs `models` or `default_model`");
+    }
+
+    let name = def.name.unwrap_or_else(|| key.to_string());
+    Ok(ProviderConfig {
+        id: ProviderId::Custom(CustomProviderId::new(key)),
+        description: def
+            .description
+            .unwrap_or_else(|| format!("Custom provider at {}", def.base_url)),
+        name,
+        base_url: def.base_url,
+        auth_header: def.auth_header,
+        models,
+        supports_tools: true,
+        dynamic_models: def.discover_models,
+        pricing_hint: None,
+        custom_headers: def.headers,
+        api_format: Some(def.api_format),
+    })
+}
+
+/// Model list URL for a provider (`<api root>/models`)
+pub fn models_url(provider: &ProviderConfig) -> String {
+    format!("{}/models", api_base_url(&provider.base_url))
+}
+
+/// Response from an OpenAI/Anthropic-style `/models` endpoint
+#[derive(Debug, Deserialize)]
+struct ModelsResponse {
+    data: Vec<RemoteModel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RemoteModel {
+    id: String,
+    /// Anthropic-style display name
+    #[serde(default)]
+    display_name: Option<String>,
+    /// vLLM reports the served context length here
+    #[serde(default)]
+    max_model_len: Option<usize>,
+    /// OpenRouter-style gateways use this name instead
+    #[serde(default)]
+    context_length: Option<usize>,
+}
+
+/// Fetch the model list of a provider with `discover_models` enabled
+///
+/// Limits and reasoning format come from the provider's configured models
+/// when listed there; otherwise from the response or defaults.
+pub async fn fetch_models(provider: &ProviderConfig, api_key: &str) -> Result<Vec<ModelMetadata>> {
+    let url = models_url(provider);
+    info!("Fetching models from {} ({})", provider.name, url);
+
+    let mut request = Client::new().get(&url);
+    match provider.auth_header {
+        AuthHeader::Bearer => request = request.bearer_auth(api_key),
+        AuthHeader::XApiKey => {
+            request = request
+                .header("x-api-key", api_key)
+                .header("anthropic-version", "2023-06-01")
+        }
+        AuthHeader::None => {}
+    }
+    for (key, value) in &provider.custom_headers {
+        request = request.header(key.as_str(), value.as_str());
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    if !status.is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(anyhow!(
+            "{} models API error: {} - {}",
+            provider.name,
+            status,
+            error_text
+        ));
+    }
+
+    let data: ModelsResponse = response.json().await?;
+    info!("{} returned {} models", provider.name, data.data.len());
+    Ok(data
+        .data
+        .into_iter()
+        .map(|remote| to_model_metadata(provider, remote))
+        .collect())
+}
+
+fn to_model_metadata(provider: &ProviderConfig, remote: RemoteModel) -> ModelMetadata {
+    if let Some(known) = provider.models.iter().find(|m| m.id == remote.id) {
+        return ModelMetadata::from_model_info(provider, known);
+    }
+    let context_window = remote
+        .max_model_len
+        .or(remote.context_length)
+        .unwrap_or(DEFAULT_CONTEXT_WINDOW);
+    let display_name = remote.display_name.unwrap_or_else(|| remote.id.clone());
+    let mut meta = ModelMetadata::new(&remote.id, &display_name, provider.id)
+        .with_context(context_window, DEFAULT_MAX_OUTPUT.min(context_window));
+    meta.api_format = provider.api_format.unwrap_or_default();
+    meta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_custom_providers() {
+        let providers = parse(
+            r#"{
+                "providers": {
+                    "local-vllm": {
+                        "name": "Local vLLM",
+                        "base_url": "http://localhost:8000/v1",
+                        "auth_header": "none",
+                        "discover_models": true,
+                        "default_model": "qwen3-coder",
+                        "models": [
+                            { "id": "deepseek-r1", "context_window": 64000, "reasoning": "deepseek" },
+                            { "id": "qwen3-coder", "max_output": 16384 }
+                        ]
+                    },
+                    "proxy": {
+                        "base_url": "https://llm.internal/anthropic/v1/messages",
+                        "api_format": "anthropic",
+                        "auth_header": "x_api_key",
+                        "headers": { "X-Team": "platform" },
+                        "models": [{ "id": "claude-sonnet-4-5" }]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(providers.len(), 2);
+
+        let vllm = &providers[0];
+        assert_eq!(
+            vllm.id,
+            ProviderId::Custom(CustomProviderId::new("local-vllm"))
+        );
+        assert_eq!(vllm.id.storage_key(), "local-vllm");
+        assert_eq!(vllm.name, "Local vLLM");
+        assert_eq!(vllm.auth_header, AuthHeader::None);
+        assert_eq!(vllm.api_format, Some(ApiFormat::OpenAI));
+        assert!(vllm.dynamic_models);
+        assert_eq!(vllm.default_model(), "qwen3-coder");
+        assert_eq!(vllm.models[0].max_output, 16_384);
+        assert_eq!(vllm.models[1].context_window, 64_000);
+        assert_eq!(vllm.models[1].reasoning, Some(ReasoningFormat::DeepSeek));
+        assert_eq!(models_url(vllm), "http://localhost:8000/v1/models");
+
+        let proxy = &providers[1];
+        assert_eq!(proxy.name, "proxy");
+        assert_eq!(proxy.auth_header, AuthHeader::XApiKey);
+        assert_eq!(proxy.api_format, Some(ApiFormat::Anthropic));
+        assert_eq!(proxy.custom_headers["X-Team"], "platform");
+        assert!(!proxy.has_model("other"));
+        assert_eq!(
+            models_url(proxy),
+            "https://llm.internal/anthropic/v1/models"
+        );
+    }
+
+    #[test]
+    fn test_invalid_custom_providers() {
+        let err = |json: &str| parse(json).unwrap_err().root_cause().to_string();
+
+        assert!(
+            err(r#"{"providers":{"anthropic":{"base_url":"http://x","default_model":"m"}}}"#)
+                .contains("built-in")
+        );
+        assert!(
+            err(r#"{"providers":{"a b":{"base_url":"http://x","default_model":"m"}}}"#)
+                .contains("name")
+        );
+        assert!(
+            err(r#"{"providers":{"x":{"base_url":"localhost","default_model":"m"}}}"#)
+                .contains("base_url")
+        );
+        assert!(err(r#"{"providers":{"x":{"base_url":"http://x"}}}"#).contains("models"));
+    }
+
+    #[test]
+    fn test_discovered_model_limits() {
+        let provider = &parse(
+            r#"{"providers":{"llama":{"base_url":"http://localhost:8080/v1",
+                "models":[{"id":"known","context_window":32000,"max_output":4000}]}}}"#,
+        )
+        .unwrap()[0];
+
+        let known = to_model_metadata(
+            provider,
+            RemoteModel {
+                id: "known".to_string(),
+                display_name: None,
+                max_model_len: Some(8_000),
+                context_length: None,
+            },
+        );
+        assert_eq!(known.context_window, 32_000);
+        assert_eq!(known.max_output, 4_000);
+
+        let served = to_model_metadata(
+            provider,
+            RemoteModel {
+                id: "served".to_string(),
+                display_name: None,
+                max_model_len: Some(4_096),
+                context_length: None,
+            },
+        );
+        assert_eq!(served.context_window, 4_096);
+        assert_eq!(served.max_output, 4_096);
+        assert_eq!(served.api_format, ApiFormat::OpenAI);
+        assert_eq!(served.provider, provider.id);
+    }
+}
@@ -5,6 +5,52 @@
 
 use serde_json::Value;
 
+use crate::ai::types::Usage;
+
+/// Anthropic-format usage from counts where `input` includes `cached`
+fn anthropic_usage(input: u64, output: u64, cached: u64) -> Value {
+    serde_json::json!({
+        "input_tokens": input.saturating_sub(cached),
+        "output_tokens": output,
+        "cache_read_input_tokens": cached
+    })
+}
+
+fn count(value: &Value, key: &str) -> u64 {
+    value.get(key).and_then(|v| v.as_u64()).unwrap_or(0)
+}
+
+/// Anthropic-format usage from an OpenAI Chat Completions or Responses `usage` object
+pub fn usage_from_openai(usage: &Value) -> Value {
+    // Chat Completions uses prompt/completion, Responses uses input/output
+    let input = count(usage, "prompt_tokens").max(count(usage, "input_tokens"));
+    let output = count(usage, "completion_tokens").max(count(usage, "output_tokens"));
+    let cached = ["prompt_tokens_details", "input_tokens_details"]
+        .iter()
+        .filter_map(|key| usage.get(*key))
+        .map(|details| count(details, "cached_tokens"))
+        .max()
+        .unwrap_or(0);
+    anthropic_usage(input, output, cached)
+}
+
+/// Usage of an Anthropic-format response, if it reports any
+pub fn extract_usage(response: &Value) -> Option<Usage> {
+    let usage = response.get("usage").filter(|u| u.is_object())?;
+    let input = count(usage, "input_tokens") as usize;
+    let output = count(usage, "output_tokens") as usize;
+    let cache_read = count(usage, "cache_read_input_tokens") as usize;
+    let cache_write = count(usage, "cache_creation_input_tokens") as usize;
+    let prompt = input + cache_read + cache_write;
+    Some(Usage {
+        prompt_tokens: prompt,
+        completion_tokens: output,
+        total_tokens: prompt + output,
+        cache_read_input_tokens: cache_read,
+        cache_creation_input_tokens: cache_write,
+    })
+}
+
 /// Convert OpenAI response format to Anthropic format
 pub fn normalize_openai_response(response: &Value) -> Value {
     let mut content: Vec<Value> = vec![];
@@ -63,7 +109,8 @@ pub fn normalize_openai_response(response: &Value) -> Value {
     serde_json::json!({
         "content": content,
         "stop_reason": stop_reason,
-        "model": response.get("model").cloned().unwrap_or(Value::Null)
+        "model": response.get("model").cloned().unwrap_or(Value::Null),
+        "usage": response.get("usage").map(usage_from_openai)
     })
 }
 
@@ -121,10 +168,19 @@ pub fn normalize_google_response(response: &Value) -> Value {
         }
     }
 
+    let usage = response.get("usageMetadata").map(|u| {
+        anthropic_usage(
+            count(u, "promptTokenCount"),
+            count(u, "candidatesTokenCount") + count(u, "thoughtsTokenCount"),
+            count(u, "cachedContentTokenCount"),
+        )
+    });
+
     serde_json::json!({
         "content": content,
         "stop_reason": stop_reason,
-        "model": response.get("modelVersion").cloned().unwrap_or(Value::Null)
+        "model": response.get("modelVersion").cloned().unwrap_or(Value::Null),
+        "usage": usage
     })
 }
 
@@ -224,6 +280,7 @@ pub fn normalize_codex_response(response: &Value) -> Value {
     serde_json::json!({
         "content": content,
         "stop_reason": stop_reason,
-        "model": response.get("model").cloned().unwrap_or(Value::Null)
+        "model": response.get("model").cloned().unwrap_or(Value::Null),
+        "usage": response.get("usage").map(usage_from_openai)
     })
 }
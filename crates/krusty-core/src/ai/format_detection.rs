@@ -4,7 +4,7 @@
 //! Used by both ACP and TUI to route requests correctly.
 
 use super::models::ApiFormat;
-use super::providers::ProviderId;
+use super::providers::{get_provider, ProviderId};
 
 /// Detect the appropriate API format for a provider/model combination
 ///
@@ -12,11 +12,15 @@ use super::providers::ProviderId;
 /// Provider-specific routing:
 /// - OpenCodeZen: model-based detection (Claude→Anthropic, GPT-5→OpenAIResponses, Gemini→Google, etc)
 /// - Kimi, OpenAI: OpenAI chat/completions format
+/// - Custom providers: the format declared in providers.json
 /// - All others (Anthropic, OpenRouter, MiniMax, ZAI): Anthropic format
 pub fn detect_api_format(provider: ProviderId, model: &str) -> ApiFormat {
     match provider {
         ProviderId::OpenCodeZen => detect_opencodezen_format(model),
         ProviderId::Kimi | ProviderId::OpenAI => ApiFormat::OpenAI,
+        ProviderId::Custom(_) => get_provider(provider)
+            .and_then(|p| p.api_format)
+            .unwrap_or(ApiFormat::OpenAI),
         _ => ApiFormat::Anthropic,
     }
 }
@@ -10,12 +10,14 @@ pub mod format_detection;
 pub mod retry;
 
 // Provider-specific configuration
+pub mod custom_providers;
 pub mod glm;
 pub mod models;
 pub mod opencodezen;
 pub mod openrouter;
 
 // Shared infrastructure
+pub mod cost;
 pub mod parsers;
 pub mod providers;
 pub mod reasoning;
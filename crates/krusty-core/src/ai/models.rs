@@ -8,7 +8,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
-use super::providers::{ProviderId, ReasoningFormat};
+use super::providers::{ModelInfo, ProviderConfig, ProviderId, ReasoningFormat};
 
 /// API format for model requests
 ///
@@ -20,8 +20,10 @@ pub enum ApiFormat {
     #[default]
     Anthropic,
     /// OpenAI Chat Completions API (/v1/chat/completions)
+    #[serde(alias = "openai")]
     OpenAI,
     /// OpenAI Responses API (/v1/responses) - GPT-5 models
+    #[serde(alias = "openai_responses")]
     OpenAIResponses,
     /// Google AI API (/v1/models/{model})
     Google,
@@ -56,6 +58,12 @@ pub struct ModelMetadata {
     pub input_price: Option<f64>,
     /// Output/completion price per million tokens
     pub output_price: Option<f64>,
+    /// Cache read price per million tokens (None = derived from input price)
+    #[serde(default)]
+    pub cache_read_price: Option<f64>,
+    /// Cache write price per million tokens (None = derived from input price)
+    #[serde(default)]
+    pub cache_write_price: Option<f64>,
 
     // Provider-specific metadata
     /// Sub-provider for OpenRouter models (e.g., "anthropic", "openai")
@@ -84,12 +92,29 @@ impl ModelMetadata {
             supports_vision: false,
             input_price: None,
             output_price: None,
+            cache_read_price: None,
+            cache_write_price: None,
             sub_provider: None,
             is_free: false,
             api_format: ApiFormat::default(),
         }
     }
 
+    /// Metadata for a model from a provider's static configuration
+    pub fn from_model_info(provider: &ProviderConfig, model: &ModelInfo) -> Self {
+        let mut meta = Self::new(&model.id, &model.display_name, provider.id)
+            .with_context(model.context_window, model.max_output);
+        if let Some(format) = model.reasoning {
+            meta = meta.with_thinking(format);
+        }
+        if let Some(format) = provider.api_format {
+            meta.api_format = format;
+        }
+        meta.input_price = model.input_price;
+        meta.output_price = model.output_price;
+        meta
+    }
+
     /// Builder: set context window
     pub fn with_context(mut self, context: usize, max_output: usize) -> Self {
         self.context_window = context;
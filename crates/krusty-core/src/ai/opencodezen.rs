@@ -113,6 +113,8 @@ fn parse_model(raw: OpenCodeZenModel) -> ModelMetadata {
         supports_vision: false,
         input_price: None,
         output_price: None,
+        cache_read_price: None,
+        cache_write_price: None,
         sub_provider: Some(raw.owned_by),
         is_free,
         api_format,
@@ -1,13 +1,15 @@
 //! AI provider configuration
 //!
 //! Defines provider types, configurations, and built-in provider registry
-//! for Anthropic-compatible API endpoints.
+//! for Anthropic-compatible API endpoints. User-defined providers from
+//! `~/.krusty/providers.json` are merged into the registry on first use.
 
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, Mutex};
 
+use crate::ai::custom_providers;
 use crate::ai::models::ApiFormat;
 use crate::auth::OpenAIAuthType;
 
@@ -32,21 +34,46 @@ pub enum ProviderId {
     MiniMax,
     Kimi,
     OpenAI,
+    /// User-defined provider from providers.json
+    Custom(CustomProviderId),
 }
 
+/// Built-in providers in display order
+/// Order: Anthropic first (default), then smallest to largest, OpenRouter last
+const BUILTIN_PROVIDER_ORDER: &[ProviderId] = &[
+    ProviderId::Anthropic,   // Default provider, always first
+    ProviderId::OpenAI,      // OpenAI direct (OAuth or API key)
+    ProviderId::MiniMax,     // 3 models
+    ProviderId::Kimi,        // 2 models
+    ProviderId::ZAi,         // 2 models
+    ProviderId::OpenCodeZen, // 11 models
+    ProviderId::OpenRouter,  // 100+ dynamic models
+];
+
+/// All provider IDs: built-ins, then user-defined providers
+static ALL_PROVIDER_IDS: LazyLock<Vec<ProviderId>> = LazyLock::new(|| {
+    BUILTIN_PROVIDER_ORDER
+        .iter()
+        .copied()
+        .chain(PROVIDERS.iter().map(|p| p.id).filter(ProviderId::is_custom))
+        .collect()
+});
+
 impl ProviderId {
     /// Get all available provider IDs
-    /// Order: Anthropic first (default), then smallest to largest, OpenRouter last
+    /// Order: built-ins (Anthropic first, OpenRouter last), then custom providers
     pub fn all() -> &'static [ProviderId] {
-        &[
-            ProviderId::Anthropic,   // Default provider, always first
-            ProviderId::OpenAI,      // OpenAI direct (OAuth or API key)
-            ProviderId::MiniMax,     // 3 models
-            ProviderId::Kimi,        // 2 models
-            ProviderId::ZAi,         // 2 models
-            ProviderId::OpenCodeZen, // 11 models
-            ProviderId::OpenRouter,  // 100+ dynamic models, always last
-        ]
+        &ALL_PROVIDER_IDS
+    }
+
+    /// Whether this is a user-defined provider
+    pub fn is_custom(&self) -> bool {
+        matches!(self, ProviderId::Custom(_))
+    }
+
+    /// Whether requests need an API key (false for unauthenticated local endpoints)
+    pub fn requires_api_key(&self) -> bool {
+        get_provider(*self).is_none_or(|p| p.auth_header != AuthHeader::None)
     }
 
     /// Get the storage key for this provider (used in credentials.json)
@@ -59,6 +86,7 @@ impl ProviderId {
             ProviderId::MiniMax => "minimax",
             ProviderId::Kimi => "kimi",
             ProviderId::OpenAI => "openai",
+            ProviderId::Custom(id) => id.as_str(),
         }
     }
 
@@ -91,18 +119,65 @@ impl fmt::Display for ProviderId {
             ProviderId::MiniMax => write!(f, "MiniMax"),
             ProviderId::Kimi => write!(f, "Kimi"),
             ProviderId::OpenAI => write!(f, "OpenAI"),
+            ProviderId::Custom(id) => match get_provider(*self) {
+                Some(config) => write!(f, "{}", config.name),
+                None => write!(f, "{}", id.as_str()),
+            },
         }
     }
 }
 
+/// Key of a user-defined provider (its name in providers.json)
+///
+/// Keys are interned so `ProviderId` stays `Copy`; there are only ever a
+/// handful of them, loaded once at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CustomProviderId(&'static str);
+
+impl CustomProviderId {
+    pub fn new(key: &str) -> Self {
+        static INTERNED: LazyLock<Mutex<HashSet<&'static str>>> =
+            LazyLock::new(|| Mutex::new(HashSet::new()));
+
+        let mut interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(existing) = interned.get(key) {
+            return Self(existing);
+        }
+        let key: &'static str = Box::leak(key.to_string().into_boxed_str());
+        interned.insert(key);
+        Self(key)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+impl Serialize for CustomProviderId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for CustomProviderId {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|key| Self::new(&key))
+    }
+}
+
 /// How to send the API key in requests
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum AuthHeader {
     /// Use `x-api-key: <key>` header (Anthropic style)
     #[default]
+    #[serde(alias = "x_api_key", alias = "x-api-key")]
     XApiKey,
     /// Use `Authorization: Bearer <key>` header (OpenAI style)
+    #[serde(alias = "bearer")]
     Bearer,
+    /// No authentication (local endpoints)
+    #[serde(alias = "none")]
+    None,
 }
 
 // ============================================================================
@@ -114,10 +189,13 @@ pub enum AuthHeader {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ReasoningFormat {
     /// Anthropic Claude: `thinking.budget_tokens` (we use max: 32000)
+    #[serde(alias = "anthropic")]
     Anthropic,
     /// OpenAI o1/o3/GPT-5: `reasoning_effort: "high"`
+    #[serde(alias = "openai")]
     OpenAI,
     /// DeepSeek R1: `reasoning.enabled: true`
+    #[serde(alias = "deepseek")]
     DeepSeek,
 }
 
@@ -134,6 +212,12 @@ pub struct ModelInfo {
     pub max_output: usize,
     /// Reasoning/thinking support (None = not supported)
     pub reasoning: Option<ReasoningFormat>,
+    /// Input price per million tokens (None = unknown)
+    #[serde(default)]
+    pub input_price: Option<f64>,
+    /// Output price per million tokens (None = unknown)
+    #[serde(default)]
+    pub output_price: Option<f64>,
 }
 
 impl ModelInfo {
@@ -144,9 +228,18 @@ impl ModelInfo {
             context_window,
             max_output,
             reasoning: None,
+            input_price: None,
+            output_price: None,
         }
     }
 
+    /// Set list pricing in USD per million tokens
+    pub fn with_pricing(mut self, input: f64, output: f64) -> Self {
+        self.input_price = Some(input);
+        self.output_price = Some(output);
+        self
+    }
+
     /// Add Anthropic-style extended thinking support
     pub fn with_anthropic_thinking(mut self) -> Self {
         self.reasoning = Some(ReasoningFormat::Anthropic);
@@ -178,6 +271,9 @@ pub struct ProviderConfig {
     /// Custom headers to send with requests (e.g., User-Agent for Kimi)
     #[serde(default)]
     pub custom_headers: HashMap<String, String>,
+    /// Fixed API format for every model (None = detected per provider/model)
+    #[serde(default)]
+    pub api_format: Option<ApiFormat>,
 }
 
 impl ProviderConfig {
@@ -413,7 +509,9 @@ impl ProviderCapabilities {
                 web_plugins: false,
             },
             // Other providers: minimal capabilities
-            ProviderId::ZAi | ProviderId::MiniMax | ProviderId::Kimi => Self::default(),
+            ProviderId::ZAi | ProviderId::MiniMax | ProviderId::Kimi | ProviderId::Custom(_) => {
+                Self::default()
+            }
         }
     }
 }
@@ -435,25 +533,29 @@ static BUILTIN_PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
                     200_000,
                     16_384,
                 )
-                .with_anthropic_thinking(),
+                .with_anthropic_thinking()
+                .with_pricing(5.0, 25.0),
                 ModelInfo::new(
                     "claude-sonnet-4-5-20250929",
                     "Claude Sonnet 4.5",
                     1_000_000, // Sonnet 4.5 has 1M context
                     16_384,
                 )
-                .with_anthropic_thinking(),
+                .with_anthropic_thinking()
+                .with_pricing(3.0, 15.0),
                 ModelInfo::new(
                     "claude-haiku-4-5-20251001",
                     "Claude Haiku 4.5",
                     200_000,
                     16_384,
-                ),
+                )
+                .with_pricing(1.0, 5.0),
             ],
             supports_tools: true,
             dynamic_models: false,
             pricing_hint: None,
             custom_headers: HashMap::new(),
+            api_format: None,
         },
         // OpenRouter - access to 100+ models (Anthropic-compatible "skin")
         ProviderConfig {
@@ -557,6 +659,7 @@ static BUILTIN_PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
             dynamic_models: true,
             pricing_hint: None,
             custom_headers: HashMap::new(),
+            api_format: None,
         },
         // OpenCode Zen - curated models for coding agents (Anthropic-compatible)
         ProviderConfig {
@@ -589,6 +692,7 @@ static BUILTIN_PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
             dynamic_models: true,
             pricing_hint: None,
             custom_headers: HashMap::new(),
+            api_format: None,
         },
         // Z.ai - GLM Coding Plan (Anthropic-compatible endpoint)
         ProviderConfig {
@@ -605,6 +709,7 @@ static BUILTIN_PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
             dynamic_models: false,
             pricing_hint: None,
             custom_headers: HashMap::new(),
+            api_format: None,
         },
         // MiniMax - M2 models (Anthropic-compatible API)
         ProviderConfig {
@@ -633,6 +738,7 @@ static BUILTIN_PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
             dynamic_models: false,
             pricing_hint: None,
             custom_headers: HashMap::new(),
+            api_format: None,
         },
         // Kimi Code - Coding agent API (OpenAI-compatible format)
         // API: api.kimi.com/coding/v1 (requires KimiCLI User-Agent)
@@ -656,6 +762,7 @@ static BUILTIN_PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
                 // Required: Kimi Code API checks User-Agent for coding agent access
                 ("User-Agent".to_string(), "KimiCLI/1.0".to_string()),
             ]),
+            api_format: None,
         },
         // OpenAI - Direct access with OAuth or API key (OpenAI-compatible format)
         // Supports OAuth browser flow, device code flow, and API key authentication
@@ -682,6 +789,7 @@ static BUILTIN_PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
             dynamic_models: true,
             pricing_hint: None,
             custom_headers: HashMap::new(),
+            api_format: None,
         },
     ]
 });
@@ -691,10 +799,25 @@ pub fn builtin_providers() -> &'static [ProviderConfig] {
     &BUILTIN_PROVIDERS
 }
 
+/// Built-in providers followed by user-defined ones
+static PROVIDERS: LazyLock<Vec<ProviderConfig>> = LazyLock::new(|| {
+    let mut providers = BUILTIN_PROVIDERS.clone();
+    providers.extend(custom_providers::load(
+        &crate::paths::custom_providers_path(),
+    ));
+    providers
+});
+
+/// Get all provider configurations, including user-defined ones
+pub fn all_providers() -> &'static [ProviderConfig] {
+    &PROVIDERS
+}
+
 /// Get a specific provider configuration by ID
 pub fn get_provider(id: ProviderId) -> Option<&'static ProviderConfig> {
-    BUILTIN_PROVIDERS.iter().find(|p| p.id == id)
+    PROVIDERS.iter().find(|p| p.id == id)
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
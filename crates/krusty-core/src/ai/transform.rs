@@ -184,8 +184,12 @@ pub fn wrap_provider_options(options: Value, provider_id: ProviderId) -> Provide
                 }
             }
         }
-        ProviderId::ZAi | ProviderId::MiniMax | ProviderId::Kimi | ProviderId::OpenAI => {
-            // For OpenAI-compatible providers (GLM, MiniMax, Kimi, OpenAI)
+        ProviderId::ZAi
+        | ProviderId::MiniMax
+        | ProviderId::Kimi
+        | ProviderId::OpenAI
+        | ProviderId::Custom(_) => {
+            // For OpenAI-compatible providers (GLM, MiniMax, Kimi, OpenAI, custom)
             // Check if options contain reasoning_content (DeepSeek/MiniMax style)
             if options
                 .as_object()
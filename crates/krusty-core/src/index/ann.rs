@@ -0,0 +1,966 @@
+//! Approximate nearest-neighbour search over symbol embeddings
+//!
+//! Each codebase with at least [`ANN_MIN_SYMBOLS`] embedded symbols gets a
+//! hierarchical navigable small world (HNSW) graph stored next to the
+//! database as `ann/<codebase_id>.hnsw`. The indexer keeps it in step with
+//! `codebase_index` after every run: new symbols are inserted, removed ones
+//! tombstoned, and the graph is rebuilt once too many tombstones pile up.
+//! Updates are appended to a journal (`<codebase_id>.hnsw.log`) so a small
+//! edit doesn't rewrite the whole file; the journal is folded back into the
+//! graph file once it grows past a fraction of the graph.
+//! Smaller codebases have no graph and are searched by brute force.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use rusqlite::Connection;
+use tracing::{info, warn};
+
+use super::embeddings::{EmbeddingEngine, EMBEDDING_DIM};
+
+/// Embedded symbols needed before a codebase gets a graph
+pub const ANN_MIN_SYMBOLS: usize = 5_000;
+
+/// Candidate list size when searching (higher is slower but more accurate)
+pub const DEFAULT_EF_SEARCH: usize = 96;
+
+/// Neighbours kept per node above layer 0
+const M: usize = 16;
+
+/// Neighbours kept per node on layer 0
+const M0: usize = 2 * M;
+
+/// Candidate list size when inserting
+const EF_CONSTRUCTION: usize = 128;
+
+/// Highest layer a node can be assigned to
+const MAX_LEVEL: usize = 16;
+
+/// Fraction of tombstoned nodes that triggers a rebuild
+const MAX_TOMBSTONE_RATIO: f32 = 0.25;
+
+/// Journal records, as a fraction of the graph, that trigger rewriting
+/// the graph file
+const MAX_LOG_RATIO: f32 = 0.1;
+
+/// File header: format name and version
+const MAGIC: &[u8; 8] = b"KRHNSW01";
+
+/// Node index paired with its distance to the query, ordered by distance
+#[derive(Clone, Copy, PartialEq)]
+struct Scored(f32, u32);
+
+impl Eq for Scored {}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+/// HNSW graph over unit-length vectors, keyed by symbol id
+///
+/// Distance is `1 - cosine similarity`; vectors are normalized on insert.
+#[derive(Clone)]
+pub struct HnswIndex {
+    dim: usize,
+    /// Symbol id of each node
+    ids: Vec<i64>,
+    /// Node vectors, `dim` values each
+    vectors: Vec<f32>,
+    /// Neighbours of each node, per layer from 0 up to the node's level
+    links: Vec<Vec<Vec<u32>>>,
+    deleted: Vec<bool>,
+    deleted_count: usize,
+    nodes: HashMap<i64, u32>,
+    entry: Option<u32>,
+}
+
+impl HnswIndex {
+    pub fn new(dim: usize) -> Self {
+        Self {
+            dim,
+            ids: Vec::new(),
+            vectors: Vec::new(),
+            links: Vec::new(),
+            deleted: Vec::new(),
+            deleted_count: 0,
+            nodes: HashMap::new(),
+            entry: None,
+        }
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Number of searchable (not removed) vectors
+    pub fn len(&self) -> usize {
+        self.ids.len() - self.deleted_count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(&self, id: i64) -> bool {
+        self.nodes.contains_key(&id)
+    }
+
+    /// Symbol ids of all searchable vectors
+    pub fn ids(&self) -> impl Iterator<Item = i64> + '_ {
+        self.nodes.keys().copied()
+    }
+
+    /// Whether enough nodes were removed that [`rebuilt`](Self::rebuilt) pays off
+    pub fn needs_rebuild(&self) -> bool {
+        self.deleted_count as f32 > self.ids.len() as f32 * MAX_TOMBSTONE_RATIO
+    }
+
+    /// Add a vector, replacing any previous vector for the same id
+    pub fn insert(&mut self, id: i64, vector: &[f32]) -> Result<()> {
+        if vector.len() != self.dim {
+            bail!(
+                "Vector has {} dimensions, index expects {}",
+                vector.len(),
+                self.dim
+            );
+        }
+        self.remove(id);
+
+        let node = self.ids.len() as u32;
+        let level = random_level();
+        self.ids.push(id);
+        self.vectors.extend(normalized(vector));
+        self.links.push(vec![Vec::new(); level + 1]);
+        self.deleted.push(false);
+        self.nodes.insert(id, node);
+
+        let Some(entry) = self.entry else {
+            self.entry = Some(node);
+            return Ok(());
+        };
+
+        let query = self.vector(node).to_vec();
+        let top = self.level(entry);
+        let mut nearest = entry;
+        for layer in (level + 1..=top).rev() {
+            nearest = self.greedy_closest(&query, nearest, layer);
+        }
+
+        for layer in (0..=level.min(top)).rev() {
+            let candidates = self.search_layer(&query, &[nearest], EF_CONSTRUCTION, layer);
+            let max_links = if layer == 0 { M0 } else { M };
+            let neighbours = self.select_neighbours(&candidates, M);
+            for &neighbour in &neighbours {
+                let links = &mut self.links[neighbour as usize][layer];
+                links.push(node);
+                if links.len() > max_links {
+                    self.prune(neighbour, layer, max_links);
+                }
+            }
+            self.links[node as usize][layer] = neighbours;
+            if let Some(closest) = candidates.first() {
+                nearest = closest.1;
+            }
+        }
+
+        if level > top {
+            self.entry = Some(node);
+        }
+        Ok(())
+    }
+
+    /// Stop returning a vector from searches
+    ///
+    /// The node stays in the graph as a waypoint until the next rebuild.
+    pub fn remove(&mut self, id: i64) -> bool {
+        let Some(node) = self.nodes.remove(&id) else {
+            return false;
+        };
+        self.deleted[node as usize] = true;
+        self.deleted_count += 1;
+        true
+    }
+
+    /// The `k` nearest vectors to `query` as `(id, cosine similarity)`, best first
+    pub fn search(&self, query: &[f32], k: usize, ef: usize) -> Vec<(i64, f32)> {
+        let Some(entry) = self.entry else {
+            return Vec::new();
+        };
+        if query.len() != self.dim || k == 0 {
+            return Vec::new();
+        }
+
+        let query = normalized(query);
+        let mut nearest = entry;
+        for layer in (1..=self.level(entry)).rev() {
+            nearest = self.greedy_closest(&query, nearest, layer);
+        }
+
+        // Tombstones still occupy candidate slots, so widen the search
+        let ef = ef.max(k) + self.deleted_count.min(ef.max(k));
+        self.search_layer(&query, &[nearest], ef, 0)
+            .into_iter()
+            .filter(|scored| !self.deleted[scored.1 as usize])
+            .take(k)
+            .map(|Scored(distance, node)| (self.ids[node as usize], 1.0 - distance))
+            .collect()
+    }
+
+    /// A fresh graph holding only the searchable vectors
+    pub fn rebuilt(&self) -> Result<Self> {
+        let mut index = Self::new(self.dim);
+        for (node, &id) in self.ids.iter().enumerate() {
+            if !self.deleted[node] {
+                index.insert(id, self.vector(node as u32))?;
+            }
+        }
+        Ok(index)
+    }
+
+    /// Write the graph to `path` (via a temporary file, so readers never
+    /// see a partial index)
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("hnsw.tmp");
+        let mut out = BufWriter::new(File::create(&tmp)?);
+
+        out.write_all(MAGIC)?;
+        write_u64(&mut out, self.dim as u64)?;
+        write_u64(&mut out, self.ids.len() as u64)?;
+        write_u64(&mut out, self.entry.map_or(u64::MAX, u64::from))?;
+        for node in 0..self.ids.len() {
+            out.write_all(&self.ids[node].to_le_bytes())?;
+            out.write_all(&[self.deleted[node] as u8, self.links[node].len() as u8])?;
+            for value in self.vector(node as u32) {
+                out.write_all(&value.to_le_bytes())?;
+            }
+            for links in &self.links[node] {
+                write_u64(&mut out, links.len() as u64)?;
+                for link in links {
+                    out.write_all(&link.to_le_bytes())?;
+                }
+            }
+        }
+        out.into_inner()?.sync_all()?;
+        std::fs::rename(&tmp, path)?;
+        Ok(())
+    }
+
+    /// Read a graph written by [`save`](Self::save)
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut input = BufReader::new(
+            File::open(path).with_context(|| format!("Failed to open {}", path.display()))?,
+        );
+
+        let mut magic = [0u8; 8];
+        input.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            bail!("{} is not a Krusty ANN index", path.display());
+        }
+        let dim = read_u64(&mut input)? as usize;
+        let count = read_u64(&mut input)? as usize;
+        let entry = read_u64(&mut input)?;
+
+        let mut index = Self::new(dim);
+        index.entry = (entry != u64::MAX).then_some(entry as u32);
+        index.ids.reserve(count);
+        index.vectors.reserve(count * dim);
+
+        for node in 0..count {
+            let mut id = [0u8; 8];
+            input.read_exact(&mut id)?;
+            let id = i64::from_le_bytes(id);
+            let mut flags = [0u8; 2];
+            input.read_exact(&mut flags)?;
+            let deleted = flags[0] != 0;
+
+            let mut vector = vec![0u8; dim * 4];
+            input.read_exact(&mut vector)?;
+            index.vectors.extend(
+                vector
+                    .chunks_exact(4)
+                    .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])),
+            );
+
+            let mut layers = Vec::with_capacity(flags[1] as usize);
+            for _ in 0..flags[1] {
+                let len = read_u64(&mut input)? as usize;
+                let mut bytes = vec![0u8; len * 4];
+                input.read_exact(&mut bytes)?;
+                let links: Vec<u32> = bytes
+                    .chunks_exact(4)
+                    .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect();
+                if links.iter().any(|&l| l as usize >= count) {
+                    bail!("Corrupt ANN index {}", path.display());
+                }
+                layers.push(links);
+            }
+
+            index.ids.push(id);
+            index.links.push(layers);
+            index.deleted.push(deleted);
+            if deleted {
+                index.deleted_count += 1;
+            } else {
+                index.nodes.insert(id, node as u32);
+            }
+        }
+
+        if index.entry.is_some_and(|e| e as usize >= count) {
+            bail!("Corrupt ANN index {}", path.display());
+        }
+        Ok(index)
+    }
+
+    fn vector(&self, node: u32) -> &[f32] {
+        let start = node as usize * self.dim;
+        &self.vectors[start..start + self.dim]
+    }
+
+    fn level(&self, node: u32) -> usize {
+        self.links[node as usize].len() - 1
+    }
+
+    fn distance(&self, query: &[f32], node: u32) -> f32 {
+        let dot: f32 = query
+            .iter()
+            .zip(self.vector(node))
+            .map(|(a, b)| a * b)
+            .sum();
+        1.0 - dot
+    }
+
+    /// Walk a layer towards `query` until no neighbour is closer
+    fn greedy_closest(&self, query: &[f32], start: u32, layer: usize) -> u32 {
+        let mut current = start;
+        let mut best = self.distance(query, current);
+        loop {
+            let mut improved = false;
+            for &neighbour in &self.links[current as usize][layer] {
+                let distance = self.distance(query, neighbour);
+                if distance < best {
+                    best = distance;
+                    current = neighbour;
+                    improved = true;
+                }
+            }
+            if !improved {
+                return current;
+            }
+        }
+    }
+
+    /// Best-first search of one layer, returning up to `ef` nodes nearest first
+    fn search_layer(&self, query: &[f32], entries: &[u32], ef: usize, layer: usize) -> Vec<Scored> {
+        let mut visited: HashSet<u32> = entries.iter().copied().collect();
+        let mut candidates: BinaryHeap<Reverse<Scored>> = BinaryHeap::new();
+        let mut found: BinaryHeap<Scored> = BinaryHeap::new();
+
+        for &entry in entries {
+            let scored = Scored(self.distance(query, entry), entry);
+            candidates.push(Reverse(scored));
+            found.push(scored);
+        }
+
+        while let Some(Reverse(candidate)) = candidates.pop() {
+            if found.len() >= ef && found.peek().is_some_and(|worst| candidate.0 > worst.0) {
+                break;
+            }
+            let Some(links) = self.links[candidate.1 as usize].get(layer) else {
+                continue;
+            };
+            for &neighbour in links {
+                if !visited.insert(neighbour) {
+                    continue;
+                }
+                let distance = self.distance(query, neighbour);
+                if found.len() < ef || found.peek().is_some_and(|worst| distance < worst.0) {
+                    let scored = Scored(distance, neighbour);
+                    candidates.push(Reverse(scored));
+                    found.push(scored);
+                    if found.len() > ef {
+                        found.pop();
+                    }
+                }
+            }
+        }
+
+        found.into_sorted_vec()
+    }
+
+    /// Pick up to `max` diverse neighbours from candidates sorted nearest first
+    ///
+    /// A candidate is skipped when it is closer to an already picked
+    /// neighbour than to the new node; skipped candidates fill any free slots.
+    fn select_neighbours(&self, candidates: &[Scored], max: usize) -> Vec<u32> {
+        let mut picked: Vec<u32> = Vec::with_capacity(max);
+        let mut skipped = Vec::new();
+        for &Scored(distance, node) in candidates {
+            if picked.len() >= max {
+                break;
+            }
+            let vector = self.vector(node);
+            if picked.iter().all(|&p| self.distance(vector, p) > distance) {
+                picked.push(node);
+            } else {
+                skipped.push(node);
+            }
+        }
+        for node in skipped {
+            if picked.len() >= max {
+                break;
+            }
+            picked.push(node);
+        }
+        picked
+    }
+
+    /// Trim a node's links on a layer back to `max`
+    fn prune(&mut self, node: u32, layer: usize, max: usize) {
+        let vector = self.vector(node).to_vec();
+        let mut candidates: Vec<Scored> = self.links[node as usize][layer]
+            .iter()
+            .map(|&n| Scored(self.distance(&vector, n), n))
+            .collect();
+        candidates.sort();
+        self.links[node as usize][layer] = self.select_neighbours(&candidates, max);
+    }
+}
+
+/// Layer for a new node: geometric with ratio `1 / M`
+fn random_level() -> usize {
+    let uniform: f64 = rand::random::<f64>().max(f64::MIN_POSITIVE);
+    let level = (-uniform.ln() / (M as f64).ln()).floor() as usize;
+    level.min(MAX_LEVEL)
+}
+
+fn normalized(vector: &[f32]) -> Vec<f32> {
+    let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm == 0.0 {
+        return vector.to_vec();
+    }
+    vector.iter().map(|v| v / norm).collect()
+}
+
+fn write_u64(out: &mut impl Write, value: u64) -> std::io::Result<()> {
+    out.write_all(&value.to_le_bytes())
+}
+
+fn read_u64(input: &mut impl Read) -> std::io::Result<u64> {
+    let mut bytes = [0u8; 8];
+    input.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Where the graph of a codebase lives, or `None` for in-memory databases
+pub fn index_path(conn: &Connection, codebase_id: &str) -> Option<PathBuf> {
+    let db_path = conn.path().filter(|p| !p.is_empty())?;
+    let dir = Path::new(db_path).parent()?;
+    Some(dir.join("ann").join(format!("{}.hnsw", codebase_id)))
+}
+
+/// Journal of changes made since the graph file was written
+fn log_path(path: &Path) -> PathBuf {
+    path.with_extension("hnsw.log")
+}
+
+/// Journal record: `[op][id: i64][vector: dim x f32 for inserts]`
+const LOG_INSERT: u8 = 1;
+const LOG_REMOVE: u8 = 2;
+
+/// A journalled change
+enum LogRecord {
+    Insert(i64, Vec<f32>),
+    Remove(i64),
+}
+
+/// Read the next journal record
+///
+/// Returns `None` at the end of the journal or at a record cut short by an
+/// interrupted append.
+fn read_record(input: &mut impl Read, dim: usize) -> Option<LogRecord> {
+    let mut header = [0u8; 9];
+    input.read_exact(&mut header).ok()?;
+    let id = i64::from_le_bytes(header[1..].try_into().ok()?);
+    match header[0] {
+        LOG_INSERT => {
+            let mut bytes = vec![0u8; dim * 4];
+            input.read_exact(&mut bytes).ok()?;
+            let vector = bytes
+                .chunks_exact(4)
+                .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                .collect();
+            Some(LogRecord::Insert(id, vector))
+        }
+        LOG_REMOVE => Some(LogRecord::Remove(id)),
+        _ => None,
+    }
+}
+
+fn record_len(record: &LogRecord) -> u64 {
+    match record {
+        LogRecord::Insert(_, vector) => 9 + vector.len() as u64 * 4,
+        LogRecord::Remove(_) => 9,
+    }
+}
+
+impl HnswIndex {
+    /// Apply journal records, returning the bytes and records consumed
+    fn replay(&mut self, input: &mut impl Read) -> Result<(u64, usize)> {
+        let mut consumed = 0;
+        let mut records = 0;
+        while let Some(record) = read_record(input, self.dim) {
+            consumed += record_len(&record);
+            records += 1;
+            match record {
+                LogRecord::Insert(id, vector) => self.insert(id, &vector)?,
+                LogRecord::Remove(id) => {
+                    self.remove(id);
+                }
+            }
+        }
+        Ok((consumed, records))
+    }
+}
+
+struct CachedIndex {
+    /// Modification time of the graph file
+    modified: SystemTime,
+    /// Bytes of the journal applied to `index`
+    log_len: u64,
+    /// Records in the journal
+    log_records: usize,
+    index: Arc<HnswIndex>,
+}
+
+impl CachedIndex {
+    /// Read a graph file and apply its journal
+    fn load(path: &Path, modified: SystemTime) -> Result<Self> {
+        let mut index = HnswIndex::load(path)?;
+        let (log_len, log_records) = match File::open(log_path(path)) {
+            Ok(file) => index.replay(&mut BufReader::new(file))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (0, 0),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            modified,
+            log_len,
+            log_records,
+            index: Arc::new(index),
+        })
+    }
+
+    /// Apply journal records appended since the last look
+    fn catch_up(&mut self, path: &Path) -> Result<()> {
+        let mut file = File::open(log_path(path))?;
+        file.seek(SeekFrom::Start(self.log_len))?;
+        let (consumed, records) =
+            Arc::make_mut(&mut self.index).replay(&mut BufReader::new(file))?;
+        self.log_len += consumed;
+        self.log_records += records;
+        Ok(())
+    }
+}
+
+/// Loaded graphs, refreshed when their file or journal changes
+static LOADED: LazyLock<Mutex<HashMap<PathBuf, CachedIndex>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Bring the loaded copy of a graph up to date with the files on disk
+fn refresh<'a>(
+    loaded: &'a mut HashMap<PathBuf, CachedIndex>,
+    path: &Path,
+) -> Option<&'a mut CachedIndex> {
+    let Ok(modified) = std::fs::metadata(path).and_then(|m| m.modified()) else {
+        loaded.remove(path);
+        return None;
+    };
+    let log_size = std::fs::metadata(log_path(path)).map_or(0, |m| m.len());
+
+    let up_to_date = match loaded.get_mut(path) {
+        Some(cached) if cached.modified == modified && cached.log_len == log_size => true,
+        Some(cached) if cached.modified == modified && cached.log_len < log_size => {
+            cached.catch_up(path).is_ok()
+        }
+        _ => false,
+    };
+    if !up_to_date {
+        match CachedIndex::load(path, modified) {
+            Ok(cached) => {
+                loaded.insert(path.to_path_buf(), cached);
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to load ANN index, using brute-force search");
+                loaded.remove(path);
+                return None;
+            }
+        }
+    }
+    loaded.get_mut(path)
+}
+
+/// The graph for a codebase, if it has one
+pub fn open(conn: &Connection, codebase_id: &str) -> Option<Arc<HnswIndex>> {
+    let path = index_path(conn, codebase_id)?;
+    let mut loaded = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+    refresh(&mut loaded, &path).map(|cached| cached.index.clone())
+}
+
+/// Bring the graph of a codebase in line with its embedded symbols
+///
+/// Creates the graph once the codebase reaches [`ANN_MIN_SYMBOLS`] and
+/// deletes it if the codebase shrinks below that.
+pub fn sync(conn: &Connection, codebase_id: &str) -> Result<()> {
+    sync_changed(conn, codebase_id, &[])
+}
+
+/// Like [`sync`], also replacing the vectors of symbols re-embedded in place
+///
+/// Small updates are appended to the graph's journal; the graph file is
+/// only rewritten once the journal reaches [`MAX_LOG_RATIO`] of the graph
+/// or too many tombstones pile up.
+pub fn sync_changed(conn: &Connection, codebase_id: &str, changed: &[i64]) -> Result<()> {
+    let Some(path) = index_path(conn, codebase_id) else {
+        return Ok(());
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT id FROM codebase_index WHERE codebase_id = ?1 AND embedding IS NOT NULL",
+    )?;
+    let live: HashSet<i64> = stmt
+        .query_map([codebase_id], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    if live.len() < ANN_MIN_SYMBOLS {
+        if path.exists() {
+            remove_if_exists(&log_path(&path))?;
+            std::fs::remove_file(&path)?;
+            LOADED
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&path);
+            info!(codebase_id, "Removed ANN index below size threshold");
+        }
+        return Ok(());
+    }
+
+    // Take the loaded graph out of the cache so it can be updated in place
+    let current = {
+        let mut loaded = LOADED.lock().unwrap_or_else(|e| e.into_inner());
+        refresh(&mut loaded, &path);
+        loaded
+            .remove(&path)
+            .filter(|cached| cached.index.dim() == EMBEDDING_DIM)
+    };
+    let changed: HashSet<i64> = changed.iter().copied().collect();
+
+    let Some(mut cached) = current else {
+        // No usable graph yet: build one from scratch
+        let mut index = HnswIndex::new(EMBEDDING_DIM);
+        let live: Vec<i64> = live.into_iter().collect();
+        for (id, embedding) in load_embeddings(conn, &live)? {
+            index.insert(id, &embedding)?;
+        }
+        return write_graph(&path, index, codebase_id, live.len(), 0);
+    };
+
+    let removed: Vec<i64> = cached
+        .index
+        .ids()
+        .filter(|id| !live.contains(id) || changed.contains(id))
+        .collect();
+    let missing: Vec<i64> = live
+        .iter()
+        .copied()
+        .filter(|id| !cached.index.contains(*id) || changed.contains(id))
+        .collect();
+    if removed.is_empty() && missing.is_empty() {
+        LOADED
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(path, cached);
+        return Ok(());
+    }
+    let added = load_embeddings(conn, &missing)?;
+
+    let index = Arc::make_mut(&mut cached.index);
+    for id in &removed {
+        index.remove(*id);
+    }
+    let journal_full = (cached.log_records + removed.len() + added.len()) as f32
+        > index.len().max(1) as f32 * MAX_LOG_RATIO;
+    if index.needs_rebuild() || journal_full {
+        let mut index = if index.needs_rebuild() {
+            index.rebuilt()?
+        } else {
+            Arc::unwrap_or_clone(cached.index)
+        };
+        for (id, embedding) in &added {
+            index.insert(*id, embedding)?;
+        }
+        return write_graph(&path, index, codebase_id, added.len(), removed.len());
+    }
+
+    let records: Vec<LogRecord> = removed
+        .iter()
+        .map(|id| LogRecord::Remove(*id))
+        .chain(
+            added
+                .into_iter()
+                .map(|(id, embedding)| LogRecord::Insert(id, embedding)),
+        )
+        .collect();
+    cached.log_len = append_log(&path, cached.log_len, &records)?;
+    cached.log_records += records.len();
+    for record in &records {
+        if let LogRecord::Insert(id, vector) = record {
+            index.insert(*id, vector)?;
+        }
+    }
+
+    info!(
+        codebase_id,
+        vectors = index.len(),
+        added = records.len() - removed.len(),
+        removed = removed.len(),
+        "Journalled ANN index update"
+    );
+    LOADED
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(path, cached);
+    Ok(())
+}
+
+/// Append records to a graph's journal, returning its new length
+///
+/// The journal is first cut back to `valid_len`, dropping any record left
+/// incomplete by an interrupted append.
+fn append_log(path: &Path, valid_len: u64, records: &[LogRecord]) -> Result<u64> {
+    let file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(false)
+        .open(log_path(path))?;
+    file.set_len(valid_len)?;
+    let mut out = BufWriter::new(file);
+    out.seek(SeekFrom::End(0))?;
+    let mut len = valid_len;
+    for record in records {
+        match record {
+            LogRecord::Insert(id, vector) => {
+                out.write_all(&[LOG_INSERT])?;
+                out.write_all(&id.to_le_bytes())?;
+                for value in vector {
+                    out.write_all(&value.to_le_bytes())?;
+                }
+            }
+            LogRecord::Remove(id) => {
+                out.write_all(&[LOG_REMOVE])?;
+                out.write_all(&id.to_le_bytes())?;
+            }
+        }
+        len += record_len(record);
+    }
+    out.into_inner()?.sync_data()?;
+    Ok(len)
+}
+
+/// Embeddings of the given symbols (chunked to stay under SQLite's
+/// variable limit)
+fn load_embeddings(conn: &Connection, ids: &[i64]) -> Result<Vec<(i64, Vec<f32>)>> {
+    let mut embeddings = Vec::with_capacity(ids.len());
+    for chunk in ids.chunks(500) {
+        let placeholders = vec!["?"; chunk.len()].join(",");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, embedding FROM codebase_index WHERE id IN ({})",
+            placeholders
+        ))?;
+        let rows = stmt.query_map(rusqlite::params_from_iter(chunk), |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, Vec<u8>>(1)?))
+        })?;
+        for row in rows {
+            let (id, blob) = row?;
+            if let Some(embedding) = EmbeddingEngine::blob_to_embedding(&blob) {
+                embeddings.push((id, embedding));
+            }
+        }
+    }
+    Ok(embeddings)
+}
+
+/// Write a fresh graph file, clear its journal and keep the graph loaded
+fn write_graph(
+    path: &Path,
+    index: HnswIndex,
+    codebase_id: &str,
+    added: usize,
+    removed: usize,
+) -> Result<()> {
+    // Drop the journal first: a reader that sees the old graph without it is
+    // merely stale, while old records replayed onto the new graph could
+    // resurrect removed symbols
+    remove_if_exists(&log_path(path))?;
+    index.save(path)?;
+    info!(
+        codebase_id,
+        vectors = index.len(),
+        added,
+        removed,
+        "Wrote ANN index"
+    );
+
+    let modified = std::fs::metadata(path)?.modified()?;
+    LOADED.lock().unwrap_or_else(|e| e.into_inner()).insert(
+        path.to_path_buf(),
+        CachedIndex {
+            modified,
+            log_len: 0,
+            log_records: 0,
+            index: Arc::new(index),
+        },
+    );
+    Ok(())
+}
+
+fn remove_if_exists(path: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vector(seed: u64, dim: usize) -> Vec<f32> {
+        let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (0..dim)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state % 1000) as f32 / 500.0 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_search_finds_exact_vector() {
+        let mut index = HnswIndex::new(16);
+        for id in 0..500 {
+            index.insert(id, &vector(id as u64, 16)).unwrap();
+        }
+        let hits = index.search(&vector(42, 16), 5, DEFAULT_EF_SEARCH);
+        assert_eq!(hits[0].0, 42);
+        assert!((hits[0].1 - 1.0).abs() < 1e-4);
+        assert!(index.insert(1, &[0.0; 3]).is_err());
+    }
+
+    #[test]
+    fn test_remove_and_rebuild() {
+        let mut index = HnswIndex::new(8);
+        for id in 0..100 {
+            index.insert(id, &vector(id as u64, 8)).unwrap();
+        }
+        for id in 0..40 {
+            assert!(index.remove(id));
+        }
+        assert_eq!(index.len(), 60);
+        assert!(index.needs_rebuild());
+        assert!(index
+            .search(&vector(3, 8), 10, DEFAULT_EF_SEARCH)
+            .iter()
+            .all(|(id, _)| *id >= 40));
+
+        let rebuilt = index.rebuilt().unwrap();
+        assert_eq!(rebuilt.len(), 60);
+        assert!(!rebuilt.needs_rebuild());
+        assert_eq!(
+            rebuilt.search(&vector(77, 8), 1, DEFAULT_EF_SEARCH)[0].0,
+            77
+        );
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("ann").join("codebase.hnsw");
+        let mut index = HnswIndex::new(8);
+        for id in 0..200 {
+            index.insert(id * 3, &vector(id as u64, 8)).unwrap();
+        }
+        index.remove(0);
+        index.save(&path).unwrap();
+
+        let loaded = HnswIndex::load(&path).unwrap();
+        assert_eq!(loaded.len(), 199);
+        assert!(!loaded.contains(0));
+        let query = vector(10, 8);
+        assert_eq!(
+            loaded.search(&query, 5, DEFAULT_EF_SEARCH),
+            index.search(&query, 5, DEFAULT_EF_SEARCH)
+        );
+
+        std::fs::write(&path, b"not an index").unwrap();
+        assert!(HnswIndex::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_journal_replay() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("codebase.hnsw");
+        let mut index = HnswIndex::new(8);
+        for id in 0..100 {
+            index.insert(id, &vector(id as u64, 8)).unwrap();
+        }
+        index.save(&path).unwrap();
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+
+        let records = [LogRecord::Remove(5), LogRecord::Insert(500, vector(500, 8))];
+        let len = append_log(&path, 0, &records).unwrap();
+        // An append cut short by a crash
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(log_path(&path))
+            .unwrap();
+        file.write_all(&[LOG_INSERT, 1, 2]).unwrap();
+
+        let mut cached = CachedIndex::load(&path, modified).unwrap();
+        assert_eq!((cached.log_len, cached.log_records), (len, 2));
+        assert!(!cached.index.contains(5));
+        assert_eq!(
+            cached.index.search(&vector(500, 8), 1, DEFAULT_EF_SEARCH)[0].0,
+            500
+        );
+
+        // Later appends replace the torn record and are picked up incrementally
+        append_log(&path, cached.log_len, &[LogRecord::Remove(500)]).unwrap();
+        cached.catch_up(&path).unwrap();
+        assert_eq!(cached.log_records, 3);
+        assert!(!cached.index.contains(500));
+        assert_eq!(cached.index.len(), 99);
+        assert_eq!(
+            cached.log_len,
+            std::fs::metadata(log_path(&path)).unwrap().len()
+        );
+    }
+}
@@ -4,6 +4,7 @@ use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use rusqlite::{params, Connection};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// A codebase entity representing an indexed project
@@ -198,6 +199,37 @@ impl<'a> CodebaseStore<'a> {
             "DELETE FROM codebase_index WHERE codebase_id = ?1",
             [codebase_id],
         )?;
+        self.conn.execute(
+            "DELETE FROM codebase_files WHERE codebase_id = ?1",
+            [codebase_id],
+        )?;
+        Ok(deleted)
+    }
+
+    /// Get the content hash of every indexed file, keyed by file path
+    pub fn file_hashes(&self, codebase_id: &str) -> Result<HashMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT file_path, content_hash FROM codebase_files WHERE codebase_id = ?1")?;
+        let rows = stmt.query_map([codebase_id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<Result<HashMap<_, _>, _>>()?)
+    }
+
+    /// Delete index entries and hashes for specific files
+    pub fn remove_files(&self, codebase_id: &str, file_paths: &[String]) -> Result<usize> {
+        let tx = self.conn.unchecked_transaction()?;
+        let mut deleted = 0;
+        for file_path in file_paths {
+            deleted += tx.execute(
+                "DELETE FROM codebase_index WHERE codebase_id = ?1 AND file_path = ?2",
+                params![codebase_id, file_path],
+            )?;
+            tx.execute(
+                "DELETE FROM codebase_files WHERE codebase_id = ?1 AND file_path = ?2",
+                params![codebase_id, file_path],
+            )?;
+        }
+        tx.commit()?;
         Ok(deleted)
     }
 
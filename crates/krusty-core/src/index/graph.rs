@@ -0,0 +1,430 @@
+//! Code graph queries over the symbol index
+//!
+//! Edges come from what the parsers record per symbol: `calls` (names of
+//! called functions) and `implements` (the trait of an `impl Trait for Type`
+//! block). Calls are matched by name, so same-named functions in different
+//! modules aren't told apart.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Component, Path};
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+
+use super::codebase::CodebaseStore;
+use super::parser::trait_name;
+use super::retrieval::{read_symbol, SearchResult, SemanticRetrieval};
+
+/// Most callers returned by a transitive caller query
+const MAX_CALLERS: usize = 200;
+
+/// File stems that stand for their directory's module
+const MODULE_ROOT_FILES: &[&str] = &["mod", "lib", "main", "index", "__init__"];
+
+const SYMBOL_COLUMNS: &str = "id, symbol_type, symbol_name, symbol_path, file_path,
+                              line_start, line_end, signature, summary";
+
+/// A name called by a symbol, with the indexed definitions of that name
+#[derive(Debug, Clone)]
+pub struct Callee {
+    pub name: String,
+    /// Empty for functions outside the index (std, dependencies)
+    pub definitions: Vec<SearchResult>,
+}
+
+/// A symbol reached by walking callers upwards
+#[derive(Debug, Clone)]
+pub struct Caller {
+    /// 1 for direct callers, 2 for their callers, ...
+    pub depth: usize,
+    pub symbol: SearchResult,
+    /// Name of the function it calls on the way to the target
+    pub calls: String,
+}
+
+/// An impl block with the functions defined inside it
+#[derive(Debug, Clone)]
+pub struct ImplBlock {
+    pub block: SearchResult,
+    pub trait_name: Option<String>,
+    pub methods: Vec<SearchResult>,
+}
+
+/// A module in the tree built from indexed file paths
+#[derive(Debug, Clone, Default)]
+pub struct ModuleNode {
+    pub name: String,
+    /// Files defining this module, relative to the codebase root
+    pub files: Vec<String>,
+    /// Symbols defined directly in this module's files
+    pub symbols: usize,
+    pub children: Vec<ModuleNode>,
+}
+
+/// Graph queries for one database connection
+pub struct CodeGraph<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> CodeGraph<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Names called by every symbol named `symbol`, resolved to definitions
+    pub fn callees(&self, codebase_id: &str, symbol: &str) -> Result<Vec<Callee>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT calls FROM codebase_index
+             WHERE codebase_id = ?1 AND symbol_name = ?2 AND symbol_type != 'impl'",
+        )?;
+        let call_lists = stmt
+            .query_map(params![codebase_id, symbol], |row| {
+                row.get::<_, Option<String>>(0)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut names: Vec<String> = Vec::new();
+        for calls in call_lists.into_iter().flatten() {
+            for name in serde_json::from_str::<Vec<String>>(&calls).unwrap_or_default() {
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+
+        names
+            .into_iter()
+            .map(|name| {
+                let definitions = self.functions_named(codebase_id, &name)?;
+                Ok(Callee { name, definitions })
+            })
+            .collect()
+    }
+
+    /// Symbols that call `symbol`, directly or through up to `max_depth` hops
+    ///
+    /// Closest callers come first; each symbol is listed once.
+    pub fn callers(
+        &self,
+        codebase_id: &str,
+        symbol: &str,
+        max_depth: usize,
+    ) -> Result<Vec<Caller>> {
+        let retrieval = SemanticRetrieval::new(self.conn);
+        let mut seen_names: HashSet<String> = HashSet::from([symbol.to_string()]);
+        let mut seen_ids: HashSet<i64> = HashSet::new();
+        let mut frontier = vec![symbol.to_string()];
+        let mut callers = Vec::new();
+
+        for depth in 1..=max_depth {
+            let mut next = Vec::new();
+            for name in &frontier {
+                for found in retrieval.find_callers(codebase_id, name)? {
+                    if callers.len() >= MAX_CALLERS {
+                        return Ok(callers);
+                    }
+                    if !seen_ids.insert(found.id) {
+                        continue;
+                    }
+                    if seen_names.insert(found.symbol_name.clone()) {
+                        next.push(found.symbol_name.clone());
+                    }
+                    callers.push(Caller {
+                        depth,
+                        symbol: found,
+                        calls: name.clone(),
+                    });
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+        }
+
+        Ok(callers)
+    }
+
+    /// Impl blocks implementing the trait (`Display` or `std::fmt::Display`)
+    pub fn implementors(&self, codebase_id: &str, trait_ref: &str) -> Result<Vec<ImplBlock>> {
+        self.impl_blocks(codebase_id, "implements = ?2", &trait_name(trait_ref))
+    }
+
+    /// Impl blocks for a type, inherent and trait impls alike
+    pub fn impls_for(&self, codebase_id: &str, type_name: &str) -> Result<Vec<ImplBlock>> {
+        self.impl_blocks(codebase_id, "symbol_name = ?2", type_name)
+    }
+
+    /// Modules of the codebase, one root per crate or package
+    ///
+    /// Modules follow file paths below each `src` directory; `mod.rs`,
+    /// `lib.rs`, `index.ts` and the like stand for their directory. `prefix`
+    /// limits the tree to files under that relative path.
+    pub fn module_tree(&self, codebase_id: &str, prefix: Option<&str>) -> Result<Vec<ModuleNode>> {
+        let codebase = CodebaseStore::new(self.conn)
+            .get_by_id(codebase_id)?
+            .context("Codebase not found")?;
+        let root = Path::new(&codebase.path);
+
+        let mut stmt = self.conn.prepare(
+            "SELECT f.file_path, COUNT(c.id) FROM codebase_files f
+             LEFT JOIN codebase_index c
+                ON c.codebase_id = f.codebase_id AND c.file_path = f.file_path
+             WHERE f.codebase_id = ?1
+             GROUP BY f.file_path
+             ORDER BY f.file_path",
+        )?;
+        let files = stmt
+            .query_map([codebase_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut crates: BTreeMap<String, ModuleNode> = BTreeMap::new();
+        for (file, symbols) in files {
+            let relative = Path::new(&file)
+                .strip_prefix(root)
+                .unwrap_or(Path::new(&file));
+            if prefix.is_some_and(|p| !relative.starts_with(p)) {
+                continue;
+            }
+            let (crate_dir, modules) = module_segments(relative);
+            let crate_node = crates
+                .entry(crate_dir.clone())
+                .or_insert_with(|| ModuleNode {
+                    name: crate_dir,
+                    ..Default::default()
+                });
+
+            let mut node = crate_node;
+            for module in modules {
+                let index = match node.children.iter().position(|c| c.name == module) {
+                    Some(index) => index,
+                    None => {
+                        node.children.push(ModuleNode {
+                            name: module,
+                            ..Default::default()
+                        });
+                        node.children.len() - 1
+                    }
+                };
+                node = &mut node.children[index];
+            }
+            node.files.push(relative.to_string_lossy().to_string());
+            node.symbols += symbols as usize;
+        }
+
+        let mut tree: Vec<ModuleNode> = crates.into_values().collect();
+        tree.iter_mut().for_each(sort_children);
+        Ok(tree)
+    }
+
+    /// Functions, methods and macros with the given name
+    fn functions_named(&self, codebase_id: &str, name: &str) -> Result<Vec<SearchResult>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {SYMBOL_COLUMNS} FROM codebase_index
+             WHERE codebase_id = ?1 AND symbol_name = ?2
+               AND symbol_type IN ('function', 'method', 'macro')
+             ORDER BY file_path, line_start"
+        ))?;
+        let results = stmt
+            .query_map(params![codebase_id, name], |row| read_symbol(row, 1.0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
+    }
+
+    /// Impl rows matching `condition` (on `?2`), with the functions inside them
+    fn impl_blocks(
+        &self,
+        codebase_id: &str,
+        condition: &str,
+        value: &str,
+    ) -> Result<Vec<ImplBlock>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {SYMBOL_COLUMNS}, implements FROM codebase_index
+             WHERE codebase_id = ?1 AND symbol_type = 'impl' AND {condition}
+             ORDER BY file_path, line_start"
+        ))?;
+        let blocks = stmt
+            .query_map(params![codebase_id, value], |row| {
+                Ok((read_symbol(row, 1.0)?, row.get::<_, Option<String>>(9)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut methods_stmt = self.conn.prepare(&format!(
+            "SELECT {SYMBOL_COLUMNS} FROM codebase_index
+             WHERE codebase_id = ?1 AND file_path = ?2
+               AND symbol_type IN ('function', 'method')
+               AND line_start > ?3 AND line_end <= ?4
+             ORDER BY line_start"
+        ))?;
+        blocks
+            .into_iter()
+            .map(|(block, trait_name)| {
+                let methods = methods_stmt
+                    .query_map(
+                        params![
+                            codebase_id,
+                            block.file_path,
+                            block.line_start as i64,
+                            block.line_end as i64
+                        ],
+                        |row| read_symbol(row, 1.0),
+                    )?
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ImplBlock {
+                    block,
+                    trait_name,
+                    methods,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Crate directory and module names for a file path relative to the root
+///
+/// `crates/core/src/tools/mod.rs` gives `("crates/core", ["tools"])`; files
+/// outside any `src` directory belong to the `.` crate.
+fn module_segments(relative: &Path) -> (String, Vec<String>) {
+    let parts: Vec<String> = relative
+        .with_extension("")
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+
+    let (crate_parts, mut modules) = match parts.iter().position(|p| p == "src") {
+        Some(src) => (parts[..src].to_vec(), parts[src + 1..].to_vec()),
+        None => (Vec::new(), parts),
+    };
+    if modules
+        .last()
+        .is_some_and(|m| MODULE_ROOT_FILES.contains(&m.as_str()))
+    {
+        modules.pop();
+    }
+
+    let crate_dir = if crate_parts.is_empty() {
+        ".".to_string()
+    } else {
+        crate_parts.join("/")
+    };
+    (crate_dir, modules)
+}
+
+fn sort_children(node: &mut ModuleNode) {
+    node.children.sort_by(|a, b| a.name.cmp(&b.name));
+    node.children.iter_mut().for_each(sort_children);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::Indexer;
+    use crate::storage::Database;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_module_segments() {
+        let segments = |p: &str| module_segments(Path::new(p));
+        assert_eq!(
+            segments("crates/core/src/tools/mod.rs"),
+            ("crates/core".to_string(), vec!["tools".to_string()])
+        );
+        assert_eq!(segments("src/lib.rs"), (".".to_string(), vec![]));
+        assert_eq!(
+            segments("scripts/build.py"),
+            (
+                ".".to_string(),
+                vec!["scripts".to_string(), "build".to_string()]
+            )
+        );
+    }
+
+    #[test]
+    fn test_graph_queries() {
+        let project = tempfile::Builder::new()
+            .prefix("krusty-graph")
+            .tempdir()
+            .unwrap();
+        let db_dir = TempDir::new().unwrap();
+        let db = Database::new(&db_dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+
+        let src = project.path().join("src");
+        std::fs::create_dir_all(src.join("shapes")).unwrap();
+        std::fs::write(
+            src.join("lib.rs"),
+            "fn main() { run(); }\nfn run() { draw(); }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            src.join("shapes/mod.rs"),
+            r#"
+pub trait Shape {
+    fn area(&self) -> f64;
+}
+
+pub struct Square(f64);
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        square(self.0)
+    }
+}
+
+impl Square {
+    pub fn new(side: f64) -> Self {
+        Self(side)
+    }
+}
+
+fn square(x: f64) -> f64 { x * x }
+
+pub fn draw() { square(1.0); }
+"#,
+        )
+        .unwrap();
+
+        let codebase = Indexer::new()
+            .unwrap()
+            .index_codebase_sync(conn, project.path(), None)
+            .unwrap();
+        let graph = CodeGraph::new(conn);
+
+        let callees = graph.callees(&codebase.id, "draw").unwrap();
+        assert_eq!(callees.len(), 1);
+        assert_eq!(callees[0].name, "square");
+        assert_eq!(callees[0].definitions.len(), 1);
+
+        let callers = graph.callers(&codebase.id, "square", 3).unwrap();
+        let found: Vec<(usize, &str)> = callers
+            .iter()
+            .map(|c| (c.depth, c.symbol.symbol_name.as_str()))
+            .collect();
+        assert!(found.contains(&(1, "area")));
+        assert!(found.contains(&(1, "draw")));
+        assert!(found.contains(&(2, "run")));
+        assert!(found.contains(&(3, "main")));
+
+        let implementors = graph.implementors(&codebase.id, "crate::Shape").unwrap();
+        assert_eq!(implementors.len(), 1);
+        assert_eq!(implementors[0].block.symbol_name, "Square");
+        assert_eq!(implementors[0].methods[0].symbol_name, "area");
+
+        let impls = graph.impls_for(&codebase.id, "Square").unwrap();
+        assert_eq!(impls.len(), 2);
+        assert_eq!(impls[1].trait_name, None);
+        assert_eq!(impls[1].methods[0].symbol_name, "new");
+
+        let tree = graph.module_tree(&codebase.id, None).unwrap();
+        assert_eq!(tree.len(), 1);
+        assert_eq!(tree[0].name, ".");
+        assert_eq!(tree[0].files, vec!["src/lib.rs"]);
+        assert_eq!(tree[0].children[0].name, "shapes");
+        assert!(tree[0].children[0].symbols >= 6);
+    }
+}
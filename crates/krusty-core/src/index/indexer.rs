@@ -3,17 +3,25 @@
 use anyhow::{Context, Result};
 use chrono::Utc;
 use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use tokio::sync::mpsc;
 use tracing::{info, warn};
 use walkdir::WalkDir;
 
+use super::ann;
 use super::codebase::{Codebase, CodebaseStore};
 use super::embeddings::EmbeddingEngine;
-use super::parser::{ParsedSymbol, RustParser, SymbolType};
+use super::parser::{parser_for, LanguageParser, ParsedSymbol, SourceLanguage, SymbolType};
+use super::retrieval::{index_symbol_text, SymbolText};
+use super::summaries;
 
 /// Current index version (bump when format changes)
-pub const INDEX_VERSION: i32 = 1;
+///
+/// Codebases indexed with an older version are rebuilt from scratch.
+pub const INDEX_VERSION: i32 = 5;
 
 /// Phase of the indexing process
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -46,16 +54,36 @@ pub struct IndexProgress {
     pub current_file: Option<String>,
 }
 
+/// A file that needs (re)indexing
+struct PendingFile {
+    path: PathBuf,
+    content_hash: String,
+}
+
+/// A parsed symbol with its summary cache entry
+struct IndexedSymbol {
+    symbol: ParsedSymbol,
+    /// Source hash of public functions, types and modules
+    summary_hash: Option<String>,
+    /// Summary cached for that hash
+    summary: Option<String>,
+}
+
 /// Orchestrates codebase indexing
+///
+/// Indexing is incremental: each file's content hash is stored in
+/// `codebase_files`, and only added or modified files are re-parsed and
+/// re-embedded. Rows for deleted files are removed.
 pub struct Indexer {
-    parser: RustParser,
+    /// Parsers by language, created on first use
+    parsers: HashMap<SourceLanguage, Box<dyn LanguageParser>>,
     embeddings: Option<EmbeddingEngine>,
 }
 
 impl Indexer {
     pub fn new() -> Result<Self> {
         Ok(Self {
-            parser: RustParser::new()?,
+            parsers: HashMap::new(),
             embeddings: None,
         })
     }
@@ -66,6 +94,11 @@ impl Indexer {
         Ok(self)
     }
 
+    /// Whether this indexer generates embeddings
+    pub fn has_embeddings(&self) -> bool {
+        self.embeddings.is_some()
+    }
+
     /// Index a codebase synchronously (no embeddings)
     ///
     /// Use this when embeddings are disabled to avoid async runtime issues.
@@ -96,37 +129,43 @@ impl Indexer {
             current_file: None,
         });
 
-        let rust_files = self.scan_rust_files(path)?;
-        let total_files = rust_files.len();
-        info!(files = total_files, "Found Rust files to index");
+        let source_files = scan_source_files(path)?;
+        info!(files = source_files.len(), "Found source files");
+
+        let pending = self.prepare_changes(&store, &codebase, source_files)?;
+        let total_files = pending.len();
 
         if total_files == 0 {
+            store.mark_indexed(&codebase.id, INDEX_VERSION)?;
+            sync_ann(conn, &codebase.id);
             send_progress(IndexProgress {
                 phase: IndexPhase::Complete,
                 current: 0,
                 total: 0,
                 current_file: None,
             });
-            return Ok(codebase);
+            return store
+                .get_by_id(&codebase.id)?
+                .context("Codebase not found after indexing");
         }
 
-        let mut all_symbols: Vec<(PathBuf, ParsedSymbol)> = Vec::new();
-        for (idx, file_path) in rust_files.iter().enumerate() {
+        let mut all_symbols: Vec<(&PendingFile, IndexedSymbol)> = Vec::new();
+        for (idx, file) in pending.iter().enumerate() {
             send_progress(IndexProgress {
                 phase: IndexPhase::Parsing,
                 current: idx + 1,
                 total: total_files,
-                current_file: Some(file_path.display().to_string()),
+                current_file: Some(file.path.display().to_string()),
             });
 
-            match self.parse_file(file_path) {
+            match self.parse_file(conn, path, &file.path) {
                 Ok(symbols) => {
                     for symbol in symbols {
-                        all_symbols.push((file_path.clone(), symbol));
+                        all_symbols.push((file, symbol));
                     }
                 }
                 Err(e) => {
-                    warn!(file = %file_path.display(), error = %e, "Failed to parse file");
+                    warn!(file = %file.path.display(), error = %e, "Failed to parse file");
                 }
             }
         }
@@ -141,22 +180,13 @@ impl Indexer {
             current_file: None,
         });
 
-        store.clear_index(&codebase.id)?;
         let now = Utc::now().to_rfc3339();
-
-        for (idx, (file_path, symbol)) in all_symbols.into_iter().enumerate() {
-            if idx % 100 == 0 {
-                send_progress(IndexProgress {
-                    phase: IndexPhase::Storing,
-                    current: idx,
-                    total: total_symbols,
-                    current_file: None,
-                });
-            }
-            self.insert_symbol(conn, &codebase.id, &file_path, &symbol, None, &now)?;
-        }
+        let files: Vec<&PendingFile> = pending.iter().collect();
+        let embeddings = vec![None; total_symbols];
+        self.insert_symbols_batch(conn, &codebase.id, &files, &all_symbols, &embeddings, &now)?;
 
         store.mark_indexed(&codebase.id, INDEX_VERSION)?;
+        sync_ann(conn, &codebase.id);
 
         send_progress(IndexProgress {
             phase: IndexPhase::Complete,
@@ -189,7 +219,7 @@ impl Indexer {
         let codebase = store.get_or_create(path)?;
         info!(codebase_id = %codebase.id, path = %codebase.path, "Starting index");
 
-        // Phase 1: Scan for Rust files
+        // Phase 1: Scan for source files and work out what changed
         send_progress(IndexProgress {
             phase: IndexPhase::Scanning,
             current: 0,
@@ -197,18 +227,24 @@ impl Indexer {
             current_file: None,
         });
 
-        let rust_files = self.scan_rust_files(path)?;
-        let total_files = rust_files.len();
-        info!(files = total_files, "Found Rust files to index");
+        let source_files = scan_source_files(path)?;
+        info!(files = source_files.len(), "Found source files");
+
+        let pending = self.prepare_changes(&store, &codebase, source_files)?;
+        let total_files = pending.len();
 
         if total_files == 0 {
+            store.mark_indexed(&codebase.id, INDEX_VERSION)?;
+            sync_ann(conn, &codebase.id);
             send_progress(IndexProgress {
                 phase: IndexPhase::Complete,
                 current: 0,
                 total: 0,
                 current_file: None,
             });
-            return Ok(codebase);
+            return store
+                .get_by_id(&codebase.id)?
+                .context("Codebase not found after indexing");
         }
 
         // Phase 2 & 3: Parse files, extract symbols, and generate embeddings in streaming batches
@@ -222,32 +258,31 @@ impl Indexer {
             current_file: None,
         });
 
-        // Clear existing index
-        store.clear_index(&codebase.id)?;
-
         let now = Utc::now().to_rfc3339();
-        let mut parsed_symbols: Vec<(PathBuf, ParsedSymbol)> = Vec::new();
+        let mut parsed_symbols: Vec<(&PendingFile, IndexedSymbol)> = Vec::new();
+        let mut parsed_files: Vec<&PendingFile> = Vec::new();
         let mut total_symbols = 0;
         let mut embedding_failed = false;
 
-        for (idx, file_path) in rust_files.iter().enumerate() {
+        for (idx, file) in pending.iter().enumerate() {
             send_progress(IndexProgress {
                 phase: IndexPhase::Parsing,
                 current: idx + 1,
                 total: total_files,
-                current_file: Some(file_path.display().to_string()),
+                current_file: Some(file.path.display().to_string()),
             });
 
-            match self.parse_file(file_path) {
+            match self.parse_file(conn, path, &file.path) {
                 Ok(symbols) => {
                     for symbol in symbols {
-                        parsed_symbols.push((file_path.clone(), symbol));
+                        parsed_symbols.push((file, symbol));
                     }
                 }
                 Err(e) => {
-                    warn!(file = %file_path.display(), error = %e, "Failed to parse file");
+                    warn!(file = %file.path.display(), error = %e, "Failed to parse file");
                 }
             }
+            parsed_files.push(file);
 
             // When we have enough symbols, process embeddings and insert in batch
             if parsed_symbols.len() >= EMBED_CHUNK_SIZE || idx == total_files - 1 {
@@ -262,7 +297,7 @@ impl Indexer {
 
                     let texts: Vec<String> = parsed_symbols
                         .iter()
-                        .map(|(_, sym)| self.symbol_to_embedding_text(sym))
+                        .map(|(_, entry)| embedding_text(&entry.symbol, entry.summary.as_deref()))
                         .collect();
 
                     if !embedding_failed {
@@ -282,10 +317,18 @@ impl Indexer {
                 };
 
                 // Insert batch with transaction
-                self.insert_symbols_batch(conn, &codebase.id, &parsed_symbols, &embeddings, &now)?;
+                self.insert_symbols_batch(
+                    conn,
+                    &codebase.id,
+                    &parsed_files,
+                    &parsed_symbols,
+                    &embeddings,
+                    &now,
+                )?;
 
                 total_symbols += parsed_symbols.len();
                 parsed_symbols.clear();
+                parsed_files.clear();
 
                 send_progress(IndexProgress {
                     phase: IndexPhase::Storing,
@@ -298,6 +341,7 @@ impl Indexer {
 
         // Mark as indexed
         store.mark_indexed(&codebase.id, INDEX_VERSION)?;
+        sync_ann(conn, &codebase.id);
 
         send_progress(IndexProgress {
             phase: IndexPhase::Complete,
@@ -318,27 +362,90 @@ impl Indexer {
             .context("Codebase not found after indexing")
     }
 
-    /// Insert a batch of symbols in a single transaction
+    /// Work out which files need (re)indexing and drop rows for stale files
+    ///
+    /// Files whose content hash matches the stored one are skipped. Modified
+    /// and deleted files have their rows removed up front. An index built by
+    /// an older `INDEX_VERSION` is cleared and rebuilt.
+    fn prepare_changes(
+        &self,
+        store: &CodebaseStore,
+        codebase: &Codebase,
+        files: Vec<PathBuf>,
+    ) -> Result<Vec<PendingFile>> {
+        let rebuild = codebase.index_version != INDEX_VERSION;
+        let mut known = if rebuild {
+            store.clear_index(&codebase.id)?;
+            HashMap::new()
+        } else {
+            store.file_hashes(&codebase.id)?
+        };
+
+        let mut pending = Vec::new();
+        let mut stale = Vec::new();
+        let (mut added, mut modified, mut unchanged) = (0, 0, 0);
+
+        for path in files {
+            let content_hash = match hash_file(&path) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    warn!(file = %path.display(), error = %e, "Failed to hash file");
+                    continue;
+                }
+            };
+
+            match known.remove(path.to_string_lossy().as_ref()) {
+                Some(old) if old == content_hash => unchanged += 1,
+                Some(_) => {
+                    modified += 1;
+                    stale.push(path.to_string_lossy().to_string());
+                    pending.push(PendingFile { path, content_hash });
+                }
+                None => {
+                    added += 1;
+                    pending.push(PendingFile { path, content_hash });
+                }
+            }
+        }
+
+        // Anything left in `known` is no longer on disk
+        let deleted = known.len();
+        stale.extend(known.into_keys());
+        if !stale.is_empty() {
+            store.remove_files(&codebase.id, &stale)?;
+        }
+
+        info!(
+            rebuild,
+            added, modified, deleted, unchanged, "Computed index changes"
+        );
+        Ok(pending)
+    }
+
+    /// Insert a batch of symbols and record file hashes in a single transaction
     fn insert_symbols_batch(
         &self,
         conn: &Connection,
         codebase_id: &str,
-        symbols: &[(PathBuf, ParsedSymbol)],
+        files: &[&PendingFile],
+        symbols: &[(&PendingFile, IndexedSymbol)],
         embeddings: &[Option<Vec<f32>>],
         indexed_at: &str,
     ) -> Result<()> {
         let tx = conn.unchecked_transaction()?;
 
-        for ((file_path, symbol), embedding) in symbols.iter().zip(embeddings.iter()) {
-            let file_path_str = file_path.to_string_lossy().to_string();
+        for ((file, entry), embedding) in symbols.iter().zip(embeddings.iter()) {
+            let symbol = &entry.symbol;
+            let file_path_str = file.path.to_string_lossy().to_string();
             let calls_json = serde_json::to_string(&symbol.calls)?;
             let embedding_blob = embedding.as_deref().map(EmbeddingEngine::embedding_to_blob);
 
             tx.execute(
                 "INSERT INTO codebase_index
                  (codebase_id, symbol_type, symbol_name, symbol_path, file_path,
-                  line_start, line_end, signature, embedding, calls, indexed_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                  line_start, line_end, signature, doc, implements, summary, summary_hash,
+                  embedding, calls, indexed_at, content_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
                 params![
                     codebase_id,
                     symbol.symbol_type.as_str(),
@@ -348,96 +455,88 @@ impl Indexer {
                     symbol.line_start as i64,
                     symbol.line_end as i64,
                     symbol.signature,
+                    symbol.doc,
+                    symbol.implements,
+                    entry.summary,
+                    entry.summary_hash,
                     embedding_blob,
                     calls_json,
                     indexed_at,
+                    file.content_hash,
                 ],
             )?;
+            index_symbol_text(
+                &tx,
+                tx.last_insert_rowid(),
+                &SymbolText {
+                    name: &symbol.name,
+                    path: &symbol.full_path,
+                    file: &file_path_str,
+                    signature: symbol.signature.as_deref(),
+                    doc: symbol.doc.as_deref(),
+                },
+            )?;
         }
 
-        tx.commit()?;
-        Ok(())
-    }
-
-    /// Scan for Rust files in a directory
-    fn scan_rust_files(&self, path: &Path) -> Result<Vec<PathBuf>> {
-        let mut files = Vec::new();
-
-        for entry in WalkDir::new(path)
-            .follow_links(false)
-            .into_iter()
-            .filter_entry(|e| !is_hidden(e) && !is_target_dir(e))
-        {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.extension().map(|e| e == "rs").unwrap_or(false) {
-                files.push(path.to_path_buf());
-            }
+        // Files are recorded even when they produced no symbols (or failed
+        // to parse) so they aren't reprocessed until their content changes
+        for file in files {
+            tx.execute(
+                "INSERT INTO codebase_files (codebase_id, file_path, content_hash, indexed_at)
+                 VALUES (?1, ?2, ?3, ?4)
+                 ON CONFLICT(codebase_id, file_path) DO UPDATE SET
+                    content_hash = excluded.content_hash,
+                    indexed_at = excluded.indexed_at",
+                params![
+                    codebase_id,
+                    file.path.to_string_lossy().to_string(),
+                    file.content_hash,
+                    indexed_at,
+                ],
+            )?;
         }
 
-        Ok(files)
+        tx.commit()?;
+        Ok(())
     }
 
-    /// Parse a single Rust file
-    fn parse_file(&mut self, path: &Path) -> Result<Vec<ParsedSymbol>> {
+    /// Parse a single source file with the parser for its language
+    ///
+    /// Summaries cached for unchanged symbols are picked up here.
+    fn parse_file(
+        &mut self,
+        conn: &Connection,
+        root: &Path,
+        path: &Path,
+    ) -> Result<Vec<IndexedSymbol>> {
+        let lang = SourceLanguage::from_path(path)
+            .with_context(|| format!("Unsupported file type: {}", path.display()))?;
         let source = std::fs::read_to_string(path)
             .with_context(|| format!("Failed to read {}", path.display()))?;
-        self.parser.parse_file(path, &source)
-    }
-
-    /// Convert symbol to text for embedding
-    fn symbol_to_embedding_text(&self, symbol: &ParsedSymbol) -> String {
-        let mut text = format!("{} {}", symbol.symbol_type.as_str(), symbol.name);
 
-        if let Some(ref sig) = symbol.signature {
-            text.push_str(": ");
-            text.push_str(sig);
-        }
-
-        if !symbol.calls.is_empty() {
-            text.push_str(" calls: ");
-            text.push_str(&symbol.calls.join(", "));
-        }
-
-        text
-    }
-
-    /// Insert a symbol into the database
-    fn insert_symbol(
-        &self,
-        conn: &Connection,
-        codebase_id: &str,
-        file_path: &Path,
-        symbol: &ParsedSymbol,
-        embedding: Option<&[f32]>,
-        indexed_at: &str,
-    ) -> Result<()> {
-        let file_path_str = file_path.to_string_lossy().to_string();
-        let calls_json = serde_json::to_string(&symbol.calls)?;
-        let embedding_blob = embedding.map(EmbeddingEngine::embedding_to_blob);
-
-        conn.execute(
-            "INSERT INTO codebase_index
-             (codebase_id, symbol_type, symbol_name, symbol_path, file_path,
-              line_start, line_end, signature, embedding, calls, indexed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-            params![
-                codebase_id,
-                symbol.symbol_type.as_str(),
-                symbol.name,
-                symbol.full_path,
-                file_path_str,
-                symbol.line_start as i64,
-                symbol.line_end as i64,
-                symbol.signature,
-                embedding_blob,
-                calls_json,
-                indexed_at,
-            ],
-        )?;
+        let parser = match self.parsers.entry(lang) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(parser_for(lang)?),
+        };
+        // Module paths are derived relative to the codebase root
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let symbols = parser.parse_file(relative, &source)?;
 
-        Ok(())
+        symbols
+            .into_iter()
+            .map(|symbol| {
+                let summary_hash = summaries::summary_hash(lang, path, &source, &symbol);
+                let summary = match &summary_hash {
+                    Some(hash) => summaries::cached_summary(conn, hash)?,
+                    None => None,
+                };
+                Ok(IndexedSymbol {
+                    symbol,
+                    summary_hash,
+                    summary,
+                })
+            })
+            .collect()
     }
 
     /// Get index statistics for a codebase
@@ -496,6 +595,65 @@ pub struct IndexStats {
     pub symbols_with_embeddings: usize,
 }
 
+/// Convert a symbol to text for embedding
+pub(super) fn embedding_text(symbol: &ParsedSymbol, summary: Option<&str>) -> String {
+    let mut text = format!("{} {}", symbol.symbol_type.as_str(), symbol.name);
+
+    if let Some(ref sig) = symbol.signature {
+        text.push_str(": ");
+        text.push_str(sig);
+    }
+
+    if let Some(summary) = summary {
+        text.push_str(" - ");
+        text.push_str(summary);
+    } else if let Some(doc) = symbol.doc.as_deref().and_then(|d| d.lines().next()) {
+        text.push_str(" - ");
+        text.push_str(doc);
+    }
+
+    if !symbol.calls.is_empty() {
+        text.push_str(" calls: ");
+        text.push_str(&symbol.calls.join(", "));
+    }
+
+    text
+}
+
+/// Update the codebase's ANN graph; search falls back to brute force if this fails
+fn sync_ann(conn: &Connection, codebase_id: &str) {
+    if let Err(e) = ann::sync(conn, codebase_id) {
+        warn!(codebase_id, error = %e, "Failed to update ANN index");
+    }
+}
+
+/// Scan for files in any supported language
+pub(super) fn scan_source_files(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for entry in WalkDir::new(path)
+        .follow_links(false)
+        .into_iter()
+        .filter_entry(|e| !is_hidden(e) && !is_target_dir(e))
+    {
+        let entry = entry?;
+        let path = entry.path();
+
+        if entry.file_type().is_file() && SourceLanguage::from_path(path).is_some() {
+            files.push(path.to_path_buf());
+        }
+    }
+
+    Ok(files)
+}
+
+/// Hash file contents for change detection
+fn hash_file(path: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    Ok(format!("{:x}", Sha256::digest(&bytes)))
+}
+
 /// Check if entry is a hidden file/directory
 fn is_hidden(entry: &walkdir::DirEntry) -> bool {
     entry
@@ -505,12 +663,138 @@ fn is_hidden(entry: &walkdir::DirEntry) -> bool {
         .unwrap_or(false)
 }
 
-/// Check if entry is the target directory
+/// Check if entry is a build output or dependency directory
 fn is_target_dir(entry: &walkdir::DirEntry) -> bool {
     entry.file_type().is_dir()
         && entry
             .file_name()
             .to_str()
-            .map(|s| s == "target" || s == "node_modules" || s == ".git")
+            .map(|s| {
+                matches!(
+                    s,
+                    "target" | "node_modules" | ".git" | "dist" | "vendor" | "__pycache__" | "venv"
+                )
+            })
             .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Database;
+    use tempfile::TempDir;
+
+    /// Temp project dir (the default `.tmp` prefix would be skipped as hidden)
+    fn project_dir() -> TempDir {
+        tempfile::Builder::new()
+            .prefix("krusty-index")
+            .tempdir()
+            .unwrap()
+    }
+
+    fn indexed_files(conn: &Connection, codebase_id: &str) -> Vec<(String, String)> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT DISTINCT symbol_name, file_path FROM codebase_index
+                 WHERE codebase_id = ?1 ORDER BY symbol_name",
+            )
+            .unwrap();
+        let rows = stmt
+            .query_map([codebase_id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        rows.collect::<Result<Vec<_>, _>>().unwrap()
+    }
+
+    #[test]
+    fn test_incremental_index_only_touches_changed_files() {
+        let project = project_dir();
+        let db_dir = TempDir::new().unwrap();
+        let db = Database::new(&db_dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+
+        std::fs::write(project.path().join("a.rs"), "fn alpha() {}\n").unwrap();
+        std::fs::write(project.path().join("b.rs"), "fn beta() {}\n").unwrap();
+        std::fs::write(project.path().join("c.rs"), "fn gamma() {}\n").unwrap();
+
+        let mut indexer = Indexer::new().unwrap();
+        let codebase = indexer
+            .index_codebase_sync(conn, project.path(), None)
+            .unwrap();
+        assert_eq!(codebase.index_version, INDEX_VERSION);
+        let names: Vec<_> = indexed_files(conn, &codebase.id)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["alpha", "beta", "gamma"]);
+
+        let alpha_row_id = |conn: &Connection| -> i64 {
+            conn.query_row(
+                "SELECT id FROM codebase_index WHERE symbol_name = 'alpha'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap()
+        };
+        let alpha_before = alpha_row_id(conn);
+
+        // Modify one file, delete another, add a new one
+        std::fs::write(project.path().join("b.rs"), "fn beta_two() {}\n").unwrap();
+        std::fs::remove_file(project.path().join("c.rs")).unwrap();
+        std::fs::write(project.path().join("d.rs"), "fn delta() {}\n").unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        indexer
+            .index_codebase_sync(conn, project.path(), Some(tx))
+            .unwrap();
+
+        let names: Vec<_> = indexed_files(conn, &codebase.id)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, vec!["alpha", "beta_two", "delta"]);
+
+        // Unchanged file keeps its row; only the two changed files were parsed
+        assert_eq!(alpha_row_id(conn), alpha_before);
+        let mut parsing_total = None;
+        while let Ok(progress) = rx.try_recv() {
+            if progress.phase == IndexPhase::Parsing {
+                parsing_total = Some(progress.total);
+            }
+        }
+        assert_eq!(parsing_total, Some(2));
+
+        let hashes = CodebaseStore::new(conn).file_hashes(&codebase.id).unwrap();
+        assert_eq!(hashes.len(), 3);
+        assert!(!hashes.keys().any(|path| path.ends_with("c.rs")));
+    }
+
+    #[test]
+    fn test_outdated_index_version_rebuilds() {
+        let project = project_dir();
+        let db_dir = TempDir::new().unwrap();
+        let db = Database::new(&db_dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+
+        std::fs::write(project.path().join("lib.rs"), "fn only() {}\n").unwrap();
+
+        let mut indexer = Indexer::new().unwrap();
+        let codebase = indexer
+            .index_codebase_sync(conn, project.path(), None)
+            .unwrap();
+        let store = CodebaseStore::new(conn);
+        store.mark_indexed(&codebase.id, INDEX_VERSION - 1).unwrap();
+
+        indexer
+            .index_codebase_sync(conn, project.path(), None)
+            .unwrap();
+        assert_eq!(indexed_files(conn, &codebase.id).len(), 1);
+        assert_eq!(
+            store
+                .get_by_id(&codebase.id)
+                .unwrap()
+                .unwrap()
+                .index_version,
+            INDEX_VERSION
+        );
+    }
+}
@@ -96,8 +96,20 @@ pub struct CodebaseInsight {
     pub access_count: i32,
     pub created_at: DateTime<Utc>,
     pub last_accessed_at: DateTime<Utc>,
+    /// Pinned insights are injected first and kept when /init purges
+    pub pinned: bool,
 }
 
+/// Columns read by `query_insights`, in order
+const INSIGHT_COLUMNS: &str = "id, codebase_id, insight_type, content, embedding, confidence,
+     source_session_id, access_count, created_at, last_accessed_at, pinned";
+
+/// Embedding similarity at which two insights state the same fact
+const EMBEDDING_SIMILARITY: f32 = 0.85;
+
+/// Word overlap at which two insights state the same fact
+const WORD_SIMILARITY: f64 = 0.5;
+
 /// Store for insight operations
 pub struct InsightStore<'a> {
     conn: &'a Connection,
@@ -118,8 +130,8 @@ impl<'a> InsightStore<'a> {
         self.conn.execute(
             "INSERT INTO codebase_insights
              (id, codebase_id, insight_type, content, embedding, confidence,
-              source_session_id, access_count, created_at, last_accessed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+              source_session_id, access_count, created_at, last_accessed_at, pinned)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
             params![
                 insight.id,
                 insight.codebase_id,
@@ -131,6 +143,7 @@ impl<'a> InsightStore<'a> {
                 insight.access_count,
                 insight.created_at.to_rfc3339(),
                 insight.last_accessed_at.to_rfc3339(),
+                insight.pinned,
             ],
         )?;
         Ok(())
@@ -138,12 +151,11 @@ impl<'a> InsightStore<'a> {
 
     /// Get insights for a codebase
     pub fn get_by_codebase(&self, codebase_id: &str) -> Result<Vec<CodebaseInsight>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, codebase_id, insight_type, content, embedding, confidence,
-                    source_session_id, access_count, created_at, last_accessed_at
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {INSIGHT_COLUMNS}
              FROM codebase_insights WHERE codebase_id = ?1
-             ORDER BY confidence DESC, access_count DESC",
-        )?;
+             ORDER BY pinned DESC, confidence DESC, access_count DESC"
+        ))?;
 
         self.query_insights(&mut stmt, [codebase_id])
     }
@@ -154,51 +166,116 @@ impl<'a> InsightStore<'a> {
         codebase_id: &str,
         insight_type: InsightType,
     ) -> Result<Vec<CodebaseInsight>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, codebase_id, insight_type, content, embedding, confidence,
-                    source_session_id, access_count, created_at, last_accessed_at
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {INSIGHT_COLUMNS}
              FROM codebase_insights WHERE codebase_id = ?1 AND insight_type = ?2
-             ORDER BY confidence DESC, access_count DESC",
-        )?;
+             ORDER BY pinned DESC, confidence DESC, access_count DESC"
+        ))?;
 
         self.query_insights(&mut stmt, params![codebase_id, insight_type.as_str()])
     }
 
-    /// Get top insights by confidence
+    /// Get top insights by confidence, pinned ones first
     pub fn get_top(&self, codebase_id: &str, limit: usize) -> Result<Vec<CodebaseInsight>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id, codebase_id, insight_type, content, embedding, confidence,
-                    source_session_id, access_count, created_at, last_accessed_at
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {INSIGHT_COLUMNS}
              FROM codebase_insights WHERE codebase_id = ?1
-             ORDER BY confidence DESC, access_count DESC
-             LIMIT ?2",
-        )?;
+             ORDER BY pinned DESC, confidence DESC, access_count DESC
+             LIMIT ?2"
+        ))?;
 
         self.query_insights(&mut stmt, params![codebase_id, limit as i64])
     }
 
     /// Check if a similar insight already exists (by content similarity)
     pub fn has_similar(&self, codebase_id: &str, content: &str) -> Result<bool> {
-        // Simple check: look for high text overlap
+        Ok(self.find_similar(codebase_id, content, None)?.is_some())
+    }
+
+    /// Find the insight that best matches `content`
+    ///
+    /// Compares embeddings when both sides have one and word overlap
+    /// otherwise.
+    pub fn find_similar(
+        &self,
+        codebase_id: &str,
+        content: &str,
+        embedding: Option<&[f32]>,
+    ) -> Result<Option<CodebaseInsight>> {
         let normalized = normalize_content(content);
+        let mut best: Option<(f64, CodebaseInsight)> = None;
+
+        for insight in self.get_by_codebase(codebase_id)? {
+            let score = match (embedding, insight.embedding.as_deref()) {
+                (Some(a), Some(b)) => {
+                    let similarity = EmbeddingEngine::cosine_similarity(a, b);
+                    (similarity >= EMBEDDING_SIMILARITY).then_some(similarity as f64)
+                }
+                _ => {
+                    let similarity =
+                        jaccard_similarity(&normalized, &normalize_content(&insight.content));
+                    (similarity > WORD_SIMILARITY).then_some(similarity)
+                }
+            };
+            if let Some(score) = score {
+                if best.as_ref().is_none_or(|(top, _)| score > *top) {
+                    best = Some((score, insight));
+                }
+            }
+        }
 
-        let mut stmt = self
-            .conn
-            .prepare("SELECT content FROM codebase_insights WHERE codebase_id = ?1 LIMIT 100")?;
+        Ok(best.map(|(_, insight)| insight))
+    }
 
-        let rows = stmt.query_map([codebase_id], |row| row.get::<_, String>(0))?;
+    /// Raise an insight's confidence because its fact came up again
+    ///
+    /// Never lowers confidence, and fills in a missing embedding.
+    pub fn reinforce(
+        &self,
+        id: &str,
+        boost: f64,
+        max_confidence: f64,
+        embedding: Option<&[f32]>,
+    ) -> Result<()> {
+        let embedding_blob = embedding.map(EmbeddingEngine::embedding_to_blob);
+        self.conn.execute(
+            "UPDATE codebase_insights
+             SET confidence = MAX(confidence, MIN(confidence + ?1, ?2)),
+                 embedding = COALESCE(embedding, ?3)
+             WHERE id = ?4",
+            params![boost, max_confidence, embedding_blob, id],
+        )?;
+        Ok(())
+    }
 
-        for row in rows {
-            let existing = row?;
-            let existing_normalized = normalize_content(&existing);
+    /// Replace an insight's text; its embedding no longer applies
+    pub fn update_content(&self, id: &str, content: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE codebase_insights SET content = ?1, embedding = NULL WHERE id = ?2",
+            params![content, id],
+        )?;
+        Ok(())
+    }
 
-            // Check for significant overlap using Jaccard similarity
-            if jaccard_similarity(&normalized, &existing_normalized) > 0.5 {
-                return Ok(true);
-            }
-        }
+    /// Pin or unpin an insight
+    pub fn set_pinned(&self, id: &str, pinned: bool) -> Result<()> {
+        self.conn.execute(
+            "UPDATE codebase_insights SET pinned = ?1 WHERE id = ?2",
+            params![pinned, id],
+        )?;
+        Ok(())
+    }
 
-        Ok(false)
+    /// Delete an insight along with the session memories promoted into it,
+    /// so a later promotion pass doesn't bring it back
+    pub fn delete(&self, id: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM session_memories WHERE promoted_to_insight_id = ?1",
+            [id],
+        )?;
+        self.conn
+            .execute("DELETE FROM codebase_insights WHERE id = ?1", [id])?;
+        Ok(())
     }
 
     fn query_insights<P: rusqlite::Params>(
@@ -217,6 +294,7 @@ impl<'a> InsightStore<'a> {
             let access_count: i32 = row.get(7)?;
             let created_at: String = row.get(8)?;
             let last_accessed_at: String = row.get(9)?;
+            let pinned: bool = row.get(10)?;
 
             Ok((
                 id,
@@ -229,6 +307,7 @@ impl<'a> InsightStore<'a> {
                 access_count,
                 created_at,
                 last_accessed_at,
+                pinned,
             ))
         })?;
 
@@ -245,6 +324,7 @@ impl<'a> InsightStore<'a> {
                 access_count,
                 created_at,
                 last_accessed_at,
+                pinned,
             ) = row?;
 
             let insight_type =
@@ -268,6 +348,7 @@ impl<'a> InsightStore<'a> {
                 access_count,
                 created_at,
                 last_accessed_at,
+                pinned,
             });
         }
 
@@ -287,10 +368,11 @@ impl<'a> InsightStore<'a> {
         Ok(())
     }
 
-    /// Delete insights at or above a confidence threshold for a codebase
+    /// Delete unpinned insights at or above a confidence threshold for a codebase
     pub fn delete_by_confidence_above(&self, codebase_id: &str, threshold: f64) -> Result<usize> {
         let count = self.conn.execute(
-            "DELETE FROM codebase_insights WHERE codebase_id = ?1 AND confidence >= ?2",
+            "DELETE FROM codebase_insights
+             WHERE codebase_id = ?1 AND confidence >= ?2 AND pinned = 0",
             params![codebase_id, threshold],
         )?;
         Ok(count)
@@ -298,7 +380,7 @@ impl<'a> InsightStore<'a> {
 }
 
 /// Normalize content for comparison
-fn normalize_content(content: &str) -> Vec<String> {
+pub(super) fn normalize_content(content: &str) -> Vec<String> {
     content
         .to_lowercase()
         .split_whitespace()
@@ -308,7 +390,7 @@ fn normalize_content(content: &str) -> Vec<String> {
 }
 
 /// Calculate Jaccard similarity between two word sets
-fn jaccard_similarity(a: &[String], b: &[String]) -> f64 {
+pub(super) fn jaccard_similarity(a: &[String], b: &[String]) -> f64 {
     use std::collections::HashSet;
     let set_a: HashSet<_> = a.iter().collect();
     let set_b: HashSet<_> = b.iter().collect();
@@ -343,5 +425,6 @@ pub fn create_insight(
         access_count: 0,
         created_at: now,
         last_accessed_at: now,
+        pinned: false,
     }
 }
@@ -0,0 +1,503 @@
+//! Query-driven parsers for TypeScript/JavaScript, Python and Go
+//!
+//! Each language is described by a `LanguageSpec`: a tree-sitter grammar, a
+//! symbol query whose `@<kind>_name` captures name a definition, and a call
+//! query. The same extraction code turns matches into `ParsedSymbol`s.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use streaming_iterator::StreamingIterator;
+use tree_sitter::{Language, Node, Parser, Query, QueryCursor};
+
+use super::parser::{doc_comment, LanguageParser, ParsedSymbol, SourceLanguage, SymbolType};
+
+const TYPESCRIPT_SYMBOLS: &str = r#"
+    (function_declaration name: (identifier) @function_name)
+    (generator_function_declaration name: (identifier) @function_name)
+    (lexical_declaration
+        (variable_declarator
+            name: (identifier) @function_name
+            value: [(arrow_function) (function_expression)]))
+    (class_declaration name: (type_identifier) @class_name)
+    (abstract_class_declaration name: (type_identifier) @class_name)
+    (method_definition name: (property_identifier) @method_name)
+    (interface_declaration name: (type_identifier) @interface_name)
+    (type_alias_declaration name: (type_identifier) @type_name)
+    (enum_declaration name: (identifier) @enum_name)
+"#;
+
+const JAVASCRIPT_SYMBOLS: &str = r#"
+    (function_declaration name: (identifier) @function_name)
+    (generator_function_declaration name: (identifier) @function_name)
+    (lexical_declaration
+        (variable_declarator
+            name: (identifier) @function_name
+            value: [(arrow_function) (function_expression)]))
+    (class_declaration name: (identifier) @class_name)
+    (method_definition name: (property_identifier) @method_name)
+"#;
+
+const JS_CALLS: &str = r#"
+    (call_expression function: (identifier) @call)
+    (call_expression function: (member_expression property: (property_identifier) @call))
+"#;
+
+const PYTHON_SYMBOLS: &str = r#"
+    (function_definition name: (identifier) @function_name)
+    (class_definition name: (identifier) @class_name)
+"#;
+
+const PYTHON_CALLS: &str = r#"
+    (call function: (identifier) @call)
+    (call function: (attribute attribute: (identifier) @call))
+"#;
+
+const GO_SYMBOLS: &str = r#"
+    (function_declaration name: (identifier) @function_name)
+    (method_declaration name: (field_identifier) @method_name)
+    (type_spec name: (type_identifier) @type_name)
+    (type_alias name: (type_identifier) @type_name)
+    (const_spec name: (identifier) @const_name)
+"#;
+
+const GO_CALLS: &str = r#"
+    (call_expression function: (identifier) @call)
+    (call_expression function: (selector_expression field: (field_identifier) @call))
+"#;
+
+/// Grammar and queries for one language
+struct LanguageSpec {
+    language: Language,
+    symbols: &'static str,
+    calls: &'static str,
+    /// Node kinds that own methods defined inside them
+    class_kinds: &'static [&'static str],
+    /// Node kinds between a method and its class (bodies, decorators)
+    container_kinds: &'static [&'static str],
+}
+
+impl LanguageSpec {
+    fn for_language(lang: SourceLanguage) -> Option<Self> {
+        let js_classes: &[&str] = &["class_declaration", "abstract_class_declaration", "class"];
+        let spec = match lang {
+            SourceLanguage::Rust => return None,
+            SourceLanguage::TypeScript | SourceLanguage::Tsx => Self {
+                language: if lang == SourceLanguage::Tsx {
+                    tree_sitter_typescript::LANGUAGE_TSX.into()
+                } else {
+                    tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into()
+                },
+                symbols: TYPESCRIPT_SYMBOLS,
+                calls: JS_CALLS,
+                class_kinds: js_classes,
+                container_kinds: &["class_body"],
+            },
+            SourceLanguage::JavaScript => Self {
+                language: tree_sitter_javascript::LANGUAGE.into(),
+                symbols: JAVASCRIPT_SYMBOLS,
+                calls: JS_CALLS,
+                class_kinds: js_classes,
+                container_kinds: &["class_body"],
+            },
+            SourceLanguage::Python => Self {
+                language: tree_sitter_python::LANGUAGE.into(),
+                symbols: PYTHON_SYMBOLS,
+                calls: PYTHON_CALLS,
+                class_kinds: &["class_definition"],
+                container_kinds: &["block", "decorated_definition"],
+            },
+            SourceLanguage::Go => Self {
+                language: tree_sitter_go::LANGUAGE.into(),
+                symbols: GO_SYMBOLS,
+                calls: GO_CALLS,
+                class_kinds: &[],
+                container_kinds: &[],
+            },
+        };
+        Some(spec)
+    }
+}
+
+/// Tree-sitter parser for a non-Rust language
+pub struct QueryParser {
+    lang: SourceLanguage,
+    parser: Parser,
+    spec: LanguageSpec,
+    symbols_query: Query,
+    calls_query: Query,
+}
+
+impl QueryParser {
+    pub fn new(lang: SourceLanguage) -> Result<Self> {
+        let spec = LanguageSpec::for_language(lang)
+            .with_context(|| format!("No query parser for {}", lang.as_str()))?;
+        let mut parser = Parser::new();
+        parser
+            .set_language(&spec.language)
+            .with_context(|| format!("Failed to set {} language", lang.as_str()))?;
+        let symbols_query = Query::new(&spec.language, spec.symbols)
+            .with_context(|| format!("Failed to compile {} symbol query", lang.as_str()))?;
+        let calls_query = Query::new(&spec.language, spec.calls)
+            .with_context(|| format!("Failed to compile {} call query", lang.as_str()))?;
+        Ok(Self {
+            lang,
+            parser,
+            spec,
+            symbols_query,
+            calls_query,
+        })
+    }
+
+    /// Module path used as the prefix of every symbol's full path
+    fn module_path(&self, path: &Path, root: Node, source: &str) -> String {
+        if self.lang == SourceLanguage::Go {
+            // Go symbols are referenced through their package name
+            let package = (0..root.named_child_count())
+                .filter_map(|i| root.named_child(i))
+                .find(|n| n.kind() == "package_clause")
+                .and_then(|n| n.named_child(0))
+                .and_then(|n| n.utf8_text(source.as_bytes()).ok());
+            if let Some(package) = package {
+                return package.to_string();
+            }
+        }
+
+        let stem = path.with_extension("");
+        let mut components: Vec<&str> = stem
+            .components()
+            .filter_map(|c| c.as_os_str().to_str())
+            .collect();
+        if components.first() == Some(&"src") {
+            components.remove(0);
+        }
+        // index.ts / __init__.py name their directory
+        if components.len() > 1 && matches!(components.last(), Some(&"index" | &"__init__")) {
+            components.pop();
+        }
+
+        let separator = if self.lang == SourceLanguage::Python {
+            "."
+        } else {
+            "/"
+        };
+        components.join(separator)
+    }
+
+    /// Classify a name capture, refining by its definition node
+    fn symbol_type(&self, capture_name: &str, definition: Node) -> Option<SymbolType> {
+        let symbol_type = match capture_name {
+            "function_name" => SymbolType::Function,
+            "method_name" => SymbolType::Method,
+            "class_name" => SymbolType::Class,
+            "interface_name" => SymbolType::Interface,
+            "enum_name" => SymbolType::Enum,
+            "const_name" => SymbolType::Const,
+            // Go declares structs and interfaces as named types
+            "type_name" => match definition.child_by_field_name("type").map(|t| t.kind()) {
+                Some("struct_type") => SymbolType::Struct,
+                Some("interface_type") => SymbolType::Interface,
+                _ => SymbolType::TypeAlias,
+            },
+            _ => return None,
+        };
+        Some(symbol_type)
+    }
+
+    /// Name of the type owning a method, if any
+    fn owner_name(&self, definition: Node, source: &str) -> Option<String> {
+        // Go: func (s *Server) Start()
+        if let Some(receiver) = definition.child_by_field_name("receiver") {
+            return find_descendant(receiver, "type_identifier")
+                .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                .map(str::to_string);
+        }
+
+        let mut current = definition.parent();
+        while let Some(node) = current {
+            if self.spec.class_kinds.contains(&node.kind()) {
+                return node
+                    .child_by_field_name("name")
+                    .and_then(|n| n.utf8_text(source.as_bytes()).ok())
+                    .map(str::to_string);
+            }
+            if !self.spec.container_kinds.contains(&node.kind()) {
+                return None;
+            }
+            current = node.parent();
+        }
+        None
+    }
+
+    /// First line of a definition, without the body opener
+    fn extract_signature(&self, node: Node, source: &str) -> String {
+        let text = node.utf8_text(source.as_bytes()).unwrap_or("");
+        text.lines()
+            .next()
+            .unwrap_or("")
+            .trim_end()
+            .trim_end_matches(['{', ':'])
+            .trim()
+            .to_string()
+    }
+
+    /// Names of functions and methods called within a node
+    fn extract_calls(&self, node: Node, source: &str) -> Vec<String> {
+        let mut calls: Vec<String> = Vec::new();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&self.calls_query, node, source.as_bytes());
+        while let Some(match_) = matches.next() {
+            for capture in match_.captures.iter() {
+                if let Ok(text) = capture.node.utf8_text(source.as_bytes()) {
+                    if !calls.iter().any(|c| c == text) {
+                        calls.push(text.to_string());
+                    }
+                }
+            }
+        }
+        calls
+    }
+}
+
+impl LanguageParser for QueryParser {
+    fn parse_file(&mut self, path: &Path, source: &str) -> Result<Vec<ParsedSymbol>> {
+        let tree = self
+            .parser
+            .parse(source, None)
+            .context("Failed to parse source")?;
+        let root = tree.root_node();
+        let module_path = self.module_path(path, root, source);
+
+        let mut symbols = Vec::new();
+        let mut cursor = QueryCursor::new();
+        let mut matches = cursor.matches(&self.symbols_query, root, source.as_bytes());
+
+        while let Some(match_) = matches.next() {
+            for capture in match_.captures.iter() {
+                let capture_name = self.symbols_query.capture_names()[capture.index as usize];
+                let node = capture.node;
+                let definition = match capture_name {
+                    // `const f = () => {}`: span the whole declaration
+                    "function_name"
+                        if node.parent().map(|p| p.kind()) == Some("variable_declarator") =>
+                    {
+                        node.parent().and_then(|p| p.parent())
+                    }
+                    _ => node.parent(),
+                }
+                .unwrap_or(node);
+
+                let Some(mut symbol_type) = self.symbol_type(capture_name, definition) else {
+                    continue;
+                };
+                let name = node.utf8_text(source.as_bytes()).unwrap_or("").to_string();
+                if name.is_empty() {
+                    continue;
+                }
+
+                let owner = match symbol_type {
+                    SymbolType::Function | SymbolType::Method => {
+                        self.owner_name(definition, source)
+                    }
+                    _ => None,
+                };
+                match (&owner, symbol_type) {
+                    (Some(_), SymbolType::Function) => symbol_type = SymbolType::Method,
+                    // Methods of object literals aren't worth indexing
+                    (None, SymbolType::Method) => continue,
+                    _ => {}
+                }
+
+                let mut full_path = module_path.clone();
+                for part in owner.iter().chain(std::iter::once(&name)) {
+                    if !full_path.is_empty() {
+                        full_path.push('.');
+                    }
+                    full_path.push_str(part);
+                }
+
+                let signature = matches!(symbol_type, SymbolType::Function | SymbolType::Method)
+                    .then(|| self.extract_signature(definition, source));
+
+                symbols.push(ParsedSymbol {
+                    symbol_type,
+                    name,
+                    full_path,
+                    line_start: definition.start_position().row + 1,
+                    line_end: definition.end_position().row + 1,
+                    signature,
+                    doc: doc_comment(definition, source),
+                    implements: None,
+                    calls: self.extract_calls(definition, source),
+                });
+            }
+        }
+
+        Ok(symbols)
+    }
+}
+
+/// First descendant of `node` with the given kind (depth-first)
+fn find_descendant<'a>(node: Node<'a>, kind: &str) -> Option<Node<'a>> {
+    if node.kind() == kind {
+        return Some(node);
+    }
+    let mut cursor = node.walk();
+    let children: Vec<Node<'a>> = node.named_children(&mut cursor).collect();
+    children
+        .into_iter()
+        .find_map(|child| find_descendant(child, kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(lang: SourceLanguage, path: &str, source: &str) -> Vec<ParsedSymbol> {
+        QueryParser::new(lang)
+            .unwrap()
+            .parse_file(Path::new(path), source)
+            .unwrap()
+    }
+
+    fn find<'a>(symbols: &'a [ParsedSymbol], name: &str) -> &'a ParsedSymbol {
+        symbols
+            .iter()
+            .find(|s| s.name == name)
+            .unwrap_or_else(|| panic!("symbol {name} not found in {symbols:?}"))
+    }
+
+    #[test]
+    fn test_parse_typescript() {
+        let source = r#"
+export interface Greeter {
+    greet(name: string): string;
+}
+
+export type Id = string;
+
+export class Console implements Greeter {
+    greet(name: string): string {
+        return format(name).trim();
+    }
+}
+
+export const shout = (text: string) => text.toUpperCase();
+
+/**
+ * Build the greeting text
+ */
+function format(name: string): string {
+    return `Hello, ${name}`;
+}
+"#;
+        let symbols = parse(SourceLanguage::TypeScript, "src/util/greet.ts", source);
+
+        assert_eq!(find(&symbols, "Greeter").symbol_type, SymbolType::Interface);
+        assert_eq!(find(&symbols, "Id").symbol_type, SymbolType::TypeAlias);
+        assert_eq!(find(&symbols, "Console").symbol_type, SymbolType::Class);
+        assert_eq!(find(&symbols, "shout").symbol_type, SymbolType::Function);
+
+        let greet = find(&symbols, "greet");
+        assert_eq!(greet.symbol_type, SymbolType::Method);
+        assert_eq!(greet.full_path, "util/greet.Console.greet");
+        assert_eq!(
+            greet.signature.as_deref(),
+            Some("greet(name: string): string")
+        );
+        assert_eq!(greet.calls, vec!["format", "trim"]);
+        assert_eq!(
+            find(&symbols, "format").doc.as_deref(),
+            Some("Build the greeting text")
+        );
+    }
+
+    #[test]
+    fn test_parse_javascript() {
+        let source = r#"
+class Store {
+    load() { return fetchAll(); }
+}
+function fetchAll() {}
+const helpers = { ignored() {} };
+"#;
+        let symbols = parse(SourceLanguage::JavaScript, "lib/store/index.js", source);
+
+        assert_eq!(find(&symbols, "Store").symbol_type, SymbolType::Class);
+        assert_eq!(find(&symbols, "load").full_path, "lib/store.Store.load");
+        assert_eq!(find(&symbols, "fetchAll").symbol_type, SymbolType::Function);
+        assert!(symbols.iter().all(|s| s.name != "ignored"));
+    }
+
+    #[test]
+    fn test_parse_python() {
+        let source = r#"
+class Repo:
+    @staticmethod
+    def open(path):
+        return Repo.load(path)
+
+    def load(self, path):
+        def helper():
+            pass
+        return read(path)
+
+def read(path: str) -> bytes:
+    """Read a whole file."""
+    return open(path).read()
+"#;
+        let symbols = parse(SourceLanguage::Python, "pkg/repo.py", source);
+
+        assert_eq!(find(&symbols, "Repo").symbol_type, SymbolType::Class);
+        let open = find(&symbols, "open");
+        assert_eq!(open.symbol_type, SymbolType::Method);
+        assert_eq!(open.full_path, "pkg.repo.Repo.open");
+        assert_eq!(find(&symbols, "helper").symbol_type, SymbolType::Function);
+
+        let read = find(&symbols, "read");
+        assert_eq!(read.symbol_type, SymbolType::Function);
+        assert_eq!(
+            read.signature.as_deref(),
+            Some("def read(path: str) -> bytes")
+        );
+        assert_eq!(read.doc.as_deref(), Some("Read a whole file."));
+        assert!(read.calls.iter().any(|c| c == "open"));
+        assert!(read.calls.iter().any(|c| c == "read"));
+    }
+
+    #[test]
+    fn test_parse_go() {
+        let source = r#"
+package server
+
+type Server struct {
+    addr string
+}
+
+type Handler interface {
+    Serve()
+}
+
+func (s *Server) Start() error {
+    return listen(s.addr)
+}
+
+// listen opens the TCP listener
+func listen(addr string) error {
+    return net.Listen(addr)
+}
+"#;
+        let symbols = parse(SourceLanguage::Go, "internal/server/server.go", source);
+
+        assert_eq!(find(&symbols, "Server").symbol_type, SymbolType::Struct);
+        assert_eq!(find(&symbols, "Handler").symbol_type, SymbolType::Interface);
+
+        let start = find(&symbols, "Start");
+        assert_eq!(start.symbol_type, SymbolType::Method);
+        assert_eq!(start.full_path, "server.Server.Start");
+        assert_eq!(start.calls, vec!["listen"]);
+
+        let listen = find(&symbols, "listen");
+        assert_eq!(listen.calls, vec!["Listen"]);
+        assert_eq!(listen.doc.as_deref(), Some("listen opens the TCP listener"));
+    }
+}
@@ -0,0 +1,418 @@
+//! Session memories and their promotion into codebase insights
+//!
+//! Pinch and session end record durable learnings (conventions, pitfalls,
+//! architecture facts) in `session_memories`. Promotion folds them into
+//! `codebase_insights`: a learning that matches an existing insight raises
+//! its confidence, anything new becomes a low-confidence insight.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use super::embeddings::EmbeddingEngine;
+use super::insights::{
+    create_insight, jaccard_similarity, normalize_content, InsightStore, InsightType,
+};
+
+/// Confidence of an insight seen in a single session
+pub const BASE_CONFIDENCE: f64 = 0.5;
+
+/// Confidence gained each time a fact recurs
+pub const RECURRENCE_BOOST: f64 = 0.1;
+
+/// Ceiling for confidence earned by recurrence
+pub const MAX_CONFIDENCE: f64 = 0.95;
+
+/// Most learnings kept per extraction
+const MAX_LEARNINGS: usize = 10;
+
+/// Longest learning kept, in bytes
+const MAX_LEARNING_LEN: usize = 500;
+
+/// Word overlap at which two learnings of a session are the same
+const DUPLICATE_SIMILARITY: f64 = 0.5;
+
+/// A learning as written by the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Learning {
+    /// One of the `InsightType` names; classified from the content otherwise
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    pub content: String,
+}
+
+impl Learning {
+    pub fn insight_type(&self) -> InsightType {
+        InsightType::parse(&self.kind).unwrap_or_else(|| InsightType::classify(&self.content))
+    }
+}
+
+/// A learning recorded for a session
+#[derive(Debug, Clone)]
+pub struct SessionMemory {
+    pub id: String,
+    pub session_id: String,
+    pub memory_type: InsightType,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+    pub promoted_to_insight_id: Option<String>,
+}
+
+/// Outcome of a promotion pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PromotionReport {
+    /// New insights
+    pub created: usize,
+    /// Existing insights whose confidence went up
+    pub reinforced: usize,
+}
+
+/// Store for session memory operations
+pub struct SessionMemoryStore<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SessionMemoryStore<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+
+    /// Record learnings for a session, skipping blanks and repeats
+    ///
+    /// Returns how many were stored.
+    pub fn record(&self, session_id: &str, learnings: &[Learning]) -> Result<usize> {
+        let mut seen: Vec<Vec<String>> = self
+            .for_session(session_id)?
+            .iter()
+            .map(|m| normalize_content(&m.content))
+            .collect();
+        let now = Utc::now().to_rfc3339();
+        let mut stored = 0;
+
+        for learning in learnings.iter().take(MAX_LEARNINGS) {
+            let content = truncate(learning.content.trim(), MAX_LEARNING_LEN);
+            if content.is_empty() {
+                continue;
+            }
+            let words = normalize_content(content);
+            if seen
+                .iter()
+                .any(|s| jaccard_similarity(s, &words) > DUPLICATE_SIMILARITY)
+            {
+                continue;
+            }
+
+            self.conn.execute(
+                "INSERT INTO session_memories (id, session_id, memory_type, content, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    uuid::Uuid::new_v4().to_string(),
+                    session_id,
+                    learning.insight_type().as_str(),
+                    content,
+                    now,
+                ],
+            )?;
+            seen.push(words);
+            stored += 1;
+        }
+
+        Ok(stored)
+    }
+
+    /// Whether learnings were already recorded for a session
+    pub fn has_memories(&self, session_id: &str) -> Result<bool> {
+        let exists = self.conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM session_memories WHERE session_id = ?1)",
+            [session_id],
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
+    /// All memories of a session, oldest first
+    pub fn for_session(&self, session_id: &str) -> Result<Vec<SessionMemory>> {
+        self.query(
+            "SELECT id, session_id, memory_type, content, created_at, promoted_to_insight_id
+             FROM session_memories WHERE session_id = ?1
+             ORDER BY created_at, rowid",
+            session_id,
+        )
+    }
+
+    /// Memories of a session that haven't been promoted yet
+    pub fn unpromoted(&self, session_id: &str) -> Result<Vec<SessionMemory>> {
+        self.query(
+            "SELECT id, session_id, memory_type, content, created_at, promoted_to_insight_id
+             FROM session_memories
+             WHERE session_id = ?1 AND promoted_to_insight_id IS NULL
+             ORDER BY created_at, rowid",
+            session_id,
+        )
+    }
+
+    /// Link a memory to the insight it was promoted into
+    pub fn mark_promoted(&self, id: &str, insight_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE session_memories SET promoted_to_insight_id = ?1 WHERE id = ?2",
+            params![insight_id, id],
+        )?;
+        Ok(())
+    }
+
+    fn query(&self, sql: &str, session_id: &str) -> Result<Vec<SessionMemory>> {
+        let mut stmt = self.conn.prepare(sql)?;
+        let rows = stmt.query_map([session_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+                row.get::<_, Option<String>>(5)?,
+            ))
+        })?;
+
+        let mut memories = Vec::new();
+        for row in rows {
+            let (id, session_id, memory_type, content, created_at, promoted_to_insight_id) = row?;
+            memories.push(SessionMemory {
+                id,
+                session_id,
+                memory_type: InsightType::parse(&memory_type)
+                    .unwrap_or_else(|| InsightType::classify(&content)),
+                content,
+                created_at: DateTime::parse_from_rfc3339(&created_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+                promoted_to_insight_id,
+            });
+        }
+        Ok(memories)
+    }
+}
+
+/// Promote a session's pending memories into insights for a codebase
+///
+/// A memory that matches an existing insight (by embedding when available,
+/// word overlap otherwise) raises that insight's confidence; the rest become
+/// new insights at `BASE_CONFIDENCE`.
+pub async fn promote_memories(
+    conn: &Connection,
+    codebase_id: &str,
+    session_id: &str,
+    embeddings: Option<&EmbeddingEngine>,
+) -> Result<PromotionReport> {
+    let memories = SessionMemoryStore::new(conn);
+    let insights = InsightStore::new(conn);
+    let mut report = PromotionReport::default();
+
+    for memory in memories.unpromoted(session_id)? {
+        let embedding = match embeddings {
+            Some(engine) => match engine.embed(&memory.content).await {
+                Ok(embedding) => Some(embedding),
+                Err(e) => {
+                    tracing::debug!("Failed to embed memory {}: {}", memory.id, e);
+                    None
+                }
+            },
+            None => None,
+        };
+
+        let insight_id =
+            match insights.find_similar(codebase_id, &memory.content, embedding.as_deref())? {
+                Some(existing) => {
+                    insights.reinforce(
+                        &existing.id,
+                        RECURRENCE_BOOST,
+                        MAX_CONFIDENCE,
+                        embedding.as_deref(),
+                    )?;
+                    report.reinforced += 1;
+                    existing.id
+                }
+                None => {
+                    let mut insight = create_insight(
+                        codebase_id,
+                        &memory.content,
+                        Some(session_id),
+                        BASE_CONFIDENCE,
+                        Some(memory.memory_type),
+                    );
+                    insight.embedding = embedding;
+                    insights.create(&insight)?;
+                    report.created += 1;
+                    insight.id
+                }
+            };
+        memories.mark_promoted(&memory.id, &insight_id)?;
+    }
+
+    Ok(report)
+}
+
+/// Truncate to at most `max` bytes on a char boundary
+fn truncate(s: &str, max: usize) -> &str {
+    if s.len() <= max {
+        return s;
+    }
+    let mut end = max;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s[..end].trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::CodebaseStore;
+    use crate::storage::Database;
+    use tempfile::TempDir;
+
+    fn learning(kind: &str, content: &str) -> Learning {
+        Learning {
+            kind: kind.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn create_session(conn: &Connection) -> String {
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "INSERT INTO sessions (id, title, created_at, updated_at) VALUES (?1, 'test', ?2, ?2)",
+            params![id, now],
+        )
+        .unwrap();
+        id
+    }
+
+    #[test]
+    fn test_learning_type_falls_back_to_classification() {
+        assert_eq!(
+            learning("pitfall", "Run migrations in order").insight_type(),
+            InsightType::Pitfall
+        );
+        assert_eq!(
+            learning("gotcha", "Avoid holding the lock across awaits").insight_type(),
+            InsightType::Pitfall
+        );
+    }
+
+    #[test]
+    fn test_record_skips_blanks_and_repeats() {
+        let dir = TempDir::new().unwrap();
+        let db = Database::new(&dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+        let session = create_session(conn);
+        let store = SessionMemoryStore::new(conn);
+
+        let stored = store
+            .record(
+                &session,
+                &[
+                    learning("convention", "Errors use anyhow with context messages"),
+                    learning("convention", "  "),
+                    learning("convention", "errors use anyhow with context messages."),
+                ],
+            )
+            .unwrap();
+        assert_eq!(stored, 1);
+        assert!(store.has_memories(&session).unwrap());
+
+        // Repeats of earlier extractions are skipped too
+        let stored = store
+            .record(
+                &session,
+                &[learning(
+                    "convention",
+                    "Errors use anyhow with context messages",
+                )],
+            )
+            .unwrap();
+        assert_eq!(stored, 0);
+    }
+
+    #[tokio::test]
+    async fn test_promotion_reinforces_recurring_facts() {
+        let dir = TempDir::new().unwrap();
+        let db = Database::new(&dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+        let codebase = CodebaseStore::new(conn).get_or_create(dir.path()).unwrap();
+        let memories = SessionMemoryStore::new(conn);
+        let insights = InsightStore::new(conn);
+        let fact = "Database migrations must bump SCHEMA_VERSION in storage/database.rs";
+
+        let first = create_session(conn);
+        memories
+            .record(&first, &[learning("pitfall", fact)])
+            .unwrap();
+        let report = promote_memories(conn, &codebase.id, &first, None)
+            .await
+            .unwrap();
+        assert_eq!(report.created, 1);
+        let top = insights.get_top(&codebase.id, 10).unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].confidence, BASE_CONFIDENCE);
+        assert_eq!(top[0].insight_type, InsightType::Pitfall);
+        assert!(memories.unpromoted(&first).unwrap().is_empty());
+
+        // The same fact from another session raises confidence instead
+        let second = create_session(conn);
+        memories
+            .record(&second, &[learning("pitfall", &fact.to_lowercase())])
+            .unwrap();
+        let report = promote_memories(conn, &codebase.id, &second, None)
+            .await
+            .unwrap();
+        assert_eq!(report.reinforced, 1);
+        let top = insights.get_top(&codebase.id, 10).unwrap();
+        assert_eq!(top.len(), 1);
+        assert!(top[0].confidence > BASE_CONFIDENCE);
+        assert_eq!(
+            memories.for_session(&second).unwrap()[0]
+                .promoted_to_insight_id
+                .as_deref(),
+            Some(top[0].id.as_str())
+        );
+    }
+
+    #[test]
+    fn test_pinned_insights_come_first_and_survive_purges() {
+        let dir = TempDir::new().unwrap();
+        let db = Database::new(&dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+        let codebase = CodebaseStore::new(conn).get_or_create(dir.path()).unwrap();
+        let insights = InsightStore::new(conn);
+
+        let low = create_insight(&codebase.id, "Prefer builders", None, 0.5, None);
+        let high = create_insight(&codebase.id, "Use tracing for logs", None, 0.9, None);
+        insights.create(&low).unwrap();
+        insights.create(&high).unwrap();
+        insights.set_pinned(&low.id, true).unwrap();
+
+        let top = insights.get_top(&codebase.id, 1).unwrap();
+        assert_eq!(top[0].id, low.id);
+        assert!(top[0].pinned);
+
+        insights.set_pinned(&high.id, true).unwrap();
+        insights.set_pinned(&high.id, false).unwrap();
+        assert_eq!(
+            insights
+                .delete_by_confidence_above(&codebase.id, 0.0)
+                .unwrap(),
+            1
+        );
+        insights
+            .update_content(&low.id, "Prefer with_* builders")
+            .unwrap();
+        let remaining = insights.get_by_codebase(&codebase.id).unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].content, "Prefer with_* builders");
+
+        insights.delete(&low.id).unwrap();
+        assert!(insights.get_by_codebase(&codebase.id).unwrap().is_empty());
+    }
+}
@@ -4,22 +4,44 @@
 //!
 //! Key components:
 //! - `parser` - AST-based code parsing via tree-sitter
+//! - `languages` - Symbol queries for TypeScript/JavaScript, Python and Go
 //! - `embeddings` - Local embeddings via fastembed (bge-small-en-v1.5)
 //! - `codebase` - Codebase entity CRUD operations
 //! - `insights` - Insight storage and retrieval
+//! - `memories` - Session learnings and their promotion into insights
 //! - `indexer` - Orchestrates the indexing process
-//! - `retrieval` - Semantic search over indexed symbols
+//! - `retrieval` - Keyword, semantic and hybrid search over indexed symbols
+//! - `ann` - HNSW nearest-neighbour graphs for large codebases
+//! - `graph` - Callers, callees, trait impls and module trees
+//! - `summaries` - Cached model-written summaries of public symbols
+//! - `watcher` - Background re-indexing of changed files
 
+pub mod ann;
 pub mod codebase;
 pub mod embeddings;
+pub mod graph;
 pub mod indexer;
 pub mod insights;
+pub mod languages;
+pub mod memories;
 pub mod parser;
 pub mod retrieval;
+pub mod summaries;
+pub mod watcher;
 
+pub use ann::HnswIndex;
 pub use codebase::{Codebase, CodebaseStore};
 pub use embeddings::EmbeddingEngine;
+pub use graph::{Callee, Caller, CodeGraph, ImplBlock, ModuleNode};
 pub use indexer::{IndexPhase, IndexProgress, Indexer};
 pub use insights::{CodebaseInsight, InsightStore, InsightType};
-pub use parser::{ParsedSymbol, RustParser, SymbolType};
-pub use retrieval::{SearchQuery, SearchResult, SemanticRetrieval};
+pub use languages::QueryParser;
+pub use memories::{
+    promote_memories, Learning, PromotionReport, SessionMemory, SessionMemoryStore,
+};
+pub use parser::{
+    parser_for, LanguageParser, ParsedSymbol, RustParser, SourceLanguage, SymbolType,
+};
+pub use retrieval::{RetrievalWeights, SearchMode, SearchQuery, SearchResult, SemanticRetrieval};
+pub use summaries::{summary_model, Summarizer, SummaryPlan, SummaryProgress, SummaryReport};
+pub use watcher::{IndexWatcher, DEFAULT_WATCH_INTERVAL};
@@ -5,6 +5,8 @@ use std::path::Path;
 use streaming_iterator::StreamingIterator;
 use tree_sitter::{Language, Node, Parser, Query, QueryCursor};
 
+use super::languages::QueryParser;
+
 /// Types of symbols we index
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SymbolType {
@@ -18,6 +20,9 @@ pub enum SymbolType {
     Static,
     TypeAlias,
     Macro,
+    Class,
+    Interface,
+    Method,
 }
 
 impl SymbolType {
@@ -33,6 +38,9 @@ impl SymbolType {
             Self::Static => "static",
             Self::TypeAlias => "type_alias",
             Self::Macro => "macro",
+            Self::Class => "class",
+            Self::Interface => "interface",
+            Self::Method => "method",
         }
     }
 
@@ -48,6 +56,9 @@ impl SymbolType {
             "static" => Some(Self::Static),
             "type_alias" => Some(Self::TypeAlias),
             "macro" => Some(Self::Macro),
+            "class" => Some(Self::Class),
+            "interface" => Some(Self::Interface),
+            "method" => Some(Self::Method),
             _ => None,
         }
     }
@@ -62,9 +73,76 @@ pub struct ParsedSymbol {
     pub line_start: usize,
     pub line_end: usize,
     pub signature: Option<String>,
+    /// Doc comment (or Python docstring) attached to the definition
+    pub doc: Option<String>,
+    /// Trait of an `impl Trait for Type` block (last path segment, no generics)
+    pub implements: Option<String>,
     pub calls: Vec<String>,
 }
 
+/// Source languages the indexer understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SourceLanguage {
+    Rust,
+    TypeScript,
+    Tsx,
+    JavaScript,
+    Python,
+    Go,
+}
+
+impl SourceLanguage {
+    pub const ALL: [SourceLanguage; 6] = [
+        Self::Rust,
+        Self::TypeScript,
+        Self::Tsx,
+        Self::JavaScript,
+        Self::Python,
+        Self::Go,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Rust => "rust",
+            Self::TypeScript => "typescript",
+            Self::Tsx => "tsx",
+            Self::JavaScript => "javascript",
+            Self::Python => "python",
+            Self::Go => "go",
+        }
+    }
+
+    /// Detect the language from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "py" | "pyi" => Some(Self::Python),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+}
+
+/// Extracts symbols from one language's source files
+pub trait LanguageParser: Send {
+    /// Parse a source file and extract symbols
+    ///
+    /// `path` is used to derive module paths; pass it relative to the
+    /// codebase root.
+    fn parse_file(&mut self, path: &Path, source: &str) -> Result<Vec<ParsedSymbol>>;
+}
+
+/// Create the parser for a language
+pub fn parser_for(lang: SourceLanguage) -> Result<Box<dyn LanguageParser>> {
+    match lang {
+        SourceLanguage::Rust => Ok(Box::new(RustParser::new()?)),
+        other => Ok(Box::new(QueryParser::new(other)?)),
+    }
+}
+
 /// Rust source code parser using tree-sitter
 pub struct RustParser {
     parser: Parser,
@@ -136,6 +214,7 @@ impl RustParser {
             (enum_item name: (type_identifier) @enum_name) @enum
             (trait_item name: (type_identifier) @trait_name) @trait
             (impl_item type: (type_identifier) @impl_name) @impl
+            (impl_item type: (generic_type type: (type_identifier) @impl_name)) @impl
             (mod_item name: (identifier) @mod_name) @module
             (const_item name: (identifier) @const_name) @const
             (static_item name: (identifier) @static_name) @static
@@ -162,7 +241,13 @@ impl RustParser {
                     continue;
                 }
 
-                let parent = node.parent();
+                // `impl<T> Trait for Type<T>`: the name sits inside a generic type
+                let parent = if capture_name == "impl_name" {
+                    std::iter::successors(node.parent(), |n| n.parent())
+                        .find(|n| n.kind() == "impl_item")
+                } else {
+                    node.parent()
+                };
                 let (symbol_type, signature) = match capture_name {
                     "fn_name" => (
                         SymbolType::Function,
@@ -171,7 +256,10 @@ impl RustParser {
                     "struct_name" => (SymbolType::Struct, None),
                     "enum_name" => (SymbolType::Enum, None),
                     "trait_name" => (SymbolType::Trait, None),
-                    "impl_name" => (SymbolType::Impl, None),
+                    "impl_name" => (
+                        SymbolType::Impl,
+                        parent.map(|p| self.extract_function_signature(p, source)),
+                    ),
                     "mod_name" => (SymbolType::Module, None),
                     "const_name" => (SymbolType::Const, None),
                     "static_name" => (SymbolType::Static, None),
@@ -188,6 +276,12 @@ impl RustParser {
 
                 let parent_node = parent.unwrap_or(node);
                 let calls = self.extract_function_calls(parent_node, source);
+                let doc = doc_comment(parent_node, source);
+                let implements = (symbol_type == SymbolType::Impl)
+                    .then(|| parent_node.child_by_field_name("trait"))
+                    .flatten()
+                    .and_then(|t| t.utf8_text(source.as_bytes()).ok())
+                    .map(trait_name);
 
                 symbols.push(ParsedSymbol {
                     symbol_type,
@@ -196,6 +290,8 @@ impl RustParser {
                     line_start: parent_node.start_position().row + 1,
                     line_end: parent_node.end_position().row + 1,
                     signature,
+                    doc,
+                    implements,
                     calls,
                 });
             }
@@ -239,6 +335,119 @@ impl RustParser {
     }
 }
 
+/// Bare name of a trait reference: `fmt::Display` and `From<T>` give
+/// `Display` and `From`
+pub fn trait_name(reference: &str) -> String {
+    let without_generics = reference.split('<').next().unwrap_or(reference);
+    without_generics
+        .rsplit("::")
+        .next()
+        .unwrap_or(without_generics)
+        .trim()
+        .to_string()
+}
+
+/// Longest doc comment kept for a symbol, in bytes
+const MAX_DOC_LEN: usize = 1000;
+
+/// Nodes that wrap a definition and carry its leading comments
+const DEFINITION_WRAPPERS: &[&str] = &[
+    "export_statement",
+    "decorated_definition",
+    "type_declaration",
+    "const_declaration",
+];
+
+/// Comment block directly above a definition, or its Python docstring
+///
+/// Attributes and decorators between the comment and the definition are
+/// skipped; inner doc comments (`//!`) describe the enclosing module and
+/// are ignored.
+pub(super) fn doc_comment(definition: Node, source: &str) -> Option<String> {
+    let mut node = definition;
+    while let Some(parent) = node.parent() {
+        if !DEFINITION_WRAPPERS.contains(&parent.kind()) {
+            break;
+        }
+        node = parent;
+    }
+
+    let mut lines = Vec::new();
+    let mut next_row = node.start_position().row;
+    let mut sibling = node.prev_named_sibling();
+    while let Some(prev) = sibling {
+        let kind = prev.kind();
+        if kind == "attribute_item" || kind == "decorator" {
+            next_row = prev.start_position().row;
+        } else if kind.contains("comment") && prev.end_position().row + 1 >= next_row {
+            let text = prev.utf8_text(source.as_bytes()).unwrap_or("");
+            if text.starts_with("//!") || text.starts_with("/*!") {
+                break;
+            }
+            lines.push(text);
+            next_row = prev.start_position().row;
+        } else {
+            break;
+        }
+        sibling = prev.prev_named_sibling();
+    }
+    lines.reverse();
+
+    let raw = if lines.is_empty() {
+        docstring(definition, source)?.to_string()
+    } else {
+        lines.join("\n")
+    };
+    let mut doc = clean_comment(&raw);
+    if doc.is_empty() {
+        return None;
+    }
+    if doc.len() > MAX_DOC_LEN {
+        let mut end = MAX_DOC_LEN;
+        while !doc.is_char_boundary(end) {
+            end -= 1;
+        }
+        doc.truncate(end);
+    }
+    Some(doc)
+}
+
+/// Leading string literal of a Python function or class body
+fn docstring<'a>(definition: Node, source: &'a str) -> Option<&'a str> {
+    let body = definition.child_by_field_name("body")?;
+    let first = body.named_child(0)?;
+    if first.kind() != "expression_statement" {
+        return None;
+    }
+    let string = first.named_child(0).filter(|n| n.kind() == "string")?;
+    string.utf8_text(source.as_bytes()).ok()
+}
+
+/// Strip comment and string delimiters, leaving the prose
+fn clean_comment(raw: &str) -> String {
+    let lines: Vec<&str> = raw
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let line = line
+                .trim_start_matches('/')
+                .trim_start_matches('*')
+                .trim_start_matches('#')
+                .trim_end_matches("*/")
+                .trim_matches(|c| c == '"' || c == '\'');
+            line.trim()
+        })
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines.join("\n")
+}
+
+impl LanguageParser for RustParser {
+    fn parse_file(&mut self, path: &Path, source: &str) -> Result<Vec<ParsedSymbol>> {
+        RustParser::parse_file(self, path, source)
+    }
+}
+
 impl Default for RustParser {
     fn default() -> Self {
         Self::new().expect("Failed to create RustParser")
@@ -282,4 +491,82 @@ mod tests {
         assert_eq!(symbols[0].name, "User");
         assert!(symbols[0].full_path.contains("user"));
     }
+
+    #[test]
+    fn test_parse_trait_impls() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+            struct Wrapper<T>(T);
+
+            impl<T> std::fmt::Display for Wrapper<T> {
+                fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                    Ok(())
+                }
+            }
+
+            impl<T> Wrapper<T> {
+                fn new(value: T) -> Self {
+                    Self(value)
+                }
+            }
+        "#;
+        let symbols = parser.parse_file(Path::new("src/wrap.rs"), source).unwrap();
+        let impls: Vec<_> = symbols
+            .iter()
+            .filter(|s| s.symbol_type == SymbolType::Impl)
+            .collect();
+
+        assert_eq!(impls.len(), 2);
+        assert!(impls.iter().all(|s| s.name == "Wrapper"));
+        assert_eq!(impls[0].implements.as_deref(), Some("Display"));
+        assert_eq!(
+            impls[0].signature.as_deref(),
+            Some("impl<T> std::fmt::Display for Wrapper<T>")
+        );
+        assert_eq!(impls[1].implements, None);
+        assert_eq!(trait_name("From<String>"), "From");
+    }
+
+    #[test]
+    fn test_parse_doc_comments() {
+        let mut parser = RustParser::new().unwrap();
+        let source = r#"
+            //! Module docs
+
+            /// Resolve a new path inside the sandbox
+            ///
+            /// Rejects paths that escape the root.
+            #[inline]
+            pub fn sandboxed_resolve_new_path() {}
+
+            // Not attached
+
+            fn undocumented() {}
+        "#;
+        let symbols = parser.parse_file(Path::new("src/lib.rs"), source).unwrap();
+
+        assert_eq!(
+            symbols[0].doc.as_deref(),
+            Some("Resolve a new path inside the sandbox\nRejects paths that escape the root.")
+        );
+        assert_eq!(symbols[1].doc, None);
+    }
+
+    #[test]
+    fn test_language_from_path() {
+        let detect = |p: &str| SourceLanguage::from_path(Path::new(p));
+        assert_eq!(detect("src/main.rs"), Some(SourceLanguage::Rust));
+        assert_eq!(detect("web/App.tsx"), Some(SourceLanguage::Tsx));
+        assert_eq!(detect("index.mjs"), Some(SourceLanguage::JavaScript));
+        assert_eq!(detect("pkg/mod.py"), Some(SourceLanguage::Python));
+        assert_eq!(detect("cmd/main.go"), Some(SourceLanguage::Go));
+        assert_eq!(detect("README.md"), None);
+    }
+
+    #[test]
+    fn test_every_language_has_a_parser() {
+        for lang in SourceLanguage::ALL {
+            assert!(parser_for(lang).is_ok(), "no parser for {}", lang.as_str());
+        }
+    }
 }
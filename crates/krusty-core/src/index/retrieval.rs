@@ -1,8 +1,17 @@
-//! Semantic search over indexed symbols
+//! Symbol search over the codebase index
+//!
+//! Two rankings are available: BM25 over the `codebase_index_fts` keyword
+//! index (symbol names, paths, signatures and doc comments) and embedding
+//! similarity. Hybrid search merges both with reciprocal-rank fusion, so an
+//! exact identifier isn't outranked by symbols that merely sound alike.
+
+use std::collections::{HashMap, HashSet};
 
 use anyhow::Result;
-use rusqlite::{params, Connection};
+use rusqlite::{params, Connection, Row};
+use serde::Deserialize;
 
+use super::ann::{self, HnswIndex, DEFAULT_EF_SEARCH};
 use super::embeddings::EmbeddingEngine;
 use super::parser::SymbolType;
 
@@ -18,6 +27,9 @@ const STOP_WORDS: &[&str] = &[
     "our", "you", "your", "he", "him", "his", "she", "her", "they", "them", "their",
 ];
 
+/// Each ranking fetches this many times the requested limit before fusion
+const CANDIDATE_FACTOR: usize = 3;
+
 fn extract_search_words(query: &str) -> Vec<String> {
     query
         .split(|c: char| !c.is_alphanumeric() && c != '_')
@@ -27,11 +39,85 @@ fn extract_search_words(query: &str) -> Vec<String> {
         .collect()
 }
 
+/// How a text query is matched
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchMode {
+    /// BM25 over names, paths, signatures and doc comments
+    Keyword,
+    /// Embedding similarity (keyword search when no embedding engine is loaded)
+    Semantic,
+    /// Keyword and semantic rankings merged with reciprocal-rank fusion
+    #[default]
+    Hybrid,
+}
+
+impl SearchMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Keyword => "keyword",
+            Self::Semantic => "semantic",
+            Self::Hybrid => "hybrid",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "keyword" => Some(Self::Keyword),
+            "semantic" => Some(Self::Semantic),
+            "hybrid" => Some(Self::Hybrid),
+            _ => None,
+        }
+    }
+}
+
+/// Scoring weights for keyword and hybrid search
+#[derive(Debug, Clone)]
+pub struct RetrievalWeights {
+    /// BM25 weight of symbol name matches
+    pub name: f64,
+    /// BM25 weight of qualified path matches
+    pub path: f64,
+    /// BM25 weight of file path matches
+    pub file: f64,
+    /// BM25 weight of signature matches
+    pub signature: f64,
+    /// BM25 weight of doc comment matches
+    pub doc: f64,
+    /// Weight of the keyword ranking in fusion
+    pub keyword: f32,
+    /// Weight of the semantic ranking in fusion
+    pub semantic: f32,
+    /// Bonus for a symbol named exactly like a query word, as if it ranked
+    /// first in one more list
+    pub exact_name: f32,
+    /// Reciprocal-rank fusion constant; larger values flatten rank differences
+    pub rrf_k: f32,
+}
+
+impl Default for RetrievalWeights {
+    fn default() -> Self {
+        Self {
+            name: 10.0,
+            path: 4.0,
+            file: 2.0,
+            signature: 3.0,
+            doc: 1.0,
+            keyword: 1.0,
+            semantic: 1.0,
+            exact_name: 1.0,
+            rrf_k: 60.0,
+        }
+    }
+}
+
 /// A search query
 #[derive(Debug, Clone, Default)]
 pub struct SearchQuery {
     /// Text query for semantic search
     pub text: Option<String>,
+    /// How the text query is matched
+    pub mode: SearchMode,
     /// Filter by symbol type
     pub symbol_type: Option<SymbolType>,
     /// Filter by file path pattern
@@ -53,6 +139,11 @@ impl SearchQuery {
         self
     }
 
+    pub fn mode(mut self, mode: SearchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
     pub fn symbol_type(mut self, st: SymbolType) -> Self {
         self.symbol_type = Some(st);
         self
@@ -80,6 +171,8 @@ pub struct SearchResult {
     pub line_start: usize,
     pub line_end: usize,
     pub signature: Option<String>,
+    /// One-line description written by the summarisation pass
+    pub summary: Option<String>,
     pub score: f32,
 }
 
@@ -87,6 +180,7 @@ pub struct SearchResult {
 pub struct SemanticRetrieval<'a> {
     conn: &'a Connection,
     embeddings: Option<&'a EmbeddingEngine>,
+    weights: RetrievalWeights,
 }
 
 impl<'a> SemanticRetrieval<'a> {
@@ -94,6 +188,7 @@ impl<'a> SemanticRetrieval<'a> {
         Self {
             conn,
             embeddings: None,
+            weights: RetrievalWeights::default(),
         }
     }
 
@@ -102,17 +197,99 @@ impl<'a> SemanticRetrieval<'a> {
         self
     }
 
+    pub fn with_weights(mut self, weights: RetrievalWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
     /// Search for symbols matching the query
+    ///
+    /// Keyword and hybrid scores are normalized to 0..=1, where 1 means first
+    /// in every ranking and an exact name match; semantic scores are cosine
+    /// similarities.
     pub async fn search(&self, codebase_id: &str, query: SearchQuery) -> Result<Vec<SearchResult>> {
-        // If we have a text query and embeddings, do semantic search
-        if let (Some(text), Some(engine)) = (&query.text, self.embeddings) {
+        let Some(text) = query.text.as_deref().filter(|t| !t.trim().is_empty()) else {
+            return self.filter_search(codebase_id, &query);
+        };
+        let words = extract_search_words(text);
+        let depth = query.limit * CANDIDATE_FACTOR;
+
+        let engine = match query.mode {
+            SearchMode::Keyword => None,
+            SearchMode::Semantic | SearchMode::Hybrid => self.embeddings,
+        };
+        let Some(engine) = engine else {
+            let keyword = self.keyword_search(codebase_id, &words, &query, depth)?;
+            return Ok(self.fuse(vec![(self.weights.keyword, keyword)], &words, query.limit));
+        };
+
+        if query.mode == SearchMode::Semantic {
             return self
-                .semantic_search(codebase_id, text, &query, engine)
+                .semantic_search(codebase_id, text, &query, engine, query.limit)
                 .await;
         }
 
-        // Otherwise do keyword/filter search
-        self.keyword_search(codebase_id, &query)
+        let keyword = self.keyword_search(codebase_id, &words, &query, depth)?;
+        let semantic = self
+            .semantic_search(codebase_id, text, &query, engine, depth)
+            .await?;
+        Ok(self.fuse(
+            vec![
+                (self.weights.keyword, keyword),
+                (self.weights.semantic, semantic),
+            ],
+            &words,
+            query.limit,
+        ))
+    }
+
+    /// Merge rankings with reciprocal-rank fusion
+    ///
+    /// Each list adds `weight / (rrf_k + rank)` to a symbol's score.
+    fn fuse(
+        &self,
+        rankings: Vec<(f32, Vec<SearchResult>)>,
+        words: &[String],
+        limit: usize,
+    ) -> Vec<SearchResult> {
+        let k = self.weights.rrf_k;
+        let mut best = self.weights.exact_name / (k + 1.0);
+        let mut fused: HashMap<i64, SearchResult> = HashMap::new();
+
+        for (weight, results) in rankings {
+            best += weight / (k + 1.0);
+            for (rank, result) in results.into_iter().enumerate() {
+                let contribution = weight / (k + rank as f32 + 1.0);
+                fused
+                    .entry(result.id)
+                    .or_insert(SearchResult {
+                        score: 0.0,
+                        ..result
+                    })
+                    .score += contribution;
+            }
+        }
+
+        let mut results: Vec<SearchResult> = fused.into_values().collect();
+        for result in &mut results {
+            let name = result.symbol_name.to_lowercase();
+            if words.contains(&name) {
+                result.score += self.weights.exact_name / (k + 1.0);
+            }
+            if best > 0.0 {
+                result.score /= best;
+            }
+        }
+
+        results.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.symbol_name.cmp(&b.symbol_name))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        results.truncate(limit);
+        results
     }
 
     /// Semantic search using embeddings
@@ -122,12 +299,18 @@ impl<'a> SemanticRetrieval<'a> {
         text: &str,
         query: &SearchQuery,
         engine: &EmbeddingEngine,
+        limit: usize,
     ) -> Result<Vec<SearchResult>> {
         // Generate query embedding
         let query_embedding = engine.embed(text).await?;
 
+        // Large codebases have an ANN graph; small ones are scored exhaustively
+        if let Some(graph) = ann::open(self.conn, codebase_id) {
+            return self.ann_search(codebase_id, &graph, &query_embedding, query, limit);
+        }
+
         // Load candidate embeddings from database
-        let candidates = self.load_candidates(codebase_id, query)?;
+        let candidates = self.load_candidates(codebase_id, query, limit)?;
 
         if candidates.is_empty() {
             return Ok(Vec::new());
@@ -140,8 +323,7 @@ impl<'a> SemanticRetrieval<'a> {
             .filter_map(|(idx, (_, emb_opt, _))| emb_opt.clone().map(|e| (idx, e)))
             .collect();
 
-        let scored =
-            EmbeddingEngine::top_k_similar(&query_embedding, &candidate_embeddings, query.limit);
+        let scored = EmbeddingEngine::top_k_similar(&query_embedding, &candidate_embeddings, limit);
 
         // Build results
         let mut results = Vec::new();
@@ -156,6 +338,7 @@ impl<'a> SemanticRetrieval<'a> {
                     line_start: meta.line_start,
                     line_end: meta.line_end,
                     signature: meta.signature.clone(),
+                    summary: meta.summary.clone(),
                     score,
                 });
             }
@@ -164,11 +347,91 @@ impl<'a> SemanticRetrieval<'a> {
         Ok(results)
     }
 
+    /// Semantic search through the codebase's ANN graph
+    ///
+    /// Filters apply to the graph's hits, so the search widens until enough
+    /// hits pass them or the graph is exhausted. Each widening only looks up
+    /// hits not seen before, in chunks that stay under SQLite's variable limit.
+    fn ann_search(
+        &self,
+        codebase_id: &str,
+        graph: &HnswIndex,
+        embedding: &[f32],
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        const CHUNK_SIZE: usize = 500;
+        let mut k = limit.max(1);
+        let mut seen = HashSet::new();
+        let mut results = Vec::new();
+        loop {
+            let hits = graph.search(embedding, k, DEFAULT_EF_SEARCH.max(k));
+            let exhausted = hits.len() < k || k >= graph.len();
+            let fresh: Vec<(i64, f32)> = hits
+                .into_iter()
+                .filter(|(id, _)| seen.insert(*id))
+                .collect();
+
+            for chunk in fresh.chunks(CHUNK_SIZE) {
+                results.extend(self.load_hits(codebase_id, chunk, query)?);
+            }
+
+            if results.len() >= limit || exhausted {
+                results.sort_by(|a, b| {
+                    b.score
+                        .partial_cmp(&a.score)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                });
+                results.truncate(limit);
+                return Ok(results);
+            }
+            k = (k * 4).min(graph.len());
+        }
+    }
+
+    /// Symbols for a batch of ANN hits that pass the query's filters
+    fn load_hits(
+        &self,
+        codebase_id: &str,
+        hits: &[(i64, f32)],
+        query: &SearchQuery,
+    ) -> Result<Vec<SearchResult>> {
+        if hits.is_empty() {
+            return Ok(Vec::new());
+        }
+        let scores: HashMap<i64, f32> = hits.iter().copied().collect();
+
+        let mut sql = format!(
+            "SELECT id, symbol_type, symbol_name, symbol_path, file_path,
+                    line_start, line_end, signature, summary
+             FROM codebase_index WHERE codebase_id = ? AND id IN ({})",
+            vec!["?"; hits.len()].join(",")
+        );
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(codebase_id.to_string())];
+        params_vec.extend(
+            hits.iter()
+                .map(|(id, _)| Box::new(*id) as Box<dyn rusqlite::ToSql>),
+        );
+        push_filters(&mut sql, &mut params_vec, query, "");
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|b| b.as_ref()).collect();
+        let results = stmt
+            .query_map(params_refs.as_slice(), |row| {
+                let id: i64 = row.get(0)?;
+                read_symbol(row, scores.get(&id).copied().unwrap_or(0.0))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
+    }
+
     /// Load candidate symbols with embeddings (chunked for scalability)
     fn load_candidates(
         &self,
         codebase_id: &str,
         query: &SearchQuery,
+        limit: usize,
     ) -> Result<Vec<SearchCandidate>> {
         const CHUNK_SIZE: i64 = 500;
         let mut all_candidates = Vec::new();
@@ -177,7 +440,7 @@ impl<'a> SemanticRetrieval<'a> {
         loop {
             let mut sql = String::from(
                 "SELECT id, symbol_type, symbol_name, symbol_path, file_path,
-                        line_start, line_end, signature, embedding
+                        line_start, line_end, signature, summary, embedding
                  FROM codebase_index WHERE codebase_id = ?1 AND embedding IS NOT NULL",
             );
 
@@ -212,7 +475,8 @@ impl<'a> SemanticRetrieval<'a> {
                     let line_start: i64 = row.get(5)?;
                     let line_end: i64 = row.get(6)?;
                     let signature: Option<String> = row.get(7)?;
-                    let embedding_blob: Option<Vec<u8>> = row.get(8)?;
+                    let summary: Option<String> = row.get(8)?;
+                    let embedding_blob: Option<Vec<u8>> = row.get(9)?;
 
                     // Parse embedding blob with validation
                     let embedding_opt = match embedding_blob {
@@ -234,6 +498,7 @@ impl<'a> SemanticRetrieval<'a> {
                             line_start: line_start as usize,
                             line_end: line_end as usize,
                             signature,
+                            summary,
                         },
                     ))
                 })?
@@ -247,7 +512,7 @@ impl<'a> SemanticRetrieval<'a> {
             offset += CHUNK_SIZE;
 
             // Stop if we have enough candidates (2x requested limit for better results)
-            if query.limit > 0 && all_candidates.len() >= query.limit * 2 {
+            if limit > 0 && all_candidates.len() >= limit * 2 {
                 break;
             }
         }
@@ -255,143 +520,64 @@ impl<'a> SemanticRetrieval<'a> {
         Ok(all_candidates)
     }
 
-    /// Keyword search with per-word OR matching and relevance ranking
-    fn keyword_search(&self, codebase_id: &str, query: &SearchQuery) -> Result<Vec<SearchResult>> {
-        let words = query
-            .text
-            .as_ref()
-            .map(|t| extract_search_words(t))
-            .unwrap_or_default();
+    /// BM25 search over the keyword index, best matches first
+    fn keyword_search(
+        &self,
+        codebase_id: &str,
+        words: &[String],
+        query: &SearchQuery,
+        limit: usize,
+    ) -> Result<Vec<SearchResult>> {
+        let Some(fts_query) = fts_query(words) else {
+            return self.filter_search(codebase_id, query);
+        };
+
+        let w = &self.weights;
+        let mut sql = format!(
+            "SELECT c.id, c.symbol_type, c.symbol_name, c.symbol_path, c.file_path,
+                    c.line_start, c.line_end, c.signature, c.summary,
+                    bm25(codebase_index_fts, {}, {}, {}, {}, {})
+             FROM codebase_index_fts
+             JOIN codebase_index c ON c.id = codebase_index_fts.rowid
+             WHERE codebase_index_fts MATCH ?1 AND c.codebase_id = ?2",
+            w.name, w.path, w.file, w.signature, w.doc
+        );
+        let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> =
+            vec![Box::new(fts_query), Box::new(codebase_id.to_string())];
+        push_filters(&mut sql, &mut params_vec, query, "c.");
+        sql.push_str(&format!(" ORDER BY 10 LIMIT {limit}"));
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            params_vec.iter().map(|b| b.as_ref()).collect();
+        let results = stmt
+            .query_map(params_refs.as_slice(), |row| {
+                // bm25() is lower for better matches
+                let bm25: f64 = row.get(9)?;
+                read_symbol(row, -bm25 as f32)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
 
-        // Build SQL with per-word OR clauses for better matching
+    /// Symbols matching only the type and file filters, by name
+    fn filter_search(&self, codebase_id: &str, query: &SearchQuery) -> Result<Vec<SearchResult>> {
         let mut sql = String::from(
             "SELECT id, symbol_type, symbol_name, symbol_path, file_path,
-                    line_start, line_end, signature
+                    line_start, line_end, signature, summary
              FROM codebase_index WHERE codebase_id = ?1",
         );
-
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(codebase_id.to_string())];
-
-        if !words.is_empty() {
-            let word_clauses: Vec<String> = words
-                .iter()
-                .map(|_| {
-                    let idx = params_vec.len() + 1;
-                    let clause = format!("(symbol_name LIKE ?{idx} OR symbol_path LIKE ?{idx} OR signature LIKE ?{idx})");
-                    clause
-                })
-                .collect();
-
-            // Collect patterns after building clauses to avoid double borrow
-            let patterns: Vec<String> = words.iter().map(|w| format!("%{w}%")).collect();
-            for pattern in patterns {
-                params_vec.push(Box::new(pattern));
-            }
-
-            sql.push_str(&format!(" AND ({})", word_clauses.join(" OR ")));
-        }
-
-        if let Some(st) = &query.symbol_type {
-            sql.push_str(" AND symbol_type = ?");
-            params_vec.push(Box::new(st.as_str().to_string()));
-        }
-
-        if let Some(pattern) = &query.file_pattern {
-            sql.push_str(" AND file_path LIKE ?");
-            params_vec.push(Box::new(format!("%{pattern}%")));
-        }
-
-        // Fetch more than limit to allow re-ranking
-        let fetch_limit = if words.len() > 1 {
-            query.limit * 5
-        } else {
-            query.limit
-        };
-        sql.push_str(&format!(" LIMIT {fetch_limit}"));
+        push_filters(&mut sql, &mut params_vec, query, "");
+        sql.push_str(&format!(" ORDER BY symbol_name LIMIT {}", query.limit));
 
         let mut stmt = self.conn.prepare(&sql)?;
         let params_refs: Vec<&dyn rusqlite::ToSql> =
             params_vec.iter().map(|b| b.as_ref()).collect();
-
-        let rows = stmt.query_map(params_refs.as_slice(), |row| {
-            let id: i64 = row.get(0)?;
-            let symbol_type_str: String = row.get(1)?;
-            let symbol_name: String = row.get(2)?;
-            let symbol_path: String = row.get(3)?;
-            let file_path: String = row.get(4)?;
-            let line_start: i64 = row.get(5)?;
-            let line_end: i64 = row.get(6)?;
-            let signature: Option<String> = row.get(7)?;
-
-            Ok((
-                id,
-                symbol_type_str,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start,
-                line_end,
-                signature,
-            ))
-        })?;
-
-        let mut results = Vec::new();
-        for row in rows {
-            let (
-                id,
-                symbol_type_str,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start,
-                line_end,
-                signature,
-            ) = row?;
-
-            let symbol_type = SymbolType::parse(&symbol_type_str).unwrap_or(SymbolType::Function);
-
-            // Score by how many query words match (name, path, or signature)
-            let score = if words.is_empty() {
-                1.0
-            } else {
-                let name_lower = symbol_name.to_lowercase();
-                let path_lower = symbol_path.to_lowercase();
-                let sig_lower = signature
-                    .as_deref()
-                    .map(|s| s.to_lowercase())
-                    .unwrap_or_default();
-                let matches = words
-                    .iter()
-                    .filter(|w| {
-                        name_lower.contains(w.as_str())
-                            || path_lower.contains(w.as_str())
-                            || sig_lower.contains(w.as_str())
-                    })
-                    .count();
-                matches as f32 / words.len() as f32
-            };
-
-            results.push(SearchResult {
-                id,
-                symbol_type,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start: line_start as usize,
-                line_end: line_end as usize,
-                signature,
-                score,
-            });
-        }
-
-        // Sort by match count descending, then by name
-        results.sort_by(|a, b| {
-            b.score
-                .partial_cmp(&a.score)
-                .unwrap_or(std::cmp::Ordering::Equal)
-                .then_with(|| a.symbol_name.cmp(&b.symbol_name))
-        });
-        results.truncate(query.limit);
+        let results = stmt
+            .query_map(params_refs.as_slice(), |row| read_symbol(row, 1.0))?
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(results)
     }
@@ -400,58 +586,12 @@ impl<'a> SemanticRetrieval<'a> {
     pub fn get_symbol(&self, symbol_id: i64) -> Result<Option<SearchResult>> {
         let mut stmt = self.conn.prepare(
             "SELECT id, symbol_type, symbol_name, symbol_path, file_path,
-                    line_start, line_end, signature
+                    line_start, line_end, signature, summary
              FROM codebase_index WHERE id = ?1",
         )?;
 
-        let result = stmt.query_row([symbol_id], |row| {
-            let id: i64 = row.get(0)?;
-            let symbol_type_str: String = row.get(1)?;
-            let symbol_name: String = row.get(2)?;
-            let symbol_path: String = row.get(3)?;
-            let file_path: String = row.get(4)?;
-            let line_start: i64 = row.get(5)?;
-            let line_end: i64 = row.get(6)?;
-            let signature: Option<String> = row.get(7)?;
-
-            Ok((
-                id,
-                symbol_type_str,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start,
-                line_end,
-                signature,
-            ))
-        });
-
-        match result {
-            Ok((
-                id,
-                symbol_type_str,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start,
-                line_end,
-                signature,
-            )) => {
-                let symbol_type =
-                    SymbolType::parse(&symbol_type_str).unwrap_or(SymbolType::Function);
-
-                Ok(Some(SearchResult {
-                    id,
-                    symbol_type,
-                    symbol_name,
-                    symbol_path,
-                    file_path,
-                    line_start: line_start as usize,
-                    line_end: line_end as usize,
-                    signature,
-                    score: 1.0,
-                }))
-            }
+        match stmt.query_row([symbol_id], |row| read_symbol(row, 1.0)) {
+            Ok(result) => Ok(Some(result)),
             Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
             Err(e) => Err(e.into()),
         }
@@ -459,64 +599,18 @@ impl<'a> SemanticRetrieval<'a> {
 
     /// Find symbols that call a given symbol
     pub fn find_callers(&self, codebase_id: &str, symbol_name: &str) -> Result<Vec<SearchResult>> {
-        let pattern = format!("%\"{}%", symbol_name);
+        // `calls` is a JSON array of names; match the whole quoted name
+        let pattern = format!("%\"{}\"%", symbol_name);
 
         let mut stmt = self.conn.prepare(
             "SELECT id, symbol_type, symbol_name, symbol_path, file_path,
-                    line_start, line_end, signature
+                    line_start, line_end, signature, summary
              FROM codebase_index WHERE codebase_id = ?1 AND calls LIKE ?2",
         )?;
 
-        let rows = stmt.query_map(params![codebase_id, pattern], |row| {
-            let id: i64 = row.get(0)?;
-            let symbol_type_str: String = row.get(1)?;
-            let symbol_name: String = row.get(2)?;
-            let symbol_path: String = row.get(3)?;
-            let file_path: String = row.get(4)?;
-            let line_start: i64 = row.get(5)?;
-            let line_end: i64 = row.get(6)?;
-            let signature: Option<String> = row.get(7)?;
-
-            Ok((
-                id,
-                symbol_type_str,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start,
-                line_end,
-                signature,
-            ))
-        })?;
-
-        let mut results = Vec::new();
-        for row in rows {
-            let (
-                id,
-                symbol_type_str,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start,
-                line_end,
-                signature,
-            ) = row?;
-
-            let symbol_type = SymbolType::parse(&symbol_type_str).unwrap_or(SymbolType::Function);
-
-            results.push(SearchResult {
-                id,
-                symbol_type,
-                symbol_name,
-                symbol_path,
-                file_path,
-                line_start: line_start as usize,
-                line_end: line_end as usize,
-                signature,
-                score: 1.0,
-            });
-        }
-
+        let results = stmt
+            .query_map(params![codebase_id, pattern], |row| read_symbol(row, 1.0))?
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(results)
     }
 }
@@ -531,4 +625,266 @@ struct SymbolMeta {
     line_start: usize,
     line_end: usize,
     signature: Option<String>,
+    summary: Option<String>,
+}
+
+/// Append the symbol type and file pattern filters of `query`
+fn push_filters(
+    sql: &mut String,
+    params_vec: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    query: &SearchQuery,
+    table: &str,
+) {
+    if let Some(st) = &query.symbol_type {
+        sql.push_str(&format!(" AND {table}symbol_type = ?"));
+        params_vec.push(Box::new(st.as_str().to_string()));
+    }
+
+    if let Some(pattern) = &query.file_pattern {
+        sql.push_str(&format!(" AND {table}file_path LIKE ?"));
+        params_vec.push(Box::new(format!("%{pattern}%")));
+    }
+}
+
+/// Read the leading `id, symbol_type, ..., signature` columns of a row
+pub(super) fn read_symbol(row: &Row, score: f32) -> rusqlite::Result<SearchResult> {
+    let symbol_type: String = row.get(1)?;
+    let line_start: i64 = row.get(5)?;
+    let line_end: i64 = row.get(6)?;
+    Ok(SearchResult {
+        id: row.get(0)?,
+        symbol_type: SymbolType::parse(&symbol_type).unwrap_or(SymbolType::Function),
+        symbol_name: row.get(2)?,
+        symbol_path: row.get(3)?,
+        file_path: row.get(4)?,
+        line_start: line_start as usize,
+        line_end: line_end as usize,
+        signature: row.get(7)?,
+        summary: row.get(8)?,
+        score,
+    })
+}
+
+/// FTS5 query matching any of the words, each also as a prefix
+fn fts_query(words: &[String]) -> Option<String> {
+    if words.is_empty() {
+        return None;
+    }
+    let terms: Vec<String> = words
+        .iter()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect();
+    Some(terms.join(" OR "))
+}
+
+/// Split an identifier at underscores and case changes
+///
+/// `sandboxed_resolve_new_path` gives `sandboxed resolve new path`,
+/// `HTTPServerError` gives `HTTP Server Error`.
+fn identifier_words(ident: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = ident.char_indices().collect();
+    let mut words = Vec::new();
+    let mut start: Option<usize> = None;
+
+    for (i, &(pos, c)) in chars.iter().enumerate() {
+        if c == '_' {
+            if let Some(s) = start.take() {
+                words.push(&ident[s..pos]);
+            }
+            continue;
+        }
+        if let Some(s) = start {
+            let prev = chars[i - 1].1;
+            let next_lower = chars.get(i + 1).is_some_and(|&(_, n)| n.is_lowercase());
+            let boundary =
+                c.is_uppercase() && (prev.is_lowercase() || prev.is_ascii_digit() || next_lower);
+            if boundary {
+                words.push(&ident[s..pos]);
+                start = Some(pos);
+            }
+        } else {
+            start = Some(pos);
+        }
+    }
+    if let Some(s) = start {
+        words.push(&ident[s..]);
+    }
+    words
+}
+
+/// Text followed by the words of every compound identifier in it
+///
+/// The index keeps whole identifiers as tokens (so exact names match
+/// precisely) and also their parts (so `resolve path` finds them).
+fn searchable_text(text: &str) -> String {
+    let mut out = text.to_string();
+    for token in text.split(|c: char| !c.is_alphanumeric() && c != '_') {
+        let words = identifier_words(token);
+        if words.len() > 1 {
+            out.push(' ');
+            out.push_str(&words.join(" "));
+        }
+    }
+    out
+}
+
+/// Indexed text of one symbol
+pub(crate) struct SymbolText<'a> {
+    pub name: &'a str,
+    pub path: &'a str,
+    pub file: &'a str,
+    pub signature: Option<&'a str>,
+    pub doc: Option<&'a str>,
+}
+
+/// Add a symbol row to the keyword index
+pub(crate) fn index_symbol_text(
+    conn: &Connection,
+    symbol_id: i64,
+    text: &SymbolText,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO codebase_index_fts (rowid, symbol_name, symbol_path, file_path, signature, doc)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            symbol_id,
+            searchable_text(text.name),
+            searchable_text(text.path),
+            searchable_text(text.file),
+            text.signature.map(searchable_text),
+            text.doc.map(searchable_text),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Index every symbol stored before the keyword index existed
+pub(crate) fn index_existing_symbols(conn: &Connection) -> Result<usize> {
+    let mut stmt = conn.prepare(
+        "SELECT id, symbol_name, symbol_path, file_path, signature, doc
+         FROM codebase_index ORDER BY id",
+    )?;
+    type Row = (i64, String, String, String, Option<String>, Option<String>);
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+            ))
+        })?
+        .collect::<Result<Vec<Row>, _>>()?;
+    for (id, name, path, file, signature, doc) in &rows {
+        let text = SymbolText {
+            name,
+            path,
+            file,
+            signature: signature.as_deref(),
+            doc: doc.as_deref(),
+        };
+        index_symbol_text(conn, *id, &text)?;
+    }
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::index::{CodebaseStore, Indexer};
+    use crate::storage::Database;
+
+    #[test]
+    fn test_identifier_words() {
+        assert_eq!(
+            identifier_words("sandboxed_resolve_new_path"),
+            vec!["sandboxed", "resolve", "new", "path"]
+        );
+        assert_eq!(
+            identifier_words("HTTPServerError"),
+            vec!["HTTP", "Server", "Error"]
+        );
+        assert_eq!(identifier_words("parse2Json"), vec!["parse2", "Json"]);
+        assert_eq!(identifier_words("plain"), vec!["plain"]);
+    }
+
+    #[test]
+    fn test_fts_query() {
+        assert_eq!(fts_query(&[]), None);
+        let words = extract_search_words("the resolve_path in sandbox");
+        assert_eq!(
+            fts_query(&words).as_deref(),
+            Some("\"resolve_path\"* OR \"sandbox\"*")
+        );
+    }
+
+    #[test]
+    fn test_keyword_search_ranks_exact_identifier_first() {
+        let project = tempfile::Builder::new()
+            .prefix("krusty-retrieval")
+            .tempdir()
+            .unwrap();
+        let db_dir = TempDir::new().unwrap();
+        let db = Database::new(&db_dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+
+        std::fs::write(
+            project.path().join("sandbox.rs"),
+            r#"
+/// Resolve a path that may not exist yet inside the sandbox
+fn sandboxed_resolve_new_path() {}
+
+/// Resolve an existing path inside the sandbox
+fn sandboxed_resolve_path() {}
+
+fn resolve_new_sandboxed_config_path_for_tests() {}
+
+/// Load user settings
+fn load_settings() {}
+"#,
+        )
+        .unwrap();
+
+        let mut indexer = Indexer::new().unwrap();
+        indexer
+            .index_codebase_sync(conn, project.path(), None)
+            .unwrap();
+        let codebase_id = CodebaseStore::new(conn)
+            .get_by_path(&project.path().to_string_lossy())
+            .unwrap()
+            .unwrap()
+            .id;
+
+        let retrieval = SemanticRetrieval::new(conn);
+        let search = |text: &str, mode: SearchMode| {
+            futures::executor::block_on(
+                retrieval.search(&codebase_id, SearchQuery::new().text(text).mode(mode)),
+            )
+            .unwrap()
+        };
+
+        let results = search("sandboxed_resolve_new_path", SearchMode::Keyword);
+        assert_eq!(results[0].symbol_name, "sandboxed_resolve_new_path");
+        assert!((results[0].score - 1.0).abs() < 1e-6);
+        assert!(results.iter().all(|r| r.symbol_name != "load_settings"));
+
+        // Split identifier words and doc comments are searchable
+        let results = search("resolve path", SearchMode::Keyword);
+        assert!(results.len() >= 3);
+        let results = search("user settings", SearchMode::Hybrid);
+        assert_eq!(results[0].symbol_name, "load_settings");
+
+        // Rows leave the keyword index with their symbols
+        conn.execute("DELETE FROM codebase_index", []).unwrap();
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM codebase_index_fts", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
 }
@@ -0,0 +1,773 @@
+//! Summaries - Model-written descriptions of public symbols
+//!
+//! An optional pass asks a cheap model for a one-to-two sentence description
+//! of each public function, type and module. Summaries are cached in
+//! `symbol_summaries` by a hash of the symbol's source, so re-indexing an
+//! unchanged symbol reuses its summary without another request. Summaries
+//! are part of the embedding text and are returned by `search_codebase`.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use chrono::Utc;
+use futures::future::join_all;
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::ann;
+use super::codebase::CodebaseStore;
+use super::embeddings::EmbeddingEngine;
+use super::indexer::embedding_text;
+use super::parser::{ParsedSymbol, SourceLanguage, SymbolType};
+use crate::agent::constants::models::HAIKU_4_5;
+use crate::ai::client::AiClient;
+use crate::ai::cost::ModelPricing;
+use crate::ai::providers::{translate_model_id, ProviderId};
+use crate::ai::types::Usage;
+
+/// Output token cap per summary
+const SUMMARY_MAX_TOKENS: usize = 120;
+/// Output tokens expected per summary, for estimates
+const EXPECTED_OUTPUT_TOKENS: usize = 50;
+/// Longest source excerpt sent to the model, in bytes
+const MAX_SOURCE_LEN: usize = 6000;
+/// Longest summary kept, in bytes
+const MAX_SUMMARY_LEN: usize = 400;
+/// Rough bytes per token, for estimates
+const BYTES_PER_TOKEN: usize = 4;
+/// Summary requests in flight at once
+const CONCURRENCY: usize = 4;
+
+const SUMMARY_SYSTEM_PROMPT: &str = "\
+Describe the given code for a code search index in one or two sentences.
+
+Rules:
+- Output ONLY the description, nothing else
+- Say what it does and what it is for, not how it is written
+- Don't repeat the name or signature
+- No markdown, quotes or lists";
+
+/// Model used for summaries: Claude Haiku where the provider offers it,
+/// otherwise the current model
+pub fn summary_model(provider: ProviderId, current_model: &str) -> String {
+    translate_model_id(HAIKU_4_5, ProviderId::Anthropic, provider)
+        .unwrap_or_else(|| current_model.to_string())
+}
+
+/// Cache key for a symbol's summary, or `None` if the symbol doesn't get one
+///
+/// Only public functions, types and modules are summarised.
+pub(super) fn summary_hash(
+    language: SourceLanguage,
+    path: &Path,
+    source: &str,
+    symbol: &ParsedSymbol,
+) -> Option<String> {
+    let kind = matches!(
+        symbol.symbol_type,
+        SymbolType::Function
+            | SymbolType::Method
+            | SymbolType::Struct
+            | SymbolType::Enum
+            | SymbolType::Trait
+            | SymbolType::Class
+            | SymbolType::Interface
+            | SymbolType::TypeAlias
+            | SymbolType::Module
+    );
+    let first_line = source
+        .lines()
+        .nth(symbol.line_start.saturating_sub(1))
+        .unwrap_or("")
+        .trim_start();
+    if !kind || !is_public(language, symbol, first_line) {
+        return None;
+    }
+    let excerpt = symbol_source(language, path, source, symbol);
+    Some(hash_excerpt(symbol.symbol_type, &excerpt))
+}
+
+/// Summary cached for a source hash
+pub(super) fn cached_summary(conn: &Connection, hash: &str) -> Result<Option<String>> {
+    Ok(conn
+        .query_row(
+            "SELECT summary FROM symbol_summaries WHERE hash = ?1",
+            [hash],
+            |row| row.get(0),
+        )
+        .optional()?)
+}
+
+/// Whether a definition is exported from its module
+fn is_public(language: SourceLanguage, symbol: &ParsedSymbol, first_line: &str) -> bool {
+    match language {
+        SourceLanguage::Rust => first_line.starts_with("pub "),
+        SourceLanguage::Python => !symbol.name.starts_with('_'),
+        SourceLanguage::Go => symbol.name.starts_with(|c: char| c.is_uppercase()),
+        SourceLanguage::TypeScript | SourceLanguage::Tsx | SourceLanguage::JavaScript => {
+            match symbol.symbol_type {
+                SymbolType::Method => {
+                    !(first_line.starts_with("private")
+                        || first_line.starts_with("protected")
+                        || symbol.name.starts_with('#'))
+                }
+                _ => first_line.starts_with("export "),
+            }
+        }
+    }
+}
+
+/// Source lines of a symbol; a Rust `mod name;` declaration brings the
+/// head of its module file instead of a single line
+fn symbol_source(
+    language: SourceLanguage,
+    path: &Path,
+    source: &str,
+    symbol: &ParsedSymbol,
+) -> String {
+    let mut excerpt = source
+        .lines()
+        .skip(symbol.line_start.saturating_sub(1))
+        .take(symbol.line_end.saturating_sub(symbol.line_start) + 1)
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    if language == SourceLanguage::Rust
+        && symbol.symbol_type == SymbolType::Module
+        && excerpt.trim_end().ends_with(';')
+    {
+        if let Some(module) =
+            module_file(path, &symbol.name).and_then(|file| std::fs::read_to_string(file).ok())
+        {
+            excerpt.push_str("\n\n");
+            excerpt.push_str(truncate(&module, MAX_SOURCE_LEN));
+        }
+    }
+    excerpt
+}
+
+/// File defining the Rust module `name` declared in `path`
+fn module_file(path: &Path, name: &str) -> Option<PathBuf> {
+    let dir = path.parent()?;
+    let stem = path.file_stem()?.to_str()?;
+    let base = if matches!(stem, "mod" | "lib" | "main") {
+        dir.to_path_buf()
+    } else {
+        dir.join(stem)
+    };
+    [
+        base.join(format!("{}.rs", name)),
+        base.join(name).join("mod.rs"),
+    ]
+    .into_iter()
+    .find(|file| file.is_file())
+}
+
+fn hash_excerpt(symbol_type: SymbolType, excerpt: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(symbol_type.as_str());
+    hasher.update([0]);
+    hasher.update(excerpt);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Longest prefix of `text` within `max` bytes, on a char boundary
+fn truncate(text: &str, max: usize) -> &str {
+    if text.len() <= max {
+        return text;
+    }
+    let mut end = max;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Collapse a model response to one line, or `None` if it's empty
+fn clean_summary(text: &str) -> Option<String> {
+    let summary = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let summary = summary.trim_matches(|c| c == '"' || c == '`').trim();
+    if summary.is_empty() {
+        return None;
+    }
+    Some(truncate(summary, MAX_SUMMARY_LEN).to_string())
+}
+
+fn usage(input_tokens: usize, output_tokens: usize) -> Usage {
+    Usage {
+        prompt_tokens: input_tokens,
+        completion_tokens: output_tokens,
+        total_tokens: input_tokens + output_tokens,
+        ..Default::default()
+    }
+}
+
+/// One summary to write, shared by every symbol with the same source hash
+#[derive(Debug, Clone)]
+pub struct SummaryJob {
+    pub hash: String,
+    pub symbol_path: String,
+    prompt: String,
+    input_tokens: usize,
+}
+
+/// Summaries still missing for a codebase, with their expected token use
+#[derive(Debug, Clone, Default)]
+pub struct SummaryPlan {
+    pub codebase_id: String,
+    pub jobs: Vec<SummaryJob>,
+    /// Public symbols that already have a summary
+    pub summarized: usize,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+}
+
+impl SummaryPlan {
+    /// Collect the public symbols of a codebase that have no summary yet
+    ///
+    /// Symbols whose file changed since it was indexed are left for the
+    /// next re-index.
+    pub fn build(conn: &Connection, codebase_id: &str) -> Result<Self> {
+        let root = CodebaseStore::new(conn)
+            .get_by_id(codebase_id)?
+            .map(|codebase| PathBuf::from(codebase.path))
+            .unwrap_or_default();
+
+        let summarized: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM codebase_index
+             WHERE codebase_id = ?1 AND summary_hash IS NOT NULL AND summary IS NOT NULL",
+            [codebase_id],
+            |row| row.get(0),
+        )?;
+        let mut plan = Self {
+            codebase_id: codebase_id.to_string(),
+            summarized: summarized as usize,
+            ..Default::default()
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT summary_hash, symbol_type, symbol_name, symbol_path, file_path,
+                    line_start, line_end
+             FROM codebase_index
+             WHERE codebase_id = ?1 AND summary_hash IS NOT NULL AND summary IS NULL
+             ORDER BY file_path, line_start",
+        )?;
+        let rows = stmt.query_map([codebase_id], |row| {
+            let symbol_type: String = row.get(1)?;
+            let line_start: i64 = row.get(5)?;
+            let line_end: i64 = row.get(6)?;
+            let symbol = ParsedSymbol {
+                symbol_type: SymbolType::parse(&symbol_type).unwrap_or(SymbolType::Function),
+                name: row.get(2)?,
+                full_path: row.get(3)?,
+                line_start: line_start as usize,
+                line_end: line_end as usize,
+                signature: None,
+                doc: None,
+                implements: None,
+                calls: Vec::new(),
+            };
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(4)?, symbol))
+        })?;
+
+        // Rows come grouped by file, so only the current file is kept in memory
+        let mut current: Option<(String, Option<String>)> = None;
+        let mut seen = HashSet::new();
+        for row in rows {
+            let (hash, file_path, symbol) = row?;
+            if !seen.insert(hash.clone()) {
+                continue;
+            }
+            let path = Path::new(&file_path);
+            let Some(language) = SourceLanguage::from_path(path) else {
+                continue;
+            };
+            if current.as_ref().is_none_or(|(file, _)| *file != file_path) {
+                current = Some((file_path.clone(), std::fs::read_to_string(path).ok()));
+            }
+            let Some(source) = current.as_ref().and_then(|(_, source)| source.as_deref()) else {
+                continue;
+            };
+
+            let excerpt = symbol_source(language, path, source, &symbol);
+            if hash_excerpt(symbol.symbol_type, &excerpt) != hash {
+                continue;
+            }
+            let relative = path.strip_prefix(&root).unwrap_or(path);
+            let prompt = format!(
+                "{} `{}` in {}:\n\n{}",
+                symbol.symbol_type.as_str(),
+                symbol.full_path,
+                relative.display(),
+                truncate(&excerpt, MAX_SOURCE_LEN)
+            );
+            let input_tokens =
+                (SUMMARY_SYSTEM_PROMPT.len() + prompt.len()).div_ceil(BYTES_PER_TOKEN);
+            plan.input_tokens += input_tokens;
+            plan.output_tokens += EXPECTED_OUTPUT_TOKENS;
+            plan.jobs.push(SummaryJob {
+                hash,
+                symbol_path: symbol.full_path,
+                prompt,
+                input_tokens,
+            });
+        }
+
+        Ok(plan)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Expected cost of the whole plan in USD
+    pub fn estimated_cost(&self, pricing: &ModelPricing) -> f64 {
+        pricing.cost(&usage(self.input_tokens, self.output_tokens))
+    }
+}
+
+/// Progress of a summarisation run
+#[derive(Debug, Clone)]
+pub struct SummaryProgress {
+    pub done: usize,
+    pub total: usize,
+    /// Estimated spend so far in USD
+    pub spent: f64,
+}
+
+/// Outcome of a summarisation run
+#[derive(Debug, Clone, Default)]
+pub struct SummaryReport {
+    pub written: usize,
+    pub failed: usize,
+    /// Jobs not attempted
+    pub remaining: usize,
+    /// Estimated spend in USD (0 when the model's pricing is unknown)
+    pub spent: f64,
+    pub budget_reached: bool,
+    pub cancelled: bool,
+    /// Error that stopped the run early
+    pub error: Option<String>,
+}
+
+/// Writes summaries for a [`SummaryPlan`]
+///
+/// Spend is estimated from request sizes, as simple calls don't report
+/// usage back. The run stops before a batch would cross the budget.
+pub struct Summarizer {
+    client: Arc<AiClient>,
+    model: String,
+    pricing: Option<ModelPricing>,
+    budget: Option<f64>,
+    embeddings: Option<EmbeddingEngine>,
+    cancellation: CancellationToken,
+}
+
+impl Summarizer {
+    pub fn new(client: Arc<AiClient>, model: impl Into<String>) -> Self {
+        Self {
+            client,
+            model: model.into(),
+            pricing: None,
+            budget: None,
+            embeddings: None,
+            cancellation: CancellationToken::new(),
+        }
+    }
+
+    /// Price requests with the model's pricing (the budget needs this)
+    pub fn with_pricing(mut self, pricing: Option<ModelPricing>) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
+    /// Stop before the estimated spend would exceed `budget` USD
+    pub fn with_budget(mut self, budget: f64) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Re-embed symbols as their summaries are written
+    pub fn with_embeddings(mut self, engine: EmbeddingEngine) -> Self {
+        self.embeddings = Some(engine);
+        self
+    }
+
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Summarise every job in `plan`, storing results as they arrive
+    pub async fn run(
+        &self,
+        conn: &Connection,
+        plan: &SummaryPlan,
+        progress_tx: Option<mpsc::UnboundedSender<SummaryProgress>>,
+    ) -> Result<SummaryReport> {
+        let total = plan.jobs.len();
+        let mut report = SummaryReport::default();
+        let mut attempted = 0;
+        let mut changed = Vec::new();
+
+        for chunk in plan.jobs.chunks(CONCURRENCY) {
+            if self.cancellation.is_cancelled() {
+                report.cancelled = true;
+                break;
+            }
+            if let (Some(budget), Some(pricing)) = (self.budget, self.pricing) {
+                let next: f64 = chunk
+                    .iter()
+                    .map(|job| pricing.cost(&usage(job.input_tokens, EXPECTED_OUTPUT_TOKENS)))
+                    .sum();
+                if report.spent + next > budget {
+                    report.budget_reached = true;
+                    break;
+                }
+            }
+
+            let responses = join_all(chunk.iter().map(|job| {
+                self.client.call_simple(
+                    &self.model,
+                    SUMMARY_SYSTEM_PROMPT,
+                    &job.prompt,
+                    SUMMARY_MAX_TOKENS,
+                )
+            }))
+            .await;
+            attempted += chunk.len();
+
+            let mut written = Vec::new();
+            let mut last_error = None;
+            for (job, response) in chunk.iter().zip(responses) {
+                let text = match response {
+                    Ok(text) => text,
+                    Err(e) => {
+                        warn!(symbol = %job.symbol_path, error = %e, "Summary request failed");
+                        report.failed += 1;
+                        last_error = Some(e.to_string());
+                        continue;
+                    }
+                };
+                if let Some(pricing) = self.pricing {
+                    let output_tokens = text.len().div_ceil(BYTES_PER_TOKEN);
+                    report.spent += pricing.cost(&usage(job.input_tokens, output_tokens));
+                }
+                match clean_summary(&text) {
+                    Some(summary) => {
+                        store_summary(conn, &plan.codebase_id, &job.hash, &summary, &self.model)?;
+                        written.push(job.hash.as_str());
+                        report.written += 1;
+                    }
+                    None => report.failed += 1,
+                }
+            }
+
+            match self.reembed(conn, &plan.codebase_id, &written).await {
+                Ok(ids) => changed.extend(ids),
+                Err(e) => warn!(error = %e, "Failed to re-embed summarised symbols"),
+            }
+
+            if let Some(ref tx) = progress_tx {
+                let _ = tx.send(SummaryProgress {
+                    done: attempted,
+                    total,
+                    spent: report.spent,
+                });
+            }
+
+            // A batch with no successes usually means the provider is unusable
+            if written.is_empty() && last_error.is_some() {
+                report.error = last_error;
+                break;
+            }
+        }
+
+        report.remaining = total - attempted;
+        if !changed.is_empty() {
+            if let Err(e) = ann::sync_changed(conn, &plan.codebase_id, &changed) {
+                warn!(error = %e, "Failed to update ANN index");
+            }
+        }
+
+        info!(
+            codebase_id = %plan.codebase_id,
+            written = report.written,
+            failed = report.failed,
+            remaining = report.remaining,
+            spent = report.spent,
+            "Summarisation finished"
+        );
+        Ok(report)
+    }
+
+    /// Recompute the embeddings of symbols with these hashes, returning their ids
+    async fn reembed(
+        &self,
+        conn: &Connection,
+        codebase_id: &str,
+        hashes: &[&str],
+    ) -> Result<Vec<i64>> {
+        let Some(ref engine) = self.embeddings else {
+            return Ok(Vec::new());
+        };
+
+        let mut stmt = conn.prepare(
+            "SELECT id, symbol_type, symbol_name, symbol_path, signature, doc, calls, summary
+             FROM codebase_index
+             WHERE codebase_id = ?1 AND summary_hash = ?2 AND embedding IS NOT NULL",
+        )?;
+        let mut ids = Vec::new();
+        let mut texts = Vec::new();
+        for hash in hashes {
+            let rows = stmt.query_map(params![codebase_id, hash], |row| {
+                let symbol_type: String = row.get(1)?;
+                let calls: Option<String> = row.get(6)?;
+                let symbol = ParsedSymbol {
+                    symbol_type: SymbolType::parse(&symbol_type).unwrap_or(SymbolType::Function),
+                    name: row.get(2)?,
+                    full_path: row.get(3)?,
+                    line_start: 0,
+                    line_end: 0,
+                    signature: row.get(4)?,
+                    doc: row.get(5)?,
+                    implements: None,
+                    calls: calls
+                        .and_then(|c| serde_json::from_str(&c).ok())
+                        .unwrap_or_default(),
+                };
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    symbol,
+                    row.get::<_, Option<String>>(7)?,
+                ))
+            })?;
+            for row in rows {
+                let (id, symbol, summary) = row?;
+                ids.push(id);
+                texts.push(embedding_text(&symbol, summary.as_deref()));
+            }
+        }
+        if texts.is_empty() {
+            return Ok(ids);
+        }
+
+        let embeddings = engine.embed_batch(texts).await?;
+        let tx = conn.unchecked_transaction()?;
+        for (id, embedding) in ids.iter().zip(&embeddings) {
+            tx.execute(
+                "UPDATE codebase_index SET embedding = ?1 WHERE id = ?2",
+                params![EmbeddingEngine::embedding_to_blob(embedding), id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(ids)
+    }
+}
+
+/// Cache a summary and apply it to the codebase's symbols with that hash
+fn store_summary(
+    conn: &Connection,
+    codebase_id: &str,
+    hash: &str,
+    summary: &str,
+    model: &str,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO symbol_summaries (hash, summary, model, created_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(hash) DO UPDATE SET
+            summary = excluded.summary,
+            model = excluded.model,
+            created_at = excluded.created_at",
+        params![hash, summary, model, Utc::now().to_rfc3339()],
+    )?;
+    conn.execute(
+        "UPDATE codebase_index SET summary = ?1 WHERE codebase_id = ?2 AND summary_hash = ?3",
+        params![summary, codebase_id, hash],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::index::Indexer;
+    use crate::storage::Database;
+    use tempfile::TempDir;
+
+    fn project_dir() -> TempDir {
+        tempfile::Builder::new()
+            .prefix("krusty-summaries")
+            .tempdir()
+            .unwrap()
+    }
+
+    fn symbol(
+        symbol_type: SymbolType,
+        name: &str,
+        line_start: usize,
+        line_end: usize,
+    ) -> ParsedSymbol {
+        ParsedSymbol {
+            symbol_type,
+            name: name.to_string(),
+            full_path: name.to_string(),
+            line_start,
+            line_end,
+            signature: None,
+            doc: None,
+            implements: None,
+            calls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_only_public_symbols_are_hashed() {
+        let source = "pub fn open() {}\nfn helper() {}\npub(crate) struct Inner;\n";
+        let path = Path::new("lib.rs");
+        let rust = SourceLanguage::Rust;
+
+        assert!(summary_hash(
+            rust,
+            path,
+            source,
+            &symbol(SymbolType::Function, "open", 1, 1)
+        )
+        .is_some());
+        assert!(summary_hash(
+            rust,
+            path,
+            source,
+            &symbol(SymbolType::Function, "helper", 2, 2)
+        )
+        .is_none());
+        assert!(summary_hash(
+            rust,
+            path,
+            source,
+            &symbol(SymbolType::Struct, "Inner", 3, 3)
+        )
+        .is_none());
+
+        let py = "def load():\n    pass\ndef _cache():\n    pass\n";
+        let python = SourceLanguage::Python;
+        assert!(summary_hash(
+            python,
+            path,
+            py,
+            &symbol(SymbolType::Function, "load", 1, 2)
+        )
+        .is_some());
+        assert!(summary_hash(
+            python,
+            path,
+            py,
+            &symbol(SymbolType::Function, "_cache", 3, 4)
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_hash_follows_symbol_source() {
+        let path = Path::new("lib.rs");
+        let open = symbol(SymbolType::Function, "open", 2, 2);
+        let before = "// header\npub fn open() {}\n";
+        let moved = "// header\n// more\npub fn open() {}\n";
+        let edited = "// header\npub fn open() { run() }\n";
+
+        let hash = summary_hash(SourceLanguage::Rust, path, before, &open).unwrap();
+        let moved_hash = summary_hash(
+            SourceLanguage::Rust,
+            path,
+            moved,
+            &symbol(SymbolType::Function, "open", 3, 3),
+        );
+        assert_eq!(moved_hash.as_deref(), Some(hash.as_str()));
+        assert_ne!(
+            summary_hash(SourceLanguage::Rust, path, edited, &open).unwrap(),
+            hash
+        );
+    }
+
+    #[test]
+    fn test_module_declaration_includes_module_file() {
+        let project = project_dir();
+        let lib = project.path().join("lib.rs");
+        std::fs::write(&lib, "pub mod net;\n").unwrap();
+        std::fs::write(project.path().join("net.rs"), "//! Networking\n").unwrap();
+
+        let module = symbol(SymbolType::Module, "net", 1, 1);
+        let excerpt = symbol_source(SourceLanguage::Rust, &lib, "pub mod net;\n", &module);
+        assert!(excerpt.contains("//! Networking"));
+    }
+
+    #[test]
+    fn test_clean_summary() {
+        assert_eq!(
+            clean_summary("  \"Opens the\n  database.\" ").as_deref(),
+            Some("Opens the database.")
+        );
+        assert_eq!(clean_summary(" \n "), None);
+        assert!(clean_summary(&"word ".repeat(200)).unwrap().len() <= MAX_SUMMARY_LEN);
+    }
+
+    #[test]
+    fn test_summaries_are_reused_after_reindex() {
+        let project = project_dir();
+        let db_dir = TempDir::new().unwrap();
+        let db = Database::new(&db_dir.path().join("test.db")).unwrap();
+        let conn = db.conn();
+        let file = project.path().join("lib.rs");
+        std::fs::write(&file, "pub fn open() {}\nfn helper() {}\n").unwrap();
+
+        let mut indexer = Indexer::new().unwrap();
+        let codebase = indexer
+            .index_codebase_sync(conn, project.path(), None)
+            .unwrap();
+
+        let plan = SummaryPlan::build(conn, &codebase.id).unwrap();
+        assert_eq!(plan.jobs.len(), 1);
+        assert_eq!(plan.jobs[0].symbol_path, "open");
+        assert!(plan.input_tokens > 0);
+        let pricing = ModelPricing {
+            input: 1.0,
+            output: 5.0,
+            cache_read: 0.1,
+            cache_write: 1.25,
+        };
+        assert!(plan.estimated_cost(&pricing) > 0.0);
+
+        store_summary(
+            conn,
+            &codebase.id,
+            &plan.jobs[0].hash,
+            "Opens things.",
+            "test",
+        )
+        .unwrap();
+        assert!(SummaryPlan::build(conn, &codebase.id).unwrap().is_empty());
+
+        // Touching another symbol re-indexes the file; `open` keeps its summary
+        std::fs::write(&file, "pub fn open() {}\nfn helper() { open() }\n").unwrap();
+        indexer
+            .index_codebase_sync(conn, project.path(), None)
+            .unwrap();
+        let summary: Option<String> = conn
+            .query_row(
+                "SELECT summary FROM codebase_index WHERE symbol_name = 'open'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(summary.as_deref(), Some("Opens things."));
+        let plan = SummaryPlan::build(conn, &codebase.id).unwrap();
+        assert!(plan.is_empty());
+        assert_eq!(plan.summarized, 1);
+    }
+}
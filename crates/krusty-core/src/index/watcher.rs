@@ -0,0 +1,179 @@
+//! Index watcher - Keeps the codebase index fresh while a session is open
+//!
+//! Polls file metadata (mtime + size) on an interval and runs an incremental
+//! index when anything changed. The indexer skips files whose content hash
+//! is unchanged, so a touch without edits only costs a directory walk.
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self as std_mpsc, RecvTimeoutError};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+use tracing::{debug, info, warn};
+
+use super::indexer::{scan_source_files, IndexProgress, Indexer};
+use crate::storage::Database;
+
+/// Default time between file system checks
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// File fingerprints used to detect changes without reading contents
+type Snapshot = HashMap<PathBuf, (Option<SystemTime>, u64)>;
+
+/// Background watcher that re-indexes changed files
+///
+/// The watcher thread stops when this handle is dropped.
+pub struct IndexWatcher {
+    root: PathBuf,
+    stop_tx: Option<std_mpsc::Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl IndexWatcher {
+    /// Start watching `root`, indexing into the database at `db_path`
+    ///
+    /// Embeddings are used when the embedding model can be loaded (it is
+    /// loaded on the first change, not at startup); otherwise symbols are
+    /// indexed without them. Must be called from within a Tokio runtime.
+    pub fn start(
+        db_path: PathBuf,
+        root: PathBuf,
+        interval: Duration,
+        progress_tx: Option<mpsc::UnboundedSender<IndexProgress>>,
+    ) -> Result<Self> {
+        let runtime = tokio::runtime::Handle::try_current()?;
+        let (stop_tx, stop_rx) = std_mpsc::channel();
+        let thread_root = root.clone();
+
+        let handle = std::thread::Builder::new()
+            .name("krusty-index-watcher".to_string())
+            .spawn(move || {
+                watch_loop(
+                    &db_path,
+                    &thread_root,
+                    interval,
+                    progress_tx,
+                    stop_rx,
+                    runtime,
+                )
+            })?;
+
+        info!(root = %root.display(), "Index watcher started");
+        Ok(Self {
+            root,
+            stop_tx: Some(stop_tx),
+            handle: Some(handle),
+        })
+    }
+
+    /// Directory being watched
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether the watcher thread is still running
+    pub fn is_running(&self) -> bool {
+        self.handle.as_ref().is_some_and(|h| !h.is_finished())
+    }
+
+    /// Signal the watcher to stop (returns immediately)
+    pub fn stop(&mut self) {
+        if self.stop_tx.take().is_some() {
+            debug!(root = %self.root.display(), "Stopping index watcher");
+        }
+        // Detach: an in-flight index run finishes on its own
+        self.handle.take();
+    }
+}
+
+impl Drop for IndexWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn watch_loop(
+    db_path: &Path,
+    root: &Path,
+    interval: Duration,
+    progress_tx: Option<mpsc::UnboundedSender<IndexProgress>>,
+    stop_rx: std_mpsc::Receiver<()>,
+    runtime: tokio::runtime::Handle,
+) {
+    let db = match Database::new(db_path) {
+        Ok(db) => db,
+        Err(e) => {
+            warn!(error = %e, "Index watcher could not open database");
+            return;
+        }
+    };
+
+    let mut indexer: Option<Indexer> = None;
+    // Start empty so the first tick catches up on edits made while closed
+    let mut last = Snapshot::new();
+
+    // Sender dropped (or explicit stop) ends the loop
+    while let Err(RecvTimeoutError::Timeout) = stop_rx.recv_timeout(interval) {
+        let current = snapshot(root);
+        if current == last {
+            continue;
+        }
+        last = current;
+        debug!(root = %root.display(), "Detected file changes, updating index");
+
+        let indexer = match indexer.as_mut() {
+            Some(indexer) => indexer,
+            None => match load_indexer() {
+                Ok(loaded) => indexer.insert(loaded),
+                Err(e) => {
+                    warn!(error = %e, "Index watcher could not create indexer");
+                    return;
+                }
+            },
+        };
+
+        let result = if indexer.has_embeddings() {
+            runtime.block_on(indexer.index_codebase(db.conn(), root, progress_tx.clone()))
+        } else {
+            indexer.index_codebase_sync(db.conn(), root, progress_tx.clone())
+        };
+        if let Err(e) = result {
+            warn!(error = %e, "Incremental index update failed");
+        }
+    }
+
+    debug!(root = %root.display(), "Index watcher stopped");
+}
+
+/// Create an indexer, preferring one with embeddings
+fn load_indexer() -> Result<Indexer> {
+    match Indexer::new()?.with_embeddings() {
+        Ok(indexer) => Ok(indexer),
+        Err(e) => {
+            info!("Embeddings unavailable ({e}), watcher indexing without");
+            Indexer::new()
+        }
+    }
+}
+
+/// Fingerprint every indexable file under `root`
+fn snapshot(root: &Path) -> Snapshot {
+    let files = match scan_source_files(root) {
+        Ok(files) => files,
+        Err(e) => {
+            debug!(error = %e, "Failed to scan files for index watcher");
+            return Snapshot::new();
+        }
+    };
+
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let meta = std::fs::metadata(&path).ok()?;
+            let fingerprint = (meta.modified().ok(), meta.len());
+            Some((path, fingerprint))
+        })
+        .collect()
+}
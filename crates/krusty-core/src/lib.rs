@@ -5,6 +5,7 @@
 //! - Tool execution framework
 //! - Session and preference storage
 //! - MCP (Model Context Protocol) support
+//! - LSP client for diagnostics and code navigation
 //! - ACP (Agent Client Protocol) server for editor integration
 
 pub mod acp;
@@ -14,6 +15,7 @@ pub mod auth;
 pub mod constants;
 pub mod extensions;
 pub mod index;
+pub mod lsp;
 pub mod mcp;
 pub mod paths;
 pub mod plan;
@@ -31,6 +33,7 @@ pub use index::{
     CodebaseInsight, CodebaseStore, EmbeddingEngine, IndexPhase, IndexProgress, Indexer,
     InsightStore, InsightType, SemanticRetrieval,
 };
+pub use lsp::LspManager;
 pub use mcp::McpManager;
 pub use skills::SkillsManager;
 pub use storage::{Database, SessionManager};
@@ -0,0 +1,441 @@
+//! LSP client for a single language server
+//!
+//! Handles the JSON-RPC exchange with one server process: requests with a
+//! background receive loop, document sync, and collecting published
+//! diagnostics.
+
+use anyhow::{anyhow, Result};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex, Notify, RwLock};
+use tracing::{debug, info, warn};
+
+use super::config::{language_id, LspServerConfig};
+use super::protocol::{hover_text, path_to_uri, position_params, Diagnostic, Location};
+use super::transport::LspTransport;
+
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+type PendingMap = HashMap<i64, oneshot::Sender<Result<Value>>>;
+
+/// Latest diagnostics per document URI, with a counter bumped on every publish
+#[derive(Default)]
+struct DiagnosticsStore {
+    by_uri: HashMap<String, (u64, Vec<Diagnostic>)>,
+}
+
+/// Client for one running language server
+pub struct LspClient {
+    name: String,
+    transport: Arc<LspTransport>,
+    next_id: AtomicI64,
+    pending: Arc<RwLock<PendingMap>>,
+    diagnostics: Arc<RwLock<DiagnosticsStore>>,
+    diagnostics_changed: Arc<Notify>,
+    /// Open documents and their current version
+    documents: Mutex<HashMap<String, i32>>,
+    shutdown_tx: Option<mpsc::Sender<()>>,
+}
+
+impl LspClient {
+    /// Spawn a server and complete the initialize handshake
+    pub async fn start(config: &LspServerConfig, root: &Path) -> Result<Self> {
+        info!(
+            "Starting language server {} for {}",
+            config.name,
+            root.display()
+        );
+        let transport =
+            Arc::new(LspTransport::spawn(&config.command, &config.args, &config.env, root).await?);
+        let client = Self::with_transport(&config.name, transport);
+        client.initialize(root).await?;
+        Ok(client)
+    }
+
+    fn with_transport(name: &str, transport: Arc<LspTransport>) -> Self {
+        let pending: Arc<RwLock<PendingMap>> = Arc::new(RwLock::new(HashMap::new()));
+        let diagnostics = Arc::new(RwLock::new(DiagnosticsStore::default()));
+        let diagnostics_changed = Arc::new(Notify::new());
+        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+
+        let recv_transport = Arc::clone(&transport);
+        let recv_pending = Arc::clone(&pending);
+        let recv_diagnostics = Arc::clone(&diagnostics);
+        let recv_notify = Arc::clone(&diagnostics_changed);
+        let recv_name = name.to_string();
+
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = shutdown_rx.recv() => {
+                        debug!("LSP client {} shutting down receive loop", recv_name);
+                        break;
+                    }
+                    result = recv_transport.receive() => {
+                        match result {
+                            Ok(message) => {
+                                let handled = handle_message(
+                                    &message,
+                                    &recv_transport,
+                                    &recv_pending,
+                                    &recv_diagnostics,
+                                    &recv_notify,
+                                )
+                                .await;
+                                if let Err(e) = handled {
+                                    warn!("LSP {} message error: {}", recv_name, e);
+                                }
+                            }
+                            Err(e) => {
+                                warn!("LSP {} receive error: {}", recv_name, e);
+                                let mut pending = recv_pending.write().await;
+                                for (_, tx) in pending.drain() {
+                                    let _ = tx.send(Err(anyhow!("Connection lost")));
+                                }
+                                break;
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            name: name.to_string(),
+            transport,
+            next_id: AtomicI64::new(1),
+            pending,
+            diagnostics,
+            diagnostics_changed,
+            documents: Mutex::new(HashMap::new()),
+            shutdown_tx: Some(shutdown_tx),
+        }
+    }
+
+    async fn initialize(&self, root: &Path) -> Result<()> {
+        let root_uri = path_to_uri(root);
+        let root_name = root
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "workspace".to_string());
+
+        let params = json!({
+            "processId": std::process::id(),
+            "clientInfo": { "name": "krusty", "version": env!("CARGO_PKG_VERSION") },
+            "rootUri": root_uri,
+            "workspaceFolders": [{ "uri": root_uri, "name": root_name }],
+            "capabilities": {
+                "textDocument": {
+                    "synchronization": { "didSave": true, "dynamicRegistration": false },
+                    "publishDiagnostics": { "versionSupport": true },
+                    "definition": { "linkSupport": true },
+                    "references": {},
+                    "hover": { "contentFormat": ["markdown", "plaintext"] },
+                },
+                "workspace": { "workspaceFolders": true, "configuration": true },
+            },
+        });
+
+        let _: Value = self.request("initialize", Some(params)).await?;
+        self.notify("initialized", Some(json!({}))).await?;
+        info!("Language server {} initialized", self.name);
+        Ok(())
+    }
+
+    /// Server name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Check if the server is still running
+    pub async fn is_alive(&self) -> bool {
+        self.transport.is_alive().await
+    }
+
+    /// Open a document, or replace its contents if already open
+    ///
+    /// Returns the diagnostics publish counter before the sync, for use
+    /// with `wait_for_diagnostics`.
+    pub async fn sync_document(&self, path: &Path, text: &str) -> Result<u64> {
+        let uri = path_to_uri(path);
+        let before = self.diagnostics_generation(&uri).await;
+
+        let mut documents = self.documents.lock().await;
+        match documents.get_mut(&uri) {
+            Some(version) => {
+                *version += 1;
+                self.notify(
+                    "textDocument/didChange",
+                    Some(json!({
+                        "textDocument": { "uri": uri, "version": *version },
+                        "contentChanges": [{ "text": text }],
+                    })),
+                )
+                .await?;
+            }
+            None => {
+                self.notify(
+                    "textDocument/didOpen",
+                    Some(json!({
+                        "textDocument": {
+                            "uri": uri,
+                            "languageId": language_id(path),
+                            "version": 1,
+                            "text": text,
+                        },
+                    })),
+                )
+                .await?;
+                documents.insert(uri.clone(), 1);
+            }
+        }
+        drop(documents);
+
+        // Some servers (rust-analyzer's check-on-save) only re-check on save
+        self.notify(
+            "textDocument/didSave",
+            Some(json!({ "textDocument": { "uri": uri }, "text": text })),
+        )
+        .await?;
+
+        Ok(before)
+    }
+
+    /// Open a document from disk unless it is already open
+    pub async fn ensure_open(&self, path: &Path) -> Result<()> {
+        let uri = path_to_uri(path);
+        if self.documents.lock().await.contains_key(&uri) {
+            return Ok(());
+        }
+        let text = tokio::fs::read_to_string(path).await?;
+        self.sync_document(path, &text).await.map(|_| ())
+    }
+
+    /// Wait for diagnostics newer than `after` to be published for `path`
+    ///
+    /// Returns `None` if the server published nothing within `timeout`.
+    pub async fn wait_for_diagnostics(
+        &self,
+        path: &Path,
+        after: u64,
+        timeout: Duration,
+    ) -> Option<Vec<Diagnostic>> {
+        let uri = path_to_uri(path);
+        let deadline = tokio::time::Instant::now() + timeout;
+
+        loop {
+            let notified = self.diagnostics_changed.notified();
+            if let Some((generation, diagnostics)) = self.diagnostics.read().await.by_uri.get(&uri)
+            {
+                if *generation > after {
+                    return Some(diagnostics.clone());
+                }
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                return None;
+            }
+        }
+    }
+
+    /// Most recently published diagnostics for `path`
+    pub async fn diagnostics(&self, path: &Path) -> Vec<Diagnostic> {
+        let uri = path_to_uri(path);
+        self.diagnostics
+            .read()
+            .await
+            .by_uri
+            .get(&uri)
+            .map(|(_, d)| d.clone())
+            .unwrap_or_default()
+    }
+
+    async fn diagnostics_generation(&self, uri: &str) -> u64 {
+        self.diagnostics
+            .read()
+            .await
+            .by_uri
+            .get(uri)
+            .map(|(g, _)| *g)
+            .unwrap_or(0)
+    }
+
+    /// Go to definition (0-based position)
+    pub async fn definition(
+        &self,
+        path: &Path,
+        line: u32,
+        character: u32,
+    ) -> Result<Vec<Location>> {
+        let params = position_params(&path_to_uri(path), line, character);
+        let result: Value = self
+            .request("textDocument/definition", Some(params))
+            .await?;
+        Ok(Location::list_from_lsp(&result))
+    }
+
+    /// Find references, including the declaration (0-based position)
+    pub async fn references(
+        &self,
+        path: &Path,
+        line: u32,
+        character: u32,
+    ) -> Result<Vec<Location>> {
+        let mut params = position_params(&path_to_uri(path), line, character);
+        params["context"] = json!({ "includeDeclaration": true });
+        let result: Value = self
+            .request("textDocument/references", Some(params))
+            .await?;
+        Ok(Location::list_from_lsp(&result))
+    }
+
+    /// Hover information (0-based position)
+    pub async fn hover(&self, path: &Path, line: u32, character: u32) -> Result<Option<String>> {
+        let params = position_params(&path_to_uri(path), line, character);
+        let result: Value = self.request("textDocument/hover", Some(params)).await?;
+        Ok(hover_text(&result))
+    }
+
+    /// Ask the server to shut down and exit
+    pub async fn shutdown(&self) {
+        let shutdown = tokio::time::timeout(
+            Duration::from_secs(2),
+            self.request::<Value>("shutdown", None),
+        )
+        .await;
+        if !matches!(shutdown, Ok(Ok(_))) {
+            debug!("Language server {} did not acknowledge shutdown", self.name);
+        }
+        let _ = self.notify("exit", None).await;
+        self.transport.kill().await;
+    }
+
+    /// Send a request and wait for the response
+    async fn request<R: for<'de> serde::Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: Option<Value>,
+    ) -> Result<R> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let mut request = json!({ "jsonrpc": "2.0", "id": id, "method": method });
+        if let Some(params) = params {
+            request["params"] = params;
+        }
+
+        debug!("LSP {} request [{}]: {}", self.name, id, method);
+
+        let (tx, rx) = oneshot::channel();
+        self.pending.write().await.insert(id, tx);
+        self.transport.send(&request.to_string()).await?;
+
+        let result = tokio::time::timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS), rx).await;
+        match result {
+            Ok(Ok(Ok(value))) => Ok(serde_json::from_value(value)?),
+            Ok(Ok(Err(e))) => Err(e),
+            Ok(Err(_)) => Err(anyhow!("Request cancelled")),
+            Err(_) => {
+                self.pending.write().await.remove(&id);
+                Err(anyhow!("Request timed out after {}s", REQUEST_TIMEOUT_SECS))
+            }
+        }
+    }
+
+    /// Send a notification (no response expected)
+    async fn notify(&self, method: &str, params: Option<Value>) -> Result<()> {
+        let mut notification = json!({ "jsonrpc": "2.0", "method": method });
+        if let Some(params) = params {
+            notification["params"] = params;
+        }
+        debug!("LSP {} notify: {}", self.name, method);
+        self.transport.send(&notification.to_string()).await
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.try_send(());
+        }
+    }
+}
+
+/// Handle an incoming message (called by the receive loop)
+async fn handle_message(
+    message: &str,
+    transport: &LspTransport,
+    pending: &RwLock<PendingMap>,
+    diagnostics: &RwLock<DiagnosticsStore>,
+    diagnostics_changed: &Notify,
+) -> Result<()> {
+    let message: Value = serde_json::from_str(message)?;
+    let method = message.get("method").and_then(Value::as_str);
+    let id = message.get("id");
+
+    match (method, id) {
+        // Response to one of our requests
+        (None, Some(id)) => {
+            let Some(id) = id.as_i64() else {
+                return Ok(());
+            };
+            if let Some(tx) = pending.write().await.remove(&id) {
+                let result = match message.get("error") {
+                    Some(error) => Err(anyhow!(
+                        "LSP error {}: {}",
+                        error.get("code").and_then(Value::as_i64).unwrap_or(0),
+                        error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown error")
+                    )),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                let _ = tx.send(result);
+            }
+        }
+        // Request from the server: answer so it doesn't block waiting on us
+        (Some(method), Some(id)) => {
+            let result = match method {
+                // One (empty) settings object per requested item
+                "workspace/configuration" => {
+                    let items = message
+                        .pointer("/params/items")
+                        .and_then(Value::as_array)
+                        .map_or(0, Vec::len);
+                    Value::Array(vec![Value::Null; items])
+                }
+                _ => Value::Null,
+            };
+            let response = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+            transport.send(&response.to_string()).await?;
+        }
+        (Some("textDocument/publishDiagnostics"), None) => {
+            let Some(params) = message.get("params") else {
+                return Ok(());
+            };
+            let Some(uri) = params.get("uri").and_then(Value::as_str) else {
+                return Ok(());
+            };
+            let list: Vec<Diagnostic> = params
+                .get("diagnostics")
+                .and_then(Value::as_array)
+                .map(|items| items.iter().filter_map(Diagnostic::from_lsp).collect())
+                .unwrap_or_default();
+
+            let mut store = diagnostics.write().await;
+            let entry = store.by_uri.entry(uri.to_string()).or_default();
+            entry.0 += 1;
+            entry.1 = list;
+            drop(store);
+            diagnostics_changed.notify_waiters();
+        }
+        (Some(method), None) => {
+            debug!("LSP notification: {}", method);
+        }
+        (None, None) => {}
+    }
+
+    Ok(())
+}
@@ -0,0 +1,173 @@
+//! Language server configuration
+//!
+//! Servers come from a built-in list of well-known binaries (used when found
+//! in PATH) or from installed extensions that provide language servers.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::extensions::wasm_host::Command;
+
+/// How to launch a language server and which files it handles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LspServerConfig {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: HashMap<String, String>,
+    /// File extensions (without the dot) this server handles
+    pub extensions: Vec<String>,
+}
+
+impl LspServerConfig {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extensions = extensions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Build a config from a command resolved by an extension
+    ///
+    /// `languages` are Zed language names (e.g. "Rust", "TSX").
+    pub fn from_extension_command(name: &str, command: Command, languages: &[String]) -> Self {
+        let extensions = languages
+            .iter()
+            .flat_map(|lang| extensions_for_language(lang).iter().copied())
+            .collect::<Vec<_>>();
+        Self {
+            name: name.to_string(),
+            command: command.command,
+            args: command.args,
+            env: command.env.into_iter().collect(),
+            extensions: extensions.into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    /// Whether this server handles the given file
+    pub fn handles(&self, path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| self.extensions.iter().any(|e| e == ext))
+    }
+}
+
+/// Well-known language servers, used when their binary is in PATH
+pub fn default_servers() -> Vec<LspServerConfig> {
+    vec![
+        LspServerConfig::new("rust-analyzer", "rust-analyzer").with_extensions(["rs"]),
+        LspServerConfig::new("typescript-language-server", "typescript-language-server")
+            .with_args(["--stdio"])
+            .with_extensions(["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"]),
+        LspServerConfig::new("pyright", "pyright-langserver")
+            .with_args(["--stdio"])
+            .with_extensions(["py", "pyi"]),
+        LspServerConfig::new("gopls", "gopls").with_extensions(["go"]),
+    ]
+}
+
+/// File extensions for a Zed language name
+pub fn extensions_for_language(language: &str) -> &'static [&'static str] {
+    match language {
+        "Rust" => &["rs"],
+        "TypeScript" => &["ts", "mts", "cts"],
+        "TSX" => &["tsx"],
+        "JavaScript" => &["js", "jsx", "mjs", "cjs"],
+        "Python" => &["py", "pyi"],
+        "Go" => &["go"],
+        "C" => &["c", "h"],
+        "C++" => &["cc", "cpp", "cxx", "hh", "hpp", "hxx"],
+        "Java" => &["java"],
+        "Kotlin" => &["kt", "kts"],
+        "Swift" => &["swift"],
+        "Ruby" => &["rb"],
+        "PHP" => &["php"],
+        "Lua" => &["lua"],
+        "Zig" => &["zig"],
+        "Elixir" => &["ex", "exs"],
+        "Haskell" => &["hs"],
+        "OCaml" => &["ml", "mli"],
+        "Dart" => &["dart"],
+        "Scala" => &["scala"],
+        "HTML" => &["html", "htm"],
+        "CSS" => &["css"],
+        "JSON" => &["json"],
+        "TOML" => &["toml"],
+        "YAML" => &["yml", "yaml"],
+        "Markdown" => &["md"],
+        _ => &[],
+    }
+}
+
+/// LSP `languageId` for a file, sent with `textDocument/didOpen`
+pub fn language_id(path: &Path) -> String {
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let id = match ext {
+        "rs" => "rust",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "py" | "pyi" => "python",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "cpp",
+        "rb" => "ruby",
+        "kt" | "kts" => "kotlin",
+        "ex" | "exs" => "elixir",
+        "hs" => "haskell",
+        "ml" | "mli" => "ocaml",
+        "htm" => "html",
+        "yml" => "yaml",
+        "md" => "markdown",
+        other => other,
+    };
+    id.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_extension_command() {
+        let command = Command {
+            command: "/ext/zls".to_string(),
+            args: vec!["--stdio".to_string()],
+            env: vec![("ZLS_LOG".to_string(), "1".to_string())],
+        };
+        let config = LspServerConfig::from_extension_command("zls", command, &["Zig".to_string()]);
+
+        assert_eq!(config.command, "/ext/zls");
+        assert_eq!(config.env.get("ZLS_LOG").map(String::as_str), Some("1"));
+        assert!(config.handles(Path::new("src/main.zig")));
+        assert!(!config.handles(Path::new("src/main.rs")));
+    }
+
+    #[test]
+    fn test_language_id() {
+        assert_eq!(language_id(Path::new("a/b.tsx")), "typescriptreact");
+        assert_eq!(language_id(Path::new("lib.rs")), "rust");
+        assert_eq!(language_id(Path::new("main.zig")), "zig");
+    }
+}
@@ -0,0 +1,274 @@
+//! LSP Manager - language servers for one workspace
+//!
+//! Servers are started lazily the first time a file they handle is synced
+//! or queried, and kept running for the rest of the session. A server that
+//! fails to start is not retried.
+
+use anyhow::{anyhow, Result};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use super::client::LspClient;
+use super::config::{default_servers, LspServerConfig};
+use super::protocol::{Diagnostic, DiagnosticSeverity, Location};
+use crate::extensions::types::{LanguageServerName, WorktreeDelegate};
+use crate::extensions::wasm_host::WasmExtension;
+
+/// How long to wait for diagnostics after a change
+pub const DEFAULT_DIAGNOSTICS_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Diagnostics for one file after a sync
+#[derive(Debug, Clone, Default)]
+pub struct FileDiagnostics {
+    pub server: String,
+    /// Errors and warnings not present before the change
+    pub new: Vec<Diagnostic>,
+    /// Total errors currently reported for the file
+    pub error_count: usize,
+}
+
+impl FileDiagnostics {
+    /// Render for appending to a tool result
+    pub fn format(&self) -> String {
+        let mut out = format!(
+            "{} new diagnostic(s) from {} ({} error(s) in file):",
+            self.new.len(),
+            self.server,
+            self.error_count
+        );
+        for diagnostic in &self.new {
+            out.push_str("\n  ");
+            out.push_str(&diagnostic.to_string());
+        }
+        out
+    }
+}
+
+/// Manages language servers for a workspace root
+pub struct LspManager {
+    root: PathBuf,
+    configs: RwLock<Vec<LspServerConfig>>,
+    /// Running clients by server name
+    clients: Mutex<HashMap<String, Arc<LspClient>>>,
+    /// Servers that failed to start
+    failed: RwLock<HashSet<String>>,
+    diagnostics_timeout: Duration,
+}
+
+impl LspManager {
+    /// Create a manager using the built-in server list
+    pub fn new(root: PathBuf) -> Self {
+        Self::with_servers(root, default_servers())
+    }
+
+    /// Create a manager with an explicit server list
+    pub fn with_servers(root: PathBuf, configs: Vec<LspServerConfig>) -> Self {
+        Self {
+            root,
+            configs: RwLock::new(configs),
+            clients: Mutex::new(HashMap::new()),
+            failed: RwLock::new(HashSet::new()),
+            diagnostics_timeout: DEFAULT_DIAGNOSTICS_TIMEOUT,
+        }
+    }
+
+    /// Set how long syncs wait for diagnostics
+    pub fn with_diagnostics_timeout(mut self, timeout: Duration) -> Self {
+        self.diagnostics_timeout = timeout;
+        self
+    }
+
+    /// Workspace root
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Add a server, taking priority over earlier ones for its extensions
+    pub async fn add_server(&self, config: LspServerConfig) {
+        let mut configs = self.configs.write().await;
+        configs.retain(|c| c.name != config.name);
+        configs.insert(0, config);
+    }
+
+    /// Register the language servers provided by an installed extension
+    ///
+    /// Returns how many servers were added.
+    pub async fn register_extension_servers(
+        &self,
+        extension: &WasmExtension,
+        worktree: Arc<dyn WorktreeDelegate>,
+    ) -> Result<usize> {
+        let mut added = 0;
+        for (name, entry) in &extension.manifest.language_servers {
+            let mut languages = entry.languages.clone();
+            languages.extend(entry.language.clone());
+
+            let command = match extension
+                .language_server_command(LanguageServerName::from(name.clone()), worktree.clone())
+                .await
+            {
+                Ok(command) => command,
+                Err(e) => {
+                    warn!(
+                        "Extension {} could not provide {}: {}",
+                        extension.manifest.id, name, e
+                    );
+                    continue;
+                }
+            };
+
+            let config = LspServerConfig::from_extension_command(name, command, &languages);
+            if config.extensions.is_empty() {
+                warn!("Language server {} has no known file types, skipping", name);
+                continue;
+            }
+            self.add_server(config).await;
+            added += 1;
+        }
+        Ok(added)
+    }
+
+    /// Whether any configured server handles `path`
+    pub async fn handles(&self, path: &Path) -> bool {
+        self.configs.read().await.iter().any(|c| c.handles(path))
+    }
+
+    /// Running client for `path`, starting its server if needed
+    async fn client_for(&self, path: &Path) -> Option<Arc<LspClient>> {
+        let config = {
+            let configs = self.configs.read().await;
+            let failed = self.failed.read().await;
+            configs
+                .iter()
+                .find(|c| c.handles(path) && !failed.contains(&c.name))
+                .cloned()?
+        };
+
+        // Held across startup so concurrent edits don't spawn duplicates
+        let mut clients = self.clients.lock().await;
+        if let Some(client) = clients.get(&config.name) {
+            if client.is_alive().await {
+                return Some(client.clone());
+            }
+            info!("Language server {} exited, restarting", config.name);
+            clients.remove(&config.name);
+        }
+
+        match LspClient::start(&config, &self.root).await {
+            Ok(client) => {
+                let client = Arc::new(client);
+                clients.insert(config.name.clone(), client.clone());
+                Some(client)
+            }
+            Err(e) => {
+                warn!("Language server {} unavailable: {}", config.name, e);
+                self.failed.write().await.insert(config.name);
+                None
+            }
+        }
+    }
+
+    /// Sync a file's new contents and collect diagnostics it introduced
+    ///
+    /// Returns `None` when no server handles the file or none reported in
+    /// time. Hints and informational diagnostics are ignored.
+    pub async fn sync_file(&self, path: &Path, text: &str) -> Option<FileDiagnostics> {
+        let client = self.client_for(path).await?;
+        let previous = client.diagnostics(path).await;
+
+        let generation = match client.sync_document(path, text).await {
+            Ok(generation) => generation,
+            Err(e) => {
+                warn!(
+                    "Failed to sync {} with {}: {}",
+                    path.display(),
+                    client.name(),
+                    e
+                );
+                return None;
+            }
+        };
+
+        let current = client
+            .wait_for_diagnostics(path, generation, self.diagnostics_timeout)
+            .await?;
+
+        let relevant = |d: &Diagnostic| d.severity <= DiagnosticSeverity::Warning;
+        let new = current
+            .iter()
+            .filter(|d| relevant(d) && !previous.iter().any(|p| p.same_problem(d)))
+            .cloned()
+            .collect();
+        let error_count = current
+            .iter()
+            .filter(|d| d.severity == DiagnosticSeverity::Error)
+            .count();
+
+        Some(FileDiagnostics {
+            server: client.name().to_string(),
+            new,
+            error_count,
+        })
+    }
+
+    /// Client for a query on `path`, with the document open
+    async fn query_client(&self, path: &Path) -> Result<Arc<LspClient>> {
+        let client = self
+            .client_for(path)
+            .await
+            .ok_or_else(|| anyhow!("No language server available for {}", path.display()))?;
+        client.ensure_open(path).await?;
+        Ok(client)
+    }
+
+    /// Go to definition (0-based position)
+    pub async fn definition(
+        &self,
+        path: &Path,
+        line: u32,
+        character: u32,
+    ) -> Result<Vec<Location>> {
+        self.query_client(path)
+            .await?
+            .definition(path, line, character)
+            .await
+    }
+
+    /// Find references (0-based position)
+    pub async fn references(
+        &self,
+        path: &Path,
+        line: u32,
+        character: u32,
+    ) -> Result<Vec<Location>> {
+        self.query_client(path)
+            .await?
+            .references(path, line, character)
+            .await
+    }
+
+    /// Hover information (0-based position)
+    pub async fn hover(&self, path: &Path, line: u32, character: u32) -> Result<Option<String>> {
+        self.query_client(path)
+            .await?
+            .hover(path, line, character)
+            .await
+    }
+
+    /// Names of running servers
+    pub async fn running_servers(&self) -> Vec<String> {
+        self.clients.lock().await.keys().cloned().collect()
+    }
+
+    /// Shut down all running servers
+    pub async fn shutdown_all(&self) {
+        let clients: Vec<_> = self.clients.lock().await.drain().map(|(_, c)| c).collect();
+        for client in clients {
+            client.shutdown().await;
+        }
+    }
+}
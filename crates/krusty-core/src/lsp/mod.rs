@@ -0,0 +1,18 @@
+//! LSP (Language Server Protocol) client
+//!
+//! Starts language servers per workspace over stdio, keeps them in sync with
+//! files the agent writes, and surfaces their diagnostics and navigation
+//! (definition, references, hover) to tools.
+
+mod client;
+mod config;
+mod manager;
+mod protocol;
+mod transport;
+
+pub use client::LspClient;
+pub use config::{default_servers, extensions_for_language, language_id, LspServerConfig};
+pub use manager::{FileDiagnostics, LspManager, DEFAULT_DIAGNOSTICS_TIMEOUT};
+pub use protocol::{
+    path_to_uri, uri_to_path, utf16_column, Diagnostic, DiagnosticSeverity, Location,
+};
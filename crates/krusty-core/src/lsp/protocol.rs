@@ -0,0 +1,257 @@
+//! LSP protocol types
+//!
+//! Only the small subset of the protocol krusty uses, parsed leniently from
+//! `serde_json::Value` so unusual server responses degrade instead of failing.
+
+use serde_json::{json, Value};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Diagnostic severity (LSP numbering)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DiagnosticSeverity {
+    Error = 1,
+    Warning = 2,
+    Information = 3,
+    Hint = 4,
+}
+
+impl DiagnosticSeverity {
+    fn from_lsp(value: Option<u64>) -> Self {
+        match value {
+            Some(2) => Self::Warning,
+            Some(3) => Self::Information,
+            Some(4) => Self::Hint,
+            // Servers may omit severity; treat it as an error
+            _ => Self::Error,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Information => "info",
+            Self::Hint => "hint",
+        }
+    }
+}
+
+/// A diagnostic published by a language server
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub severity: DiagnosticSeverity,
+    /// 1-based line
+    pub line: u32,
+    /// 1-based column
+    pub column: u32,
+    pub message: String,
+    pub source: Option<String>,
+}
+
+impl Diagnostic {
+    pub(crate) fn from_lsp(value: &Value) -> Option<Self> {
+        let start = value.get("range")?.get("start")?;
+        Some(Self {
+            severity: DiagnosticSeverity::from_lsp(value.get("severity").and_then(Value::as_u64)),
+            line: start.get("line")?.as_u64()? as u32 + 1,
+            column: start.get("character")?.as_u64()? as u32 + 1,
+            message: value.get("message")?.as_str()?.to_string(),
+            source: value
+                .get("source")
+                .and_then(Value::as_str)
+                .map(str::to_string),
+        })
+    }
+
+    /// Whether two diagnostics report the same problem, ignoring position
+    ///
+    /// Edits above a diagnostic shift its line without making it new.
+    pub fn same_problem(&self, other: &Diagnostic) -> bool {
+        self.severity == other.severity
+            && self.message == other.message
+            && self.source == other.source
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}:{}] {}",
+            self.severity.as_str(),
+            self.line,
+            self.column,
+            self.message
+        )?;
+        if let Some(source) = &self.source {
+            write!(f, " ({})", source)?;
+        }
+        Ok(())
+    }
+}
+
+/// A location in a file (1-based line and column)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub path: PathBuf,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Location {
+    /// Parse a `Location` or `LocationLink`
+    pub(crate) fn from_lsp(value: &Value) -> Option<Self> {
+        let uri = value
+            .get("uri")
+            .or_else(|| value.get("targetUri"))?
+            .as_str()?;
+        let range = value
+            .get("range")
+            .or_else(|| value.get("targetSelectionRange"))?;
+        let start = range.get("start")?;
+        Some(Self {
+            path: uri_to_path(uri)?,
+            line: start.get("line")?.as_u64()? as u32 + 1,
+            column: start.get("character")?.as_u64()? as u32 + 1,
+        })
+    }
+
+    /// Parse a definition/references result (single, array or null)
+    pub(crate) fn list_from_lsp(value: &Value) -> Vec<Self> {
+        match value {
+            Value::Array(items) => items.iter().filter_map(Self::from_lsp).collect(),
+            Value::Null => Vec::new(),
+            single => Self::from_lsp(single).into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path.display(), self.line, self.column)
+    }
+}
+
+/// Flatten hover contents (MarkupContent, MarkedString or an array) to text
+pub(crate) fn hover_text(value: &Value) -> Option<String> {
+    fn marked(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Object(obj) => obj.get("value").and_then(Value::as_str).map(|v| {
+                match obj.get("language").and_then(Value::as_str) {
+                    Some(lang) => format!("```{}\n{}\n```", lang, v),
+                    None => v.to_string(),
+                }
+            }),
+            _ => None,
+        }
+    }
+
+    let contents = value.get("contents")?;
+    let text = match contents {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(marked)
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        other => marked(other)?,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+/// Convert a file path to a `file://` URI
+pub fn path_to_uri(path: &Path) -> String {
+    Url::from_file_path(path)
+        .map(|u| u.to_string())
+        .unwrap_or_else(|_| format!("file://{}", path.display()))
+}
+
+/// Convert a `file://` URI back to a path
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// Text document position params for a path and 0-based position
+pub(crate) fn position_params(uri: &str, line: u32, character: u32) -> Value {
+    json!({
+        "textDocument": { "uri": uri },
+        "position": { "line": line, "character": character },
+    })
+}
+
+/// Convert a 1-based column counted in chars to an LSP (UTF-16) character offset
+pub fn utf16_column(line_text: &str, column: u32) -> u32 {
+    line_text
+        .chars()
+        .take(column.saturating_sub(1) as usize)
+        .map(|c| c.len_utf16() as u32)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_from_lsp() {
+        let value = json!({
+            "range": { "start": { "line": 4, "character": 2 }, "end": { "line": 4, "character": 6 } },
+            "severity": 2,
+            "message": "unused variable",
+            "source": "rustc",
+        });
+        let diagnostic = Diagnostic::from_lsp(&value).unwrap();
+        assert_eq!(diagnostic.severity, DiagnosticSeverity::Warning);
+        assert_eq!(
+            diagnostic.to_string(),
+            "warning[5:3] unused variable (rustc)"
+        );
+    }
+
+    #[test]
+    fn test_locations_from_lsp() {
+        let location = json!({
+            "uri": "file:///tmp/a.rs",
+            "range": { "start": { "line": 0, "character": 3 }, "end": { "line": 0, "character": 5 } },
+        });
+        let link = json!({
+            "targetUri": "file:///tmp/b.rs",
+            "targetRange": { "start": { "line": 9, "character": 0 }, "end": { "line": 12, "character": 1 } },
+            "targetSelectionRange": { "start": { "line": 9, "character": 4 }, "end": { "line": 9, "character": 8 } },
+        });
+
+        let single = Location::list_from_lsp(&location);
+        assert_eq!(single[0].to_string(), "/tmp/a.rs:1:4");
+
+        let links = Location::list_from_lsp(&json!([link]));
+        assert_eq!(links[0].path, PathBuf::from("/tmp/b.rs"));
+        assert_eq!(links[0].line, 10);
+        assert_eq!(links[0].column, 5);
+
+        assert!(Location::list_from_lsp(&Value::Null).is_empty());
+    }
+
+    #[test]
+    fn test_hover_text() {
+        let markup = json!({ "contents": { "kind": "markdown", "value": "fn main()" } });
+        assert_eq!(hover_text(&markup).as_deref(), Some("fn main()"));
+
+        let marked =
+            json!({ "contents": [{ "language": "rust", "value": "fn main()" }, "Entry point"] });
+        assert_eq!(
+            hover_text(&marked).as_deref(),
+            Some("```rust\nfn main()\n```\n\nEntry point")
+        );
+
+        assert!(hover_text(&json!({ "contents": "" })).is_none());
+    }
+
+    #[test]
+    fn test_utf16_column() {
+        assert_eq!(utf16_column("let x = 1;", 5), 4);
+        // '𝔸' is one char but two UTF-16 code units
+        assert_eq!(utf16_column("𝔸 = 1", 3), 3);
+    }
+}
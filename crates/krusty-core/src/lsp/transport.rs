@@ -0,0 +1,159 @@
+//! LSP stdio transport
+//!
+//! Language servers frame each JSON-RPC message with a `Content-Length`
+//! header followed by a blank line, unlike MCP's newline-delimited JSON.
+
+use anyhow::{anyhow, Context, Result};
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+/// Stdio transport for a language server process
+pub struct LspTransport {
+    stdin: Mutex<ChildStdin>,
+    stdout: Mutex<BufReader<ChildStdout>>,
+    child: Mutex<Child>,
+}
+
+impl LspTransport {
+    /// Spawn a language server
+    pub async fn spawn(
+        command: &str,
+        args: &[String],
+        env: &HashMap<String, String>,
+        working_dir: &Path,
+    ) -> Result<Self> {
+        tracing::info!("Spawning language server: {} {:?}", command, args);
+
+        let mut cmd = Command::new(command);
+        cmd.args(args)
+            .envs(env)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            // Servers log heavily to stderr; nobody reads it
+            .stderr(Stdio::null())
+            .current_dir(working_dir)
+            .kill_on_drop(true);
+
+        let mut child = cmd.spawn().map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                anyhow!("Command not found: {}", command)
+            } else {
+                anyhow!("Failed to spawn {}: {}", command, e)
+            }
+        })?;
+
+        let stdin = child.stdin.take().ok_or_else(|| anyhow!("No stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow!("No stdout"))?;
+
+        Ok(Self {
+            stdin: Mutex::new(stdin),
+            stdout: Mutex::new(BufReader::new(stdout)),
+            child: Mutex::new(child),
+        })
+    }
+
+    /// Send a JSON-RPC message with its Content-Length header
+    pub async fn send(&self, message: &str) -> Result<()> {
+        let mut stdin = self.stdin.lock().await;
+        let header = format!("Content-Length: {}\r\n\r\n", message.len());
+        stdin.write_all(header.as_bytes()).await?;
+        stdin.write_all(message.as_bytes()).await?;
+        stdin.flush().await?;
+        tracing::trace!("LSP sent: {}", message);
+        Ok(())
+    }
+
+    /// Receive the next JSON-RPC message
+    pub async fn receive(&self) -> Result<String> {
+        let mut stdout = self.stdout.lock().await;
+        let length = read_headers(&mut *stdout).await?;
+
+        let Some(length) = length else {
+            let mut child = self.child.lock().await;
+            return match child.try_wait() {
+                Ok(Some(status)) => Err(anyhow!("Language server exited with {}", status)),
+                _ => Err(anyhow!("Language server closed stdout unexpectedly")),
+            };
+        };
+
+        let mut body = vec![0u8; length];
+        stdout
+            .read_exact(&mut body)
+            .await
+            .context("Truncated LSP message")?;
+        let message = String::from_utf8(body).context("LSP message is not UTF-8")?;
+        tracing::trace!("LSP received: {}", message);
+        Ok(message)
+    }
+
+    /// Check if the server process is still running
+    pub async fn is_alive(&self) -> bool {
+        let mut child = self.child.lock().await;
+        matches!(child.try_wait(), Ok(None))
+    }
+
+    /// Kill the server process
+    pub async fn kill(&self) {
+        let mut child = self.child.lock().await;
+        let _ = child.kill().await;
+    }
+}
+
+/// Read headers up to the blank line, returning the Content-Length
+///
+/// Returns `Ok(None)` on EOF before any header.
+async fn read_headers<R>(reader: &mut R) -> Result<Option<usize>>
+where
+    R: AsyncBufReadExt + Unpin,
+{
+    let mut length = None;
+    let mut seen_header = false;
+
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await? == 0 {
+            if seen_header {
+                return Err(anyhow!("Language server closed stdout mid-message"));
+            }
+            return Ok(None);
+        }
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            if length.is_some() {
+                return Ok(length);
+            }
+            // Stray blank line before any header
+            continue;
+        }
+
+        seen_header = true;
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                length = Some(value.trim().parse().context("Invalid Content-Length")?);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_headers() {
+        let input = b"Content-Length: 12\r\nContent-Type: application/json\r\n\r\n{\"id\":1}";
+        let mut reader = BufReader::new(&input[..]);
+        assert_eq!(read_headers(&mut reader).await.unwrap(), Some(12));
+
+        let mut empty = BufReader::new(&b""[..]);
+        assert_eq!(read_headers(&mut empty).await.unwrap(), None);
+
+        let mut truncated = BufReader::new(&b"Content-Length: 3\r\n"[..]);
+        assert!(read_headers(&mut truncated).await.is_err());
+    }
+}
@@ -1,6 +1,7 @@
-//! MCP Client for local stdio servers
+//! MCP Client
 //!
-//! Handles JSON-RPC communication with a single MCP server.
+//! Handles JSON-RPC communication with a single MCP server, local (stdio)
+//! or remote (HTTP).
 //! Uses a background receive loop to avoid race conditions.
 
 use anyhow::{anyhow, Result};
@@ -13,38 +14,58 @@ use tokio::sync::{mpsc, oneshot, RwLock};
 use tracing::{debug, error, info};
 
 use super::config::McpServerConfig;
+use super::http_transport::HttpTransport;
 use super::protocol::{
-    ClientCapabilities, ClientInfo, InitializeParams, InitializeResult, McpRequest, McpResponse,
-    McpToolDef, McpToolResult, ToolCallParams, ToolCallResult, ToolsListResult,
+    ClientCapabilities, ClientInfo, InitializeParams, InitializeResult, ListParams, McpPrompt,
+    McpPromptResult, McpRequest, McpResource, McpResourceContents, McpResourceTemplate,
+    McpResponse, McpToolDef, McpToolResult, PromptGetParams, PromptsListResult, ResourceReadParams,
+    ResourceReadResult, ResourceTemplatesListResult, ResourcesListResult, ServerCapabilities,
+    ToolCallParams, ToolCallResult, ToolsListResult,
 };
-use super::transport::StdioTransport;
+use super::transport::{McpTransport, StdioTransport};
 
 const PROTOCOL_VERSION: &str = "2024-11-05";
 const REQUEST_TIMEOUT_SECS: u64 = 30;
 
-/// MCP client for a local server
+/// MCP client for a single server
 pub struct McpClient {
     name: String,
-    transport: Arc<StdioTransport>,
+    transport: Arc<dyn McpTransport>,
     next_id: AtomicI64,
     /// Pending request handlers
     pending: Arc<RwLock<HashMap<i64, oneshot::Sender<Result<Value>>>>>,
+    /// Capabilities from initialize
+    capabilities: RwLock<ServerCapabilities>,
     /// Cached tools
     tools: RwLock<Vec<McpToolDef>>,
+    /// Cached resources and resource templates
+    resources: RwLock<Vec<McpResource>>,
+    resource_templates: RwLock<Vec<McpResourceTemplate>>,
+    /// Cached prompts
+    prompts: RwLock<Vec<McpPrompt>>,
     /// Shutdown signal
     shutdown_tx: Option<mpsc::Sender<()>>,
 }
 
 impl McpClient {
-    /// Connect to a local MCP server
+    /// Connect to an MCP server
     pub async fn connect(name: &str, config: &McpServerConfig, working_dir: &Path) -> Result<Self> {
-        let McpServerConfig::Local { command, args, env } = config else {
-            return Err(anyhow!("McpClient only handles local servers"));
-        };
-
         info!("Connecting to MCP server: {}", name);
 
-        let transport = Arc::new(StdioTransport::spawn(command, args, env, working_dir).await?);
+        let transport: Arc<dyn McpTransport> = match config {
+            McpServerConfig::Local { command, args, env } => {
+                Arc::new(StdioTransport::spawn(command, args, env, working_dir).await?)
+            }
+            McpServerConfig::Remote {
+                url,
+                authorization_token,
+                headers,
+            } => Arc::new(HttpTransport::new(
+                url,
+                authorization_token.as_deref(),
+                headers,
+            )?),
+        };
 
         let pending: Arc<RwLock<HashMap<i64, oneshot::Sender<Result<Value>>>>> =
             Arc::new(RwLock::new(HashMap::new()));
@@ -90,7 +111,11 @@ impl McpClient {
             transport,
             next_id: AtomicI64::new(1),
             pending,
+            capabilities: RwLock::new(ServerCapabilities::default()),
             tools: RwLock::new(Vec::new()),
+            resources: RwLock::new(Vec::new()),
+            resource_templates: RwLock::new(Vec::new()),
+            prompts: RwLock::new(Vec::new()),
             shutdown_tx: Some(shutdown_tx),
         };
 
@@ -124,6 +149,8 @@ impl McpClient {
             self.name, result.protocol_version
         );
 
+        *self.capabilities.write().await = result.capabilities.clone();
+
         // Send initialized notification
         self.notify("notifications/initialized", None).await?;
 
@@ -174,6 +201,113 @@ impl McpClient {
         self.tools.read().await.clone()
     }
 
+    /// Whether the server advertised resources
+    pub async fn supports_resources(&self) -> bool {
+        self.capabilities.read().await.resources.is_some()
+    }
+
+    /// Whether the server advertised prompts
+    pub async fn supports_prompts(&self) -> bool {
+        self.capabilities.read().await.prompts.is_some()
+    }
+
+    /// List available resources (all pages)
+    pub async fn list_resources(&self) -> Result<Vec<McpResource>> {
+        let mut resources = Vec::new();
+        let mut cursor = None;
+        loop {
+            let result: ResourcesListResult =
+                self.request("resources/list", list_params(cursor)?).await?;
+            resources.extend(result.resources);
+            cursor = result.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        info!("MCP {} has {} resources", self.name, resources.len());
+
+        *self.resources.write().await = resources.clone();
+        Ok(resources)
+    }
+
+    /// List resource templates (all pages)
+    pub async fn list_resource_templates(&self) -> Result<Vec<McpResourceTemplate>> {
+        let mut templates = Vec::new();
+        let mut cursor = None;
+        loop {
+            let result: ResourceTemplatesListResult = self
+                .request("resources/templates/list", list_params(cursor)?)
+                .await?;
+            templates.extend(result.resource_templates);
+            cursor = result.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+
+        *self.resource_templates.write().await = templates.clone();
+        Ok(templates)
+    }
+
+    /// Read a resource's contents
+    pub async fn read_resource(&self, uri: &str) -> Result<Vec<McpResourceContents>> {
+        let params = ResourceReadParams {
+            uri: uri.to_string(),
+        };
+        let result: ResourceReadResult = self
+            .request("resources/read", Some(serde_json::to_value(params)?))
+            .await?;
+        Ok(result.contents)
+    }
+
+    /// List available prompts (all pages)
+    pub async fn list_prompts(&self) -> Result<Vec<McpPrompt>> {
+        let mut prompts = Vec::new();
+        let mut cursor = None;
+        loop {
+            let result: PromptsListResult =
+                self.request("prompts/list", list_params(cursor)?).await?;
+            prompts.extend(result.prompts);
+            cursor = result.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        info!("MCP {} has {} prompts", self.name, prompts.len());
+
+        *self.prompts.write().await = prompts.clone();
+        Ok(prompts)
+    }
+
+    /// Expand a prompt with arguments
+    pub async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<McpPromptResult> {
+        let params = PromptGetParams {
+            name: name.to_string(),
+            arguments,
+        };
+        self.request("prompts/get", Some(serde_json::to_value(params)?))
+            .await
+    }
+
+    /// Get cached resources
+    pub async fn get_resources(&self) -> Vec<McpResource> {
+        self.resources.read().await.clone()
+    }
+
+    /// Get cached resource templates
+    pub async fn get_resource_templates(&self) -> Vec<McpResourceTemplate> {
+        self.resource_templates.read().await.clone()
+    }
+
+    /// Get cached prompts
+    pub async fn get_prompts(&self) -> Vec<McpPrompt> {
+        self.prompts.read().await.clone()
+    }
+
     /// Get server name
     pub fn name(&self) -> &str {
         &self.name
@@ -250,6 +384,14 @@ impl Drop for McpClient {
     }
 }
 
+/// Params for a paginated list request
+fn list_params(cursor: Option<String>) -> Result<Option<Value>> {
+    cursor
+        .map(|cursor| serde_json::to_value(ListParams { cursor }))
+        .transpose()
+        .map_err(Into::into)
+}
+
 /// Handle an incoming message (called by receive loop)
 async fn handle_message(
     message: &str,
@@ -2,10 +2,10 @@
 //!
 //! Parses .mcp.json files. Supports two server types:
 //! - Local (stdio): Spawns a local process, we act as MCP client
-//! - Remote (url): Passed to Anthropic API's MCP Connector
+//! - Remote (url): Connected over HTTP, we act as MCP client
 
 use anyhow::{Context, Result};
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
 
@@ -29,13 +29,17 @@ pub enum McpServerConfigRaw {
         #[serde(default)]
         env: HashMap<String, String>,
     },
-    /// Remote server (passed to Anthropic MCP Connector API)
+    /// Remote server (Streamable HTTP or SSE transport)
     Remote {
-        #[serde(rename = "type")]
-        server_type: String, // Must be "url"
+        /// "url", "http" or "sse" - the transport is detected either way
+        #[serde(rename = "type", default)]
+        server_type: Option<String>,
         url: String,
         #[serde(default)]
         authorization_token: Option<String>,
+        /// Extra HTTP headers sent with every request
+        #[serde(default)]
+        headers: HashMap<String, String>,
     },
 }
 
@@ -48,24 +52,14 @@ pub enum McpServerConfig {
         args: Vec<String>,
         env: HashMap<String, String>,
     },
-    /// Remote server - passed to Anthropic API's MCP Connector
+    /// Remote server - we connect over HTTP
     Remote {
         url: String,
         authorization_token: Option<String>,
+        headers: HashMap<String, String>,
     },
 }
 
-/// Remote server config for Anthropic API
-#[derive(Debug, Clone, Serialize)]
-pub struct RemoteMcpServer {
-    #[serde(rename = "type")]
-    pub server_type: String,
-    pub url: String,
-    pub name: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub authorization_token: Option<String>,
-}
-
 impl McpServerConfig {
     pub fn is_local(&self) -> bool {
         matches!(self, McpServerConfig::Local { .. })
@@ -78,7 +72,7 @@ impl McpServerConfig {
     pub fn transport_type(&self) -> &'static str {
         match self {
             McpServerConfig::Local { .. } => "stdio",
-            McpServerConfig::Remote { .. } => "remote",
+            McpServerConfig::Remote { .. } => "http",
         }
     }
 }
@@ -128,15 +122,21 @@ impl McpConfig {
                 McpServerConfigRaw::Remote {
                     url,
                     authorization_token,
+                    headers,
                     ..
                 } => {
                     let token = match authorization_token {
                         Some(t) => Some(expand_env_var(t).await),
                         None => None,
                     };
+                    let mut expanded_headers = HashMap::new();
+                    for (k, v) in headers {
+                        expanded_headers.insert(k.clone(), expand_env_var(v).await);
+                    }
                     McpServerConfig::Remote {
                         url: url.clone(),
                         authorization_token: token,
+                        headers: expanded_headers,
                     }
                 }
             };
@@ -144,31 +144,6 @@ impl McpConfig {
         }
         result
     }
-
-    /// Get remote servers formatted for Anthropic API's MCP Connector
-    pub async fn remote_servers_for_api(&self) -> Vec<RemoteMcpServer> {
-        let mut result = Vec::new();
-        for (name, raw) in &self.mcp_servers {
-            if let McpServerConfigRaw::Remote {
-                url,
-                authorization_token,
-                ..
-            } = raw
-            {
-                let token = match authorization_token {
-                    Some(t) => Some(expand_env_var(t).await),
-                    None => None,
-                };
-                result.push(RemoteMcpServer {
-                    server_type: "url".to_string(),
-                    url: url.clone(),
-                    name: name.clone(),
-                    authorization_token: token,
-                });
-            }
-        }
-        result
-    }
 }
 
 /// Expand ${VAR} environment variables, with fallback to credentials store
@@ -320,6 +295,32 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_parse_remote_server_headers() {
+        let json = r#"{
+            "mcpServers": {
+                "docs": {
+                    "url": "https://mcp.example.com/mcp",
+                    "headers": {"X-Api-Key": "key123"}
+                }
+            }
+        }"#;
+
+        let config: McpConfig = serde_json::from_str(json).unwrap();
+        let servers = config.servers().await;
+        let Some(McpServerConfig::Remote {
+            url,
+            authorization_token,
+            headers,
+        }) = servers.get("docs")
+        else {
+            panic!("expected a remote server");
+        };
+        assert_eq!(url, "https://mcp.example.com/mcp");
+        assert!(authorization_token.is_none());
+        assert_eq!(headers.get("X-Api-Key").map(String::as_str), Some("key123"));
+    }
+
     #[tokio::test]
     async fn test_expand_env_var() {
         // Test that direct values pass through
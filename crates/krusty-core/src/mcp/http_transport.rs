@@ -0,0 +1,442 @@
+//! MCP HTTP transport for remote servers
+//!
+//! Speaks Streamable HTTP: every client message is POSTed to the server URL
+//! and replies come back either as a JSON body or as an SSE stream. Servers
+//! that only implement the older HTTP+SSE transport reject that first POST,
+//! in which case we open their SSE stream, wait for the `endpoint` event and
+//! POST messages there instead, reading replies from the stream.
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use futures::StreamExt;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Response, StatusCode};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex, RwLock};
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, warn};
+use url::Url;
+
+use super::transport::McpTransport;
+
+const SESSION_HEADER: &str = "mcp-session-id";
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a legacy SSE server has to announce its message endpoint
+const ENDPOINT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Which HTTP transport the server speaks
+#[derive(Debug, Clone)]
+enum Mode {
+    /// Decided by the response to the first POST
+    Unknown,
+    /// Streamable HTTP: POST to the server URL
+    Streamable,
+    /// Legacy HTTP+SSE: POST to the endpoint announced on the stream
+    Sse { endpoint: Url },
+}
+
+/// HTTP transport for remote MCP servers
+pub struct HttpTransport {
+    url: Url,
+    http: reqwest::Client,
+    mode: Mutex<Mode>,
+    session_id: RwLock<Option<String>>,
+    incoming_tx: mpsc::UnboundedSender<String>,
+    incoming_rx: Mutex<mpsc::UnboundedReceiver<String>>,
+    /// Cancelled when the server ends the session or its event stream
+    closed: CancellationToken,
+    /// Cancelled on drop to stop stream readers
+    shutdown: CancellationToken,
+}
+
+impl HttpTransport {
+    /// Create a transport for `url`
+    ///
+    /// `authorization_token` is sent as a bearer token and `headers` are
+    /// added to every request. Nothing is sent until the first message.
+    pub fn new(
+        url: &str,
+        authorization_token: Option<&str>,
+        headers: &HashMap<String, String>,
+    ) -> Result<Self> {
+        let url = Url::parse(url).with_context(|| format!("Invalid MCP server URL: {}", url))?;
+
+        let mut default_headers = HeaderMap::new();
+        for (name, value) in headers {
+            let name = HeaderName::from_bytes(name.as_bytes())
+                .with_context(|| format!("Invalid header name: {}", name))?;
+            let value = HeaderValue::from_str(value)
+                .with_context(|| format!("Invalid value for header {}", name))?;
+            default_headers.insert(name, value);
+        }
+        if let Some(token) = authorization_token.filter(|t| !t.is_empty()) {
+            let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+                .context("Invalid authorization token")?;
+            value.set_sensitive(true);
+            default_headers.insert(AUTHORIZATION, value);
+        }
+
+        let http = reqwest::Client::builder()
+            .default_headers(default_headers)
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()?;
+
+        info!("Using HTTP transport for MCP server at {}", url);
+
+        let (incoming_tx, incoming_rx) = mpsc::unbounded_channel();
+        Ok(Self {
+            url,
+            http,
+            mode: Mutex::new(Mode::Unknown),
+            session_id: RwLock::new(None),
+            incoming_tx,
+            incoming_rx: Mutex::new(incoming_rx),
+            closed: CancellationToken::new(),
+            shutdown: CancellationToken::new(),
+        })
+    }
+
+    /// POST a message to the Streamable HTTP endpoint
+    async fn post(&self, message: &str) -> Result<Response> {
+        let mut request = self
+            .http
+            .post(self.url.clone())
+            .header(ACCEPT, "application/json, text/event-stream")
+            .header(CONTENT_TYPE, "application/json")
+            .body(message.to_string());
+        if let Some(session_id) = self.session_id.read().await.as_deref() {
+            request = request.header(SESSION_HEADER, session_id);
+        }
+        request
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach MCP server at {}", self.url))
+    }
+
+    /// Queue the messages carried by a Streamable HTTP response
+    async fn handle_response(&self, response: Response) -> Result<()> {
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND && self.session_id.read().await.is_some() {
+            self.closed.cancel();
+            return Err(anyhow!("MCP session expired"));
+        }
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("MCP server returned {}: {}", status, body.trim()));
+        }
+
+        if let Some(session_id) = response
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            *self.session_id.write().await = Some(session_id.to_string());
+        }
+
+        let is_stream = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+
+        if is_stream {
+            // The reply arrives on the stream; read it in the background so
+            // the client can keep sending
+            let incoming = self.incoming_tx.clone();
+            let shutdown = self.shutdown.clone();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = shutdown.cancelled() => {}
+                    result = forward_events(response, incoming, None) => {
+                        if let Err(e) = result {
+                            warn!("MCP response stream error: {}", e);
+                        }
+                    }
+                }
+            });
+            return Ok(());
+        }
+
+        // 202 Accepted (notifications) has an empty body
+        let body = response.text().await?;
+        for message in split_messages(&body)? {
+            let _ = self.incoming_tx.send(message);
+        }
+        Ok(())
+    }
+
+    /// Open a legacy SSE stream and wait for its message endpoint
+    async fn open_sse_stream(&self) -> Result<Url> {
+        let response = self
+            .http
+            .get(self.url.clone())
+            .header(ACCEPT, "text/event-stream")
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach MCP server at {}", self.url))?;
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "MCP server rejected both Streamable HTTP and SSE ({})",
+                response.status()
+            ));
+        }
+
+        let (endpoint_tx, endpoint_rx) = oneshot::channel();
+        let incoming = self.incoming_tx.clone();
+        let closed = self.closed.clone();
+        let shutdown = self.shutdown.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                _ = shutdown.cancelled() => {}
+                result = forward_events(response, incoming, Some(endpoint_tx)) => {
+                    if let Err(e) = result {
+                        warn!("MCP event stream error: {}", e);
+                    }
+                    closed.cancel();
+                }
+            }
+        });
+
+        let endpoint = tokio::time::timeout(ENDPOINT_TIMEOUT, endpoint_rx)
+            .await
+            .map_err(|_| anyhow!("MCP server did not announce an SSE endpoint"))?
+            .map_err(|_| anyhow!("MCP event stream closed before announcing an endpoint"))?;
+        self.url
+            .join(endpoint.trim())
+            .with_context(|| format!("Invalid SSE endpoint: {}", endpoint))
+    }
+
+    /// POST a message to a legacy SSE endpoint (the reply comes on the stream)
+    async fn post_legacy(&self, endpoint: &Url, message: &str) -> Result<()> {
+        let response = self
+            .http
+            .post(endpoint.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(message.to_string())
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach MCP server at {}", endpoint))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(anyhow!("MCP server returned {}: {}", status, body.trim()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl McpTransport for HttpTransport {
+    async fn send(&self, message: &str) -> Result<()> {
+        if self.closed.is_cancelled() {
+            return Err(anyhow!("MCP connection closed"));
+        }
+        debug!("Sent: {}", message);
+
+        let mut mode = self.mode.lock().await;
+        match mode.clone() {
+            Mode::Streamable => {
+                drop(mode);
+                let response = self.post(message).await?;
+                self.handle_response(response).await
+            }
+            Mode::Sse { endpoint } => {
+                drop(mode);
+                self.post_legacy(&endpoint, message).await
+            }
+            Mode::Unknown => {
+                // Held until the transport is known so nothing else is sent
+                // to the wrong place
+                let response = self.post(message).await?;
+                if matches!(
+                    response.status(),
+                    StatusCode::BAD_REQUEST
+                        | StatusCode::NOT_FOUND
+                        | StatusCode::METHOD_NOT_ALLOWED
+                ) {
+                    info!(
+                        "MCP server at {} rejected Streamable HTTP ({}), trying SSE",
+                        self.url,
+                        response.status()
+                    );
+                    let endpoint = self.open_sse_stream().await?;
+                    *mode = Mode::Sse {
+                        endpoint: endpoint.clone(),
+                    };
+                    drop(mode);
+                    return self.post_legacy(&endpoint, message).await;
+                }
+                if response.status().is_success() {
+                    *mode = Mode::Streamable;
+                }
+                drop(mode);
+                self.handle_response(response).await
+            }
+        }
+    }
+
+    async fn receive(&self) -> Result<String> {
+        let mut incoming = self.incoming_rx.lock().await;
+        tokio::select! {
+            // Drain messages that arrived before the stream closed
+            biased;
+            message = incoming.recv() => {
+                let message = message.ok_or_else(|| anyhow!("MCP connection closed"))?;
+                debug!("Received: {}", message);
+                Ok(message)
+            }
+            _ = self.closed.cancelled() => Err(anyhow!("MCP server closed the connection")),
+        }
+    }
+
+    async fn is_alive(&self) -> bool {
+        !self.closed.is_cancelled()
+    }
+}
+
+impl Drop for HttpTransport {
+    fn drop(&mut self) {
+        self.shutdown.cancel();
+    }
+}
+
+/// Read SSE events from a response, queueing JSON-RPC messages
+///
+/// For legacy streams the first `endpoint` event is passed to `endpoint_tx`.
+async fn forward_events(
+    response: Response,
+    incoming: mpsc::UnboundedSender<String>,
+    mut endpoint_tx: Option<oneshot::Sender<String>>,
+) -> Result<()> {
+    let mut stream = response.bytes_stream();
+    let mut parser = SseParser::default();
+
+    while let Some(chunk) = stream.next().await {
+        for event in parser.feed(&chunk?) {
+            match event.event.as_deref() {
+                Some("endpoint") => {
+                    if let Some(tx) = endpoint_tx.take() {
+                        let _ = tx.send(event.data);
+                    }
+                }
+                None | Some("message") => {
+                    for message in split_messages(&event.data)? {
+                        if incoming.send(message).is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                Some(other) => debug!("Ignoring SSE event: {}", other),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Split a JSON-RPC message or batch into individual messages
+fn split_messages(body: &str) -> Result<Vec<String>> {
+    let body = body.trim();
+    if body.is_empty() {
+        return Ok(Vec::new());
+    }
+    let value: Value = serde_json::from_str(body).context("Invalid JSON from MCP server")?;
+    Ok(match value {
+        Value::Array(batch) => batch.iter().map(Value::to_string).collect(),
+        _ => vec![body.to_string()],
+    })
+}
+
+/// A dispatched server-sent event
+#[derive(Debug, PartialEq)]
+struct SseEvent {
+    event: Option<String>,
+    data: String,
+}
+
+/// Incremental `text/event-stream` parser
+#[derive(Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feed a chunk of the stream, returning the events it completed
+    fn feed(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let raw: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&raw);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                // Blank line dispatches the event
+                if self.data.is_empty() {
+                    self.event = None;
+                } else {
+                    events.push(SseEvent {
+                        event: self.event.take(),
+                        data: std::mem::take(&mut self.data).join("\n"),
+                    });
+                }
+                continue;
+            }
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.split_once(':') {
+                Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                None => (line, ""),
+            };
+            match field {
+                "event" => self.event = Some(value.to_string()),
+                "data" => self.data.push(value.to_string()),
+                _ => {}
+            }
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_parser_across_chunks() {
+        let mut parser = SseParser::default();
+        assert!(parser.feed(b"event: endpoint\r\nda").is_empty());
+        let events = parser.feed(b"ta: /messages?id=1\r\n\r\n: keep-alive\n\n");
+        assert_eq!(
+            events,
+            vec![SseEvent {
+                event: Some("endpoint".to_string()),
+                data: "/messages?id=1".to_string(),
+            }]
+        );
+
+        let events = parser.feed(b"data: {\"a\":\ndata: 1}\n\n");
+        assert_eq!(events[0].event, None);
+        assert_eq!(events[0].data, "{\"a\":\n1}");
+    }
+
+    #[test]
+    fn test_split_messages() {
+        assert!(split_messages("  ").unwrap().is_empty());
+        assert_eq!(
+            split_messages(r#"{"jsonrpc":"2.0","id":1}"#).unwrap(),
+            vec![r#"{"jsonrpc":"2.0","id":1}"#]
+        );
+        assert_eq!(
+            split_messages(r#"[{"id":1},{"id":2}]"#).unwrap(),
+            vec![r#"{"id":1}"#, r#"{"id":2}"#]
+        );
+        assert!(split_messages("not json").is_err());
+    }
+}
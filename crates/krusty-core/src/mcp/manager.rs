@@ -1,7 +1,7 @@
-//! MCP Manager - manages local MCP server connections
+//! MCP Manager - manages MCP server connections
 //!
-//! Simple manager for local stdio servers. Remote servers are handled
-//! by passing them to the Anthropic API's MCP Connector.
+//! Connects to local stdio servers and remote HTTP servers alike and
+//! exposes their tools.
 
 use anyhow::Result;
 use serde_json::Value;
@@ -9,11 +9,14 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 use super::client::McpClient;
-use super::config::{McpConfig, McpServerConfig, RemoteMcpServer};
-use super::protocol::{McpToolDef, McpToolResult};
+use super::config::{McpConfig, McpServerConfig};
+use super::protocol::{
+    McpPrompt, McpPromptResult, McpResource, McpResourceContents, McpResourceTemplate, McpToolDef,
+    McpToolResult,
+};
 
 /// Server status
 #[derive(Debug, Clone, PartialEq)]
@@ -37,21 +40,22 @@ impl std::fmt::Display for McpServerStatus {
 #[derive(Debug, Clone)]
 pub struct McpServerInfo {
     pub name: String,
-    pub server_type: String, // "stdio" or "remote"
+    pub server_type: String, // "stdio" or "http"
     pub status: McpServerStatus,
     pub tool_count: usize,
     pub tools: Vec<McpToolDef>,
+    pub resources: Vec<McpResource>,
+    pub resource_templates: Vec<McpResourceTemplate>,
+    pub prompts: Vec<McpPrompt>,
     pub error: Option<String>,
 }
 
 /// MCP Manager
 pub struct McpManager {
-    /// Connected local clients
+    /// Connected clients
     clients: RwLock<HashMap<String, Arc<McpClient>>>,
     /// Server configurations
     configs: RwLock<HashMap<String, McpServerConfig>>,
-    /// Remote servers (for API)
-    remote_servers: RwLock<Vec<RemoteMcpServer>>,
     /// Working directory
     working_dir: PathBuf,
 }
@@ -61,7 +65,6 @@ impl McpManager {
         Self {
             clients: RwLock::new(HashMap::new()),
             configs: RwLock::new(HashMap::new()),
-            remote_servers: RwLock::new(Vec::new()),
             working_dir,
         }
     }
@@ -73,9 +76,6 @@ impl McpManager {
         let mut configs = self.configs.write().await;
         *configs = config.servers().await;
 
-        // Store remote servers for API
-        *self.remote_servers.write().await = config.remote_servers_for_api().await;
-
         let local_count = configs.values().filter(|c| c.is_local()).count();
         let remote_count = configs.values().filter(|c| c.is_remote()).count();
 
@@ -87,30 +87,25 @@ impl McpManager {
         Ok(())
     }
 
-    /// Connect to all local servers in parallel
+    /// Add a server configuration that doesn't come from .mcp.json
+    pub async fn add_server(&self, name: impl Into<String>, config: McpServerConfig) {
+        self.configs.write().await.insert(name.into(), config);
+    }
+
+    /// Connect to all servers in parallel
     pub async fn connect_all(&self) -> Result<()> {
-        let configs: Vec<_> = {
-            let configs = self.configs.read().await;
-            configs
-                .iter()
-                .filter(|(_, c)| c.is_local())
-                .map(|(n, c)| (n.clone(), c.clone()))
-                .collect()
-        };
+        let names: Vec<_> = self.configs.read().await.keys().cloned().collect();
 
-        if configs.is_empty() {
+        if names.is_empty() {
             return Ok(());
         }
 
-        info!(
-            "Connecting to {} local MCP servers in parallel",
-            configs.len()
-        );
+        info!("Connecting to {} MCP servers in parallel", names.len());
 
         // Connect to all servers in parallel
-        let connect_futures: Vec<_> = configs
+        let connect_futures: Vec<_> = names
             .iter()
-            .map(|(name, _)| {
+            .map(|name| {
                 let name = name.clone();
                 async move {
                     info!("Attempting to connect to MCP server: {}", name);
@@ -130,7 +125,7 @@ impl McpManager {
         Ok(())
     }
 
-    /// Connect to a specific local server
+    /// Connect to a specific server
     pub async fn connect(&self, name: &str) -> Result<()> {
         let config = {
             let configs = self.configs.read().await;
@@ -141,13 +136,6 @@ impl McpManager {
             return Err(anyhow::anyhow!("Unknown server: {}", name));
         };
 
-        if config.is_remote() {
-            return Err(anyhow::anyhow!(
-                "Server {} is remote - handled by Anthropic API",
-                name
-            ));
-        }
-
         // Disconnect first if already connected
         self.disconnect(name).await;
 
@@ -160,6 +148,22 @@ impl McpManager {
         // Get tools
         client.list_tools().await?;
 
+        // Resources and prompts are optional; a server failing to list them
+        // is still usable for its tools
+        if client.supports_resources().await {
+            if let Err(e) = client.list_resources().await {
+                warn!("MCP {} failed to list resources: {}", name, e);
+            }
+            if let Err(e) = client.list_resource_templates().await {
+                debug!("MCP {} has no resource templates: {}", name, e);
+            }
+        }
+        if client.supports_prompts().await {
+            if let Err(e) = client.list_prompts().await {
+                warn!("MCP {} failed to list prompts: {}", name, e);
+            }
+        }
+
         let client = Arc::new(client);
         self.clients.write().await.insert(name.to_string(), client);
 
@@ -174,7 +178,20 @@ impl McpManager {
         }
     }
 
-    /// Get all tools from connected local servers
+    /// Disconnect from every server
+    pub async fn disconnect_all(&self) {
+        let count = {
+            let mut clients = self.clients.write().await;
+            let count = clients.len();
+            clients.clear();
+            count
+        };
+        if count > 0 {
+            info!("Disconnected from {} MCP servers", count);
+        }
+    }
+
+    /// Get all tools from connected servers
     pub async fn get_all_tools(&self) -> Vec<(String, McpToolDef)> {
         let clients = self.clients.read().await;
         let mut tools = Vec::new();
@@ -188,7 +205,7 @@ impl McpManager {
         tools
     }
 
-    /// Call a tool on a local server
+    /// Call a tool on a connected server
     pub async fn call_tool(
         &self,
         server: &str,
@@ -203,6 +220,61 @@ impl McpManager {
         client.call_tool(tool, arguments).await
     }
 
+    /// Get all resources from connected servers
+    pub async fn get_all_resources(&self) -> Vec<(String, McpResource)> {
+        let clients = self.clients.read().await;
+        let mut resources = Vec::new();
+
+        for (name, client) in clients.iter() {
+            for resource in client.get_resources().await {
+                resources.push((name.clone(), resource));
+            }
+        }
+
+        resources.sort_by(|a, b| (&a.0, &a.1.uri).cmp(&(&b.0, &b.1.uri)));
+        resources
+    }
+
+    /// Get all prompts from connected servers
+    pub async fn get_all_prompts(&self) -> Vec<(String, McpPrompt)> {
+        let clients = self.clients.read().await;
+        let mut prompts = Vec::new();
+
+        for (name, client) in clients.iter() {
+            for prompt in client.get_prompts().await {
+                prompts.push((name.clone(), prompt));
+            }
+        }
+
+        prompts.sort_by(|a, b| (&a.0, &a.1.name).cmp(&(&b.0, &b.1.name)));
+        prompts
+    }
+
+    /// Read a resource from a connected server
+    pub async fn read_resource(&self, server: &str, uri: &str) -> Result<Vec<McpResourceContents>> {
+        let client = self
+            .get_client(server)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Server not connected: {}", server))?;
+
+        client.read_resource(uri).await
+    }
+
+    /// Expand a prompt on a connected server
+    pub async fn get_prompt(
+        &self,
+        server: &str,
+        prompt: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<McpPromptResult> {
+        let client = self
+            .get_client(server)
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Server not connected: {}", server))?;
+
+        client.get_prompt(prompt, arguments).await
+    }
+
     /// Get server info for UI
     pub async fn list_servers(&self) -> Vec<McpServerInfo> {
         let configs = self.configs.read().await;
@@ -211,25 +283,31 @@ impl McpManager {
         let mut servers = Vec::new();
 
         for (name, config) in configs.iter() {
-            let (status, tool_count, tools, error) = if config.is_local() {
-                if let Some(client) = clients.get(name) {
-                    let t = client.get_tools().await;
-                    if client.is_alive().await {
-                        (McpServerStatus::Connected, t.len(), t, None)
-                    } else {
-                        (
-                            McpServerStatus::Error("Process died".to_string()),
-                            0,
-                            Vec::new(),
-                            Some("Process died".to_string()),
-                        )
-                    }
+            let mut resources = Vec::new();
+            let mut resource_templates = Vec::new();
+            let mut prompts = Vec::new();
+            let (status, tool_count, tools, error) = if let Some(client) = clients.get(name) {
+                let t = client.get_tools().await;
+                if client.is_alive().await {
+                    resources = client.get_resources().await;
+                    resource_templates = client.get_resource_templates().await;
+                    prompts = client.get_prompts().await;
+                    (McpServerStatus::Connected, t.len(), t, None)
                 } else {
-                    (McpServerStatus::Disconnected, 0, Vec::new(), None)
+                    let reason = if config.is_local() {
+                        "Process died"
+                    } else {
+                        "Connection lost"
+                    };
+                    (
+                        McpServerStatus::Error(reason.to_string()),
+                        0,
+                        Vec::new(),
+                        Some(reason.to_string()),
+                    )
                 }
             } else {
-                // Remote servers are always "connected" (handled by API)
-                (McpServerStatus::Connected, 0, Vec::new(), None)
+                (McpServerStatus::Disconnected, 0, Vec::new(), None)
             };
 
             servers.push(McpServerInfo {
@@ -238,6 +316,9 @@ impl McpManager {
                 status,
                 tool_count,
                 tools,
+                resources,
+                resource_templates,
+                prompts,
                 error,
             });
         }
@@ -246,11 +327,6 @@ impl McpManager {
         servers
     }
 
-    /// Get remote servers for Anthropic API
-    pub async fn get_remote_servers(&self) -> Vec<RemoteMcpServer> {
-        self.remote_servers.read().await.clone()
-    }
-
     /// Check if any servers are configured
     pub async fn has_servers(&self) -> bool {
         !self.configs.read().await.is_empty()
@@ -2,18 +2,22 @@
 //!
 //! Supports two types of MCP servers:
 //! - Local (stdio): We spawn the process and act as MCP client
-//! - Remote (url): Passed to Anthropic API's MCP Connector
+//! - Remote (url): We connect over Streamable HTTP, falling back to SSE
 //!
-//! Local servers are managed here. Remote servers are passed to the API.
+//! Both are connected client-side, so their tools work with every provider.
 
 mod client;
 mod config;
+mod http_transport;
 mod manager;
 mod protocol;
 pub mod tool;
 mod transport;
 
-pub use config::{McpConfig, McpServerConfig, RemoteMcpServer};
+pub use config::{McpConfig, McpServerConfig};
 pub use manager::{McpManager, McpServerInfo, McpServerStatus};
-pub use protocol::{McpContent, McpToolDef, McpToolResult};
+pub use protocol::{
+    McpContent, McpPrompt, McpPromptArgument, McpPromptResult, McpResource, McpResourceContents,
+    McpResourceTemplate, McpToolDef, McpToolResult,
+};
 pub use tool::McpTool;
@@ -4,6 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::HashMap;
 
 /// JSON-RPC request
 #[derive(Debug, Serialize)]
@@ -201,6 +202,142 @@ impl From<ToolCallResult> for McpToolResult {
     }
 }
 
+/// MCP resource from resources/list
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResource {
+    pub uri: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+/// MCP resource template from resources/templates/list
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResourceTemplate {
+    pub uri_template: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+}
+
+/// Resources list response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourcesListResult {
+    pub resources: Vec<McpResource>,
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Resource templates list response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceTemplatesListResult {
+    pub resource_templates: Vec<McpResourceTemplate>,
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Resource read params
+#[derive(Debug, Serialize)]
+pub struct ResourceReadParams {
+    pub uri: String,
+}
+
+/// Resource read response
+#[derive(Debug, Deserialize)]
+pub struct ResourceReadResult {
+    pub contents: Vec<McpResourceContents>,
+}
+
+/// Contents of a read resource (text or base64 blob)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResourceContents {
+    pub uri: String,
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub blob: Option<String>,
+}
+
+/// MCP prompt from prompts/list
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct McpPrompt {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub arguments: Vec<McpPromptArgument>,
+}
+
+/// Argument accepted by a prompt
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct McpPromptArgument {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Prompts list response
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptsListResult {
+    pub prompts: Vec<McpPrompt>,
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Prompt get params
+#[derive(Debug, Serialize)]
+pub struct PromptGetParams {
+    pub name: String,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, String>,
+}
+
+/// Prompt get response
+#[derive(Debug, Clone, Deserialize)]
+pub struct McpPromptResult {
+    #[serde(default)]
+    pub description: Option<String>,
+    pub messages: Vec<McpPromptMessage>,
+}
+
+/// A message in an expanded prompt
+#[derive(Debug, Clone, Deserialize)]
+pub struct McpPromptMessage {
+    pub role: String,
+    pub content: McpContent,
+}
+
+impl McpPromptResult {
+    /// Join the prompt's messages into a single text
+    pub fn text(&self) -> String {
+        self.messages
+            .iter()
+            .map(|m| m.content.to_string())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+}
+
+/// Pagination params for list requests
+#[derive(Debug, Serialize)]
+pub struct ListParams {
+    pub cursor: String,
+}
+
 /// Format MCP tool result for display
 pub fn format_mcp_result(result: &McpToolResult) -> String {
     result
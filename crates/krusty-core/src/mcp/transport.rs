@@ -1,9 +1,12 @@
-//! MCP stdio transport
+//! MCP transports
 //!
-//! Simple stdio transport for local MCP servers. Uses newline-delimited JSON.
-//! Each message is a JSON object followed by a newline.
+//! `McpTransport` moves raw JSON-RPC messages between the client and a
+//! server. Local servers use `StdioTransport` (newline-delimited JSON: each
+//! message is a JSON object followed by a newline). Remote servers use
+//! `HttpTransport`.
 
 use anyhow::{anyhow, Result};
+use async_trait::async_trait;
 use std::collections::HashMap;
 use std::path::Path;
 use std::process::Stdio;
@@ -11,6 +14,19 @@ use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 use tokio::sync::Mutex;
 
+/// A bidirectional channel of JSON-RPC messages
+#[async_trait]
+pub trait McpTransport: Send + Sync {
+    /// Send one JSON-RPC message
+    async fn send(&self, message: &str) -> Result<()>;
+
+    /// Wait for the next message from the server
+    async fn receive(&self) -> Result<String>;
+
+    /// Whether the server can still be reached
+    async fn is_alive(&self) -> bool;
+}
+
 /// Stdio transport for MCP servers
 pub struct StdioTransport {
     stdin: Mutex<ChildStdin>,
@@ -79,9 +95,12 @@ impl StdioTransport {
             child: Mutex::new(child),
         })
     }
+}
 
+#[async_trait]
+impl McpTransport for StdioTransport {
     /// Send a JSON-RPC message (newline-delimited JSON)
-    pub async fn send(&self, message: &str) -> Result<()> {
+    async fn send(&self, message: &str) -> Result<()> {
         let mut stdin = self.stdin.lock().await;
         stdin.write_all(message.as_bytes()).await?;
         stdin.write_all(b"\n").await?;
@@ -91,7 +110,7 @@ impl StdioTransport {
     }
 
     /// Receive a JSON-RPC message (newline-delimited JSON)
-    pub async fn receive(&self) -> Result<String> {
+    async fn receive(&self) -> Result<String> {
         let mut stdout = self.stdout.lock().await;
 
         loop {
@@ -131,7 +150,7 @@ impl StdioTransport {
     }
 
     /// Check if process is still running
-    pub async fn is_alive(&self) -> bool {
+    async fn is_alive(&self) -> bool {
         let mut child = self.child.lock().await;
         matches!(child.try_wait(), Ok(None))
     }
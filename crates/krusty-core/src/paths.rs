@@ -28,6 +28,11 @@ pub fn tokens_dir() -> PathBuf {
     config_dir().join("tokens")
 }
 
+/// Get the session traces directory (~/.krusty/traces)
+pub fn traces_dir() -> PathBuf {
+    config_dir().join("traces")
+}
+
 /// Get the plans directory (~/.krusty/plans)
 /// Used for storing plan files in plan mode
 pub fn plans_dir() -> PathBuf {
@@ -41,6 +46,16 @@ pub fn ensure_plans_dir() -> std::io::Result<PathBuf> {
     Ok(dir)
 }
 
+/// Get the user-defined providers file (~/.krusty/providers.json)
+pub fn custom_providers_path() -> PathBuf {
+    config_dir().join("providers.json")
+}
+
+/// Get the web tools settings file (~/.krusty/web.json)
+pub fn web_config_path() -> PathBuf {
+    config_dir().join("web.json")
+}
+
 /// Get the MCP keys file (~/.krusty/tokens/mcp_keys.json)
 /// Used for storing API keys for MCP servers
 pub fn mcp_keys_path() -> PathBuf {
@@ -2,14 +2,24 @@
 //!
 //! Tracks spawned background processes for visibility and control
 
+mod output;
+
+pub use output::{
+    LogChunk, OutputLine, OutputStream, ProcessOutput, SharedOutput, DEFAULT_BUFFER_LINES,
+};
+
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
-use tokio::process::Command;
-use tokio::sync::RwLock;
+use regex::Regex;
+use tokio::io::AsyncWriteExt;
+use tokio::process::{ChildStdin, Command};
+use tokio::sync::{Mutex, RwLock};
+
+use output::{capture, lock_output};
 
 pub type ProcessId = String;
 
@@ -70,8 +80,32 @@ impl ProcessInfo {
     }
 }
 
+/// Which part of the captured output to return
+#[derive(Debug, Clone, Copy)]
+pub enum LogQuery {
+    /// The last N lines
+    Tail(usize),
+    /// Lines starting at an absolute offset
+    Since { offset: u64, limit: Option<usize> },
+}
+
+/// Result of waiting for a pattern in process output
+#[derive(Debug, Clone)]
+pub enum WaitOutcome {
+    /// A line matched the pattern
+    Matched(OutputLine),
+    /// All output streams closed without a match
+    Exited { next_offset: u64 },
+    /// The timeout elapsed without a match
+    TimedOut { next_offset: u64 },
+}
+
 struct ProcessEntry {
     info: ProcessInfo,
+    /// Captured stdout/stderr (None for external processes)
+    output: Option<SharedOutput>,
+    /// Piped stdin for send_input
+    stdin: Option<Arc<Mutex<ChildStdin>>>,
     /// Keep handle alive to prevent task cancellation
     _handle: Option<tokio::task::JoinHandle<()>>,
 }
@@ -84,6 +118,10 @@ const DEFAULT_USER: &str = "default";
 pub struct ProcessRegistry {
     /// Outer key: user_id, Inner key: process_id
     processes: Arc<RwLock<HashMap<String, HashMap<ProcessId, ProcessEntry>>>>,
+    /// Lines of output kept in memory per process
+    buffer_lines: usize,
+    /// Directory for full output logs (spill-to-disk disabled when None)
+    log_dir: Option<PathBuf>,
 }
 
 impl Default for ProcessRegistry {
@@ -96,9 +134,37 @@ impl ProcessRegistry {
     pub fn new() -> Self {
         Self {
             processes: Arc::new(RwLock::new(HashMap::new())),
+            buffer_lines: DEFAULT_BUFFER_LINES,
+            log_dir: None,
         }
     }
 
+    /// Write each process's full output to `<dir>/<process_id>.log`
+    pub fn with_log_dir(mut self, dir: PathBuf) -> Self {
+        self.log_dir = Some(dir);
+        self
+    }
+
+    /// Set how many lines of output are kept in memory per process
+    pub fn with_buffer_lines(mut self, lines: usize) -> Self {
+        self.buffer_lines = lines;
+        self
+    }
+
+    /// Create the output buffer for a new process, spilling to disk if configured
+    fn new_output(&self, id: &str) -> ProcessOutput {
+        let Some(dir) = &self.log_dir else {
+            return ProcessOutput::new(self.buffer_lines);
+        };
+        let path = dir.join(format!("{}.log", id));
+        ProcessOutput::new(self.buffer_lines)
+            .with_spill_file(path)
+            .unwrap_or_else(|e| {
+                tracing::warn!(id = %id, "Failed to create process log file: {}", e);
+                ProcessOutput::new(self.buffer_lines)
+            })
+    }
+
     /// Get or create user's process map
     fn ensure_user_map<'a>(
         map: &'a mut HashMap<String, HashMap<ProcessId, ProcessEntry>>,
@@ -146,12 +212,22 @@ impl ProcessRegistry {
         };
 
         cmd.current_dir(&working_dir);
-        cmd.stdout(std::process::Stdio::null());
-        cmd.stderr(std::process::Stdio::null());
+        cmd.stdin(std::process::Stdio::piped());
+        cmd.stdout(std::process::Stdio::piped());
+        cmd.stderr(std::process::Stdio::piped());
 
-        let child = cmd.spawn()?;
+        let mut child = cmd.spawn()?;
         let pid = child.id();
 
+        let output: SharedOutput = Arc::new(std::sync::Mutex::new(self.new_output(&id)));
+        if let Some(stdout) = child.stdout.take() {
+            capture(stdout, OutputStream::Stdout, output.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            capture(stderr, OutputStream::Stderr, output.clone());
+        }
+        let stdin = child.stdin.take().map(|s| Arc::new(Mutex::new(s)));
+
         let info = ProcessInfo {
             id: id.clone(),
             command: command.clone(),
@@ -170,13 +246,13 @@ impl ProcessRegistry {
         let owner_id = user_id.to_string();
         let start_time = info.started_at;
         let handle = tokio::spawn(async move {
-            let result = child.wait_with_output().await;
+            let result = child.wait().await;
             let duration_ms = start_time.elapsed().as_millis() as u64;
 
             let status = match result {
-                Ok(output) => {
-                    let code = output.status.code().unwrap_or(-1);
-                    if output.status.success() {
+                Ok(exit_status) => {
+                    let code = exit_status.code().unwrap_or(-1);
+                    if exit_status.success() {
                         ProcessStatus::Completed {
                             exit_code: code,
                             duration_ms,
@@ -201,6 +277,8 @@ impl ProcessRegistry {
 
         let entry = ProcessEntry {
             info,
+            output: Some(output),
+            stdin,
             _handle: Some(handle),
         };
 
@@ -439,6 +517,125 @@ impl ProcessRegistry {
             .and_then(|user_map| user_map.get(id).map(|e| e.info.clone()))
     }
 
+    /// Find a process entry, optionally scoped to a user
+    fn find_entry<'a>(
+        map: &'a HashMap<String, HashMap<ProcessId, ProcessEntry>>,
+        user_id: Option<&str>,
+        id: &str,
+    ) -> Option<&'a ProcessEntry> {
+        match user_id {
+            Some(uid) => map.get(uid).and_then(|user_map| user_map.get(id)),
+            None => map.values().find_map(|user_map| user_map.get(id)),
+        }
+    }
+
+    /// Get the output buffer of a process
+    async fn output_handle(&self, user_id: Option<&str>, id: &str) -> Result<SharedOutput> {
+        let processes = self.processes.read().await;
+        let entry = Self::find_entry(&processes, user_id, id)
+            .ok_or_else(|| anyhow::anyhow!("Process not found"))?;
+        entry
+            .output
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("Output is not captured for this process"))
+    }
+
+    /// Read captured output (single-tenant compatibility, searches all users)
+    pub async fn logs(&self, id: &str, query: LogQuery) -> Result<LogChunk> {
+        self.read_logs(None, id, query).await
+    }
+
+    /// Read captured output for a specific user (multi-tenant)
+    pub async fn logs_for_user(
+        &self,
+        user_id: &str,
+        id: &str,
+        query: LogQuery,
+    ) -> Result<LogChunk> {
+        self.read_logs(Some(user_id), id, query).await
+    }
+
+    async fn read_logs(
+        &self,
+        user_id: Option<&str>,
+        id: &str,
+        query: LogQuery,
+    ) -> Result<LogChunk> {
+        let output = self.output_handle(user_id, id).await?;
+        let output = lock_output(&output);
+        Ok(match query {
+            LogQuery::Tail(n) => output.tail(n),
+            LogQuery::Since { offset, limit } => output.since(offset, limit),
+        })
+    }
+
+    /// Read output from `since` across all users (non-blocking, for UI polling)
+    pub fn try_logs(&self, id: &str, since: u64) -> Option<LogChunk> {
+        let processes = self.processes.try_read().ok()?;
+        let output = Self::find_entry(&processes, None, id)?.output.clone()?;
+        drop(processes);
+        let output = lock_output(&output);
+        Some(output.since(since, None))
+    }
+
+    /// Wait until a line matching `pattern` appears (single-tenant compatibility)
+    ///
+    /// Lines already buffered from `since` onwards are checked first.
+    pub async fn wait_for(
+        &self,
+        id: &str,
+        pattern: &Regex,
+        since: u64,
+        timeout: Duration,
+    ) -> Result<WaitOutcome> {
+        let output = self.output_handle(None, id).await?;
+        Ok(wait_on_output(output, pattern, since, timeout).await)
+    }
+
+    /// Wait until a line matching `pattern` appears for a specific user (multi-tenant)
+    pub async fn wait_for_for_user(
+        &self,
+        user_id: &str,
+        id: &str,
+        pattern: &Regex,
+        since: u64,
+        timeout: Duration,
+    ) -> Result<WaitOutcome> {
+        let output = self.output_handle(Some(user_id), id).await?;
+        Ok(wait_on_output(output, pattern, since, timeout).await)
+    }
+
+    /// Write to a process's stdin (single-tenant compatibility)
+    pub async fn send_input(&self, id: &str, input: &str) -> Result<()> {
+        self.write_stdin(None, id, input).await
+    }
+
+    /// Write to a process's stdin for a specific user (multi-tenant)
+    pub async fn send_input_for_user(&self, user_id: &str, id: &str, input: &str) -> Result<()> {
+        self.write_stdin(Some(user_id), id, input).await
+    }
+
+    async fn write_stdin(&self, user_id: Option<&str>, id: &str, input: &str) -> Result<()> {
+        let stdin = {
+            let processes = self.processes.read().await;
+            let entry = Self::find_entry(&processes, user_id, id)
+                .ok_or_else(|| anyhow::anyhow!("Process not found"))?;
+            if !entry.info.is_running() {
+                anyhow::bail!("Process not running");
+            }
+            entry
+                .stdin
+                .clone()
+                .ok_or_else(|| anyhow::anyhow!("Process does not accept input"))?
+        };
+
+        let mut stdin = stdin.lock().await;
+        stdin.write_all(input.as_bytes()).await?;
+        stdin.flush().await?;
+        tracing::debug!(id = %id, bytes = input.len(), "Sent input to process");
+        Ok(())
+    }
+
     /// Update process status (single-tenant compatibility, searches all users)
     pub async fn update_status(&self, id: &str, status: ProcessStatus) {
         let mut processes = self.processes.write().await;
@@ -536,6 +733,8 @@ impl ProcessRegistry {
         };
         let entry = ProcessEntry {
             info,
+            output: None,
+            stdin: None,
             _handle: None,
         };
         let mut processes = self.processes.write().await;
@@ -564,3 +763,140 @@ impl ProcessRegistry {
         }
     }
 }
+
+/// Scan output for `pattern`, waiting for new lines until closed or timed out
+async fn wait_on_output(
+    output: SharedOutput,
+    pattern: &Regex,
+    since: u64,
+    timeout: Duration,
+) -> WaitOutcome {
+    let deadline = tokio::time::Instant::now() + timeout;
+    let notify = lock_output(&output).notifier();
+    let mut offset = since;
+
+    loop {
+        // Register for wakeups before checking so a push in between isn't missed
+        let notified = notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        {
+            let output = lock_output(&output);
+            let chunk = output.since(offset, None);
+            if let Some(line) = chunk.lines.iter().find(|l| pattern.is_match(&l.text)) {
+                return WaitOutcome::Matched(line.clone());
+            }
+            offset = chunk.next_offset;
+            if output.is_closed() {
+                return WaitOutcome::Exited {
+                    next_offset: offset,
+                };
+            }
+        }
+
+        if tokio::time::timeout_at(deadline, notified).await.is_err() {
+            return WaitOutcome::TimedOut {
+                next_offset: offset,
+            };
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn registry() -> ProcessRegistry {
+        ProcessRegistry::new().with_buffer_lines(100)
+    }
+
+    #[tokio::test]
+    async fn test_captures_stdout_and_stderr() {
+        let registry = registry();
+        let id = registry
+            .spawn(
+                "echo out; echo err >&2".to_string(),
+                PathBuf::from("."),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let pattern = Regex::new("err").unwrap();
+        let outcome = registry
+            .wait_for(&id, &pattern, 0, Duration::from_secs(5))
+            .await
+            .unwrap();
+        let WaitOutcome::Matched(line) = outcome else {
+            panic!("expected match, got {:?}", outcome);
+        };
+        assert_eq!(line.stream, OutputStream::Stderr);
+
+        let chunk = registry.logs(&id, LogQuery::Tail(10)).await.unwrap();
+        assert!(chunk
+            .lines
+            .iter()
+            .any(|l| l.text == "out" && l.stream == OutputStream::Stdout));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_times_out_and_exits() {
+        let registry = registry();
+        let id = registry
+            .spawn("sleep 5".to_string(), PathBuf::from("."), None)
+            .await
+            .unwrap();
+        let pattern = Regex::new("never").unwrap();
+        let outcome = registry
+            .wait_for(&id, &pattern, 0, Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, WaitOutcome::TimedOut { .. }));
+        registry.kill(&id).await.unwrap();
+
+        let id = registry
+            .spawn("echo done".to_string(), PathBuf::from("."), None)
+            .await
+            .unwrap();
+        let outcome = registry
+            .wait_for(&id, &pattern, 0, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, WaitOutcome::Exited { next_offset: 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_send_input() {
+        let registry = registry();
+        let id = registry
+            .spawn("cat".to_string(), PathBuf::from("."), None)
+            .await
+            .unwrap();
+        registry.send_input(&id, "hello\n").await.unwrap();
+
+        let pattern = Regex::new("^hello$").unwrap();
+        let outcome = registry
+            .wait_for(&id, &pattern, 0, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, WaitOutcome::Matched(_)));
+        registry.kill(&id).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_external_process_has_no_output() {
+        let registry = registry();
+        registry
+            .register_external(
+                "ext".to_string(),
+                "vim".to_string(),
+                None,
+                None,
+                PathBuf::from("."),
+            )
+            .await;
+        assert!(registry.logs("ext", LogQuery::Tail(5)).await.is_err());
+        assert!(registry.send_input("ext", "x").await.is_err());
+    }
+}
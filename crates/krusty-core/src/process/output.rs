@@ -0,0 +1,302 @@
+//! Captured output for background processes
+//!
+//! Keeps a bounded ring buffer of recent lines per process, optionally
+//! spilling every line to a log file on disk so nothing is lost once the
+//! buffer wraps.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::sync::Notify;
+
+/// Default number of lines kept in memory per process
+pub const DEFAULT_BUFFER_LINES: usize = 2000;
+
+/// Longest single line kept (progress bars can emit huge lines without newlines)
+const MAX_LINE_BYTES: usize = 4096;
+
+/// Which stream a line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+impl OutputStream {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputStream::Stdout => "stdout",
+            OutputStream::Stderr => "stderr",
+        }
+    }
+}
+
+/// A single captured line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputLine {
+    /// Absolute line number since the process started (0-based)
+    pub offset: u64,
+    pub stream: OutputStream,
+    pub text: String,
+}
+
+/// A slice of captured output returned by queries
+#[derive(Debug, Clone, Default)]
+pub struct LogChunk {
+    pub lines: Vec<OutputLine>,
+    /// Offset to pass as `since` to continue reading
+    pub next_offset: u64,
+    /// Lines requested but already evicted from the ring buffer
+    pub dropped: u64,
+    /// Spill file holding the full log, if enabled
+    pub log_path: Option<PathBuf>,
+}
+
+/// Bounded line buffer for one process
+pub struct ProcessOutput {
+    lines: VecDeque<OutputLine>,
+    capacity: usize,
+    next_offset: u64,
+    open_streams: usize,
+    spill: Option<(PathBuf, File)>,
+    notify: Arc<Notify>,
+}
+
+impl ProcessOutput {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::new(),
+            capacity: capacity.max(1),
+            next_offset: 0,
+            open_streams: 0,
+            spill: None,
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Also append every line to a file at `path`
+    pub fn with_spill_file(mut self, path: PathBuf) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let file = File::create(&path)?;
+        self.spill = Some((path, file));
+        Ok(self)
+    }
+
+    /// Append a line, evicting the oldest when full
+    pub fn push(&mut self, stream: OutputStream, text: String) {
+        if let Some((_, file)) = &mut self.spill {
+            let prefix = match stream {
+                OutputStream::Stdout => "",
+                OutputStream::Stderr => "[stderr] ",
+            };
+            if let Err(e) = writeln!(file, "{}{}", prefix, text) {
+                tracing::warn!("Failed to write process log: {}", e);
+                self.spill = None;
+            }
+        }
+
+        if self.lines.len() == self.capacity {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(OutputLine {
+            offset: self.next_offset,
+            stream,
+            text,
+        });
+        self.next_offset += 1;
+        self.notify.notify_waiters();
+    }
+
+    /// Offset of the next line to be written
+    pub fn next_offset(&self) -> u64 {
+        self.next_offset
+    }
+
+    /// Offset of the oldest line still buffered
+    pub fn first_offset(&self) -> u64 {
+        self.lines
+            .front()
+            .map(|l| l.offset)
+            .unwrap_or(self.next_offset)
+    }
+
+    /// True once every captured stream has hit EOF
+    pub fn is_closed(&self) -> bool {
+        self.open_streams == 0
+    }
+
+    pub fn log_path(&self) -> Option<&PathBuf> {
+        self.spill.as_ref().map(|(path, _)| path)
+    }
+
+    /// Handle woken whenever a line is pushed or a stream closes
+    pub fn notifier(&self) -> Arc<Notify> {
+        self.notify.clone()
+    }
+
+    /// Lines from `since` onwards, at most `limit` of them (oldest first)
+    pub fn since(&self, since: u64, limit: Option<usize>) -> LogChunk {
+        let first = self.first_offset();
+        let start = since.clamp(first, self.next_offset);
+        let skip = (start - first) as usize;
+        let lines: Vec<OutputLine> = self
+            .lines
+            .iter()
+            .skip(skip)
+            .take(limit.unwrap_or(usize::MAX))
+            .cloned()
+            .collect();
+        let next_offset = lines.last().map(|l| l.offset + 1).unwrap_or(start);
+        LogChunk {
+            lines,
+            next_offset,
+            dropped: first.saturating_sub(since),
+            log_path: self.log_path().cloned(),
+        }
+    }
+
+    /// The last `n` buffered lines
+    pub fn tail(&self, n: usize) -> LogChunk {
+        let since = self.next_offset.saturating_sub(n as u64);
+        let mut chunk = self.since(since.max(self.first_offset()), None);
+        chunk.dropped = 0;
+        chunk
+    }
+
+    fn open_stream(&mut self) {
+        self.open_streams += 1;
+    }
+
+    fn close_stream(&mut self) {
+        self.open_streams = self.open_streams.saturating_sub(1);
+        self.notify.notify_waiters();
+    }
+}
+
+/// Shared handle to a process's output buffer
+pub type SharedOutput = Arc<Mutex<ProcessOutput>>;
+
+/// Lock an output buffer, recovering from a poisoned lock
+pub(crate) fn lock_output(output: &SharedOutput) -> MutexGuard<'_, ProcessOutput> {
+    output.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Spawn a task that reads `reader` line by line into `output`
+pub(crate) fn capture<R>(reader: R, stream: OutputStream, output: SharedOutput)
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    lock_output(&output).open_stream();
+
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(reader);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match reader.read_until(b'\n', &mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    let text = clean_line(&buf);
+                    lock_output(&output).push(stream, text);
+                }
+            }
+        }
+        lock_output(&output).close_stream();
+    });
+}
+
+/// Decode a raw line: lossy UTF-8, trailing newline and CRs stripped, length capped
+fn clean_line(raw: &[u8]) -> String {
+    let raw = raw.strip_suffix(b"\n").unwrap_or(raw);
+    let raw = raw.strip_suffix(b"\r").unwrap_or(raw);
+    // Keep only the last carriage-return segment, like a terminal would show
+    let raw = match raw.iter().rposition(|&b| b == b'\r') {
+        Some(pos) => &raw[pos + 1..],
+        None => raw,
+    };
+    let mut text = String::from_utf8_lossy(raw).into_owned();
+    if text.len() > MAX_LINE_BYTES {
+        let mut end = MAX_LINE_BYTES;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push('…');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(capacity: usize, count: usize) -> ProcessOutput {
+        let mut out = ProcessOutput::new(capacity);
+        for i in 0..count {
+            out.push(OutputStream::Stdout, format!("line {}", i));
+        }
+        out
+    }
+
+    #[test]
+    fn test_ring_buffer_evicts_oldest() {
+        let out = filled(3, 5);
+        let chunk = out.since(0, None);
+        let texts: Vec<_> = chunk.lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, vec!["line 2", "line 3", "line 4"]);
+        assert_eq!(chunk.dropped, 2);
+        assert_eq!(chunk.next_offset, 5);
+    }
+
+    #[test]
+    fn test_since_and_limit() {
+        let out = filled(10, 6);
+        let chunk = out.since(2, Some(2));
+        assert_eq!(chunk.lines[0].offset, 2);
+        assert_eq!(chunk.lines.len(), 2);
+        assert_eq!(chunk.next_offset, 4);
+        assert_eq!(chunk.dropped, 0);
+
+        let empty = out.since(6, None);
+        assert!(empty.lines.is_empty());
+        assert_eq!(empty.next_offset, 6);
+    }
+
+    #[test]
+    fn test_tail() {
+        let out = filled(4, 10);
+        let chunk = out.tail(2);
+        let texts: Vec<_> = chunk.lines.iter().map(|l| l.text.as_str()).collect();
+        assert_eq!(texts, vec!["line 8", "line 9"]);
+
+        // Asking for more than is buffered returns what's there
+        assert_eq!(out.tail(100).lines.len(), 4);
+    }
+
+    #[test]
+    fn test_spill_file_keeps_everything() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("logs").join("proc.log");
+        let mut out = ProcessOutput::new(1).with_spill_file(path.clone()).unwrap();
+        out.push(OutputStream::Stdout, "first".to_string());
+        out.push(OutputStream::Stderr, "second".to_string());
+
+        assert_eq!(out.since(0, None).lines.len(), 1);
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents, "first\n[stderr] second\n");
+    }
+
+    #[test]
+    fn test_clean_line() {
+        assert_eq!(clean_line(b"hello\r\n"), "hello");
+        assert_eq!(clean_line(b"10%\r50%\r100%\n"), "100%");
+        assert_eq!(clean_line(b"\xffok\n"), "\u{fffd}ok");
+        assert!(clean_line(&vec![b'a'; MAX_LINE_BYTES + 10]).ends_with('…'));
+    }
+}
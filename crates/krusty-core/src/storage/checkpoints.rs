@@ -0,0 +1,279 @@
+//! File checkpoints for undoing agent edits
+//!
+//! Before a file is first modified in a user turn, its previous contents are
+//! saved against that turn. Contents are stored content-addressed, so a file
+//! snapshotted in many turns without changing is stored once.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use super::database::Database;
+
+/// A user turn that modified files
+#[derive(Debug, Clone)]
+pub struct Checkpoint {
+    pub turn: usize,
+    /// Preview of the user message that started the turn
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+    /// Files modified during the turn
+    pub files: Vec<String>,
+}
+
+/// A file as it was at a checkpoint
+#[derive(Debug, Clone)]
+pub struct FileSnapshot {
+    pub path: PathBuf,
+    /// Contents, or `None` if the file did not exist
+    pub content: Option<Vec<u8>>,
+}
+
+/// Checkpoint storage for sessions
+pub struct CheckpointStore<'a> {
+    db: &'a Database,
+}
+
+impl<'a> CheckpointStore<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Save a file's contents before it is modified in `turn`
+    ///
+    /// Only the first snapshot of a file per turn is kept, since that is the
+    /// state the turn started from. Returns whether a snapshot was saved.
+    pub fn save_file(
+        &self,
+        session_id: &str,
+        turn: usize,
+        label: &str,
+        path: &str,
+        content: Option<&[u8]>,
+    ) -> Result<bool> {
+        let conn = self.db.conn();
+        conn.execute(
+            "INSERT OR IGNORE INTO checkpoint_turns (session_id, turn, label, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![session_id, turn as i64, label, Utc::now().to_rfc3339()],
+        )?;
+
+        let hash = match content {
+            Some(bytes) => {
+                let hash = format!("{:x}", Sha256::digest(bytes));
+                conn.execute(
+                    "INSERT OR IGNORE INTO checkpoint_blobs (hash, content) VALUES (?1, ?2)",
+                    params![hash, bytes],
+                )?;
+                Some(hash)
+            }
+            None => None,
+        };
+
+        let inserted = conn.execute(
+            "INSERT OR IGNORE INTO checkpoint_files (session_id, turn, file_path, blob_hash)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![session_id, turn as i64, path, hash],
+        )?;
+        Ok(inserted > 0)
+    }
+
+    /// List a session's checkpoints, oldest first
+    pub fn list(&self, session_id: &str) -> Result<Vec<Checkpoint>> {
+        let mut stmt = self.db.conn().prepare(
+            "SELECT t.turn, t.label, t.created_at, f.file_path
+             FROM checkpoint_turns t
+             JOIN checkpoint_files f ON f.session_id = t.session_id AND f.turn = t.turn
+             WHERE t.session_id = ?1
+             ORDER BY t.turn, f.file_path",
+        )?;
+        let rows = stmt.query_map([session_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)? as usize,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+
+        let mut checkpoints: Vec<Checkpoint> = Vec::new();
+        for row in rows {
+            let (turn, label, created_at, file_path) = row?;
+            match checkpoints.last_mut() {
+                Some(last) if last.turn == turn => last.files.push(file_path),
+                _ => checkpoints.push(Checkpoint {
+                    turn,
+                    label,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    files: vec![file_path],
+                }),
+            }
+        }
+        Ok(checkpoints)
+    }
+
+    /// Files as they were before `turn` started
+    ///
+    /// Covers every file modified in `turn` or later, each taken from the
+    /// earliest of those turns that touched it.
+    pub fn files_before(&self, session_id: &str, turn: usize) -> Result<Vec<FileSnapshot>> {
+        let mut stmt = self.db.conn().prepare(
+            "SELECT f.file_path, b.content
+             FROM checkpoint_files f
+             LEFT JOIN checkpoint_blobs b ON b.hash = f.blob_hash
+             WHERE f.session_id = ?1
+               AND f.turn = (
+                   SELECT MIN(turn) FROM checkpoint_files
+                   WHERE session_id = ?1 AND file_path = f.file_path AND turn >= ?2
+               )
+             ORDER BY f.file_path",
+        )?;
+        let files = stmt.query_map(params![session_id, turn as i64], |row| {
+            Ok(FileSnapshot {
+                path: PathBuf::from(row.get::<_, String>(0)?),
+                content: row.get(1)?,
+            })
+        })?;
+        files.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Most recent turn with a checkpoint
+    pub fn latest_turn(&self, session_id: &str) -> Result<Option<usize>> {
+        let turn: Option<i64> = self
+            .db
+            .conn()
+            .query_row(
+                "SELECT MAX(turn) FROM checkpoint_turns WHERE session_id = ?1",
+                [session_id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .flatten();
+        Ok(turn.map(|t| t as usize))
+    }
+
+    /// Drop checkpoints for `turn` and every later turn
+    ///
+    /// Called after restoring to `turn`, since those edits are now undone.
+    pub fn discard_from(&self, session_id: &str, turn: usize) -> Result<()> {
+        let conn = self.db.conn();
+        conn.execute(
+            "DELETE FROM checkpoint_turns WHERE session_id = ?1 AND turn >= ?2",
+            params![session_id, turn as i64],
+        )?;
+        self.prune_blobs()
+    }
+
+    /// Delete all checkpoints for a session
+    pub fn delete_session(&self, session_id: &str) -> Result<()> {
+        self.db.conn().execute(
+            "DELETE FROM checkpoint_turns WHERE session_id = ?1",
+            [session_id],
+        )?;
+        self.prune_blobs()
+    }
+
+    /// Remove blobs no checkpoint refers to anymore
+    fn prune_blobs(&self) -> Result<()> {
+        self.db.conn().execute(
+            "DELETE FROM checkpoint_blobs WHERE hash NOT IN
+             (SELECT blob_hash FROM checkpoint_files WHERE blob_hash IS NOT NULL)",
+            [],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SessionManager;
+    use tempfile::TempDir;
+
+    fn setup() -> (SessionManager, String, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(&temp.path().join("test.db")).unwrap();
+        let sessions = SessionManager::new(db);
+        let id = sessions.create_session("test", None, None).unwrap();
+        (sessions, id, temp)
+    }
+
+    #[test]
+    fn test_first_snapshot_per_turn_wins() {
+        let (sessions, id, _temp) = setup();
+        let store = CheckpointStore::new(sessions.db());
+
+        assert!(store
+            .save_file(&id, 1, "fix it", "/a.rs", Some(b"one"))
+            .unwrap());
+        assert!(!store
+            .save_file(&id, 1, "fix it", "/a.rs", Some(b"two"))
+            .unwrap());
+        store.save_file(&id, 1, "fix it", "/b.rs", None).unwrap();
+
+        let checkpoints = store.list(&id).unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].label, "fix it");
+        assert_eq!(checkpoints[0].files, vec!["/a.rs", "/b.rs"]);
+
+        let files = store.files_before(&id, 1).unwrap();
+        assert_eq!(files[0].content.as_deref(), Some(&b"one"[..]));
+        assert_eq!(files[1].content, None);
+    }
+
+    #[test]
+    fn test_files_before_uses_earliest_later_turn() {
+        let (sessions, id, _temp) = setup();
+        let store = CheckpointStore::new(sessions.db());
+
+        store
+            .save_file(&id, 1, "first", "/a.rs", Some(b"v0"))
+            .unwrap();
+        store
+            .save_file(&id, 2, "second", "/a.rs", Some(b"v1"))
+            .unwrap();
+        store
+            .save_file(&id, 3, "third", "/b.rs", Some(b"b2"))
+            .unwrap();
+
+        let files = store.files_before(&id, 2).unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, PathBuf::from("/a.rs"));
+        assert_eq!(files[0].content.as_deref(), Some(&b"v1"[..]));
+        assert_eq!(files[1].content.as_deref(), Some(&b"b2"[..]));
+
+        assert_eq!(store.latest_turn(&id).unwrap(), Some(3));
+        store.discard_from(&id, 2).unwrap();
+        assert_eq!(store.latest_turn(&id).unwrap(), Some(1));
+        assert_eq!(store.list(&id).unwrap().len(), 1);
+
+        // Only the blob still referenced by turn 1 remains
+        let blobs: i64 = sessions
+            .db()
+            .conn()
+            .query_row("SELECT COUNT(*) FROM checkpoint_blobs", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(blobs, 1);
+    }
+
+    #[test]
+    fn test_deleting_session_removes_checkpoints() {
+        let (sessions, id, _temp) = setup();
+        let store = CheckpointStore::new(sessions.db());
+        store
+            .save_file(&id, 1, "edit", "/a.rs", Some(b"x"))
+            .unwrap();
+
+        sessions.delete_session(&id).unwrap();
+        let store = CheckpointStore::new(sessions.db());
+        assert!(store.list(&id).unwrap().is_empty());
+        assert_eq!(store.latest_turn(&id).unwrap(), None);
+    }
+}
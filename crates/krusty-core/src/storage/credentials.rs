@@ -130,6 +130,16 @@ impl CredentialStore {
         self.keys.get(provider.storage_key())
     }
 
+    /// Get the API key to send for a provider, without OAuth fallback
+    ///
+    /// Providers that need no key get an empty one.
+    pub fn api_key(&self, provider: &ProviderId) -> Option<String> {
+        match self.get(provider) {
+            Some(key) => Some(key.clone()),
+            None => (!provider.requires_api_key()).then(String::new),
+        }
+    }
+
     /// Set API key for a provider
     pub fn set(&mut self, provider: ProviderId, key: String) {
         self.keys.insert(provider.storage_key().to_string(), key);
@@ -141,10 +151,12 @@ impl CredentialStore {
     }
 
     /// Get all providers with stored API keys
+    ///
+    /// Custom providers that need no key count as configured.
     pub fn configured_providers(&self) -> Vec<ProviderId> {
         ProviderId::all()
             .iter()
-            .filter(|p| self.has_key(p))
+            .filter(|p| self.has_key(p) || !p.requires_api_key())
             .copied()
             .collect()
     }
@@ -156,7 +168,8 @@ impl CredentialStore {
 
     /// Get authentication credential (API key or OAuth token) for a provider
     ///
-    /// This checks API keys first, then falls back to OAuth tokens.
+    /// This checks API keys first, then falls back to OAuth tokens. Providers
+    /// that need no key get an empty credential.
     /// Returns the credential string suitable for use in Authorization headers.
     pub fn get_auth(&self, provider: &ProviderId) -> Option<String> {
         // Try API key first
@@ -176,6 +189,11 @@ impl CredentialStore {
             }
         }
 
+        // Local endpoints without authentication
+        if !provider.requires_api_key() {
+            return Some(String::new());
+        }
+
         None
     }
 
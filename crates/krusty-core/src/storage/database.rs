@@ -7,7 +7,7 @@ use std::sync::{Arc, Mutex};
 use tracing::info;
 
 /// Current schema version
-const SCHEMA_VERSION: i32 = 12;
+const SCHEMA_VERSION: i32 = 22;
 
 /// Shared database handle for connection reuse
 ///
@@ -530,6 +530,244 @@ impl Database {
             self.set_schema_version_tx(&tx, 12)?;
         }
 
+        // Migration 13: Tool permission rules
+        if current_version < 13 {
+            info!("Running migration 13: Tool permission rules");
+            tx.execute_batch(
+                r#"
+                -- Persisted allow/ask/deny rules for tool execution
+                -- action: allow, ask, deny
+                -- scope: project (requires project_path) or global
+                -- Session-scoped rules are kept in memory only
+                CREATE TABLE IF NOT EXISTS permission_rules (
+                    id TEXT PRIMARY KEY,
+                    tool TEXT NOT NULL,
+                    path_glob TEXT,
+                    command_prefix TEXT,
+                    action TEXT NOT NULL,
+                    scope TEXT NOT NULL,
+                    project_path TEXT,
+                    created_at TEXT NOT NULL,
+                    user_id TEXT REFERENCES users(id)
+                );
+
+                CREATE INDEX IF NOT EXISTS idx_permission_rules_project
+                    ON permission_rules(project_path);
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 13)?;
+        }
+
+        // Migration 14: Incremental codebase indexing
+        if current_version < 14 {
+            info!("Running migration 14: Incremental codebase indexing");
+            tx.execute_batch(
+                r#"
+                -- Content hash of the file each symbol came from
+                ALTER TABLE codebase_index ADD COLUMN content_hash TEXT;
+
+                -- Every indexed file with its content hash, including files
+                -- that produced no symbols, so unchanged files can be skipped
+                CREATE TABLE IF NOT EXISTS codebase_files (
+                    codebase_id TEXT NOT NULL REFERENCES codebases(id) ON DELETE CASCADE,
+                    file_path TEXT NOT NULL,
+                    content_hash TEXT NOT NULL,
+                    indexed_at TEXT NOT NULL,
+                    PRIMARY KEY (codebase_id, file_path)
+                );
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 14)?;
+        }
+
+        // Migration 15: File checkpoints
+        if current_version < 15 {
+            info!("Running migration 15: File checkpoints");
+            tx.execute_batch(
+                r#"
+                -- File contents, stored once per distinct content
+                CREATE TABLE IF NOT EXISTS checkpoint_blobs (
+                    hash TEXT PRIMARY KEY,
+                    content BLOB NOT NULL
+                );
+
+                -- One row per user turn that modified files
+                CREATE TABLE IF NOT EXISTS checkpoint_turns (
+                    session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                    turn INTEGER NOT NULL,
+                    label TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    PRIMARY KEY (session_id, turn)
+                );
+
+                -- Each file as it was before its first modification in a turn
+                -- blob_hash is NULL when the file did not exist yet
+                CREATE TABLE IF NOT EXISTS checkpoint_files (
+                    session_id TEXT NOT NULL,
+                    turn INTEGER NOT NULL,
+                    file_path TEXT NOT NULL,
+                    blob_hash TEXT REFERENCES checkpoint_blobs(hash),
+                    PRIMARY KEY (session_id, turn, file_path),
+                    FOREIGN KEY (session_id, turn)
+                        REFERENCES checkpoint_turns(session_id, turn) ON DELETE CASCADE
+                );
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 15)?;
+        }
+
+        // Migration 16: Per-request cost accounting in usage_tracking
+        // Rebuilt so user_id is optional (local usage has no user) and each
+        // row carries its session, model, token counts and cost
+        if current_version < 16 {
+            info!("Running migration 16: Cost accounting");
+            tx.execute_batch(
+                r#"
+                CREATE TABLE usage_tracking_new (
+                    id TEXT PRIMARY KEY,
+                    workspace_id TEXT REFERENCES workspaces(id) ON DELETE CASCADE,
+                    user_id TEXT REFERENCES users(id),
+                    resource_type TEXT NOT NULL,
+                    resource_id TEXT,
+                    quantity INTEGER NOT NULL DEFAULT 1,
+                    metadata TEXT,
+                    period_start TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    session_id TEXT,
+                    provider TEXT,
+                    model TEXT,
+                    source TEXT,
+                    input_tokens INTEGER NOT NULL DEFAULT 0,
+                    output_tokens INTEGER NOT NULL DEFAULT 0,
+                    cache_read_tokens INTEGER NOT NULL DEFAULT 0,
+                    cache_write_tokens INTEGER NOT NULL DEFAULT 0,
+                    cost_usd REAL
+                );
+
+                INSERT INTO usage_tracking_new (id, workspace_id, user_id, resource_type,
+                    resource_id, quantity, metadata, period_start, created_at)
+                SELECT id, workspace_id, user_id, resource_type, resource_id, quantity,
+                    metadata, period_start, created_at
+                FROM usage_tracking;
+
+                DROP TABLE usage_tracking;
+                ALTER TABLE usage_tracking_new RENAME TO usage_tracking;
+
+                CREATE INDEX IF NOT EXISTS idx_usage_workspace_period ON usage_tracking(workspace_id, period_start);
+                CREATE INDEX IF NOT EXISTS idx_usage_user_period ON usage_tracking(user_id, period_start);
+                CREATE INDEX IF NOT EXISTS idx_usage_session ON usage_tracking(session_id);
+                CREATE INDEX IF NOT EXISTS idx_usage_period ON usage_tracking(period_start);
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 16)?;
+        }
+
+        // Migration 17: Full-text search over messages
+        // Indexed text is extracted from the content JSON in Rust, so the
+        // index is filled on save (and backfilled here) rather than by triggers
+        if current_version < 17 {
+            info!("Running migration 17: Message search index");
+            tx.execute_batch(
+                r#"
+                CREATE VIRTUAL TABLE IF NOT EXISTS messages_fts USING fts5(
+                    body,
+                    tokenize = 'porter unicode61'
+                );
+
+                CREATE TRIGGER IF NOT EXISTS messages_fts_delete
+                AFTER DELETE ON messages BEGIN
+                    DELETE FROM messages_fts WHERE rowid = old.id;
+                END;
+                "#,
+            )?;
+            let indexed = super::search::index_existing_messages(&tx)?;
+            info!("Indexed {} existing messages for search", indexed);
+            self.set_schema_version_tx(&tx, 17)?;
+        }
+
+        if current_version < 18 {
+            info!("Running migration 18: Session forks");
+            tx.execute_batch(
+                r#"
+                -- Number of parent messages a fork starts from (NULL if not a fork)
+                ALTER TABLE sessions ADD COLUMN fork_message_count INTEGER;
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 18)?;
+        }
+
+        // Migration 19: Keyword index over code symbols
+        // Like messages_fts, rows are written by the indexer (identifiers are
+        // split into words in Rust) and removed by a trigger
+        if current_version < 19 {
+            info!("Running migration 19: Symbol search index");
+            tx.execute_batch(
+                r#"
+                ALTER TABLE codebase_index ADD COLUMN doc TEXT;
+
+                CREATE VIRTUAL TABLE IF NOT EXISTS codebase_index_fts USING fts5(
+                    symbol_name,
+                    symbol_path,
+                    file_path,
+                    signature,
+                    doc,
+                    tokenize = "porter unicode61 tokenchars '_'"
+                );
+
+                CREATE TRIGGER IF NOT EXISTS codebase_index_fts_delete
+                AFTER DELETE ON codebase_index BEGIN
+                    DELETE FROM codebase_index_fts WHERE rowid = old.id;
+                END;
+                "#,
+            )?;
+            let indexed = crate::index::retrieval::index_existing_symbols(&tx)?;
+            info!("Indexed {} existing symbols for keyword search", indexed);
+            self.set_schema_version_tx(&tx, 19)?;
+        }
+
+        if current_version < 20 {
+            info!("Running migration 20: Trait implementations");
+            tx.execute_batch(
+                r#"
+                -- Trait of an `impl Trait for Type` block
+                ALTER TABLE codebase_index ADD COLUMN implements TEXT;
+                CREATE INDEX IF NOT EXISTS idx_codebase_index_implements ON codebase_index(implements);
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 20)?;
+        }
+
+        if current_version < 21 {
+            info!("Running migration 21: Symbol summaries");
+            tx.execute_batch(
+                r#"
+                -- Hash of a symbol's source, set for symbols that get summaries
+                ALTER TABLE codebase_index ADD COLUMN summary_hash TEXT;
+                CREATE INDEX IF NOT EXISTS idx_codebase_index_summary_hash ON codebase_index(summary_hash);
+
+                -- Model-written summaries, reused while the symbol's source is unchanged
+                CREATE TABLE IF NOT EXISTS symbol_summaries (
+                    hash TEXT PRIMARY KEY,
+                    summary TEXT NOT NULL,
+                    model TEXT NOT NULL,
+                    created_at TEXT NOT NULL
+                );
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 21)?;
+        }
+
+        if current_version < 22 {
+            info!("Running migration 22: Pinned insights");
+            tx.execute_batch(
+                r#"
+                -- Pinned insights are always injected and survive /init purges
+                ALTER TABLE codebase_insights ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;
+                "#,
+            )?;
+            self.set_schema_version_tx(&tx, 22)?;
+        }
+
         tx.commit()?;
 
         info!("Migrations complete");
@@ -0,0 +1,593 @@
+//! Session export and import
+//!
+//! A [`SessionBundle`] holds everything stored for a session (messages,
+//! block UI state, plan and file activity) so it can be recreated in another
+//! database. Bundles also render to a readable Markdown transcript.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::block_ui::BlockUiStore;
+use super::database::Database;
+use super::file_activity::FileActivityTracker;
+use crate::ai::types::Content;
+
+/// Bundle format version, bumped on incompatible changes
+pub const BUNDLE_VERSION: u32 = 1;
+
+/// Tool output lines kept in Markdown transcripts
+const MAX_OUTPUT_LINES: usize = 200;
+
+/// Everything stored for one session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionBundle {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub session: BundleSession,
+    pub messages: Vec<BundleMessage>,
+    #[serde(default)]
+    pub block_ui: Vec<BundleBlockUi>,
+    #[serde(default)]
+    pub plan: Option<BundlePlan>,
+    #[serde(default)]
+    pub file_activity: Vec<BundleFileActivity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleSession {
+    /// ID in the exporting database (imports get a fresh one)
+    pub id: String,
+    pub title: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub model: Option<String>,
+    pub working_dir: Option<String>,
+    pub token_count: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleMessage {
+    pub role: String,
+    /// Stored content: content blocks, or a string for legacy plain text
+    pub content: Value,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleBlockUi {
+    pub block_id: String,
+    pub collapsed: bool,
+    pub scroll_offset: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundlePlan {
+    pub title: String,
+    pub status: String,
+    /// Plan file markdown
+    pub content: String,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleFileActivity {
+    pub file_path: String,
+    pub read_count: usize,
+    pub write_count: usize,
+    pub edit_count: usize,
+    pub last_accessed: DateTime<Utc>,
+    pub user_referenced: bool,
+}
+
+impl SessionBundle {
+    /// Collect a session from the database
+    pub fn export(db: &Database, session_id: &str) -> Result<Self> {
+        let session = db
+            .conn()
+            .query_row(
+                "SELECT id, title, created_at, updated_at, model, working_dir, token_count
+                 FROM sessions WHERE id = ?1",
+                [session_id],
+                |row| {
+                    Ok(BundleSession {
+                        id: row.get(0)?,
+                        title: row.get(1)?,
+                        created_at: row.get(2)?,
+                        updated_at: row.get(3)?,
+                        model: row.get(4)?,
+                        working_dir: row.get(5)?,
+                        token_count: row.get(6)?,
+                    })
+                },
+            )
+            .optional()?
+            .with_context(|| format!("Session {} not found", session_id))?;
+
+        let mut stmt = db.conn().prepare(
+            "SELECT role, content, created_at FROM messages WHERE session_id = ?1 ORDER BY id",
+        )?;
+        let messages = stmt
+            .query_map([session_id], |row| {
+                let content: String = row.get(1)?;
+                Ok(BundleMessage {
+                    role: row.get(0)?,
+                    content: serde_json::from_str(&content).unwrap_or(Value::String(content)),
+                    created_at: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let block_ui = BlockUiStore::new(db)
+            .load_block_ui_states(session_id)
+            .into_iter()
+            .map(|s| BundleBlockUi {
+                block_id: s.block_id,
+                collapsed: s.collapsed,
+                scroll_offset: s.scroll_offset,
+            })
+            .collect();
+
+        let plan = db
+            .conn()
+            .query_row(
+                "SELECT title, status, content, created_at, updated_at
+                 FROM plans WHERE session_id = ?1",
+                [session_id],
+                |row| {
+                    Ok(BundlePlan {
+                        title: row.get(0)?,
+                        status: row.get(1)?,
+                        content: row.get(2)?,
+                        created_at: row.get(3)?,
+                        updated_at: row.get(4)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        let file_activity = FileActivityTracker::new(db, session_id.to_string())
+            .get_all_activities()?
+            .into_iter()
+            .map(|a| BundleFileActivity {
+                file_path: a.file_path,
+                read_count: a.read_count,
+                write_count: a.write_count,
+                edit_count: a.edit_count,
+                last_accessed: a.last_accessed,
+                user_referenced: a.user_referenced,
+            })
+            .collect();
+
+        Ok(Self {
+            version: BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            session,
+            messages,
+            block_ui,
+            plan,
+            file_activity,
+        })
+    }
+
+    /// Parse a bundle, rejecting versions this build doesn't understand
+    pub fn from_json(json: &str) -> Result<Self> {
+        let bundle: Self = serde_json::from_str(json).context("Invalid session bundle")?;
+        if bundle.version > BUNDLE_VERSION {
+            bail!(
+                "Session bundle version {} is newer than supported ({}); update Krusty",
+                bundle.version,
+                BUNDLE_VERSION
+            );
+        }
+        Ok(bundle)
+    }
+
+    /// Recreate the session as a new session, returning its ID
+    ///
+    /// `working_dir` overrides the recorded directory, e.g. to make the
+    /// session show up under the importing machine's checkout.
+    pub fn import(&self, db: &Database, working_dir: Option<&str>) -> Result<String> {
+        let id = uuid::Uuid::new_v4().to_string();
+        let working_dir = working_dir.or(self.session.working_dir.as_deref());
+        let tx = db.conn().unchecked_transaction()?;
+
+        tx.execute(
+            "INSERT INTO sessions (id, title, created_at, updated_at, model, working_dir, token_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                id,
+                self.session.title,
+                self.session.created_at,
+                self.session.updated_at,
+                self.session.model,
+                working_dir,
+                self.session.token_count
+            ],
+        )?;
+
+        for message in &self.messages {
+            let content = match &message.content {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            tx.execute(
+                "INSERT INTO messages (session_id, role, content, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id, message.role, content, message.created_at],
+            )?;
+            super::search::index_message(&tx, tx.last_insert_rowid(), &content)?;
+        }
+
+        for state in &self.block_ui {
+            tx.execute(
+                "INSERT OR REPLACE INTO block_ui_state (session_id, block_id, block_type, collapsed, scroll_offset)
+                 VALUES (?1, ?2, '', ?3, ?4)",
+                params![id, state.block_id, state.collapsed as i32, state.scroll_offset as i32],
+            )?;
+        }
+
+        if let Some(plan) = &self.plan {
+            tx.execute(
+                "INSERT INTO plans (id, session_id, title, status, content, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    uuid::Uuid::new_v4().to_string(),
+                    id,
+                    plan.title,
+                    plan.status,
+                    plan.content,
+                    plan.created_at,
+                    plan.updated_at
+                ],
+            )?;
+        }
+
+        for activity in &self.file_activity {
+            tx.execute(
+                "INSERT INTO file_activity (session_id, file_path, read_count, write_count,
+                    edit_count, last_accessed, user_referenced)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    id,
+                    activity.file_path,
+                    activity.read_count as i64,
+                    activity.write_count as i64,
+                    activity.edit_count as i64,
+                    activity.last_accessed.to_rfc3339(),
+                    activity.user_referenced as i32
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        tracing::info!(
+            session_id = %id,
+            source_id = %self.session.id,
+            messages = self.messages.len(),
+            "Imported session"
+        );
+        Ok(id)
+    }
+
+    /// Render a readable transcript
+    ///
+    /// Thinking, tool calls and tool output are folded into `<details>`
+    /// blocks; edits are shown as diffs.
+    pub fn to_markdown(&self) -> String {
+        let mut out = format!("# {}\n\n", self.session.title);
+        if let Some(model) = &self.session.model {
+            out.push_str(&format!("- **Model:** {}\n", model));
+        }
+        if let Some(dir) = &self.session.working_dir {
+            out.push_str(&format!("- **Directory:** `{}`\n", dir));
+        }
+        out.push_str(&format!("- **Started:** {}\n", self.session.created_at));
+        out.push_str(&format!(
+            "- **Exported:** {}\n\n---\n",
+            self.exported_at.to_rfc3339()
+        ));
+
+        let mut tool_names = std::collections::HashMap::new();
+        let mut last_speaker: Option<&str> = None;
+        for message in &self.messages {
+            let blocks = message_blocks(&message.content);
+
+            // Tool results continue the assistant's turn rather than start a user one
+            let only_results = blocks
+                .iter()
+                .all(|b| matches!(b, Content::ToolResult { .. }));
+            if !only_results && last_speaker != Some(message.role.as_str()) {
+                let heading = match message.role.as_str() {
+                    "user" => "User",
+                    "assistant" => "Assistant",
+                    "system" => "System",
+                    other => other,
+                };
+                out.push_str(&format!("\n## {}\n\n", heading));
+                last_speaker = Some(message.role.as_str());
+            }
+
+            for block in &blocks {
+                match block {
+                    Content::Text { text } => {
+                        if text != "." {
+                            out.push_str(text.trim_end());
+                            out.push_str("\n\n");
+                        }
+                    }
+                    Content::Thinking { thinking, .. } => {
+                        push_details(
+                            &mut out,
+                            "Thinking",
+                            None,
+                            &format!("{}\n", thinking.trim()),
+                        );
+                    }
+                    Content::RedactedThinking { .. } => {
+                        out.push_str("*(redacted thinking)*\n\n");
+                    }
+                    Content::ToolUse { id, name, input } => {
+                        tool_names.insert(id.clone(), name.clone());
+                        push_details(
+                            &mut out,
+                            &format!("Tool: {}", name),
+                            tool_target(input).as_deref(),
+                            &tool_input_markdown(name, input),
+                        );
+                    }
+                    Content::ToolResult {
+                        tool_use_id,
+                        output,
+                        is_error,
+                    } => {
+                        let name = tool_names
+                            .get(tool_use_id)
+                            .map(String::as_str)
+                            .unwrap_or("tool");
+                        let label = if is_error.unwrap_or(false) {
+                            format!("Error from {}", name)
+                        } else {
+                            format!("Output of {}", name)
+                        };
+                        let text = match output {
+                            Value::String(s) => s.clone(),
+                            other => serde_json::to_string_pretty(other).unwrap_or_default(),
+                        };
+                        push_details(&mut out, &label, None, &fenced("", &truncate_lines(&text)));
+                    }
+                    Content::Image { .. } => out.push_str("*(image)*\n\n"),
+                    Content::Document { .. } => out.push_str("*(PDF document)*\n\n"),
+                }
+            }
+        }
+
+        if let Some(plan) = &self.plan {
+            out.push_str("\n---\n\n");
+            push_details(
+                &mut out,
+                &format!("Plan: {}", plan.title),
+                None,
+                &plan.content,
+            );
+        }
+        out
+    }
+}
+
+/// Content blocks of a stored message, as the session loader reads them
+fn message_blocks(content: &Value) -> Vec<Content> {
+    if let Value::String(text) = content {
+        return vec![Content::Text { text: text.clone() }];
+    }
+    serde_json::from_value::<Vec<Content>>(content.clone())
+        .or_else(|_| serde_json::from_value::<Content>(content.clone()).map(|c| vec![c]))
+        .unwrap_or_else(|_| {
+            vec![Content::Text {
+                text: content.to_string(),
+            }]
+        })
+}
+
+/// Fold `body` under a `<summary>` line
+///
+/// The summary is raw HTML (Markdown isn't parsed inside it), so `target`
+/// is wrapped in `<code>` rather than backticks.
+fn push_details(out: &mut String, label: &str, target: Option<&str>, body: &str) {
+    let target = target
+        .map(|t| format!(" <code>{}</code>", escape_html(t)))
+        .unwrap_or_default();
+    out.push_str(&format!(
+        "<details>\n<summary>{}{}</summary>\n\n{}\n</details>\n\n",
+        escape_html(label),
+        target,
+        body.trim_end()
+    ));
+}
+
+/// Short description of a tool call, e.g. the file or command it targets
+fn tool_target(input: &Value) -> Option<String> {
+    ["file_path", "path", "command", "pattern", "query", "url"]
+        .iter()
+        .find_map(|key| input.get(*key).and_then(Value::as_str))
+        .map(|target| {
+            let first_line = target.lines().next().unwrap_or_default();
+            let short: String = first_line.chars().take(80).collect();
+            if short.len() < target.len() {
+                format!("{}…", short)
+            } else {
+                short
+            }
+        })
+}
+
+/// Tool input as a diff for edits and writes, JSON otherwise
+fn tool_input_markdown(name: &str, input: &Value) -> String {
+    let field = |key: &str| input.get(key).and_then(Value::as_str);
+    match (
+        name,
+        field("old_string"),
+        field("new_string"),
+        field("content"),
+    ) {
+        ("edit", Some(old), Some(new), _) => {
+            let diff: Vec<String> = old
+                .lines()
+                .map(|l| format!("-{}", l))
+                .chain(new.lines().map(|l| format!("+{}", l)))
+                .collect();
+            fenced("diff", &diff.join("\n"))
+        }
+        ("write", _, _, Some(content)) => {
+            let diff: Vec<String> = content.lines().map(|l| format!("+{}", l)).collect();
+            fenced("diff", &truncate_lines(&diff.join("\n")))
+        }
+        _ => fenced(
+            "json",
+            &serde_json::to_string_pretty(input).unwrap_or_default(),
+        ),
+    }
+}
+
+/// Code fence that can't be closed early by backticks in the body
+fn fenced(lang: &str, body: &str) -> String {
+    let longest_run = body.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    let fence = "`".repeat(longest_run.max(2) + 1);
+    format!("{}{}\n{}\n{}\n", fence, lang, body.trim_end(), fence)
+}
+
+fn truncate_lines(text: &str) -> String {
+    let total = text.lines().count();
+    if total <= MAX_OUTPUT_LINES {
+        return text.to_string();
+    }
+    let kept: Vec<&str> = text.lines().take(MAX_OUTPUT_LINES).collect();
+    format!(
+        "{}\n… {} more lines",
+        kept.join("\n"),
+        total - MAX_OUTPUT_LINES
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::storage::SessionManager;
+
+    fn create_manager(dir: &TempDir, name: &str) -> SessionManager {
+        let db = Database::new(&dir.path().join(name)).expect("Failed to create db");
+        SessionManager::new(db)
+    }
+
+    fn sample_session(manager: &SessionManager) -> String {
+        let id = manager
+            .create_session("Fix login", Some("claude-sonnet-4"), Some("/repo"))
+            .unwrap();
+        manager
+            .save_message(
+                &id,
+                "user",
+                r#"[{"type":"text","text":"Fix the login bug"}]"#,
+            )
+            .unwrap();
+        manager
+            .save_message(
+                &id,
+                "assistant",
+                r#"[{"type":"thinking","thinking":"Check auth.rs","signature":""},
+                    {"type":"tool_use","id":"t1","name":"edit","input":{"file_path":"src/auth.rs","old_string":"a < b","new_string":"a <= b"}}]"#,
+            )
+            .unwrap();
+        manager
+            .save_message(
+                &id,
+                "user",
+                r#"[{"type":"tool_result","tool_use_id":"t1","output":"Edited src/auth.rs"}]"#,
+            )
+            .unwrap();
+        manager.save_block_ui_state(&id, "t1", true, 3).unwrap();
+        id
+    }
+
+    #[test]
+    fn test_bundle_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let source = create_manager(&dir, "source.db");
+        let id = sample_session(&source);
+
+        let bundle = SessionBundle::export(source.db(), &id).unwrap();
+        assert_eq!(bundle.messages.len(), 3);
+        let json = serde_json::to_string(&bundle).unwrap();
+
+        let target = create_manager(&dir, "target.db");
+        let imported = SessionBundle::from_json(&json)
+            .unwrap()
+            .import(target.db(), Some("/checkout"))
+            .unwrap();
+        assert_ne!(imported, id);
+
+        let info = target.get_session(&imported).unwrap().unwrap();
+        assert_eq!(info.title, "Fix login");
+        assert_eq!(info.working_dir.as_deref(), Some("/checkout"));
+        // Same messages, though JSON key order may differ
+        let parsed = |manager: &SessionManager, id: &str| -> Vec<(String, Value)> {
+            manager
+                .load_session_messages(id)
+                .unwrap()
+                .into_iter()
+                .map(|(role, content)| (role, serde_json::from_str(&content).unwrap()))
+                .collect()
+        };
+        assert_eq!(parsed(&target, &imported), parsed(&source, &id));
+        let states = target.load_block_ui_states(&imported);
+        assert_eq!(states.len(), 1);
+        assert!(states[0].collapsed);
+    }
+
+    #[test]
+    fn test_rejects_newer_bundles() {
+        let dir = TempDir::new().unwrap();
+        let manager = create_manager(&dir, "test.db");
+        let id = sample_session(&manager);
+        let mut bundle = SessionBundle::export(manager.db(), &id).unwrap();
+        bundle.version = BUNDLE_VERSION + 1;
+        let json = serde_json::to_string(&bundle).unwrap();
+        assert!(SessionBundle::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_markdown_transcript() {
+        let dir = TempDir::new().unwrap();
+        let manager = create_manager(&dir, "test.db");
+        let id = sample_session(&manager);
+        let markdown = SessionBundle::export(manager.db(), &id)
+            .unwrap()
+            .to_markdown();
+
+        assert!(markdown.starts_with("# Fix login\n"));
+        assert!(markdown.contains("## User\n\nFix the login bug"));
+        assert!(markdown.contains("<summary>Thinking</summary>"));
+        assert!(markdown.contains("<summary>Tool: edit <code>src/auth.rs</code></summary>"));
+        assert!(markdown.contains("-a < b\n+a <= b"));
+        assert!(markdown.contains("<summary>Output of edit</summary>"));
+        // The tool result doesn't start a new user section
+        assert_eq!(markdown.matches("## User").count(), 1);
+    }
+
+    #[test]
+    fn test_fence_outlasts_backticks() {
+        assert_eq!(fenced("", "plain"), "```\nplain\n```\n");
+        assert_eq!(fenced("md", "```rust\n```"), "````md\n```rust\n```\n````\n");
+    }
+}
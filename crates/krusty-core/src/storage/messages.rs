@@ -30,6 +30,12 @@ impl<'a> MessageStore<'a> {
             params![session_id, role, content_json, now],
         )?;
 
+        // A message that can't be indexed is still saved, just not searchable
+        let message_id = self.db.conn().last_insert_rowid();
+        if let Err(e) = super::search::index_message(self.db.conn(), message_id, content_json) {
+            tracing::warn!("Failed to index message for search: {}", e);
+        }
+
         // Update session timestamp
         self.db.conn().execute(
             "UPDATE sessions SET updated_at = ?1 WHERE id = ?2",
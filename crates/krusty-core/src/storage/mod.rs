@@ -2,32 +2,44 @@
 //!
 //! SQLite-based storage for:
 //! - Session storage and management
+//! - Full-text search across session messages
+//! - Session export (Markdown, JSON bundles) and import
 //! - Plan storage with session linkage
 //! - User preferences
 //! - File activity tracking for context
+//! - File checkpoints for undoing agent edits
+//! - Token usage and cost per request
 //! - API credentials
 
 use std::time::{SystemTime, UNIX_EPOCH};
 
 mod agent_state;
 mod block_ui;
+mod checkpoints;
 pub mod credentials;
 mod database;
+mod export;
 mod file_activity;
 mod messages;
 mod plans;
 mod preferences;
+mod search;
 mod sessions;
+mod usage;
 
 pub use agent_state::AgentState;
 pub use block_ui::BlockUiState;
+pub use checkpoints::{Checkpoint, CheckpointStore, FileSnapshot};
 pub use credentials::CredentialStore;
 pub use database::{Database, SharedDatabase};
+pub use export::{SessionBundle, BUNDLE_VERSION};
 pub use file_activity::{FileActivityTracker, RankedFile};
 pub use messages::MessageStore;
 pub use plans::{PlanStore, PlanSummary};
 pub use preferences::Preferences;
+pub use search::{MessageSearch, SearchFilter, SearchHit, HIGHLIGHT_END, HIGHLIGHT_START};
 pub use sessions::{SessionInfo, SessionManager};
+pub use usage::{today, UsageRecord, UsageStore, UsageSummary};
 
 /// Get current Unix timestamp in seconds
 #[inline]
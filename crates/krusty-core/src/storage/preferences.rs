@@ -3,6 +3,7 @@
 use anyhow::Result;
 use rusqlite::params;
 
+use crate::ai::cost::BudgetLimits;
 use crate::ai::models::ModelMetadata;
 use crate::tools::git_identity::GitIdentity;
 
@@ -193,6 +194,31 @@ impl Preferences {
         self.set("active_plugin", plugin_id)
     }
 
+    /// Whether to keep the codebase index fresh in the background (defaults to true)
+    pub fn get_index_watch(&self) -> bool {
+        self.get("index_watch")
+            .map(|v| v != "false")
+            .unwrap_or(true)
+    }
+
+    /// Enable or disable the background index watcher
+    pub fn set_index_watch(&self, enabled: bool) -> Result<()> {
+        self.set("index_watch", if enabled { "true" } else { "false" })
+    }
+
+    /// Get spending limits (defaults to none)
+    pub fn get_budget_limits(&self) -> BudgetLimits {
+        self.get("budget_limits")
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save spending limits
+    pub fn set_budget_limits(&self, limits: &BudgetLimits) -> Result<()> {
+        let json = serde_json::to_string(limits)?;
+        self.set("budget_limits", &json)
+    }
+
     /// Get git identity configuration (defaults to CoAuthor mode)
     pub fn get_git_identity(&self) -> GitIdentity {
         self.get("git_identity")
@@ -0,0 +1,331 @@
+//! Full-text search across session messages
+//!
+//! Messages are indexed in the `messages_fts` FTS5 table as they are saved:
+//! their text, tool inputs and tool outputs. Index rows share the message's
+//! rowid, and a trigger removes them when the message is deleted.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection};
+use serde_json::Value;
+
+use super::database::Database;
+
+/// Marks the start of a matched term in [`SearchHit::snippet`]
+pub const HIGHLIGHT_START: char = '\u{2}';
+/// Marks the end of a matched term in [`SearchHit::snippet`]
+pub const HIGHLIGHT_END: char = '\u{3}';
+
+/// Tokens of context shown around matches in a snippet
+const SNIPPET_TOKENS: usize = 16;
+
+/// Restricts which messages a search matches
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    /// Only sessions started in this directory
+    pub working_dir: Option<String>,
+    /// Only sessions whose model contains this (case-insensitive)
+    pub model: Option<String>,
+    /// Only messages saved at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only messages saved before this time
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// A message matching a search, best matches first
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub session_id: String,
+    pub session_title: String,
+    pub working_dir: Option<String>,
+    pub model: Option<String>,
+    pub role: String,
+    /// Position of the message within its session
+    pub message_index: usize,
+    /// Matching excerpt, with matches wrapped in [`HIGHLIGHT_START`] and [`HIGHLIGHT_END`]
+    pub snippet: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Message search over the FTS index
+pub struct MessageSearch<'a> {
+    db: &'a Database,
+}
+
+impl<'a> MessageSearch<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Search messages, returning at most `limit` hits ranked by relevance
+    ///
+    /// The query is plain words, all of which must match; the last word also
+    /// matches as a prefix so results update while typing.
+    pub fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: usize,
+    ) -> Result<Vec<SearchHit>> {
+        let Some(fts_query) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+
+        let mut conditions = vec!["messages_fts MATCH ?1".to_string()];
+        let mut values = vec![fts_query];
+        if let Some(dir) = &filter.working_dir {
+            values.push(dir.clone());
+            conditions.push(format!("s.working_dir = ?{}", values.len()));
+        }
+        if let Some(model) = &filter.model {
+            values.push(format!("%{}%", model));
+            conditions.push(format!("s.model LIKE ?{}", values.len()));
+        }
+        if let Some(since) = filter.since {
+            values.push(since.to_rfc3339());
+            conditions.push(format!("m.created_at >= ?{}", values.len()));
+        }
+        if let Some(until) = filter.until {
+            values.push(until.to_rfc3339());
+            conditions.push(format!("m.created_at < ?{}", values.len()));
+        }
+
+        let sql = format!(
+            "SELECT m.session_id, s.title, s.working_dir, s.model, m.role, m.created_at,
+                    snippet(messages_fts, 0, char(2), char(3), '…', {}),
+                    (SELECT COUNT(*) FROM messages p
+                     WHERE p.session_id = m.session_id AND p.id < m.id)
+             FROM messages_fts
+             JOIN messages m ON m.id = messages_fts.rowid
+             JOIN sessions s ON s.id = m.session_id
+             WHERE {}
+             ORDER BY rank
+             LIMIT {}",
+            SNIPPET_TOKENS,
+            conditions.join(" AND "),
+            limit
+        );
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            values.iter().map(|s| s as &dyn rusqlite::ToSql).collect();
+        let mut stmt = self.db.conn().prepare(&sql)?;
+        let hits = stmt
+            .query_map(params_refs.as_slice(), |row| {
+                let created_at: String = row.get(5)?;
+                let message_index: i64 = row.get(7)?;
+                Ok(SearchHit {
+                    session_id: row.get(0)?,
+                    session_title: row.get(1)?,
+                    working_dir: row.get(2)?,
+                    model: row.get(3)?,
+                    role: row.get(4)?,
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                    snippet: row.get(6)?,
+                    message_index: message_index as usize,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(hits)
+    }
+}
+
+/// Add a saved message to the search index
+pub(crate) fn index_message(conn: &Connection, message_id: i64, content_json: &str) -> Result<()> {
+    let body = searchable_text(content_json);
+    if body.is_empty() {
+        return Ok(());
+    }
+    conn.execute(
+        "INSERT INTO messages_fts (rowid, body) VALUES (?1, ?2)",
+        params![message_id, body],
+    )?;
+    Ok(())
+}
+
+/// Index every message saved before the search index existed
+pub(crate) fn index_existing_messages(conn: &Connection) -> Result<usize> {
+    let mut stmt = conn.prepare("SELECT id, content FROM messages ORDER BY id")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (id, content) in &rows {
+        index_message(conn, *id, content)?;
+    }
+    Ok(rows.len())
+}
+
+/// Text, tool inputs and tool outputs of a stored message
+///
+/// Content that isn't a JSON array of content blocks (legacy plain-text
+/// messages) is indexed as-is.
+fn searchable_text(content_json: &str) -> String {
+    let blocks = match serde_json::from_str::<Value>(content_json) {
+        Ok(Value::Array(blocks)) => blocks,
+        Ok(block @ Value::Object(_)) => vec![block],
+        _ => return content_json.to_string(),
+    };
+
+    let mut parts = Vec::new();
+    for block in &blocks {
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => collect_strings(&block["text"], &mut parts),
+            Some("tool_use") => {
+                collect_strings(&block["name"], &mut parts);
+                collect_strings(&block["input"], &mut parts);
+            }
+            Some("tool_result") => collect_strings(&block["output"], &mut parts),
+            _ => {}
+        }
+    }
+    parts.join("\n")
+}
+
+fn collect_strings(value: &Value, out: &mut Vec<String>) {
+    match value {
+        Value::String(s) if !s.is_empty() => out.push(s.clone()),
+        Value::Array(items) => items.iter().for_each(|v| collect_strings(v, out)),
+        Value::Object(map) => map.values().for_each(|v| collect_strings(v, out)),
+        _ => {}
+    }
+}
+
+/// Turn user input into an FTS5 query: every word quoted, last one as a prefix
+fn fts_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect();
+    if terms.is_empty() {
+        return None;
+    }
+    Some(format!("{}*", terms.join(" ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::storage::SessionManager;
+
+    fn create_manager() -> (SessionManager, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let db = Database::new(&temp_dir.path().join("test.db")).expect("Failed to create db");
+        (SessionManager::new(db), temp_dir)
+    }
+
+    #[test]
+    fn test_search_text_and_tool_content() {
+        let (manager, _temp) = create_manager();
+        let session = manager
+            .create_session("OAuth work", Some("claude-sonnet-4"), Some("/repo"))
+            .unwrap();
+        manager
+            .save_message(
+                &session,
+                "user",
+                r#"[{"type":"text","text":"The token refresh is broken"}]"#,
+            )
+            .unwrap();
+        manager
+            .save_message(
+                &session,
+                "assistant",
+                r#"[{"type":"tool_use","id":"t1","name":"grep","input":{"pattern":"refresh_oauth_token"}}]"#,
+            )
+            .unwrap();
+        manager
+            .save_message(
+                &session,
+                "user",
+                r#"[{"type":"tool_result","tool_use_id":"t1","output":"src/auth.rs: fn refresh_oauth_token()"}]"#,
+            )
+            .unwrap();
+
+        let search = MessageSearch::new(manager.db());
+        let hits = search
+            .search("refresh brok", &SearchFilter::default(), 10)
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].message_index, 0);
+        assert!(hits[0]
+            .snippet
+            .contains(&format!("{}refresh{}", HIGHLIGHT_START, HIGHLIGHT_END)));
+
+        let hits = search
+            .search("refresh_oauth_token", &SearchFilter::default(), 10)
+            .unwrap();
+        let indices: Vec<usize> = hits.iter().map(|h| h.message_index).collect();
+        assert_eq!(indices.len(), 2);
+        assert!(indices.contains(&1) && indices.contains(&2));
+    }
+
+    #[test]
+    fn test_search_filters() {
+        let (manager, _temp) = create_manager();
+        let here = manager
+            .create_session("Here", Some("claude-sonnet-4"), Some("/repo"))
+            .unwrap();
+        let there = manager
+            .create_session("There", Some("gpt-5"), Some("/other"))
+            .unwrap();
+        let text = r#"[{"type":"text","text":"flaky migration test"}]"#;
+        manager.save_message(&here, "user", text).unwrap();
+        manager.save_message(&there, "user", text).unwrap();
+
+        let search = MessageSearch::new(manager.db());
+        let by_dir = SearchFilter {
+            working_dir: Some("/repo".to_string()),
+            ..Default::default()
+        };
+        let hits = search.search("migration", &by_dir, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id, here);
+
+        let by_model = SearchFilter {
+            model: Some("GPT".to_string()),
+            ..Default::default()
+        };
+        let hits = search.search("migration", &by_model, 10).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id, there);
+
+        let future = SearchFilter {
+            since: Some(Utc::now() + chrono::Duration::days(1)),
+            ..Default::default()
+        };
+        assert!(search.search("migration", &future, 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deleted_sessions_leave_index() {
+        let (manager, _temp) = create_manager();
+        let session = manager.create_session("Temp", None, None).unwrap();
+        manager
+            .save_message(&session, "user", r#"[{"type":"text","text":"ephemeral"}]"#)
+            .unwrap();
+        manager.delete_session(&session).unwrap();
+
+        let remaining: i64 = manager
+            .db()
+            .conn()
+            .query_row("SELECT COUNT(*) FROM messages_fts", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 0);
+    }
+
+    #[test]
+    fn test_fts_query_quotes_terms() {
+        assert_eq!(fts_query("   "), None);
+        assert_eq!(fts_query("oauth"), Some("\"oauth\"*".to_string()));
+        assert_eq!(
+            fts_query("say \"hi\" AND"),
+            Some("\"say\" \"\"\"hi\"\"\" \"AND\"*".to_string())
+        );
+    }
+}
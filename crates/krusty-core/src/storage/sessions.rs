@@ -15,12 +15,14 @@ pub struct SessionInfo {
     pub title: String,
     pub updated_at: DateTime<Utc>,
     pub token_count: Option<usize>,
-    /// Parent session ID for linked sessions (pinch)
+    /// Parent session ID for linked sessions (pinch or fork)
     pub parent_session_id: Option<String>,
     /// Working directory for this session
     pub working_dir: Option<String>,
     /// User ID for multi-tenant isolation
     pub user_id: Option<String>,
+    /// For forks, how many of the parent's messages the fork started from
+    pub fork_point: Option<usize>,
 }
 
 /// Session manager for CRUD operations
@@ -98,7 +100,7 @@ impl SessionManager {
         };
 
         let sql = format!(
-            "SELECT id, title, updated_at, token_count, parent_session_id, working_dir, user_id
+            "SELECT id, title, updated_at, token_count, parent_session_id, working_dir, user_id, fork_message_count
              FROM sessions {}
              ORDER BY updated_at DESC",
             where_clause
@@ -129,6 +131,7 @@ impl SessionManager {
             parent_session_id: row.get(4)?,
             working_dir: row.get(5)?,
             user_id: row.get(6)?,
+            fork_point: row.get::<_, Option<i64>>(7)?.map(|n| n as usize),
         })
     }
 
@@ -196,7 +199,7 @@ impl SessionManager {
         use std::collections::HashMap;
 
         let mut stmt = self.db.conn().prepare(
-            "SELECT id, title, updated_at, token_count, parent_session_id, working_dir, user_id
+            "SELECT id, title, updated_at, token_count, parent_session_id, working_dir, user_id, fork_message_count
              FROM sessions
              WHERE working_dir IS NOT NULL
              ORDER BY working_dir, updated_at DESC",
@@ -221,6 +224,7 @@ impl SessionManager {
                     parent_session_id: row.get(4)?,
                     working_dir: Some(working_dir),
                     user_id: row.get(6)?,
+                    fork_point: row.get::<_, Option<i64>>(7)?.map(|n| n as usize),
                 },
             ))
         })?;
@@ -236,7 +240,7 @@ impl SessionManager {
     /// Get a specific session
     pub fn get_session(&self, session_id: &str) -> Result<Option<SessionInfo>> {
         let mut stmt = self.db.conn().prepare(
-            "SELECT id, title, updated_at, token_count, parent_session_id, working_dir, user_id FROM sessions WHERE id = ?1",
+            "SELECT id, title, updated_at, token_count, parent_session_id, working_dir, user_id, fork_message_count FROM sessions WHERE id = ?1",
         )?;
 
         let session = stmt.query_row([session_id], |row| {
@@ -253,6 +257,7 @@ impl SessionManager {
                 parent_session_id: row.get(4)?,
                 working_dir: row.get(5)?,
                 user_id: row.get(6)?,
+                fork_point: row.get::<_, Option<i64>>(7)?.map(|n| n as usize),
             })
         });
 
@@ -311,6 +316,9 @@ impl SessionManager {
             params![session_id],
         )?;
 
+        // Clear file checkpoints and their no longer referenced contents
+        super::checkpoints::CheckpointStore::new(&self.db).delete_session(session_id)?;
+
         // Messages will be deleted via ON DELETE CASCADE
         self.db
             .conn()
@@ -332,6 +340,30 @@ impl SessionManager {
         super::messages::MessageStore::new(&self.db).load_session_messages(session_id)
     }
 
+    /// Search messages across sessions, best matches first
+    pub fn search_messages(
+        &self,
+        query: &str,
+        filter: &super::search::SearchFilter,
+        limit: usize,
+    ) -> Result<Vec<super::search::SearchHit>> {
+        super::search::MessageSearch::new(&self.db).search(query, filter, limit)
+    }
+
+    /// Collect a session for export
+    pub fn export_session(&self, session_id: &str) -> Result<super::export::SessionBundle> {
+        super::export::SessionBundle::export(&self.db, session_id)
+    }
+
+    /// Import an exported session as a new session, returning its ID
+    pub fn import_session(
+        &self,
+        bundle: &super::export::SessionBundle,
+        working_dir: Option<&str>,
+    ) -> Result<String> {
+        bundle.import(&self.db, working_dir)
+    }
+
     /// Generate a title from the first message content
     /// Truncates at word boundaries for cleaner display
     /// Uses char-based indexing for UTF-8 safety
@@ -438,6 +470,93 @@ impl SessionManager {
         Ok(id)
     }
 
+    // =========================================================================
+    // Forks
+    // =========================================================================
+
+    /// Fork a session from its first `message_count` messages
+    ///
+    /// The fork is a child of the source session with a copy of those
+    /// messages and its block UI state. Tool calls at the fork point whose
+    /// results weren't copied are dropped so the conversation stays valid.
+    pub fn fork_session(
+        &self,
+        session_id: &str,
+        message_count: usize,
+        title: &str,
+    ) -> Result<String> {
+        let (model, working_dir, user_id): (Option<String>, Option<String>, Option<String>) =
+            self.db.conn().query_row(
+                "SELECT model, working_dir, user_id FROM sessions WHERE id = ?1",
+                params![session_id],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+            )?;
+
+        let mut messages: Vec<(String, String, String)> = {
+            let mut stmt = self.db.conn().prepare(
+                "SELECT role, content, created_at FROM messages
+                 WHERE session_id = ?1 ORDER BY id LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(params![session_id, message_count as i64], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        if let Some((role, content, _)) = messages.last_mut() {
+            if role == "assistant" {
+                match without_tool_calls(content) {
+                    Some(trimmed) => *content = trimmed,
+                    None => {
+                        messages.pop();
+                    }
+                }
+            }
+        }
+
+        let id = uuid::Uuid::new_v4().to_string();
+        let now = Utc::now().to_rfc3339();
+        let tx = self.db.conn().unchecked_transaction()?;
+        tx.execute(
+            "INSERT INTO sessions (id, title, created_at, updated_at, model, working_dir, user_id,
+                parent_session_id, fork_message_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            params![
+                id,
+                title,
+                now,
+                now,
+                model,
+                working_dir,
+                user_id,
+                session_id,
+                message_count as i64
+            ],
+        )?;
+        for (role, content, created_at) in &messages {
+            tx.execute(
+                "INSERT INTO messages (session_id, role, content, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id, role, content, created_at],
+            )?;
+            super::search::index_message(&tx, tx.last_insert_rowid(), content)?;
+        }
+        tx.execute(
+            "INSERT INTO block_ui_state (session_id, block_id, block_type, collapsed, scroll_offset)
+             SELECT ?1, block_id, block_type, collapsed, scroll_offset
+             FROM block_ui_state WHERE session_id = ?2",
+            params![id, session_id],
+        )?;
+        tx.commit()?;
+
+        tracing::info!(
+            session_id = %id,
+            parent = %session_id,
+            messages = messages.len(),
+            "Forked session"
+        );
+        Ok(id)
+    }
+
     // =========================================================================
     // Agent State Tracking (for background execution)
     // =========================================================================
@@ -465,6 +584,21 @@ impl SessionManager {
     }
 }
 
+/// Assistant content without its tool calls, or None if nothing else is left
+fn without_tool_calls(content_json: &str) -> Option<String> {
+    let Ok(serde_json::Value::Array(blocks)) = serde_json::from_str(content_json) else {
+        return Some(content_json.to_string());
+    };
+    let kept: Vec<_> = blocks
+        .into_iter()
+        .filter(|block| block.get("type").and_then(|t| t.as_str()) != Some("tool_use"))
+        .collect();
+    let has_text = kept
+        .iter()
+        .any(|block| block.get("type").and_then(|t| t.as_str()) == Some("text"));
+    has_text.then(|| serde_json::Value::Array(kept).to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use rusqlite::params;
@@ -795,6 +929,47 @@ mod tests {
         assert_eq!(session.working_dir, Some("/tmp".to_string()));
     }
 
+    #[test]
+    fn test_fork_session() {
+        let (db, _temp) = create_test_db();
+        let manager = SessionManager::new(db);
+        let source = manager
+            .create_session("Source", Some("claude-3-5-sonnet"), Some("/tmp"))
+            .expect("Failed to create session");
+        let messages = [
+            ("user", r#"[{"type":"text","text":"Fix the parser"}]"#),
+            (
+                "assistant",
+                r#"[{"type":"text","text":"Reading it"},{"type":"tool_use","id":"t1","name":"read","input":{}}]"#,
+            ),
+            (
+                "user",
+                r#"[{"type":"tool_result","tool_use_id":"t1","output":"fn parse()"}]"#,
+            ),
+        ];
+        for (role, content) in messages {
+            manager.save_message(&source, role, content).unwrap();
+        }
+
+        // Forking at the tool call drops it, since its result isn't copied
+        let fork = manager
+            .fork_session(&source, 2, "Source (fork)")
+            .expect("Failed to fork session");
+        let copied = manager.load_session_messages(&fork).unwrap();
+        assert_eq!(copied.len(), 2);
+        assert_eq!(copied[0].1, messages[0].1);
+        assert!(!copied[1].1.contains("tool_use"));
+        assert!(copied[1].1.contains("Reading it"));
+
+        let info = manager.get_session(&fork).unwrap().unwrap();
+        assert_eq!(info.parent_session_id.as_deref(), Some(source.as_str()));
+        assert_eq!(info.fork_point, Some(2));
+        assert_eq!(info.working_dir, Some("/tmp".to_string()));
+
+        // The source is untouched
+        assert_eq!(manager.load_session_messages(&source).unwrap().len(), 3);
+    }
+
     #[test]
     fn test_update_session_title() {
         // Test updating session title
@@ -0,0 +1,212 @@
+//! Token usage and cost records
+//!
+//! One row in `usage_tracking` per API request, tagged with the session,
+//! model and what made the call (chat, sub-agents, dual-mind, ...).
+//! Days are local calendar days, so daily budgets reset at local midnight.
+
+use anyhow::Result;
+use chrono::Local;
+use rusqlite::params;
+
+use super::database::Database;
+
+/// Resource type for API request rows
+const RESOURCE_AI_REQUEST: &str = "ai_request";
+
+/// A single API request's usage
+#[derive(Debug, Clone, Default)]
+pub struct UsageRecord {
+    pub session_id: Option<String>,
+    pub provider: String,
+    pub model: String,
+    /// What made the call (e.g. "chat", "explore")
+    pub source: String,
+    /// Input tokens billed at the full input price
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    pub cache_read_tokens: usize,
+    pub cache_write_tokens: usize,
+    /// Cost in USD, or `None` if the model's pricing is unknown
+    pub cost_usd: Option<f64>,
+}
+
+impl UsageRecord {
+    pub fn total_tokens(&self) -> usize {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_write_tokens
+    }
+}
+
+/// Usage aggregated per provider, model and source
+#[derive(Debug, Clone, Default)]
+pub struct UsageSummary {
+    pub provider: String,
+    pub model: String,
+    pub source: String,
+    pub requests: usize,
+    pub input_tokens: usize,
+    pub output_tokens: usize,
+    pub cache_read_tokens: usize,
+    pub cache_write_tokens: usize,
+    /// Cost of the priced requests in USD
+    pub cost_usd: f64,
+    /// Requests whose cost is unknown
+    pub unpriced_requests: usize,
+}
+
+/// Today's date as stored in `period_start`
+pub fn today() -> String {
+    Local::now().format("%Y-%m-%d").to_string()
+}
+
+/// Usage storage
+pub struct UsageStore<'a> {
+    db: &'a Database,
+}
+
+impl<'a> UsageStore<'a> {
+    pub fn new(db: &'a Database) -> Self {
+        Self { db }
+    }
+
+    /// Record a request against today
+    pub fn record(&self, record: &UsageRecord) -> Result<()> {
+        self.db.conn().execute(
+            "INSERT INTO usage_tracking (id, resource_type, quantity, period_start, created_at,
+                session_id, provider, model, source, input_tokens, output_tokens,
+                cache_read_tokens, cache_write_tokens, cost_usd)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                uuid::Uuid::new_v4().to_string(),
+                RESOURCE_AI_REQUEST,
+                record.total_tokens() as i64,
+                today(),
+                chrono::Utc::now().to_rfc3339(),
+                record.session_id,
+                record.provider,
+                record.model,
+                record.source,
+                record.input_tokens as i64,
+                record.output_tokens as i64,
+                record.cache_read_tokens as i64,
+                record.cache_write_tokens as i64,
+                record.cost_usd,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Total known cost of a session in USD
+    pub fn session_cost(&self, session_id: &str) -> Result<f64> {
+        let cost = self.db.conn().query_row(
+            "SELECT COALESCE(SUM(cost_usd), 0) FROM usage_tracking
+             WHERE resource_type = ?1 AND session_id = ?2",
+            params![RESOURCE_AI_REQUEST, session_id],
+            |row| row.get(0),
+        )?;
+        Ok(cost)
+    }
+
+    /// Total known cost of a day (`YYYY-MM-DD`) in USD, across all sessions
+    pub fn day_cost(&self, day: &str) -> Result<f64> {
+        let cost = self.db.conn().query_row(
+            "SELECT COALESCE(SUM(cost_usd), 0) FROM usage_tracking
+             WHERE resource_type = ?1 AND period_start = ?2",
+            params![RESOURCE_AI_REQUEST, day],
+            |row| row.get(0),
+        )?;
+        Ok(cost)
+    }
+
+    /// A session's usage per model and source, most expensive first
+    pub fn session_breakdown(&self, session_id: &str) -> Result<Vec<UsageSummary>> {
+        self.breakdown("session_id = ?2", session_id)
+    }
+
+    /// A day's usage per model and source, most expensive first
+    pub fn day_breakdown(&self, day: &str) -> Result<Vec<UsageSummary>> {
+        self.breakdown("period_start = ?2", day)
+    }
+
+    fn breakdown(&self, filter: &str, value: &str) -> Result<Vec<UsageSummary>> {
+        let sql = format!(
+            "SELECT COALESCE(provider, ''), COALESCE(model, ''), COALESCE(source, ''),
+                COUNT(*), SUM(input_tokens), SUM(output_tokens), SUM(cache_read_tokens),
+                SUM(cache_write_tokens), COALESCE(SUM(cost_usd), 0),
+                SUM(CASE WHEN cost_usd IS NULL THEN 1 ELSE 0 END)
+             FROM usage_tracking
+             WHERE resource_type = ?1 AND {}
+             GROUP BY provider, model, source
+             ORDER BY 9 DESC, SUM(input_tokens) + SUM(output_tokens) DESC",
+            filter
+        );
+        let mut stmt = self.db.conn().prepare(&sql)?;
+        let rows = stmt.query_map(params![RESOURCE_AI_REQUEST, value], |row| {
+            Ok(UsageSummary {
+                provider: row.get(0)?,
+                model: row.get(1)?,
+                source: row.get(2)?,
+                requests: row.get::<_, i64>(3)? as usize,
+                input_tokens: row.get::<_, i64>(4)? as usize,
+                output_tokens: row.get::<_, i64>(5)? as usize,
+                cache_read_tokens: row.get::<_, i64>(6)? as usize,
+                cache_write_tokens: row.get::<_, i64>(7)? as usize,
+                cost_usd: row.get(8)?,
+                unpriced_requests: row.get::<_, i64>(9)? as usize,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn record(session: &str, model: &str, source: &str, cost: Option<f64>) -> UsageRecord {
+        UsageRecord {
+            session_id: Some(session.to_string()),
+            provider: "anthropic".to_string(),
+            model: model.to_string(),
+            source: source.to_string(),
+            input_tokens: 1000,
+            output_tokens: 200,
+            cache_read_tokens: 5000,
+            cache_write_tokens: 0,
+            cost_usd: cost,
+        }
+    }
+
+    #[test]
+    fn test_costs_per_session_and_day() {
+        let temp = TempDir::new().unwrap();
+        let db = Database::new(&temp.path().join("test.db")).unwrap();
+        let store = UsageStore::new(&db);
+
+        store
+            .record(&record("a", "opus", "chat", Some(0.5)))
+            .unwrap();
+        store
+            .record(&record("a", "opus", "chat", Some(0.25)))
+            .unwrap();
+        store
+            .record(&record("a", "haiku", "explore", Some(0.01)))
+            .unwrap();
+        store.record(&record("a", "local", "chat", None)).unwrap();
+        store
+            .record(&record("b", "opus", "chat", Some(1.0)))
+            .unwrap();
+
+        assert!((store.session_cost("a").unwrap() - 0.76).abs() < 1e-9);
+        assert!((store.day_cost(&today()).unwrap() - 1.76).abs() < 1e-9);
+        assert_eq!(store.day_cost("2000-01-01").unwrap(), 0.0);
+
+        let breakdown = store.session_breakdown("a").unwrap();
+        assert_eq!(breakdown.len(), 3);
+        assert_eq!(breakdown[0].model, "opus");
+        assert_eq!(breakdown[0].requests, 2);
+        assert_eq!(breakdown[0].cache_read_tokens, 10_000);
+        assert_eq!(breakdown[1].source, "explore");
+        assert_eq!(breakdown[2].unpriced_requests, 1);
+    }
+}
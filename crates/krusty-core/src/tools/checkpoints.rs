@@ -0,0 +1,289 @@
+//! Checkpointing of files modified by tools
+//!
+//! The UI starts a turn for each user message; `write`, `edit` and builder
+//! agents call [`Checkpointer::snapshot`] before touching a file. Restoring
+//! a turn puts every file it (or any later turn) modified back the way it was.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use anyhow::{anyhow, Result};
+use similar::TextDiff;
+use tracing::{info, warn};
+
+use crate::storage::{Checkpoint, CheckpointStore, Database};
+
+/// The turn snapshots are currently recorded against
+struct ActiveTurn {
+    session_id: String,
+    turn: usize,
+    label: String,
+    /// Files already snapshotted this turn
+    saved: HashSet<PathBuf>,
+}
+
+/// Records file checkpoints for the current user turn
+pub struct Checkpointer {
+    db: Mutex<Database>,
+    active: Mutex<Option<ActiveTurn>>,
+}
+
+impl fmt::Debug for Checkpointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checkpointer").finish_non_exhaustive()
+    }
+}
+
+impl Checkpointer {
+    pub fn new(db: Database) -> Self {
+        Self {
+            db: Mutex::new(db),
+            active: Mutex::new(None),
+        }
+    }
+
+    /// Open the checkpoint database at `db_path`
+    pub fn open(db_path: &Path) -> Result<Self> {
+        Ok(Self::new(Database::new(db_path)?))
+    }
+
+    fn with_store<T>(&self, f: impl FnOnce(&CheckpointStore) -> Result<T>) -> Result<T> {
+        let db = self
+            .db
+            .lock()
+            .map_err(|_| anyhow!("Checkpoint database lock poisoned"))?;
+        f(&CheckpointStore::new(&db))
+    }
+
+    /// Start recording snapshots for a user turn
+    pub fn begin_turn(&self, session_id: &str, turn: usize, label: &str) {
+        if let Ok(mut active) = self.active.lock() {
+            *active = Some(ActiveTurn {
+                session_id: session_id.to_string(),
+                turn,
+                label: label.chars().take(200).collect(),
+                saved: HashSet::new(),
+            });
+        }
+    }
+
+    /// Stop recording (e.g. when switching sessions)
+    pub fn end_turn(&self) {
+        if let Ok(mut active) = self.active.lock() {
+            *active = None;
+        }
+    }
+
+    /// Save `path` as it is now, if this is its first change this turn
+    ///
+    /// Failures are logged rather than returned so a checkpoint problem
+    /// never blocks the edit itself.
+    pub fn snapshot(&self, path: &Path) {
+        let Ok(mut guard) = self.active.lock() else {
+            return;
+        };
+        let Some(active) = guard.as_mut() else {
+            return;
+        };
+        if !active.saved.insert(path.to_path_buf()) {
+            return;
+        }
+
+        let content = match std::fs::read(path) {
+            Ok(bytes) => Some(bytes),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                warn!("Failed to read {} for checkpoint: {}", path.display(), e);
+                return;
+            }
+        };
+        let path_str = path.to_string_lossy();
+        let result = self.with_store(|store| {
+            store.save_file(
+                &active.session_id,
+                active.turn,
+                &active.label,
+                &path_str,
+                content.as_deref(),
+            )
+        });
+        if let Err(e) = result {
+            warn!("Failed to save checkpoint for {}: {}", path.display(), e);
+        }
+    }
+
+    /// A session's checkpoints, oldest first
+    pub fn list(&self, session_id: &str) -> Result<Vec<Checkpoint>> {
+        self.with_store(|store| store.list(session_id))
+    }
+
+    /// Most recent turn with a checkpoint
+    pub fn latest_turn(&self, session_id: &str) -> Result<Option<usize>> {
+        self.with_store(|store| store.latest_turn(session_id))
+    }
+
+    /// What restoring to before `turn` would change
+    pub fn preview_restore(&self, session_id: &str, turn: usize) -> Result<Vec<FileRestore>> {
+        let snapshots = self.with_store(|store| store.files_before(session_id, turn))?;
+        Ok(snapshots
+            .into_iter()
+            .map(|snapshot| FileRestore {
+                current: std::fs::read(&snapshot.path).ok(),
+                path: snapshot.path,
+                restored: snapshot.content,
+            })
+            .filter(|restore| !restore.is_unchanged())
+            .collect())
+    }
+
+    /// Restore files to before `turn` and drop the checkpoints it undoes
+    ///
+    /// Returns the number of files written or deleted.
+    pub fn restore(&self, session_id: &str, turn: usize) -> Result<usize> {
+        let restores = self.preview_restore(session_id, turn)?;
+        for restore in &restores {
+            restore.apply()?;
+        }
+        self.with_store(|store| store.discard_from(session_id, turn))?;
+
+        // Files changed again after this point must be snapshotted again
+        if let Ok(mut active) = self.active.lock() {
+            if let Some(active) = active.as_mut().filter(|a| a.session_id == session_id) {
+                active.saved.clear();
+            }
+        }
+        info!(
+            "Restored {} files in session {} to before turn {}",
+            restores.len(),
+            session_id,
+            turn
+        );
+        Ok(restores.len())
+    }
+}
+
+/// A file change that restoring a checkpoint would make
+#[derive(Debug, Clone)]
+pub struct FileRestore {
+    pub path: PathBuf,
+    /// Contents now, or `None` if the file doesn't exist
+    pub current: Option<Vec<u8>>,
+    /// Contents after restoring, or `None` to delete the file
+    pub restored: Option<Vec<u8>>,
+}
+
+impl FileRestore {
+    fn is_unchanged(&self) -> bool {
+        self.current == self.restored
+    }
+
+    /// Short description of the change
+    pub fn action(&self) -> &'static str {
+        match (&self.current, &self.restored) {
+            (_, None) => "delete",
+            (None, Some(_)) => "recreate",
+            (Some(_), Some(_)) => "revert",
+        }
+    }
+
+    /// Unified diff from the current contents to the restored ones
+    pub fn diff(&self) -> String {
+        let current = self.current.as_deref().unwrap_or_default();
+        let restored = self.restored.as_deref().unwrap_or_default();
+        let (Ok(current), Ok(restored)) =
+            (std::str::from_utf8(current), std::str::from_utf8(restored))
+        else {
+            return "Binary file differs\n".to_string();
+        };
+
+        let path = self.path.display().to_string();
+        TextDiff::from_lines(current, restored)
+            .unified_diff()
+            .context_radius(3)
+            .header(&path, &path)
+            .to_string()
+    }
+
+    fn apply(&self) -> Result<()> {
+        match &self.restored {
+            Some(content) => {
+                if let Some(parent) = self.path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&self.path, content)?;
+            }
+            None => {
+                if self.path.exists() {
+                    std::fs::remove_file(&self.path)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::SessionManager;
+    use tempfile::TempDir;
+
+    fn setup() -> (Checkpointer, String, TempDir) {
+        let temp = TempDir::new().unwrap();
+        let db_path = temp.path().join("test.db");
+        let session_id = SessionManager::new(Database::new(&db_path).unwrap())
+            .create_session("test", None, None)
+            .unwrap();
+        (Checkpointer::open(&db_path).unwrap(), session_id, temp)
+    }
+
+    #[test]
+    fn test_restore_reverts_edits_and_removes_new_files() {
+        let (checkpointer, session_id, temp) = setup();
+        let existing = temp.path().join("main.rs");
+        let created = temp.path().join("new.rs");
+        std::fs::write(&existing, "fn main() {}\n").unwrap();
+
+        checkpointer.begin_turn(&session_id, 1, "add a feature");
+        checkpointer.snapshot(&existing);
+        std::fs::write(&existing, "fn main() { feature(); }\n").unwrap();
+        checkpointer.snapshot(&existing);
+        std::fs::write(&existing, "fn main() { feature(); }\nfn feature() {}\n").unwrap();
+        checkpointer.snapshot(&created);
+        std::fs::write(&created, "pub fn helper() {}\n").unwrap();
+
+        let preview = checkpointer.preview_restore(&session_id, 1).unwrap();
+        assert_eq!(preview.len(), 2);
+        let edit = preview.iter().find(|r| r.path == existing).unwrap();
+        assert_eq!(edit.action(), "revert");
+        assert!(edit.diff().contains("-fn feature() {}"));
+        assert!(edit.diff().contains("+fn main() {}"));
+        let new_file = preview.iter().find(|r| r.path == created).unwrap();
+        assert_eq!(new_file.action(), "delete");
+
+        assert_eq!(checkpointer.restore(&session_id, 1).unwrap(), 2);
+        assert_eq!(
+            std::fs::read_to_string(&existing).unwrap(),
+            "fn main() {}\n"
+        );
+        assert!(!created.exists());
+        assert!(checkpointer.list(&session_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_no_snapshots_outside_a_turn() {
+        let (checkpointer, session_id, temp) = setup();
+        let file = temp.path().join("a.txt");
+        std::fs::write(&file, "a").unwrap();
+
+        checkpointer.snapshot(&file);
+        assert!(checkpointer.list(&session_id).unwrap().is_empty());
+
+        checkpointer.begin_turn(&session_id, 3, "change a");
+        checkpointer.snapshot(&file);
+        checkpointer.end_turn();
+        assert_eq!(checkpointer.latest_turn(&session_id).unwrap(), Some(3));
+    }
+}
@@ -156,7 +156,8 @@ impl Tool for BashTool {
                     Ok(process_id) => {
                         return ToolResult::success(
                             json!({
-                                "output": "Process started in background",
+                                "output": "Process started in background. Use the processes tool \
+                                           (logs, wait_for) to read its output.",
                                 "processId": process_id,
                                 "status": "running"
                             })
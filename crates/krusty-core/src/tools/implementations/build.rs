@@ -2,15 +2,19 @@
 //!
 //! This tool spawns a team of Opus agents that work together to build code.
 //! Builders coordinate via SharedBuildContext to share types, modules, and file locks.
+//! With `isolation: "worktree"` each builder instead works in its own git worktree
+//! and the results are merged back when the build finishes.
 
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::{json, Value};
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
-use crate::agent::subagent::{SubAgentPool, SubAgentTask};
-use crate::agent::{AgentCancellation, SharedBuildContext};
+use crate::agent::subagent::{AgentProgress, SubAgentPool, SubAgentResult, SubAgentTask};
+use crate::agent::worktree::{self, BuildWorktrees, BuilderBranch, CONFLICT_MARKER};
+use crate::agent::{AgentCancellation, HookEvent, SharedBuildContext};
 use crate::ai::client::AiClient;
 use crate::tools::registry::{Tool, ToolContext, ToolResult};
 
@@ -50,6 +54,34 @@ struct Params {
     /// Index i maps to components[i]
     #[serde(default)]
     task_ids: Option<Vec<String>>,
+
+    /// Whether builders share the working tree or get their own worktrees
+    #[serde(default)]
+    isolation: Isolation,
+
+    /// What to do when isolated builders' changes conflict
+    #[serde(default)]
+    conflicts: ConflictMode,
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Isolation {
+    /// All builders write into the working tree, coordinated by file locks
+    #[default]
+    Shared,
+    /// Each builder works in its own git worktree on a temporary branch
+    Worktree,
+}
+
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ConflictMode {
+    /// Merge with conflict markers and have a resolver agent fix them
+    #[default]
+    Resolve,
+    /// Leave conflicting branches unmerged for the user to accept or reject
+    Report,
 }
 
 #[async_trait]
@@ -68,7 +100,9 @@ impl Tool for BuildTool {
          2-3 for tightly coupled components (shared files), \
          5-10 for independent components (separate files). \
          Default: matches component count (natural parallelism). \
-         Builders coordinate via file locking - more concurrency is fine if components don't share files."
+         Builders coordinate via file locking - more concurrency is fine if components don't share files. \
+         Set isolation='worktree' (git repos only) when components touch related files: \
+         each builder gets its own git worktree and the changes are merged back afterwards."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -94,6 +128,16 @@ impl Tool for BuildTool {
                     "description": "Max parallel builders. Default: component count. Use 2-3 for tightly coupled code (shared files), 5-10 for independent modules.",
                     "minimum": 1,
                     "maximum": 20
+                },
+                "isolation": {
+                    "type": "string",
+                    "enum": ["shared", "worktree"],
+                    "description": "'shared' (default): builders write into the working tree. 'worktree': each builder works in its own git worktree and changes are merged back when all are done."
+                },
+                "conflicts": {
+                    "type": "string",
+                    "enum": ["resolve", "report"],
+                    "description": "With isolation='worktree': 'resolve' (default) hands merge conflicts to a resolver agent, 'report' leaves conflicting builders unmerged for the user to accept or reject."
                 }
             },
             "required": ["prompt"],
@@ -176,7 +220,8 @@ impl Tool for BuildTool {
 
                 let mut task = SubAgentTask::new(format!("builder-{}", i), task_prompt)
                     .with_name(name)
-                    .with_working_dir(ctx.working_dir.clone());
+                    .with_working_dir(ctx.working_dir.clone())
+                    .with_checkpoints(ctx.checkpoints.clone());
 
                 // Attach plan task ID if provided for auto-completion
                 if let Some(ref task_ids) = params.task_ids {
@@ -192,10 +237,39 @@ impl Tool for BuildTool {
             tasks.push(
                 SubAgentTask::new("builder-main", params.prompt.clone())
                     .with_name("main")
-                    .with_working_dir(ctx.working_dir.clone()),
+                    .with_working_dir(ctx.working_dir.clone())
+                    .with_checkpoints(ctx.checkpoints.clone()),
             );
         }
 
+        // Isolated builders each get a worktree; their files are checkpointed
+        // when merged back instead
+        let mut notes: Vec<String> = Vec::new();
+        let worktrees = if params.isolation == Isolation::Worktree {
+            let ids: Vec<String> = tasks.iter().map(|t| t.id.clone()).collect();
+            match BuildWorktrees::create(&ctx.working_dir, &ids).await {
+                Ok(worktrees) => {
+                    for task in &mut tasks {
+                        if let Some(worktree) = worktrees.get(&task.id) {
+                            task.working_dir = worktree.working_dir.clone();
+                            task.checkpoints = None;
+                        }
+                    }
+                    Some(worktrees)
+                }
+                Err(e) => {
+                    warn!("Build tool: Worktree isolation unavailable: {}", e);
+                    notes.push(format!(
+                        "**Isolation**: unavailable ({}), builders shared the working tree",
+                        e
+                    ));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
         info!("Build tool: Created {} builder tasks", tasks.len());
         for (i, task) in tasks.iter().enumerate() {
             debug!("Builder {}: id={}, name={}", i, task.id, task.name);
@@ -212,17 +286,35 @@ impl Tool for BuildTool {
         );
 
         // Execute builders with progress channel if available
-        let results = if let Some(ref progress_tx) = ctx.build_progress_tx {
-            pool.execute_builders(tasks, context.clone(), progress_tx.clone())
-                .await
-        } else {
-            // Fallback: create a dummy channel and discard progress
-            let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
-            pool.execute_builders(tasks, context.clone(), tx).await
-        };
+        let (fallback_tx, _fallback_rx) = tokio::sync::mpsc::unbounded_channel();
+        let progress_tx = ctx.build_progress_tx.clone().unwrap_or(fallback_tx);
+        let mut results = pool
+            .execute_builders(tasks, context.clone(), progress_tx.clone())
+            .await;
 
         info!("Build tool: Kraken returned {} results", results.len());
 
+        for result in &results {
+            ctx.fire_user_hooks(HookEvent::subagent_stop(&ctx.working_dir, "build", result))
+                .await;
+        }
+
+        if let Some(worktrees) = worktrees {
+            let merge = self
+                .merge_worktrees(worktrees, &results, &params, ctx, &context, progress_tx)
+                .await;
+            if let Some(resolver) = merge.resolver {
+                ctx.fire_user_hooks(HookEvent::subagent_stop(
+                    &ctx.working_dir,
+                    "build",
+                    &resolver,
+                ))
+                .await;
+                results.push(resolver);
+            }
+            notes.extend(merge.notes);
+        }
+
         // Get final stats from context
         let stats = context.stats();
 
@@ -283,6 +375,11 @@ impl Tool for BuildTool {
 
         output.push_str(&summary);
 
+        for note in &notes {
+            output.push('\n');
+            output.push_str(note);
+        }
+
         if !errors.is_empty() {
             output.push_str("\n**Errors**: ");
             output.push_str(&errors.join(", "));
@@ -294,3 +391,190 @@ impl Tool for BuildTool {
         }
     }
 }
+
+/// What happened when merging isolated builders back
+#[derive(Default)]
+struct WorktreeMerge {
+    /// Lines for the tool output, including `## Unmerged:` sections
+    notes: Vec<String>,
+    /// Result of the resolver agent, if one ran
+    resolver: Option<SubAgentResult>,
+}
+
+impl BuildTool {
+    /// Commit each builder's worktree and merge the branches into the working tree
+    ///
+    /// Failed builders and, in report mode, builders whose changes conflict
+    /// are left on their branches and listed as unmerged.
+    async fn merge_worktrees(
+        &self,
+        worktrees: BuildWorktrees,
+        results: &[SubAgentResult],
+        params: &Params,
+        ctx: &ToolContext,
+        context: &Arc<SharedBuildContext>,
+        progress_tx: tokio::sync::mpsc::UnboundedSender<AgentProgress>,
+    ) -> WorktreeMerge {
+        let repo_root = worktrees.repo_root().to_path_buf();
+        let branches = worktrees.finish().await;
+        let resolve = params.conflicts == ConflictMode::Resolve;
+
+        let mut merge = WorktreeMerge::default();
+        let mut merged = Vec::new();
+        let mut unmerged: Vec<(BuilderBranch, Vec<String>, Option<String>)> = Vec::new();
+        let mut conflicted: Vec<(String, String)> = Vec::new();
+
+        for branch in branches {
+            let failed = results
+                .iter()
+                .any(|r| r.task_id == branch.task_id && !r.success);
+            if failed {
+                unmerged.push((branch, Vec::new(), Some("builder failed".to_string())));
+                continue;
+            }
+            match worktree::merge_branch(
+                &ctx.working_dir,
+                &branch.branch,
+                resolve,
+                ctx.checkpoints.as_deref(),
+            )
+            .await
+            {
+                Ok(outcome) if outcome.conflicts.is_empty() || resolve => {
+                    for file in outcome.conflicts {
+                        conflicted.push((file, branch.task_id.clone()));
+                    }
+                    merged.push(branch.branch);
+                }
+                Ok(outcome) => unmerged.push((branch, outcome.conflicts, None)),
+                Err(e) => {
+                    warn!("Build tool: Failed to merge {}: {}", branch.branch, e);
+                    unmerged.push((branch, Vec::new(), Some(e.to_string())));
+                }
+            }
+        }
+        worktree::delete_branches(&repo_root, &merged).await;
+
+        merge.notes.push(format!(
+            "**Isolation**: git worktrees, {} merged, {} unmerged",
+            merged.len(),
+            unmerged.len()
+        ));
+
+        if !conflicted.is_empty() {
+            let resolver = self
+                .run_resolver(&conflicted, params, ctx, context, progress_tx)
+                .await;
+            let mut files: Vec<&str> = conflicted.iter().map(|(f, _)| f.as_str()).collect();
+            files.sort_unstable();
+            files.dedup();
+            let remaining: Vec<&str> = files
+                .iter()
+                .copied()
+                .filter(|file| has_conflict_markers(&repo_root.join(file)))
+                .collect();
+            if remaining.is_empty() && resolver.success {
+                merge.notes.push(format!(
+                    "**Conflicts**: resolved by the resolver agent in {}",
+                    files.join(", ")
+                ));
+            } else {
+                merge.notes.push(format!(
+                    "**Unresolved Conflicts**: {} still contain conflict markers",
+                    if remaining.is_empty() {
+                        files.join(", ")
+                    } else {
+                        remaining.join(", ")
+                    }
+                ));
+            }
+            merge.resolver = Some(resolver);
+        }
+
+        if !unmerged.is_empty() {
+            merge.notes.push(
+                "Unmerged builder changes stay on their branches; the user can accept \
+                 or reject each one in the build view."
+                    .to_string(),
+            );
+        }
+        for (branch, conflicts, reason) in unmerged {
+            let mut section = format!("## Unmerged: {} {}", branch.task_id, branch.branch);
+            if let Some(reason) = reason {
+                section.push_str(&format!("\n{}", reason));
+            }
+            for file in &branch.files {
+                let stat = match (file.added, file.removed) {
+                    (Some(added), Some(removed)) => format!("+{} -{}", added, removed),
+                    _ => "binary".to_string(),
+                };
+                let conflict = if conflicts.contains(&file.path) {
+                    " (conflict)"
+                } else {
+                    ""
+                };
+                section.push_str(&format!("\n- {} {}{}", file.path, stat, conflict));
+            }
+            merge.notes.push(section);
+        }
+        merge
+    }
+
+    /// Run a builder that removes the conflict markers left by merging
+    async fn run_resolver(
+        &self,
+        conflicted: &[(String, String)],
+        params: &Params,
+        ctx: &ToolContext,
+        context: &Arc<SharedBuildContext>,
+        progress_tx: tokio::sync::mpsc::UnboundedSender<AgentProgress>,
+    ) -> SubAgentResult {
+        let files: Vec<String> = conflicted
+            .iter()
+            .map(|(file, task_id)| format!("  - {} (from {})", file, task_id))
+            .collect();
+        let prompt = format!(
+            "You are the merge resolver for a parallel build team.\n\n\
+             The builders worked in separate git worktrees. Merging their changes back \
+             left conflict markers (<<<<<<<, =======, >>>>>>>) in these files:\n{}\n\n\
+             OVERALL GOAL:\n{}\n\n\
+             For each file: read it, combine both sides so every builder's work is kept, \
+             and remove all conflict markers. Don't change anything else.",
+            files.join("\n"),
+            params.prompt
+        );
+        let task = SubAgentTask::new("resolver", prompt)
+            .with_name("resolver")
+            .with_working_dir(ctx.working_dir.clone())
+            .with_checkpoints(ctx.checkpoints.clone());
+
+        info!(
+            "Build tool: Running resolver for {} conflicted files",
+            conflicted.len()
+        );
+        let pool = SubAgentPool::new(self.client.clone(), self.cancellation.clone())
+            .with_override_model(ctx.current_model.clone());
+        pool.execute_builders(vec![task], context.clone(), progress_tx)
+            .await
+            .pop()
+            .unwrap_or_else(|| SubAgentResult {
+                task_id: "resolver".to_string(),
+                success: false,
+                output: String::new(),
+                files_examined: Vec::new(),
+                duration_ms: 0,
+                turns_used: 0,
+                error: Some("Resolver did not run".to_string()),
+            })
+    }
+}
+
+fn has_conflict_markers(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .map(|content| {
+            content
+                .lines()
+                .any(|line| line.starts_with(CONFLICT_MARKER))
+        })
+        .unwrap_or(false)
+}
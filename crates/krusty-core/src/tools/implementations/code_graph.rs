@@ -0,0 +1,196 @@
+//! Code graph tool - Navigate calls, trait impls and modules from the index
+
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::index::{CodeGraph, CodebaseStore, ImplBlock, ModuleNode, SearchResult};
+use crate::storage::Database;
+use crate::tools::registry::Tool;
+use crate::tools::{parse_params, ToolContext, ToolResult};
+
+/// Deepest caller walk allowed
+const MAX_DEPTH: usize = 10;
+
+pub struct CodeGraphTool {
+    db_path: PathBuf,
+    codebase_path: String,
+}
+
+#[derive(Deserialize, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+enum Query {
+    Callees,
+    Callers,
+    Implementors,
+    Impls,
+    ModuleTree,
+}
+
+#[derive(Deserialize)]
+struct Params {
+    query: Query,
+    symbol: Option<String>,
+    depth: Option<usize>,
+    path: Option<String>,
+}
+
+impl CodeGraphTool {
+    pub fn new(db_path: PathBuf, codebase_path: String) -> Self {
+        Self {
+            db_path,
+            codebase_path,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for CodeGraphTool {
+    fn name(&self) -> &str {
+        "code_graph"
+    }
+
+    fn description(&self) -> &str {
+        "Navigate the indexed codebase structure without grepping: functions a symbol calls (callees), functions calling it up to N levels (callers), impl blocks of a trait (implementors) or of a type (impls), and the module tree of each crate (module_tree). Calls are matched by name."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "enum": ["callees", "callers", "implementors", "impls", "module_tree"],
+                    "description": "callees: what `symbol` calls; callers: who calls `symbol`, transitively; implementors: impls of trait `symbol`; impls: impl blocks for type `symbol`; module_tree: modules and files"
+                },
+                "symbol": {
+                    "type": "string",
+                    "description": "Function, trait or type name (required except for module_tree)"
+                },
+                "depth": {
+                    "type": "integer",
+                    "description": "Caller levels to follow (callers only, default: 3, max: 10)",
+                    "default": 3
+                },
+                "path": {
+                    "type": "string",
+                    "description": "Limit module_tree to files under this relative path (e.g. 'crates/krusty-core')"
+                }
+            },
+            "required": ["query"],
+            "additionalProperties": false
+        })
+    }
+
+    async fn execute(&self, params: Value, _ctx: &ToolContext) -> ToolResult {
+        let params = match parse_params::<Params>(params) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
+        let symbol = params.symbol.clone().unwrap_or_default();
+        if symbol.is_empty() && !matches!(params.query, Query::ModuleTree) {
+            return ToolResult::error("This query needs a symbol");
+        }
+
+        let db_path = self.db_path.clone();
+        let codebase_path = self.codebase_path.clone();
+
+        // rusqlite::Connection is !Send, so query on a blocking thread
+        let result = tokio::task::spawn_blocking(move || {
+            let db =
+                Database::new(&db_path).map_err(|e| format!("Failed to open database: {}", e))?;
+            let conn = db.conn();
+
+            let codebase_id = match CodebaseStore::new(conn).get_by_path(&codebase_path) {
+                Ok(Some(codebase)) => codebase.id,
+                Ok(None) => {
+                    return Err(
+                        "Codebase not indexed. Run /index to build the codebase index first."
+                            .to_string(),
+                    );
+                }
+                Err(e) => return Err(format!("Failed to lookup codebase: {}", e)),
+            };
+
+            let graph = CodeGraph::new(conn);
+            let output = match params.query {
+                Query::Callees => graph.callees(&codebase_id, &symbol).map(|callees| {
+                    json!({
+                        "symbol": symbol,
+                        "callees": callees.iter().map(|c| json!({
+                            "name": c.name,
+                            "definitions": c.definitions.iter().map(symbol_json).collect::<Vec<_>>(),
+                        })).collect::<Vec<_>>(),
+                    })
+                }),
+                Query::Callers => {
+                    let depth = params.depth.unwrap_or(3).clamp(1, MAX_DEPTH);
+                    graph.callers(&codebase_id, &symbol, depth).map(|callers| {
+                        json!({
+                            "symbol": symbol,
+                            "callers": callers.iter().map(|c| {
+                                let mut entry = symbol_json(&c.symbol);
+                                entry["depth"] = json!(c.depth);
+                                entry["calls"] = json!(c.calls);
+                                entry
+                            }).collect::<Vec<_>>(),
+                        })
+                    })
+                }
+                Query::Implementors => graph
+                    .implementors(&codebase_id, &symbol)
+                    .map(|impls| json!({ "trait": symbol, "impls": impls_json(&impls) })),
+                Query::Impls => graph
+                    .impls_for(&codebase_id, &symbol)
+                    .map(|impls| json!({ "type": symbol, "impls": impls_json(&impls) })),
+                Query::ModuleTree => graph
+                    .module_tree(&codebase_id, params.path.as_deref())
+                    .map(|tree| json!(tree.iter().map(module_json).collect::<Vec<_>>())),
+            };
+            output.map_err(|e| format!("Graph query failed: {}", e))
+        })
+        .await;
+
+        match result {
+            Ok(Ok(output)) => {
+                ToolResult::success(serde_json::to_string_pretty(&output).unwrap_or_default())
+            }
+            Ok(Err(e)) => ToolResult::error(e),
+            Err(e) => ToolResult::error(format!("Graph query task failed: {}", e)),
+        }
+    }
+}
+
+fn symbol_json(r: &SearchResult) -> Value {
+    json!({
+        "symbol_name": r.symbol_name,
+        "symbol_type": r.symbol_type.as_str(),
+        "file_path": r.file_path,
+        "line_start": r.line_start,
+        "line_end": r.line_end,
+        "signature": r.signature,
+    })
+}
+
+fn impls_json(impls: &[ImplBlock]) -> Vec<Value> {
+    impls
+        .iter()
+        .map(|block| {
+            let mut entry = symbol_json(&block.block);
+            entry["trait"] = json!(block.trait_name);
+            entry["methods"] = json!(block.methods.iter().map(symbol_json).collect::<Vec<_>>());
+            entry
+        })
+        .collect()
+}
+
+fn module_json(node: &ModuleNode) -> Value {
+    json!({
+        "name": node.name,
+        "files": node.files,
+        "symbols": node.symbols,
+        "children": node.children.iter().map(module_json).collect::<Vec<_>>(),
+    })
+}
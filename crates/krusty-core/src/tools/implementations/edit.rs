@@ -7,6 +7,7 @@ use tokio::fs;
 
 use similar::TextDiff;
 
+use super::lsp::diagnostics_after_write;
 use crate::tools::registry::Tool;
 use crate::tools::{parse_params, ToolContext, ToolResult};
 
@@ -106,6 +107,7 @@ impl Tool for EditTool {
 
         let diff = generate_compact_diff(&content, &new_content, &path);
 
+        ctx.checkpoint_file(&path);
         match fs::write(&path, &new_content).await {
             Ok(_) => {
                 let replaced = if params.replace_all { count } else { 1 };
@@ -121,6 +123,10 @@ impl Tool for EditTool {
                     output.push_str(&diff);
                 }
 
+                if let Some(diagnostics) = diagnostics_after_write(ctx, &path, &new_content).await {
+                    output.push_str(&diagnostics);
+                }
+
                 ToolResult::success(output)
             }
             Err(e) => ToolResult::error(format!("Failed to write file: {}", e)),
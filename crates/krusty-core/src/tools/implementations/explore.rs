@@ -10,7 +10,7 @@ use std::sync::Arc;
 use tracing::{debug, info, warn};
 
 use crate::agent::subagent::{SubAgentPool, SubAgentTask};
-use crate::agent::AgentCancellation;
+use crate::agent::{AgentCancellation, HookEvent};
 use crate::ai::client::AiClient;
 use crate::tools::registry::{Tool, ToolContext, ToolResult};
 
@@ -171,6 +171,15 @@ impl Tool for ExploreTool {
         };
         info!("Explore tool: Pool returned {} results", results.len());
 
+        for result in &results {
+            ctx.fire_user_hooks(HookEvent::subagent_stop(
+                &ctx.working_dir,
+                "explore",
+                result,
+            ))
+            .await;
+        }
+
         // Format results
         let mut output = String::new();
         let mut all_files: Vec<String> = Vec::new();
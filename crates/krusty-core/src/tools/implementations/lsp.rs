@@ -0,0 +1,172 @@
+//! LSP tool - Code navigation through language servers
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::lsp::{utf16_column, Location};
+use crate::tools::registry::Tool;
+use crate::tools::{parse_params, ToolContext, ToolResult};
+
+/// Most locations listed by `references`
+const MAX_REFERENCES: usize = 100;
+
+pub struct LspTool;
+
+#[derive(Deserialize)]
+struct Params {
+    action: String,
+    file_path: String,
+    line: u32,
+    #[serde(default)]
+    symbol: Option<String>,
+    #[serde(default)]
+    column: Option<u32>,
+}
+
+#[async_trait]
+impl Tool for LspTool {
+    fn name(&self) -> &str {
+        "lsp"
+    }
+
+    fn description(&self) -> &str {
+        "Query the language server for a symbol. Actions: definition (where it is defined), \
+         references (every use), hover (type and docs). Locate the symbol by line plus either \
+         its name on that line or a column."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "action": {
+                    "type": "string",
+                    "enum": ["definition", "references", "hover"],
+                    "description": "Query to run"
+                },
+                "file_path": {
+                    "type": "string",
+                    "description": "File containing the symbol"
+                },
+                "line": {
+                    "type": "integer",
+                    "description": "1-based line number"
+                },
+                "symbol": {
+                    "type": "string",
+                    "description": "Symbol name on that line (first occurrence is used)"
+                },
+                "column": {
+                    "type": "integer",
+                    "description": "1-based column, if symbol is not given"
+                }
+            },
+            "required": ["action", "file_path", "line"],
+            "additionalProperties": false
+        })
+    }
+
+    async fn execute(&self, params: Value, ctx: &ToolContext) -> ToolResult {
+        let params = match parse_params::<Params>(params) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
+
+        let Some(lsp) = &ctx.lsp_manager else {
+            return ToolResult::error("Language servers not available");
+        };
+
+        let path = match ctx.sandboxed_resolve(&params.file_path) {
+            Ok(p) => p,
+            Err(e) => return ToolResult::error(e),
+        };
+
+        let content = match tokio::fs::read_to_string(&path).await {
+            Ok(c) => c,
+            Err(e) => return ToolResult::error(format!("Failed to read file: {}", e)),
+        };
+        let Some(line_text) = content.lines().nth(params.line.saturating_sub(1) as usize) else {
+            return ToolResult::error(format!("Line {} is past the end of the file", params.line));
+        };
+
+        let column = match (&params.symbol, params.column) {
+            (Some(symbol), _) => match line_text.find(symbol.as_str()) {
+                Some(byte) => line_text[..byte].chars().count() as u32 + 1,
+                None => {
+                    return ToolResult::error(format!(
+                        "'{}' not found on line {}",
+                        symbol, params.line
+                    ))
+                }
+            },
+            (None, Some(column)) => column,
+            (None, None) => return ToolResult::error("Either symbol or column is required"),
+        };
+
+        let line = params.line.saturating_sub(1);
+        let character = utf16_column(line_text, column);
+
+        match params.action.as_str() {
+            "definition" => match lsp.definition(&path, line, character).await {
+                Ok(locations) => format_locations(&locations, &ctx.working_dir, "definition"),
+                Err(e) => ToolResult::error(e),
+            },
+            "references" => match lsp.references(&path, line, character).await {
+                Ok(locations) => format_locations(&locations, &ctx.working_dir, "reference"),
+                Err(e) => ToolResult::error(e),
+            },
+            "hover" => match lsp.hover(&path, line, character).await {
+                Ok(Some(text)) => ToolResult::success(text),
+                Ok(None) => ToolResult::success("No hover information"),
+                Err(e) => ToolResult::error(e),
+            },
+            other => ToolResult::error(format!(
+                "Unknown action '{}'. Use definition, references or hover.",
+                other
+            )),
+        }
+    }
+}
+
+/// List locations one per line, relative to the working directory
+fn format_locations(locations: &[Location], working_dir: &Path, noun: &str) -> ToolResult {
+    if locations.is_empty() {
+        return ToolResult::success(format!("No {} found", noun));
+    }
+
+    let mut out = format!("{} {}(s):", locations.len(), noun);
+    for location in locations.iter().take(MAX_REFERENCES) {
+        let path = location
+            .path
+            .strip_prefix(working_dir)
+            .unwrap_or(&location.path);
+        out.push_str(&format!(
+            "\n{}:{}:{}",
+            path.display(),
+            location.line,
+            location.column
+        ));
+    }
+    if locations.len() > MAX_REFERENCES {
+        out.push_str(&format!("\n... {} more", locations.len() - MAX_REFERENCES));
+    }
+    ToolResult::success(out)
+}
+
+/// Sync a modified file with its language server and describe new problems
+///
+/// Used by write and edit to append diagnostics to their results.
+pub(crate) async fn diagnostics_after_write(
+    ctx: &ToolContext,
+    path: &Path,
+    content: &str,
+) -> Option<String> {
+    let diagnostics = ctx.lsp_manager.as_ref()?.sync_file(path, content).await?;
+    if diagnostics.new.is_empty() {
+        return None;
+    }
+    Some(format!("\n\n[DIAGNOSTICS]\n{}", diagnostics.format()))
+}
@@ -7,6 +7,10 @@
 //! - bash: Execute shell commands
 //! - grep: Search with ripgrep
 //! - glob: Find files by pattern
+//! - lsp: Definition, references and hover via language servers
+//! - code_graph: Callers, callees, trait impls and module tree from the index
+//! - web_fetch: Fetch a URL as Markdown (client-side, any provider)
+//! - web_search: Query the search API configured in web.json
 //! - processes: Manage background processes
 //! - explore: Spawn parallel sub-agents for deep codebase exploration
 //! - build: Spawn parallel Opus builder agents (The Kraken)
@@ -22,10 +26,12 @@ pub mod add_subtask;
 pub mod ask_user;
 pub mod bash;
 pub mod build;
+pub mod code_graph;
 pub mod edit;
 pub mod explore;
 pub mod glob;
 pub mod grep;
+pub mod lsp;
 pub mod plan_mode;
 pub mod processes;
 pub mod read;
@@ -34,16 +40,20 @@ pub mod set_dependency;
 pub mod skill;
 pub mod task_complete;
 pub mod task_start;
+pub mod web_fetch;
+pub mod web_search;
 pub mod write;
 
 pub use add_subtask::AddSubtaskTool;
 pub use ask_user::AskUserQuestionTool;
 pub use bash::BashTool;
 pub use build::BuildTool;
+pub use code_graph::CodeGraphTool;
 pub use edit::EditTool;
 pub use explore::ExploreTool;
 pub use glob::GlobTool;
 pub use grep::GrepTool;
+pub use lsp::LspTool;
 pub use plan_mode::EnterPlanModeTool;
 pub use processes::ProcessesTool;
 pub use read::ReadTool;
@@ -52,6 +62,8 @@ pub use set_dependency::SetDependencyTool;
 pub use skill::SkillTool;
 pub use task_complete::TaskCompleteTool;
 pub use task_start::TaskStartTool;
+pub use web_fetch::WebFetchTool;
+pub use web_search::WebSearchTool;
 pub use write::WriteTool;
 
 use std::path::PathBuf;
@@ -62,7 +74,9 @@ use tokio::sync::RwLock;
 use crate::agent::AgentCancellation;
 use crate::ai::client::AiClient;
 use crate::index::EmbeddingEngine;
+use crate::paths;
 use crate::tools::registry::ToolRegistry;
+use crate::tools::web::WebToolsConfig;
 
 /// Register all built-in tools (except explore which needs client)
 pub async fn register_all_tools(registry: &ToolRegistry) {
@@ -72,6 +86,7 @@ pub async fn register_all_tools(registry: &ToolRegistry) {
     registry.register(Arc::new(BashTool)).await;
     registry.register(Arc::new(GrepTool)).await;
     registry.register(Arc::new(GlobTool)).await;
+    registry.register(Arc::new(LspTool)).await;
     registry.register(Arc::new(ProcessesTool)).await;
     registry.register(Arc::new(SkillTool)).await;
     registry.register(Arc::new(AskUserQuestionTool)).await;
@@ -80,6 +95,7 @@ pub async fn register_all_tools(registry: &ToolRegistry) {
     registry.register(Arc::new(AddSubtaskTool)).await;
     registry.register(Arc::new(SetDependencyTool)).await;
     registry.register(Arc::new(EnterPlanModeTool)).await;
+    register_web_tools(registry).await;
 }
 
 /// Register tools for ACP (excludes TUI-only tools)
@@ -96,7 +112,25 @@ pub async fn register_acp_tools(registry: &ToolRegistry) {
     registry.register(Arc::new(BashTool)).await;
     registry.register(Arc::new(GrepTool)).await;
     registry.register(Arc::new(GlobTool)).await;
+    registry.register(Arc::new(LspTool)).await;
     registry.register(Arc::new(ProcessesTool)).await;
+    register_web_tools(registry).await;
+}
+
+/// Register the client-side web tools with the settings from web.json
+///
+/// `web_search` is only registered when a search backend is configured.
+/// On Anthropic the server-executed web tools replace both.
+pub async fn register_web_tools(registry: &ToolRegistry) {
+    let config = Arc::new(WebToolsConfig::load(&paths::web_config_path()));
+    registry
+        .register(Arc::new(WebFetchTool::new(config.clone())))
+        .await;
+    if let Some(search) = &config.search {
+        registry
+            .register(Arc::new(WebSearchTool::new(search.build(), config.clone())))
+            .await;
+    }
 }
 
 /// Register the explore tool (requires AI client)
@@ -125,7 +159,7 @@ pub async fn register_build_tool(
         .await;
 }
 
-/// Register the search_codebase tool for querying the semantic index
+/// Register the search_codebase and code_graph tools for querying the index
 ///
 /// Accepts a shared embedding engine reference that gets populated lazily.
 /// The tool reads the current value at call time, so it picks up the engine
@@ -138,9 +172,12 @@ pub async fn register_search_tool(
 ) {
     registry
         .register(Arc::new(SearchCodebaseTool::new(
-            db_path,
+            db_path.clone(),
             embedding_engine,
-            codebase_path,
+            codebase_path.clone(),
         )))
         .await;
+    registry
+        .register(Arc::new(CodeGraphTool::new(db_path, codebase_path)))
+        .await;
 }
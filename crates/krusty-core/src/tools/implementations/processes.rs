@@ -1,12 +1,22 @@
 //! Processes tool - Manage background processes
 
+use std::time::Duration;
+
 use async_trait::async_trait;
+use regex::Regex;
 use serde::Deserialize;
 use serde_json::{json, Value};
 
+use crate::process::{LogChunk, LogQuery, OutputStream, WaitOutcome};
 use crate::tools::registry::Tool;
 use crate::tools::{parse_params, ToolContext, ToolResult};
 
+/// Default number of lines returned by `logs`
+const DEFAULT_LOG_LINES: usize = 50;
+/// Default and maximum `wait_for` timeouts in milliseconds (kept under the tool timeout)
+const DEFAULT_WAIT_MS: u64 = 30_000;
+const MAX_WAIT_MS: u64 = 100_000;
+
 pub struct ProcessesTool;
 
 #[derive(Deserialize)]
@@ -14,6 +24,16 @@ struct Params {
     action: String,
     #[serde(default)]
     process_id: Option<String>,
+    #[serde(default)]
+    lines: Option<usize>,
+    #[serde(default)]
+    since: Option<u64>,
+    #[serde(default)]
+    pattern: Option<String>,
+    #[serde(default)]
+    timeout: Option<u64>,
+    #[serde(default)]
+    input: Option<String>,
 }
 
 #[async_trait]
@@ -23,7 +43,9 @@ impl Tool for ProcessesTool {
     }
 
     fn description(&self) -> &str {
-        "Manage background processes. Actions: list (show all), kill (stop by ID), status (check by ID)."
+        "Manage background processes. Actions: list (show all), kill (stop by ID), status (check by ID), \
+         logs (recent output by ID), wait_for (block until output matches a regex), \
+         send_input (write to stdin by ID)."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -32,12 +54,32 @@ impl Tool for ProcessesTool {
             "properties": {
                 "action": {
                     "type": "string",
-                    "enum": ["list", "kill", "status"],
+                    "enum": ["list", "kill", "status", "logs", "wait_for", "send_input"],
                     "description": "Action to perform"
                 },
                 "process_id": {
                     "type": "string",
-                    "description": "Process ID (required for kill/status)"
+                    "description": "Process ID (required for all actions except list)"
+                },
+                "lines": {
+                    "type": "integer",
+                    "description": "logs: number of lines to return (default 50)"
+                },
+                "since": {
+                    "type": "integer",
+                    "description": "logs/wait_for: only consider output from this offset (use next_offset from a previous call)"
+                },
+                "pattern": {
+                    "type": "string",
+                    "description": "wait_for: regex to match against each output line"
+                },
+                "timeout": {
+                    "type": "number",
+                    "description": "wait_for: timeout in milliseconds (default 30000, max 100000)"
+                },
+                "input": {
+                    "type": "string",
+                    "description": "send_input: text to write to stdin (include a trailing newline to submit a line)"
                 }
             },
             "required": ["action"],
@@ -122,7 +164,134 @@ impl Tool for ProcessesTool {
                     None => ToolResult::error("Process not found"),
                 }
             }
-            _ => ToolResult::error("Unknown action. Use 'list', 'kill', or 'status'"),
+            "logs" => {
+                let Some(id) = params.process_id else {
+                    return ToolResult::error("process_id required for logs");
+                };
+
+                let lines = params.lines.unwrap_or(DEFAULT_LOG_LINES);
+                let query = match params.since {
+                    Some(offset) => LogQuery::Since {
+                        offset,
+                        limit: Some(lines),
+                    },
+                    None => LogQuery::Tail(lines),
+                };
+                let result = match user_id {
+                    Some(uid) => registry.logs_for_user(uid, &id, query).await,
+                    None => registry.logs(&id, query).await,
+                };
+                let status = match user_id {
+                    Some(uid) => registry.get_for_user(uid, &id).await,
+                    None => registry.get(&id).await,
+                }
+                .map(|p| p.display_status());
+
+                match result {
+                    Ok(chunk) => ToolResult::success(
+                        json!({
+                            "id": id,
+                            "status": status,
+                            "output": format_lines(&chunk),
+                            "next_offset": chunk.next_offset,
+                            "dropped_lines": chunk.dropped,
+                            "log_file": chunk.log_path,
+                        })
+                        .to_string(),
+                    ),
+                    Err(e) => ToolResult::error(e.to_string()),
+                }
+            }
+            "wait_for" => {
+                let Some(id) = params.process_id else {
+                    return ToolResult::error("process_id required for wait_for");
+                };
+                let Some(pattern) = params.pattern else {
+                    return ToolResult::error("pattern required for wait_for");
+                };
+                let pattern = match Regex::new(&pattern) {
+                    Ok(re) => re,
+                    Err(e) => return ToolResult::error(format!("Invalid pattern: {}", e)),
+                };
+
+                let since = params.since.unwrap_or(0);
+                let timeout = Duration::from_millis(
+                    params.timeout.unwrap_or(DEFAULT_WAIT_MS).min(MAX_WAIT_MS),
+                );
+                let result = match user_id {
+                    Some(uid) => {
+                        registry
+                            .wait_for_for_user(uid, &id, &pattern, since, timeout)
+                            .await
+                    }
+                    None => registry.wait_for(&id, &pattern, since, timeout).await,
+                };
+
+                match result {
+                    Ok(WaitOutcome::Matched(line)) => ToolResult::success(
+                        json!({
+                            "matched": true,
+                            "line": line.text,
+                            "stream": line.stream.as_str(),
+                            "next_offset": line.offset + 1,
+                        })
+                        .to_string(),
+                    ),
+                    Ok(WaitOutcome::Exited { next_offset }) => ToolResult::success(
+                        json!({
+                            "matched": false,
+                            "reason": "process output closed without a match",
+                            "next_offset": next_offset,
+                        })
+                        .to_string(),
+                    ),
+                    Ok(WaitOutcome::TimedOut { next_offset }) => ToolResult::success(
+                        json!({
+                            "matched": false,
+                            "reason": "timed out",
+                            "next_offset": next_offset,
+                        })
+                        .to_string(),
+                    ),
+                    Err(e) => ToolResult::error(e.to_string()),
+                }
+            }
+            "send_input" => {
+                let Some(id) = params.process_id else {
+                    return ToolResult::error("process_id required for send_input");
+                };
+                let Some(input) = params.input else {
+                    return ToolResult::error("input required for send_input");
+                };
+
+                let result = match user_id {
+                    Some(uid) => registry.send_input_for_user(uid, &id, &input).await,
+                    None => registry.send_input(&id, &input).await,
+                };
+
+                match result {
+                    Ok(_) => ToolResult::success(
+                        json!({"success": true, "bytes": input.len()}).to_string(),
+                    ),
+                    Err(e) => ToolResult::error(e.to_string()),
+                }
+            }
+            _ => ToolResult::error(
+                "Unknown action. Use 'list', 'kill', 'status', 'logs', 'wait_for', or 'send_input'",
+            ),
         }
     }
 }
+
+/// Join captured lines into plain text, marking stderr
+fn format_lines(chunk: &LogChunk) -> String {
+    chunk
+        .lines
+        .iter()
+        .map(|line| match line.stream {
+            OutputStream::Stdout => line.text.clone(),
+            OutputStream::Stderr => format!("[stderr] {}", line.text),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
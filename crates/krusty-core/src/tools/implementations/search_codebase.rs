@@ -8,7 +8,9 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use tokio::sync::RwLock;
 
-use crate::index::{CodebaseStore, EmbeddingEngine, SearchQuery, SemanticRetrieval, SymbolType};
+use crate::index::{
+    CodebaseStore, EmbeddingEngine, SearchMode, SearchQuery, SemanticRetrieval, SymbolType,
+};
 use crate::storage::Database;
 use crate::tools::registry::Tool;
 use crate::tools::{parse_params, ToolContext, ToolResult};
@@ -22,6 +24,8 @@ pub struct SearchCodebaseTool {
 #[derive(Deserialize)]
 struct Params {
     query: String,
+    #[serde(default)]
+    mode: SearchMode,
     symbol_type: Option<String>,
     file_pattern: Option<String>,
     limit: Option<usize>,
@@ -48,7 +52,7 @@ impl Tool for SearchCodebaseTool {
     }
 
     fn description(&self) -> &str {
-        "Search the indexed codebase for symbols, functions, structs, and modules. Returns file paths, line ranges, and signatures. Use this BEFORE grep/glob for faster, smarter results."
+        "Search the indexed codebase for symbols, functions, structs, and modules. Returns file paths, line ranges, signatures and, where generated, one-line summaries. Hybrid mode (default) combines exact keyword matches on names, paths, signatures and doc comments with semantic similarity. Use this BEFORE grep/glob for faster, smarter results."
     }
 
     fn parameters_schema(&self) -> Value {
@@ -59,9 +63,15 @@ impl Tool for SearchCodebaseTool {
                     "type": "string",
                     "description": "Search query — symbol names, function names, descriptions"
                 },
+                "mode": {
+                    "type": "string",
+                    "enum": ["hybrid", "keyword", "semantic"],
+                    "description": "keyword: exact identifiers and words; semantic: similar meaning; hybrid: both, fused (default)",
+                    "default": "hybrid"
+                },
                 "symbol_type": {
                     "type": "string",
-                    "enum": ["function", "struct", "enum", "trait", "module", "impl", "const", "static", "type_alias", "macro"],
+                    "enum": ["function", "method", "struct", "class", "interface", "enum", "trait", "module", "impl", "const", "static", "type_alias", "macro"],
                     "description": "Filter results by symbol type"
                 },
                 "file_pattern": {
@@ -112,6 +122,7 @@ impl Tool for SearchCodebaseTool {
 
             let mut search_query = SearchQuery::new()
                 .text(&params.query)
+                .mode(params.mode)
                 .limit(params.limit.unwrap_or(15));
 
             if let Some(ref st) = params.symbol_type {
@@ -156,6 +167,7 @@ impl Tool for SearchCodebaseTool {
                     "line_start": r.line_start,
                     "line_end": r.line_end,
                     "signature": r.signature,
+                    "summary": r.summary,
                     "score": format!("{:.2}", r.score),
                 })
             })
@@ -0,0 +1,126 @@
+//! Web fetch tool - Download a URL as Markdown
+//!
+//! Runs locally, so it works with every provider. Long pages are split into
+//! pages of `max_page_chars`; fetched pages are cached briefly so reading
+//! the next page doesn't download it again.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use moka::sync::Cache;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::tools::registry::Tool;
+use crate::tools::web::{self, FetchedPage, WebToolsConfig};
+use crate::tools::{parse_params, ToolContext, ToolResult};
+
+/// How long fetched pages are kept for pagination
+const CACHE_TTL: Duration = Duration::from_secs(15 * 60);
+
+pub struct WebFetchTool {
+    config: Arc<WebToolsConfig>,
+    client: Client,
+    cache: Cache<String, Arc<FetchedPage>>,
+}
+
+impl WebFetchTool {
+    pub fn new(config: Arc<WebToolsConfig>) -> Self {
+        Self {
+            client: web::fetch_client(config.clone()),
+            config,
+            cache: Cache::builder()
+                .max_capacity(32)
+                .time_to_live(CACHE_TTL)
+                .build(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Params {
+    url: String,
+    #[serde(default)]
+    page: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for WebFetchTool {
+    fn name(&self) -> &str {
+        "web_fetch"
+    }
+
+    fn description(&self) -> &str {
+        "Fetch a web page (documentation, READMEs, API references) and return it as Markdown. \
+         Long pages are split into pages: if total_pages > 1, call again with 'page' to read further."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "url": {
+                    "type": "string",
+                    "description": "The http(s) URL to fetch"
+                },
+                "page": {
+                    "type": "integer",
+                    "description": "Page of the content to return (default: 1)",
+                    "minimum": 1
+                }
+            },
+            "required": ["url"],
+            "additionalProperties": false
+        })
+    }
+
+    async fn execute(&self, params: Value, _ctx: &ToolContext) -> ToolResult {
+        let params = match parse_params::<Params>(params) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
+
+        let page = match self.cache.get(&params.url) {
+            Some(page) => page,
+            None => match web::fetch_page(&self.client, &self.config, &params.url).await {
+                Ok(page) => {
+                    let page = Arc::new(page);
+                    self.cache.insert(params.url.clone(), page.clone());
+                    page
+                }
+                Err(e) => return ToolResult::error(format!("{:#}", e)),
+            },
+        };
+
+        let page_number = params.page.unwrap_or(1).max(1);
+        let (content, total_pages) =
+            web::paginate(&page.text, page_number, self.config.page_chars());
+        if page_number > total_pages {
+            return ToolResult::error(format!(
+                "Page {} is out of range: {} has {} page{}",
+                page_number,
+                page.url,
+                total_pages,
+                if total_pages == 1 { "" } else { "s" }
+            ));
+        }
+
+        let mut output = json!({
+            "url": page.url,
+            "title": page.title,
+            "content_type": page.content_type,
+            "page": page_number,
+            "total_pages": total_pages,
+            "content": content,
+        });
+        if page_number < total_pages {
+            output["next"] = json!(format!(
+                "Call web_fetch with page {} for more",
+                page_number + 1
+            ));
+        }
+        ToolResult::success(output.to_string())
+    }
+}
@@ -0,0 +1,110 @@
+//! Web search tool - Query the search API configured in web.json
+//!
+//! The backend is pluggable (SearXNG, Brave); results outside the
+//! configured domain rules are dropped.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::tools::registry::Tool;
+use crate::tools::web::{self, SearchBackend, WebToolsConfig, DEFAULT_SEARCH_RESULTS};
+use crate::tools::{parse_params, ToolContext, ToolResult};
+
+/// Most results a single search may ask for
+const MAX_SEARCH_RESULTS: usize = 20;
+
+pub struct WebSearchTool {
+    backend: Box<dyn SearchBackend>,
+    config: Arc<WebToolsConfig>,
+    client: Client,
+}
+
+impl WebSearchTool {
+    pub fn new(backend: Box<dyn SearchBackend>, config: Arc<WebToolsConfig>) -> Self {
+        Self {
+            backend,
+            config,
+            client: web::http_client(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Params {
+    query: String,
+    #[serde(default)]
+    max_results: Option<usize>,
+}
+
+#[async_trait]
+impl Tool for WebSearchTool {
+    fn name(&self) -> &str {
+        "web_search"
+    }
+
+    fn description(&self) -> &str {
+        "Search the web. Returns titles, URLs and snippets; use web_fetch to read a result."
+    }
+
+    fn parameters_schema(&self) -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {
+                    "type": "string",
+                    "description": "The search query"
+                },
+                "max_results": {
+                    "type": "integer",
+                    "description": "Number of results (default: 8)",
+                    "minimum": 1,
+                    "maximum": MAX_SEARCH_RESULTS
+                }
+            },
+            "required": ["query"],
+            "additionalProperties": false
+        })
+    }
+
+    async fn execute(&self, params: Value, _ctx: &ToolContext) -> ToolResult {
+        let params = match parse_params::<Params>(params) {
+            Ok(p) => p,
+            Err(e) => return e,
+        };
+        let limit = params
+            .max_results
+            .unwrap_or(DEFAULT_SEARCH_RESULTS)
+            .clamp(1, MAX_SEARCH_RESULTS);
+
+        // Ask for extra results to make up for any the domain rules drop
+        let hits = match self
+            .backend
+            .search(&self.client, &params.query, limit * 2)
+            .await
+        {
+            Ok(hits) => hits,
+            Err(e) => {
+                return ToolResult::error(format!("{} search failed: {:#}", self.backend.name(), e))
+            }
+        };
+        let results: Vec<_> = hits
+            .into_iter()
+            .filter(|hit| Url::parse(&hit.url).is_ok_and(|url| self.config.check_url(&url).is_ok()))
+            .take(limit)
+            .collect();
+
+        ToolResult::success(
+            json!({
+                "query": params.query,
+                "backend": self.backend.name(),
+                "results": results,
+            })
+            .to_string(),
+        )
+    }
+}
@@ -6,6 +6,7 @@ use serde_json::{json, Value};
 use tokio::fs;
 use tracing::info;
 
+use super::lsp::diagnostics_after_write;
 use crate::tools::registry::Tool;
 use crate::tools::{parse_params, ToolContext, ToolResult};
 
@@ -83,15 +84,22 @@ impl Tool for WriteTool {
             }
         }
 
+        ctx.checkpoint_file(&path);
         match fs::write(&path, &params.content).await {
             Ok(_) => {
-                let output = json!({
+                let mut output = json!({
                     "message": format!("Successfully wrote {} lines", params.content.lines().count()),
                     "bytes_written": params.content.len(),
                     "file_path": path.display().to_string()
                 })
                 .to_string();
 
+                if let Some(diagnostics) =
+                    diagnostics_after_write(ctx, &path, &params.content).await
+                {
+                    output.push_str(&diagnostics);
+                }
+
                 ToolResult::success(output)
             }
             Err(e) => ToolResult::error(format!("Failed to write file: {}", e)),
@@ -2,18 +2,21 @@
 //!
 //! Provides the tool registry and all built-in tool implementations.
 
+pub mod checkpoints;
 pub mod git_identity;
 pub mod image;
 pub mod implementations;
 pub mod path_utils;
 pub mod registry;
+pub mod web;
 
+pub use checkpoints::{Checkpointer, FileRestore};
 pub use git_identity::{GitIdentity, GitIdentityMode};
 pub use image::{
     is_image_extension, is_supported_file, load_from_clipboard_rgba, load_from_path, load_from_url,
 };
 pub use implementations::{
     register_acp_tools, register_all_tools, register_build_tool, register_explore_tool,
-    register_search_tool,
+    register_search_tool, register_web_tools,
 };
 pub use registry::{parse_params, ToolContext, ToolOutputChunk, ToolRegistry, ToolResult};
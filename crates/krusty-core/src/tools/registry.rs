@@ -11,10 +11,13 @@ use tokio::sync::{mpsc, RwLock};
 
 use crate::agent::hooks::{HookResult, PostToolHook, PreToolHook};
 use crate::agent::subagent::AgentProgress;
+use crate::agent::user_hooks::{HookEvent, UserHookExecutor, UserHookManager};
 use crate::ai::types::AiTool;
+use crate::lsp::LspManager;
 use crate::mcp::McpManager;
 use crate::process::ProcessRegistry;
 use crate::skills::SkillsManager;
+use crate::tools::checkpoints::Checkpointer;
 use crate::tools::git_identity::GitIdentity;
 
 /// Default tool execution timeout (2 minutes)
@@ -43,6 +46,14 @@ impl ToolResult {
             is_error: true,
         }
     }
+
+    /// Append text to the output on a new line
+    pub fn append_output(&mut self, text: &str) {
+        if !self.output.is_empty() && !self.output.ends_with('\n') {
+            self.output.push('\n');
+        }
+        self.output.push_str(text);
+    }
 }
 
 /// Parse tool parameters, returning a ToolResult error on failure
@@ -71,6 +82,8 @@ pub struct ToolContext {
     pub process_registry: Option<Arc<ProcessRegistry>>,
     pub skills_manager: Option<Arc<RwLock<SkillsManager>>>,
     pub mcp_manager: Option<Arc<McpManager>>,
+    /// Language servers for post-edit diagnostics and code navigation
+    pub lsp_manager: Option<Arc<LspManager>>,
     /// Optional per-call timeout override
     pub timeout: Option<Duration>,
     /// Channel for streaming output (used by bash tool)
@@ -87,6 +100,10 @@ pub struct ToolContext {
     pub current_model: Option<String>,
     /// Git identity for commit attribution
     pub git_identity: Option<GitIdentity>,
+    /// Saves files before write tools modify them
+    pub checkpoints: Option<Arc<Checkpointer>>,
+    /// User hooks for events raised by tools (e.g. `SubagentStop`)
+    pub user_hooks: Option<Arc<RwLock<UserHookManager>>>,
 }
 
 impl Default for ToolContext {
@@ -98,6 +115,7 @@ impl Default for ToolContext {
             process_registry: None,
             skills_manager: None,
             mcp_manager: None,
+            lsp_manager: None,
             timeout: None,
             output_tx: None,
             tool_use_id: None,
@@ -106,6 +124,8 @@ impl Default for ToolContext {
             build_progress_tx: None,
             current_model: None,
             git_identity: None,
+            checkpoints: None,
+            user_hooks: None,
         }
     }
 }
@@ -141,6 +161,12 @@ impl ToolContext {
         self
     }
 
+    /// Add LSP manager to context
+    pub fn with_lsp_manager(mut self, lsp_manager: Arc<LspManager>) -> Self {
+        self.lsp_manager = Some(lsp_manager);
+        self
+    }
+
     /// Add skills manager to context
     pub fn with_skills_manager(mut self, skills_manager: Arc<RwLock<SkillsManager>>) -> Self {
         self.skills_manager = Some(skills_manager);
@@ -182,6 +208,32 @@ impl ToolContext {
         self
     }
 
+    /// Record file checkpoints before write tools modify files
+    pub fn with_checkpoints(mut self, checkpoints: Arc<Checkpointer>) -> Self {
+        self.checkpoints = Some(checkpoints);
+        self
+    }
+
+    /// Run user hooks for events raised by tools
+    pub fn with_user_hooks(mut self, user_hooks: Arc<RwLock<UserHookManager>>) -> Self {
+        self.user_hooks = Some(user_hooks);
+        self
+    }
+
+    /// Fire `event` through the user hooks, if any are configured
+    pub async fn fire_user_hooks(&self, event: HookEvent) {
+        if let Some(ref user_hooks) = self.user_hooks {
+            UserHookExecutor::fire(user_hooks, &event).await;
+        }
+    }
+
+    /// Save `path` to the current checkpoint before modifying it
+    pub fn checkpoint_file(&self, path: &std::path::Path) {
+        if let Some(ref checkpoints) = self.checkpoints {
+            checkpoints.snapshot(path);
+        }
+    }
+
     /// Resolve a path relative to working directory (absolute paths pass through)
     pub fn resolve_path(&self, path: &str) -> std::path::PathBuf {
         let p = std::path::PathBuf::from(path);
@@ -350,11 +402,18 @@ impl ToolRegistry {
     }
 
     /// Add a pre-execution hook
+    ///
+    /// Hooks run in the order they are added, each seeing the parameters as
+    /// modified by earlier hooks. Add hooks that rewrite parameters before
+    /// hooks that check them.
     pub fn add_pre_hook(&mut self, hook: Arc<dyn PreToolHook>) {
         self.pre_hooks.push(hook);
     }
 
     /// Add a post-execution hook
+    ///
+    /// Hooks run in the order they are added, each seeing the output as
+    /// modified by earlier hooks.
     pub fn add_post_hook(&mut self, hook: Arc<dyn PostToolHook>) {
         self.post_hooks.push(hook);
     }
@@ -397,7 +456,7 @@ impl ToolRegistry {
     pub async fn execute(
         &self,
         name: &str,
-        params: Value,
+        mut params: Value,
         ctx: &ToolContext,
     ) -> Option<ToolResult> {
         tracing::info!(tool = name, "ToolRegistry: execute called");
@@ -406,10 +465,17 @@ impl ToolRegistry {
         let timeout = ctx.timeout.unwrap_or(self.default_timeout);
         let start = Instant::now();
 
-        // Run pre-hooks - they can block execution
+        // Run pre-hooks - they can rewrite the parameters or block execution
         for hook in &self.pre_hooks {
             match hook.before_execute(name, &params, ctx).await {
                 HookResult::Continue => {}
+                HookResult::Modify(new_params) => {
+                    tracing::info!(tool = name, "Pre-hook modified parameters");
+                    params = new_params;
+                }
+                HookResult::ReplaceOutput(_) | HookResult::AppendOutput(_) => {
+                    tracing::warn!(tool = name, "Pre-hook tried to change output, ignored");
+                }
                 HookResult::Block { reason } => {
                     tracing::info!(tool = name, reason = %reason, "Pre-hook blocked execution");
                     return Some(ToolResult {
@@ -421,30 +487,47 @@ impl ToolRegistry {
         }
 
         // Execute the tool with timeout
-        let result = match tokio::time::timeout(timeout, tool.execute(params.clone(), ctx)).await {
-            Ok(result) => result,
-            Err(_) => {
-                tracing::warn!(
-                    tool = name,
-                    timeout_secs = timeout.as_secs(),
-                    "Tool execution timed out"
-                );
-                ToolResult {
-                    output: format!(
-                        "Tool '{}' timed out after {} seconds",
-                        name,
-                        timeout.as_secs()
-                    ),
-                    is_error: true,
+        let mut result =
+            match tokio::time::timeout(timeout, tool.execute(params.clone(), ctx)).await {
+                Ok(result) => result,
+                Err(_) => {
+                    tracing::warn!(
+                        tool = name,
+                        timeout_secs = timeout.as_secs(),
+                        "Tool execution timed out"
+                    );
+                    ToolResult {
+                        output: format!(
+                            "Tool '{}' timed out after {} seconds",
+                            name,
+                            timeout.as_secs()
+                        ),
+                        is_error: true,
+                    }
                 }
-            }
-        };
+            };
 
         let duration = start.elapsed();
 
-        // Run post-hooks - they can inspect/log but we don't modify results (yet)
+        // Run post-hooks - they can rewrite the output
         for hook in &self.post_hooks {
-            let _ = hook.after_execute(name, &params, &result, duration).await;
+            match hook.after_execute(name, &params, &result, duration).await {
+                HookResult::Continue => {}
+                HookResult::ReplaceOutput(output) => {
+                    tracing::info!(tool = name, "Post-hook replaced output");
+                    result.output = output;
+                }
+                HookResult::AppendOutput(text) => {
+                    tracing::info!(tool = name, "Post-hook appended to output");
+                    result.append_output(&text);
+                }
+                HookResult::Modify(_) => {
+                    tracing::warn!(tool = name, "Post-hook tried to change parameters, ignored");
+                }
+                HookResult::Block { reason } => {
+                    tracing::warn!(tool = name, reason = %reason, "Post-hook cannot block, tool already ran");
+                }
+            }
         }
 
         Some(result)
@@ -584,4 +667,76 @@ mod tests {
         let result = ctx.sandboxed_resolve_new_path("../other/file.txt");
         assert!(result.is_ok());
     }
+
+    struct EchoTool;
+
+    #[async_trait]
+    impl Tool for EchoTool {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn description(&self) -> &str {
+            "Echo the text parameter"
+        }
+
+        fn parameters_schema(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        async fn execute(&self, params: Value, _ctx: &ToolContext) -> ToolResult {
+            ToolResult::success(params["text"].as_str().unwrap_or_default())
+        }
+    }
+
+    struct RewriteHook;
+
+    #[async_trait]
+    impl PreToolHook for RewriteHook {
+        async fn before_execute(
+            &self,
+            _name: &str,
+            params: &Value,
+            _ctx: &ToolContext,
+        ) -> HookResult {
+            let text = params["text"].as_str().unwrap_or_default();
+            HookResult::Modify(json!({"text": text.replace("secret", "public")}))
+        }
+    }
+
+    struct OutputHook(fn(&ToolResult) -> HookResult);
+
+    #[async_trait]
+    impl PostToolHook for OutputHook {
+        async fn after_execute(
+            &self,
+            _name: &str,
+            _params: &Value,
+            result: &ToolResult,
+            _duration: Duration,
+        ) -> HookResult {
+            (self.0)(result)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_hooks_modify_params_and_output() {
+        let mut registry = ToolRegistry::new();
+        registry.add_pre_hook(Arc::new(RewriteHook));
+        registry.add_post_hook(Arc::new(OutputHook(|result| {
+            HookResult::ReplaceOutput(result.output.to_uppercase())
+        })));
+        registry.add_post_hook(Arc::new(OutputHook(|_| {
+            HookResult::AppendOutput("formatted".to_string())
+        })));
+        registry.register(Arc::new(EchoTool)).await;
+
+        let result = registry
+            .execute("echo", json!({"text": "a secret"}), &create_test_context())
+            .await
+            .unwrap();
+
+        assert!(!result.is_error);
+        assert_eq!(result.output, "A PUBLIC\nformatted");
+    }
 }
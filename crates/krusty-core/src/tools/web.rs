@@ -0,0 +1,609 @@
+//! Client-side web access for the `web_fetch` and `web_search` tools
+//!
+//! Anthropic models get server-executed web tools; these run locally so
+//! every provider can read documentation. Settings live in
+//! `~/.krusty/web.json`:
+//!
+//! ```json
+//! {
+//!   "search": { "backend": "searxng", "url": "http://localhost:8888" },
+//!   "allowed_domains": [],
+//!   "blocked_domains": ["internal.example.com"],
+//!   "max_page_chars": 20000
+//! }
+//! ```
+//!
+//! `web_search` is only offered when a search backend is configured.
+//! `web_fetch` never connects to loopback, private or link-local addresses,
+//! and every redirect hop is checked against the domain rules.
+
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use html2md::{Handle, StructuredPrinter, TagHandler, TagHandlerFactory};
+use regex::Regex;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::redirect;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::{error, warn};
+use url::Url;
+
+/// Default characters per `web_fetch` page
+pub const DEFAULT_PAGE_CHARS: usize = 20_000;
+
+/// Default number of `web_search` results
+pub const DEFAULT_SEARCH_RESULTS: usize = 8;
+
+/// Largest response body downloaded
+const MAX_BODY_BYTES: usize = 5 * 1024 * 1024;
+
+/// Timeout for one web request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Most redirects followed by `web_fetch`
+const MAX_REDIRECTS: usize = 10;
+
+/// Contents of web.json
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct WebToolsConfig {
+    /// Search API used by `web_search`
+    pub search: Option<SearchBackendConfig>,
+    /// Only these domains (and their subdomains) may be fetched, if set
+    pub allowed_domains: Vec<String>,
+    /// Domains (and their subdomains) that are never fetched
+    pub blocked_domains: Vec<String>,
+    /// Characters per `web_fetch` page
+    pub max_page_chars: Option<usize>,
+}
+
+impl WebToolsConfig {
+    /// Load settings, falling back to defaults if the file is missing or invalid
+    pub fn load(path: &Path) -> Self {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                warn!("Failed to read {}: {}", path.display(), e);
+                return Self::default();
+            }
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            error!("Failed to parse {}: {}", path.display(), e);
+            Self::default()
+        })
+    }
+
+    pub fn page_chars(&self) -> usize {
+        self.max_page_chars.unwrap_or(DEFAULT_PAGE_CHARS).max(1000)
+    }
+
+    /// Check a URL against the scheme and domain rules
+    pub fn check_url(&self, url: &Url) -> Result<()> {
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("Only http and https URLs can be fetched");
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| anyhow!("URL has no host"))?
+            .to_lowercase();
+        if self
+            .blocked_domains
+            .iter()
+            .any(|domain| domain_matches(&host, domain))
+        {
+            bail!("Domain {} is blocked in web.json", host);
+        }
+        if !self.allowed_domains.is_empty()
+            && !self
+                .allowed_domains
+                .iter()
+                .any(|domain| domain_matches(&host, domain))
+        {
+            bail!("Domain {} is not in allowed_domains in web.json", host);
+        }
+        Ok(())
+    }
+
+    /// Check a URL `web_fetch` is about to request
+    ///
+    /// Adds a guard against IP-literal hosts on internal networks to
+    /// [`Self::check_url`]; hostnames are checked after DNS resolution by
+    /// the client from [`fetch_client`].
+    pub fn check_fetch_url(&self, url: &Url) -> Result<()> {
+        self.check_url(url)?;
+        let ip = match url.host() {
+            Some(url::Host::Ipv4(ip)) => IpAddr::V4(ip),
+            Some(url::Host::Ipv6(ip)) => IpAddr::V6(ip),
+            _ => return Ok(()),
+        };
+        if !is_public_ip(ip) {
+            bail!("{} is a private, loopback or link-local address", ip);
+        }
+        Ok(())
+    }
+}
+
+/// Whether an address is reachable on the public internet
+///
+/// Rejects loopback, private, link-local (including cloud metadata
+/// endpoints), shared, unspecified and multicast ranges.
+fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || ip.is_documentation()
+                // 100.64.0.0/10 carrier-grade NAT
+                || (a == 100 && (64..128).contains(&b))
+                || a == 0)
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_public_ip(IpAddr::V4(v4)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local())
+            }
+        },
+    }
+}
+
+/// DNS resolver that drops non-public addresses
+///
+/// Checking after resolution (rather than on the hostname) also covers
+/// names like `localhost` and DNS rebinding, since the client only ever
+/// connects to the addresses returned here.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let public: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if public.is_empty() {
+                return Err(format!(
+                    "{} resolves to a private, loopback or link-local address",
+                    host
+                )
+                .into());
+            }
+            let addrs: Addrs = Box::new(public.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains
+fn domain_matches(host: &str, domain: &str) -> bool {
+    let domain = domain.trim().trim_start_matches("*.").to_lowercase();
+    !domain.is_empty()
+        && (host == domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.')))
+}
+
+/// HTTP client for `web_fetch`
+///
+/// Every redirect hop goes through [`WebToolsConfig::check_fetch_url`] and
+/// hostnames only resolve to public addresses.
+pub fn fetch_client(config: Arc<WebToolsConfig>) -> Client {
+    let policy = redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            return attempt.error(anyhow!("Too many redirects"));
+        }
+        match config.check_fetch_url(attempt.url()) {
+            Ok(()) => attempt.follow(),
+            Err(e) => attempt.error(e),
+        }
+    });
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent("Krusty/1.0")
+        .redirect(policy)
+        .dns_resolver(Arc::new(PublicResolver))
+        .build()
+        .unwrap_or_default()
+}
+
+/// HTTP client for search backends
+///
+/// Not restricted to public addresses: a self-hosted SearXNG usually runs
+/// on localhost.
+pub fn http_client() -> Client {
+    Client::builder()
+        .timeout(REQUEST_TIMEOUT)
+        .user_agent("Krusty/1.0")
+        .build()
+        .unwrap_or_default()
+}
+
+/// A downloaded page converted to text
+#[derive(Debug, Clone)]
+pub struct FetchedPage {
+    /// URL after redirects
+    pub url: String,
+    pub title: Option<String>,
+    pub content_type: String,
+    /// Markdown for HTML pages, the body as-is for text
+    pub text: String,
+}
+
+/// Download a URL and convert it to Markdown
+///
+/// `client` should come from [`fetch_client`] so redirects are checked too.
+pub async fn fetch_page(
+    client: &Client,
+    config: &WebToolsConfig,
+    url: &str,
+) -> Result<FetchedPage> {
+    let parsed = Url::parse(url).with_context(|| format!("Invalid URL: {}", url))?;
+    config.check_fetch_url(&parsed)?;
+
+    let mut response = client
+        .get(parsed)
+        .header(
+            reqwest::header::ACCEPT,
+            "text/html, text/markdown, text/plain, application/json;q=0.9, */*;q=0.5",
+        )
+        .send()
+        .await
+        .with_context(|| format!("Failed to fetch {}", url))?;
+    let final_url = response.url().clone();
+
+    let status = response.status();
+    if !status.is_success() {
+        bail!("{} returned HTTP {}", final_url, status);
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("text/html")
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_lowercase();
+    if !is_text_type(&content_type) {
+        bail!("Unsupported content type {} at {}", content_type, final_url);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        if body.len() > MAX_BODY_BYTES {
+            body.truncate(MAX_BODY_BYTES);
+            break;
+        }
+    }
+    let body = String::from_utf8_lossy(&body);
+
+    let (title, text) = if content_type.contains("html") {
+        (html_title(&body), html_to_markdown(&body))
+    } else {
+        (None, body.trim().to_string())
+    };
+    Ok(FetchedPage {
+        url: final_url.to_string(),
+        title,
+        content_type,
+        text,
+    })
+}
+
+fn is_text_type(content_type: &str) -> bool {
+    content_type.starts_with("text/")
+        || content_type.contains("html")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+        || content_type.contains("markdown")
+}
+
+static TITLE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap());
+
+static BLANK_LINES_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\n{3,}").unwrap());
+
+/// Contents of the page's `<title>`
+pub fn html_title(html: &str) -> Option<String> {
+    let title = TITLE_RE.captures(html)?.get(1)?.as_str();
+    let title = decode_entities(title.split_whitespace().collect::<Vec<_>>().join(" "));
+    (!title.is_empty()).then_some(title)
+}
+
+fn decode_entities(text: String) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Tags whose content is never part of the readable page
+const SKIPPED_TAGS: &[&str] = &[
+    "head", "script", "style", "noscript", "template", "svg", "nav", "form", "button", "select",
+    "iframe",
+];
+
+/// Drops a tag and everything inside it
+struct SkipTag;
+
+impl TagHandler for SkipTag {
+    fn handle(&mut self, _tag: &Handle, _printer: &mut StructuredPrinter) {}
+
+    fn after_handle(&mut self, _printer: &mut StructuredPrinter) {}
+
+    fn skip_descendants(&self) -> bool {
+        true
+    }
+}
+
+impl TagHandlerFactory for SkipTag {
+    fn instantiate(&self) -> Box<dyn TagHandler> {
+        Box::new(SkipTag)
+    }
+}
+
+/// Convert an HTML page to Markdown, dropping scripts, styles and navigation
+pub fn html_to_markdown(html: &str) -> String {
+    let mut handlers: HashMap<String, Box<dyn TagHandlerFactory>> = HashMap::new();
+    for tag in SKIPPED_TAGS {
+        handlers.insert(tag.to_string(), Box::new(SkipTag));
+    }
+    let markdown = html2md::parse_html_custom(html, &handlers);
+    let trimmed: Vec<&str> = markdown.lines().map(str::trim_end).collect();
+    BLANK_LINES_RE
+        .replace_all(trimmed.join("\n").trim(), "\n\n")
+        .into_owned()
+}
+
+/// One page of `text`, `page_chars` characters per page (1-based)
+///
+/// Pages end at a line break where possible. Returns the page and the
+/// total number of pages.
+pub fn paginate(text: &str, page: usize, page_chars: usize) -> (String, usize) {
+    let mut pages = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = rest
+            .char_indices()
+            .nth(page_chars)
+            .map(|(i, _)| i)
+            .unwrap_or(rest.len());
+        if end < rest.len() {
+            if let Some(newline) = rest[..end].rfind('\n').filter(|&i| i > end / 2) {
+                end = newline + 1;
+            }
+        }
+        pages.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    let total = pages.len().max(1);
+    let content = pages
+        .get(page.saturating_sub(1))
+        .map(|p| p.to_string())
+        .unwrap_or_default();
+    (content, total)
+}
+
+/// Which search API `web_search` uses
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum SearchBackendConfig {
+    /// A SearXNG instance with the JSON format enabled
+    Searxng {
+        url: String,
+        #[serde(default)]
+        api_key: Option<String>,
+    },
+    /// The Brave Search API
+    Brave { api_key: String },
+}
+
+impl SearchBackendConfig {
+    pub fn build(&self) -> Box<dyn SearchBackend> {
+        match self {
+            Self::Searxng { url, api_key } => Box::new(SearxngBackend {
+                url: url.trim_end_matches('/').to_string(),
+                api_key: api_key.clone(),
+            }),
+            Self::Brave { api_key } => Box::new(BraveBackend {
+                api_key: api_key.clone(),
+            }),
+        }
+    }
+}
+
+/// A web search result
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub title: String,
+    pub url: String,
+    #[serde(default)]
+    pub snippet: String,
+}
+
+/// A search API `web_search` can query
+#[async_trait]
+pub trait SearchBackend: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchHit>>;
+}
+
+/// SearXNG (`/search?format=json`)
+struct SearxngBackend {
+    url: String,
+    api_key: Option<String>,
+}
+
+#[async_trait]
+impl SearchBackend for SearxngBackend {
+    fn name(&self) -> &str {
+        "searxng"
+    }
+
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        let mut request = client
+            .get(format!("{}/search", self.url))
+            .query(&[("q", query), ("format", "json")]);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let json: Value = request.send().await?.error_for_status()?.json().await?;
+        Ok(parse_results(&json["results"], "content", limit))
+    }
+}
+
+/// Brave Search API
+struct BraveBackend {
+    api_key: String,
+}
+
+#[async_trait]
+impl SearchBackend for BraveBackend {
+    fn name(&self) -> &str {
+        "brave"
+    }
+
+    async fn search(&self, client: &Client, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        let count = limit.to_string();
+        let json: Value = client
+            .get("https://api.search.brave.com/res/v1/web/search")
+            .query(&[("q", query), ("count", count.as_str())])
+            .header("X-Subscription-Token", &self.api_key)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(parse_results(&json["web"]["results"], "description", limit))
+    }
+}
+
+/// Read `{title, url, <snippet_key>}` objects from a search API response
+fn parse_results(results: &Value, snippet_key: &str, limit: usize) -> Vec<SearchHit> {
+    results
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    Some(SearchHit {
+                        title: item["title"].as_str()?.trim().to_string(),
+                        url: item["url"].as_str()?.to_string(),
+                        snippet: item[snippet_key]
+                            .as_str()
+                            .unwrap_or_default()
+                            .trim()
+                            .to_string(),
+                    })
+                })
+                .take(limit)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_domain_rules() {
+        let config = WebToolsConfig {
+            allowed_domains: vec!["docs.rs".to_string(), "*.rust-lang.org".to_string()],
+            blocked_domains: vec!["blog.rust-lang.org".to_string()],
+            ..Default::default()
+        };
+        let check = |url: &str| config.check_url(&Url::parse(url).unwrap()).is_ok();
+        assert!(check("https://docs.rs/serde"));
+        assert!(check("https://doc.rust-lang.org/std/"));
+        assert!(!check("https://blog.rust-lang.org/"));
+        assert!(!check("https://notdocs.rs/"));
+        assert!(!check("https://example.com/"));
+        assert!(!check("file:///etc/passwd"));
+    }
+
+    #[test]
+    fn test_fetch_rejects_internal_addresses() {
+        let config = WebToolsConfig::default();
+        let check = |url: &str| config.check_fetch_url(&Url::parse(url).unwrap()).is_ok();
+        assert!(!check("http://127.0.0.1:8080/"));
+        assert!(!check("http://169.254.169.254/latest/meta-data/"));
+        assert!(!check("http://10.0.0.5/"));
+        assert!(!check("http://192.168.1.1/"));
+        assert!(!check("http://[::1]/"));
+        assert!(!check("http://[::ffff:127.0.0.1]/"));
+        assert!(!check("http://[fe80::1]/"));
+        assert!(check("http://93.184.216.34/"));
+        assert!(check("https://docs.rs/"));
+    }
+
+    #[tokio::test]
+    async fn test_resolver_drops_localhost() {
+        let result = PublicResolver.resolve("localhost".parse().unwrap()).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_html_to_markdown_drops_page_chrome() {
+        let html = "<html><head><title>Guide &amp; Docs</title><style>p{}</style></head>\
+                    <body><nav><a href=\"/\">Home</a></nav><h1>Install</h1>\
+                    <script>track()</script><p>Run <code>cargo add</code>.</p></body></html>";
+        assert_eq!(html_title(html).as_deref(), Some("Guide & Docs"));
+        let markdown = html_to_markdown(html);
+        assert!(markdown.contains("Install"));
+        assert!(markdown.contains("`cargo add`"));
+        assert!(!markdown.contains("track()"));
+        assert!(!markdown.contains("Home"));
+        assert!(!markdown.contains("Guide"));
+    }
+
+    #[test]
+    fn test_paginate() {
+        let text = "line one\nline two\nline three\n";
+        let (first, total) = paginate(text, 1, 12);
+        assert_eq!(first, "line one\n");
+        assert_eq!(total, 3);
+        let (last, _) = paginate(text, 3, 12);
+        assert_eq!(last, "line three\n");
+        assert_eq!(paginate(text, 9, 12).0, "");
+        assert_eq!(paginate("", 1, 12), (String::new(), 1));
+    }
+
+    #[test]
+    fn test_parse_search_results() {
+        let json = serde_json::json!([
+            {"title": " Serde ", "url": "https://serde.rs", "content": "Serialization"},
+            {"title": "No URL"},
+            {"title": "Docs", "url": "https://docs.rs/serde"}
+        ]);
+        let hits = parse_results(&json, "content", 10);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].title, "Serde");
+        assert_eq!(hits[0].snippet, "Serialization");
+        assert_eq!(hits[1].snippet, "");
+        assert_eq!(parse_results(&json, "content", 1).len(), 1);
+    }
+}
@@ -0,0 +1,95 @@
+//! Recall and latency of the HNSW graph against brute-force search
+//!
+//! The default test runs on a small synthetic set. For the 50k-symbol
+//! benchmark run `cargo test --release --test ann_recall -- --ignored --nocapture`.
+
+use std::time::{Duration, Instant};
+
+use krusty_core::index::{EmbeddingEngine, HnswIndex};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+const K: usize = 10;
+const EF: usize = krusty_core::index::ann::DEFAULT_EF_SEARCH;
+
+struct Report {
+    recall: f32,
+    build: Duration,
+    ann: Duration,
+    brute_force: Duration,
+}
+
+/// Vectors scattered around random cluster centres, like embeddings of
+/// related symbols
+fn clustered_vectors(rng: &mut StdRng, count: usize, dim: usize) -> Vec<Vec<f32>> {
+    let centres: Vec<Vec<f32>> = (0..(count / 50).max(1))
+        .map(|_| (0..dim).map(|_| rng.gen_range(-1.0..1.0)).collect())
+        .collect();
+    (0..count)
+        .map(|i| {
+            centres[i % centres.len()]
+                .iter()
+                .map(|c| c + rng.gen_range(-0.3..0.3))
+                .collect()
+        })
+        .collect()
+}
+
+fn benchmark(count: usize, dim: usize, queries: usize) -> Report {
+    let mut rng = StdRng::seed_from_u64(7);
+    let vectors = clustered_vectors(&mut rng, count + queries, dim);
+    let (data, queries) = vectors.split_at(count);
+
+    let started = Instant::now();
+    let mut index = HnswIndex::new(dim);
+    for (id, vector) in data.iter().enumerate() {
+        index.insert(id as i64, vector).unwrap();
+    }
+    let build = started.elapsed();
+
+    let candidates: Vec<(usize, Vec<f32>)> = data.iter().cloned().enumerate().collect();
+    let mut ann = Duration::ZERO;
+    let mut brute_force = Duration::ZERO;
+    let mut found = 0;
+
+    for query in queries {
+        let started = Instant::now();
+        let hits = index.search(query, K, EF);
+        ann += started.elapsed();
+
+        let started = Instant::now();
+        let exact = EmbeddingEngine::top_k_similar(query, &candidates, K);
+        brute_force += started.elapsed();
+
+        found += exact
+            .iter()
+            .filter(|(id, _)| hits.iter().any(|(hit, _)| *hit == *id as i64))
+            .count();
+    }
+
+    let report = Report {
+        recall: found as f32 / (queries.len() * K) as f32,
+        build,
+        ann: ann / queries.len() as u32,
+        brute_force: brute_force / queries.len() as u32,
+    };
+    println!(
+        "{count} vectors x {dim} dims: recall@{K} {:.3}, build {:?}, query {:?} (brute force {:?})",
+        report.recall, report.build, report.ann, report.brute_force
+    );
+    report
+}
+
+#[test]
+fn test_ann_recall_small() {
+    let report = benchmark(3_000, 32, 50);
+    assert!(report.recall >= 0.9, "recall {}", report.recall);
+}
+
+#[test]
+#[ignore = "benchmark: run in release mode"]
+fn bench_ann_50k_symbols() {
+    let report = benchmark(50_000, 384, 200);
+    assert!(report.recall >= 0.9, "recall {}", report.recall);
+    assert!(report.ann < report.brute_force);
+}
@@ -0,0 +1,141 @@
+//! LSP client tests against the fake language server in `tests/support`
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use krusty_core::lsp::{LspManager, LspServerConfig};
+use krusty_core::tools::implementations::{EditTool, LspTool};
+use krusty_core::tools::registry::Tool;
+use krusty_core::tools::ToolContext;
+use serde_json::json;
+use tempfile::TempDir;
+
+fn fake_manager(root: &Path) -> LspManager {
+    let server =
+        LspServerConfig::new("fake", env!("CARGO_BIN_EXE_krusty-fake-lsp")).with_extensions(["rs"]);
+    LspManager::with_servers(root.to_path_buf(), vec![server])
+        .with_diagnostics_timeout(Duration::from_secs(5))
+}
+
+#[tokio::test]
+async fn test_sync_reports_only_new_diagnostics() {
+    let dir = TempDir::new().unwrap();
+    let lsp = fake_manager(dir.path());
+    let path = dir.path().join("main.rs");
+
+    let clean = lsp.sync_file(&path, "fn main() {}\n").await.unwrap();
+    assert_eq!(clean.server, "fake");
+    assert!(clean.new.is_empty());
+
+    let broken = lsp
+        .sync_file(&path, "fn main() {}\nlet x = ERROR;\n")
+        .await
+        .unwrap();
+    assert_eq!(broken.new.len(), 1);
+    assert_eq!(broken.new[0].line, 2);
+    assert_eq!(broken.new[0].column, 9);
+    assert_eq!(broken.error_count, 1);
+
+    // Moving an existing problem to another line doesn't make it new
+    let shifted = lsp
+        .sync_file(&path, "// moved\nfn main() {}\nlet x = ERROR;\n// WARN\n")
+        .await
+        .unwrap();
+    assert_eq!(shifted.new.len(), 1);
+    assert_eq!(shifted.new[0].message, "found WARN marker");
+    assert_eq!(shifted.error_count, 1);
+
+    lsp.shutdown_all().await;
+}
+
+#[tokio::test]
+async fn test_unhandled_files_are_ignored() {
+    let dir = TempDir::new().unwrap();
+    let lsp = fake_manager(dir.path());
+
+    assert!(lsp
+        .sync_file(&dir.path().join("notes.txt"), "ERROR")
+        .await
+        .is_none());
+    assert!(lsp.running_servers().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_missing_server_is_not_retried() {
+    let dir = TempDir::new().unwrap();
+    let server =
+        LspServerConfig::new("missing", "krusty-no-such-language-server").with_extensions(["rs"]);
+    let lsp = LspManager::with_servers(dir.path().to_path_buf(), vec![server]);
+    let path = dir.path().join("lib.rs");
+
+    assert!(lsp.sync_file(&path, "ERROR").await.is_none());
+    assert!(lsp.definition(&path, 0, 0).await.is_err());
+}
+
+#[tokio::test]
+async fn test_navigation() {
+    let dir = TempDir::new().unwrap();
+    let lsp = fake_manager(dir.path());
+    let path = dir.path().join("lib.rs");
+    std::fs::write(&path, "fn helper() {}\nfn main() { helper(); helper(); }\n").unwrap();
+
+    let definition = lsp.definition(&path, 1, 13).await.unwrap();
+    assert_eq!(definition.len(), 1);
+    assert_eq!(definition[0].path, path);
+    assert_eq!((definition[0].line, definition[0].column), (1, 4));
+
+    let references = lsp.references(&path, 1, 13).await.unwrap();
+    assert_eq!(references.len(), 3);
+
+    let hover = lsp.hover(&path, 0, 4).await.unwrap();
+    assert_eq!(hover.as_deref(), Some("hover: helper"));
+
+    lsp.shutdown_all().await;
+}
+
+#[tokio::test]
+async fn test_tools_use_language_server() {
+    let dir = TempDir::new().unwrap();
+    let lsp = Arc::new(fake_manager(dir.path()));
+    let path = dir.path().join("main.rs");
+    std::fs::write(&path, "fn helper() {}\nfn main() { helper(); }\n").unwrap();
+
+    let ctx = ToolContext {
+        working_dir: dir.path().to_path_buf(),
+        ..Default::default()
+    }
+    .with_lsp_manager(lsp.clone());
+
+    let result = EditTool
+        .execute(
+            json!({
+                "file_path": path.to_string_lossy(),
+                "old_string": "helper(); }",
+                "new_string": "helper(); ERROR }",
+            }),
+            &ctx,
+        )
+        .await;
+    assert!(!result.is_error);
+    assert!(result.output.contains("[DIAGNOSTICS]"));
+    assert!(result
+        .output
+        .contains("error[2:23] found ERROR marker (fake-lsp)"));
+
+    let result = LspTool
+        .execute(
+            json!({
+                "action": "definition",
+                "file_path": "main.rs",
+                "line": 2,
+                "symbol": "helper",
+            }),
+            &ctx,
+        )
+        .await;
+    assert!(!result.is_error);
+    assert!(result.output.contains("main.rs:1:4"));
+
+    lsp.shutdown_all().await;
+}
@@ -0,0 +1,321 @@
+//! MCP HTTP transport tests against a local stand-in server
+
+use std::io::Write;
+use std::path::Path;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use krusty_core::mcp::{McpContent, McpManager, McpServerStatus};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+const TOKEN: &str = "secret-token";
+const SESSION: &str = "session-1";
+
+/// Which transport the stand-in server speaks
+#[derive(Clone, Copy)]
+enum Flavor {
+    Streamable,
+    LegacySse,
+}
+
+/// Answer a JSON-RPC message, or `None` for notifications
+fn handle_rpc(message: &Value) -> Option<Value> {
+    let id = message.get("id")?.clone();
+    let result = match message["method"].as_str()? {
+        "initialize" => json!({
+            "protocolVersion": "2024-11-05",
+            "capabilities": { "tools": {}, "resources": {}, "prompts": {} },
+            "serverInfo": { "name": "stand-in", "version": "1.0" },
+        }),
+        "tools/list" => json!({
+            "tools": [{
+                "name": "echo",
+                "description": "Echo text back",
+                "inputSchema": { "type": "object", "properties": { "text": { "type": "string" } } },
+            }]
+        }),
+        "tools/call" => json!({
+            "content": [{ "type": "text", "text": message["params"]["arguments"]["text"] }],
+        }),
+        // Two pages, to exercise cursors
+        "resources/list" => match message["params"]["cursor"].as_str() {
+            None => json!({
+                "resources": [{ "uri": "docs://readme", "name": "README", "mimeType": "text/markdown" }],
+                "nextCursor": "page-2",
+            }),
+            Some(_) => json!({
+                "resources": [{ "uri": "docs://changelog", "name": "Changelog" }],
+            }),
+        },
+        "resources/templates/list" => json!({
+            "resourceTemplates": [{ "uriTemplate": "docs://{page}", "name": "Page" }],
+        }),
+        "resources/read" => json!({
+            "contents": [{
+                "uri": message["params"]["uri"],
+                "mimeType": "text/markdown",
+                "text": format!("contents of {}", message["params"]["uri"].as_str().unwrap_or("")),
+            }],
+        }),
+        "prompts/list" => json!({
+            "prompts": [{
+                "name": "review",
+                "description": "Review a file",
+                "arguments": [
+                    { "name": "file", "required": true },
+                    { "name": "focus" },
+                ],
+            }],
+        }),
+        "prompts/get" => json!({
+            "messages": [{
+                "role": "user",
+                "content": {
+                    "type": "text",
+                    "text": format!("Review {}", message["params"]["arguments"]["file"].as_str().unwrap_or("")),
+                },
+            }],
+        }),
+        other => {
+            return Some(json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": format!("Unknown method {}", other) },
+            }))
+        }
+    };
+    Some(json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+}
+
+fn header<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.as_str().as_str().eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+fn respond(request: Request, status: u16, body: &str, headers: &[(&str, &str)]) {
+    let mut response = Response::from_string(body).with_status_code(status);
+    for (name, value) in headers {
+        response.add_header(Header::from_bytes(name.as_bytes(), value.as_bytes()).unwrap());
+    }
+    let _ = request.respond(response);
+}
+
+/// Start an SSE response, written straight to the socket so every event is
+/// flushed immediately
+fn start_event_stream(request: Request) -> Box<dyn Write + Send> {
+    let mut writer = request.into_writer();
+    let _ = writer.write_all(
+        b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nTransfer-Encoding: chunked\r\n\r\n",
+    );
+    let _ = writer.flush();
+    writer
+}
+
+fn send_event(writer: &mut dyn Write, event: &str, data: &str) -> std::io::Result<()> {
+    let payload = format!("event: {}\ndata: {}\n\n", event, data);
+    write!(writer, "{:x}\r\n{}\r\n", payload.len(), payload)?;
+    writer.flush()
+}
+
+fn end_event_stream(mut writer: Box<dyn Write + Send>) {
+    let _ = writer.write_all(b"0\r\n\r\n");
+    let _ = writer.flush();
+}
+
+/// Serve MCP over HTTP on a random port, returning the server URL
+///
+/// Every request must carry the bearer token and the `X-Team` header.
+fn start_server(flavor: Flavor) -> String {
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let port = server.server_addr().to_ip().unwrap().port();
+    let stream: Arc<Mutex<Option<mpsc::Sender<String>>>> = Arc::new(Mutex::new(None));
+
+    thread::spawn(move || {
+        for request in server.incoming_requests() {
+            let stream = stream.clone();
+            thread::spawn(move || handle_request(request, flavor, &stream));
+        }
+    });
+
+    format!("http://127.0.0.1:{}/mcp", port)
+}
+
+fn handle_request(
+    mut request: Request,
+    flavor: Flavor,
+    stream: &Mutex<Option<mpsc::Sender<String>>>,
+) {
+    let authorized = header(&request, "Authorization")
+        == Some(format!("Bearer {}", TOKEN).as_str())
+        && header(&request, "X-Team") == Some("krusty");
+    if !authorized {
+        return respond(request, 401, "unauthorized", &[]);
+    }
+
+    let mut body = String::new();
+    let _ = request.as_reader().read_to_string(&mut body);
+    let message: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+    let method = request.method().clone();
+    let path = request.url().to_string();
+
+    match (flavor, method, path.as_str()) {
+        (Flavor::Streamable, Method::Post, "/mcp") => {
+            let is_initialize = message["method"] == "initialize";
+            if !is_initialize && header(&request, "Mcp-Session-Id") != Some(SESSION) {
+                return respond(request, 400, "missing session", &[]);
+            }
+            let Some(reply) = handle_rpc(&message) else {
+                return respond(request, 202, "", &[]);
+            };
+            if message["method"] == "tools/call" {
+                // Reply on an event stream instead of a JSON body
+                let mut writer = start_event_stream(request);
+                let _ = send_event(&mut writer, "message", &reply.to_string());
+                end_event_stream(writer);
+            } else {
+                respond(
+                    request,
+                    200,
+                    &reply.to_string(),
+                    &[
+                        ("Content-Type", "application/json"),
+                        ("Mcp-Session-Id", SESSION),
+                    ],
+                );
+            }
+        }
+        (Flavor::LegacySse, Method::Get, "/mcp") => {
+            let (tx, rx) = mpsc::channel();
+            *stream.lock().unwrap() = Some(tx);
+            let mut writer = start_event_stream(request);
+            if send_event(&mut writer, "endpoint", "/messages?session=abc").is_err() {
+                return;
+            }
+            for reply in rx {
+                if send_event(&mut writer, "message", &reply).is_err() {
+                    return;
+                }
+            }
+        }
+        (Flavor::LegacySse, Method::Post, "/messages?session=abc") => {
+            if let Some(reply) = handle_rpc(&message) {
+                if let Some(tx) = stream.lock().unwrap().as_ref() {
+                    let _ = tx.send(reply.to_string());
+                }
+            }
+            respond(request, 202, "", &[]);
+        }
+        _ => respond(request, 405, "method not allowed", &[]),
+    }
+}
+
+fn write_config(dir: &Path, url: &str, token: Option<&str>) {
+    let mut server = json!({ "type": "http", "url": url, "headers": { "X-Team": "krusty" } });
+    if let Some(token) = token {
+        server["authorization_token"] = json!(token);
+    }
+    let config = json!({ "mcpServers": { "remote": server } });
+    std::fs::write(dir.join(".mcp.json"), config.to_string()).unwrap();
+}
+
+async fn assert_echo_works(url: &str) {
+    let dir = TempDir::new().unwrap();
+    write_config(dir.path(), url, Some(TOKEN));
+
+    let manager = McpManager::new(dir.path().to_path_buf());
+    manager.load_config().await.unwrap();
+    manager.connect("remote").await.unwrap();
+
+    let tools = manager.get_all_tools().await;
+    assert_eq!(tools.len(), 1);
+    assert_eq!(tools[0].0, "remote");
+    assert_eq!(tools[0].1.name, "echo");
+
+    let result = manager
+        .call_tool("remote", "echo", json!({ "text": "hello" }))
+        .await
+        .unwrap();
+    assert!(!result.is_error);
+    assert!(matches!(&result.content[0], McpContent::Text { text } if text == "hello"));
+
+    let servers = manager.list_servers().await;
+    assert_eq!(servers[0].server_type, "http");
+    assert_eq!(servers[0].status, McpServerStatus::Connected);
+    assert_eq!(servers[0].tool_count, 1);
+}
+
+#[tokio::test]
+async fn test_streamable_http_server() {
+    let url = start_server(Flavor::Streamable);
+    assert_echo_works(&url).await;
+}
+
+#[tokio::test]
+async fn test_falls_back_to_legacy_sse() {
+    let url = start_server(Flavor::LegacySse);
+    assert_echo_works(&url).await;
+}
+
+#[tokio::test]
+async fn test_rejected_without_token() {
+    let url = start_server(Flavor::Streamable);
+    let dir = TempDir::new().unwrap();
+    write_config(dir.path(), &url, None);
+
+    let manager = McpManager::new(dir.path().to_path_buf());
+    manager.load_config().await.unwrap();
+    let err = manager.connect("remote").await.unwrap_err();
+    assert!(err.to_string().contains("401"), "{}", err);
+    assert!(manager.get_client("remote").await.is_none());
+}
+
+#[tokio::test]
+async fn test_resources_and_prompts() {
+    let url = start_server(Flavor::Streamable);
+    let dir = TempDir::new().unwrap();
+    write_config(dir.path(), &url, Some(TOKEN));
+
+    let manager = McpManager::new(dir.path().to_path_buf());
+    manager.load_config().await.unwrap();
+    manager.connect("remote").await.unwrap();
+
+    let resources = manager.get_all_resources().await;
+    let uris: Vec<_> = resources.iter().map(|(_, r)| r.uri.as_str()).collect();
+    assert_eq!(uris, vec!["docs://changelog", "docs://readme"]);
+
+    let contents = manager
+        .read_resource("remote", "docs://readme")
+        .await
+        .unwrap();
+    assert_eq!(
+        contents[0].text.as_deref(),
+        Some("contents of docs://readme")
+    );
+
+    let prompts = manager.get_all_prompts().await;
+    assert_eq!(prompts.len(), 1);
+    let (server, prompt) = &prompts[0];
+    assert_eq!(server, "remote");
+    assert!(prompt.arguments[0].required);
+    assert!(!prompt.arguments[1].required);
+
+    let arguments = [("file".to_string(), "main.rs".to_string())].into();
+    let expanded = manager
+        .get_prompt("remote", "review", arguments)
+        .await
+        .unwrap();
+    assert_eq!(expanded.text(), "Review main.rs");
+
+    let servers = manager.list_servers().await;
+    assert_eq!(servers[0].resources.len(), 2);
+    assert_eq!(
+        servers[0].resource_templates[0].uri_template,
+        "docs://{page}"
+    );
+    assert_eq!(servers[0].prompts[0].name, "review");
+}
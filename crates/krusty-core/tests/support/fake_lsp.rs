@@ -0,0 +1,188 @@
+//! Minimal language server used by the LSP client tests
+//!
+//! Speaks just enough of the protocol over stdio:
+//! - publishes an error for each line containing `ERROR` and a warning for
+//!   each line containing `WARN` whenever a document is opened or changed
+//! - definition: the first line declaring `fn <word>`
+//! - references: every occurrence of the word under the cursor
+//! - hover: `hover: <word>`
+
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+
+fn main() {
+    let stdin = std::io::stdin();
+    let mut reader = BufReader::new(stdin.lock());
+    let mut documents: HashMap<String, String> = HashMap::new();
+
+    while let Some(message) = read_message(&mut reader) {
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match (method, id) {
+            (Some("initialize"), Some(id)) => respond(
+                id,
+                json!({
+                    "capabilities": {
+                        "textDocumentSync": 1,
+                        "definitionProvider": true,
+                        "referencesProvider": true,
+                        "hoverProvider": true,
+                    }
+                }),
+            ),
+            // Exercise the client's handling of server-to-client requests
+            (Some("initialized"), None) => send(&json!({
+                "jsonrpc": "2.0",
+                "id": "config-1",
+                "method": "workspace/configuration",
+                "params": { "items": [{ "section": "fake" }] },
+            })),
+            (Some("textDocument/didOpen"), None) => {
+                let doc = &params["textDocument"];
+                let uri = doc["uri"].as_str().unwrap_or_default().to_string();
+                let text = doc["text"].as_str().unwrap_or_default().to_string();
+                publish(&uri, &text);
+                documents.insert(uri, text);
+            }
+            (Some("textDocument/didChange"), None) => {
+                let uri = params["textDocument"]["uri"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                let text = params["contentChanges"][0]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_string();
+                publish(&uri, &text);
+                documents.insert(uri, text);
+            }
+            (Some("textDocument/definition"), Some(id)) => {
+                let (uri, text, word) = lookup(&documents, &params);
+                let result = text
+                    .lines()
+                    .position(|l| l.contains(&format!("fn {}", word)))
+                    .map(|line| {
+                        let col = text.lines().nth(line).unwrap().find(&word).unwrap();
+                        location(&uri, line, col)
+                    })
+                    .unwrap_or(Value::Null);
+                respond(id, result);
+            }
+            (Some("textDocument/references"), Some(id)) => {
+                let (uri, text, word) = lookup(&documents, &params);
+                let locations: Vec<Value> = text
+                    .lines()
+                    .enumerate()
+                    .flat_map(|(line, l)| {
+                        l.match_indices(word.as_str())
+                            .map(move |(col, _)| (line, col))
+                            .collect::<Vec<_>>()
+                    })
+                    .map(|(line, col)| location(&uri, line, col))
+                    .collect();
+                respond(id, Value::Array(locations));
+            }
+            (Some("textDocument/hover"), Some(id)) => {
+                let (_, _, word) = lookup(&documents, &params);
+                respond(
+                    id,
+                    json!({ "contents": { "kind": "markdown", "value": format!("hover: {}", word) } }),
+                );
+            }
+            (Some("shutdown"), Some(id)) => respond(id, Value::Null),
+            (Some("exit"), None) => return,
+            // Responses to our own requests and other notifications
+            _ => {}
+        }
+    }
+}
+
+fn read_message(reader: &mut impl BufRead) -> Option<Value> {
+    let mut length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).ok()? == 0 {
+            return None;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some(value) = line.strip_prefix("Content-Length:") {
+            length = value.trim().parse().ok()?;
+        }
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+    serde_json::from_slice(&body).ok()
+}
+
+fn send(message: &Value) {
+    let body = message.to_string();
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+    stdout.flush().unwrap();
+}
+
+fn respond(id: Value, result: Value) {
+    send(&json!({ "jsonrpc": "2.0", "id": id, "result": result }));
+}
+
+fn publish(uri: &str, text: &str) {
+    let mut diagnostics = Vec::new();
+    for (line, content) in text.lines().enumerate() {
+        for (marker, severity) in [("ERROR", 1), ("WARN", 2)] {
+            if let Some(col) = content.find(marker) {
+                diagnostics.push(json!({
+                    "range": {
+                        "start": { "line": line, "character": col },
+                        "end": { "line": line, "character": col + marker.len() },
+                    },
+                    "severity": severity,
+                    "message": format!("found {} marker", marker),
+                    "source": "fake-lsp",
+                }));
+            }
+        }
+    }
+    send(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": { "uri": uri, "diagnostics": diagnostics },
+    }));
+}
+
+fn location(uri: &str, line: usize, col: usize) -> Value {
+    json!({
+        "uri": uri,
+        "range": {
+            "start": { "line": line, "character": col },
+            "end": { "line": line, "character": col },
+        },
+    })
+}
+
+/// Document URI, text and the identifier under the requested position
+fn lookup(documents: &HashMap<String, String>, params: &Value) -> (String, String, String) {
+    let uri = params["textDocument"]["uri"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    let text = documents.get(&uri).cloned().unwrap_or_default();
+    let line = params["position"]["line"].as_u64().unwrap_or(0) as usize;
+    let col = params["position"]["character"].as_u64().unwrap_or(0) as usize;
+
+    let line_text = text.lines().nth(line).unwrap_or("");
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    let start = line_text[..col.min(line_text.len())]
+        .rfind(|c: char| !is_ident(c))
+        .map_or(0, |i| i + 1);
+    let end = line_text[start..]
+        .find(|c: char| !is_ident(c))
+        .map_or(line_text.len(), |i| start + i);
+    let word = line_text[start..end].to_string();
+    (uri, text, word)
+}